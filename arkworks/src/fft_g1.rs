@@ -6,6 +6,7 @@ use crate::kzg_types::ArkG1ProjAddAffine;
 
 use kzg::msm::msm_impls::msm;
 
+use kzg::common_utils::try_vec;
 use kzg::msm::precompute::PrecomputationTable;
 use kzg::{Fr as KzgFr, G1Mul};
 use kzg::{FFTG1, G1};
@@ -48,7 +49,7 @@ impl FFTG1<ArkG1> for FFTSettings {
         }
 
         let stride: usize = self.max_width / data.len();
-        let mut ret = vec![ArkG1::default(); data.len()];
+        let mut ret = try_vec(ArkG1::default(), data.len())?;
 
         let roots = if inverse {
             &self.reverse_roots_of_unity
@@ -56,6 +57,10 @@ impl FFTG1<ArkG1> for FFTSettings {
             &self.expanded_roots_of_unity
         };
 
+        #[cfg(feature = "parallel")]
+        kzg::parallelism::install(|| fft_g1_fast(&mut ret, data, 1, roots, stride, 1));
+
+        #[cfg(not(feature = "parallel"))]
         fft_g1_fast(&mut ret, data, 1, roots, stride, 1);
 
         if inverse {
@@ -99,11 +104,30 @@ pub fn fft_g1_fast(
     if half > 0 {
         #[cfg(feature = "parallel")]
         {
-            let (lo, hi) = ret.split_at_mut(half);
-            rayon::join(
-                || fft_g1_fast(hi, &data[stride..], stride * 2, roots, roots_stride * 2, 1),
-                || fft_g1_fast(lo, data, stride * 2, roots, roots_stride * 2, 1),
-            );
+            if kzg::parallelism::is_parallel() {
+                let (lo, hi) = ret.split_at_mut(half);
+                rayon::join(
+                    || fft_g1_fast(hi, &data[stride..], stride * 2, roots, roots_stride * 2, 1),
+                    || fft_g1_fast(lo, data, stride * 2, roots, roots_stride * 2, 1),
+                );
+            } else {
+                fft_g1_fast(
+                    &mut ret[..half],
+                    data,
+                    stride * 2,
+                    roots,
+                    roots_stride * 2,
+                    1,
+                );
+                fft_g1_fast(
+                    &mut ret[half..],
+                    &data[stride..],
+                    stride * 2,
+                    roots,
+                    roots_stride * 2,
+                    1,
+                );
+            }
         }
 
         #[cfg(not(feature = "parallel"))]
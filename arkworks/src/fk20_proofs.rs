@@ -89,26 +89,7 @@ impl FK20MultiSettings<BlstFr, ArkG1, ArkG2, FFTSettings, PolyData, KZGSettings,
     for KzgFK20MultiSettings
 {
     fn new(ks: &KZGSettings, n2: usize, chunk_len: usize) -> Result<Self, String> {
-        if n2 > ks.fs.max_width {
-            return Err(String::from(
-                "n2 must be equal or less than kzg settings max width",
-            ));
-        }
-        if !n2.is_power_of_two() {
-            return Err(String::from("n2 must be power of 2"));
-        }
-        if n2 < 2 {
-            return Err(String::from("n2 must be equal or greater than 2"));
-        }
-        if chunk_len > n2 / 2 {
-            return Err(String::from("chunk_len must be equal or less than n2/2"));
-        }
-        if !chunk_len.is_power_of_two() {
-            return Err(String::from("chunk_len must be power of 2"));
-        }
-        if chunk_len == 0 {
-            return Err(String::from("chunk_len must be greater than 0"));
-        }
+        check_multi_args(ks, n2, chunk_len)?;
 
         let n = n2 / 2;
         let k = n / chunk_len;
@@ -135,17 +116,7 @@ impl FK20MultiSettings<BlstFr, ArkG1, ArkG2, FFTSettings, PolyData, KZGSettings,
             x_ext_fft_files.push(toeplitz_part_1(&x, &ks.fs).unwrap());
         }
 
-        let new_ks = KZGSettings {
-            fs: ks.fs.clone(),
-            ..KZGSettings::default()
-        };
-
-        Ok(KzgFK20MultiSettings {
-            ks: new_ks,
-            x_ext_fft_files,
-            chunk_len,
-            length: n, //unsure if this is right
-        })
+        Self::new_with_x_ext_fft_files(ks, n2, chunk_len, x_ext_fft_files)
     }
 
     fn data_availability(&self, p: &PolyData) -> Result<Vec<ArkG1>, String> {
@@ -169,6 +140,63 @@ impl FK20MultiSettings<BlstFr, ArkG1, ArkG2, FFTSettings, PolyData, KZGSettings,
     fn data_availability_optimized(&self, p: &PolyData) -> Result<Vec<ArkG1>, String> {
         fk20_multi_da_opt(p, self)
     }
+
+    fn get_x_ext_fft_files(&self) -> &[Vec<ArkG1>] {
+        &self.x_ext_fft_files
+    }
+
+    fn new_with_x_ext_fft_files(
+        ks: &KZGSettings,
+        n2: usize,
+        chunk_len: usize,
+        x_ext_fft_files: Vec<Vec<ArkG1>>,
+    ) -> Result<Self, String> {
+        check_multi_args(ks, n2, chunk_len)?;
+        if x_ext_fft_files.len() != chunk_len
+            || x_ext_fft_files
+                .iter()
+                .any(|file| file.len() != n2 / chunk_len)
+        {
+            return Err(String::from("Toeplitz FFTs do not match n2 and chunk_len"));
+        }
+
+        let new_ks = KZGSettings {
+            fs: ks.fs.clone(),
+            ..KZGSettings::default()
+        };
+
+        Ok(KzgFK20MultiSettings {
+            ks: new_ks,
+            x_ext_fft_files,
+            chunk_len,
+            length: n2 / 2, //unsure if this is right
+        })
+    }
+}
+
+fn check_multi_args(ks: &KZGSettings, n2: usize, chunk_len: usize) -> Result<(), String> {
+    if n2 > ks.fs.max_width {
+        return Err(String::from(
+            "n2 must be equal or less than kzg settings max width",
+        ));
+    }
+    if !n2.is_power_of_two() {
+        return Err(String::from("n2 must be power of 2"));
+    }
+    if n2 < 2 {
+        return Err(String::from("n2 must be equal or greater than 2"));
+    }
+    if chunk_len > n2 / 2 {
+        return Err(String::from("chunk_len must be equal or less than n2/2"));
+    }
+    if !chunk_len.is_power_of_two() {
+        return Err(String::from("chunk_len must be power of 2"));
+    }
+    if chunk_len == 0 {
+        return Err(String::from("chunk_len must be greater than 0"));
+    }
+
+    Ok(())
 }
 
 fn fk20_single_da_opt(p: &PolyData, fk: &KzgFK20SingleSettings) -> Result<Vec<ArkG1>, String> {
@@ -2,7 +2,6 @@
 
 extern crate alloc;
 use super::utils::{blst_poly_into_pc_poly, PolyData};
-use crate::consts::{G1_GENERATOR, G2_GENERATOR};
 use crate::kzg_types::{ArkFp, ArkFr, ArkG1Affine};
 use crate::kzg_types::{ArkFr as BlstFr, ArkG1, ArkG2};
 use alloc::sync::Arc;
@@ -11,10 +10,10 @@ use ark_ec::pairing::Pairing;
 use ark_ec::CurveGroup;
 use ark_poly::Polynomial;
 use ark_std::{vec, One};
-use kzg::eip_4844::hash_to_bls_field;
-use kzg::msm::precompute::PrecomputationTable;
+use kzg::dev_setup::INSECURE_DEV_SETUP_SEED;
+use kzg::msm::precompute::ReleasableTable;
+use kzg::secret::{generate_trusted_setup_with_secret, SecretScalar};
 use kzg::Fr as FrTrait;
-use kzg::{G1Mul, G2Mul};
 use std::ops::Neg;
 
 #[derive(Debug, Clone)]
@@ -49,24 +48,17 @@ pub struct KZGSettings {
     pub fs: FFTSettings,
     pub secret_g1: Vec<ArkG1>,
     pub secret_g2: Vec<ArkG2>,
-    pub precomputation: Option<Arc<PrecomputationTable<ArkFr, ArkG1, ArkFp, ArkG1Affine>>>,
+    pub precomputation: Arc<ReleasableTable<ArkFr, ArkG1, ArkFp, ArkG1Affine>>,
 }
 
 pub fn generate_trusted_setup(len: usize, secret: [u8; 32usize]) -> (Vec<ArkG1>, Vec<ArkG2>) {
-    let s = hash_to_bls_field::<ArkFr>(&secret);
-    let mut s_pow = ArkFr::one();
-
-    let mut s1 = Vec::with_capacity(len);
-    let mut s2 = Vec::with_capacity(len);
-
-    for _ in 0..len {
-        s1.push(G1_GENERATOR.mul(&s_pow));
-        s2.push(G2_GENERATOR.mul(&s_pow));
-
-        s_pow = s_pow.mul(&s);
-    }
+    generate_trusted_setup_with_secret::<ArkFr, _, _>(len, &SecretScalar::from_seed(secret))
+}
 
-    (s1, s2)
+/// Setup of the public development secret, for tests and local networks only. Verifying against
+/// it raises a warning, see `kzg::dev_setup`
+pub fn generate_insecure_dev_setup(len: usize) -> (Vec<ArkG1>, Vec<ArkG2>) {
+    generate_trusted_setup(len, INSECURE_DEV_SETUP_SEED)
 }
 
 pub fn eval_poly(p: &PolyData, x: &BlstFr) -> BlstFr {
@@ -26,8 +26,11 @@ use ark_std::UniformRand;
 
 use blst::{blst_fp, blst_fr, blst_p1};
 use kzg::common_utils::reverse_bit_order;
-use kzg::eip_4844::{BYTES_PER_FIELD_ELEMENT, BYTES_PER_G1, BYTES_PER_G2};
-use kzg::msm::precompute::{precompute, PrecomputationTable};
+use kzg::dev_setup::guard_insecure_dev_setup;
+use kzg::eip_4844::{
+    BYTES_PER_FIELD_ELEMENT, BYTES_PER_G1, BYTES_PER_G1_UNCOMPRESSED, BYTES_PER_G2,
+};
+use kzg::msm::precompute::{precompute, PrecomputationTable, ReleasableTable};
 use kzg::{
     FFTFr, FFTSettings, FFTSettingsPoly, Fr as KzgFr, G1Affine as G1AffineTrait, G1Fp, G1GetFp,
     G1LinComb, G1Mul, G1ProjAddAffine, G2Mul, KZGSettings, PairingVerify, Poly, Scalar256, G1, G2,
@@ -383,6 +386,40 @@ impl G1 for ArkG1 {
     fn dbl_assign(&mut self) {
         self.0.double_in_place();
     }
+
+    fn to_bytes_uncompressed(&self) -> Result<[u8; BYTES_PER_G1_UNCOMPRESSED], String> {
+        let mut out = [0u8; BYTES_PER_G1_UNCOMPRESSED];
+        unsafe {
+            blst::blst_p1_serialize(out.as_mut_ptr(), &self.to_blst_p1());
+        }
+        Ok(out)
+    }
+
+    fn from_bytes_uncompressed(bytes: &[u8]) -> Result<Self, String> {
+        bytes
+            .try_into()
+            .map_err(|_| {
+                format!(
+                    "Invalid byte length. Expected {}, got {}",
+                    BYTES_PER_G1_UNCOMPRESSED,
+                    bytes.len()
+                )
+            })
+            .and_then(|bytes: &[u8; BYTES_PER_G1_UNCOMPRESSED]| {
+                let mut tmp = blst::blst_p1_affine::default();
+                let mut g1 = blst_p1::default();
+                unsafe {
+                    // The deserialize routine also checks that the point is on the curve
+                    if blst::blst_p1_deserialize(&mut tmp, bytes.as_ptr())
+                        != blst::BLST_ERROR::BLST_SUCCESS
+                    {
+                        return Err("Failed to deserialize".to_string());
+                    }
+                    blst::blst_p1_from_affine(&mut g1, &tmp);
+                }
+                Ok(Self::from_blst_p1(g1))
+            })
+    }
 }
 
 impl G1Mul<ArkFr> for ArkG1 {
@@ -633,7 +670,7 @@ impl KZGSettings<ArkFr, ArkG1, ArkG2, LFFTSettings, PolyData, ArkFp, ArkG1Affine
             secret_g1: secret_g1.to_vec(),
             secret_g2: secret_g2.to_vec(),
             fs: fft_settings.clone(),
-            precomputation: precompute(secret_g1).ok().flatten().map(Arc::new),
+            precomputation: Arc::new(ReleasableTable::new(precompute(secret_g1).ok().flatten())),
         })
     }
 
@@ -648,7 +685,7 @@ impl KZGSettings<ArkFr, ArkG1, ArkG2, LFFTSettings, PolyData, ArkFp, ArkG1Affine
             &self.secret_g1,
             &p.coeffs,
             p.coeffs.len(),
-            self.get_precomputation(),
+            self.get_precomputation().as_deref(),
         );
 
         Ok(out)
@@ -683,6 +720,8 @@ impl KZGSettings<ArkFr, ArkG1, ArkG2, LFFTSettings, PolyData, ArkFp, ArkG1Affine
         x: &ArkFr,
         y: &ArkFr,
     ) -> Result<bool, String> {
+        guard_insecure_dev_setup(&self.secret_g2);
+
         let x_g2: ArkG2 = G2_GENERATOR.mul(x);
         let s_minus_x: ArkG2 = self.secret_g2[1].sub(&x_g2);
         let y_g1 = G1_GENERATOR.mul(y);
@@ -743,6 +782,7 @@ impl KZGSettings<ArkFr, ArkG1, ArkG2, LFFTSettings, PolyData, ArkFp, ArkG1Affine
         if !n.is_power_of_two() {
             return Err(String::from("n is not a power of two"));
         }
+        guard_insecure_dev_setup(&self.secret_g2);
 
         // Interpolate at a coset.
         let mut interp = PolyData {
@@ -765,7 +805,7 @@ impl KZGSettings<ArkFr, ArkG1, ArkG2, LFFTSettings, PolyData, ArkFp, ArkG1Affine
         let xn_minus_yn = self.secret_g2[n].sub(&xn2);
 
         // [interpolation_polynomial(s)]_1
-        let is1 = self.commit_to_poly(&interp).unwrap();
+        let is1 = self.commit_to_poly(&interp)?;
 
         // [commitment - interpolation_polynomial(s)]_1 = [commit]_1 - [interpolation_polynomial(s)]_1
         let commit_minus_interp = com.sub(&is1);
@@ -795,8 +835,16 @@ impl KZGSettings<ArkFr, ArkG1, ArkG2, LFFTSettings, PolyData, ArkFp, ArkG1Affine
         &self.secret_g2
     }
 
-    fn get_precomputation(&self) -> Option<&PrecomputationTable<ArkFr, ArkG1, ArkFp, ArkG1Affine>> {
-        self.precomputation.as_ref().map(|v| v.as_ref())
+    fn get_precomputation(
+        &self,
+    ) -> Option<Arc<PrecomputationTable<ArkFr, ArkG1, ArkFp, ArkG1Affine>>> {
+        self.precomputation.get()
+    }
+
+    fn get_releasable_precomputation(
+        &self,
+    ) -> Option<Arc<ReleasableTable<ArkFr, ArkG1, ArkFp, ArkG1Affine>>> {
+        Some(self.precomputation.clone())
     }
 }
 
@@ -823,7 +871,7 @@ impl G1Fp for ArkFp {
     }
 
     fn inverse(&self) -> Option<Self> {
-        Some(Self(self.0.inverse().unwrap()))
+        self.0.inverse().map(Self)
     }
 
     fn square(&self) -> Self {
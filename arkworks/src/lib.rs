@@ -19,6 +19,7 @@ pub mod fk20_proofs;
 pub mod kzg_proofs;
 pub mod kzg_types;
 pub mod poly;
+pub mod prelude;
 pub mod recover;
 pub mod utils;
 pub mod zero_poly;
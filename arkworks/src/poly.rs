@@ -7,7 +7,7 @@ use ark_bls12_381::Fr;
 use ark_poly::univariate::DensePolynomial;
 use ark_poly::DenseUVPolynomial;
 use ark_std::{log2, Zero};
-use kzg::common_utils::{log2_pow2, next_pow_of_2};
+use kzg::common_utils::{log2_pow2, next_pow_of_2, try_vec};
 use kzg::{FFTFr, FFTSettings as FFTSettingsT, Fr as FrTrait, Poly};
 use std::cmp::min;
 
@@ -21,7 +21,7 @@ pub fn poly_inverse(b: &PolyData, output_len: usize) -> Result<PolyData, String>
     }
 
     let mut output = PolyData {
-        coeffs: vec![BlstFr::zero(); output_len],
+        coeffs: try_vec(BlstFr::zero(), output_len)?,
     };
     if b.coeffs.len() == 1 {
         output.coeffs[0] = b.coeffs[0].inverse();
@@ -33,7 +33,7 @@ pub fn poly_inverse(b: &PolyData, output_len: usize) -> Result<PolyData, String>
 
     let maxd = output_len - 1;
     let scale = next_pow_of_2(log2_pow2(2 * output_len - 1));
-    let fs = FFTSettings::new(scale).unwrap();
+    let fs = FFTSettings::new(scale)?;
 
     let mut tmp0: PolyData;
     let mut tmp1: PolyData;
@@ -48,7 +48,7 @@ pub fn poly_inverse(b: &PolyData, output_len: usize) -> Result<PolyData, String>
 
         let len_temp: usize = min(d + 1, b.coeffs.len() + output.coeffs.len() - 1);
 
-        tmp0 = poly_mul(b, &output, Some(&fs), len_temp).unwrap();
+        tmp0 = poly_mul(b, &output, Some(&fs), len_temp)?;
 
         for i in 0..len_temp {
             tmp0.coeffs[i] = tmp0.coeffs[i].negate();
@@ -58,7 +58,7 @@ pub fn poly_inverse(b: &PolyData, output_len: usize) -> Result<PolyData, String>
 
         let len_temp2: usize = d + 1;
 
-        tmp1 = poly_mul(&output, &tmp0, Some(&fs), len_temp2).unwrap();
+        tmp1 = poly_mul(&output, &tmp0, Some(&fs), len_temp2)?;
 
         if tmp1.coeffs.len() > output_len {
             tmp1.coeffs = tmp1.coeffs[..output_len].to_vec();
@@ -79,7 +79,7 @@ pub fn poly_mul_direct(p1: &PolyData, p2: &PolyData, len: usize) -> Result<PolyD
     if p1.is_zero() || p2.is_zero() {
         Ok(pc_poly_into_blst_poly(DensePolynomial::zero()))
     } else {
-        let mut result = vec![Fr::zero(); len];
+        let mut result = try_vec(Fr::zero(), len)?;
         for (i, self_coeff) in p1.coeffs.iter().enumerate() {
             for (j, other_coeff) in p2.coeffs.iter().enumerate() {
                 if i + j >= len {
@@ -90,7 +90,7 @@ pub fn poly_mul_direct(p1: &PolyData, p2: &PolyData, len: usize) -> Result<PolyD
         }
         let p = pc_poly_into_blst_poly(DensePolynomial::from_coefficients_vec(result));
         Ok(PolyData {
-            coeffs: pad_poly(&p, len).unwrap(),
+            coeffs: pad_poly(&p, len)?,
         })
     }
 }
@@ -130,7 +130,7 @@ pub fn poly_mul_fft(
         x.clone()
     } else {
         let scale = log2_pow2(length);
-        FFTSettings::new(scale).unwrap()
+        FFTSettings::new(scale)?
     };
 
     if length > fs_p.max_width {
@@ -146,10 +146,10 @@ pub fn poly_mul_fft(
         coeffs: b.coeffs[..b_len].to_vec(),
     };
     let a_pad = PolyData {
-        coeffs: pad_poly(&a, length).unwrap(),
+        coeffs: pad_poly(&a, length)?,
     };
     let b_pad = PolyData {
-        coeffs: pad_poly(&b, length).unwrap(),
+        coeffs: pad_poly(&b, length)?,
     };
 
     let a_fft;
@@ -157,25 +157,22 @@ pub fn poly_mul_fft(
     #[cfg(feature = "parallel")]
     {
         if length > 1024 {
-            let mut a_fft_temp = vec![];
-            let mut b_fft_temp = vec![];
-
-            rayon::join(
-                || a_fft_temp = fs_p.fft_fr(&a_pad.coeffs, false).unwrap(),
-                || b_fft_temp = fs_p.fft_fr(&b_pad.coeffs, false).unwrap(),
+            let (a_fft_temp, b_fft_temp) = rayon::join(
+                || fs_p.fft_fr(&a_pad.coeffs, false),
+                || fs_p.fft_fr(&b_pad.coeffs, false),
             );
 
-            a_fft = a_fft_temp;
-            b_fft = b_fft_temp;
+            a_fft = a_fft_temp?;
+            b_fft = b_fft_temp?;
         } else {
-            a_fft = fs_p.fft_fr(&a_pad.coeffs, false).unwrap();
-            b_fft = fs_p.fft_fr(&b_pad.coeffs, false).unwrap();
+            a_fft = fs_p.fft_fr(&a_pad.coeffs, false)?;
+            b_fft = fs_p.fft_fr(&b_pad.coeffs, false)?;
         }
     }
     #[cfg(not(feature = "parallel"))]
     {
-        a_fft = fs_p.fft_fr(&a_pad.coeffs, false).unwrap();
-        b_fft = fs_p.fft_fr(&b_pad.coeffs, false).unwrap();
+        a_fft = fs_p.fft_fr(&a_pad.coeffs, false)?;
+        b_fft = fs_p.fft_fr(&b_pad.coeffs, false)?;
     }
     let mut ab_fft = a_pad;
     let mut ab = b_pad;
@@ -184,7 +181,7 @@ pub fn poly_mul_fft(
         ab_fft.coeffs[i] = a_fft[i].mul(&b_fft[i]);
     }
 
-    ab.coeffs = fs_p.fft_fr(&ab_fft.coeffs, true).unwrap();
+    ab.coeffs = fs_p.fft_fr(&ab_fft.coeffs, true)?;
 
     let data_len = min(len, length);
     let mut out = PolyData::new(len);
@@ -223,19 +220,18 @@ pub fn poly_fast_div(dividend: &PolyData, divisor: &PolyData) -> Result<PolyData
 
     if divisor.len() == 1 {
         for i in 0..dividend.len() {
-            out.coeffs
-                .push(dividend.coeffs[i].div(&divisor.coeffs[0]).unwrap());
+            out.coeffs.push(dividend.coeffs[i].div(&divisor.coeffs[0])?);
         }
         return Ok(out);
     }
 
-    let a_flip = poly_flip(dividend).unwrap();
-    let b_flip = poly_flip(divisor).unwrap();
+    let a_flip = poly_flip(dividend)?;
+    let b_flip = poly_flip(divisor)?;
 
-    let inv_b_flip = poly_inverse(&b_flip, m - n + 1).unwrap();
-    let q_flip = poly_mul(&a_flip, &inv_b_flip, None, m - n + 1).unwrap();
+    let inv_b_flip = poly_inverse(&b_flip, m - n + 1)?;
+    let q_flip = poly_mul(&a_flip, &inv_b_flip, None, m - n + 1)?;
 
-    out = poly_flip(&q_flip).unwrap();
+    out = poly_flip(&q_flip)?;
 
     Ok(PolyData {
         coeffs: out.coeffs[..m - n + 1].to_vec(),
@@ -0,0 +1,33 @@
+//! Concrete types of the arkworks backend together with the traits they implement, for
+//! `use rust_kzg_arkworks::prelude::*;`
+
+use kzg::backend::{Backend, KzgBackend};
+
+pub use kzg::{
+    FFTFr, FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1LinComb, G1Mul, G1ProjAddAffine, G2Mul,
+    KZGSettings, PairingVerify, Poly, FFTG1, G1, G2,
+};
+
+pub use crate::kzg_proofs::{FFTSettings as LFFTSettings, KZGSettings as LKZGSettings};
+pub use crate::kzg_types::{ArkFp, ArkFr, ArkG1, ArkG1Affine, ArkG1ProjAddAffine, ArkG2};
+pub use crate::utils::PolyData;
+
+/// `Backend` bundle of the arkworks types
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ArkBackend;
+
+impl Backend for ArkBackend {
+    const NAME: &'static str = "arkworks";
+
+    type Fr = ArkFr;
+    type G1 = ArkG1;
+    type G2 = ArkG2;
+    type G1Fp = ArkFp;
+    type G1Affine = ArkG1Affine;
+    type G1ProjAddAffine = ArkG1ProjAddAffine;
+    type Poly = PolyData;
+    type FFTSettings = LFFTSettings;
+    type KZGSettings = LKZGSettings;
+}
+
+impl KzgBackend for ArkBackend {}
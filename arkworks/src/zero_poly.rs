@@ -1,7 +1,7 @@
 use super::kzg_proofs::FFTSettings;
 use super::utils::{blst_poly_into_pc_poly, pc_poly_into_blst_poly, PolyData};
 use crate::kzg_types::ArkFr as BlstFr;
-use kzg::common_utils::next_pow_of_2;
+use kzg::common_utils::{next_pow_of_2, try_vec, try_vec_with_capacity};
 use kzg::{FFTFr, Fr as FrTrait, ZeroPoly};
 use std::cmp::{min, Ordering};
 use std::ops::Neg;
@@ -11,7 +11,8 @@ pub(crate) fn pad_poly(poly: &PolyData, new_length: usize) -> Result<Vec<BlstFr>
         return Ok(poly.coeffs.clone());
     }
 
-    let mut out = poly.coeffs.to_vec();
+    let mut out = try_vec_with_capacity(new_length)?;
+    out.extend_from_slice(&poly.coeffs);
 
     for _i in poly.coeffs.len()..new_length {
         out.push(BlstFr::zero())
@@ -30,7 +31,7 @@ impl ZeroPoly<BlstFr, PolyData> for FFTSettings {
             return Err(String::from("idx array must be non-zero"));
         }
         let blstpoly = PolyData {
-            coeffs: vec![BlstFr::one(); indices.len() + 1],
+            coeffs: try_vec(BlstFr::one(), indices.len() + 1)?,
         };
         let mut poly = blst_poly_into_pc_poly(&blstpoly.coeffs);
         poly.coeffs[0] = (self.expanded_roots_of_unity[indices[0] * stride]).fr.neg();
@@ -63,13 +64,13 @@ impl ZeroPoly<BlstFr, PolyData> for FFTSettings {
             return Err(String::from("Expected domain size to be a power of 2"));
         }
 
-        let mut p_partial = pad_poly(&partials[0], len_out).unwrap();
-        let mut mul_eval_ps = self.fft_fr(&p_partial, false).unwrap();
+        let mut p_partial = pad_poly(&partials[0], len_out)?;
+        let mut mul_eval_ps = self.fft_fr(&p_partial, false)?;
 
         for partial in partials.iter().skip(1) {
             p_partial = pad_poly(partial, len_out)?;
 
-            let p_eval = self.fft_fr(&p_partial, false).unwrap();
+            let p_eval = self.fft_fr(&p_partial, false)?;
             for j in 0..len_out {
                 mul_eval_ps[j].fr *= p_eval[j].fr;
             }
@@ -118,7 +119,10 @@ impl ZeroPoly<BlstFr, PolyData> for FFTSettings {
         if missing_indices.len() <= missing_per_partial {
             zero_poly = self.do_zero_poly_mul_partial(missing_indices, domain_stride)?;
         } else {
-            let mut work = vec![BlstFr::zero(); next_pow_of_2(partial_count * degree_of_partial)];
+            let mut work = try_vec(
+                BlstFr::zero(),
+                next_pow_of_2(partial_count * degree_of_partial),
+            )?;
 
             let mut partial_lens = Vec::new();
 
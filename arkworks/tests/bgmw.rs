@@ -0,0 +1,40 @@
+#[cfg(test)]
+mod tests {
+    use kzg_bench::tests::msm::batch::bgmw_multiply_batch_matches_msm;
+    use kzg_bench::tests::msm::bucket_integration::bucket_integration_matches_naive;
+    use kzg_bench::tests::msm::sparse::bgmw_sparse_scalars_match_msm;
+    use kzg_bench::tests::msm::table_file::{
+        table_file_known_answer, table_file_roundtrip, table_generate_to_file,
+    };
+    use rust_kzg_arkworks::kzg_types::{ArkFp, ArkFr, ArkG1, ArkG1Affine, ArkG1ProjAddAffine};
+
+    #[test]
+    fn bgmw_multiply_batch_matches_msm_() {
+        bgmw_multiply_batch_matches_msm::<ArkFr, ArkG1, ArkFp, ArkG1Affine, ArkG1ProjAddAffine>();
+    }
+
+    #[test]
+    fn bgmw_sparse_scalars_match_msm_() {
+        bgmw_sparse_scalars_match_msm::<ArkFr, ArkG1, ArkFp, ArkG1Affine, ArkG1ProjAddAffine>();
+    }
+
+    #[test]
+    fn bucket_integration_matches_naive_() {
+        bucket_integration_matches_naive::<ArkFr, ArkG1, ArkFp, ArkG1Affine>();
+    }
+
+    #[test]
+    fn table_file_known_answer_() {
+        table_file_known_answer::<ArkFr, ArkG1, ArkFp, ArkG1Affine, ArkG1ProjAddAffine>();
+    }
+
+    #[test]
+    fn table_file_roundtrip_() {
+        table_file_roundtrip::<ArkFr, ArkG1, ArkFp, ArkG1Affine, ArkG1ProjAddAffine>();
+    }
+
+    #[test]
+    fn table_generate_to_file_() {
+        table_generate_to_file::<ArkFr, ArkG1, ArkFp, ArkG1Affine>();
+    }
+}
@@ -92,6 +92,11 @@ mod tests {
         g1_identity_is_identity::<ArkG1>();
     }
 
+    #[test]
+    pub fn g1_batch_serialization_roundtrip_() {
+        g1_batch_serialization_roundtrip::<ArkFr, ArkG1>();
+    }
+
     #[test]
     pub fn g1_make_linear_combination_() {
         g1_make_linear_combination::<ArkFr, ArkG1, ArkFp, ArkG1Affine>(&g1_linear_combination);
@@ -0,0 +1,10 @@
+#[cfg(test)]
+mod tests {
+    use kzg_bench::tests::cross_backend::cross_backend_golden_test;
+    use rust_kzg_arkworks::prelude::ArkBackend;
+
+    #[test]
+    fn cross_backend_golden_test_() {
+        cross_backend_golden_test::<ArkBackend>();
+    }
+}
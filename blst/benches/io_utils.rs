@@ -0,0 +1,15 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use kzg_bench::benches::io_utils::bench_save_secrets;
+use rust_kzg_blst::utils::generate_trusted_setup;
+
+fn bench_save_secrets_(c: &mut Criterion) {
+    bench_save_secrets(c, &generate_trusted_setup);
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = bench_save_secrets_
+}
+
+criterion_main!(benches);
@@ -1,18 +1,47 @@
 use criterion::{criterion_group, criterion_main, Criterion};
-use kzg_bench::benches::lincomb::bench_g1_lincomb;
+use kzg_bench::benches::lincomb::{
+    bench_bounded_msm, bench_bucket_integration, bench_fixed_base_commit, bench_g1_lincomb,
+    bench_large_msm, bench_msm_recoding, bench_sparse_msm,
+};
 use rust_kzg_blst::kzg_proofs::g1_linear_combination;
 use rust_kzg_blst::types::fp::FsFp;
 use rust_kzg_blst::types::fr::FsFr;
-use rust_kzg_blst::types::g1::{FsG1, FsG1Affine};
+use rust_kzg_blst::types::g1::{FsG1, FsG1Affine, FsG1ProjAddAffine};
+use rust_kzg_blst::types::poly::FsPoly;
 
 fn bench_g1_lincomb_(c: &mut Criterion) {
     bench_g1_lincomb::<FsFr, FsG1, FsFp, FsG1Affine>(c, &g1_linear_combination);
 }
 
+fn bench_fixed_base_commit_(c: &mut Criterion) {
+    bench_fixed_base_commit::<FsFr, FsG1, FsFp, FsG1Affine, FsG1ProjAddAffine, FsPoly>(c);
+}
+
+fn bench_bounded_msm_(c: &mut Criterion) {
+    bench_bounded_msm::<FsFr, FsG1, FsFp, FsG1Affine, FsG1ProjAddAffine>(c);
+}
+
+fn bench_sparse_msm_(c: &mut Criterion) {
+    bench_sparse_msm::<FsFr, FsG1, FsFp, FsG1Affine, FsG1ProjAddAffine>(c);
+}
+
+fn bench_large_msm_(c: &mut Criterion) {
+    bench_large_msm::<FsFr, FsG1, FsFp, FsG1Affine, FsG1ProjAddAffine>(c);
+}
+
+fn bench_msm_recoding_(c: &mut Criterion) {
+    bench_msm_recoding::<FsFr, FsG1, FsFp, FsG1Affine, FsG1ProjAddAffine>(c);
+}
+
+fn bench_bucket_integration_(c: &mut Criterion) {
+    bench_bucket_integration::<FsG1, FsFp, FsG1Affine>(c);
+}
+
 criterion_group! {
     name = benches;
     config = Criterion::default().sample_size(10);
-    targets = bench_g1_lincomb_
+    targets = bench_g1_lincomb_, bench_fixed_base_commit_, bench_bounded_msm_, bench_sparse_msm_,
+        bench_large_msm_, bench_msm_recoding_, bench_bucket_integration_
 }
 
 criterion_main!(benches);
@@ -0,0 +1,14 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use kzg_bench::benches::sha256_batch::bench_sha256_many;
+
+fn bench_sha256_many_(c: &mut Criterion) {
+    bench_sha256_many(c);
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = bench_sha256_many_
+}
+
+criterion_main!(benches);
@@ -0,0 +1,79 @@
+//! Stress the blst backend from many threads sharing one `KZGSettings`, checking every result
+//! against a single-threaded reference.
+//!
+//! ```text
+//! cargo run --release --features parallel --example soak -- --threads 16 --seconds 3600
+//! ```
+
+use std::env;
+use std::process::exit;
+use std::time::Duration;
+
+use kzg_bench::soak::{run_soak, SoakConfig};
+use rust_kzg_blst::eip_4844::load_trusted_setup_filename_rust;
+use rust_kzg_blst::types::fft_settings::FsFFTSettings;
+use rust_kzg_blst::types::fp::FsFp;
+use rust_kzg_blst::types::fr::FsFr;
+use rust_kzg_blst::types::g1::{FsG1, FsG1Affine};
+use rust_kzg_blst::types::g2::FsG2;
+use rust_kzg_blst::types::kzg_settings::FsKZGSettings;
+use rust_kzg_blst::types::poly::FsPoly;
+
+const USAGE: &str =
+    "usage: soak [--threads N] [--seconds T] [--operations-per-thread K] [--reference-blobs B]";
+
+fn parse_args() -> Result<SoakConfig, String> {
+    let mut config = SoakConfig::default();
+    let mut args = env::args().skip(1);
+
+    while let Some(flag) = args.next() {
+        if flag == "--help" || flag == "-h" {
+            println!("{}", USAGE);
+            exit(0);
+        }
+
+        let value = args
+            .next()
+            .ok_or_else(|| format!("Missing value for {}", flag))?
+            .parse::<usize>()
+            .map_err(|e| format!("Invalid value for {}: {}", flag, e))?;
+
+        match flag.as_str() {
+            "--threads" => config.threads = value,
+            "--seconds" => config.duration = Duration::from_secs(value as u64),
+            "--operations-per-thread" => config.operations_per_thread = value,
+            "--reference-blobs" => config.reference_blobs = value,
+            _ => return Err(format!("Unknown argument {}", flag)),
+        }
+    }
+
+    Ok(config)
+}
+
+fn main() {
+    let config = parse_args().unwrap_or_else(|err| {
+        eprintln!("{}\n{}", err, USAGE);
+        exit(2);
+    });
+
+    println!("backend:              blst");
+    println!("{:?}", config);
+
+    let report = run_soak::<
+        FsFr,
+        FsG1,
+        FsG2,
+        FsPoly,
+        FsFFTSettings,
+        FsKZGSettings,
+        FsPoly,
+        FsFp,
+        FsG1Affine,
+    >(&config, &load_trusted_setup_filename_rust)
+    .unwrap_or_else(|err| {
+        eprintln!("Soak test failed: {}", err);
+        exit(1);
+    });
+
+    println!("{}", report);
+}
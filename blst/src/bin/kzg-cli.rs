@@ -0,0 +1,233 @@
+//! Commit to, prove and verify arbitrary files with the blst backend.
+//!
+//! ```text
+//! kzg-cli prove --setup <trusted_setup.txt> <file>
+//! kzg-cli verify --setup <trusted_setup.txt> <file> <commitment> <proof>
+//! kzg-cli tune [--windows <4-16>] [--iterations <3>] [--report <path>] [--profile <path>] <npoints>
+//! kzg-cli convert <from> <to> <input> <output>
+//! ```
+//!
+//! `prove` and `verify` load the setup given with `--setup`, in the text format of c-kzg-4844,
+//! such as the mainnet `trusted_setup.txt`.
+//!
+//! The file is packed into as many blobs as needed. `prove` prints the commitments and blob
+//! proofs of all blobs, each list encoded as a single hex envelope, which `verify` takes back as
+//! arguments. `verify` exits with status 1 if the proofs do not match the file.
+//!
+//! `tune` times MSMs over `npoints` points with a precomputation table of every window in
+//! `--windows`, a comma-separated list of windows and `min-max` ranges. It prints the
+//! measurements, also written to `--report` if given, and records the fastest window in the
+//! tuning profile at `--profile`, which is created or updated.
+//!
+//! `convert` rewrites the trusted setup at `input` into `output`, each format being one of
+//! `binary`, `binary-uncompressed`, `text`, `json` or `ptau`, the latter for `input` only.
+
+use std::env;
+use std::fs;
+use std::process::exit;
+
+use kzg::eip_4844::{
+    blob_to_kzg_commitment_rust, bytes_to_blobs, compute_blob_kzg_proof_rust,
+    verify_blob_kzg_proof_batch_rust,
+};
+use kzg::envelope::{decode_g1_list, encode_g1_list};
+use kzg::msm::tuner::{MsmTuner, TuningProfile};
+use kzg::setup_convert::{convert_setup, SetupFormat};
+use kzg::{Fr, G1Mul, G1};
+use rust_kzg_blst::eip_4844::load_trusted_setup_filename_rust;
+use rust_kzg_blst::types::fft_settings::FsFFTSettings;
+use rust_kzg_blst::types::fp::FsFp;
+use rust_kzg_blst::types::fr::FsFr;
+use rust_kzg_blst::types::g1::{FsG1, FsG1Affine};
+use rust_kzg_blst::types::g2::FsG2;
+use rust_kzg_blst::types::kzg_settings::FsKZGSettings;
+
+const USAGE: &str = "usage: kzg-cli prove --setup <path> <file>\n       \
+kzg-cli verify --setup <path> <file> <commitment> <proof>\n       \
+kzg-cli tune [--windows <list>] [--iterations <n>] [--report <path>] [--profile <path>] <npoints>\n       \
+kzg-cli convert <from> <to> <input> <output>";
+
+fn load_setup(path: Option<&str>) -> Result<FsKZGSettings, String> {
+    let path = path.ok_or_else(|| String::from("Missing --setup <path>"))?;
+    load_trusted_setup_filename_rust(path)
+}
+
+fn load_blobs(path: &str) -> Result<Vec<Vec<FsFr>>, String> {
+    let data = fs::read(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    bytes_to_blobs(&data)
+}
+
+fn decode_hex(value: &str) -> Result<Vec<u8>, String> {
+    hex::decode(value.strip_prefix("0x").unwrap_or(value))
+        .map_err(|e| format!("Invalid hex {}: {}", value, e))
+}
+
+fn prove(file: &str, ts: &FsKZGSettings) -> Result<(), String> {
+    let blobs = load_blobs(file)?;
+    let commitments = blobs
+        .iter()
+        .map(|blob| blob_to_kzg_commitment_rust(blob, ts))
+        .collect::<Result<Vec<FsG1>, String>>()?;
+    let proofs = blobs
+        .iter()
+        .zip(commitments.iter())
+        .map(|(blob, commitment)| compute_blob_kzg_proof_rust(blob, commitment, ts))
+        .collect::<Result<Vec<FsG1>, String>>()?;
+
+    println!("blobs {}", blobs.len());
+    println!("commitment 0x{}", hex::encode(encode_g1_list(&commitments)));
+    println!("proof 0x{}", hex::encode(encode_g1_list(&proofs)));
+    Ok(())
+}
+
+fn verify(file: &str, commitment: &str, proof: &str, ts: &FsKZGSettings) -> Result<bool, String> {
+    let blobs = load_blobs(file)?;
+    let commitments = decode_g1_list::<FsG1>(&decode_hex(commitment)?)?;
+    let proofs = decode_g1_list::<FsG1>(&decode_hex(proof)?)?;
+    if commitments.len() != blobs.len() || proofs.len() != blobs.len() {
+        return Ok(false);
+    }
+
+    verify_blob_kzg_proof_batch_rust(&blobs, &commitments, &proofs, ts)
+}
+
+fn parse_number(value: &str) -> Result<usize, String> {
+    value
+        .parse()
+        .map_err(|_| format!("Invalid number {}", value))
+}
+
+/// Windows of `--windows`, e.g. `4-8,10,12`
+fn parse_windows(value: &str) -> Result<Vec<usize>, String> {
+    let mut windows = Vec::new();
+    for item in value.split(',') {
+        match item.split_once('-') {
+            Some((min, max)) => windows.extend(parse_number(min)?..=parse_number(max)?),
+            None => windows.push(parse_number(item)?),
+        }
+    }
+
+    Ok(windows)
+}
+
+/// Setup-like points `[s^i]G`, for a fixed `s`
+fn tuning_points(npoints: usize) -> Vec<FsG1> {
+    let s = FsFr::from_u64(0x006b_7a67_2d63_6c69);
+    let mut s_pow = FsFr::one();
+
+    (0..npoints)
+        .map(|_| {
+            let point = FsG1::generator().mul(&s_pow);
+            s_pow = s_pow.mul(&s);
+            point
+        })
+        .collect()
+}
+
+fn tune(
+    npoints: usize,
+    tuner: &MsmTuner,
+    report_path: Option<&str>,
+    profile_path: Option<&str>,
+) -> Result<(), String> {
+    let points = tuning_points(npoints);
+    let report = tuner.sweep::<FsFr, FsG1, FsFp, FsG1Affine>(&points)?;
+
+    let text = report.to_text();
+    print!("{}", text);
+    if let Some(path) = report_path {
+        fs::write(path, &text).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    }
+
+    let window = report
+        .recommended()
+        .ok_or_else(|| String::from("No window was timed"))?;
+    println!("recommended window {}", window);
+
+    if let Some(path) = profile_path {
+        let mut profile = if fs::metadata(path).is_ok() {
+            TuningProfile::load_from_file(path)?
+        } else {
+            TuningProfile::new()
+        };
+        report.record_into(&mut profile);
+        profile.save_to_file(path)?;
+    }
+
+    Ok(())
+}
+
+fn run() -> Result<bool, String> {
+    let mut setup = None;
+    let mut windows = None;
+    let mut iterations = None;
+    let mut report = None;
+    let mut profile = None;
+    let mut positional = Vec::new();
+    let mut args = env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| format!("Missing value for {}", name))
+        };
+        match arg.as_str() {
+            "--help" | "-h" => {
+                println!("{}", USAGE);
+                exit(0);
+            }
+            "--setup" => setup = Some(value("--setup")?),
+            "--windows" => windows = Some(parse_windows(&value("--windows")?)?),
+            "--iterations" => iterations = Some(parse_number(&value("--iterations")?)?),
+            "--report" => report = Some(value("--report")?),
+            "--profile" => profile = Some(value("--profile")?),
+            _ => positional.push(arg),
+        }
+    }
+
+    match positional.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        ["prove", file] => {
+            let ts = load_setup(setup.as_deref())?;
+            prove(file, &ts).map(|_| true)
+        }
+        ["verify", file, commitment, proof] => {
+            let ts = load_setup(setup.as_deref())?;
+            let valid = verify(file, commitment, proof, &ts)?;
+            println!("{}", if valid { "valid" } else { "invalid" });
+            Ok(valid)
+        }
+        ["tune", npoints] => {
+            let default = MsmTuner::default();
+            let tuner = MsmTuner::new(
+                windows.unwrap_or_else(|| default.candidates().to_vec()),
+                iterations.unwrap_or(default.iterations()),
+            )?;
+            tune(
+                parse_number(npoints)?,
+                &tuner,
+                report.as_deref(),
+                profile.as_deref(),
+            )
+            .map(|_| true)
+        }
+        ["convert", from, to, input, output] => convert_setup::<FsFr, FsG1, FsG2, FsFFTSettings>(
+            input,
+            output,
+            SetupFormat::from_name(from)?,
+            SetupFormat::from_name(to)?,
+        )
+        .map(|_| true),
+        _ => Err(String::from("Invalid arguments")),
+    }
+}
+
+fn main() {
+    match run() {
+        Ok(true) => {}
+        Ok(false) => exit(1),
+        Err(err) => {
+            eprintln!("{}\n{}", err, USAGE);
+            exit(2);
+        }
+    }
+}
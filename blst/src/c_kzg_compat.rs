@@ -0,0 +1,350 @@
+extern crate alloc;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::{Deref, DerefMut};
+use core::str::FromStr;
+#[cfg(feature = "io")]
+use std::path::Path;
+
+use kzg::eip_4844::{
+    blob_to_kzg_commitment_rust, bytes_to_blob, compute_blob_kzg_proof_rust,
+    compute_kzg_proof_rust, load_trusted_setup_rust, verify_blob_kzg_proof_batch_rust,
+    verify_blob_kzg_proof_rust, verify_kzg_proof_rust, C_KZG_RET, C_KZG_RET_BADARGS,
+};
+use kzg::{Fr, G1};
+
+#[cfg(feature = "io")]
+use crate::eip_4844::load_trusted_setup_filename_rust;
+use crate::types::fr::FsFr;
+use crate::types::g1::FsG1;
+use crate::types::kzg_settings::FsKZGSettings;
+
+pub use kzg::eip_4844::{
+    BYTES_PER_BLOB, BYTES_PER_COMMITMENT, BYTES_PER_FIELD_ELEMENT, BYTES_PER_PROOF,
+    FIELD_ELEMENTS_PER_BLOB,
+};
+
+////////////////////////////// c-kzg compatible API //////////////////////////////
+//
+// Mirrors the surface of the `c-kzg` Rust bindings: the same type names, constructors, associated
+// functions and error enum. Rust clients switch implementations by importing this module in place
+// of the crate, `use rust_kzg_blst::c_kzg_compat as c_kzg;`, without touching call sites. Inputs
+// rejected by the implementation surface as `Error::CError(C_KZG_RET_BADARGS)`, as in c-kzg.
+
+pub const BYTES_PER_G1_POINT: usize = kzg::eip_4844::BYTES_PER_G1;
+pub const BYTES_PER_G2_POINT: usize = kzg::eip_4844::BYTES_PER_G2;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// Wrong number of bytes
+    InvalidBytesLength(String),
+    /// Input is not valid hex
+    InvalidHexFormat(String),
+    /// Proof could not be parsed
+    InvalidKzgProof(String),
+    /// Commitment could not be parsed
+    InvalidKzgCommitment(String),
+    /// Trusted setup could not be loaded
+    InvalidTrustedSetup(String),
+    /// Batch inputs of different lengths
+    MismatchLength(String),
+    /// Error code of the underlying implementation
+    CError(C_KZG_RET),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidBytesLength(s)
+            | Self::InvalidHexFormat(s)
+            | Self::InvalidKzgProof(s)
+            | Self::InvalidKzgCommitment(s)
+            | Self::InvalidTrustedSetup(s)
+            | Self::MismatchLength(s) => f.write_str(s),
+            Self::CError(code) => write!(f, "C_KZG_RET error code {}", code),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+fn badargs<T>(_: T) -> Error {
+    Error::CError(C_KZG_RET_BADARGS)
+}
+
+macro_rules! fixed_bytes {
+    ($name: ident, $size: expr) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #[repr(C)]
+        pub struct $name {
+            bytes: [u8; $size],
+        }
+
+        impl $name {
+            pub fn new(bytes: [u8; $size]) -> Self {
+                Self { bytes }
+            }
+
+            pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+                let bytes = bytes.try_into().map_err(|_| {
+                    Error::InvalidBytesLength(format!(
+                        "Invalid byte length. Expected {} got {}",
+                        $size,
+                        bytes.len()
+                    ))
+                })?;
+                Ok(Self { bytes })
+            }
+
+            pub fn from_hex(hex_str: &str) -> Result<Self, Error> {
+                let bytes = hex::decode(hex_str.strip_prefix("0x").unwrap_or(hex_str))
+                    .map_err(|e| Error::InvalidHexFormat(format!("{}", e)))?;
+                Self::from_bytes(&bytes)
+            }
+        }
+
+        impl From<[u8; $size]> for $name {
+            fn from(bytes: [u8; $size]) -> Self {
+                Self { bytes }
+            }
+        }
+
+        impl Deref for $name {
+            type Target = [u8; $size];
+
+            fn deref(&self) -> &Self::Target {
+                &self.bytes
+            }
+        }
+
+        impl DerefMut for $name {
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.bytes
+            }
+        }
+
+        hex_format!($name);
+    };
+}
+
+// `0x`-prefixed hex for `Display`, `LowerHex` with `{:#x}`, and `FromStr` through `from_hex`
+macro_rules! hex_format {
+    ($name: ident) => {
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "0x{}", hex::encode(self.bytes))
+            }
+        }
+
+        impl fmt::LowerHex for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                if f.alternate() {
+                    f.write_str("0x")?;
+                }
+                f.write_str(&hex::encode(self.bytes))
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = Error;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Self::from_hex(s)
+            }
+        }
+    };
+}
+
+fixed_bytes!(Bytes32, 32);
+fixed_bytes!(Bytes48, 48);
+fixed_bytes!(Blob, BYTES_PER_BLOB);
+
+// Compressed G1 points, shared by commitments and proofs
+macro_rules! g1_bytes {
+    ($name: ident) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #[repr(C)]
+        pub struct $name {
+            bytes: [u8; BYTES_PER_G1_POINT],
+        }
+
+        impl $name {
+            pub fn to_bytes(&self) -> Bytes48 {
+                Bytes48::new(self.bytes)
+            }
+
+            pub fn as_hex_string(&self) -> String {
+                format!("0x{}", hex::encode(self.bytes))
+            }
+
+            /// Parse the hex encoding of the compressed point, with or without `0x`. The point is
+            /// only checked when used
+            pub fn from_hex(hex_str: &str) -> Result<Self, Error> {
+                Bytes48::from_hex(hex_str).map(|bytes| Self { bytes: *bytes })
+            }
+        }
+
+        hex_format!($name);
+
+        impl Deref for $name {
+            type Target = [u8; BYTES_PER_G1_POINT];
+
+            fn deref(&self) -> &Self::Target {
+                &self.bytes
+            }
+        }
+
+        impl From<[u8; BYTES_PER_G1_POINT]> for $name {
+            fn from(bytes: [u8; BYTES_PER_G1_POINT]) -> Self {
+                Self { bytes }
+            }
+        }
+    };
+}
+
+g1_bytes!(KzgCommitment);
+g1_bytes!(KzgProof);
+
+#[derive(Debug, Clone)]
+pub struct KzgSettings(FsKZGSettings);
+
+impl KzgSettings {
+    pub fn load_trusted_setup(
+        g1_bytes: &[[u8; BYTES_PER_G1_POINT]],
+        g2_bytes: &[[u8; BYTES_PER_G2_POINT]],
+    ) -> Result<Self, Error> {
+        load_trusted_setup_rust(&g1_bytes.concat(), &g2_bytes.concat())
+            .map(Self)
+            .map_err(Error::InvalidTrustedSetup)
+    }
+
+    #[cfg(feature = "io")]
+    pub fn load_trusted_setup_file(file_path: &Path) -> Result<Self, Error> {
+        let file_path = file_path
+            .to_str()
+            .ok_or_else(|| Error::InvalidTrustedSetup(String::from("Invalid file path")))?;
+
+        load_trusted_setup_filename_rust(file_path)
+            .map(Self)
+            .map_err(Error::InvalidTrustedSetup)
+    }
+}
+
+fn deserialize_blob(blob: &Blob) -> Result<Vec<FsFr>, Error> {
+    bytes_to_blob(&blob.bytes).map_err(badargs)
+}
+
+fn deserialize_g1(bytes: &Bytes48) -> Result<FsG1, Error> {
+    FsG1::from_bytes(&bytes.bytes).map_err(badargs)
+}
+
+impl KzgCommitment {
+    pub fn blob_to_kzg_commitment(blob: &Blob, kzg_settings: &KzgSettings) -> Result<Self, Error> {
+        let commitment = blob_to_kzg_commitment_rust(&deserialize_blob(blob)?, &kzg_settings.0)
+            .map_err(badargs)?;
+
+        Ok(Self::from(commitment.to_bytes()))
+    }
+}
+
+impl KzgProof {
+    pub fn compute_kzg_proof(
+        blob: &Blob,
+        z_bytes: &Bytes32,
+        kzg_settings: &KzgSettings,
+    ) -> Result<(Self, Bytes32), Error> {
+        let z = FsFr::from_bytes(&z_bytes.bytes).map_err(badargs)?;
+        let (proof, y) = compute_kzg_proof_rust(&deserialize_blob(blob)?, &z, &kzg_settings.0)
+            .map_err(badargs)?;
+
+        Ok((Self::from(proof.to_bytes()), Bytes32::new(y.to_bytes())))
+    }
+
+    pub fn compute_blob_kzg_proof(
+        blob: &Blob,
+        commitment_bytes: &Bytes48,
+        kzg_settings: &KzgSettings,
+    ) -> Result<Self, Error> {
+        let proof = compute_blob_kzg_proof_rust(
+            &deserialize_blob(blob)?,
+            &deserialize_g1(commitment_bytes)?,
+            &kzg_settings.0,
+        )
+        .map_err(badargs)?;
+
+        Ok(Self::from(proof.to_bytes()))
+    }
+
+    pub fn verify_kzg_proof(
+        commitment_bytes: &Bytes48,
+        z_bytes: &Bytes32,
+        y_bytes: &Bytes32,
+        proof_bytes: &Bytes48,
+        kzg_settings: &KzgSettings,
+    ) -> Result<bool, Error> {
+        verify_kzg_proof_rust(
+            &deserialize_g1(commitment_bytes)?,
+            &FsFr::from_bytes(&z_bytes.bytes).map_err(badargs)?,
+            &FsFr::from_bytes(&y_bytes.bytes).map_err(badargs)?,
+            &deserialize_g1(proof_bytes)?,
+            &kzg_settings.0,
+        )
+        .map_err(badargs)
+    }
+
+    pub fn verify_blob_kzg_proof(
+        blob: &Blob,
+        commitment_bytes: &Bytes48,
+        proof_bytes: &Bytes48,
+        kzg_settings: &KzgSettings,
+    ) -> Result<bool, Error> {
+        verify_blob_kzg_proof_rust(
+            &deserialize_blob(blob)?,
+            &deserialize_g1(commitment_bytes)?,
+            &deserialize_g1(proof_bytes)?,
+            &kzg_settings.0,
+        )
+        .map_err(badargs)
+    }
+
+    pub fn verify_blob_kzg_proof_batch(
+        blobs: &[Blob],
+        commitments_bytes: &[Bytes48],
+        proofs_bytes: &[Bytes48],
+        kzg_settings: &KzgSettings,
+    ) -> Result<bool, Error> {
+        if blobs.len() != commitments_bytes.len() {
+            return Err(Error::MismatchLength(format!(
+                "There are {} blobs and {} commitments",
+                blobs.len(),
+                commitments_bytes.len()
+            )));
+        }
+        if blobs.len() != proofs_bytes.len() {
+            return Err(Error::MismatchLength(format!(
+                "There are {} blobs and {} proofs",
+                blobs.len(),
+                proofs_bytes.len()
+            )));
+        }
+
+        let blobs = blobs
+            .iter()
+            .map(deserialize_blob)
+            .collect::<Result<Vec<_>, _>>()?;
+        let commitments = commitments_bytes
+            .iter()
+            .map(deserialize_g1)
+            .collect::<Result<Vec<_>, _>>()?;
+        let proofs = proofs_bytes
+            .iter()
+            .map(deserialize_g1)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        verify_blob_kzg_proof_batch_rust(&blobs, &commitments, &proofs, &kzg_settings.0)
+            .map_err(badargs)
+    }
+}
@@ -1,49 +1,35 @@
 extern crate alloc;
 
-use alloc::boxed::Box;
-use alloc::string::String;
-use alloc::vec::Vec;
-use core::ptr::null_mut;
-use kzg::common_utils::reverse_bit_order;
-use kzg::eip_4844::{
-    blob_to_kzg_commitment_rust, compute_blob_kzg_proof_rust, compute_kzg_proof_rust,
-    load_trusted_setup_rust, verify_blob_kzg_proof_batch_rust, verify_blob_kzg_proof_rust,
-    verify_kzg_proof_rust, PrecomputationTableManager,
-};
-use kzg::{cfg_into_iter, Fr, G1};
-#[cfg(feature = "std")]
-use libc::FILE;
-#[cfg(feature = "std")]
+#[cfg(all(feature = "std", feature = "io"))]
+use alloc::string::{String, ToString};
+#[cfg(all(feature = "std", feature = "io"))]
+use kzg::eip_4844::{load_trusted_setup_rust, load_trusted_setup_string};
+#[cfg(all(feature = "std", feature = "io"))]
 use std::fs::File;
-#[cfg(feature = "std")]
+#[cfg(all(feature = "std", feature = "io"))]
 use std::io::Read;
 
-use blst::{blst_fr, blst_p1, blst_p2};
-
-#[cfg(feature = "std")]
-use kzg::eip_4844::load_trusted_setup_string;
-
-use kzg::eip_4844::{
-    Blob, Bytes32, Bytes48, CKZGSettings, KZGCommitment, KZGProof, BYTES_PER_FIELD_ELEMENT,
-    BYTES_PER_G1, BYTES_PER_G2, C_KZG_RET, C_KZG_RET_BADARGS, C_KZG_RET_OK,
-    FIELD_ELEMENTS_PER_BLOB, TRUSTED_SETUP_NUM_G1_POINTS, TRUSTED_SETUP_NUM_G2_POINTS,
-};
-
-use crate::types::fft_settings::FsFFTSettings;
-use crate::types::fp::FsFp;
-use crate::types::fr::FsFr;
-use crate::types::g1::{FsG1, FsG1Affine};
-
-use crate::types::g2::FsG2;
+#[cfg(all(feature = "std", feature = "io"))]
 use crate::types::kzg_settings::FsKZGSettings;
 
-#[cfg(feature = "parallel")]
-use rayon::prelude::*;
-
-static mut PRECOMPUTATION_TABLES: PrecomputationTableManager<FsFr, FsG1, FsFp, FsG1Affine> =
-    PrecomputationTableManager::new();
+// The C interface moved to `c_bindings`, re-exported under its former path
+#[cfg(all(
+    feature = "std",
+    feature = "io",
+    not(all(target_arch = "wasm32", target_os = "unknown"))
+))]
+pub use crate::c_bindings::load_trusted_setup_file;
+#[cfg(feature = "commit")]
+pub use crate::c_bindings::{blob_to_kzg_commitment, blob_to_kzg_commitments_sg};
+#[cfg(feature = "prove")]
+pub use crate::c_bindings::{
+    compute_blob_kzg_proof, compute_blob_kzg_proofs_sg, compute_kzg_proof,
+};
+pub use crate::c_bindings::{free_trusted_setup, load_trusted_setup};
+#[cfg(feature = "verify")]
+pub use crate::c_bindings::{verify_blob_kzg_proof, verify_blob_kzg_proof_batch, verify_kzg_proof};
 
-#[cfg(feature = "std")]
+#[cfg(all(feature = "std", feature = "io"))]
 pub fn load_trusted_setup_filename_rust(filepath: &str) -> Result<FsKZGSettings, String> {
     let mut file = File::open(filepath).map_err(|_| "Unable to open file".to_string())?;
     let mut contents = String::new();
@@ -53,418 +39,3 @@ pub fn load_trusted_setup_filename_rust(filepath: &str) -> Result<FsKZGSettings,
     let (g1_bytes, g2_bytes) = load_trusted_setup_string(&contents)?;
     load_trusted_setup_rust(g1_bytes.as_slice(), g2_bytes.as_slice())
 }
-
-fn fft_settings_to_rust(c_settings: *const CKZGSettings) -> Result<FsFFTSettings, String> {
-    let settings = unsafe { &*c_settings };
-
-    let roots_of_unity = unsafe {
-        core::slice::from_raw_parts(settings.roots_of_unity, settings.max_width as usize)
-            .iter()
-            .map(|r| FsFr(*r))
-            .collect::<Vec<FsFr>>()
-    };
-    let mut expanded_roots_of_unity = roots_of_unity.clone();
-    reverse_bit_order(&mut expanded_roots_of_unity)?;
-    expanded_roots_of_unity.push(FsFr::one());
-    let mut reverse_roots_of_unity = expanded_roots_of_unity.clone();
-    reverse_roots_of_unity.reverse();
-
-    let mut first_root = expanded_roots_of_unity[1];
-    let first_root_arr = [first_root; 1];
-    first_root = first_root_arr[0];
-
-    Ok(FsFFTSettings {
-        max_width: settings.max_width as usize,
-        root_of_unity: first_root,
-        expanded_roots_of_unity,
-        reverse_roots_of_unity,
-        roots_of_unity,
-    })
-}
-
-fn kzg_settings_to_rust(c_settings: &CKZGSettings) -> Result<FsKZGSettings, String> {
-    let secret_g1 = unsafe {
-        core::slice::from_raw_parts(c_settings.g1_values, TRUSTED_SETUP_NUM_G1_POINTS)
-            .iter()
-            .map(|r| FsG1(*r))
-            .collect::<Vec<FsG1>>()
-    };
-    Ok(FsKZGSettings {
-        fs: fft_settings_to_rust(c_settings)?,
-        secret_g1,
-        secret_g2: unsafe {
-            core::slice::from_raw_parts(c_settings.g2_values, TRUSTED_SETUP_NUM_G2_POINTS)
-                .iter()
-                .map(|r| FsG2(*r))
-                .collect::<Vec<FsG2>>()
-        },
-        precomputation: unsafe { PRECOMPUTATION_TABLES.get_precomputation(c_settings) },
-    })
-}
-
-fn kzg_settings_to_c(rust_settings: &FsKZGSettings) -> CKZGSettings {
-    let g1_val = rust_settings
-        .secret_g1
-        .iter()
-        .map(|r| r.0)
-        .collect::<Vec<blst_p1>>();
-    let g1_val = Box::new(g1_val);
-    let g2_val = rust_settings
-        .secret_g2
-        .iter()
-        .map(|r| r.0)
-        .collect::<Vec<blst_p2>>();
-    let x = g2_val.into_boxed_slice();
-    let stat_ref = Box::leak(x);
-    let v = Box::into_raw(g1_val);
-
-    let roots_of_unity = Box::new(
-        rust_settings
-            .fs
-            .roots_of_unity
-            .iter()
-            .map(|r| r.0)
-            .collect::<Vec<blst_fr>>(),
-    );
-
-    CKZGSettings {
-        max_width: rust_settings.fs.max_width as u64,
-        roots_of_unity: unsafe { (*Box::into_raw(roots_of_unity)).as_mut_ptr() },
-        g1_values: unsafe { (*v).as_mut_ptr() },
-        g2_values: stat_ref.as_mut_ptr(),
-    }
-}
-
-unsafe fn deserialize_blob(blob: *const Blob) -> Result<Vec<FsFr>, C_KZG_RET> {
-    (*blob)
-        .bytes
-        .chunks(BYTES_PER_FIELD_ELEMENT)
-        .map(|chunk| {
-            let mut bytes = [0u8; BYTES_PER_FIELD_ELEMENT];
-            bytes.copy_from_slice(chunk);
-            if let Ok(result) = FsFr::from_bytes(&bytes) {
-                Ok(result)
-            } else {
-                Err(C_KZG_RET_BADARGS)
-            }
-        })
-        .collect::<Result<Vec<FsFr>, C_KZG_RET>>()
-}
-
-macro_rules! handle_ckzg_badargs {
-    ($x: expr) => {
-        match $x {
-            Ok(value) => value,
-            Err(_) => return C_KZG_RET_BADARGS,
-        }
-    };
-}
-
-/// # Safety
-#[no_mangle]
-pub unsafe extern "C" fn blob_to_kzg_commitment(
-    out: *mut KZGCommitment,
-    blob: *const Blob,
-    s: &CKZGSettings,
-) -> C_KZG_RET {
-    if TRUSTED_SETUP_NUM_G1_POINTS == 0 {
-        // FIXME: load_trusted_setup should set this value, but if not, it fails
-        TRUSTED_SETUP_NUM_G1_POINTS = FIELD_ELEMENTS_PER_BLOB
-    };
-
-    let deserialized_blob = handle_ckzg_badargs!(deserialize_blob(blob));
-    let settings = handle_ckzg_badargs!(kzg_settings_to_rust(s));
-    let tmp = handle_ckzg_badargs!(blob_to_kzg_commitment_rust(&deserialized_blob, &settings));
-
-    (*out).bytes = tmp.to_bytes();
-    C_KZG_RET_OK
-}
-
-/// # Safety
-#[no_mangle]
-pub unsafe extern "C" fn load_trusted_setup(
-    out: *mut CKZGSettings,
-    g1_bytes: *const u8,
-    n1: usize,
-    g2_bytes: *const u8,
-    n2: usize,
-) -> C_KZG_RET {
-    let g1_bytes = core::slice::from_raw_parts(g1_bytes, n1 * BYTES_PER_G1);
-    let g2_bytes = core::slice::from_raw_parts(g2_bytes, n2 * BYTES_PER_G2);
-    TRUSTED_SETUP_NUM_G1_POINTS = g1_bytes.len() / BYTES_PER_G1;
-    let mut settings = handle_ckzg_badargs!(load_trusted_setup_rust(g1_bytes, g2_bytes));
-
-    let c_settings = kzg_settings_to_c(&settings);
-
-    PRECOMPUTATION_TABLES.save_precomputation(settings.precomputation.take(), &c_settings);
-
-    *out = c_settings;
-    C_KZG_RET_OK
-}
-
-/// # Safety
-#[cfg(feature = "std")]
-#[no_mangle]
-pub unsafe extern "C" fn load_trusted_setup_file(
-    out: *mut CKZGSettings,
-    in_: *mut FILE,
-) -> C_KZG_RET {
-    let mut buf = vec![0u8; 1024 * 1024];
-    let len: usize = libc::fread(buf.as_mut_ptr() as *mut libc::c_void, 1, buf.len(), in_);
-    let s = handle_ckzg_badargs!(String::from_utf8(buf[..len].to_vec()));
-    let (g1_bytes, g2_bytes) = handle_ckzg_badargs!(load_trusted_setup_string(&s));
-    TRUSTED_SETUP_NUM_G1_POINTS = g1_bytes.len() / BYTES_PER_G1;
-    if TRUSTED_SETUP_NUM_G1_POINTS != FIELD_ELEMENTS_PER_BLOB {
-        // Helps pass the Java test "shouldThrowExceptionOnIncorrectTrustedSetupFromFile",
-        // as well as 5 others that pass only if this one passes (likely because Java doesn't
-        // deallocate its KZGSettings pointer when no exception is thrown).
-        return C_KZG_RET_BADARGS;
-    }
-    let mut settings = handle_ckzg_badargs!(load_trusted_setup_rust(
-        g1_bytes.as_slice(),
-        g2_bytes.as_slice()
-    ));
-
-    let c_settings = kzg_settings_to_c(&settings);
-
-    PRECOMPUTATION_TABLES.save_precomputation(settings.precomputation.take(), &c_settings);
-
-    *out = c_settings;
-
-    C_KZG_RET_OK
-}
-
-/// # Safety
-#[no_mangle]
-pub unsafe extern "C" fn compute_blob_kzg_proof(
-    out: *mut KZGProof,
-    blob: *const Blob,
-    commitment_bytes: *const Bytes48,
-    s: &CKZGSettings,
-) -> C_KZG_RET {
-    let deserialized_blob = match deserialize_blob(blob) {
-        Ok(value) => value,
-        Err(err) => return err,
-    };
-
-    let commitment_g1 = handle_ckzg_badargs!(FsG1::from_bytes(&(*commitment_bytes).bytes));
-    let settings = handle_ckzg_badargs!(kzg_settings_to_rust(s));
-    let proof = handle_ckzg_badargs!(compute_blob_kzg_proof_rust(
-        &deserialized_blob,
-        &commitment_g1,
-        &settings
-    ));
-
-    (*out).bytes = proof.to_bytes();
-    C_KZG_RET_OK
-}
-
-/// # Safety
-#[no_mangle]
-pub unsafe extern "C" fn free_trusted_setup(s: *mut CKZGSettings) {
-    if s.is_null() {
-        return;
-    }
-
-    PRECOMPUTATION_TABLES.remove_precomputation(&*s);
-
-    let max_width = (*s).max_width as usize;
-    let roots = Box::from_raw(core::slice::from_raw_parts_mut(
-        (*s).roots_of_unity,
-        max_width,
-    ));
-    drop(roots);
-    (*s).roots_of_unity = null_mut();
-
-    let g1 = Box::from_raw(core::slice::from_raw_parts_mut(
-        (*s).g1_values,
-        TRUSTED_SETUP_NUM_G1_POINTS,
-    ));
-    drop(g1);
-    (*s).g1_values = null_mut();
-
-    let g2 = Box::from_raw(core::slice::from_raw_parts_mut(
-        (*s).g2_values,
-        TRUSTED_SETUP_NUM_G2_POINTS,
-    ));
-    drop(g2);
-    (*s).g2_values = null_mut();
-    (*s).max_width = 0;
-}
-
-/// # Safety
-#[no_mangle]
-pub unsafe extern "C" fn verify_kzg_proof(
-    ok: *mut bool,
-    commitment_bytes: *const Bytes48,
-    z_bytes: *const Bytes32,
-    y_bytes: *const Bytes32,
-    proof_bytes: *const Bytes48,
-    s: &CKZGSettings,
-) -> C_KZG_RET {
-    let frz = handle_ckzg_badargs!(FsFr::from_bytes(&(*z_bytes).bytes));
-    let fry = handle_ckzg_badargs!(FsFr::from_bytes(&(*y_bytes).bytes));
-    let g1commitment = handle_ckzg_badargs!(FsG1::from_bytes(&(*commitment_bytes).bytes));
-    let g1proof = handle_ckzg_badargs!(FsG1::from_bytes(&(*proof_bytes).bytes));
-
-    let settings = handle_ckzg_badargs!(kzg_settings_to_rust(s));
-
-    let result = handle_ckzg_badargs!(verify_kzg_proof_rust(
-        &g1commitment,
-        &frz,
-        &fry,
-        &g1proof,
-        &settings
-    ));
-
-    *ok = result;
-    C_KZG_RET_OK
-}
-
-/// # Safety
-#[no_mangle]
-pub unsafe extern "C" fn verify_blob_kzg_proof(
-    ok: *mut bool,
-    blob: *const Blob,
-    commitment_bytes: *const Bytes48,
-    proof_bytes: *const Bytes48,
-    s: &CKZGSettings,
-) -> C_KZG_RET {
-    let deserialized_blob = handle_ckzg_badargs!(deserialize_blob(blob));
-    let commitment_g1 = handle_ckzg_badargs!(FsG1::from_bytes(&(*commitment_bytes).bytes));
-    let proof_g1 = handle_ckzg_badargs!(FsG1::from_bytes(&(*proof_bytes).bytes));
-    let settings = handle_ckzg_badargs!(kzg_settings_to_rust(s));
-
-    let result = handle_ckzg_badargs!(verify_blob_kzg_proof_rust(
-        &deserialized_blob,
-        &commitment_g1,
-        &proof_g1,
-        &settings,
-    ));
-
-    *ok = result;
-    C_KZG_RET_OK
-}
-
-/// # Safety
-#[no_mangle]
-pub unsafe extern "C" fn verify_blob_kzg_proof_batch(
-    ok: *mut bool,
-    blobs: *const Blob,
-    commitments_bytes: *const Bytes48,
-    proofs_bytes: *const Bytes48,
-    n: usize,
-    s: &CKZGSettings,
-) -> C_KZG_RET {
-    let raw_blobs = core::slice::from_raw_parts(blobs, n);
-    let raw_commitments = core::slice::from_raw_parts(commitments_bytes, n);
-    let raw_proofs = core::slice::from_raw_parts(proofs_bytes, n);
-
-    let deserialized_blobs: Result<Vec<Vec<FsFr>>, C_KZG_RET> = cfg_into_iter!(raw_blobs)
-        .map(|raw_blob| deserialize_blob(raw_blob).map_err(|_| C_KZG_RET_BADARGS))
-        .collect();
-
-    let commitments_g1: Result<Vec<FsG1>, C_KZG_RET> = cfg_into_iter!(raw_commitments)
-        .map(|raw_commitment| {
-            FsG1::from_bytes(&raw_commitment.bytes).map_err(|_| C_KZG_RET_BADARGS)
-        })
-        .collect();
-
-    let proofs_g1: Result<Vec<FsG1>, C_KZG_RET> = cfg_into_iter!(raw_proofs)
-        .map(|raw_proof| FsG1::from_bytes(&raw_proof.bytes).map_err(|_| C_KZG_RET_BADARGS))
-        .collect();
-
-    if let (Ok(blobs), Ok(commitments), Ok(proofs)) =
-        (deserialized_blobs, commitments_g1, proofs_g1)
-    {
-        let settings = match kzg_settings_to_rust(s) {
-            Ok(value) => value,
-            Err(_) => return C_KZG_RET_BADARGS,
-        };
-
-        let result =
-            verify_blob_kzg_proof_batch_rust(blobs.as_slice(), &commitments, &proofs, &settings);
-
-        if let Ok(result) = result {
-            *ok = result;
-            C_KZG_RET_OK
-        } else {
-            C_KZG_RET_BADARGS
-        }
-    } else {
-        *ok = false;
-        C_KZG_RET_BADARGS
-    }
-}
-
-/// # Safety
-#[no_mangle]
-pub unsafe extern "C" fn compute_kzg_proof(
-    proof_out: *mut KZGProof,
-    y_out: *mut Bytes32,
-    blob: *const Blob,
-    z_bytes: *const Bytes32,
-    s: &CKZGSettings,
-) -> C_KZG_RET {
-    let deserialized_blob = match deserialize_blob(blob) {
-        Ok(value) => value,
-        Err(err) => return err,
-    };
-
-    let frz = match FsFr::from_bytes(&(*z_bytes).bytes) {
-        Ok(value) => value,
-        Err(_) => return C_KZG_RET_BADARGS,
-    };
-
-    let settings = match kzg_settings_to_rust(s) {
-        Ok(value) => value,
-        Err(_) => return C_KZG_RET_BADARGS,
-    };
-
-    let (proof_out_tmp, fry_tmp) = match compute_kzg_proof_rust(&deserialized_blob, &frz, &settings)
-    {
-        Ok(value) => value,
-        Err(_) => return C_KZG_RET_BADARGS,
-    };
-
-    (*proof_out).bytes = proof_out_tmp.to_bytes();
-    (*y_out).bytes = fry_tmp.to_bytes();
-    C_KZG_RET_OK
-}
-
-#[cfg(test)]
-mod tests {
-    use kzg_bench::tests::utils::get_trusted_setup_path;
-
-    use crate::eip_4844::{kzg_settings_to_c, kzg_settings_to_rust};
-
-    use super::load_trusted_setup_filename_rust;
-
-    #[test]
-    fn kzg_settings_to_rust_check_conversion() {
-        let settings = load_trusted_setup_filename_rust(get_trusted_setup_path().as_str());
-
-        assert!(settings.is_ok());
-
-        let settings = settings.unwrap();
-
-        let converted_settings = kzg_settings_to_rust(&kzg_settings_to_c(&settings)).unwrap();
-
-        assert_eq!(
-            settings.fs.root_of_unity,
-            converted_settings.fs.root_of_unity
-        );
-        assert_eq!(
-            settings.fs.roots_of_unity,
-            converted_settings.fs.roots_of_unity
-        );
-        assert_eq!(
-            settings.fs.expanded_roots_of_unity,
-            converted_settings.fs.expanded_roots_of_unity
-        );
-        assert_eq!(
-            settings.fs.reverse_roots_of_unity,
-            converted_settings.fs.reverse_roots_of_unity
-        );
-    }
-}
@@ -0,0 +1,49 @@
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use kzg::eip_7594::{
+    compute_cells_and_kzg_proofs_rust, recover_cells_and_kzg_proofs_rust,
+    verify_cell_kzg_proof_batch_rust, CellSettings,
+};
+
+use crate::types::fk20_multi_settings::FsFK20MultiSettings;
+use crate::types::fr::FsFr;
+use crate::types::g1::FsG1;
+use crate::types::kzg_settings::FsKZGSettings;
+
+/// Cell settings of the blst backend
+pub type FsCellSettings = CellSettings<FsKZGSettings, FsFK20MultiSettings>;
+
+/// Cell settings of the blobs committed to with `s`
+pub fn load_cell_settings(s: &FsKZGSettings) -> Result<FsCellSettings, String> {
+    FsCellSettings::new(s)
+}
+
+#[allow(clippy::type_complexity)]
+pub fn compute_cells_and_kzg_proofs(
+    blob: &[FsFr],
+    s: &FsCellSettings,
+) -> Result<(Vec<Vec<FsFr>>, Vec<FsG1>), String> {
+    compute_cells_and_kzg_proofs_rust(blob, s)
+}
+
+#[allow(clippy::type_complexity)]
+pub fn recover_cells_and_kzg_proofs(
+    cell_indices: &[usize],
+    cells: &[Vec<FsFr>],
+    s: &FsCellSettings,
+) -> Result<(Vec<Vec<FsFr>>, Vec<FsG1>), String> {
+    recover_cells_and_kzg_proofs_rust(cell_indices, cells, s)
+}
+
+pub fn verify_cell_kzg_proof_batch(
+    commitments: &[FsG1],
+    cell_indices: &[usize],
+    cells: &[Vec<FsFr>],
+    proofs: &[FsG1],
+    s: &FsCellSettings,
+) -> Result<bool, String> {
+    verify_cell_kzg_proof_batch_rust(commitments, cell_indices, cells, proofs, s)
+}
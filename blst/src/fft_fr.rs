@@ -2,9 +2,10 @@ extern crate alloc;
 
 use alloc::format;
 use alloc::string::String;
-use alloc::vec;
 use alloc::vec::Vec;
 
+use kzg::common_utils::try_vec;
+use kzg::cooperative::maybe_yield;
 use kzg::{FFTFr, Fr};
 
 use crate::types::fft_settings::FsFFTSettings;
@@ -37,7 +38,7 @@ pub fn fft_fr_fast(
 
         #[cfg(feature = "parallel")]
         {
-            if half > 256 {
+            if half > 256 && kzg::parallelism::is_parallel() {
                 let (lo, hi) = ret.split_at_mut(half);
                 rayon::join(
                     || fft_fr_fast(lo, data, stride * 2, roots, roots_stride * 2),
@@ -60,12 +61,53 @@ pub fn fft_fr_fast(
             ret[i + half] = ret[i].sub(&y_times_root);
             ret[i] = ret[i].add(&y_times_root);
         }
+        if half >= 256 {
+            maybe_yield();
+        }
     } else {
         // When len = 1, return the permuted element
         ret[0] = data[0];
     }
 }
 
+/// Iterative (loop-based) version of `fft_fr_fast`, with the same arguments and output.
+///
+/// Uses constant stack space, so it is safe on targets with small stacks (wasm, zkVMs).
+pub fn fft_fr_iterative(
+    ret: &mut [FsFr],
+    data: &[FsFr],
+    stride: usize,
+    roots: &[FsFr],
+    roots_stride: usize,
+) {
+    let n = ret.len();
+    if n == 1 {
+        ret[0] = data[0];
+        return;
+    }
+
+    // Start from the bit-reversed permutation, which is what the recursion bottoms out with
+    let unused_bits = usize::BITS - n.trailing_zeros();
+    for (i, out) in ret.iter_mut().enumerate() {
+        *out = data[(i.reverse_bits() >> unused_bits) * stride];
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let half = len / 2;
+        let step = roots_stride * (n / len);
+        for chunk in ret.chunks_exact_mut(len) {
+            for i in 0..half {
+                let y_times_root = chunk[i + half].mul(&roots[i * step]);
+                chunk[i + half] = chunk[i].sub(&y_times_root);
+                chunk[i] = chunk[i].add(&y_times_root);
+            }
+        }
+        maybe_yield();
+        len *= 2;
+    }
+}
+
 impl FsFFTSettings {
     /// Fast Fourier Transform for finite field elements, `output` must be zeroes
     pub(crate) fn fft_fr_output(
@@ -101,6 +143,16 @@ impl FsFFTSettings {
             &self.expanded_roots_of_unity
         };
 
+        #[cfg(any(feature = "iterative-fft", not(feature = "std")))]
+        fft_fr_iterative(output, data, 1, roots, stride);
+
+        #[cfg(all(feature = "parallel", not(feature = "iterative-fft")))]
+        kzg::parallelism::install(|| fft_fr_fast(output, data, 1, roots, stride));
+
+        #[cfg(all(
+            not(feature = "parallel"),
+            not(any(feature = "iterative-fft", not(feature = "std")))
+        ))]
         fft_fr_fast(output, data, 1, roots, stride);
 
         if inverse {
@@ -115,7 +167,7 @@ impl FsFFTSettings {
 impl FFTFr<FsFr> for FsFFTSettings {
     /// Fast Fourier Transform for finite field elements
     fn fft_fr(&self, data: &[FsFr], inverse: bool) -> Result<Vec<FsFr>, String> {
-        let mut ret = vec![FsFr::default(); data.len()];
+        let mut ret = try_vec(FsFr::default(), data.len())?;
 
         self.fft_fr_output(data, inverse, &mut ret)?;
 
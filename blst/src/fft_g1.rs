@@ -1,9 +1,10 @@
 extern crate alloc;
 
 use alloc::string::String;
-use alloc::vec;
 use alloc::vec::Vec;
 
+use kzg::common_utils::try_vec;
+use kzg::cooperative::maybe_yield;
 use kzg::{Fr, G1Mul, FFTG1, G1};
 
 use crate::types::fft_settings::FsFFTSettings;
@@ -21,11 +22,22 @@ pub fn fft_g1_fast(
     if half > 0 {
         #[cfg(feature = "parallel")]
         {
-            let (lo, hi) = ret.split_at_mut(half);
-            rayon::join(
-                || fft_g1_fast(lo, data, stride * 2, roots, roots_stride * 2),
-                || fft_g1_fast(hi, &data[stride..], stride * 2, roots, roots_stride * 2),
-            );
+            if kzg::parallelism::is_parallel() {
+                let (lo, hi) = ret.split_at_mut(half);
+                rayon::join(
+                    || fft_g1_fast(lo, data, stride * 2, roots, roots_stride * 2),
+                    || fft_g1_fast(hi, &data[stride..], stride * 2, roots, roots_stride * 2),
+                );
+            } else {
+                fft_g1_fast(&mut ret[..half], data, stride * 2, roots, roots_stride * 2);
+                fft_g1_fast(
+                    &mut ret[half..],
+                    &data[stride..],
+                    stride * 2,
+                    roots,
+                    roots_stride * 2,
+                );
+            }
         }
 
         #[cfg(not(feature = "parallel"))]
@@ -45,11 +57,50 @@ pub fn fft_g1_fast(
             ret[i + half] = ret[i].sub(&y_times_root);
             ret[i] = ret[i].add_or_dbl(&y_times_root);
         }
+        maybe_yield();
     } else {
         ret[0] = data[0];
     }
 }
 
+/// Iterative (loop-based) version of `fft_g1_fast`, with the same arguments and output.
+///
+/// Uses constant stack space, so it is safe on targets with small stacks (wasm, zkVMs).
+pub fn fft_g1_iterative(
+    ret: &mut [FsG1],
+    data: &[FsG1],
+    stride: usize,
+    roots: &[FsFr],
+    roots_stride: usize,
+) {
+    let n = ret.len();
+    if n == 1 {
+        ret[0] = data[0];
+        return;
+    }
+
+    // Start from the bit-reversed permutation, which is what the recursion bottoms out with
+    let unused_bits = usize::BITS - n.trailing_zeros();
+    for (i, out) in ret.iter_mut().enumerate() {
+        *out = data[(i.reverse_bits() >> unused_bits) * stride];
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let half = len / 2;
+        let step = roots_stride * (n / len);
+        for chunk in ret.chunks_exact_mut(len) {
+            for i in 0..half {
+                let y_times_root = chunk[i + half].mul(&roots[i * step]);
+                chunk[i + half] = chunk[i].sub(&y_times_root);
+                chunk[i] = chunk[i].add_or_dbl(&y_times_root);
+            }
+            maybe_yield();
+        }
+        len *= 2;
+    }
+}
+
 impl FFTG1<FsG1> for FsFFTSettings {
     fn fft_g1(&self, data: &[FsG1], inverse: bool) -> Result<Vec<FsG1>, String> {
         if data.len() > self.max_width {
@@ -61,7 +112,7 @@ impl FFTG1<FsG1> for FsFFTSettings {
         }
 
         let stride = self.max_width / data.len();
-        let mut ret = vec![FsG1::default(); data.len()];
+        let mut ret = try_vec(FsG1::default(), data.len())?;
 
         let roots = if inverse {
             &self.reverse_roots_of_unity
@@ -69,6 +120,16 @@ impl FFTG1<FsG1> for FsFFTSettings {
             &self.expanded_roots_of_unity
         };
 
+        #[cfg(any(feature = "iterative-fft", not(feature = "std")))]
+        fft_g1_iterative(&mut ret, data, 1, roots, stride);
+
+        #[cfg(all(feature = "parallel", not(feature = "iterative-fft")))]
+        kzg::parallelism::install(|| fft_g1_fast(&mut ret, data, 1, roots, stride));
+
+        #[cfg(all(
+            not(feature = "parallel"),
+            not(any(feature = "iterative-fft", not(feature = "std")))
+        ))]
         fft_g1_fast(&mut ret, data, 1, roots, stride);
 
         if inverse {
@@ -1,13 +1,26 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+pub mod c_bindings;
+#[cfg(all(
+    feature = "std",
+    feature = "commit",
+    feature = "prove",
+    feature = "verify"
+))]
+pub mod c_kzg_compat;
 pub mod consts;
 pub mod data_availability_sampling;
 pub mod eip_4844;
+#[cfg(feature = "das")]
+pub mod eip_7594;
 pub mod fft_fr;
 pub mod fft_g1;
 pub mod fk20_proofs;
 pub mod kzg_proofs;
+pub mod prelude;
 pub mod recovery;
 pub mod types;
 pub mod utils;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 pub mod zero_poly;
@@ -0,0 +1,37 @@
+//! Concrete types of the blst backend together with the traits they implement, for
+//! `use rust_kzg_blst::prelude::*;`
+
+use kzg::backend::{Backend, KzgBackend};
+
+pub use kzg::{
+    FFTFr, FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1LinComb, G1Mul, G1ProjAddAffine, G2Mul,
+    KZGSettings, PairingVerify, Poly, FFTG1, G1, G2,
+};
+
+pub use crate::types::fft_settings::FsFFTSettings;
+pub use crate::types::fp::FsFp;
+pub use crate::types::fr::FsFr;
+pub use crate::types::g1::{FsG1, FsG1Affine, FsG1ProjAddAffine};
+pub use crate::types::g2::FsG2;
+pub use crate::types::kzg_settings::FsKZGSettings;
+pub use crate::types::poly::FsPoly;
+
+/// `Backend` bundle of the blst types
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FsBackend;
+
+impl Backend for FsBackend {
+    const NAME: &'static str = "blst";
+
+    type Fr = FsFr;
+    type G1 = FsG1;
+    type G2 = FsG2;
+    type G1Fp = FsFp;
+    type G1Affine = FsG1Affine;
+    type G1ProjAddAffine = FsG1ProjAddAffine;
+    type Poly = FsPoly;
+    type FFTSettings = FsFFTSettings;
+    type KZGSettings = FsKZGSettings;
+}
+
+impl KzgBackend for FsBackend {}
@@ -10,11 +10,12 @@ use alloc::vec::Vec;
 use blst::p1_affines;
 use blst::{
     blst_fp, blst_p1, blst_p1_add, blst_p1_add_or_double, blst_p1_affine, blst_p1_cneg,
-    blst_p1_compress, blst_p1_double, blst_p1_from_affine, blst_p1_in_g1, blst_p1_is_equal,
-    blst_p1_is_inf, blst_p1_mult, blst_p1_uncompress, blst_scalar, blst_scalar_from_fr, BLST_ERROR,
+    blst_p1_compress, blst_p1_deserialize, blst_p1_double, blst_p1_from_affine, blst_p1_in_g1,
+    blst_p1_is_equal, blst_p1_is_inf, blst_p1_mult, blst_p1_serialize, blst_p1_uncompress,
+    blst_scalar, blst_scalar_from_fr, BLST_ERROR,
 };
 use kzg::common_utils::log_2_byte;
-use kzg::eip_4844::BYTES_PER_G1;
+use kzg::eip_4844::{BYTES_PER_G1, BYTES_PER_G1_UNCOMPRESSED};
 use kzg::msm::precompute::PrecomputationTable;
 use kzg::G1Affine;
 use kzg::G1GetFp;
@@ -27,11 +28,14 @@ use crate::kzg_proofs::g1_linear_combination;
 use crate::types::fr::FsFr;
 
 use super::fp::FsFp;
+use super::impl_hex_format;
 
 #[repr(C)]
 #[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
 pub struct FsG1(pub blst_p1);
 
+impl_hex_format!(FsG1, G1);
+
 impl FsG1 {
     pub(crate) const fn from_xyz(x: blst_fp, y: blst_fp, z: blst_fp) -> Self {
         FsG1(blst_p1 { x, y, z })
@@ -186,6 +190,38 @@ impl G1 for FsG1 {
             blst::blst_p1_double(&mut self.0, &self.0);
         }
     }
+
+    fn to_bytes_uncompressed(&self) -> Result<[u8; BYTES_PER_G1_UNCOMPRESSED], String> {
+        let mut out = [0u8; BYTES_PER_G1_UNCOMPRESSED];
+        unsafe {
+            blst_p1_serialize(out.as_mut_ptr(), &self.0);
+        }
+        Ok(out)
+    }
+
+    fn from_bytes_uncompressed(bytes: &[u8]) -> Result<Self, String> {
+        bytes
+            .try_into()
+            .map_err(|_| {
+                format!(
+                    "Invalid byte length. Expected {}, got {}",
+                    BYTES_PER_G1_UNCOMPRESSED,
+                    bytes.len()
+                )
+            })
+            .and_then(|bytes: &[u8; BYTES_PER_G1_UNCOMPRESSED]| {
+                let mut tmp = blst_p1_affine::default();
+                let mut g1 = blst_p1::default();
+                unsafe {
+                    // The deserialize routine also checks that the point is on the curve
+                    if blst_p1_deserialize(&mut tmp, bytes.as_ptr()) != BLST_ERROR::BLST_SUCCESS {
+                        return Err("Failed to deserialize".to_string());
+                    }
+                    blst_p1_from_affine(&mut g1, &tmp);
+                }
+                Ok(FsG1(g1))
+            })
+    }
 }
 
 impl G1GetFp<FsFp> for FsG1 {
@@ -6,8 +6,8 @@ use alloc::string::ToString;
 
 use blst::{
     blst_fp2, blst_p2, blst_p2_add_or_double, blst_p2_affine, blst_p2_cneg, blst_p2_compress,
-    blst_p2_double, blst_p2_from_affine, blst_p2_is_equal, blst_p2_mult, blst_p2_uncompress,
-    blst_scalar, blst_scalar_from_fr, BLST_ERROR,
+    blst_p2_double, blst_p2_from_affine, blst_p2_in_g2, blst_p2_is_equal, blst_p2_mult,
+    blst_p2_uncompress, blst_scalar, blst_scalar_from_fr, BLST_ERROR,
 };
 use kzg::eip_4844::BYTES_PER_G2;
 #[cfg(feature = "rand")]
@@ -107,6 +107,13 @@ impl G2 for FsG2 {
     fn equals(&self, b: &Self) -> bool {
         unsafe { blst_p2_is_equal(&self.0, &b.0) }
     }
+
+    fn is_valid(&self) -> bool {
+        unsafe {
+            // The point must be on the right subgroup
+            blst_p2_in_g2(&self.0)
+        }
+    }
 }
 
 impl FsG2 {
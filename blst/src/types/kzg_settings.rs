@@ -4,7 +4,9 @@ use alloc::string::String;
 use alloc::sync::Arc;
 use alloc::vec::Vec;
 
-use kzg::msm::precompute::{precompute, PrecomputationTable};
+use kzg::common_utils::try_vec_with_capacity;
+use kzg::dev_setup::guard_insecure_dev_setup;
+use kzg::msm::precompute::{precompute, PrecomputationTable, ReleasableTable};
 use kzg::{FFTFr, FFTSettings, Fr, G1Mul, G2Mul, KZGSettings, Poly, G1, G2};
 
 use crate::consts::{G1_GENERATOR, G2_GENERATOR};
@@ -23,7 +25,7 @@ pub struct FsKZGSettings {
     pub fs: FsFFTSettings,
     pub secret_g1: Vec<FsG1>,
     pub secret_g2: Vec<FsG2>,
-    pub precomputation: Option<Arc<PrecomputationTable<FsFr, FsG1, FsFp, FsG1Affine>>>,
+    pub precomputation: Arc<ReleasableTable<FsFr, FsG1, FsFp, FsG1Affine>>,
 }
 
 impl KZGSettings<FsFr, FsG1, FsG2, FsFFTSettings, FsPoly, FsFp, FsG1Affine> for FsKZGSettings {
@@ -37,7 +39,7 @@ impl KZGSettings<FsFr, FsG1, FsG2, FsFFTSettings, FsPoly, FsFp, FsG1Affine> for
             secret_g1: secret_g1.to_vec(),
             secret_g2: secret_g2.to_vec(),
             fs: fft_settings.clone(),
-            precomputation: precompute(secret_g1).ok().flatten().map(Arc::new),
+            precomputation: Arc::new(ReleasableTable::new(precompute(secret_g1).ok().flatten())),
         })
     }
 
@@ -52,7 +54,7 @@ impl KZGSettings<FsFr, FsG1, FsG2, FsFFTSettings, FsPoly, FsFp, FsG1Affine> for
             &self.secret_g1,
             &poly.coeffs,
             poly.coeffs.len(),
-            self.get_precomputation(),
+            self.get_precomputation().as_deref(),
         );
 
         Ok(out)
@@ -88,6 +90,8 @@ impl KZGSettings<FsFr, FsG1, FsG2, FsFFTSettings, FsPoly, FsFp, FsG1Affine> for
         x: &FsFr,
         y: &FsFr,
     ) -> Result<bool, String> {
+        guard_insecure_dev_setup(&self.secret_g2);
+
         let x_g2: FsG2 = G2_GENERATOR.mul(x);
         let s_minus_x: FsG2 = self.secret_g2[1].sub(&x_g2);
         let y_g1 = G1_GENERATOR.mul(y);
@@ -112,7 +116,7 @@ impl KZGSettings<FsFr, FsG1, FsG2, FsFFTSettings, FsPoly, FsFp, FsG1Affine> for
 
         // Construct x^n - x0^n = (x - x0.w^0)(x - x0.w^1)...(x - x0.w^(n-1))
         let mut divisor = FsPoly {
-            coeffs: Vec::with_capacity(n + 1),
+            coeffs: try_vec_with_capacity(n + 1)?,
         };
 
         // -(x0^n)
@@ -150,6 +154,7 @@ impl KZGSettings<FsFr, FsG1, FsG2, FsFFTSettings, FsPoly, FsFp, FsG1Affine> for
         if !n.is_power_of_two() {
             return Err(String::from("n is not a power of two"));
         }
+        guard_insecure_dev_setup(&self.secret_g2);
 
         // Interpolate at a coset.
         let mut interp = FsPoly {
@@ -172,7 +177,7 @@ impl KZGSettings<FsFr, FsG1, FsG2, FsFFTSettings, FsPoly, FsFp, FsG1Affine> for
         let xn_minus_yn = self.secret_g2[n].sub(&xn2);
 
         // [interpolation_polynomial(s)]_1
-        let is1 = self.commit_to_poly(&interp).unwrap();
+        let is1 = self.commit_to_poly(&interp)?;
 
         // [commitment - interpolation_polynomial(s)]_1 = [commit]_1 - [interpolation_polynomial(s)]_1
         let commit_minus_interp = com.sub(&is1);
@@ -202,7 +207,13 @@ impl KZGSettings<FsFr, FsG1, FsG2, FsFFTSettings, FsPoly, FsFp, FsG1Affine> for
         &self.secret_g2
     }
 
-    fn get_precomputation(&self) -> Option<&PrecomputationTable<FsFr, FsG1, FsFp, FsG1Affine>> {
-        self.precomputation.as_ref().map(|v| v.as_ref())
+    fn get_precomputation(&self) -> Option<Arc<PrecomputationTable<FsFr, FsG1, FsFp, FsG1Affine>>> {
+        self.precomputation.get()
+    }
+
+    fn get_releasable_precomputation(
+        &self,
+    ) -> Option<Arc<ReleasableTable<FsFr, FsG1, FsFp, FsG1Affine>>> {
+        Some(self.precomputation.clone())
     }
 }
@@ -7,3 +7,37 @@ pub mod g1;
 pub mod g2;
 pub mod kzg_settings;
 pub mod poly;
+
+// `Display` and `LowerHex` print the big-endian encoding of `to_bytes` as hex, `0x`-prefixed as
+// in Ethereum tooling (`{:#x}` for `LowerHex`), and `FromStr` parses it back with `from_bytes`,
+// with or without the prefix
+macro_rules! impl_hex_format {
+    ($name: ident, $trait: path) => {
+        impl core::fmt::Display for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(f, "0x{}", hex::encode(<$name as $trait>::to_bytes(self)))
+            }
+        }
+
+        impl core::fmt::LowerHex for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                if f.alternate() {
+                    f.write_str("0x")?;
+                }
+                f.write_str(&hex::encode(<$name as $trait>::to_bytes(self)))
+            }
+        }
+
+        impl core::str::FromStr for $name {
+            type Err = alloc::string::String;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let bytes = hex::decode(s.strip_prefix("0x").unwrap_or(s))
+                    .map_err(|e| alloc::format!("Invalid hex {}: {}", s, e))?;
+                <$name as $trait>::from_bytes(&bytes)
+            }
+        }
+    };
+}
+
+pub(crate) use impl_hex_format;
@@ -4,7 +4,7 @@ use alloc::string::String;
 use alloc::vec;
 use alloc::vec::Vec;
 
-use kzg::common_utils::{log2_pow2, log2_u64, next_pow_of_2};
+use kzg::common_utils::{log2_pow2, log2_u64, next_pow_of_2, try_vec};
 use kzg::{FFTFr, FFTSettings, FFTSettingsPoly, Fr, Poly};
 
 use crate::consts::SCALE_FACTOR;
@@ -42,7 +42,7 @@ impl Poly<FsFr> for FsPoly {
     fn eval(&self, x: &FsFr) -> FsFr {
         if self.coeffs.is_empty() {
             return FsFr::zero();
-        } else if x.is_zero() {
+        } else if x.is_zero() || self.coeffs.len() == 1 {
             return self.coeffs[0];
         }
 
@@ -95,7 +95,7 @@ impl Poly<FsFr> for FsPoly {
         }
 
         let mut ret = FsPoly {
-            coeffs: vec![FsFr::zero(); output_len],
+            coeffs: try_vec(FsFr::zero(), output_len)?,
         };
         // If the input polynomial is constant, the remainder of the series is zero
         if self.coeffs.len() == 1 {
@@ -124,7 +124,7 @@ impl Poly<FsFr> for FsPoly {
             // b.c -> tmp0 (we're using out for c)
             // tmp0.length = min_u64(d + 1, b->length + output->length - 1);
             let len_temp = (d + 1).min(self.len() + output_len - 1);
-            let mut tmp0 = self.mul(&ret, len_temp).unwrap();
+            let mut tmp0 = self.mul(&ret, len_temp)?;
 
             // 2 - b.c -> tmp0
             for i in 0..tmp0.len() {
@@ -134,7 +134,7 @@ impl Poly<FsFr> for FsPoly {
             tmp0.coeffs[0] = tmp0.coeffs[0].add(&fr_two);
 
             // c.(2 - b.c) -> tmp1;
-            let tmp1 = ret.mul(&tmp0, d + 1).unwrap();
+            let tmp1 = ret.mul(&tmp0, d + 1)?;
 
             for i in 0..tmp1.len() {
                 ret.coeffs[i] = tmp1.coeffs[i];
@@ -176,18 +176,18 @@ impl Poly<FsFr> for FsPoly {
 
             let mut out_coeffs = Vec::from(&self.coeffs[1..]);
             for i in (1..out_length).rev() {
-                out_coeffs[i] = out_coeffs[i].div(&divisor_1).unwrap();
+                out_coeffs[i] = out_coeffs[i].div(&divisor_1)?;
 
                 let tmp = out_coeffs[i].mul(&divisor_0);
                 out_coeffs[i - 1] = out_coeffs[i - 1].sub(&tmp);
             }
 
-            out_coeffs[0] = out_coeffs[0].div(&divisor_1).unwrap();
+            out_coeffs[0] = out_coeffs[0].div(&divisor_1)?;
 
             Ok(FsPoly { coeffs: out_coeffs })
         } else {
             let mut out: FsPoly = FsPoly {
-                coeffs: vec![FsFr::default(); out_length],
+                coeffs: try_vec(FsFr::default(), out_length)?,
             };
 
             let mut a_pos = self.len() - 1;
@@ -197,7 +197,7 @@ impl Poly<FsFr> for FsPoly {
             let mut a = self.coeffs.clone();
 
             while diff > 0 {
-                out.coeffs[diff] = a[a_pos].div(&divisor.coeffs[b_pos]).unwrap();
+                out.coeffs[diff] = a[a_pos].div(&divisor.coeffs[b_pos])?;
 
                 for i in 0..(b_pos + 1) {
                     let tmp = out.coeffs[diff].mul(&divisor.coeffs[i]);
@@ -208,7 +208,7 @@ impl Poly<FsFr> for FsPoly {
                 a_pos -= 1;
             }
 
-            out.coeffs[0] = a[a_pos].div(&divisor.coeffs[b_pos]).unwrap();
+            out.coeffs[0] = a[a_pos].div(&divisor.coeffs[b_pos])?;
             Ok(out)
         }
     }
@@ -231,21 +231,21 @@ impl Poly<FsFr> for FsPoly {
         // Special case for divisor.length == 1 (it's a constant)
         if divisor.len() == 1 {
             let mut out = FsPoly {
-                coeffs: vec![FsFr::zero(); self.len()],
+                coeffs: try_vec(FsFr::zero(), self.len())?,
             };
             for i in 0..out.len() {
-                out.coeffs[i] = self.coeffs[i].div(&divisor.coeffs[0]).unwrap();
+                out.coeffs[i] = self.coeffs[i].div(&divisor.coeffs[0])?;
             }
             return Ok(out);
         }
 
-        let mut a_flip = self.flip().unwrap();
-        let mut b_flip = divisor.flip().unwrap();
+        let mut a_flip = self.flip()?;
+        let mut b_flip = divisor.flip()?;
 
-        let inv_b_flip = b_flip.inverse(m - n + 1).unwrap();
-        let q_flip = a_flip.mul(&inv_b_flip, m - n + 1).unwrap();
+        let inv_b_flip = b_flip.inverse(m - n + 1)?;
+        let q_flip = a_flip.mul(&inv_b_flip, m - n + 1)?;
 
-        let out = q_flip.flip().unwrap();
+        let out = q_flip.flip()?;
         Ok(out)
     }
 
@@ -258,7 +258,7 @@ impl Poly<FsFr> for FsPoly {
         let b_degree = multiplier.len() - 1;
 
         let mut ret = FsPoly {
-            coeffs: vec![Fr::zero(); output_len],
+            coeffs: try_vec(Fr::zero(), output_len)?,
         };
 
         // Truncate the output to the length of the output polynomial
@@ -328,7 +328,7 @@ impl FsPoly {
 
     pub fn flip(&self) -> Result<FsPoly, String> {
         let mut ret = FsPoly {
-            coeffs: vec![FsFr::default(); self.len()],
+            coeffs: try_vec(FsFr::default(), self.len())?,
         };
         for i in 0..self.len() {
             ret.coeffs[i] = self.coeffs[self.coeffs.len() - i - 1]
@@ -341,7 +341,7 @@ impl FsPoly {
         let length = next_pow_of_2(self.len() + multiplier.len() - 1);
 
         let scale = log2_pow2(length);
-        let fft_settings = FsFFTSettings::new(scale).unwrap();
+        let fft_settings = FsFFTSettings::new(scale)?;
 
         let a_pad = self.pad(length);
         let b_pad = multiplier.pad(length);
@@ -352,27 +352,24 @@ impl FsPoly {
         #[cfg(feature = "parallel")]
         {
             if length > 1024 {
-                let mut a_fft_temp = vec![];
-                let mut b_fft_temp = vec![];
-
-                rayon::join(
-                    || a_fft_temp = fft_settings.fft_fr(&a_pad.coeffs, false).unwrap(),
-                    || b_fft_temp = fft_settings.fft_fr(&b_pad.coeffs, false).unwrap(),
+                let (a_fft_temp, b_fft_temp) = rayon::join(
+                    || fft_settings.fft_fr(&a_pad.coeffs, false),
+                    || fft_settings.fft_fr(&b_pad.coeffs, false),
                 );
 
-                a_fft = a_fft_temp;
-                b_fft = b_fft_temp;
+                a_fft = a_fft_temp?;
+                b_fft = b_fft_temp?;
             } else {
-                a_fft = fft_settings.fft_fr(&a_pad.coeffs, false).unwrap();
-                b_fft = fft_settings.fft_fr(&b_pad.coeffs, false).unwrap();
+                a_fft = fft_settings.fft_fr(&a_pad.coeffs, false)?;
+                b_fft = fft_settings.fft_fr(&b_pad.coeffs, false)?;
             }
         }
 
         #[cfg(not(feature = "parallel"))]
         {
             // Convert Poly to values
-            a_fft = fft_settings.fft_fr(&a_pad.coeffs, false).unwrap();
-            b_fft = fft_settings.fft_fr(&b_pad.coeffs, false).unwrap();
+            a_fft = fft_settings.fft_fr(&a_pad.coeffs, false)?;
+            b_fft = fft_settings.fft_fr(&b_pad.coeffs, false)?;
         }
 
         // Multiply two value ranges
@@ -382,11 +379,11 @@ impl FsPoly {
         });
 
         // Convert value range multiplication to a resulting polynomial
-        let ab = fft_settings.fft_fr(&ab_fft, true).unwrap();
+        let ab = fft_settings.fft_fr(&ab_fft, true)?;
         drop(ab_fft);
 
         let mut ret = FsPoly {
-            coeffs: vec![FsFr::zero(); output_len],
+            coeffs: try_vec(FsFr::zero(), output_len)?,
         };
 
         let range = ..output_len.min(length);
@@ -1,27 +1,109 @@
 extern crate alloc;
 
+#[cfg(all(feature = "std", feature = "io"))]
+use alloc::format;
+#[cfg(all(feature = "std", feature = "io"))]
+use alloc::string::String;
 use alloc::vec::Vec;
 
-use kzg::eip_4844::hash_to_bls_field;
-use kzg::{Fr, G1Mul, G2Mul};
+#[cfg(all(feature = "std", feature = "io"))]
+use kzg::artifact_store::artifact_digest;
+use kzg::dev_setup::INSECURE_DEV_SETUP_SEED;
+#[cfg(all(feature = "std", feature = "io"))]
+use kzg::eip_4844::{
+    load_trusted_setup_json_rust, load_trusted_setup_json_string, load_trusted_setup_string,
+};
+#[cfg(all(feature = "std", feature = "io"))]
+use kzg::error::KzgError;
+use kzg::secret::{generate_trusted_setup_with_secret, SecretScalar};
+#[cfg(all(feature = "std", feature = "io"))]
+use kzg::{Compression, G1, G2};
 
-use crate::consts::{G1_GENERATOR, G2_GENERATOR};
+use crate::types::fr::FsFr;
 use crate::types::g1::FsG1;
 use crate::types::g2::FsG2;
+#[cfg(all(feature = "std", feature = "io"))]
+use crate::types::kzg_settings::FsKZGSettings;
 
 pub fn generate_trusted_setup(n: usize, secret: [u8; 32usize]) -> (Vec<FsG1>, Vec<FsG2>) {
-    let s = hash_to_bls_field(&secret);
-    let mut s_pow = Fr::one();
+    generate_trusted_setup_with_secret::<FsFr, _, _>(n, &SecretScalar::from_seed(secret))
+}
+
+/// Setup of the public development secret, for tests and local networks only. Verifying against
+/// it raises a warning, see `kzg::dev_setup`
+pub fn generate_insecure_dev_setup(n: usize) -> (Vec<FsG1>, Vec<FsG2>) {
+    generate_trusted_setup(n, INSECURE_DEV_SETUP_SEED)
+}
+
+/// Load the JSON trusted setup published by the KZG ceremony, e.g. `trusted_setup_4096.json`
+#[cfg(all(feature = "std", feature = "io"))]
+pub fn load_trusted_setup_json(path: &str) -> Result<FsKZGSettings, KzgError> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| std::io::Error::new(e.kind(), format!("Failed to read {}: {}", path, e)))?;
+    Ok(load_trusted_setup_json_rust(&contents)?)
+}
+
+/// Setup points of `bytes`, in the text format of c-kzg or the JSON of the KZG ceremony, after
+/// checking that their SHA-256 digest is the hex `expected_sha256`. Points are returned in the
+/// order of the file
+#[cfg(all(feature = "std", feature = "io"))]
+pub fn parse_pinned_trusted_setup(
+    bytes: &[u8],
+    expected_sha256: &str,
+) -> Result<(Vec<FsG1>, Vec<FsG2>), KzgError> {
+    let expected = hex::decode(expected_sha256.trim().trim_start_matches("0x"))
+        .map_err(|e| KzgError::InvalidArgument(format!("Invalid expected digest: {}", e)))?;
+    let digest = artifact_digest(bytes);
+    if expected != digest {
+        return Err(KzgError::SetupMismatch(format!(
+            "Trusted setup digest {} does not match the expected {}",
+            hex::encode(digest),
+            hex::encode(expected)
+        )));
+    }
+
+    let contents = core::str::from_utf8(bytes)
+        .map_err(|_| KzgError::InvalidFormat(String::from("Trusted setup is not valid UTF-8")))?;
+    let (g1_bytes, g2_bytes) = if contents.trim_start().starts_with('{') {
+        load_trusted_setup_json_string(contents)?
+    } else {
+        load_trusted_setup_string(contents)?
+    };
+    let g1 = FsG1::batch_from_bytes(&g1_bytes, Compression::Compressed)
+        .map_err(|e| e.at_offset("G1 point", 0))?;
+    let g2 = FsG2::batch_from_bytes(&g2_bytes).map_err(|e| e.at_offset("G2 point", 0))?;
+
+    Ok((g1, g2))
+}
 
-    let mut s1 = Vec::with_capacity(n);
-    let mut s2 = Vec::with_capacity(n);
+/// Largest trusted setup `fetch_trusted_setup` downloads
+#[cfg(feature = "http")]
+pub const MAX_TRUSTED_SETUP_DOWNLOAD: u64 = 64 * 1024 * 1024;
 
-    for _ in 0..n {
-        s1.push(G1_GENERATOR.mul(&s_pow));
-        s2.push(G2_GENERATOR.mul(&s_pow));
+/// Download the trusted setup at `url` and parse it, failing unless its SHA-256 digest is the hex
+/// `expected_sha256`, see `parse_pinned_trusted_setup`
+#[cfg(feature = "http")]
+pub fn fetch_trusted_setup(
+    url: &str,
+    expected_sha256: &str,
+) -> Result<(Vec<FsG1>, Vec<FsG2>), KzgError> {
+    use std::io::Read;
 
-        s_pow = s_pow.mul(&s);
+    let response = ureq::get(url)
+        .call()
+        .map_err(|e| KzgError::Other(format!("Failed to download {}: {}", url, e)))?;
+    let mut bytes = Vec::new();
+    response
+        .into_reader()
+        .take(MAX_TRUSTED_SETUP_DOWNLOAD + 1)
+        .read_to_end(&mut bytes)
+        .map_err(|e| std::io::Error::new(e.kind(), format!("Failed to download {}: {}", url, e)))?;
+    if bytes.len() as u64 > MAX_TRUSTED_SETUP_DOWNLOAD {
+        return Err(KzgError::InvalidLength(format!(
+            "Trusted setup at {} is larger than {} bytes",
+            url, MAX_TRUSTED_SETUP_DOWNLOAD
+        )));
     }
 
-    (s1, s2)
+    parse_pinned_trusted_setup(&bytes, expected_sha256)
 }
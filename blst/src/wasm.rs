@@ -0,0 +1,109 @@
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use kzg::eip_4844::{
+    blob_to_kzg_commitment_rust, bytes_to_blob, load_trusted_setup_rust, load_trusted_setup_string,
+    verify_blob_kzg_proof_rust, verify_kzg_proof_rust,
+};
+use kzg::{Fr, G1};
+use wasm_bindgen::prelude::*;
+
+use crate::types::fr::FsFr;
+use crate::types::g1::FsG1;
+use crate::types::kzg_settings::FsKZGSettings;
+
+////////////////////////////// JavaScript bindings //////////////////////////////
+//
+// `wasm-bindgen` exports for browser light clients, enabled by the `wasm` feature. Blobs, points
+// and field elements cross the boundary as `Uint8Array`s in their c-kzg-4844 encodings, and
+// failures as thrown `Error`s. The trusted setup comes from bytes, as a page has no file system:
+// either the contents of `trusted_setup.txt` or the concatenated compressed G1 and G2 points.
+// Build with `--target wasm32-unknown-unknown --no-default-features --features wasm`, leaving out
+// `parallel` and `async`, which need threads.
+
+fn js_error(e: String) -> JsError {
+    JsError::new(&e)
+}
+
+fn deserialize_g1(bytes: &[u8]) -> Result<FsG1, JsError> {
+    FsG1::from_bytes(bytes).map_err(js_error)
+}
+
+fn deserialize_fr(bytes: &[u8]) -> Result<FsFr, JsError> {
+    FsFr::from_bytes(bytes).map_err(js_error)
+}
+
+/// Trusted setup, as `KzgSettings` in JavaScript
+#[wasm_bindgen(js_name = KzgSettings)]
+pub struct WasmKzgSettings(FsKZGSettings);
+
+#[wasm_bindgen(js_class = KzgSettings)]
+impl WasmKzgSettings {
+    /// Load the setup from the contents of a `trusted_setup.txt` file
+    #[wasm_bindgen(js_name = loadTrustedSetup)]
+    pub fn load_trusted_setup(contents: &[u8]) -> Result<WasmKzgSettings, JsError> {
+        let contents = core::str::from_utf8(contents)
+            .map_err(|_| JsError::new("Trusted setup is not valid UTF-8"))?;
+        let (g1_bytes, g2_bytes) = load_trusted_setup_string(contents).map_err(js_error)?;
+
+        Self::load_trusted_setup_from_bytes(&g1_bytes, &g2_bytes)
+    }
+
+    /// Load the setup from concatenated compressed G1 points in Lagrange form and G2 points
+    #[wasm_bindgen(js_name = loadTrustedSetupFromBytes)]
+    pub fn load_trusted_setup_from_bytes(
+        g1_bytes: &[u8],
+        g2_bytes: &[u8],
+    ) -> Result<WasmKzgSettings, JsError> {
+        load_trusted_setup_rust(g1_bytes, g2_bytes)
+            .map(Self)
+            .map_err(js_error)
+    }
+
+    /// Commitment to a blob, 48 bytes
+    #[wasm_bindgen(js_name = blobToKzgCommitment)]
+    pub fn blob_to_kzg_commitment(&self, blob: &[u8]) -> Result<Vec<u8>, JsError> {
+        let blob = bytes_to_blob::<FsFr>(blob).map_err(js_error)?;
+        let commitment = blob_to_kzg_commitment_rust(&blob, &self.0).map_err(js_error)?;
+
+        Ok(commitment.to_bytes().to_vec())
+    }
+
+    /// Check that `proof` opens `commitment` to `y` at `z`
+    #[wasm_bindgen(js_name = verifyKzgProof)]
+    pub fn verify_kzg_proof(
+        &self,
+        commitment: &[u8],
+        z: &[u8],
+        y: &[u8],
+        proof: &[u8],
+    ) -> Result<bool, JsError> {
+        verify_kzg_proof_rust(
+            &deserialize_g1(commitment)?,
+            &deserialize_fr(z)?,
+            &deserialize_fr(y)?,
+            &deserialize_g1(proof)?,
+            &self.0,
+        )
+        .map_err(js_error)
+    }
+
+    /// Check a blob proof against the blob and its commitment
+    #[wasm_bindgen(js_name = verifyBlobKzgProof)]
+    pub fn verify_blob_kzg_proof(
+        &self,
+        blob: &[u8],
+        commitment: &[u8],
+        proof: &[u8],
+    ) -> Result<bool, JsError> {
+        verify_blob_kzg_proof_rust(
+            &bytes_to_blob::<FsFr>(blob).map_err(js_error)?,
+            &deserialize_g1(commitment)?,
+            &deserialize_g1(proof)?,
+            &self.0,
+        )
+        .map_err(js_error)
+    }
+}
@@ -0,0 +1,26 @@
+#[cfg(test)]
+mod tests {
+    use kzg_bench::tests::artifact_store::{
+        artifact_store_setup_test, artifact_store_table_test, fs_artifact_store_test,
+    };
+    use rust_kzg_blst::types::fp::FsFp;
+    use rust_kzg_blst::types::fr::FsFr;
+    use rust_kzg_blst::types::g1::{FsG1, FsG1Affine, FsG1ProjAddAffine};
+    use rust_kzg_blst::types::g2::FsG2;
+    use rust_kzg_blst::utils::generate_trusted_setup;
+
+    #[test]
+    fn fs_artifact_store_test_() {
+        fs_artifact_store_test();
+    }
+
+    #[test]
+    fn artifact_store_setup_test_() {
+        artifact_store_setup_test::<FsFr, FsG1, FsG2>(&generate_trusted_setup);
+    }
+
+    #[test]
+    fn artifact_store_table_test_() {
+        artifact_store_table_test::<FsFr, FsG1, FsFp, FsG1Affine, FsG1ProjAddAffine>();
+    }
+}
@@ -0,0 +1,38 @@
+#[cfg(test)]
+mod tests {
+    use kzg_bench::tests::backend::{
+        backend_bundle_proof_single, kzg_backend_capabilities, kzg_backend_constructors,
+    };
+    use kzg_bench::tests::kzg_proofs::proof_single;
+    use rust_kzg_blst::prelude::FsBackend;
+    use rust_kzg_blst::utils::generate_trusted_setup;
+
+    mod kzg_types {
+        kzg::backend!(rust_kzg_blst::prelude::FsBackend);
+    }
+
+    #[test]
+    fn backend_bundle_proof_single_() {
+        backend_bundle_proof_single::<FsBackend>(&generate_trusted_setup);
+    }
+
+    #[test]
+    fn kzg_backend_constructors_() {
+        kzg_backend_constructors::<FsBackend>(&generate_trusted_setup);
+    }
+
+    #[test]
+    fn kzg_backend_capabilities_() {
+        kzg_backend_capabilities::<FsBackend>();
+    }
+
+    #[test]
+    fn backend_macro_aliases() {
+        use kzg_types::*;
+
+        proof_single::<Fr, G1, G2, Poly, FFTSettings, KZGSettings, G1Fp, G1Affine>(
+            &generate_trusted_setup,
+        );
+        assert!(Fr::from_u64(3).mul(&Fr::one()).equals(&Fr::from_u64(3)));
+    }
+}
@@ -0,0 +1,24 @@
+#[cfg(test)]
+mod tests {
+    use kzg_bench::tests::batch_inverse::{
+        batch_inverse_fp_test, batch_inverse_fr_test, to_affines_batch_test,
+    };
+    use rust_kzg_blst::types::fp::FsFp;
+    use rust_kzg_blst::types::fr::FsFr;
+    use rust_kzg_blst::types::g1::{FsG1, FsG1Affine};
+
+    #[test]
+    fn batch_inverse_fr_test_() {
+        batch_inverse_fr_test::<FsFr>();
+    }
+
+    #[test]
+    fn batch_inverse_fp_test_() {
+        batch_inverse_fp_test::<FsG1, FsFp>();
+    }
+
+    #[test]
+    fn to_affines_batch_test_() {
+        to_affines_batch_test::<FsG1, FsFp, FsG1Affine>();
+    }
+}
@@ -3,10 +3,12 @@ mod tests {
     use kzg::common_utils::log_2_byte;
     use kzg_bench::tests::bls12_381::{
         fr_div_by_zero, fr_div_works, fr_equal_works, fr_from_uint64_works, fr_is_null_works,
-        fr_is_one_works, fr_is_zero_works, fr_negate_works, fr_pow_works, fr_uint64s_roundtrip,
+        fr_is_one_works, fr_is_zero_works, fr_negate_works, fr_pow_works,
+        fr_scalar256_batch_roundtrip, fr_uint64s_roundtrip, g1_batch_serialization_roundtrip,
         g1_identity_is_identity, g1_identity_is_infinity, g1_make_linear_combination,
-        g1_random_linear_combination, log_2_byte_works, p1_mul_works, p1_sub_works,
-        p2_add_or_dbl_works, p2_mul_works, p2_sub_works, pairings_work,
+        g1_random_linear_combination, g2_batch_serialization_roundtrip, hex_format_roundtrip,
+        log_2_byte_works, p1_mul_works, p1_sub_works, p2_add_or_dbl_works, p2_mul_works,
+        p2_sub_works, pairings_work,
     };
 
     use rust_kzg_blst::kzg_proofs::{g1_linear_combination, pairings_verify};
@@ -70,6 +72,11 @@ mod tests {
         fr_uint64s_roundtrip::<FsFr>()
     }
 
+    #[test]
+    fn fr_scalar256_batch_roundtrip_() {
+        fr_scalar256_batch_roundtrip::<FsFr>()
+    }
+
     #[test]
     fn p1_mul_works_() {
         p1_mul_works::<FsFr, FsG1>()
@@ -105,6 +112,21 @@ mod tests {
         g1_identity_is_identity::<FsG1>()
     }
 
+    #[test]
+    fn g1_batch_serialization_roundtrip_() {
+        g1_batch_serialization_roundtrip::<FsFr, FsG1>()
+    }
+
+    #[test]
+    fn g2_batch_serialization_roundtrip_() {
+        g2_batch_serialization_roundtrip::<FsFr, FsG2>()
+    }
+
+    #[test]
+    fn hex_format_roundtrip_() {
+        hex_format_roundtrip::<FsFr, FsG1>()
+    }
+
     #[test]
     fn g1_make_linear_combination_() {
         g1_make_linear_combination::<FsFr, FsG1, FsFp, FsG1Affine>(&g1_linear_combination)
@@ -0,0 +1,31 @@
+#[cfg(test)]
+mod tests {
+    use kzg_bench::tests::bundle::{bundle_inclusion_test, bundle_opening_test};
+    use rust_kzg_blst::eip_4844::load_trusted_setup_filename_rust;
+    use rust_kzg_blst::types::fft_settings::FsFFTSettings;
+    use rust_kzg_blst::types::fp::FsFp;
+    use rust_kzg_blst::types::fr::FsFr;
+    use rust_kzg_blst::types::g1::{FsG1, FsG1Affine};
+    use rust_kzg_blst::types::g2::FsG2;
+    use rust_kzg_blst::types::kzg_settings::FsKZGSettings;
+    use rust_kzg_blst::types::poly::FsPoly;
+
+    #[test]
+    fn bundle_inclusion_test_() {
+        bundle_inclusion_test::<FsG1>();
+    }
+
+    #[test]
+    fn bundle_opening_test_() {
+        bundle_opening_test::<
+            FsFr,
+            FsG1,
+            FsG2,
+            FsPoly,
+            FsFFTSettings,
+            FsKZGSettings,
+            FsFp,
+            FsG1Affine,
+        >(&load_trusted_setup_filename_rust);
+    }
+}
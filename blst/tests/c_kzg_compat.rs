@@ -0,0 +1,171 @@
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use kzg::eip_4844::{load_trusted_setup_string, C_KZG_RET_BADARGS};
+    use kzg_bench::tests::eip_4844::generate_random_blob_bytes;
+    use kzg_bench::tests::utils::get_trusted_setup_path;
+    use rust_kzg_blst::c_kzg_compat::{
+        Blob, Bytes32, Bytes48, Error, KzgCommitment, KzgProof, KzgSettings, BYTES_PER_BLOB,
+        BYTES_PER_G1_POINT, BYTES_PER_G2_POINT,
+    };
+
+    fn load_settings() -> KzgSettings {
+        KzgSettings::load_trusted_setup_file(Path::new(&get_trusted_setup_path())).unwrap()
+    }
+
+    fn random_blob() -> Blob {
+        Blob::new(generate_random_blob_bytes(&mut rand::thread_rng()))
+    }
+
+    #[test]
+    fn blob_proof_roundtrip() {
+        let settings = load_settings();
+        let blobs = [random_blob(), random_blob()];
+
+        let mut commitments = Vec::new();
+        let mut proofs = Vec::new();
+        for blob in &blobs {
+            let commitment = KzgCommitment::blob_to_kzg_commitment(blob, &settings).unwrap();
+            let proof =
+                KzgProof::compute_blob_kzg_proof(blob, &commitment.to_bytes(), &settings).unwrap();
+            assert!(KzgProof::verify_blob_kzg_proof(
+                blob,
+                &commitment.to_bytes(),
+                &proof.to_bytes(),
+                &settings
+            )
+            .unwrap());
+            commitments.push(commitment.to_bytes());
+            proofs.push(proof.to_bytes());
+        }
+
+        assert!(
+            KzgProof::verify_blob_kzg_proof_batch(&blobs, &commitments, &proofs, &settings)
+                .unwrap()
+        );
+
+        proofs.swap(0, 1);
+        assert!(
+            !KzgProof::verify_blob_kzg_proof_batch(&blobs, &commitments, &proofs, &settings)
+                .unwrap()
+        );
+        assert!(matches!(
+            KzgProof::verify_blob_kzg_proof_batch(&blobs, &commitments[..1], &proofs, &settings),
+            Err(Error::MismatchLength(_))
+        ));
+    }
+
+    #[test]
+    fn kzg_proof_roundtrip() {
+        let settings = load_settings();
+        let blob = random_blob();
+        let commitment = KzgCommitment::blob_to_kzg_commitment(&blob, &settings).unwrap();
+
+        let mut z = [0u8; 32];
+        z[31] = 7;
+        let z = Bytes32::new(z);
+        let (proof, y) = KzgProof::compute_kzg_proof(&blob, &z, &settings).unwrap();
+
+        assert!(KzgProof::verify_kzg_proof(
+            &commitment.to_bytes(),
+            &z,
+            &y,
+            &proof.to_bytes(),
+            &settings
+        )
+        .unwrap());
+        assert!(!KzgProof::verify_kzg_proof(
+            &commitment.to_bytes(),
+            &z,
+            &Bytes32::new([0; 32]),
+            &proof.to_bytes(),
+            &settings
+        )
+        .unwrap());
+    }
+
+    #[test]
+    fn invalid_inputs_are_rejected() {
+        let settings = load_settings();
+
+        let blob = Blob::new([0xff; BYTES_PER_BLOB]);
+        assert_eq!(
+            KzgCommitment::blob_to_kzg_commitment(&blob, &settings),
+            Err(Error::CError(C_KZG_RET_BADARGS))
+        );
+
+        let blob = random_blob();
+        assert_eq!(
+            KzgProof::compute_blob_kzg_proof(&blob, &Bytes48::new([0xff; 48]), &settings),
+            Err(Error::CError(C_KZG_RET_BADARGS))
+        );
+
+        assert!(matches!(
+            Bytes48::from_bytes(&[0; 47]),
+            Err(Error::InvalidBytesLength(_))
+        ));
+        assert!(matches!(
+            Bytes32::from_hex("0xzz"),
+            Err(Error::InvalidHexFormat(_))
+        ));
+        assert_eq!(
+            Bytes32::from_hex(&format!("0x{}", "01".repeat(32))).unwrap(),
+            Bytes32::new([1; 32])
+        );
+    }
+
+    #[test]
+    fn hex_display_roundtrip() {
+        let settings = load_settings();
+        let commitment = KzgCommitment::blob_to_kzg_commitment(&random_blob(), &settings).unwrap();
+
+        let hex = commitment.to_string();
+        assert_eq!(hex, commitment.as_hex_string());
+        assert_eq!(format!("{:#x}", commitment), hex);
+        assert_eq!(format!("{:x}", commitment), hex[2..]);
+        assert_eq!(hex.parse::<KzgCommitment>().unwrap(), commitment);
+        assert_eq!(
+            hex[2..].parse::<KzgProof>().unwrap().to_bytes(),
+            commitment.to_bytes()
+        );
+
+        let bytes = Bytes32::new([0xab; 32]);
+        assert_eq!(bytes.to_string(), format!("0x{}", "ab".repeat(32)));
+        assert_eq!(bytes.to_string().parse::<Bytes32>().unwrap(), bytes);
+        assert!(matches!(
+            "0x1234".parse::<KzgProof>(),
+            Err(Error::InvalidBytesLength(_))
+        ));
+        assert!(matches!(
+            "0xzz".parse::<Bytes48>(),
+            Err(Error::InvalidHexFormat(_))
+        ));
+    }
+
+    #[test]
+    fn load_trusted_setup_from_points() {
+        let contents = std::fs::read_to_string(get_trusted_setup_path()).unwrap();
+        let (g1_bytes, g2_bytes) = load_trusted_setup_string(&contents).unwrap();
+        let g1 = g1_bytes
+            .chunks(BYTES_PER_G1_POINT)
+            .map(|chunk| chunk.try_into().unwrap())
+            .collect::<Vec<[u8; BYTES_PER_G1_POINT]>>();
+        let g2 = g2_bytes
+            .chunks(BYTES_PER_G2_POINT)
+            .map(|chunk| chunk.try_into().unwrap())
+            .collect::<Vec<[u8; BYTES_PER_G2_POINT]>>();
+
+        let settings = KzgSettings::load_trusted_setup(&g1, &g2).unwrap();
+        let blob = random_blob();
+        assert_eq!(
+            KzgCommitment::blob_to_kzg_commitment(&blob, &settings).unwrap(),
+            KzgCommitment::blob_to_kzg_commitment(&blob, &load_settings()).unwrap()
+        );
+
+        assert!(matches!(
+            KzgSettings::load_trusted_setup(&g1[..10], &g2),
+            Err(Error::InvalidTrustedSetup(_))
+        ));
+    }
+}
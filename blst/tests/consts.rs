@@ -4,12 +4,15 @@
 #[cfg(test)]
 mod tests {
     use kzg_bench::tests::consts::{
-        expand_roots_is_plausible, new_fft_settings_is_plausible, roots_of_unity_are_plausible,
+        expand_roots_is_plausible, generators_are_plausible, new_fft_settings_is_plausible,
+        root_of_unity_matches_fft_settings, roots_of_unity_are_plausible,
         roots_of_unity_is_the_expected_size, roots_of_unity_out_of_bounds_fails,
     };
     use rust_kzg_blst::consts::SCALE2_ROOT_OF_UNITY;
     use rust_kzg_blst::types::fft_settings::{expand_root_of_unity, FsFFTSettings};
     use rust_kzg_blst::types::fr::FsFr;
+    use rust_kzg_blst::types::g1::FsG1;
+    use rust_kzg_blst::types::g2::FsG2;
 
     // Shared tests
     #[test]
@@ -37,6 +40,16 @@ mod tests {
         new_fft_settings_is_plausible::<FsFr, FsFFTSettings>();
     }
 
+    #[test]
+    fn root_of_unity_matches_fft_settings_() {
+        root_of_unity_matches_fft_settings::<FsFr, FsFFTSettings>();
+    }
+
+    #[test]
+    fn generators_are_plausible_() {
+        generators_are_plausible::<FsG1, FsG2>();
+    }
+
     // Local tests
     // #[test]
     // fn roots_of_unity_repeat_at_stride_() {
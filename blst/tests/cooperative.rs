@@ -0,0 +1,13 @@
+#[cfg(test)]
+mod tests {
+    use kzg_bench::tests::cooperative::yield_handler_test;
+    use rust_kzg_blst::types::fft_settings::FsFFTSettings;
+    use rust_kzg_blst::types::fp::FsFp;
+    use rust_kzg_blst::types::fr::FsFr;
+    use rust_kzg_blst::types::g1::{FsG1, FsG1Affine};
+
+    #[test]
+    fn yield_handler_test_() {
+        yield_handler_test::<FsFr, FsG1, FsFFTSettings, FsFp, FsG1Affine>();
+    }
+}
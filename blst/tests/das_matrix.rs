@@ -0,0 +1,40 @@
+#[cfg(test)]
+mod tests {
+    use kzg_bench::tests::das_matrix::{das_matrix_sample_test, das_matrix_sample_verifier_test};
+    use rust_kzg_blst::types::fft_settings::FsFFTSettings;
+    use rust_kzg_blst::types::fp::FsFp;
+    use rust_kzg_blst::types::fr::FsFr;
+    use rust_kzg_blst::types::g1::{FsG1, FsG1Affine};
+    use rust_kzg_blst::types::g2::FsG2;
+    use rust_kzg_blst::types::kzg_settings::FsKZGSettings;
+    use rust_kzg_blst::types::poly::FsPoly;
+    use rust_kzg_blst::utils::generate_trusted_setup;
+
+    #[test]
+    fn das_matrix_sample_test_() {
+        das_matrix_sample_test::<
+            FsFr,
+            FsG1,
+            FsG2,
+            FsPoly,
+            FsFFTSettings,
+            FsKZGSettings,
+            FsFp,
+            FsG1Affine,
+        >(&generate_trusted_setup);
+    }
+
+    #[test]
+    fn das_matrix_sample_verifier_test_() {
+        das_matrix_sample_verifier_test::<
+            FsFr,
+            FsG1,
+            FsG2,
+            FsPoly,
+            FsFFTSettings,
+            FsKZGSettings,
+            FsFp,
+            FsG1Affine,
+        >(&generate_trusted_setup);
+    }
+}
@@ -0,0 +1,42 @@
+#[cfg(test)]
+mod tests {
+    use kzg_bench::tests::dev_setup::{insecure_dev_setup_paths_test, insecure_dev_setup_test};
+    use rust_kzg_blst::eip_4844::load_trusted_setup_filename_rust;
+    use rust_kzg_blst::types::fft_settings::FsFFTSettings;
+    use rust_kzg_blst::types::fp::FsFp;
+    use rust_kzg_blst::types::fr::FsFr;
+    use rust_kzg_blst::types::g1::{FsG1, FsG1Affine};
+    use rust_kzg_blst::types::g2::FsG2;
+    use rust_kzg_blst::types::kzg_settings::FsKZGSettings;
+    use rust_kzg_blst::types::poly::FsPoly;
+    use rust_kzg_blst::utils::{generate_insecure_dev_setup, generate_trusted_setup};
+
+    #[test]
+    fn insecure_dev_setup_test_() {
+        insecure_dev_setup_test::<
+            FsFr,
+            FsG1,
+            FsG2,
+            FsPoly,
+            FsFFTSettings,
+            FsKZGSettings,
+            FsFp,
+            FsG1Affine,
+        >(
+            &generate_trusted_setup,
+            &generate_insecure_dev_setup,
+            &load_trusted_setup_filename_rust,
+        );
+        // The flag and the warning handler are process-wide, so the paths run in the same test
+        insecure_dev_setup_paths_test::<
+            FsFr,
+            FsG1,
+            FsG2,
+            FsPoly,
+            FsFFTSettings,
+            FsKZGSettings,
+            FsFp,
+            FsG1Affine,
+        >(&generate_trusted_setup, &generate_insecure_dev_setup);
+    }
+}
@@ -0,0 +1,24 @@
+#[cfg(test)]
+mod tests {
+    use kzg_bench::tests::diagnostics::{memory_usage_test, precompute_warning_test};
+    use rust_kzg_blst::types::fft_settings::FsFFTSettings;
+    use rust_kzg_blst::types::fp::FsFp;
+    use rust_kzg_blst::types::fr::FsFr;
+    use rust_kzg_blst::types::g1::{FsG1, FsG1Affine};
+    use rust_kzg_blst::types::g2::FsG2;
+    use rust_kzg_blst::types::kzg_settings::FsKZGSettings;
+    use rust_kzg_blst::types::poly::FsPoly;
+    use rust_kzg_blst::utils::generate_trusted_setup;
+
+    #[test]
+    fn precompute_warning_test_() {
+        precompute_warning_test::<FsFr, FsG1, FsFp, FsG1Affine>();
+    }
+
+    #[test]
+    fn memory_usage_test_() {
+        memory_usage_test::<FsFr, FsG1, FsG2, FsPoly, FsFFTSettings, FsKZGSettings, FsFp, FsG1Affine>(
+            &generate_trusted_setup,
+        );
+    }
+}
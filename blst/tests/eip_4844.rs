@@ -1,7 +1,7 @@
 #[cfg(test)]
 mod tests {
     use kzg::eip_4844::{
-        blob_to_kzg_commitment_rust, blob_to_polynomial, bytes_to_blob,
+        blob_to_kzg_commitment_rust, blob_to_polynomial, bytes_to_blob, bytes_to_blobs,
         compute_blob_kzg_proof_rust, compute_kzg_proof_rust, compute_powers,
         evaluate_polynomial_in_evaluation_form, verify_blob_kzg_proof_batch_rust,
         verify_blob_kzg_proof_rust, verify_kzg_proof_rust,
@@ -9,7 +9,8 @@ mod tests {
     use kzg::Fr;
 
     use kzg_bench::tests::eip_4844::{
-        blob_to_kzg_commitment_test, bytes_to_bls_field_test,
+        batched_hashes_test, blob_from_chunks_test, blob_to_kzg_commitment_test,
+        bytes_to_blobs_test, bytes_to_bls_field_test,
         compute_and_verify_blob_kzg_proof_fails_with_incorrect_proof_test,
         compute_and_verify_blob_kzg_proof_test,
         compute_and_verify_kzg_proof_fails_with_incorrect_proof_test,
@@ -18,11 +19,15 @@ mod tests {
         compute_kzg_proof_incorrect_blob_length_test,
         compute_kzg_proof_incorrect_commitments_len_test,
         compute_kzg_proof_incorrect_poly_length_test, compute_kzg_proof_incorrect_proofs_len_test,
-        compute_kzg_proof_test, compute_powers_test, test_vectors_blob_to_kzg_commitment,
-        test_vectors_compute_blob_kzg_proof, test_vectors_compute_kzg_proof,
-        test_vectors_verify_blob_kzg_proof, test_vectors_verify_blob_kzg_proof_batch,
-        test_vectors_verify_kzg_proof, validate_batched_input_test,
+        compute_kzg_proof_test, compute_powers_test, load_trusted_setup_json_test,
+        precomputation_table_manager_concurrency_test, shared_settings_concurrent_use_test,
+        test_vectors_blob_to_kzg_commitment, test_vectors_compute_blob_kzg_proof,
+        test_vectors_compute_kzg_proof, test_vectors_verify_blob_kzg_proof,
+        test_vectors_verify_blob_kzg_proof_batch, test_vectors_verify_kzg_proof,
+        validate_batched_input_test, verify_batch_openings_test,
+        verify_blob_kzg_proof_batch_with_domain_test,
         verify_kzg_proof_batch_fails_with_incorrect_proof_test, verify_kzg_proof_batch_test,
+        verify_with_precomputed_challenge_test,
     };
     use rust_kzg_blst::consts::SCALE2_ROOT_OF_UNITY;
     use rust_kzg_blst::eip_4844::load_trusted_setup_filename_rust;
@@ -33,12 +38,23 @@ mod tests {
         fft_settings::FsFFTSettings, fr::FsFr, g1::FsG1, g2::FsG2, kzg_settings::FsKZGSettings,
         poly::FsPoly,
     };
+    use rust_kzg_blst::utils::load_trusted_setup_json;
 
     #[test]
     pub fn bytes_to_bls_field_test_() {
         bytes_to_bls_field_test::<FsFr>();
     }
 
+    #[test]
+    pub fn bytes_to_blobs_test_() {
+        bytes_to_blobs_test::<FsFr>(&bytes_to_blobs);
+    }
+
+    #[test]
+    pub fn blob_from_chunks_test_() {
+        blob_from_chunks_test::<FsFr>(&bytes_to_blob);
+    }
+
     #[test]
     pub fn compute_powers_test_() {
         compute_powers_test::<FsFr>(&compute_powers);
@@ -226,6 +242,69 @@ mod tests {
         );
     }
 
+    #[test]
+    pub fn verify_blob_kzg_proof_batch_with_domain_test_() {
+        verify_blob_kzg_proof_batch_with_domain_test::<
+            FsFr,
+            FsG1,
+            FsG2,
+            FsPoly,
+            FsFFTSettings,
+            FsKZGSettings,
+            FsFp,
+            FsG1Affine,
+        >(&load_trusted_setup_filename_rust);
+    }
+
+    #[test]
+    pub fn load_trusted_setup_json_test_() {
+        load_trusted_setup_json_test::<
+            FsFr,
+            FsG1,
+            FsG2,
+            FsPoly,
+            FsFFTSettings,
+            FsKZGSettings,
+            FsFp,
+            FsG1Affine,
+        >(&load_trusted_setup_filename_rust, &|path| {
+            load_trusted_setup_json(path).map_err(String::from)
+        });
+    }
+
+    #[test]
+    pub fn batched_hashes_test_() {
+        batched_hashes_test::<FsFr, FsG1>();
+    }
+
+    #[test]
+    pub fn verify_with_precomputed_challenge_test_() {
+        verify_with_precomputed_challenge_test::<
+            FsFr,
+            FsG1,
+            FsG2,
+            FsPoly,
+            FsFFTSettings,
+            FsKZGSettings,
+            FsFp,
+            FsG1Affine,
+        >(&load_trusted_setup_filename_rust);
+    }
+
+    #[test]
+    pub fn verify_batch_openings_test_() {
+        verify_batch_openings_test::<
+            FsFr,
+            FsG1,
+            FsG2,
+            FsPoly,
+            FsFFTSettings,
+            FsKZGSettings,
+            FsFp,
+            FsG1Affine,
+        >(&load_trusted_setup_filename_rust);
+    }
+
     #[test]
     pub fn test_vectors_blob_to_kzg_commitment_() {
         test_vectors_blob_to_kzg_commitment::<
@@ -419,4 +498,23 @@ mod tests {
             &load_trusted_setup_filename_rust,
         )
     }
+
+    #[test]
+    pub fn precomputation_table_manager_concurrency_test_() {
+        precomputation_table_manager_concurrency_test::<FsFr, FsG1, FsFp, FsG1Affine>();
+    }
+
+    #[test]
+    pub fn shared_settings_concurrent_use_test_() {
+        shared_settings_concurrent_use_test::<
+            FsFr,
+            FsG1,
+            FsG2,
+            FsPoly,
+            FsFFTSettings,
+            FsKZGSettings,
+            FsFp,
+            FsG1Affine,
+        >(&load_trusted_setup_filename_rust);
+    }
 }
@@ -0,0 +1,16 @@
+#[cfg(test)]
+mod tests {
+    use kzg_bench::tests::envelope::{envelope_rejects_malformed_test, envelope_roundtrip_test};
+    use rust_kzg_blst::types::fr::FsFr;
+    use rust_kzg_blst::types::g1::FsG1;
+
+    #[test]
+    fn envelope_roundtrip_test_() {
+        envelope_roundtrip_test::<FsFr, FsG1>();
+    }
+
+    #[test]
+    fn envelope_rejects_malformed_test_() {
+        envelope_rejects_malformed_test::<FsFr, FsG1>();
+    }
+}
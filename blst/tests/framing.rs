@@ -0,0 +1,13 @@
+#[cfg(test)]
+mod tests {
+    use kzg_bench::tests::framing::framed_setup_roundtrip_test;
+    use rust_kzg_blst::types::fr::FsFr;
+    use rust_kzg_blst::types::g1::FsG1;
+    use rust_kzg_blst::types::g2::FsG2;
+    use rust_kzg_blst::utils::generate_trusted_setup;
+
+    #[test]
+    fn framed_setup_roundtrip_test_() {
+        framed_setup_roundtrip_test::<FsFr, FsG1, FsG2>(&generate_trusted_setup);
+    }
+}
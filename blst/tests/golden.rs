@@ -0,0 +1,38 @@
+#[cfg(test)]
+mod tests {
+    use kzg_bench::tests::golden::{
+        golden_table_file_test, golden_vectors_test, seeded_blob_is_deterministic_test,
+    };
+    use rust_kzg_blst::eip_4844::load_trusted_setup_filename_rust;
+    use rust_kzg_blst::types::fft_settings::FsFFTSettings;
+    use rust_kzg_blst::types::fp::FsFp;
+    use rust_kzg_blst::types::fr::FsFr;
+    use rust_kzg_blst::types::g1::{FsG1, FsG1Affine};
+    use rust_kzg_blst::types::g2::FsG2;
+    use rust_kzg_blst::types::kzg_settings::FsKZGSettings;
+    use rust_kzg_blst::types::poly::FsPoly;
+
+    #[test]
+    fn seeded_blob_is_deterministic_test_() {
+        seeded_blob_is_deterministic_test();
+    }
+
+    #[test]
+    fn golden_vectors_test_() {
+        golden_vectors_test::<
+            FsFr,
+            FsG1,
+            FsG2,
+            FsPoly,
+            FsFFTSettings,
+            FsKZGSettings,
+            FsFp,
+            FsG1Affine,
+        >(&load_trusted_setup_filename_rust);
+    }
+
+    #[test]
+    fn golden_table_file_test_() {
+        golden_table_file_test::<FsFr, FsG1, FsFp, FsG1Affine>();
+    }
+}
@@ -0,0 +1,18 @@
+#[cfg(test)]
+mod tests {
+    use kzg_bench::tests::io_utils::{batch_writer_test, secrets_file_roundtrip_test};
+    use rust_kzg_blst::types::fr::FsFr;
+    use rust_kzg_blst::types::g1::FsG1;
+    use rust_kzg_blst::types::g2::FsG2;
+    use rust_kzg_blst::utils::generate_trusted_setup;
+
+    #[test]
+    fn secrets_file_roundtrip_test_() {
+        secrets_file_roundtrip_test::<FsFr, FsG1, FsG2>(&generate_trusted_setup);
+    }
+
+    #[test]
+    fn batch_writer_test_() {
+        batch_writer_test::<FsFr, FsG1>();
+    }
+}
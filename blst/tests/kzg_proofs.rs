@@ -4,11 +4,14 @@ mod tests {
         blst_final_exp, blst_fp12, blst_fp12_mul, blst_miller_loop, blst_p1_affine, blst_p1_cneg,
         blst_p1_to_affine, blst_p2_affine, blst_p2_to_affine, Pairing,
     };
+    use kzg::secret::generate_trusted_setup_from_rng;
     use kzg::G1;
     use kzg_bench::tests::kzg_proofs::{
         commit_to_nil_poly, commit_to_too_long_poly_returns_err, proof_multi, proof_single,
+        setup_from_bytes, truncate_loaded_setup, truncate_setup, trusted_setup_from_rng_test,
     };
 
+    use rust_kzg_blst::eip_4844::load_trusted_setup_filename_rust;
     use rust_kzg_blst::types::fft_settings::FsFFTSettings;
     use rust_kzg_blst::types::fp::FsFp;
     use rust_kzg_blst::types::fr::FsFr;
@@ -25,6 +28,13 @@ mod tests {
         );
     }
 
+    #[test]
+    pub fn test_trusted_setup_from_rng() {
+        trusted_setup_from_rng_test::<FsFr, FsG1, FsG2>(&|rng, n| {
+            generate_trusted_setup_from_rng::<FsFr, _, _, _>(rng, n)
+        });
+    }
+
     #[test]
     pub fn test_commit_to_nil_poly() {
         commit_to_nil_poly::<
@@ -53,6 +63,34 @@ mod tests {
         >(&generate_trusted_setup);
     }
 
+    #[test]
+    pub fn test_truncate_setup() {
+        truncate_setup::<FsFr, FsG1, FsG2, FsPoly, FsFFTSettings, FsKZGSettings, FsFp, FsG1Affine>(
+            &generate_trusted_setup,
+        );
+    }
+
+    #[test]
+    pub fn test_truncate_loaded_setup() {
+        truncate_loaded_setup::<
+            FsFr,
+            FsG1,
+            FsG2,
+            FsPoly,
+            FsFFTSettings,
+            FsKZGSettings,
+            FsFp,
+            FsG1Affine,
+        >(&load_trusted_setup_filename_rust);
+    }
+
+    #[test]
+    pub fn test_setup_from_bytes() {
+        setup_from_bytes::<FsFr, FsG1, FsG2, FsPoly, FsFFTSettings, FsKZGSettings, FsFp, FsG1Affine>(
+            &generate_trusted_setup,
+        );
+    }
+
     #[test]
     pub fn test_proof_multi() {
         proof_multi::<FsFr, FsG1, FsG2, FsPoly, FsFFTSettings, FsKZGSettings, FsFp, FsG1Affine>(
@@ -0,0 +1,10 @@
+#[cfg(test)]
+mod tests {
+    use kzg_bench::tests::lagrange::lagrange_poly_test;
+    use rust_kzg_blst::prelude::FsBackend;
+
+    #[test]
+    fn lagrange_poly_test_() {
+        lagrange_poly_test::<FsBackend>();
+    }
+}
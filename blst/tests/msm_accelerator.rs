@@ -0,0 +1,12 @@
+#[cfg(all(test, feature = "gpu"))]
+mod tests {
+    use kzg_bench::tests::msm::accelerator::msm_accelerator_offload_and_fallback;
+    use rust_kzg_blst::types::fp::FsFp;
+    use rust_kzg_blst::types::fr::FsFr;
+    use rust_kzg_blst::types::g1::{FsG1, FsG1Affine, FsG1ProjAddAffine};
+
+    #[test]
+    fn msm_accelerator_offload_and_fallback_() {
+        msm_accelerator_offload_and_fallback::<FsFr, FsG1, FsFp, FsG1Affine, FsG1ProjAddAffine>();
+    }
+}
@@ -0,0 +1,12 @@
+#[cfg(test)]
+mod tests {
+    use kzg_bench::tests::msm::bounded::bounded_msm_matches_msm;
+    use rust_kzg_blst::types::fp::FsFp;
+    use rust_kzg_blst::types::fr::FsFr;
+    use rust_kzg_blst::types::g1::{FsG1, FsG1Affine, FsG1ProjAddAffine};
+
+    #[test]
+    fn bounded_msm_matches_msm_() {
+        bounded_msm_matches_msm::<FsFr, FsG1, FsFp, FsG1Affine, FsG1ProjAddAffine>();
+    }
+}
@@ -0,0 +1,12 @@
+#[cfg(test)]
+mod tests {
+    use kzg_bench::tests::msm::bucket_integration::bucket_integration_matches_naive;
+    use rust_kzg_blst::types::fp::FsFp;
+    use rust_kzg_blst::types::fr::FsFr;
+    use rust_kzg_blst::types::g1::{FsG1, FsG1Affine};
+
+    #[test]
+    fn bucket_integration_matches_naive_() {
+        bucket_integration_matches_naive::<FsFr, FsG1, FsFp, FsG1Affine>();
+    }
+}
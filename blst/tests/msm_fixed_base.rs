@@ -0,0 +1,13 @@
+#[cfg(test)]
+mod tests {
+    use kzg_bench::tests::msm::fixed_base::fixed_base_msm_matches_naive;
+    use rust_kzg_blst::types::fp::FsFp;
+    use rust_kzg_blst::types::fr::FsFr;
+    use rust_kzg_blst::types::g1::{FsG1, FsG1Affine, FsG1ProjAddAffine};
+    use rust_kzg_blst::types::poly::FsPoly;
+
+    #[test]
+    fn fixed_base_msm_matches_naive_() {
+        fixed_base_msm_matches_naive::<FsFr, FsG1, FsFp, FsG1Affine, FsG1ProjAddAffine, FsPoly>();
+    }
+}
@@ -0,0 +1,11 @@
+#[cfg(test)]
+mod tests {
+    use kzg_bench::tests::msm::msm_g2::msm_g2_matches_naive;
+    use rust_kzg_blst::types::fr::FsFr;
+    use rust_kzg_blst::types::g2::FsG2;
+
+    #[test]
+    fn msm_g2_matches_naive_() {
+        msm_g2_matches_naive::<FsFr, FsG2>();
+    }
+}
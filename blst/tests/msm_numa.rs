@@ -0,0 +1,43 @@
+#[cfg(all(test, feature = "numa"))]
+mod tests {
+    use kzg::msm::msm_impls::msm;
+    use kzg::msm::numa::{numa_msm, numa_nodes, set_numa_msm};
+    use kzg::msm::precompute::precompute;
+    use kzg::{Fr, G1};
+    use rust_kzg_blst::types::fp::FsFp;
+    use rust_kzg_blst::types::fr::FsFr;
+    use rust_kzg_blst::types::g1::{FsG1, FsG1Affine, FsG1ProjAddAffine};
+
+    #[test]
+    fn numa_msm_matches_reference() {
+        // Every core belongs to at most one node
+        let mut cpus = numa_nodes()
+            .iter()
+            .flat_map(|node| node.cpus.iter().copied())
+            .collect::<Vec<_>>();
+        let len = cpus.len();
+        cpus.sort_unstable();
+        cpus.dedup();
+        assert_eq!(cpus.len(), len);
+
+        let npoints = 1000;
+        let points = (0..npoints).map(|_| FsG1::rand()).collect::<Vec<_>>();
+        let scalars = (0..npoints).map(|_| FsFr::rand()).collect::<Vec<_>>();
+        let table = precompute::<FsFr, FsG1, FsFp, FsG1Affine>(&points)
+            .unwrap()
+            .unwrap();
+        let scalars_256 = scalars.iter().map(FsFr::to_scalar).collect::<Vec<_>>();
+        let expected = msm::<FsG1, FsFp, FsG1Affine, FsG1ProjAddAffine, FsFr>(
+            &points, &scalars, npoints, None,
+        );
+
+        assert!(!numa_msm());
+        set_numa_msm(true);
+        assert!(numa_msm());
+        assert!(table.multiply_parallel(&scalars_256).equals(&expected));
+        let batch = table.multiply_batch(&[&scalars_256, &scalars_256[..10]]);
+        assert!(batch[0].equals(&expected));
+        assert!(batch[1].equals(&table.multiply_sequential(&scalars_256[..10])));
+        set_numa_msm(false);
+    }
+}
@@ -0,0 +1,12 @@
+#[cfg(test)]
+mod tests {
+    use kzg_bench::tests::msm::progress::precompute_progress_test;
+    use rust_kzg_blst::types::fp::FsFp;
+    use rust_kzg_blst::types::fr::FsFr;
+    use rust_kzg_blst::types::g1::{FsG1, FsG1Affine, FsG1ProjAddAffine};
+
+    #[test]
+    fn precompute_progress_test_() {
+        precompute_progress_test::<FsFr, FsG1, FsFp, FsG1Affine, FsG1ProjAddAffine>();
+    }
+}
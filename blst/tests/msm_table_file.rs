@@ -0,0 +1,24 @@
+#[cfg(test)]
+mod tests {
+    use kzg_bench::tests::msm::table_file::{
+        table_file_known_answer, table_file_roundtrip, table_generate_to_file,
+    };
+    use rust_kzg_blst::types::fp::FsFp;
+    use rust_kzg_blst::types::fr::FsFr;
+    use rust_kzg_blst::types::g1::{FsG1, FsG1Affine, FsG1ProjAddAffine};
+
+    #[test]
+    fn table_file_roundtrip_() {
+        table_file_roundtrip::<FsFr, FsG1, FsFp, FsG1Affine, FsG1ProjAddAffine>();
+    }
+
+    #[test]
+    fn table_file_known_answer_() {
+        table_file_known_answer::<FsFr, FsG1, FsFp, FsG1Affine, FsG1ProjAddAffine>();
+    }
+
+    #[test]
+    fn table_generate_to_file_() {
+        table_generate_to_file::<FsFr, FsG1, FsFp, FsG1Affine>();
+    }
+}
@@ -0,0 +1,22 @@
+#[cfg(test)]
+mod tests {
+    use kzg_bench::tests::msm::tuner::{table_with_window, tune_window, tuning_profile_roundtrip};
+    use rust_kzg_blst::types::fp::FsFp;
+    use rust_kzg_blst::types::fr::FsFr;
+    use rust_kzg_blst::types::g1::{FsG1, FsG1Affine, FsG1ProjAddAffine};
+
+    #[test]
+    fn table_with_window_() {
+        table_with_window::<FsFr, FsG1, FsFp, FsG1Affine, FsG1ProjAddAffine>();
+    }
+
+    #[test]
+    fn tune_window_() {
+        tune_window::<FsFr, FsG1, FsFp, FsG1Affine>();
+    }
+
+    #[test]
+    fn tuning_profile_roundtrip_() {
+        tuning_profile_roundtrip();
+    }
+}
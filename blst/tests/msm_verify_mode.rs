@@ -0,0 +1,12 @@
+#[cfg(all(test, feature = "msm-verify"))]
+mod tests {
+    use kzg_bench::tests::msm::verify_mode::msm_verify_mode_test;
+    use rust_kzg_blst::types::fp::FsFp;
+    use rust_kzg_blst::types::fr::FsFr;
+    use rust_kzg_blst::types::g1::{FsG1, FsG1Affine, FsG1ProjAddAffine};
+
+    #[test]
+    fn msm_verify_mode_test_() {
+        msm_verify_mode_test::<FsFr, FsG1, FsFp, FsG1Affine, FsG1ProjAddAffine>();
+    }
+}
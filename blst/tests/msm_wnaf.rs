@@ -0,0 +1,17 @@
+#[cfg(test)]
+mod tests {
+    use kzg_bench::tests::msm::wnaf::{wnaf_digits_recompose, wnaf_msm_matches_msm};
+    use rust_kzg_blst::types::fp::FsFp;
+    use rust_kzg_blst::types::fr::FsFr;
+    use rust_kzg_blst::types::g1::{FsG1, FsG1Affine, FsG1ProjAddAffine};
+
+    #[test]
+    fn wnaf_digits_recompose_() {
+        wnaf_digits_recompose::<FsFr>();
+    }
+
+    #[test]
+    fn wnaf_msm_matches_msm_() {
+        wnaf_msm_matches_msm::<FsFr, FsG1, FsFp, FsG1Affine, FsG1ProjAddAffine>();
+    }
+}
@@ -0,0 +1,31 @@
+#[cfg(all(test, feature = "async"))]
+mod tests {
+    use kzg_bench::tests::nonblocking::{blocking_pool_test, nonblocking_eip_4844_test};
+    use rust_kzg_blst::eip_4844::load_trusted_setup_filename_rust;
+    use rust_kzg_blst::types::fft_settings::FsFFTSettings;
+    use rust_kzg_blst::types::fp::FsFp;
+    use rust_kzg_blst::types::fr::FsFr;
+    use rust_kzg_blst::types::g1::{FsG1, FsG1Affine};
+    use rust_kzg_blst::types::g2::FsG2;
+    use rust_kzg_blst::types::kzg_settings::FsKZGSettings;
+    use rust_kzg_blst::types::poly::FsPoly;
+
+    #[test]
+    fn blocking_pool_test_() {
+        blocking_pool_test();
+    }
+
+    #[test]
+    fn nonblocking_eip_4844_test_() {
+        nonblocking_eip_4844_test::<
+            FsFr,
+            FsG1,
+            FsG2,
+            FsPoly,
+            FsFFTSettings,
+            FsKZGSettings,
+            FsFp,
+            FsG1Affine,
+        >(load_trusted_setup_filename_rust);
+    }
+}
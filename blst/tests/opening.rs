@@ -0,0 +1,31 @@
+#[cfg(test)]
+mod tests {
+    use kzg_bench::tests::opening::{divide_by_linear_test, quotient_commitment_test};
+    use rust_kzg_blst::types::fft_settings::FsFFTSettings;
+    use rust_kzg_blst::types::fp::FsFp;
+    use rust_kzg_blst::types::fr::FsFr;
+    use rust_kzg_blst::types::g1::{FsG1, FsG1Affine};
+    use rust_kzg_blst::types::g2::FsG2;
+    use rust_kzg_blst::types::kzg_settings::FsKZGSettings;
+    use rust_kzg_blst::types::poly::FsPoly;
+    use rust_kzg_blst::utils::generate_trusted_setup;
+
+    #[test]
+    fn divide_by_linear_test_() {
+        divide_by_linear_test::<FsFr, FsPoly>();
+    }
+
+    #[test]
+    fn quotient_commitment_test_() {
+        quotient_commitment_test::<
+            FsFr,
+            FsG1,
+            FsG2,
+            FsPoly,
+            FsFFTSettings,
+            FsKZGSettings,
+            FsFp,
+            FsG1Affine,
+        >(&generate_trusted_setup);
+    }
+}
@@ -0,0 +1,13 @@
+#[cfg(test)]
+mod tests {
+    use kzg_bench::tests::parallelism::parallelism_modes_test;
+    use rust_kzg_blst::types::fft_settings::FsFFTSettings;
+    use rust_kzg_blst::types::fp::FsFp;
+    use rust_kzg_blst::types::fr::FsFr;
+    use rust_kzg_blst::types::g1::{FsG1, FsG1Affine, FsG1ProjAddAffine};
+
+    #[test]
+    fn parallelism_modes_test_() {
+        parallelism_modes_test::<FsFr, FsG1, FsFp, FsG1Affine, FsG1ProjAddAffine, FsFFTSettings>();
+    }
+}
@@ -0,0 +1,15 @@
+#[cfg(test)]
+mod tests {
+    use kzg_bench::tests::progress::progress_sink_test;
+    use rust_kzg_blst::eip_4844::load_trusted_setup_filename_rust;
+    use rust_kzg_blst::types::fp::FsFp;
+    use rust_kzg_blst::types::fr::FsFr;
+    use rust_kzg_blst::types::g1::{FsG1, FsG1Affine, FsG1ProjAddAffine};
+
+    #[test]
+    fn progress_sink_test_() {
+        progress_sink_test::<FsFr, FsG1, FsFp, FsG1Affine, FsG1ProjAddAffine, _>(
+            &load_trusted_setup_filename_rust,
+        );
+    }
+}
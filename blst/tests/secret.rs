@@ -0,0 +1,13 @@
+#[cfg(test)]
+mod tests {
+    use kzg_bench::tests::secret::secret_scalar_setup_test;
+    use rust_kzg_blst::types::fr::FsFr;
+    use rust_kzg_blst::types::g1::FsG1;
+    use rust_kzg_blst::types::g2::FsG2;
+    use rust_kzg_blst::utils::generate_trusted_setup;
+
+    #[test]
+    fn secret_scalar_setup_test_() {
+        secret_scalar_setup_test::<FsFr, FsG1, FsG2>(&generate_trusted_setup);
+    }
+}
@@ -0,0 +1,19 @@
+#[cfg(test)]
+mod tests {
+    use kzg_bench::tests::setup_convert::{convert_ptau_setup_test, convert_text_setup_test};
+    use rust_kzg_blst::types::fft_settings::FsFFTSettings;
+    use rust_kzg_blst::types::fr::FsFr;
+    use rust_kzg_blst::types::g1::FsG1;
+    use rust_kzg_blst::types::g2::FsG2;
+    use rust_kzg_blst::utils::generate_trusted_setup;
+
+    #[test]
+    fn convert_text_setup_test_() {
+        convert_text_setup_test::<FsFr, FsG1, FsG2, FsFFTSettings>();
+    }
+
+    #[test]
+    fn convert_ptau_setup_test_() {
+        convert_ptau_setup_test::<FsFr, FsG1, FsG2, FsFFTSettings>(&generate_trusted_setup);
+    }
+}
@@ -0,0 +1,35 @@
+#[cfg(test)]
+mod tests {
+    use kzg::artifact_store::artifact_digest;
+    use kzg::G1;
+    use kzg_bench::tests::setup_file::setup_file_roundtrip_test;
+    use kzg_bench::tests::utils::get_trusted_setup_path;
+    use rust_kzg_blst::types::fr::FsFr;
+    use rust_kzg_blst::types::g1::FsG1;
+    use rust_kzg_blst::types::g2::FsG2;
+    use rust_kzg_blst::utils::{generate_trusted_setup, parse_pinned_trusted_setup};
+
+    #[test]
+    fn setup_file_roundtrip_test_() {
+        setup_file_roundtrip_test::<FsFr, FsG1, FsG2>(&generate_trusted_setup);
+    }
+
+    #[test]
+    fn pinned_trusted_setup_test() {
+        let bytes = std::fs::read(get_trusted_setup_path()).unwrap();
+        let digest = hex::encode(artifact_digest(&bytes));
+
+        let (g1, g2) = parse_pinned_trusted_setup(&bytes, &digest).unwrap();
+        assert_eq!((g1.len(), g2.len()), (4096, 65));
+        let (prefixed_g1, _) =
+            parse_pinned_trusted_setup(&bytes, &format!("0x{}", digest)).unwrap();
+        assert!(prefixed_g1[17].equals(&g1[17]));
+
+        let mut corrupted = bytes.clone();
+        let last = corrupted.len() - 2;
+        corrupted[last] ^= 1;
+        assert!(parse_pinned_trusted_setup(&corrupted, &digest).is_err());
+        assert!(parse_pinned_trusted_setup(&bytes, &digest[2..]).is_err());
+        assert!(parse_pinned_trusted_setup(&bytes, "not hex").is_err());
+    }
+}
@@ -0,0 +1,40 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use kzg_bench::soak::{run_soak, SoakConfig};
+    use rust_kzg_blst::eip_4844::load_trusted_setup_filename_rust;
+    use rust_kzg_blst::types::fft_settings::FsFFTSettings;
+    use rust_kzg_blst::types::fp::FsFp;
+    use rust_kzg_blst::types::fr::FsFr;
+    use rust_kzg_blst::types::g1::{FsG1, FsG1Affine};
+    use rust_kzg_blst::types::g2::FsG2;
+    use rust_kzg_blst::types::kzg_settings::FsKZGSettings;
+    use rust_kzg_blst::types::poly::FsPoly;
+
+    /// Short run of the soak test, see `examples/soak.rs` for long runs
+    #[test]
+    fn soak_smoke_test() {
+        let config = SoakConfig {
+            threads: 4,
+            duration: Duration::from_secs(60),
+            operations_per_thread: 5,
+            reference_blobs: 2,
+        };
+
+        let report = run_soak::<
+            FsFr,
+            FsG1,
+            FsG2,
+            FsPoly,
+            FsFFTSettings,
+            FsKZGSettings,
+            FsPoly,
+            FsFp,
+            FsG1Affine,
+        >(&config, &load_trusted_setup_filename_rust)
+        .unwrap();
+        assert!(report.total_operations() > 0);
+        assert!(report.total_operations() <= 20);
+    }
+}
@@ -0,0 +1,63 @@
+#[cfg(all(test, feature = "parallel"))]
+mod tests {
+    use kzg::error::KzgError;
+    use kzg::msm::msm_impls::msm;
+    use kzg::msm::precompute::precompute;
+    use kzg::msm::thread_pool::da_pool;
+    use kzg::parallelism::{install, max_threads, set_rayon_pool, ThreadPoolConfig};
+    use kzg::{Fr, G1};
+    use rust_kzg_blst::types::fp::FsFp;
+    use rust_kzg_blst::types::fr::FsFr;
+    use rust_kzg_blst::types::g1::{FsG1, FsG1Affine, FsG1ProjAddAffine};
+
+    #[test]
+    fn thread_pool_config_install() {
+        let npoints = 300;
+        let points = (0..npoints).map(|_| FsG1::rand()).collect::<Vec<_>>();
+        let scalars = (0..npoints).map(|_| FsFr::rand()).collect::<Vec<_>>();
+        let table = precompute::<FsFr, FsG1, FsFp, FsG1Affine>(&points).unwrap();
+        let run = || {
+            [None, table.as_ref()].map(|table| {
+                msm::<FsG1, FsFp, FsG1Affine, FsG1ProjAddAffine, FsFr>(
+                    &points, &scalars, npoints, table,
+                )
+            })
+        };
+        let expected = run();
+
+        assert!(matches!(
+            ThreadPoolConfig::with_threads(0).install(),
+            Err(KzgError::InvalidArgument(_))
+        ));
+
+        let config = ThreadPoolConfig {
+            thread_name: Some(String::from("kzg-test")),
+            stack_size: Some(4 << 20),
+            ..ThreadPoolConfig::with_threads(3)
+        };
+        config.install().unwrap();
+        assert_eq!(da_pool().max_count(), 3);
+        assert_eq!(max_threads(), 3);
+        let name = install(|| std::thread::current().name().map(String::from));
+        assert!(name.unwrap().starts_with("kzg-test-"));
+
+        // MSMs give the same results on the installed pools
+        let actual = run();
+        assert!(actual.iter().zip(&expected).all(|(a, b)| a.equals(b)));
+
+        #[cfg(feature = "pin-threads")]
+        {
+            let config = ThreadPoolConfig {
+                pin_threads: true,
+                ..ThreadPoolConfig::with_threads(2)
+            };
+            config.install().unwrap();
+            assert_eq!(da_pool().max_count(), 2);
+            let actual = run();
+            assert!(actual.iter().zip(&expected).all(|(a, b)| a.equals(b)));
+        }
+
+        set_rayon_pool(None);
+        assert_eq!(install(rayon::current_thread_index), None);
+    }
+}
@@ -0,0 +1,19 @@
+#[cfg(test)]
+mod tests {
+    use kzg_bench::tests::validation::{setup_validation_test, table_validation_test};
+    use rust_kzg_blst::types::fp::FsFp;
+    use rust_kzg_blst::types::fr::FsFr;
+    use rust_kzg_blst::types::g1::{FsG1, FsG1Affine};
+    use rust_kzg_blst::types::g2::FsG2;
+    use rust_kzg_blst::utils::generate_trusted_setup;
+
+    #[test]
+    fn setup_validation_test_() {
+        setup_validation_test::<FsFr, FsG1, FsG2>(&generate_trusted_setup);
+    }
+
+    #[test]
+    fn table_validation_test_() {
+        table_validation_test::<FsFr, FsG1, FsFp, FsG1Affine>();
+    }
+}
@@ -0,0 +1,78 @@
+#[cfg(all(test, feature = "wasm"))]
+mod tests {
+    use kzg::eip_4844::load_trusted_setup_string;
+    use kzg_bench::tests::eip_4844::generate_random_blob_bytes;
+    use kzg_bench::tests::utils::get_trusted_setup_path;
+    use rust_kzg_blst::c_kzg_compat::{Blob, Bytes32, Bytes48, KzgProof, KzgSettings};
+    use rust_kzg_blst::wasm::WasmKzgSettings;
+    use wasm_bindgen::JsError;
+
+    // Errors become JavaScript exceptions, which only exist on wasm32, so only successes run here
+    fn ok<T>(result: Result<T, JsError>) -> T {
+        result.unwrap_or_else(|_| panic!("call into the bindings failed"))
+    }
+
+    #[test]
+    fn wasm_bindings_match_native_api() {
+        let contents = std::fs::read(get_trusted_setup_path()).unwrap();
+        let settings = ok(WasmKzgSettings::load_trusted_setup(&contents));
+        let (g1_bytes, g2_bytes) =
+            load_trusted_setup_string(core::str::from_utf8(&contents).unwrap()).unwrap();
+        let from_bytes = ok(WasmKzgSettings::load_trusted_setup_from_bytes(
+            &g1_bytes, &g2_bytes,
+        ));
+
+        let blob_bytes = generate_random_blob_bytes(&mut rand::thread_rng());
+        let commitment = ok(settings.blob_to_kzg_commitment(&blob_bytes));
+        assert_eq!(
+            ok(from_bytes.blob_to_kzg_commitment(&blob_bytes)),
+            commitment
+        );
+
+        let native = KzgSettings::load_trusted_setup(
+            &g1_bytes
+                .chunks(48)
+                .map(|c| c.try_into().unwrap())
+                .collect::<Vec<_>>(),
+            &g2_bytes
+                .chunks(96)
+                .map(|c| c.try_into().unwrap())
+                .collect::<Vec<_>>(),
+        )
+        .unwrap();
+        let blob = Blob::new(blob_bytes);
+        let mut z = [0u8; 32];
+        z[31] = 7;
+        let (proof, y) = KzgProof::compute_kzg_proof(&blob, &Bytes32::new(z), &native).unwrap();
+        assert!(ok(settings.verify_kzg_proof(
+            &commitment,
+            &z,
+            &*y,
+            &*proof.to_bytes()
+        )));
+        assert!(!ok(settings.verify_kzg_proof(
+            &commitment,
+            &z,
+            &[0u8; 32],
+            &*proof.to_bytes()
+        )));
+
+        let blob_proof = KzgProof::compute_blob_kzg_proof(
+            &blob,
+            &Bytes48::from_bytes(&commitment).unwrap(),
+            &native,
+        )
+        .unwrap()
+        .to_bytes();
+        assert!(ok(settings.verify_blob_kzg_proof(
+            &*blob,
+            &commitment,
+            &*blob_proof
+        )));
+        assert!(!ok(settings.verify_blob_kzg_proof(
+            &*blob,
+            &commitment,
+            &*proof.to_bytes()
+        )));
+    }
+}
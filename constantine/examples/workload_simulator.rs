@@ -0,0 +1,13 @@
+//! Simulate a client workload against the constantine backend, to size hardware before deployment.
+//! Every backend has this example, so the backend is picked by the package it runs from.
+//!
+//! ```text
+//! cargo run --release -p rust-kzg-constantine --example workload_simulator -- --blobs 6 --verifications 100 --recovery-every 4 --slots 8
+//! ```
+
+use kzg_bench::simulator::workload_simulator_main;
+use rust_kzg_constantine::prelude::CtBackend;
+
+fn main() {
+    workload_simulator_main::<CtBackend>();
+}
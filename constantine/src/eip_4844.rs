@@ -2,6 +2,7 @@ extern crate alloc;
 
 use alloc::boxed::Box;
 use alloc::string::String;
+use alloc::sync::Arc;
 use alloc::vec::Vec;
 use core::ptr::null_mut;
 use kzg::common_utils::reverse_bit_order;
@@ -24,8 +25,7 @@ use kzg::eip_4844::load_trusted_setup_string;
 use kzg::eip_4844::{
     Blob, Bytes32, Bytes48, CKZGSettings, KZGCommitment, KZGProof, PrecomputationTableManager,
     BYTES_PER_FIELD_ELEMENT, BYTES_PER_G1, BYTES_PER_G2, C_KZG_RET, C_KZG_RET_BADARGS,
-    C_KZG_RET_OK, FIELD_ELEMENTS_PER_BLOB, TRUSTED_SETUP_NUM_G1_POINTS,
-    TRUSTED_SETUP_NUM_G2_POINTS,
+    C_KZG_RET_OK, FIELD_ELEMENTS_PER_BLOB, TRUSTED_SETUP_NUM_G2_POINTS,
 };
 
 use crate::types::fft_settings::CtFFTSettings;
@@ -39,7 +39,7 @@ use crate::types::kzg_settings::CtKZGSettings;
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
-static mut PRECOMPUTATION_TABLES: PrecomputationTableManager<CtFr, CtG1, CtFp, CtG1Affine> =
+static PRECOMPUTATION_TABLES: PrecomputationTableManager<CtFr, CtG1, CtFp, CtG1Affine> =
     PrecomputationTableManager::new();
 
 #[cfg(feature = "std")]
@@ -83,7 +83,7 @@ fn fft_settings_to_rust(c_settings: *const CKZGSettings) -> Result<CtFFTSettings
 
 fn kzg_settings_to_rust(c_settings: &CKZGSettings) -> Result<CtKZGSettings, String> {
     let secret_g1 = unsafe {
-        core::slice::from_raw_parts(c_settings.g1_values, TRUSTED_SETUP_NUM_G1_POINTS)
+        core::slice::from_raw_parts(c_settings.g1_values, FIELD_ELEMENTS_PER_BLOB)
             .iter()
             .map(|r| CtG1::from_blst_p1(*r))
             .collect::<Vec<CtG1>>()
@@ -97,7 +97,7 @@ fn kzg_settings_to_rust(c_settings: &CKZGSettings) -> Result<CtKZGSettings, Stri
                 .map(|r| CtG2::from_blst_p2(*r))
                 .collect::<Vec<CtG2>>()
         },
-        precomputation: None,
+        precomputation: Arc::default(),
     })
 }
 
@@ -166,11 +166,6 @@ pub unsafe extern "C" fn blob_to_kzg_commitment(
     blob: *const Blob,
     s: &CKZGSettings,
 ) -> C_KZG_RET {
-    if TRUSTED_SETUP_NUM_G1_POINTS == 0 {
-        // FIXME: load_trusted_setup should set this value, but if not, it fails
-        TRUSTED_SETUP_NUM_G1_POINTS = FIELD_ELEMENTS_PER_BLOB
-    };
-
     let deserialized_blob = handle_ckzg_badargs!(deserialize_blob(blob));
     let settings = handle_ckzg_badargs!(kzg_settings_to_rust(s));
     let tmp = handle_ckzg_badargs!(blob_to_kzg_commitment_rust(&deserialized_blob, &settings));
@@ -190,8 +185,7 @@ pub unsafe extern "C" fn load_trusted_setup(
 ) -> C_KZG_RET {
     let g1_bytes = core::slice::from_raw_parts(g1_bytes, n1 * BYTES_PER_G1);
     let g2_bytes = core::slice::from_raw_parts(g2_bytes, n2 * BYTES_PER_G2);
-    TRUSTED_SETUP_NUM_G1_POINTS = g1_bytes.len() / BYTES_PER_G1;
-    let mut settings = handle_ckzg_badargs!(load_trusted_setup_rust(g1_bytes, g2_bytes));
+    let settings = handle_ckzg_badargs!(load_trusted_setup_rust(g1_bytes, g2_bytes));
 
     let c_settings = kzg_settings_to_c(&settings);
 
@@ -213,14 +207,13 @@ pub unsafe extern "C" fn load_trusted_setup_file(
     let len: usize = libc::fread(buf.as_mut_ptr() as *mut libc::c_void, 1, buf.len(), in_);
     let s = handle_ckzg_badargs!(String::from_utf8(buf[..len].to_vec()));
     let (g1_bytes, g2_bytes) = handle_ckzg_badargs!(load_trusted_setup_string(&s));
-    TRUSTED_SETUP_NUM_G1_POINTS = g1_bytes.len() / BYTES_PER_G1;
-    if TRUSTED_SETUP_NUM_G1_POINTS != FIELD_ELEMENTS_PER_BLOB {
+    if g1_bytes.len() / BYTES_PER_G1 != FIELD_ELEMENTS_PER_BLOB {
         // Helps pass the Java test "shouldThrowExceptionOnIncorrectTrustedSetupFromFile",
         // as well as 5 others that pass only if this one passes (likely because Java doesn't
         // deallocate its KZGSettings pointer when no exception is thrown).
         return C_KZG_RET_BADARGS;
     }
-    let mut settings = handle_ckzg_badargs!(load_trusted_setup_rust(
+    let settings = handle_ckzg_badargs!(load_trusted_setup_rust(
         g1_bytes.as_slice(),
         g2_bytes.as_slice()
     ));
@@ -278,7 +271,7 @@ pub unsafe extern "C" fn free_trusted_setup(s: *mut CKZGSettings) {
 
     let g1 = Box::from_raw(core::slice::from_raw_parts_mut(
         (*s).g1_values,
-        TRUSTED_SETUP_NUM_G1_POINTS,
+        FIELD_ELEMENTS_PER_BLOB,
     ));
     drop(g1);
     (*s).g1_values = null_mut();
@@ -2,9 +2,9 @@ extern crate alloc;
 
 use alloc::format;
 use alloc::string::String;
-use alloc::vec;
 use alloc::vec::Vec;
 
+use kzg::common_utils::try_vec;
 use kzg::{FFTFr, Fr};
 
 use crate::types::fft_settings::CtFFTSettings;
@@ -37,7 +37,7 @@ pub fn fft_fr_fast(
 
         #[cfg(feature = "parallel")]
         {
-            if half > 256 {
+            if half > 256 && kzg::parallelism::is_parallel() {
                 let (lo, hi) = ret.split_at_mut(half);
                 rayon::join(
                     || fft_fr_fast(lo, data, stride * 2, roots, roots_stride * 2),
@@ -101,6 +101,10 @@ impl CtFFTSettings {
             &self.expanded_roots_of_unity
         };
 
+        #[cfg(feature = "parallel")]
+        kzg::parallelism::install(|| fft_fr_fast(output, data, 1, roots, stride));
+
+        #[cfg(not(feature = "parallel"))]
         fft_fr_fast(output, data, 1, roots, stride);
 
         if inverse {
@@ -115,7 +119,7 @@ impl CtFFTSettings {
 impl FFTFr<CtFr> for CtFFTSettings {
     /// Fast Fourier Transform for finite field elements
     fn fft_fr(&self, data: &[CtFr], inverse: bool) -> Result<Vec<CtFr>, String> {
-        let mut ret = vec![CtFr::default(); data.len()];
+        let mut ret = try_vec(CtFr::default(), data.len())?;
 
         self.fft_fr_output(data, inverse, &mut ret)?;
 
@@ -1,9 +1,9 @@
 extern crate alloc;
 
 use alloc::string::String;
-use alloc::vec;
 use alloc::vec::Vec;
 
+use kzg::common_utils::try_vec;
 use kzg::{Fr, G1Mul, FFTG1, G1};
 
 use crate::types::fft_settings::CtFFTSettings;
@@ -21,11 +21,22 @@ pub fn fft_g1_fast(
     if half > 0 {
         #[cfg(feature = "parallel")]
         {
-            let (lo, hi) = ret.split_at_mut(half);
-            rayon::join(
-                || fft_g1_fast(lo, data, stride * 2, roots, roots_stride * 2),
-                || fft_g1_fast(hi, &data[stride..], stride * 2, roots, roots_stride * 2),
-            );
+            if kzg::parallelism::is_parallel() {
+                let (lo, hi) = ret.split_at_mut(half);
+                rayon::join(
+                    || fft_g1_fast(lo, data, stride * 2, roots, roots_stride * 2),
+                    || fft_g1_fast(hi, &data[stride..], stride * 2, roots, roots_stride * 2),
+                );
+            } else {
+                fft_g1_fast(&mut ret[..half], data, stride * 2, roots, roots_stride * 2);
+                fft_g1_fast(
+                    &mut ret[half..],
+                    &data[stride..],
+                    stride * 2,
+                    roots,
+                    roots_stride * 2,
+                );
+            }
         }
 
         #[cfg(not(feature = "parallel"))]
@@ -61,7 +72,7 @@ impl FFTG1<CtG1> for CtFFTSettings {
         }
 
         let stride = self.max_width / data.len();
-        let mut ret = vec![CtG1::default(); data.len()];
+        let mut ret = try_vec(CtG1::default(), data.len())?;
 
         let roots = if inverse {
             &self.reverse_roots_of_unity
@@ -69,6 +80,10 @@ impl FFTG1<CtG1> for CtFFTSettings {
             &self.expanded_roots_of_unity
         };
 
+        #[cfg(feature = "parallel")]
+        kzg::parallelism::install(|| fft_g1_fast(&mut ret, data, 1, roots, stride));
+
+        #[cfg(not(feature = "parallel"))]
         fft_g1_fast(&mut ret, data, 1, roots, stride);
 
         if inverse {
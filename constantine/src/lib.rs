@@ -8,6 +8,7 @@ pub mod fft_g1;
 pub mod fk20_proofs;
 pub mod kzg_proofs;
 pub mod mixed_kzg;
+pub mod prelude;
 pub mod recovery;
 pub mod types;
 pub mod utils;
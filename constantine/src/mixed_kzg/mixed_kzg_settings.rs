@@ -1,4 +1,5 @@
 use std::path::Path;
+use std::sync::Arc;
 
 use crate::types::{
     fft_settings::CtFFTSettings,
@@ -223,7 +224,7 @@ impl KZGSettings<CtFr, CtG1, CtG2, CtFFTSettings, CtPoly, CtFp, CtG1Affine> for
 
     fn get_precomputation(
         &self,
-    ) -> Option<&kzg::msm::precompute::PrecomputationTable<CtFr, CtG1, CtFp, CtG1Affine>> {
+    ) -> Option<Arc<kzg::msm::precompute::PrecomputationTable<CtFr, CtG1, CtFp, CtG1Affine>>> {
         match self {
             MixedKzgSettings::Constantine(_) => {
                 panic!("Context not in generic format")
@@ -231,4 +232,16 @@ impl KZGSettings<CtFr, CtG1, CtG2, CtFFTSettings, CtPoly, CtFp, CtG1Affine> for
             MixedKzgSettings::Generic(generic_context) => generic_context.get_precomputation(),
         }
     }
+
+    fn get_releasable_precomputation(
+        &self,
+    ) -> Option<Arc<kzg::msm::precompute::ReleasableTable<CtFr, CtG1, CtFp, CtG1Affine>>> {
+        match self {
+            // The native context keeps no table
+            MixedKzgSettings::Constantine(_) => None,
+            MixedKzgSettings::Generic(generic_context) => {
+                generic_context.get_releasable_precomputation()
+            }
+        }
+    }
 }
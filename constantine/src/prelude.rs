@@ -0,0 +1,37 @@
+//! Concrete types of the constantine backend together with the traits they implement, for
+//! `use rust_kzg_constantine::prelude::*;`
+
+use kzg::backend::{Backend, KzgBackend};
+
+pub use kzg::{
+    FFTFr, FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1LinComb, G1Mul, G1ProjAddAffine, G2Mul,
+    KZGSettings, PairingVerify, Poly, FFTG1, G1, G2,
+};
+
+pub use crate::types::fft_settings::CtFFTSettings;
+pub use crate::types::fp::CtFp;
+pub use crate::types::fr::CtFr;
+pub use crate::types::g1::{CtG1, CtG1Affine, CtG1ProjAddAffine};
+pub use crate::types::g2::CtG2;
+pub use crate::types::kzg_settings::CtKZGSettings;
+pub use crate::types::poly::CtPoly;
+
+/// `Backend` bundle of the constantine types
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CtBackend;
+
+impl Backend for CtBackend {
+    const NAME: &'static str = "constantine";
+
+    type Fr = CtFr;
+    type G1 = CtG1;
+    type G2 = CtG2;
+    type G1Fp = CtFp;
+    type G1Affine = CtG1Affine;
+    type G1ProjAddAffine = CtG1ProjAddAffine;
+    type Poly = CtPoly;
+    type FFTSettings = CtFFTSettings;
+    type KZGSettings = CtKZGSettings;
+}
+
+impl KzgBackend for CtBackend {}
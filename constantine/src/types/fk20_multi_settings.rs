@@ -48,19 +48,7 @@ impl FK20MultiSettings<CtFr, CtG1, CtG2, CtFFTSettings, CtPoly, CtKZGSettings, C
 {
     #[allow(clippy::many_single_char_names)]
     fn new(ks: &CtKZGSettings, n2: usize, chunk_len: usize) -> Result<Self, String> {
-        if n2 > ks.fs.max_width {
-            return Err(String::from(
-                "n2 must be less than or equal to kzg settings max width",
-            ));
-        } else if !n2.is_power_of_two() {
-            return Err(String::from("n2 must be a power of two"));
-        } else if n2 < 2 {
-            return Err(String::from("n2 must be greater than or equal to 2"));
-        } else if chunk_len > n2 / 2 {
-            return Err(String::from("chunk_len must be greater or equal to n2 / 2"));
-        } else if !chunk_len.is_power_of_two() {
-            return Err(String::from("chunk_len must be a power of two"));
-        }
+        check_args(ks, n2, chunk_len)?;
 
         let n = n2 / 2;
         let k = n / chunk_len;
@@ -96,13 +84,7 @@ impl FK20MultiSettings<CtFr, CtG1, CtG2, CtFFTSettings, CtPoly, CtKZGSettings, C
             }
         }
 
-        let ret = Self {
-            kzg_settings: ks.clone(),
-            chunk_len,
-            x_ext_fft_files: ext_fft_files,
-        };
-
-        Ok(ret)
+        Self::new_with_x_ext_fft_files(ks, n2, chunk_len, ext_fft_files)
     }
 
     fn data_availability(&self, p: &CtPoly) -> Result<Vec<CtG1>, String> {
@@ -163,4 +145,48 @@ impl FK20MultiSettings<CtFr, CtG1, CtG2, CtFFTSettings, CtPoly, CtKZGSettings, C
 
         Ok(ret)
     }
+
+    fn get_x_ext_fft_files(&self) -> &[Vec<CtG1>] {
+        &self.x_ext_fft_files
+    }
+
+    fn new_with_x_ext_fft_files(
+        ks: &CtKZGSettings,
+        n2: usize,
+        chunk_len: usize,
+        x_ext_fft_files: Vec<Vec<CtG1>>,
+    ) -> Result<Self, String> {
+        check_args(ks, n2, chunk_len)?;
+        if x_ext_fft_files.len() != chunk_len
+            || x_ext_fft_files
+                .iter()
+                .any(|file| file.len() != n2 / chunk_len)
+        {
+            return Err(String::from("Toeplitz FFTs do not match n2 and chunk_len"));
+        }
+
+        Ok(Self {
+            kzg_settings: ks.clone(),
+            chunk_len,
+            x_ext_fft_files,
+        })
+    }
+}
+
+fn check_args(ks: &CtKZGSettings, n2: usize, chunk_len: usize) -> Result<(), String> {
+    if n2 > ks.fs.max_width {
+        return Err(String::from(
+            "n2 must be less than or equal to kzg settings max width",
+        ));
+    } else if !n2.is_power_of_two() {
+        return Err(String::from("n2 must be a power of two"));
+    } else if n2 < 2 {
+        return Err(String::from("n2 must be greater than or equal to 2"));
+    } else if chunk_len > n2 / 2 {
+        return Err(String::from("chunk_len must be greater or equal to n2 / 2"));
+    } else if !chunk_len.is_power_of_two() {
+        return Err(String::from("chunk_len must be a power of two"));
+    }
+
+    Ok(())
 }
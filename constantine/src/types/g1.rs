@@ -13,7 +13,7 @@ use crate::kzg_proofs::g1_linear_combination;
 use crate::types::fp::CtFp;
 use crate::types::fr::CtFr;
 
-use kzg::eip_4844::BYTES_PER_G1;
+use kzg::eip_4844::{BYTES_PER_G1, BYTES_PER_G1_UNCOMPRESSED};
 use kzg::G1Affine;
 use kzg::G1GetFp;
 use kzg::G1ProjAddAffine;
@@ -239,6 +239,40 @@ impl G1 for CtG1 {
             constantine::ctt_bls12_381_g1_jac_double_in_place(&mut self.0);
         }
     }
+
+    fn to_bytes_uncompressed(&self) -> Result<[u8; BYTES_PER_G1_UNCOMPRESSED], String> {
+        let mut out = [0u8; BYTES_PER_G1_UNCOMPRESSED];
+        unsafe {
+            blst::blst_p1_serialize(out.as_mut_ptr(), &self.to_blst_p1());
+        }
+        Ok(out)
+    }
+
+    fn from_bytes_uncompressed(bytes: &[u8]) -> Result<Self, String> {
+        bytes
+            .try_into()
+            .map_err(|_| {
+                format!(
+                    "Invalid byte length. Expected {}, got {}",
+                    BYTES_PER_G1_UNCOMPRESSED,
+                    bytes.len()
+                )
+            })
+            .and_then(|bytes: &[u8; BYTES_PER_G1_UNCOMPRESSED]| {
+                let mut tmp = blst::blst_p1_affine::default();
+                let mut g1 = blst::blst_p1::default();
+                unsafe {
+                    // The deserialize routine also checks that the point is on the curve
+                    if blst::blst_p1_deserialize(&mut tmp, bytes.as_ptr())
+                        != blst::BLST_ERROR::BLST_SUCCESS
+                    {
+                        return Err("Failed to deserialize".to_string());
+                    }
+                    blst::blst_p1_from_affine(&mut g1, &tmp);
+                }
+                Ok(Self::from_blst_p1(g1))
+            })
+    }
 }
 
 impl G1Mul<CtFr> for CtG1 {
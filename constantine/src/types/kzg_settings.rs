@@ -4,7 +4,8 @@ use alloc::string::String;
 use alloc::sync::Arc;
 use alloc::vec::Vec;
 
-use kzg::msm::precompute::{precompute, PrecomputationTable};
+use kzg::dev_setup::guard_insecure_dev_setup;
+use kzg::msm::precompute::{precompute, PrecomputationTable, ReleasableTable};
 use kzg::{FFTFr, FFTSettings, Fr, G1Mul, G2Mul, KZGSettings, Poly, G1, G2};
 
 use crate::consts::{G1_GENERATOR, G2_GENERATOR};
@@ -23,7 +24,7 @@ pub struct CtKZGSettings {
     pub fs: CtFFTSettings,
     pub secret_g1: Vec<CtG1>,
     pub secret_g2: Vec<CtG2>,
-    pub precomputation: Option<Arc<PrecomputationTable<CtFr, CtG1, CtFp, CtG1Affine>>>,
+    pub precomputation: Arc<ReleasableTable<CtFr, CtG1, CtFp, CtG1Affine>>,
 }
 
 impl KZGSettings<CtFr, CtG1, CtG2, CtFFTSettings, CtPoly, CtFp, CtG1Affine> for CtKZGSettings {
@@ -37,7 +38,7 @@ impl KZGSettings<CtFr, CtG1, CtG2, CtFFTSettings, CtPoly, CtFp, CtG1Affine> for
             secret_g1: secret_g1.to_vec(),
             secret_g2: secret_g2.to_vec(),
             fs: fft_settings.clone(),
-            precomputation: precompute(secret_g1).ok().flatten().map(Arc::new),
+            precomputation: Arc::new(ReleasableTable::new(precompute(secret_g1).ok().flatten())),
         })
     }
 
@@ -52,7 +53,7 @@ impl KZGSettings<CtFr, CtG1, CtG2, CtFFTSettings, CtPoly, CtFp, CtG1Affine> for
             &self.secret_g1,
             &poly.coeffs,
             poly.coeffs.len(),
-            self.get_precomputation(),
+            self.get_precomputation().as_deref(),
         );
 
         Ok(out)
@@ -88,6 +89,8 @@ impl KZGSettings<CtFr, CtG1, CtG2, CtFFTSettings, CtPoly, CtFp, CtG1Affine> for
         x: &CtFr,
         y: &CtFr,
     ) -> Result<bool, String> {
+        guard_insecure_dev_setup(&self.secret_g2);
+
         let x_g2: CtG2 = G2_GENERATOR.mul(x);
         let s_minus_x: CtG2 = self.secret_g2[1].sub(&x_g2);
         let y_g1 = G1_GENERATOR.mul(y);
@@ -150,6 +153,7 @@ impl KZGSettings<CtFr, CtG1, CtG2, CtFFTSettings, CtPoly, CtFp, CtG1Affine> for
         if !n.is_power_of_two() {
             return Err(String::from("n is not a power of two"));
         }
+        guard_insecure_dev_setup(&self.secret_g2);
 
         // Interpolate at a coset.
         let mut interp = CtPoly {
@@ -172,7 +176,7 @@ impl KZGSettings<CtFr, CtG1, CtG2, CtFFTSettings, CtPoly, CtFp, CtG1Affine> for
         let xn_minus_yn = self.secret_g2[n].sub(&xn2);
 
         // [interpolation_polynomial(s)]_1
-        let is1 = self.commit_to_poly(&interp).unwrap();
+        let is1 = self.commit_to_poly(&interp)?;
 
         // [commitment - interpolation_polynomial(s)]_1 = [commit]_1 - [interpolation_polynomial(s)]_1
         let commit_minus_interp = com.sub(&is1);
@@ -202,7 +206,13 @@ impl KZGSettings<CtFr, CtG1, CtG2, CtFFTSettings, CtPoly, CtFp, CtG1Affine> for
         &self.secret_g2
     }
 
-    fn get_precomputation(&self) -> Option<&PrecomputationTable<CtFr, CtG1, CtFp, CtG1Affine>> {
-        self.precomputation.as_ref().map(|v| v.as_ref())
+    fn get_precomputation(&self) -> Option<Arc<PrecomputationTable<CtFr, CtG1, CtFp, CtG1Affine>>> {
+        self.precomputation.get()
+    }
+
+    fn get_releasable_precomputation(
+        &self,
+    ) -> Option<Arc<ReleasableTable<CtFr, CtG1, CtFp, CtG1Affine>>> {
+        Some(self.precomputation.clone())
     }
 }
@@ -1,11 +1,11 @@
 extern crate alloc;
 
 use alloc::string::String;
-use alloc::vec;
 use alloc::vec::Vec;
 use core::cmp::{min, Ordering};
 
-use kzg::{common_utils::next_pow_of_2, FFTFr, Fr, ZeroPoly};
+use kzg::common_utils::{next_pow_of_2, try_vec};
+use kzg::{FFTFr, Fr, ZeroPoly};
 
 use crate::types::fft_settings::CtFFTSettings;
 use crate::types::fr::CtFr;
@@ -216,9 +216,9 @@ impl ZeroPoly<CtFr, CtPoly> for CtFFTSettings {
         } else {
             // Otherwise, construct a set of partial polynomials
             // Save all constructed polynomials in a shared 'work' vector
-            let mut work = vec![CtFr::zero(); next_pow];
+            let mut work = try_vec(CtFr::zero(), next_pow)?;
 
-            let mut partial_lens = vec![DEGREE_OF_PARTIAL; partial_count];
+            let mut partial_lens = try_vec(DEGREE_OF_PARTIAL, partial_count)?;
 
             #[cfg(not(feature = "parallel"))]
             let iter = missing_idxs
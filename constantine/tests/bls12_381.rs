@@ -4,9 +4,9 @@ mod tests {
     use kzg_bench::tests::bls12_381::{
         fr_div_by_zero, fr_div_works, fr_equal_works, fr_from_uint64_works, fr_is_null_works,
         fr_is_one_works, fr_is_zero_works, fr_negate_works, fr_pow_works, fr_uint64s_roundtrip,
-        g1_identity_is_identity, g1_identity_is_infinity, g1_make_linear_combination,
-        g1_random_linear_combination, log_2_byte_works, p1_mul_works, p1_sub_works,
-        p2_add_or_dbl_works, p2_mul_works, p2_sub_works, pairings_work,
+        g1_batch_serialization_roundtrip, g1_identity_is_identity, g1_identity_is_infinity,
+        g1_make_linear_combination, g1_random_linear_combination, log_2_byte_works, p1_mul_works,
+        p1_sub_works, p2_add_or_dbl_works, p2_mul_works, p2_sub_works, pairings_work,
     };
 
     use rust_kzg_constantine::kzg_proofs::{g1_linear_combination, pairings_verify};
@@ -105,6 +105,11 @@ mod tests {
         g1_identity_is_identity::<CtG1>()
     }
 
+    #[test]
+    fn g1_batch_serialization_roundtrip_() {
+        g1_batch_serialization_roundtrip::<CtFr, CtG1>()
+    }
+
     #[test]
     fn g1_make_linear_combination_() {
         g1_make_linear_combination::<CtFr, CtG1, CtFp, CtG1Affine>(&g1_linear_combination)
@@ -0,0 +1,28 @@
+#[cfg(test)]
+mod tests {
+    use kzg_bench::tests::fk20_cache::fk20_cache_roundtrip_test;
+    use rust_kzg_constantine::eip_4844::load_trusted_setup_filename_rust;
+    use rust_kzg_constantine::types::fft_settings::CtFFTSettings;
+    use rust_kzg_constantine::types::fk20_multi_settings::CtFK20MultiSettings;
+    use rust_kzg_constantine::types::fp::CtFp;
+    use rust_kzg_constantine::types::fr::CtFr;
+    use rust_kzg_constantine::types::g1::{CtG1, CtG1Affine};
+    use rust_kzg_constantine::types::g2::CtG2;
+    use rust_kzg_constantine::types::kzg_settings::CtKZGSettings;
+    use rust_kzg_constantine::types::poly::CtPoly;
+
+    #[test]
+    fn fk20_cache_roundtrip_test_() {
+        fk20_cache_roundtrip_test::<
+            CtFr,
+            CtG1,
+            CtG2,
+            CtPoly,
+            CtFFTSettings,
+            CtKZGSettings,
+            CtFK20MultiSettings,
+            CtFp,
+            CtG1Affine,
+        >(&load_trusted_setup_filename_rust);
+    }
+}
@@ -0,0 +1,49 @@
+use criterion::Criterion;
+use kzg::io_utils::save_secrets_to_writer;
+use kzg::{Compression, G1, G2};
+
+use crate::tests::kzg_proofs::SECRET;
+
+#[allow(clippy::type_complexity)]
+pub fn bench_save_secrets<TG1: G1, TG2: G2>(
+    c: &mut Criterion,
+    generate_trusted_setup: &dyn Fn(usize, [u8; 32usize]) -> (Vec<TG1>, Vec<TG2>),
+) {
+    const NUM_POINTS: usize = 4096;
+    const NUM_G2_POINTS: usize = 65;
+
+    let (secret_g1, mut secret_g2) = generate_trusted_setup(NUM_POINTS, SECRET);
+    secret_g2.truncate(NUM_G2_POINTS);
+
+    let id = format!("bench_save_secrets points: '{}'", NUM_POINTS);
+    c.bench_function(&id, |b| {
+        b.iter(|| {
+            let mut bytes = Vec::new();
+            save_secrets_to_writer(&mut bytes, &secret_g1, &secret_g2).unwrap();
+            bytes
+        })
+    });
+
+    for (name, compression) in [
+        ("compressed", Compression::Compressed),
+        ("uncompressed", Compression::Uncompressed),
+    ] {
+        if TG1::batch_to_bytes(&secret_g1[..1], compression).is_err() {
+            continue;
+        }
+        let bytes = TG1::batch_to_bytes(&secret_g1, compression).unwrap();
+
+        let id = format!("bench_g1_batch_to_bytes {} points: '{}'", name, NUM_POINTS);
+        c.bench_function(&id, |b| {
+            b.iter(|| TG1::batch_to_bytes(&secret_g1, compression).unwrap())
+        });
+
+        let id = format!(
+            "bench_g1_batch_from_bytes {} points: '{}'",
+            name, NUM_POINTS
+        );
+        c.bench_function(&id, |b| {
+            b.iter(|| TG1::batch_from_bytes(&bytes, compression).unwrap())
+        });
+    }
+}
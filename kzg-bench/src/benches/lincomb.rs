@@ -1,7 +1,11 @@
+use crate::tests::msm::bucket_integration::random_buckets;
 use criterion::Criterion;
 use kzg::{
+    msm::fixed_base::FixedBaseMsm,
+    msm::msm_impls::{msm, msm_u128, msm_u64, msm_with_config, MsmConfig},
     msm::precompute::{precompute, PrecomputationTable},
-    Fr, G1Affine, G1Fp, G1GetFp, G1Mul, G1,
+    msm::{integrate_buckets_affine, integrate_buckets_xyzz},
+    Fr, G1Affine, G1Fp, G1GetFp, G1Mul, G1ProjAddAffine, Poly, G1,
 };
 
 #[allow(clippy::type_complexity)]
@@ -60,3 +64,221 @@ pub fn bench_g1_lincomb<
         });
     }
 }
+
+pub fn bench_fixed_base_commit<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+    TProjAddAffine: G1ProjAddAffine<TG1, TG1Fp, TG1Affine>,
+    TPoly: Poly<TFr>,
+>(
+    c: &mut Criterion,
+) {
+    const NUM_POINTS: usize = 4096;
+
+    let points = (0..NUM_POINTS).map(|_| TG1::rand()).collect::<Vec<_>>();
+    let scalars = (0..NUM_POINTS).map(|_| TFr::rand()).collect::<Vec<_>>();
+
+    for (npoints, window) in [(64, 8), (NUM_POINTS, 4)] {
+        let table = FixedBaseMsm::<TFr, TG1, TG1Fp, TG1Affine, TProjAddAffine>::new(
+            &points[..npoints],
+            window,
+        )
+        .unwrap();
+        let poly = TPoly::from_coeffs(&scalars[..npoints]);
+        let id = format!(
+            "bench_fixed_base_commit points: '{}', window: '{}'",
+            npoints, window
+        );
+        c.bench_function(&id, |b| b.iter(|| table.commit(&poly)));
+    }
+}
+
+pub fn bench_bounded_msm<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+    TProjAddAffine: G1ProjAddAffine<TG1, TG1Fp, TG1Affine>,
+>(
+    c: &mut Criterion,
+) {
+    const NUM_POINTS: usize = 4096;
+
+    let points = (0..NUM_POINTS).map(|_| TG1::rand()).collect::<Vec<_>>();
+    let small = (0..NUM_POINTS as u64)
+        .map(|i| i.wrapping_mul(0x9E37_79B9_7F4A_7C15))
+        .collect::<Vec<_>>();
+    let wide = small
+        .iter()
+        .map(|&scalar| (scalar as u128) << 64 | scalar as u128)
+        .collect::<Vec<_>>();
+    let full = small
+        .iter()
+        .map(|&scalar| TFr::from_u64(scalar))
+        .collect::<Vec<_>>();
+
+    let id = format!("bench_bounded_msm 64-bit scalars points: '{}'", NUM_POINTS);
+    c.bench_function(&id, |b| {
+        b.iter(|| msm_u64::<TG1, TG1Fp, TG1Affine>(&points, &small))
+    });
+    let id = format!("bench_bounded_msm 128-bit scalars points: '{}'", NUM_POINTS);
+    c.bench_function(&id, |b| {
+        b.iter(|| msm_u128::<TG1, TG1Fp, TG1Affine>(&points, &wide))
+    });
+    // The same 64-bit values as field elements, through the full-width MSM
+    let id = format!("bench_bounded_msm msm points: '{}'", NUM_POINTS);
+    c.bench_function(&id, |b| {
+        b.iter(|| {
+            msm::<TG1, TG1Fp, TG1Affine, TProjAddAffine, TFr>(&points, &full, NUM_POINTS, None)
+        })
+    });
+}
+
+pub fn bench_sparse_msm<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+    TProjAddAffine: G1ProjAddAffine<TG1, TG1Fp, TG1Affine>,
+>(
+    c: &mut Criterion,
+) {
+    const NUM_POINTS: usize = 4096;
+
+    let points = (0..NUM_POINTS).map(|_| TG1::rand()).collect::<Vec<_>>();
+    let Some(precomputation) = precompute::<TFr, TG1, TG1Fp, TG1Affine>(&points).unwrap() else {
+        return;
+    };
+
+    for nonzero in [1, 10, 100] {
+        let scalars = (0..NUM_POINTS)
+            .map(|i| {
+                if i * nonzero % 100 < nonzero {
+                    TFr::rand()
+                } else {
+                    TFr::zero()
+                }
+            })
+            .collect::<Vec<_>>();
+        let id = format!(
+            "bench_sparse_msm with precomputation points: '{}', non-zero: '{}%'",
+            NUM_POINTS, nonzero
+        );
+        c.bench_function(&id, |b| {
+            b.iter(|| {
+                msm::<TG1, TG1Fp, TG1Affine, TProjAddAffine, TFr>(
+                    &points,
+                    &scalars,
+                    NUM_POINTS,
+                    Some(&precomputation),
+                )
+            })
+        });
+    }
+}
+
+/// MSMs large enough for the buckets to fall out of cache. Run once with and once without the
+/// `prefetch` feature to compare, the benchmark name records which one was built.
+pub fn bench_large_msm<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+    TProjAddAffine: G1ProjAddAffine<TG1, TG1Fp, TG1Affine>,
+>(
+    c: &mut Criterion,
+) {
+    const NUM_POINTS: usize = 1 << 16;
+
+    let points = (0..NUM_POINTS).map(|_| TG1::rand()).collect::<Vec<_>>();
+    let scalars = (0..NUM_POINTS).map(|_| TFr::rand()).collect::<Vec<_>>();
+    let prefetch = kzg::capabilities().prefetch;
+
+    let id = format!(
+        "bench_large_msm points: '{}', prefetch: '{}'",
+        NUM_POINTS, prefetch
+    );
+    c.bench_function(&id, |b| {
+        b.iter(|| {
+            msm::<TG1, TG1Fp, TG1Affine, TProjAddAffine, TFr>(&points, &scalars, NUM_POINTS, None)
+        })
+    });
+
+    let Some(precomputation) = precompute::<TFr, TG1, TG1Fp, TG1Affine>(&points).unwrap() else {
+        return;
+    };
+    let id = format!(
+        "bench_large_msm with precomputation points: '{}', prefetch: '{}'",
+        NUM_POINTS, prefetch
+    );
+    c.bench_function(&id, |b| {
+        b.iter(|| {
+            msm::<TG1, TG1Fp, TG1Affine, TProjAddAffine, TFr>(
+                &points,
+                &scalars,
+                NUM_POINTS,
+                Some(&precomputation),
+            )
+        })
+    });
+}
+
+/// Booth and wNAF recoding over the same MSMs
+pub fn bench_msm_recoding<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+    TProjAddAffine: G1ProjAddAffine<TG1, TG1Fp, TG1Affine>,
+>(
+    c: &mut Criterion,
+) {
+    for num_points in [16, 64, 256, 1024] {
+        let points = (0..num_points).map(|_| TG1::rand()).collect::<Vec<_>>();
+        let scalars = (0..num_points).map(|_| TFr::rand()).collect::<Vec<_>>();
+
+        for (name, config) in [("booth", MsmConfig::default()), ("wnaf", MsmConfig::wnaf())] {
+            let id = format!(
+                "bench_msm_recoding points: '{}', recoding: '{}'",
+                num_points, name
+            );
+            c.bench_function(&id, |b| {
+                b.iter(|| {
+                    msm_with_config::<TG1, TG1Fp, TG1Affine, TProjAddAffine, TFr>(
+                        &points, &scalars, num_points, None, &config,
+                    )
+                })
+            });
+        }
+    }
+}
+
+/// Bucket integration of Pippenger, with mixed addition for the buckets holding a single point and
+/// with every bucket converted to affine first
+pub fn bench_bucket_integration<
+    TG1: G1 + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    c: &mut Criterion,
+) {
+    for window in [4, 8, 12] {
+        for single in [true, false] {
+            let (buckets, _) = random_buckets::<TG1, TG1Fp, TG1Affine>(1 << (window - 1), single);
+            let contents = if single { "single" } else { "sums" };
+
+            let id = format!(
+                "bench_bucket_integration window: '{}', buckets: '{}', integration: 'xyzz'",
+                window, contents
+            );
+            c.bench_function(&id, |b| b.iter(|| integrate_buckets_xyzz(&buckets)));
+            let id = format!(
+                "bench_bucket_integration window: '{}', buckets: '{}', integration: 'affine'",
+                window, contents
+            );
+            c.bench_function(&id, |b| b.iter(|| integrate_buckets_affine(&buckets)));
+        }
+    }
+}
@@ -2,8 +2,10 @@ pub mod das;
 pub mod eip_4844;
 pub mod fft;
 pub mod fk20;
+pub mod io_utils;
 pub mod kzg;
 pub mod lincomb;
 pub mod poly;
 pub mod recover;
+pub mod sha256_batch;
 pub mod zero_poly;
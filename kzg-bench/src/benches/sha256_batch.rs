@@ -0,0 +1,31 @@
+use criterion::{BenchmarkId, Criterion, Throughput};
+use kzg::eip_4844::{BYTES_PER_COMMITMENT, CHALLENGE_INPUT_SIZE};
+use kzg::sha256_batch::{sha256_many_with, Sha256Path};
+use rand::{thread_rng, RngCore};
+
+pub fn bench_sha256_many(c: &mut Criterion) {
+    const NUM_MESSAGES: usize = 64;
+
+    for (name, len) in [
+        ("versioned_hashes", BYTES_PER_COMMITMENT),
+        ("challenges", CHALLENGE_INPUT_SIZE),
+    ] {
+        let messages: Vec<Vec<u8>> = (0..NUM_MESSAGES)
+            .map(|_| {
+                let mut message = vec![0u8; len];
+                thread_rng().fill_bytes(&mut message);
+                message
+            })
+            .collect();
+        let inputs: Vec<&[u8]> = messages.iter().map(Vec::as_slice).collect();
+
+        let mut group = c.benchmark_group(format!("bench_sha256_many {}", name));
+        group.throughput(Throughput::Bytes((NUM_MESSAGES * len) as u64));
+        for path in Sha256Path::available() {
+            group.bench_function(BenchmarkId::new(format!("{:?}", path), NUM_MESSAGES), |b| {
+                b.iter(|| sha256_many_with(&inputs, path))
+            });
+        }
+        group.finish();
+    }
+}
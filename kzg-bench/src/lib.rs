@@ -1,6 +1,8 @@
 use std::env::set_current_dir;
 
 pub mod benches;
+pub mod simulator;
+pub mod soak;
 pub mod test_vectors;
 pub mod tests;
 
@@ -0,0 +1,282 @@
+use std::env;
+use std::fmt;
+use std::process::exit;
+use std::time::{Duration, Instant};
+
+use kzg::backend::KzgBackend;
+use kzg::eip_4844::{
+    blob_to_kzg_commitment_rust, bytes_to_blob, compute_blob_kzg_proof_rust,
+    compute_kzg_proof_rust, verify_blob_kzg_proof_batch_rust, verify_kzg_proof_rust,
+    FIELD_ELEMENTS_PER_BLOB, TRUSTED_SETUP_PATH,
+};
+use kzg::{FFTFr, FFTSettings, Fr, Poly, PolyRecover};
+use rand::Rng;
+
+use crate::set_trusted_setup_dir;
+use crate::tests::eip_4844::{generate_random_blob_bytes, generate_random_field_element_bytes};
+
+/// Shape of the simulated client workload
+#[derive(Debug, Clone)]
+pub struct WorkloadConfig {
+    /// Blobs committed to, proven and batch-verified in every slot
+    pub blobs_per_slot: usize,
+    /// Single-point proof verifications per second of slot time
+    pub verifications_per_second: usize,
+    /// Recover one erased blob extension every this many slots, 0 disables recovery
+    pub recovery_every_slots: usize,
+    /// Number of slots to simulate
+    pub slots: usize,
+    /// Wall-clock duration of a slot
+    pub slot_duration: Duration,
+}
+
+const USAGE: &str = "usage: workload_simulator [--blobs N] [--verifications M] \
+[--recovery-every K] [--slots S] [--slot-seconds T]";
+
+impl WorkloadConfig {
+    /// Configuration from command line flags, `None` when help was asked for
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Result<Option<Self>, String> {
+        let mut config = WorkloadConfig::default();
+        let mut args = args.into_iter();
+
+        while let Some(flag) = args.next() {
+            if flag == "--help" || flag == "-h" {
+                return Ok(None);
+            }
+
+            let value = args
+                .next()
+                .ok_or_else(|| format!("Missing value for {}", flag))?
+                .parse::<usize>()
+                .map_err(|e| format!("Invalid value for {}: {}", flag, e))?;
+
+            match flag.as_str() {
+                "--blobs" => config.blobs_per_slot = value,
+                "--verifications" => config.verifications_per_second = value,
+                "--recovery-every" => config.recovery_every_slots = value,
+                "--slots" => config.slots = value,
+                "--slot-seconds" => config.slot_duration = Duration::from_secs(value as u64),
+                _ => return Err(format!("Unknown argument {}", flag)),
+            }
+        }
+
+        Ok(Some(config))
+    }
+}
+
+impl Default for WorkloadConfig {
+    fn default() -> Self {
+        Self {
+            blobs_per_slot: 6,
+            verifications_per_second: 100,
+            recovery_every_slots: 4,
+            slots: 8,
+            slot_duration: Duration::from_secs(12),
+        }
+    }
+}
+
+/// Operation counts and time spent, summed over all simulated slots
+#[derive(Debug, Clone, Default)]
+pub struct WorkloadReport {
+    pub slots: usize,
+    pub slot_duration: Duration,
+    pub blobs: usize,
+    pub blob_time: Duration,
+    pub verifications: usize,
+    pub verification_time: Duration,
+    pub recoveries: usize,
+    pub recovery_time: Duration,
+    /// Slowest slot, compared against `slot_duration` to find the headroom
+    pub max_slot_time: Duration,
+    /// Peak resident set size in KiB, if the platform reports it
+    pub peak_rss_kib: Option<u64>,
+}
+
+fn per_second(count: usize, time: Duration) -> f64 {
+    if time.is_zero() {
+        return 0.0;
+    }
+    count as f64 / time.as_secs_f64()
+}
+
+impl fmt::Display for WorkloadReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "slots simulated:      {}", self.slots)?;
+        writeln!(
+            f,
+            "blobs:                {} in {:?} ({:.2} blobs/s)",
+            self.blobs,
+            self.blob_time,
+            per_second(self.blobs, self.blob_time)
+        )?;
+        writeln!(
+            f,
+            "proof verifications:  {} in {:?} ({:.2} verifications/s)",
+            self.verifications,
+            self.verification_time,
+            per_second(self.verifications, self.verification_time)
+        )?;
+        writeln!(
+            f,
+            "recoveries:           {} in {:?} ({:.2} recoveries/s)",
+            self.recoveries,
+            self.recovery_time,
+            per_second(self.recoveries, self.recovery_time)
+        )?;
+        writeln!(
+            f,
+            "slowest slot:         {:?} of {:?} ({:.1}% busy)",
+            self.max_slot_time,
+            self.slot_duration,
+            100.0 * self.max_slot_time.as_secs_f64() / self.slot_duration.as_secs_f64()
+        )?;
+        match self.peak_rss_kib {
+            Some(kib) => write!(f, "peak RSS:             {} MiB", kib / 1024),
+            None => write!(f, "peak RSS:             unavailable"),
+        }
+    }
+}
+
+/// Peak resident set size of the current process in KiB (Linux only)
+pub fn peak_rss_kib() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmHWM:"))
+        .and_then(|value| value.trim().trim_end_matches("kB").trim().parse().ok())
+}
+
+/// Entry point of the `workload_simulator` examples: run the workload given on the command line
+/// against backend `B` and print the report
+pub fn workload_simulator_main<B: KzgBackend>()
+where
+    B::Poly: PolyRecover<B::Fr, B::Poly, B::FFTSettings>,
+    B::KZGSettings: Sync,
+{
+    let config = match WorkloadConfig::from_args(env::args().skip(1)) {
+        Ok(Some(config)) => config,
+        Ok(None) => {
+            println!("{}", USAGE);
+            exit(0);
+        }
+        Err(err) => {
+            eprintln!("{}\n{}", err, USAGE);
+            exit(2);
+        }
+    };
+
+    println!("backend:              {}", B::NAME);
+    println!("{:?}", config);
+
+    let report = simulate_workload::<B>(&config).unwrap_or_else(|err| {
+        eprintln!("Simulation failed: {}", err);
+        exit(1);
+    });
+
+    println!("{}", report);
+}
+
+/// Run the workload described by `config` against backend `B` and report the time spent on each
+/// kind of operation.
+///
+/// Every slot commits to, proves and batch-verifies `blobs_per_slot` random blobs, then runs
+/// `verifications_per_second * slot_duration` single-point proof verifications. Every
+/// `recovery_every_slots` slots, one blob is extended to twice its size, half of the extension is
+/// erased and the blob is recovered from the rest.
+pub fn simulate_workload<B: KzgBackend>(config: &WorkloadConfig) -> Result<WorkloadReport, String>
+where
+    B::Poly: PolyRecover<B::Fr, B::Poly, B::FFTSettings>,
+    B::KZGSettings: Sync,
+{
+    set_trusted_setup_dir();
+    let ts = B::load_trusted_setup_file(TRUSTED_SETUP_PATH)?;
+    // Extended blobs are twice as large as the blob itself
+    let recovery_fs = B::new_fft_settings(FIELD_ELEMENTS_PER_BLOB.trailing_zeros() as usize + 1)?;
+    let mut rng = rand::thread_rng();
+
+    let verifications_per_slot =
+        (config.verifications_per_second as f64 * config.slot_duration.as_secs_f64()) as usize;
+
+    let mut report = WorkloadReport {
+        slots: config.slots,
+        slot_duration: config.slot_duration,
+        ..Default::default()
+    };
+
+    for slot in 0..config.slots {
+        let slot_start = Instant::now();
+
+        let blobs = (0..config.blobs_per_slot)
+            .map(|_| bytes_to_blob(&generate_random_blob_bytes(&mut rng)))
+            .collect::<Result<Vec<Vec<B::Fr>>, String>>()?;
+
+        // Blob publication: commit, prove and batch-verify
+        let start = Instant::now();
+        let commitments = blobs
+            .iter()
+            .map(|blob| blob_to_kzg_commitment_rust(blob, &ts))
+            .collect::<Result<Vec<B::G1>, String>>()?;
+        let proofs = blobs
+            .iter()
+            .zip(commitments.iter())
+            .map(|(blob, commitment)| compute_blob_kzg_proof_rust(blob, commitment, &ts))
+            .collect::<Result<Vec<B::G1>, String>>()?;
+        if !verify_blob_kzg_proof_batch_rust(&blobs, &commitments, &proofs, &ts)? {
+            return Err(String::from("Blob proof batch failed to verify"));
+        }
+        report.blob_time += start.elapsed();
+        report.blobs += blobs.len();
+
+        // Point openings, verified round-robin over the blobs of this slot
+        if !blobs.is_empty() && verifications_per_slot > 0 {
+            let z = B::Fr::from_bytes(&generate_random_field_element_bytes(&mut rng))?;
+            let openings = blobs
+                .iter()
+                .map(|blob| compute_kzg_proof_rust(blob, &z, &ts))
+                .collect::<Result<Vec<(B::G1, B::Fr)>, String>>()?;
+
+            let start = Instant::now();
+            for i in 0..verifications_per_slot {
+                let (proof, y) = &openings[i % openings.len()];
+                if !verify_kzg_proof_rust(&commitments[i % openings.len()], &z, y, proof, &ts)? {
+                    return Err(String::from("KZG proof failed to verify"));
+                }
+            }
+            report.verification_time += start.elapsed();
+            report.verifications += verifications_per_slot;
+        }
+
+        if config.recovery_every_slots != 0
+            && (slot + 1) % config.recovery_every_slots == 0
+            && !blobs.is_empty()
+        {
+            let mut coeffs = blobs[0].clone();
+            coeffs.resize(recovery_fs.get_max_width(), B::Fr::zero());
+            let extended = recovery_fs.fft_fr(&coeffs, false)?;
+
+            let mut samples = extended.iter().copied().map(Some).collect::<Vec<_>>();
+            let mut erased = 0;
+            while erased < samples.len() / 2 {
+                let j = rng.gen_range(0..samples.len());
+                if samples[j].take().is_some() {
+                    erased += 1;
+                }
+            }
+
+            let start = Instant::now();
+            let recovered = B::Poly::recover_poly_from_samples(&samples, &recovery_fs)?;
+            report.recovery_time += start.elapsed();
+            report.recoveries += 1;
+
+            if (0..extended.len()).any(|i| !recovered.get_coeff_at(i).equals(&extended[i])) {
+                return Err(String::from("Recovered data does not match the original"));
+            }
+        }
+
+        report.max_slot_time = report.max_slot_time.max(slot_start.elapsed());
+    }
+
+    report.peak_rss_kib = peak_rss_kib();
+    Ok(report)
+}
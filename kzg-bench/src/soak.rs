@@ -0,0 +1,308 @@
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use kzg::eip_4844::{
+    blob_to_kzg_commitment_rust, bytes_to_blob, compute_blob_kzg_proof_rust,
+    compute_kzg_proof_rust, verify_blob_kzg_proof_batch_rust, verify_blob_kzg_proof_rust,
+    verify_kzg_proof_rust, FIELD_ELEMENTS_PER_BLOB, TRUSTED_SETUP_PATH,
+};
+use kzg::{
+    FFTFr, FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1LinComb, G1Mul, KZGSettings, PairingVerify,
+    Poly, PolyRecover, G1, G2,
+};
+use rand::rngs::ThreadRng;
+use rand::Rng;
+
+use crate::set_trusted_setup_dir;
+use crate::simulator::peak_rss_kib;
+use crate::tests::eip_4844::{generate_random_blob_bytes, generate_random_field_element_bytes};
+
+/// Shape of the soak test
+#[derive(Debug, Clone)]
+pub struct SoakConfig {
+    /// Threads sharing the settings
+    pub threads: usize,
+    /// Stop once this much time has passed
+    pub duration: Duration,
+    /// Stop once every thread has run this many operations, 0 for no limit
+    pub operations_per_thread: usize,
+    /// Blobs whose commitments and proofs are computed up front and checked by every thread
+    pub reference_blobs: usize,
+}
+
+impl Default for SoakConfig {
+    fn default() -> Self {
+        Self {
+            threads: thread::available_parallelism().map_or(4, |n| n.get()),
+            duration: Duration::from_secs(600),
+            operations_per_thread: 0,
+            reference_blobs: 4,
+        }
+    }
+}
+
+/// Operations run by the soak test, picked uniformly at random
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SoakOperation {
+    /// Commit to a reference blob and compare with its reference commitment
+    Commit,
+    /// Prove a reference blob, compare with its reference proof and verify it
+    BlobProof,
+    /// Open a reference blob at a random point and verify the opening
+    PointProof,
+    /// Batch-verify all reference proofs, then again with two proofs swapped
+    BatchVerify,
+    /// Recover the extension of a reference blob from half of its samples
+    Recover,
+}
+
+const OPERATIONS: [SoakOperation; 5] = [
+    SoakOperation::Commit,
+    SoakOperation::BlobProof,
+    SoakOperation::PointProof,
+    SoakOperation::BatchVerify,
+    SoakOperation::Recover,
+];
+
+/// Operation counts, summed over all threads
+#[derive(Debug, Default)]
+pub struct SoakReport {
+    pub threads: usize,
+    pub elapsed: Duration,
+    pub operations: [usize; OPERATIONS.len()],
+    /// Peak resident set size in KiB, if the platform reports it
+    pub peak_rss_kib: Option<u64>,
+}
+
+impl SoakReport {
+    pub fn total_operations(&self) -> usize {
+        self.operations.iter().sum()
+    }
+}
+
+impl fmt::Display for SoakReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "operations:           {} on {} threads in {:?}",
+            self.total_operations(),
+            self.threads,
+            self.elapsed
+        )?;
+        for (operation, count) in OPERATIONS.iter().zip(&self.operations) {
+            writeln!(f, "  {:<20}{}", format!("{:?}:", operation), count)?;
+        }
+        match self.peak_rss_kib {
+            Some(kib) => write!(f, "peak RSS:             {} MiB", kib / 1024),
+            None => write!(f, "peak RSS:             unavailable"),
+        }
+    }
+}
+
+struct Reference<TFr, TG1> {
+    blobs: Vec<Vec<TFr>>,
+    commitments: Vec<TG1>,
+    proofs: Vec<TG1>,
+    /// Extension of the first blob to twice its size, for recovery
+    extended: Vec<TFr>,
+}
+
+/// Hammer settings shared by `config.threads` threads with a random mix of commit, prove, verify
+/// and recover operations, and check that every result matches the single-threaded reference.
+///
+/// Commitments and proofs are deterministic, so any difference between threads points at a race
+/// in the thread pool, the precomputation or lazily initialized state. The first violated
+/// invariant stops all threads and is returned as the error, with the thread and operation that
+/// found it.
+pub fn run_soak<
+    TFr: Fr + Copy,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + G1LinComb<TFr, TG1Fp, TG1Affine> + PairingVerify<TG1, TG2>,
+    TG2: G2,
+    TPoly: Poly<TFr>,
+    TFFTSettings: FFTSettings<TFr> + FFTFr<TFr> + Sync,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine> + Sync,
+    TPolyRecover: PolyRecover<TFr, TPoly, TFFTSettings>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    config: &SoakConfig,
+    load_trusted_setup: &dyn Fn(&str) -> Result<TKZGSettings, String>,
+) -> Result<SoakReport, String> {
+    if config.threads == 0 || config.reference_blobs < 2 {
+        return Err(String::from(
+            "Soak test needs at least one thread and two reference blobs",
+        ));
+    }
+
+    set_trusted_setup_dir();
+    let ts = load_trusted_setup(TRUSTED_SETUP_PATH)?;
+    let recovery_fs = TFFTSettings::new(FIELD_ELEMENTS_PER_BLOB.trailing_zeros() as usize + 1)?;
+
+    let mut rng = rand::thread_rng();
+    let blobs = (0..config.reference_blobs)
+        .map(|_| bytes_to_blob(&generate_random_blob_bytes(&mut rng)))
+        .collect::<Result<Vec<Vec<TFr>>, String>>()?;
+    let commitments = blobs
+        .iter()
+        .map(|blob| blob_to_kzg_commitment_rust(blob, &ts))
+        .collect::<Result<Vec<TG1>, String>>()?;
+    let proofs = blobs
+        .iter()
+        .zip(&commitments)
+        .map(|(blob, commitment)| compute_blob_kzg_proof_rust(blob, commitment, &ts))
+        .collect::<Result<Vec<TG1>, String>>()?;
+    let mut coeffs = blobs[0].clone();
+    coeffs.resize(recovery_fs.get_max_width(), TFr::zero());
+    let extended = recovery_fs.fft_fr(&coeffs, false)?;
+    let reference = Reference {
+        blobs,
+        commitments,
+        proofs,
+        extended,
+    };
+
+    let counts = OPERATIONS.map(|_| AtomicUsize::new(0));
+    let stop = AtomicBool::new(false);
+    let failure = Mutex::new(None);
+    let start = Instant::now();
+
+    thread::scope(|scope| {
+        for thread in 0..config.threads {
+            let (ts, recovery_fs, reference) = (&ts, &recovery_fs, &reference);
+            let (counts, stop, failure) = (&counts, &stop, &failure);
+
+            scope.spawn(move || {
+                let mut rng = rand::thread_rng();
+                let mut done = 0;
+                while !stop.load(Ordering::Relaxed)
+                    && start.elapsed() < config.duration
+                    && (config.operations_per_thread == 0 || done < config.operations_per_thread)
+                {
+                    let index = rng.gen_range(0..OPERATIONS.len());
+                    let operation = OPERATIONS[index];
+                    let result =
+                        run_operation::<
+                            TFr,
+                            TG1,
+                            TG2,
+                            TPoly,
+                            TFFTSettings,
+                            TKZGSettings,
+                            TPolyRecover,
+                            TG1Fp,
+                            TG1Affine,
+                        >(operation, ts, recovery_fs, reference, &mut rng);
+
+                    if let Err(err) = result {
+                        stop.store(true, Ordering::Relaxed);
+                        failure.lock().unwrap().get_or_insert(format!(
+                            "Thread {}, operation {:?}: {}",
+                            thread, operation, err
+                        ));
+                        return;
+                    }
+                    counts[index].fetch_add(1, Ordering::Relaxed);
+                    done += 1;
+                }
+            });
+        }
+    });
+
+    if let Some(err) = failure.into_inner().unwrap() {
+        return Err(err);
+    }
+
+    Ok(SoakReport {
+        threads: config.threads,
+        elapsed: start.elapsed(),
+        operations: counts.map(AtomicUsize::into_inner),
+        peak_rss_kib: peak_rss_kib(),
+    })
+}
+
+fn run_operation<
+    TFr: Fr + Copy,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + G1LinComb<TFr, TG1Fp, TG1Affine> + PairingVerify<TG1, TG2>,
+    TG2: G2,
+    TPoly: Poly<TFr>,
+    TFFTSettings: FFTSettings<TFr> + FFTFr<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine> + Sync,
+    TPolyRecover: PolyRecover<TFr, TPoly, TFFTSettings>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    operation: SoakOperation,
+    ts: &TKZGSettings,
+    recovery_fs: &TFFTSettings,
+    reference: &Reference<TFr, TG1>,
+    rng: &mut ThreadRng,
+) -> Result<(), String> {
+    let i = rng.gen_range(0..reference.blobs.len());
+    let blob = &reference.blobs[i];
+    let commitment = &reference.commitments[i];
+
+    match operation {
+        SoakOperation::Commit => {
+            if !blob_to_kzg_commitment_rust(blob, ts)?.equals(commitment) {
+                return Err(String::from("Commitment differs from the reference"));
+            }
+        }
+        SoakOperation::BlobProof => {
+            let proof = compute_blob_kzg_proof_rust(blob, commitment, ts)?;
+            if !proof.equals(&reference.proofs[i]) {
+                return Err(String::from("Blob proof differs from the reference"));
+            }
+            if !verify_blob_kzg_proof_rust(blob, commitment, &proof, ts)? {
+                return Err(String::from("Blob proof failed to verify"));
+            }
+            let other = &reference.commitments[(i + 1) % reference.blobs.len()];
+            if verify_blob_kzg_proof_rust(blob, other, &proof, ts)? {
+                return Err(String::from("Blob proof verified for another commitment"));
+            }
+        }
+        SoakOperation::PointProof => {
+            let z = TFr::from_bytes(&generate_random_field_element_bytes(rng))?;
+            let (proof, y) = compute_kzg_proof_rust(blob, &z, ts)?;
+            if !verify_kzg_proof_rust(commitment, &z, &y, &proof, ts)? {
+                return Err(String::from("KZG proof failed to verify"));
+            }
+            if verify_kzg_proof_rust(commitment, &z, &y.add(&TFr::one()), &proof, ts)? {
+                return Err(String::from("KZG proof verified for a wrong value"));
+            }
+        }
+        SoakOperation::BatchVerify => {
+            let (blobs, commitments) = (&reference.blobs, &reference.commitments);
+            if !verify_blob_kzg_proof_batch_rust(blobs, commitments, &reference.proofs, ts)? {
+                return Err(String::from("Blob proof batch failed to verify"));
+            }
+            let mut proofs = reference.proofs.clone();
+            proofs.swap(0, 1);
+            if verify_blob_kzg_proof_batch_rust(blobs, commitments, &proofs, ts)? {
+                return Err(String::from(
+                    "Blob proof batch verified with swapped proofs",
+                ));
+            }
+        }
+        SoakOperation::Recover => {
+            let extended = &reference.extended;
+            let mut samples = extended.iter().copied().map(Some).collect::<Vec<_>>();
+            let mut erased = 0;
+            while erased < samples.len() / 2 {
+                let j = rng.gen_range(0..samples.len());
+                if samples[j].take().is_some() {
+                    erased += 1;
+                }
+            }
+
+            let recovered = TPolyRecover::recover_poly_from_samples(&samples, recovery_fs)?;
+            if (0..extended.len()).any(|j| !recovered.get_coeff_at(j).equals(&extended[j])) {
+                return Err(String::from("Recovered data does not match the original"));
+            }
+        }
+    }
+
+    Ok(())
+}
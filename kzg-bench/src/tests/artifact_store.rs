@@ -0,0 +1,116 @@
+use kzg::artifact_store::{
+    artifact_digest, cached_table, check_artifact_key, get_setup, get_table, put_setup,
+    ArtifactStore, FsArtifactStore,
+};
+use kzg::msm::msm_impls::msm;
+use kzg::{Fr, G1Affine, G1Fp, G1GetFp, G1Mul, G1ProjAddAffine, G2Mul, G1, G2};
+
+use crate::tests::kzg_proofs::SECRET;
+
+fn temp_store(name: &str) -> (FsArtifactStore, String) {
+    let root = std::env::temp_dir().join(format!("kzg_{}_{}", name, std::process::id()));
+    let root = root.to_str().unwrap().to_string();
+    let _ = std::fs::remove_dir_all(&root);
+
+    (FsArtifactStore::new(&root).unwrap(), root)
+}
+
+pub fn fs_artifact_store_test() {
+    let (store, root) = temp_store("artifact_store");
+    assert!(store.list().unwrap().is_empty());
+    assert_eq!(store.get("missing").unwrap(), None);
+
+    let digest = store.put("setup-4096.bin", b"first").unwrap();
+    assert_eq!(digest, artifact_digest(b"first"));
+    store.put("setup-4096.bin", b"second").unwrap();
+    store.put("table_64", b"table").unwrap();
+    assert_eq!(
+        store.get("setup-4096.bin").unwrap().unwrap(),
+        b"second".to_vec()
+    );
+
+    let list = store.list().unwrap();
+    assert_eq!(list.len(), 2);
+    assert_eq!(list[0].key, "setup-4096.bin");
+    assert_eq!(list[0].size, 6);
+    assert_eq!(list[0].digest, artifact_digest(b"second"));
+    assert_eq!(list[1].key, "table_64");
+
+    // Pinned digests
+    let pinned = artifact_digest(b"table");
+    assert!(store
+        .get_with_digest("table_64", &pinned)
+        .unwrap()
+        .is_some());
+    assert!(store.get_with_digest("table_64", &digest).is_err());
+    assert_eq!(store.get_with_digest("missing", &pinned).unwrap(), None);
+
+    // Bytes changed behind the back of the store are rejected
+    std::fs::write(format!("{}/table_64", root), b"tablf").unwrap();
+    assert!(store.get("table_64").is_err());
+
+    for key in ["", ".hidden", "../escape", "a/b", "a b"] {
+        assert!(check_artifact_key(key).is_err());
+        assert!(store.put(key, b"").is_err());
+        assert!(store.get(key).is_err());
+    }
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+pub fn artifact_store_setup_test<TFr: Fr, TG1: G1 + G1Mul<TFr>, TG2: G2 + G2Mul<TFr>>(
+    generate_trusted_setup: &dyn Fn(usize, [u8; 32usize]) -> (Vec<TG1>, Vec<TG2>),
+) {
+    let (store, root) = temp_store("artifact_store_setup");
+    let (expected_g1, expected_g2) = generate_trusted_setup(16, SECRET);
+
+    assert!(get_setup::<TG1, TG2>(&store, "setup").unwrap().is_none());
+    put_setup(&store, "setup", &expected_g1, &expected_g2).unwrap();
+    let (g1, g2) = get_setup::<TG1, TG2>(&store, "setup").unwrap().unwrap();
+    assert_eq!(g1.len(), 16);
+    assert!(g1.iter().zip(&expected_g1).all(|(a, b)| a.equals(b)));
+    assert!(g2.iter().zip(&expected_g2).all(|(a, b)| a.equals(b)));
+
+    std::fs::remove_dir_all(root).unwrap();
+}
+
+pub fn artifact_store_table_test<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+    TProjAddAffine: G1ProjAddAffine<TG1, TG1Fp, TG1Affine>,
+>() {
+    let (store, root) = temp_store("artifact_store_table");
+    let npoints = 64;
+    let points = (0..npoints).map(|_| TG1::rand()).collect::<Vec<_>>();
+    let scalars = (0..npoints).map(|_| TFr::rand()).collect::<Vec<_>>();
+    let expected =
+        msm::<TG1, TG1Fp, TG1Affine, TProjAddAffine, TFr>(&points, &scalars, npoints, None);
+
+    let built =
+        unsafe { cached_table::<TFr, TG1, TG1Fp, TG1Affine>(&store, "table", &points) }.unwrap();
+    if built.is_none() {
+        // Tables are compiled out, nothing is stored
+        assert!(store.list().unwrap().is_empty());
+        std::fs::remove_dir_all(&root).unwrap();
+        return;
+    }
+    assert_eq!(store.list().unwrap().len(), 1);
+
+    // Second call reads the stored table, even for other points
+    let loaded = unsafe { cached_table::<TFr, TG1, TG1Fp, TG1Affine>(&store, "table", &[]) }
+        .unwrap()
+        .unwrap();
+    let actual = msm::<TG1, TG1Fp, TG1Affine, TProjAddAffine, TFr>(
+        &points,
+        &scalars,
+        npoints,
+        Some(&loaded),
+    );
+    assert!(actual.equals(&expected));
+    let loaded = unsafe { get_table::<TFr, TG1, TG1Fp, TG1Affine>(&store, "table") }.unwrap();
+    assert!(loaded.is_some());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,100 @@
+use kzg::backend::{Backend, KzgBackend};
+use kzg::capabilities::{Capabilities, MsmStrategy, PrecomputeStrategy};
+use kzg::{FFTSettings, Fr, KZGSettings, Poly, G1, G2};
+
+use crate::tests::kzg_proofs::SECRET;
+use crate::tests::utils::get_trusted_setup_path;
+
+/// Commit to and open a polynomial using only the types of the bundle `B`
+#[allow(clippy::type_complexity)]
+pub fn backend_bundle_proof_single<B: Backend>(
+    generate_trusted_setup: &dyn Fn(usize, [u8; 32usize]) -> (Vec<B::G1>, Vec<B::G2>),
+) {
+    let coeffs = [1, 2, 3, 4, 7, 7, 7, 7, 13, 13, 13, 13, 13, 13, 13, 13];
+    let mut p = B::Poly::new(coeffs.len());
+    for (i, &coeff) in coeffs.iter().enumerate() {
+        p.set_coeff_at(i, &B::Fr::from_u64(coeff));
+    }
+
+    let (s1, s2) = generate_trusted_setup(coeffs.len() + 1, SECRET);
+    let fs = B::FFTSettings::new(4).unwrap();
+    let ks = B::KZGSettings::new(&s1, &s2, coeffs.len() + 1, &fs).unwrap();
+
+    let x = B::Fr::from_u64(25);
+    let commitment = ks.commit_to_poly(&p).unwrap();
+    let proof = ks.compute_proof_single(&p, &x).unwrap();
+    let value = p.eval(&x);
+
+    assert!(ks
+        .check_proof_single(&commitment, &proof, &x, &value)
+        .unwrap());
+    assert!(!ks
+        .check_proof_single(&commitment, &proof, &x, &value.add(&B::Fr::one()))
+        .unwrap());
+}
+
+/// Check the shared constructors of `KzgBackend` against the functions of the backend
+#[allow(clippy::type_complexity)]
+pub fn kzg_backend_constructors<B: KzgBackend>(
+    generate_trusted_setup: &dyn Fn(usize, [u8; 32usize]) -> (Vec<B::G1>, Vec<B::G2>),
+) {
+    let (s1, s2) = B::generate_trusted_setup(17, SECRET);
+    let (expected_s1, expected_s2) = generate_trusted_setup(17, SECRET);
+    assert!(s1.iter().zip(&expected_s1).all(|(a, b)| a.equals(b)));
+    assert!(s2.iter().zip(&expected_s2).all(|(a, b)| a.equals(b)));
+
+    let fs = B::new_fft_settings(4).unwrap();
+    let ks = B::new_kzg_settings(&s1, &s2, 17, &fs).unwrap();
+    assert_eq!(ks.get_g1_secret().len(), 17);
+
+    let ks = B::load_trusted_setup_file(&get_trusted_setup_path()).unwrap();
+    assert_eq!(ks.get_fft_settings().get_max_width(), 4096);
+    let contents = std::fs::read(get_trusted_setup_path()).unwrap();
+    let reader: &mut dyn std::io::Read = &mut contents.as_slice();
+    let ks = B::load_trusted_setup_reader(reader).unwrap();
+    assert_eq!(ks.get_fft_settings().get_max_width(), 4096);
+    assert!(B::load_trusted_setup_reader(&mut &contents[..100]).is_err());
+    assert!(B::load_trusted_setup(&[], &[]).is_err());
+
+    backend_bundle_proof_single::<B>(&B::generate_trusted_setup);
+}
+
+/// Check the build configuration reported for the backend `B`
+pub fn kzg_backend_capabilities<B: KzgBackend>() {
+    let capabilities = B::capabilities();
+    assert_eq!(capabilities.backend, Some(B::NAME));
+    assert_eq!(kzg::capabilities().backend, None);
+    assert_eq!(
+        Capabilities {
+            backend: None,
+            ..capabilities
+        },
+        kzg::capabilities()
+    );
+
+    // Features this crate forwards to `kzg`
+    assert!(capabilities.std);
+    assert_eq!(capabilities.nonblocking, cfg!(feature = "async"));
+    assert_eq!(capabilities.gpu, cfg!(feature = "gpu"));
+    assert_eq!(capabilities.mmap, cfg!(feature = "mmap"));
+    assert_eq!(
+        capabilities.msm == MsmStrategy::ParallelPippenger,
+        capabilities.parallel
+    );
+    if capabilities.precompute == PrecomputeStrategy::Bgmw {
+        assert_ne!(capabilities.msm, MsmStrategy::Arkmsm);
+    }
+
+    let line = capabilities.to_string();
+    assert!(line.starts_with(&format!(
+        "backend={} curve=BLS12-381 features=std,",
+        B::NAME
+    )));
+    for (feature, enabled) in [
+        ("async", cfg!(feature = "async")),
+        ("gpu", cfg!(feature = "gpu")),
+        ("mmap", cfg!(feature = "mmap")),
+    ] {
+        assert_eq!(line.split([' ', ',']).any(|word| word == feature), enabled);
+    }
+}
@@ -0,0 +1,71 @@
+use kzg::batch_inverse::{batch_inverse, batch_inverse_fr, to_affines_batch, BATCH_INVERSE_CHUNK};
+use kzg::{Fr, G1Affine, G1Fp, G1GetFp, G1};
+
+pub fn batch_inverse_fr_test<TFr: Fr + Copy + Send>() {
+    let len = BATCH_INVERSE_CHUNK * 2 + 3;
+    let values = (0..len)
+        .map(|i| {
+            if i % 97 == 0 {
+                TFr::zero()
+            } else {
+                TFr::rand()
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut inverses = values.clone();
+    batch_inverse_fr(&mut inverses);
+    for (value, inverse) in values.iter().zip(&inverses) {
+        if value.is_zero() {
+            assert!(inverse.is_zero());
+        } else {
+            assert!(value.mul(inverse).is_one());
+        }
+    }
+
+    let mut empty: Vec<TFr> = Vec::new();
+    batch_inverse_fr(&mut empty);
+}
+
+pub fn batch_inverse_fp_test<TG1: G1 + G1GetFp<TFp>, TFp: G1Fp>() {
+    let len = BATCH_INVERSE_CHUNK + 5;
+    let values = (0..len)
+        .map(|i| {
+            if i % 31 == 0 {
+                TFp::ZERO
+            } else {
+                *TG1::rand().x()
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut inverses = values.clone();
+    batch_inverse(&mut inverses);
+    for (value, inverse) in values.iter().zip(&inverses) {
+        if value.is_zero() {
+            assert!(inverse.is_zero());
+        } else {
+            assert!(value.mul_fp(inverse).is_one());
+        }
+    }
+}
+
+pub fn to_affines_batch_test<TG1: G1, TFp: G1Fp, TG1Affine: G1Affine<TG1, TFp>>() {
+    let len = BATCH_INVERSE_CHUNK * 2 + 7;
+    let points = (0..len)
+        .map(|i| {
+            if i % 101 == 0 {
+                TG1::identity()
+            } else {
+                TG1::rand()
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let affines = to_affines_batch::<TG1, TFp, TG1Affine>(&points);
+    assert_eq!(affines.len(), len);
+    for (point, affine) in points.iter().zip(&affines) {
+        assert_eq!(*affine, TG1Affine::into_affine(point));
+        assert!(affine.to_proj().equals(point));
+    }
+}
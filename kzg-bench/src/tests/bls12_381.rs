@@ -1,7 +1,10 @@
 use kzg::{
-    msm::precompute::PrecomputationTable, Fr, G1Affine, G1Fp, G1GetFp, G1Mul, G2Mul, G1, G2,
+    error::KzgError, msm::precompute::PrecomputationTable, Compression, Fr, G1Affine, G1Fp,
+    G1GetFp, G1Mul, G2Mul, Scalar256, G1, G2,
 };
 use std::convert::TryInto;
+use std::fmt::{Display, LowerHex};
+use std::str::FromStr;
 
 pub fn log_2_byte_works(log_2_byte: &dyn Fn(u8) -> usize) {
     assert_eq!(0, log_2_byte(0x01));
@@ -113,6 +116,24 @@ pub fn fr_uint64s_roundtrip<TFr: Fr>() {
     assert_eq!(expected[3], actual[3]);
 }
 
+pub fn fr_scalar256_batch_roundtrip<TFr: Fr + Send>() {
+    let frs = (0..100)
+        .map(|_| TFr::rand())
+        .chain([TFr::zero(), TFr::one(), TFr::one().negate()])
+        .collect::<Vec<_>>();
+
+    let scalars = Scalar256::from_fr_batch(&frs);
+    assert_eq!(scalars.len(), frs.len());
+    assert!(scalars.iter().zip(&frs).all(|(s, fr)| *s == fr.to_scalar()));
+
+    let back = Scalar256::to_fr_batch::<TFr>(&scalars);
+    assert!(back.iter().zip(&frs).all(|(a, b)| a.equals(b)));
+    assert!(Scalar256::from_u64_s(7)
+        .to_fr::<TFr>()
+        .equals(&TFr::from_u64(7)));
+    assert!(Scalar256::from_fr_batch::<TFr>(&[]).is_empty());
+}
+
 pub fn p1_mul_works<TFr: Fr, TG1: G1 + G1Mul<TFr>>() {
     let m1: [u64; 4] = [
         0xffffffff00000000,
@@ -165,6 +186,105 @@ pub fn g1_identity_is_identity<TG1: G1>() {
     assert!(actual.equals(&TG1::generator()));
 }
 
+pub fn g1_batch_serialization_roundtrip<TFr: Fr, TG1: G1 + G1Mul<TFr>>() {
+    let mut points: Vec<TG1> = (1..10)
+        .map(|i| TG1::generator().mul(&TFr::from_u64(i)))
+        .collect();
+    points.push(TG1::identity());
+
+    for compression in [Compression::Compressed, Compression::Uncompressed] {
+        let bytes = TG1::batch_to_bytes(&points, compression).unwrap();
+        assert_eq!(bytes.len(), points.len() * compression.g1_size());
+
+        let decoded = TG1::batch_from_bytes(&bytes, compression).unwrap();
+        assert_eq!(decoded.len(), points.len());
+        assert!(decoded.iter().zip(&points).all(|(a, b)| a.equals(b)));
+
+        assert!(TG1::batch_from_bytes(&bytes[1..], compression).is_err());
+        assert!(TG1::batch_from_bytes(&[], compression).unwrap().is_empty());
+
+        // The error names the point that failed to decode
+        let mut corrupted = bytes.clone();
+        let size = compression.g1_size();
+        corrupted[3 * size..4 * size].fill(0xff);
+        let err = TG1::batch_from_bytes(&corrupted, compression).unwrap_err();
+        assert!(
+            matches!(err, KzgError::Deserialization { index: 3, .. }),
+            "{}",
+            err
+        );
+        assert!(err.to_string().starts_with("Point 3:"), "{}", err);
+    }
+
+    // The compressed batch is the concatenation of the single point encodings
+    let bytes = TG1::batch_to_bytes(&points, Compression::Compressed).unwrap();
+    assert!(bytes
+        .chunks(48)
+        .zip(&points)
+        .all(|(chunk, point)| chunk == point.to_bytes()));
+}
+
+pub fn g2_batch_serialization_roundtrip<TFr: Fr, TG2: G2 + G2Mul<TFr>>() {
+    let points: Vec<TG2> = (1..10)
+        .map(|i| TG2::generator().mul(&TFr::from_u64(i)))
+        .collect();
+
+    let bytes = TG2::batch_to_bytes(&points);
+    assert_eq!(bytes.len(), points.len() * 96);
+    assert!(bytes
+        .chunks(96)
+        .zip(&points)
+        .all(|(chunk, point)| chunk == point.to_bytes()));
+
+    let decoded = TG2::batch_from_bytes(&bytes).unwrap();
+    assert_eq!(decoded.len(), points.len());
+    assert!(decoded.iter().zip(&points).all(|(a, b)| a.equals(b)));
+
+    assert!(TG2::batch_from_bytes(&bytes[1..]).is_err());
+    assert!(TG2::batch_from_bytes(&[]).unwrap().is_empty());
+
+    // The error names the point that failed to decode
+    let mut corrupted = bytes.clone();
+    corrupted[3 * 96..4 * 96].fill(0xff);
+    let err = TG2::batch_from_bytes(&corrupted).err().unwrap();
+    assert!(
+        matches!(err, KzgError::Deserialization { index: 3, .. }),
+        "{}",
+        err
+    );
+    assert!(err.to_string().starts_with("Point 3:"), "{}", err);
+}
+
+pub fn hex_format_roundtrip<
+    TFr: Fr + Display + LowerHex + FromStr<Err = String>,
+    TG1: G1 + G1Mul<TFr> + Display + LowerHex + FromStr<Err = String>,
+>() {
+    let fr = TFr::from_u64(0x1234);
+    let hex = fr.to_string();
+    assert_eq!(hex, format!("0x{}1234", "0".repeat(60)));
+    assert_eq!(format!("{:#x}", fr), hex);
+    assert_eq!(format!("{:x}", fr), hex[2..]);
+    assert!(hex.parse::<TFr>().unwrap().equals(&fr));
+    assert!(hex[2..].parse::<TFr>().unwrap().equals(&fr));
+
+    let point = TG1::generator().mul(&fr);
+    let hex = point.to_string();
+    assert_eq!(hex, format!("0x{}", hex::encode(point.to_bytes())));
+    assert_eq!(format!("{:#x}", point), hex);
+    assert!(hex.parse::<TG1>().unwrap().equals(&point));
+    assert_eq!(
+        TG1::identity().to_string(),
+        format!("0xc0{}", "0".repeat(94))
+    );
+
+    // Invalid hex, wrong length and values outside of the field or the curve
+    assert!("0xzz".parse::<TFr>().is_err());
+    assert!("0x1234".parse::<TFr>().is_err());
+    assert!(format!("0x{}", "ff".repeat(32)).parse::<TFr>().is_err());
+    assert!(hex[..hex.len() - 2].parse::<TG1>().is_err());
+    assert!(format!("0x{}", "ff".repeat(48)).parse::<TG1>().is_err());
+}
+
 #[allow(clippy::type_complexity)]
 pub fn g1_make_linear_combination<
     TFr: Fr,
@@ -0,0 +1,96 @@
+use kzg::bundle::{
+    bundle_inclusion_proof, bundle_openings, bundle_root, verify_bundle_inclusion,
+    verify_bundle_opening_rust, verify_bundle_rust,
+};
+use kzg::eip_4844::{blob_to_kzg_commitment_rust, bytes_to_blob, compute_blob_kzg_proof_rust};
+use kzg::{
+    FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1LinComb, G1Mul, KZGSettings, PairingVerify, Poly,
+    G1, G2,
+};
+
+use crate::tests::eip_4844::generate_random_blob_bytes;
+use crate::tests::utils::get_trusted_setup_path;
+
+pub fn bundle_inclusion_test<TG1: G1>() {
+    let commitments = (1..=5u64)
+        .map(|i| {
+            let mut point = TG1::generator();
+            for _ in 1..i {
+                point = point.add_or_dbl(&TG1::generator());
+            }
+            point
+        })
+        .collect::<Vec<_>>();
+    let root = bundle_root(&commitments).unwrap();
+
+    for (i, commitment) in commitments.iter().enumerate() {
+        let proof = bundle_inclusion_proof(&commitments, i).unwrap();
+        assert_eq!(proof.siblings.len(), 3);
+        assert!(verify_bundle_inclusion(&root, commitment, &proof));
+
+        // The same path does not prove any other commitment
+        let other = &commitments[(i + 1) % commitments.len()];
+        assert!(!verify_bundle_inclusion(&root, other, &proof));
+    }
+
+    // Padding leaves and out-of-range indices are rejected
+    let mut proof = bundle_inclusion_proof(&commitments, 4).unwrap();
+    proof.index = 12;
+    assert!(!verify_bundle_inclusion(&root, &commitments[4], &proof));
+    assert!(bundle_inclusion_proof(&commitments, 5).is_err());
+    assert!(bundle_root::<TG1>(&[]).is_err());
+
+    // A single commitment is its own bundle
+    let single = bundle_inclusion_proof(&commitments[..1], 0).unwrap();
+    assert!(single.siblings.is_empty());
+    assert!(verify_bundle_inclusion(
+        &bundle_root(&commitments[..1]).unwrap(),
+        &commitments[0],
+        &single
+    ));
+}
+
+pub fn bundle_opening_test<
+    TFr: Fr + Copy,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + G1LinComb<TFr, TG1Fp, TG1Affine> + PairingVerify<TG1, TG2>,
+    TG2: G2,
+    TPoly: Poly<TFr>,
+    TFFTSettings: FFTSettings<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine> + Sync,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    load_trusted_setup: &dyn Fn(&str) -> Result<TKZGSettings, String>,
+) {
+    let ts = load_trusted_setup(get_trusted_setup_path().as_str()).unwrap();
+    let mut rng = rand::thread_rng();
+
+    let blobs: Vec<Vec<TFr>> = (0..3)
+        .map(|_| bytes_to_blob(&generate_random_blob_bytes(&mut rng)).unwrap())
+        .collect();
+    let commitments: Vec<TG1> = blobs
+        .iter()
+        .map(|blob| blob_to_kzg_commitment_rust(blob, &ts).unwrap())
+        .collect();
+    let proofs: Vec<TG1> = blobs
+        .iter()
+        .zip(commitments.iter())
+        .map(|(blob, commitment)| compute_blob_kzg_proof_rust(blob, commitment, &ts).unwrap())
+        .collect();
+
+    let root = bundle_root(&commitments).unwrap();
+    let openings = bundle_openings(&commitments, &proofs).unwrap();
+
+    for (blob, opening) in blobs.iter().zip(openings.iter()) {
+        assert!(verify_bundle_opening_rust(&root, blob, opening, &ts).unwrap());
+    }
+    // An opening of one blob does not verify another blob
+    assert!(!verify_bundle_opening_rust(&root, &blobs[1], &openings[0], &ts).unwrap());
+
+    assert!(verify_bundle_rust(&root, &blobs, &commitments, &proofs, &ts).unwrap());
+
+    // Reordering the bundle changes its root
+    let mut swapped = commitments.clone();
+    swapped.swap(0, 1);
+    assert!(!verify_bundle_rust(&root, &blobs, &swapped, &proofs, &ts).unwrap());
+}
@@ -2,14 +2,16 @@ use std::{
     ffi::{CStr, CString},
     fs::File,
     io::Read,
+    mem::{align_of, size_of, MaybeUninit},
     path::PathBuf,
-    ptr::null_mut,
+    ptr::{addr_of, null_mut},
 };
 
 use kzg::eip_4844::{
-    load_trusted_setup_string, Blob, Bytes48, CKZGSettings, KZGCommitment, KZGProof,
-    BYTES_PER_COMMITMENT, BYTES_PER_FIELD_ELEMENT, BYTES_PER_G1, BYTES_PER_G2, BYTES_PER_PROOF,
-    C_KZG_RET, C_KZG_RET_BADARGS, C_KZG_RET_OK,
+    blst_fr, blst_p1, blst_p2, load_trusted_setup_string, Blob, Bytes32, Bytes48, CKZGSettings,
+    KZGCommitment, KZGProof, BYTES_PER_BLOB, BYTES_PER_COMMITMENT, BYTES_PER_FIELD_ELEMENT,
+    BYTES_PER_G1, BYTES_PER_G2, BYTES_PER_PROOF, C_KZG_RET, C_KZG_RET_BADARGS, C_KZG_RET_ERROR,
+    C_KZG_RET_MALLOC, C_KZG_RET_OK,
 };
 use libc::FILE;
 
@@ -568,3 +570,245 @@ pub fn compute_blob_kzg_proof_zero_input_test(
 
     assert_eq!(out, C_KZG_RET_OK);
 }
+
+#[allow(clippy::type_complexity)]
+pub fn blob_to_kzg_commitments_sg_test(
+    blob_to_kzg_commitment: unsafe extern "C" fn(
+        out: *mut KZGCommitment,
+        blob: *const Blob,
+        s: &CKZGSettings,
+    ) -> C_KZG_RET,
+    blob_to_kzg_commitments_sg: unsafe extern "C" fn(
+        out: *const *mut KZGCommitment,
+        blobs: *const *const Blob,
+        n: usize,
+        s: &CKZGSettings,
+    ) -> C_KZG_RET,
+    load_trusted_setup_file: unsafe extern "C" fn(
+        out: *mut CKZGSettings,
+        in_: *mut FILE,
+    ) -> C_KZG_RET,
+) {
+    let settings = get_ckzg_settings(load_trusted_setup_file);
+
+    let mut rng = rand::thread_rng();
+    let blobs = (0..3)
+        .map(|_| {
+            Box::new(Blob {
+                bytes: generate_random_blob_bytes(&mut rng),
+            })
+        })
+        .collect::<Vec<_>>();
+    let mut commitments = (0..3)
+        .map(|_| KZGCommitment {
+            bytes: [0; BYTES_PER_COMMITMENT],
+        })
+        .collect::<Vec<_>>();
+
+    let blob_ptrs = blobs
+        .iter()
+        .map(|blob| &**blob as *const Blob)
+        .collect::<Vec<_>>();
+    let out_ptrs = commitments
+        .iter_mut()
+        .map(|commitment| commitment as *mut KZGCommitment)
+        .collect::<Vec<_>>();
+    let ret =
+        unsafe { blob_to_kzg_commitments_sg(out_ptrs.as_ptr(), blob_ptrs.as_ptr(), 3, &settings) };
+    assert_eq!(ret, C_KZG_RET_OK);
+
+    for (blob, commitment) in blobs.iter().zip(&commitments) {
+        let mut expected = KZGCommitment {
+            bytes: [0; BYTES_PER_COMMITMENT],
+        };
+        assert_eq!(
+            unsafe { blob_to_kzg_commitment(&mut expected, &**blob, &settings) },
+            C_KZG_RET_OK
+        );
+        assert_eq!(commitment.bytes, expected.bytes);
+    }
+
+    // Null pointers are rejected
+    let mut null_ptrs = blob_ptrs.clone();
+    null_ptrs[1] = std::ptr::null();
+    let ret =
+        unsafe { blob_to_kzg_commitments_sg(out_ptrs.as_ptr(), null_ptrs.as_ptr(), 3, &settings) };
+    assert_eq!(ret, C_KZG_RET_BADARGS);
+    let ret =
+        unsafe { blob_to_kzg_commitments_sg(out_ptrs.as_ptr(), std::ptr::null(), 3, &settings) };
+    assert_eq!(ret, C_KZG_RET_BADARGS);
+
+    // So are repeated outputs
+    let mut repeated_ptrs = out_ptrs.clone();
+    repeated_ptrs[2] = repeated_ptrs[0];
+    let ret = unsafe {
+        blob_to_kzg_commitments_sg(repeated_ptrs.as_ptr(), blob_ptrs.as_ptr(), 3, &settings)
+    };
+    assert_eq!(ret, C_KZG_RET_BADARGS);
+    let ret =
+        unsafe { blob_to_kzg_commitments_sg(std::ptr::null(), std::ptr::null(), 0, &settings) };
+    assert_eq!(ret, C_KZG_RET_OK);
+}
+
+#[allow(clippy::type_complexity)]
+pub fn compute_blob_kzg_proofs_sg_test(
+    blob_to_kzg_commitment: unsafe extern "C" fn(
+        out: *mut KZGCommitment,
+        blob: *const Blob,
+        s: &CKZGSettings,
+    ) -> C_KZG_RET,
+    compute_blob_kzg_proof: unsafe extern "C" fn(
+        out: *mut KZGProof,
+        blob: *const Blob,
+        commitment_bytes: *const Bytes48,
+        s: &CKZGSettings,
+    ) -> C_KZG_RET,
+    compute_blob_kzg_proofs_sg: unsafe extern "C" fn(
+        out: *const *mut KZGProof,
+        blobs: *const *const Blob,
+        commitments_bytes: *const *const Bytes48,
+        n: usize,
+        s: &CKZGSettings,
+    ) -> C_KZG_RET,
+    load_trusted_setup_file: unsafe extern "C" fn(
+        out: *mut CKZGSettings,
+        in_: *mut FILE,
+    ) -> C_KZG_RET,
+) {
+    let settings = get_ckzg_settings(load_trusted_setup_file);
+
+    let mut rng = rand::thread_rng();
+    let mut blobs = (0..3)
+        .map(|_| {
+            Box::new(Blob {
+                bytes: generate_random_blob_bytes(&mut rng),
+            })
+        })
+        .collect::<Vec<_>>();
+    let commitments = blobs
+        .iter()
+        .map(|blob| {
+            let mut commitment = KZGCommitment {
+                bytes: [0; BYTES_PER_COMMITMENT],
+            };
+            assert_eq!(
+                unsafe { blob_to_kzg_commitment(&mut commitment, &**blob, &settings) },
+                C_KZG_RET_OK
+            );
+            Bytes48 {
+                bytes: commitment.bytes,
+            }
+        })
+        .collect::<Vec<_>>();
+    let mut proofs = (0..3)
+        .map(|_| KZGProof {
+            bytes: [0; BYTES_PER_PROOF],
+        })
+        .collect::<Vec<_>>();
+
+    let commitment_ptrs = commitments
+        .iter()
+        .map(|commitment| commitment as *const Bytes48)
+        .collect::<Vec<_>>();
+    let out_ptrs = proofs
+        .iter_mut()
+        .map(|proof| proof as *mut KZGProof)
+        .collect::<Vec<_>>();
+    let blob_ptrs = blobs
+        .iter()
+        .map(|blob| &**blob as *const Blob)
+        .collect::<Vec<_>>();
+    let ret = unsafe {
+        compute_blob_kzg_proofs_sg(
+            out_ptrs.as_ptr(),
+            blob_ptrs.as_ptr(),
+            commitment_ptrs.as_ptr(),
+            3,
+            &settings,
+        )
+    };
+    assert_eq!(ret, C_KZG_RET_OK);
+
+    for ((blob, commitment), proof) in blobs.iter().zip(&commitments).zip(&proofs) {
+        let mut expected = KZGProof {
+            bytes: [0; BYTES_PER_PROOF],
+        };
+        assert_eq!(
+            unsafe { compute_blob_kzg_proof(&mut expected, &**blob, commitment, &settings) },
+            C_KZG_RET_OK
+        );
+        assert_eq!(proof.bytes, expected.bytes);
+    }
+
+    // Repeated outputs are rejected
+    let mut repeated_ptrs = out_ptrs.clone();
+    repeated_ptrs[1] = repeated_ptrs[2];
+    let ret = unsafe {
+        compute_blob_kzg_proofs_sg(
+            repeated_ptrs.as_ptr(),
+            blob_ptrs.as_ptr(),
+            commitment_ptrs.as_ptr(),
+            3,
+            &settings,
+        )
+    };
+    assert_eq!(ret, C_KZG_RET_BADARGS);
+
+    // One invalid blob fails the whole call
+    blobs[2].bytes[0..BYTES_PER_FIELD_ELEMENT].copy_from_slice(&[0xff; BYTES_PER_FIELD_ELEMENT]);
+    let blob_ptrs = blobs
+        .iter()
+        .map(|blob| &**blob as *const Blob)
+        .collect::<Vec<_>>();
+    let ret = unsafe {
+        compute_blob_kzg_proofs_sg(
+            out_ptrs.as_ptr(),
+            blob_ptrs.as_ptr(),
+            commitment_ptrs.as_ptr(),
+            3,
+            &settings,
+        )
+    };
+    assert_eq!(ret, C_KZG_RET_BADARGS);
+}
+
+/// Layout of the structs shared with the c-kzg-4844 headers, on 64-bit targets
+pub fn c_abi_layout_test() {
+    assert_eq!(size_of::<Bytes32>(), 32);
+    assert_eq!(size_of::<Bytes48>(), 48);
+    assert_eq!(size_of::<Blob>(), BYTES_PER_BLOB);
+    assert_eq!(size_of::<KZGCommitment>(), 48);
+    assert_eq!(size_of::<KZGProof>(), 48);
+    assert_eq!(align_of::<Blob>(), 1);
+
+    // fr_t, g1_t and g2_t are the blst types
+    assert_eq!(size_of::<blst_fr>(), 32);
+    assert_eq!(size_of::<blst_p1>(), 144);
+    assert_eq!(size_of::<blst_p2>(), 288);
+
+    // C_KZG_RET is a C enum
+    assert_eq!(size_of::<C_KZG_RET>(), 4);
+    assert_eq!(
+        [
+            C_KZG_RET_OK,
+            C_KZG_RET_BADARGS,
+            C_KZG_RET_ERROR,
+            C_KZG_RET_MALLOC
+        ],
+        [0, 1, 2, 3]
+    );
+
+    // { uint64_t max_width; fr_t *roots_of_unity; g1_t *g1_values; g2_t *g2_values; }
+    let settings = MaybeUninit::<CKZGSettings>::uninit();
+    let base = settings.as_ptr() as usize;
+    let offsets = unsafe {
+        [
+            addr_of!((*settings.as_ptr()).max_width) as usize - base,
+            addr_of!((*settings.as_ptr()).roots_of_unity) as usize - base,
+            addr_of!((*settings.as_ptr()).g1_values) as usize - base,
+            addr_of!((*settings.as_ptr()).g2_values) as usize - base,
+        ]
+    };
+    assert_eq!(offsets, [0, 8, 16, 24]);
+    assert_eq!(size_of::<CKZGSettings>(), 32);
+}
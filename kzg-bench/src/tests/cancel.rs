@@ -0,0 +1,86 @@
+use kzg::cancel::{CancelToken, Cancelled};
+use kzg::eip_4844::{
+    blob_to_kzg_commitment_rust, bytes_to_blob, compute_blob_kzg_proof_rust,
+    verify_blob_kzg_proof_batch_cancellable_rust,
+};
+use kzg::msm::msm_impls::msm;
+use kzg::msm::precompute::precompute;
+use kzg::{
+    FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1LinComb, G1Mul, G1ProjAddAffine, KZGSettings,
+    PairingVerify, Poly, G1, G2,
+};
+
+use crate::tests::eip_4844::generate_random_blob_bytes;
+use crate::tests::utils::get_trusted_setup_path;
+
+pub fn cancel_msm_test<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+    TProjAddAffine: G1ProjAddAffine<TG1, TG1Fp, TG1Affine>,
+>() {
+    let npoints = 300;
+    let points = (0..npoints as u64)
+        .map(|i| TG1::generator().mul(&TFr::from_u64(i + 1)))
+        .collect::<Vec<_>>();
+    let scalars = (0..npoints as u64)
+        .map(|i| TFr::from_u64(7 * i + 3))
+        .collect::<Vec<_>>();
+    let Some(table) = precompute::<TFr, TG1, TG1Fp, TG1Affine>(&points).unwrap() else {
+        return;
+    };
+    let scalars_256 = scalars.iter().map(TFr::to_scalar).collect::<Vec<_>>();
+
+    let token = CancelToken::new();
+    let expected =
+        msm::<TG1, TG1Fp, TG1Affine, TProjAddAffine, TFr>(&points, &scalars, npoints, None);
+    let actual = table.multiply_cancellable(&scalars_256, &token).unwrap();
+    assert!(actual.equals(&expected));
+
+    // Cancelling a clone cancels the token
+    token.clone().cancel();
+    assert!(token.is_cancelled());
+    assert!(matches!(
+        table.multiply_cancellable(&scalars_256, &token),
+        Err(Cancelled)
+    ));
+}
+
+pub fn cancel_batch_verification_test<
+    TFr: Fr + Copy,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + G1LinComb<TFr, TG1Fp, TG1Affine> + PairingVerify<TG1, TG2>,
+    TG2: G2,
+    TPoly: Poly<TFr>,
+    TFFTSettings: FFTSettings<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine> + Sync,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    load_trusted_setup: &dyn Fn(&str) -> Result<TKZGSettings, String>,
+) {
+    let ts = load_trusted_setup(get_trusted_setup_path().as_str()).unwrap();
+    let mut rng = rand::thread_rng();
+
+    let blobs = (0..3)
+        .map(|_| bytes_to_blob::<TFr>(&generate_random_blob_bytes(&mut rng)).unwrap())
+        .collect::<Vec<_>>();
+    let commitments = blobs
+        .iter()
+        .map(|blob| blob_to_kzg_commitment_rust(blob, &ts).unwrap())
+        .collect::<Vec<_>>();
+    let proofs = blobs
+        .iter()
+        .zip(&commitments)
+        .map(|(blob, commitment)| compute_blob_kzg_proof_rust(blob, commitment, &ts).unwrap())
+        .collect::<Vec<_>>();
+
+    let token = CancelToken::new();
+    let verify = |token: &CancelToken| {
+        verify_blob_kzg_proof_batch_cancellable_rust(&blobs, &commitments, &proofs, token, &ts)
+    };
+    assert_eq!(verify(&token), Ok(true));
+
+    token.cancel();
+    assert_eq!(verify(&token), Err(Cancelled.to_string()));
+}
@@ -0,0 +1,67 @@
+use kzg::ceremony::{
+    contribution_secret, verify_contribution, verify_contribution_chain, ContributionProof,
+    PowersOfTau,
+};
+use kzg::{Fr, G1Mul, G2Mul, PairingVerify, G1, G2};
+
+pub fn ceremony_contribution_test<
+    TFr: Fr + Copy,
+    TG1: G1 + G1Mul<TFr> + PairingVerify<TG1, TG2>,
+    TG2: G2 + G2Mul<TFr>,
+>(
+    generate_trusted_setup: &dyn Fn(usize, [u8; 32usize]) -> (Vec<TG1>, Vec<TG2>),
+) {
+    let initial = PowersOfTau::<TG1, TG2>::initial(16, 5).unwrap();
+    assert!(initial.verify::<TFr>().unwrap());
+
+    let mut setups = vec![initial.clone()];
+    let mut proofs = Vec::new();
+    for entropy in [[1u8; 32], [2u8; 32], [3u8; 32]] {
+        let (next, proof) = setups.last().unwrap().contribute::<TFr>(&entropy).unwrap();
+        assert!(
+            verify_contribution::<TFr, TG1, TG2>(setups.last().unwrap(), &next, &proof).unwrap()
+        );
+        setups.push(next);
+        proofs.push(proof);
+    }
+    let last = setups.last().unwrap();
+    assert!(verify_contribution_chain::<TFr, TG1, TG2>(&initial, &proofs, last).unwrap());
+
+    // The final secret is the product of the contributions
+    let secret = [[1u8; 32], [2u8; 32], [3u8; 32]]
+        .iter()
+        .map(|entropy| contribution_secret::<TFr>(entropy).unwrap())
+        .fold(TFr::one(), |acc, x| acc.mul(&x));
+    let (expected_g1, expected_g2) = generate_trusted_setup(16, secret.to_bytes());
+    assert!(last.g1.iter().zip(&expected_g1).all(|(a, b)| a.equals(b)));
+    assert!(last.g2.iter().zip(&expected_g2).all(|(a, b)| a.equals(b)));
+
+    // Proofs out of order, missing, replayed or of another secret
+    let swapped = [proofs[1].clone(), proofs[0].clone(), proofs[2].clone()];
+    assert!(!verify_contribution_chain::<TFr, TG1, TG2>(&initial, &swapped, last).unwrap());
+    assert!(!verify_contribution_chain::<TFr, TG1, TG2>(&initial, &proofs[1..], last).unwrap());
+    let replayed = [proofs[0].clone(), proofs[0].clone()];
+    assert!(!verify_contribution_chain::<TFr, TG1, TG2>(&initial, &replayed, &setups[1]).unwrap());
+    let forged = ContributionProof {
+        pubkey: TG2::generator().mul(&TFr::from_u64(7)),
+        tau_g1: setups[1].g1[1].clone(),
+    };
+    assert!(!verify_contribution::<TFr, TG1, TG2>(&initial, &setups[1], &forged).unwrap());
+    assert!(!verify_contribution::<TFr, TG1, TG2>(&setups[1], &setups[3], &proofs[2]).unwrap());
+
+    // A setup tampered with after the contribution
+    let mut tampered = last.clone();
+    tampered.g1[9] = tampered.g1[8].clone();
+    assert!(!verify_contribution::<TFr, TG1, TG2>(&setups[2], &tampered, &proofs[2]).unwrap());
+    assert!(!verify_contribution_chain::<TFr, TG1, TG2>(&initial, &proofs, &tampered).unwrap());
+
+    // A zero secret would erase the previous contributions
+    assert!(setups[1].contribute_with_secret(&TFr::zero()).is_err());
+    assert!(initial.contribute::<TFr>(&[1u8; 31]).is_err());
+    let truncated = PowersOfTau {
+        g1: last.g1[..8].to_vec(),
+        g2: last.g2.clone(),
+    };
+    assert!(verify_contribution_chain::<TFr, TG1, TG2>(&initial, &proofs, &truncated).is_err());
+    assert!(PowersOfTau::<TG1, TG2>::initial(1, 5).is_err());
+}
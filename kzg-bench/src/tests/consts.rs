@@ -1,4 +1,4 @@
-use kzg::{FFTSettings, Fr};
+use kzg::{FFTSettings, Fr, G1, G2};
 
 pub fn roots_of_unity_is_the_expected_size(roots: &[[u64; 4]; 32]) {
     assert_eq!(roots.len(), 32);
@@ -58,3 +58,34 @@ pub fn new_fft_settings_is_plausible<TFr: Fr, TFFTSettings: FFTSettings<TFr>>()
         assert!(prod.is_one());
     }
 }
+
+/// Check that the trait-level roots of unity match the ones of the FFT settings
+pub fn root_of_unity_matches_fft_settings<TFr: Fr, TFFTSettings: FFTSettings<TFr>>() {
+    for scale in 1..8 {
+        let order: usize = 1 << scale;
+        let fft_settings = TFFTSettings::new(scale).unwrap();
+        let root = TFr::root_of_unity(order).unwrap();
+        assert!(root.equals(&fft_settings.get_expanded_roots_of_unity_at(1)));
+
+        // Primitive: the root reaches -1 halfway
+        assert!(root.pow(order / 2).equals(&TFr::one().negate()));
+        assert!(root.pow(order).is_one());
+    }
+
+    assert!(TFr::root_of_unity(1).unwrap().is_one());
+    assert!(TFr::root_of_unity(0).is_err());
+    assert!(TFr::root_of_unity(12).is_err());
+    assert!(TFr::root_of_unity(1 << 32).is_err());
+}
+
+/// Check that the trait-level generators are consistent with their negations
+pub fn generators_are_plausible<TG1: G1, TG2: G2>() {
+    assert!(TG1::generator()
+        .add_or_dbl(&TG1::negative_generator())
+        .is_inf());
+    assert!(TG1::generator().is_valid());
+    assert!(!TG2::generator().equals(&TG2::negative_generator()));
+    assert!(TG2::generator()
+        .sub(&TG2::negative_generator())
+        .equals(&TG2::generator().dbl()));
+}
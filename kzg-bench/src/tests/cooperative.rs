@@ -0,0 +1,50 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use kzg::cooperative::set_yield_handler;
+use kzg::msm::tiling_pippenger_ops::tiling_pippenger;
+use kzg::{FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1Mul, FFTG1, G1};
+
+static YIELDS: AtomicUsize = AtomicUsize::new(0);
+
+fn count_yield() {
+    YIELDS.fetch_add(1, Ordering::Relaxed);
+}
+
+pub fn yield_handler_test<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TFFTSettings: FFTSettings<TFr> + FFTG1<TG1>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>() {
+    let points = (0..64u64)
+        .map(|i| TG1::generator().mul(&TFr::from_u64(i + 1)))
+        .collect::<Vec<_>>();
+    let affines = TG1Affine::into_affines(&points);
+    let scalars = (0..64u64)
+        .map(|i| TFr::from_u64(3 * i + 7).to_scalar())
+        .collect::<Vec<_>>();
+    let fs = TFFTSettings::new(6).unwrap();
+
+    set_yield_handler(Some(count_yield));
+    let with_handler = tiling_pippenger::<TG1, TG1Fp, TG1Affine>(&affines, &scalars);
+    let fft_with_handler = fs.fft_g1(&points, false).unwrap();
+    set_yield_handler(None);
+
+    let yields = YIELDS.swap(0, Ordering::Relaxed);
+    if kzg::capabilities().parallel {
+        assert_eq!(yields, 0);
+    } else {
+        assert!(yields > 0);
+    }
+
+    // The handler does not change the results, and is not called once removed
+    let without_handler = tiling_pippenger::<TG1, TG1Fp, TG1Affine>(&affines, &scalars);
+    assert!(with_handler.equals(&without_handler));
+    let fft_without_handler = fs.fft_g1(&points, false).unwrap();
+    assert!(fft_with_handler
+        .iter()
+        .zip(&fft_without_handler)
+        .all(|(a, b)| a.equals(b)));
+    assert_eq!(YIELDS.load(Ordering::Relaxed), 0);
+}
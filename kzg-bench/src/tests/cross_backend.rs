@@ -0,0 +1,164 @@
+use kzg::backend::KzgBackend;
+use kzg::eip_4844::{
+    blob_to_kzg_commitment_rust, bytes_to_blob, compute_blob_kzg_proof_rust,
+    compute_kzg_proof_rust, verify_blob_kzg_proof_batch_rust, verify_blob_kzg_proof_rust,
+    verify_kzg_proof_rust,
+};
+use kzg::golden::{bytes_to_hex, seeded_blob_bytes};
+use kzg::msm::precompute::precompute;
+use kzg::{FFTFr, Fr, G1LinComb, G1Mul, KZGSettings, Poly, FFTG1, G1};
+
+use crate::tests::utils::get_trusted_setup_path;
+
+/// Outputs of `cross_backend_golden_test`, as computed by blst. Every backend must produce the same
+/// bytes; when an output changes on purpose, the assertion message gives the new value
+pub const CROSS_BACKEND_VECTORS: [(&str, &str); 9] = [
+    (
+        "fft_fr",
+        "0x09d8eeb4cdc978ea8d62be51c7c94e1fe64d34c4d1b17316b2b3140be165ace90360f2387ba70811328a9cf0f3e20c7fd5342a0e86ca131ea09f96235955b25807ef20b8ec305e8a326d19f1eed646c8c7237e5a7f8fe9868b368204acf80d686c7f9afaddb92f7e85683ed78e7a802f73166012a4bfd121b59c655bc5519bf535fe2b089d2ba497210c10d1f34de84efbae8dff16ae05c1f12f5821cb1cbdab60ac9ae706724ae00ef6ce5c8786376b9bbfbc29245bf1d232645841156b48104f05135bd82467c767138d0ae15ecc610100d2f3f61b543d16e12bee96fb73fd4c4b5dc1fe2778c6ccdac731d8222a552d01f73af2e47e81384ec4639ad2b38d00b89da453d2ce700254dd2e08013a5b4ea2ee166c3fc70fa6e56b21e890d90d228d3e7b8b3cba01e2969bb78f564ca7a642edb5cb06a7bb05228fb4d91a23b84afd7efb9867fd61386a629ec17b05356c1dbd7ca36043787f6920bedb4279a25c5b930ebb0d185e9dbb84a3fd6e04639491f52d4237c0c03995765cf039f57b3bd9768256076968ac8ca083b2c3f1a4745caaa855d351bcec6c7a8da2ab7d682d6a92c07bc2f9893925582e271975d0b25a816653181a0507c85a9e69578e3845306be41948276d82ad4f883e9a076eb7908d4784eebe5ab5de50094fab2fb3024ab4691f6d4b8c098966e0f5b04dc2320b7a2f4e7211c99a8ff6464168928f",
+    ),
+    (
+        "fft_g1",
+        "0x9718567efc4776425b17ac2450ae0c117fdf6e9eeeabb4ede117f86bee413b31b2c07cf82e38c6ecaf14001453ce29d0a1c1f4e6268185eab115436011c6480f08a86f7060b910cbe579c7044b166f3ada98e0027726af1fc6e649e6d4db8210a970a703c36c173a76212ecccfc7442ea1c0b43b97a2688654cb066d56c89f715f4b5cae8e873bdb689070af66127326b30c13c4389eaa51417eb289b0497f95373d44f067fb397ab40b1c0372b3364416c677a0dbb7b5b11583f55ec3ac8423a283c624b2c75c487f6a3896ba5dbfaece574eea3002e413b289a9bee39ed4c3a5244d52611d3a6c72c88ef0abdce030933c0f3f89bf75c1cf0ede824a48ec31b1c4871eefbc71d6ce8998c2cbf2ef12337593cc613cf3735763c2d783ad3d6085dbe0202d6a3eb89d4863e3b1d0116cfbafb2d83b3bf010aa7bd8ddaef151234694bb5038a88e9b1bbe08409a46eeb59868734bad2f6f0bd47c4328f79a6efe163c92bd71fae8d7d420ed9eed651462fb703724e8118d03e47ca2f70d5e6bff885ae765588126f5e860d019c0e26235f567a9c0c0b2d8ff30f3e8d436b1082596e5e7462d20f5be3764fd473e57f9cf932e8263b6525d73ae6816240669a257684af838324e50cd7e07e5ed4f5bdbf2d62681692653e232ae96014646035cd8b20f5157ade5112914eba46880587b46020d0afc53f33ed8ab11936298fc29528b5f4d82a8ba2b416d5f688d0a24ddc8a4712ee0518845e46c77718fcea8c44bde141e6ed0f4178df3c35fd5d8daa85125171560c8e574f576174c87b596ccac931b352b9695b2f41fd811f103ce7d017cf31505787326dea077f3698e314c39fc690e248beee15c6e391e98febfede394a6c3bb413b5d4b57021a087e238252e9062557643d67ef195b966bd05fe10fd0c0854b52d0a7ebe6b77bc54bb15b869667d115174bd9f20bd80c2740583e51af70de8dca0566fd57ca4deb82413966a1d0d53c89338fe3c390c87423dd4293a369ac92ab5dd1d94ee0b0b6bbb84798e9ef80ada135b40dd334b6858950f8e55b77d6fcd8c4bc8a788945f581c99474",
+    ),
+    (
+        "g1_lincomb",
+        "0xa1845b30b9fc62f9afad73dab68d1a6e568bb3f8b9713f7d6230f9b4bf6f6c548aa13570d59dace8d8bbbc89e1987dda",
+    ),
+    (
+        "commitment",
+        "0xa29455e45eb377fa0def839073a70e0411d93fedf60d65aac90b09c15425735b91dbc22c7ab60507d495a010ea0d8107",
+    ),
+    (
+        "proof_single",
+        "0x8caa2d907fa244c5e9d371489d95cec6a14b76e4173421682c6c211d274a68180415f04794a669357d7617fb629b0099",
+    ),
+    (
+        "blob_commitment",
+        "0x8defb6fdb5eb30817691803c212e7dc721a07748f057b881212e82e7539d081d6d628b019185efa0230d53b08af5170c",
+    ),
+    (
+        "blob_proof",
+        "0x8e07510e15e80438992fabb790ba3ccaaf345a80e12530a53bebe09f6d2635fda80dcc3bdedec5b47dd62ea9f785018d",
+    ),
+    (
+        "kzg_proof",
+        "0x8c0650463c9faffeb334ea9958baab82cee55ff59a37a803e6ba4ad295883aa0d39c1fdb0d9ace5b08003947be048ab8",
+    ),
+    (
+        "kzg_proof_y",
+        "0x18205eb4ce8f390d283fcd79886ae6ba0ad65dc35b6781e5d348fcd428d24746",
+    ),
+];
+
+/// `n` field elements derived from `seed`, see `seeded_blob_bytes`
+fn seeded_frs<TFr: Fr>(seed: u64, n: usize) -> Vec<TFr> {
+    seeded_blob_bytes(seed)
+        .chunks(32)
+        .take(n)
+        .map(|bytes| TFr::from_bytes(bytes).unwrap())
+        .collect()
+}
+
+/// `(i + 1) * G` for `i` below `n`
+fn generator_multiples<B: KzgBackend>(n: usize) -> Vec<B::G1> {
+    (0..n as u64)
+        .map(|i| B::G1::generator().mul(&B::Fr::from_u64(i + 1)))
+        .collect()
+}
+
+/// Run FFTs, MSMs, commitments, proofs and their verification, and the EIP-4844 blob functions
+/// on fixed seeds, and check their serialized outputs against `CROSS_BACKEND_VECTORS`, so that
+/// all backends are checked to agree byte for byte
+pub fn cross_backend_golden_test<B: KzgBackend>()
+where
+    B::Fr: Copy,
+    B::KZGSettings: Sync,
+{
+    let mut outputs: Vec<(&str, String)> = Vec::new();
+    let mut record = |name, bytes: Vec<u8>| outputs.push((name, bytes_to_hex(&bytes)));
+
+    // FFTs, and their inverses back to the input
+    let fs = B::new_fft_settings(4).unwrap();
+    let data = seeded_frs::<B::Fr>(1, 16);
+    let transformed = fs.fft_fr(&data, false).unwrap();
+    let inverse = fs.fft_fr(&transformed, true).unwrap();
+    assert!(inverse.iter().zip(&data).all(|(a, b)| a.equals(b)));
+    record(
+        "fft_fr",
+        transformed.iter().flat_map(Fr::to_bytes).collect(),
+    );
+
+    let points = generator_multiples::<B>(16);
+    let transformed = fs.fft_g1(&points, false).unwrap();
+    let inverse = fs.fft_g1(&transformed, true).unwrap();
+    assert!(inverse.iter().zip(&points).all(|(a, b)| a.equals(b)));
+    record(
+        "fft_g1",
+        transformed.iter().flat_map(G1::to_bytes).collect(),
+    );
+
+    // MSM, without and with a precomputation table
+    let points = generator_multiples::<B>(64);
+    let scalars = seeded_frs::<B::Fr>(2, 64);
+    let lincomb = B::G1::g1_lincomb(&points, &scalars, points.len(), None);
+    if let Some(table) = precompute(&points).unwrap() {
+        let with_table = B::G1::g1_lincomb(&points, &scalars, points.len(), Some(&table));
+        assert!(with_table.equals(&lincomb));
+    }
+    record("g1_lincomb", lincomb.to_bytes().to_vec());
+
+    // Commitment and opening of a polynomial, with the insecure development setup
+    let (s1, s2) = B::generate_insecure_dev_setup(17);
+    let ks = B::new_kzg_settings(&s1, &s2, 17, &fs).unwrap();
+    let mut poly = B::Poly::new(16);
+    for (i, coeff) in seeded_frs::<B::Fr>(3, 16).iter().enumerate() {
+        poly.set_coeff_at(i, coeff);
+    }
+    let x = seeded_frs::<B::Fr>(4, 1)[0];
+    let commitment = ks.commit_to_poly(&poly).unwrap();
+    let proof = ks.compute_proof_single(&poly, &x).unwrap();
+    let value = poly.eval(&x);
+    assert!(ks
+        .check_proof_single(&commitment, &proof, &x, &value)
+        .unwrap());
+    assert!(!ks
+        .check_proof_single(&commitment, &proof, &x, &value.add(&B::Fr::one()))
+        .unwrap());
+    record("commitment", commitment.to_bytes().to_vec());
+    record("proof_single", proof.to_bytes().to_vec());
+
+    // EIP-4844 blob commitment and proofs, with the mainnet setup
+    let ts = B::load_trusted_setup_file(&get_trusted_setup_path()).unwrap();
+    let blob = bytes_to_blob::<B::Fr>(&seeded_blob_bytes(5)).unwrap();
+    let commitment = blob_to_kzg_commitment_rust(&blob, &ts).unwrap();
+    let proof = compute_blob_kzg_proof_rust(&blob, &commitment, &ts).unwrap();
+    assert!(verify_blob_kzg_proof_rust(&blob, &commitment, &proof, &ts).unwrap());
+    assert!(verify_blob_kzg_proof_batch_rust(
+        &[blob.clone()],
+        &[commitment.clone()],
+        &[proof.clone()],
+        &ts
+    )
+    .unwrap());
+    record("blob_commitment", commitment.to_bytes().to_vec());
+    record("blob_proof", proof.to_bytes().to_vec());
+
+    let z = seeded_frs::<B::Fr>(6, 1)[0];
+    let (proof, y) = compute_kzg_proof_rust(&blob, &z, &ts).unwrap();
+    assert!(verify_kzg_proof_rust(&commitment, &z, &y, &proof, &ts).unwrap());
+    record("kzg_proof", proof.to_bytes().to_vec());
+    record("kzg_proof_y", y.to_bytes().to_vec());
+
+    assert_eq!(outputs.len(), CROSS_BACKEND_VECTORS.len());
+    for ((name, actual), (expected_name, expected)) in outputs.iter().zip(CROSS_BACKEND_VECTORS) {
+        assert_eq!(*name, expected_name);
+        assert_eq!(
+            actual,
+            expected,
+            "{} differs from the golden vector of {}",
+            B::NAME,
+            name
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use kzg::das_matrix::{verify_sample, DasMatrix, SampleAxis, SampleVerifier};
+use kzg::{
+    FFTFr, FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1Mul, G2Mul, KZGSettings, PairingVerify,
+    Poly, DAS, G1, G2,
+};
+
+use crate::tests::kzg_proofs::SECRET;
+use crate::tests::validation::off_subgroup_encoding;
+
+pub fn das_matrix_sample_test<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG2: G2,
+    TPoly: Poly<TFr>,
+    TFFTSettings: FFTSettings<TFr> + FFTFr<TFr> + DAS<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    generate_trusted_setup: &dyn Fn(usize, [u8; 32usize]) -> (Vec<TG1>, Vec<TG2>),
+) {
+    let k = 4;
+    let (s1, s2) = generate_trusted_setup(k + 1, SECRET);
+    let fs = TFFTSettings::new(3).unwrap();
+    let ks = TKZGSettings::new(&s1, &s2, k + 1, &fs).unwrap();
+
+    let data = (0..(k * k) as u64)
+        .map(|i| TFr::from_u64(i * i + 7))
+        .collect::<Vec<_>>();
+    let matrix: DasMatrix<TFr, TG1, TPoly> = DasMatrix::new(&data, k, &ks).unwrap();
+    let commitments = matrix.commitments();
+    assert_eq!(commitments.rows.len(), 2 * k);
+    assert_eq!(commitments.columns.len(), 2 * k);
+
+    // Original data sits at even indices
+    for i in 0..k {
+        for j in 0..k {
+            assert!(matrix.get(2 * i, 2 * j).unwrap().equals(&data[i * k + j]));
+        }
+    }
+    assert!(matrix.get(2 * k, 0).is_none());
+
+    // Every cell opens against both its row and its column, including the extended ones
+    for row in 0..2 * k {
+        for column in 0..2 * k {
+            for axis in [SampleAxis::Row, SampleAxis::Column] {
+                let sample = matrix.prove_sample(row, column, axis, &ks).unwrap();
+                assert!(verify_sample(commitments, &sample, &ks).unwrap());
+            }
+        }
+    }
+
+    // Wrong values or positions are rejected
+    let mut sample = matrix.prove_sample(3, 5, SampleAxis::Row, &ks).unwrap();
+    sample.value = sample.value.add(&TFr::one());
+    assert!(!verify_sample(commitments, &sample, &ks).unwrap());
+
+    let mut sample = matrix.prove_sample(3, 5, SampleAxis::Column, &ks).unwrap();
+    sample.row = 2;
+    assert!(!verify_sample(commitments, &sample, &ks).unwrap());
+
+    assert!(DasMatrix::<TFr, TG1, TPoly>::new(&data[1..], k, &ks).is_err());
+}
+
+pub fn das_matrix_sample_verifier_test<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + PairingVerify<TG1, TG2>,
+    TG2: G2 + G2Mul<TFr>,
+    TPoly: Poly<TFr>,
+    TFFTSettings: FFTSettings<TFr> + FFTFr<TFr> + DAS<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    generate_trusted_setup: &dyn Fn(usize, [u8; 32usize]) -> (Vec<TG1>, Vec<TG2>),
+) {
+    let k = 4;
+    let (s1, s2) = generate_trusted_setup(k + 1, SECRET);
+    let fs = TFFTSettings::new(3).unwrap();
+    let ks = TKZGSettings::new(&s1, &s2, k + 1, &fs).unwrap();
+
+    let data = (0..(k * k) as u64)
+        .map(|i| TFr::from_u64(3 * i + 1))
+        .collect::<Vec<_>>();
+    let matrix: DasMatrix<TFr, TG1, TPoly> = DasMatrix::new(&data, k, &ks).unwrap();
+    let verifier = SampleVerifier::from_settings(matrix.commitments().clone(), &ks).unwrap();
+
+    let positions = [
+        (0, 0, SampleAxis::Row),
+        (1, 6, SampleAxis::Column),
+        (7, 2, SampleAxis::Row),
+        (5, 5, SampleAxis::Column),
+        (2, 7, SampleAxis::Row),
+    ];
+    let mut samples = positions
+        .iter()
+        .map(|&(row, column, axis)| matrix.prove_sample(row, column, axis, &ks).unwrap())
+        .collect::<Vec<_>>();
+
+    for sample in &samples {
+        assert!(verifier.verify_sample(sample).unwrap());
+    }
+    assert!(verifier.verify_samples_batch(&samples).unwrap());
+    assert!(verifier.verify_samples_batch(&samples[..1]).unwrap());
+    assert!(verifier.verify_samples_batch(&[]).unwrap());
+
+    // A single bad sample fails the whole batch
+    samples[3].value = samples[3].value.add(&TFr::one());
+    assert!(!verifier.verify_sample(&samples[3]).unwrap());
+    assert!(!verifier.verify_samples_batch(&samples).unwrap());
+
+    // Samples swapped between positions are rejected
+    let mut samples = samples[..3].to_vec();
+    let proof = samples[0].proof.clone();
+    samples[0].proof = samples[2].proof.clone();
+    samples[2].proof = proof;
+    assert!(!verifier.verify_samples_batch(&samples).unwrap());
+
+    samples[1].row = 2 * k;
+    assert!(verifier.verify_samples_batch(&samples).is_err());
+
+    // Proofs outside of the subgroup are rejected before any pairing
+    if let Some(bytes) = off_subgroup_encoding(48, TG1::from_bytes) {
+        let mut samples = samples[..2].to_vec();
+        samples[1].row = 1;
+        samples[1].proof = TG1::from_bytes(&bytes).unwrap();
+        assert!(verifier.verify_sample(&samples[1]).is_err());
+        assert!(verifier.verify_samples_batch(&samples).is_err());
+        assert!(verify_sample(matrix.commitments(), &samples[1], &ks).is_err());
+    }
+}
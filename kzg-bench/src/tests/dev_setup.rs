@@ -0,0 +1,243 @@
+use std::sync::Mutex;
+
+use kzg::common_utils::reverse_bit_order;
+use kzg::dev_setup::insecure_dev_setup_used;
+use kzg::diagnostics::{set_warning_handler, Warning};
+use kzg::eip_4844::{verify_kzg_proof_rust, FIELD_ELEMENTS_PER_BLOB};
+use kzg::{
+    FFTFr, FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1LinComb, G1Mul, G2Mul, KZGSettings,
+    PairingVerify, Poly, SetupForm, DAS, FFTG1, G1, G2,
+};
+
+use crate::tests::das_matrix::{das_matrix_sample_test, das_matrix_sample_verifier_test};
+use crate::tests::equivalence::same_polynomial_proof_test;
+use crate::tests::evaluation_cache::evaluation_cache_test;
+use crate::tests::kzg_proofs::SECRET;
+use crate::tests::namespace::namespace_range_proof_test;
+use crate::tests::opening::quotient_commitment_test;
+use crate::tests::sampled_opening::sampled_opening_test;
+use crate::tests::shifted_opening::shifted_opening_test;
+use crate::tests::utils::get_trusted_setup_path;
+use crate::tests::verifier::fixed_size_verifier_test;
+use crate::tests::witness::verify_blob_kzg_proof_witness_test;
+
+static WARNINGS: Mutex<Vec<Warning>> = Mutex::new(Vec::new());
+
+fn record_warning(warning: &Warning) {
+    WARNINGS.lock().unwrap().push(*warning);
+}
+
+pub fn insecure_dev_setup_test<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG2: G2,
+    TPoly: Poly<TFr>,
+    TFFTSettings: FFTSettings<TFr> + FFTG1<TG1>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    generate_trusted_setup: &dyn Fn(usize, [u8; 32usize]) -> (Vec<TG1>, Vec<TG2>),
+    generate_insecure_dev_setup: &dyn Fn(usize) -> (Vec<TG1>, Vec<TG2>),
+    load_trusted_setup: &dyn Fn(&str) -> Result<TKZGSettings, String>,
+) {
+    let fs = TFFTSettings::new(4).unwrap();
+    let (s1, s2) = generate_insecure_dev_setup(16);
+    let dev = TKZGSettings::new(&s1, &s2, 16, &fs).unwrap();
+    assert!(dev.is_insecure_dev_setup());
+    // Truncated or reloaded, the setup keeps its tag
+    assert!(dev
+        .truncate(8, SetupForm::Monomial)
+        .unwrap()
+        .is_insecure_dev_setup());
+    let (s1, s2) = generate_trusted_setup(16, SECRET);
+    let secure = TKZGSettings::new(&s1, &s2, 16, &fs).unwrap();
+    assert!(!secure.is_insecure_dev_setup());
+    let mainnet = load_trusted_setup(get_trusted_setup_path().as_str()).unwrap();
+    assert!(!mainnet.is_insecure_dev_setup());
+
+    let verify = |ks: &TKZGSettings| {
+        let mut p = TPoly::new(16);
+        for i in 0..16 {
+            p.set_coeff_at(i, &TFr::from_u64((i * i + 1) as u64));
+        }
+        let commitment = ks.commit_to_poly(&p).unwrap();
+        let z = TFr::from_u64(5);
+        let proof = ks.compute_proof_single(&p, &z).unwrap();
+        verify_kzg_proof_rust(&commitment, &z, &p.eval(&z), &proof, ks).unwrap()
+    };
+
+    set_warning_handler(Some(record_warning));
+    assert!(verify(&secure));
+    assert!(WARNINGS.lock().unwrap().is_empty());
+    assert!(!insecure_dev_setup_used());
+
+    // Verification still works, but is flagged
+    assert!(verify(&dev));
+    set_warning_handler(None);
+    assert_eq!(*WARNINGS.lock().unwrap(), [Warning::InsecureDevSetup]);
+    assert!(insecure_dev_setup_used());
+}
+
+fn insecure_dev_setup_warnings() -> usize {
+    WARNINGS
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|w| **w == Warning::InsecureDevSetup)
+        .count()
+}
+
+/// Assert that `f` warns about the development setup at least once
+fn assert_warns(path: &str, f: impl FnOnce()) {
+    let before = insecure_dev_setup_warnings();
+    f();
+    assert!(
+        insecure_dev_setup_warnings() > before,
+        "{path} did not warn"
+    );
+}
+
+/// Run every verification path against the development setup, each must warn
+pub fn insecure_dev_setup_paths_test<
+    TFr: Fr + Copy,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + G1LinComb<TFr, TG1Fp, TG1Affine> + PairingVerify<TG1, TG2>,
+    TG2: G2 + G2Mul<TFr>,
+    TPoly: Poly<TFr>,
+    TFFTSettings: FFTSettings<TFr> + FFTFr<TFr> + FFTG1<TG1> + DAS<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    generate_trusted_setup: &dyn Fn(usize, [u8; 32usize]) -> (Vec<TG1>, Vec<TG2>),
+    generate_insecure_dev_setup: &dyn Fn(usize) -> (Vec<TG1>, Vec<TG2>),
+) {
+    // The tests below build the setups they verify against from `SECRET`, equivalence also needs a
+    // second, different one
+    let generate_trusted_setup = |n: usize, secret: [u8; 32]| {
+        if secret == SECRET {
+            generate_insecure_dev_setup(n)
+        } else {
+            generate_trusted_setup(n, secret)
+        }
+    };
+
+    // The blob paths need the Lagrange form of a full size setup
+    let (monomial, s2) = generate_insecure_dev_setup(FIELD_ELEMENTS_PER_BLOB);
+    let blob_fs = TFFTSettings::new(FIELD_ELEMENTS_PER_BLOB.trailing_zeros() as usize).unwrap();
+    let mut lagrange = blob_fs.fft_g1(&monomial, true).unwrap();
+    reverse_bit_order(&mut lagrange).unwrap();
+    let load_trusted_setup =
+        |_: &str| TKZGSettings::new(&lagrange, &s2, FIELD_ELEMENTS_PER_BLOB, &blob_fs);
+
+    set_warning_handler(Some(record_warning));
+
+    let fs = TFFTSettings::new(4).unwrap();
+    let (s1, s2) = generate_insecure_dev_setup(16);
+    let ks = TKZGSettings::new(&s1, &s2, 16, &fs).unwrap();
+    let mut p = TPoly::new(16);
+    for i in 0..16 {
+        p.set_coeff_at(i, &TFr::from_u64((i * i + 1) as u64));
+    }
+    let commitment = ks.commit_to_poly(&p).unwrap();
+    assert_warns("check_proof_single", || {
+        let z = TFr::from_u64(5);
+        let proof = ks.compute_proof_single(&p, &z).unwrap();
+        assert!(ks
+            .check_proof_single(&commitment, &proof, &z, &p.eval(&z))
+            .unwrap());
+    });
+    assert_warns("check_proof_multi", || {
+        let coset_fs = TFFTSettings::new(2).unwrap();
+        let coset_ks = TKZGSettings::new(&s1, &s2, 16, &coset_fs).unwrap();
+        let x = TFr::from_u64(5);
+        let proof = coset_ks.compute_proof_multi(&p, &x, 4).unwrap();
+        let ys: Vec<TFr> = (0..4)
+            .map(|i| p.eval(&x.mul(&coset_ks.get_expanded_roots_of_unity_at(i))))
+            .collect();
+        assert!(coset_ks
+            .check_proof_multi(&commitment, &proof, &x, &ys, 4)
+            .unwrap());
+    });
+
+    assert_warns("opening", || {
+        quotient_commitment_test::<TFr, TG1, TG2, TPoly, TFFTSettings, TKZGSettings, TG1Fp, TG1Affine>(
+            &generate_trusted_setup,
+        )
+    });
+    assert_warns("shifted_opening", || {
+        shifted_opening_test::<TFr, TG1, TG2, TPoly, TFFTSettings, TKZGSettings, TG1Fp, TG1Affine>(
+            &generate_trusted_setup,
+        )
+    });
+    assert_warns("equivalence", || {
+        same_polynomial_proof_test::<
+            TFr,
+            TG1,
+            TG2,
+            TPoly,
+            TFFTSettings,
+            TKZGSettings,
+            TG1Fp,
+            TG1Affine,
+        >(&generate_trusted_setup)
+    });
+    assert_warns("sampled_opening", || {
+        sampled_opening_test::<TFr, TG1, TG2, TPoly, TFFTSettings, TKZGSettings, TG1Fp, TG1Affine>(
+            &generate_trusted_setup,
+        )
+    });
+    assert_warns("das_matrix", || {
+        das_matrix_sample_test::<TFr, TG1, TG2, TPoly, TFFTSettings, TKZGSettings, TG1Fp, TG1Affine>(
+            &generate_trusted_setup,
+        )
+    });
+    assert_warns("SampleVerifier", || {
+        das_matrix_sample_verifier_test::<
+            TFr,
+            TG1,
+            TG2,
+            TPoly,
+            TFFTSettings,
+            TKZGSettings,
+            TG1Fp,
+            TG1Affine,
+        >(&generate_trusted_setup)
+    });
+    assert_warns("namespace", || {
+        namespace_range_proof_test::<
+            TFr,
+            TG1,
+            TG2,
+            TPoly,
+            TFFTSettings,
+            TKZGSettings,
+            TG1Fp,
+            TG1Affine,
+        >(&generate_trusted_setup)
+    });
+    assert_warns("witness", || {
+        verify_blob_kzg_proof_witness_test::<
+            TFr,
+            TG1,
+            TG2,
+            TPoly,
+            TFFTSettings,
+            TKZGSettings,
+            TG1Fp,
+            TG1Affine,
+        >(&load_trusted_setup)
+    });
+    assert_warns("KzgVerifier", || {
+        fixed_size_verifier_test::<TFr, TG1, TG2, TPoly, TFFTSettings, TKZGSettings, TG1Fp, TG1Affine>(
+            &load_trusted_setup,
+        )
+    });
+    assert_warns("EvaluationCache", || {
+        evaluation_cache_test::<TFr, TG1, TG2, TPoly, TFFTSettings, TKZGSettings, TG1Fp, TG1Affine>(
+            &load_trusted_setup,
+        )
+    });
+
+    set_warning_handler(None);
+}
@@ -0,0 +1,101 @@
+use std::sync::Mutex;
+
+use kzg::diagnostics::{
+    report_memory_usage, set_metrics_handler, set_warning_handler, trusted_setup_memory_usage,
+    MemoryComponent, Metric, Warning,
+};
+use kzg::msm::precompute::precompute;
+use kzg::{FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1Mul, KZGSettings, Poly, G1, G2};
+
+use crate::tests::kzg_proofs::SECRET;
+
+static WARNINGS: Mutex<Vec<Warning>> = Mutex::new(Vec::new());
+
+fn record_warning(warning: &Warning) {
+    WARNINGS.lock().unwrap().push(*warning);
+}
+
+pub fn precompute_warning_test<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>() {
+    // 100 points has no tuned window, so either the table is missing or its window is not optimal
+    let points = (0..100u64)
+        .map(|i| TG1::generator().mul(&TFr::from_u64(i + 1)))
+        .collect::<Vec<_>>();
+
+    set_warning_handler(Some(record_warning));
+    let _ = precompute::<TFr, TG1, TG1Fp, TG1Affine>(&points);
+    set_warning_handler(None);
+
+    let warnings = core::mem::take(&mut *WARNINGS.lock().unwrap());
+    assert!(!warnings.is_empty());
+    assert!(warnings.iter().all(|warning| match *warning {
+        Warning::PrecomputationUnavailable { npoints }
+        | Warning::PrecomputationFailed { npoints }
+        | Warning::ParallelFallbackToSequential { npoints, .. }
+        | Warning::SuboptimalWindow { npoints, .. }
+        | Warning::PrecomputationSkipped { npoints } => npoints == points.len(),
+        Warning::MemoryPressure { .. } | Warning::InsecureDevSetup => false,
+    }));
+    assert!(!warnings[0].to_string().is_empty());
+
+    // Without a handler, warnings are dropped
+    let _ = precompute::<TFr, TG1, TG1Fp, TG1Affine>(&points);
+    assert!(WARNINGS.lock().unwrap().is_empty());
+}
+
+static METRICS: Mutex<Vec<Metric>> = Mutex::new(Vec::new());
+
+fn record_metric(metric: &Metric) {
+    METRICS.lock().unwrap().push(*metric);
+}
+
+pub fn memory_usage_test<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG2: G2,
+    TPoly: Poly<TFr>,
+    TFFTSettings: FFTSettings<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    generate_trusted_setup: &dyn Fn(usize, [u8; 32usize]) -> (Vec<TG1>, Vec<TG2>),
+) {
+    let (s1, s2) = generate_trusted_setup(256, SECRET);
+    let fs = TFFTSettings::new(8).unwrap();
+    let ks = TKZGSettings::new(&s1, &s2, 256, &fs).unwrap();
+
+    let fs_bytes = ks.get_fft_settings().memory_usage();
+    assert!(fs_bytes >= 256 * core::mem::size_of::<TFr>());
+    assert!(
+        ks.memory_usage()
+            >= fs_bytes + 256 * (core::mem::size_of::<TG1>() + core::mem::size_of::<TG2>())
+    );
+
+    set_metrics_handler(Some(record_metric));
+    report_memory_usage(&ks);
+    set_metrics_handler(None);
+
+    let metrics = core::mem::take(&mut *METRICS.lock().unwrap());
+    assert_eq!(metrics.len(), 3);
+    let total: usize = metrics
+        .iter()
+        .map(|metric| match *metric {
+            Metric::MemoryUsage { bytes, .. } => bytes,
+        })
+        .sum();
+    assert_eq!(total, ks.memory_usage());
+    assert!(metrics.contains(&Metric::MemoryUsage {
+        component: MemoryComponent::FftSettings,
+        bytes: fs_bytes,
+    }));
+    assert!(metrics.contains(&Metric::MemoryUsage {
+        component: MemoryComponent::TrustedSetup,
+        bytes: trusted_setup_memory_usage(&ks),
+    }));
+    assert!(!metrics[0].to_string().is_empty());
+}
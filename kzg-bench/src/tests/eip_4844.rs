@@ -6,16 +6,28 @@ use crate::test_vectors::{
 };
 use crate::tests::utils::{get_manifest_dir, get_trusted_setup_path};
 use kzg::eip_4844::{
-    BYTES_PER_BLOB, BYTES_PER_COMMITMENT, BYTES_PER_FIELD_ELEMENT, BYTES_PER_PROOF,
-    FIELD_ELEMENTS_PER_BLOB, TRUSTED_SETUP_PATH,
+    blob_from_chunks, blob_to_kzg_commitment_rust, blst_p1, bytes_to_blob,
+    compute_batch_challenge_powers, compute_blob_kzg_proof_rust, compute_challenge,
+    compute_challenges, compute_kzg_proof_rust, kzg_to_versioned_hash, kzg_to_versioned_hashes,
+    load_trusted_setup_json_string, load_trusted_setup_string, verify_batch_openings,
+    verify_blob_kzg_proof_batch_with_domain_rust, verify_blob_kzg_proof_rust,
+    verify_with_precomputed_challenge_rust, CKZGSettings, ChallengeDomain,
+    PrecomputationTableManager, BYTES_PER_BLOB, BYTES_PER_COMMITMENT, BYTES_PER_FIELD_ELEMENT,
+    BYTES_PER_PROOF, FIELD_ELEMENTS_PER_BLOB, TRUSTED_SETUP_PATH, USABLE_BYTES_PER_BLOB,
+    VERSIONED_HASH_VERSION_KZG,
+};
+use kzg::msm::precompute::{precompute, PrecomputationTable};
+use kzg::{
+    FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1LinComb, G1Mul, KZGSettings, PairingVerify, Poly,
+    G1, G2,
 };
-use kzg::{FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1Mul, KZGSettings, Poly, G1, G2};
 use pathdiff::diff_paths;
 use rand::rngs::{OsRng, ThreadRng};
 use rand::Rng;
 use std::env::current_dir;
 use std::fs;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 fn u64_to_bytes(x: u64) -> [u8; 32] {
     let mut bytes = [0u8; 32];
@@ -66,6 +78,73 @@ pub fn bytes_to_bls_field_test<TFr: Fr>() {
     assert_eq!(x, x_fr.to_u64_arr()[0]);
 }
 
+#[allow(clippy::type_complexity)]
+pub fn bytes_to_blobs_test<TFr: Fr>(
+    bytes_to_blobs: &dyn Fn(&[u8]) -> Result<Vec<Vec<TFr>>, String>,
+) {
+    let empty = bytes_to_blobs(&[]).unwrap();
+    assert_eq!(empty.len(), 1);
+    assert!(empty[0].iter().all(|x| x.is_zero()));
+
+    // Every byte value must land in a canonical field element
+    let data = (0..USABLE_BYTES_PER_BLOB + 40)
+        .map(|i| 0xff - (i % 7) as u8)
+        .collect::<Vec<u8>>();
+    let blobs = bytes_to_blobs(&data).unwrap();
+    assert_eq!(blobs.len(), 2);
+    assert!(blobs
+        .iter()
+        .all(|blob| blob.len() == FIELD_ELEMENTS_PER_BLOB));
+
+    // The length comes first, then the data
+    let length = blobs[0][0].to_bytes();
+    assert!(length[..24].iter().all(|&b| b == 0));
+    assert_eq!(length[24..], (data.len() as u64).to_be_bytes());
+
+    let unpacked = blobs
+        .iter()
+        .flatten()
+        .skip(1)
+        .flat_map(|x| x.to_bytes()[1..].to_vec())
+        .collect::<Vec<u8>>();
+    assert_eq!(&unpacked[..data.len()], &data[..]);
+    assert!(unpacked[data.len()..].iter().all(|&b| b == 0));
+
+    // Trailing zeros are part of the data
+    let mut padded = data.clone();
+    padded.push(0);
+    let padded_blobs = bytes_to_blobs(&padded).unwrap();
+    assert!(!padded_blobs[0][0].equals(&blobs[0][0]));
+}
+
+#[allow(clippy::type_complexity)]
+pub fn blob_from_chunks_test<TFr: Fr>(bytes_to_blob: &dyn Fn(&[u8]) -> Result<Vec<TFr>, String>) {
+    let mut rng = rand::thread_rng();
+    let bytes = generate_random_blob_bytes(&mut rng);
+    let expected = bytes_to_blob(&bytes).unwrap();
+
+    // Buffer sizes aligned with field elements, smaller than one and straddling them
+    for size in [BYTES_PER_BLOB, 4096, 32, 31, 45, 1000, 1] {
+        let blob: Vec<TFr> = blob_from_chunks(bytes.chunks(size)).unwrap();
+        assert_eq!(blob.len(), FIELD_ELEMENTS_PER_BLOB);
+        assert!(blob.iter().zip(&expected).all(|(a, b)| a.equals(b)));
+    }
+
+    // Empty buffers in between are skipped
+    let (head, tail) = bytes.split_at(100);
+    let blob: Vec<TFr> = blob_from_chunks([head, &[], tail].into_iter()).unwrap();
+    assert!(blob.iter().zip(&expected).all(|(a, b)| a.equals(b)));
+
+    assert!(blob_from_chunks::<TFr>(bytes[1..].chunks(45)).is_err());
+    assert!(blob_from_chunks::<TFr>(bytes.chunks(45).chain([&[0u8][..]])).is_err());
+    assert!(blob_from_chunks::<TFr>(core::iter::empty()).is_err());
+
+    // A non-canonical field element split across two buffers
+    let mut invalid = bytes;
+    invalid[32..64].copy_from_slice(&[0xff; 32]);
+    assert!(blob_from_chunks::<TFr>(invalid.chunks(45)).is_err());
+}
+
 pub fn compute_powers_test<TFr: Fr>(compute_powers: &dyn Fn(&TFr, usize) -> Vec<TFr>) {
     let x: u64 = 32930439;
     let n = 11;
@@ -524,6 +603,201 @@ pub fn verify_kzg_proof_batch_fails_with_incorrect_proof_test<
     assert!(!result);
 }
 
+pub fn verify_blob_kzg_proof_batch_with_domain_test<
+    TFr: Fr + Copy,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + G1LinComb<TFr, TG1Fp, TG1Affine> + PairingVerify<TG1, TG2>,
+    TG2: G2,
+    TPoly: Poly<TFr>,
+    TFFTSettings: FFTSettings<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine> + Sync,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    load_trusted_setup: &dyn Fn(&str) -> Result<TKZGSettings, String>,
+) {
+    let ts = load_trusted_setup(get_trusted_setup_path().as_str()).unwrap();
+    let mut rng = rand::thread_rng();
+
+    let blobs = (0..3)
+        .map(|_| bytes_to_blob::<TFr>(&generate_random_blob_bytes(&mut rng)).unwrap())
+        .collect::<Vec<_>>();
+    let commitments = blobs
+        .iter()
+        .map(|blob| blob_to_kzg_commitment_rust(blob, &ts).unwrap())
+        .collect::<Vec<TG1>>();
+    let mut proofs = blobs
+        .iter()
+        .zip(commitments.iter())
+        .map(|(blob, commitment)| compute_blob_kzg_proof_rust(blob, commitment, &ts).unwrap())
+        .collect::<Vec<TG1>>();
+
+    let domain = ChallengeDomain::new(b"example-protocol/v1", b"slot 42").unwrap();
+    assert!(verify_blob_kzg_proof_batch_with_domain_rust(
+        &blobs,
+        &commitments,
+        &proofs,
+        &domain,
+        &ts
+    )
+    .unwrap());
+
+    // The challenge depends on both the tag and the context
+    let zs = (0..3).map(|i| TFr::from_u64(i + 1)).collect::<Vec<_>>();
+    let ys = (0..3).map(|i| TFr::from_u64(i + 7)).collect::<Vec<_>>();
+    let challenge = |tag: &[u8], context: &[u8]| {
+        let domain = ChallengeDomain::new(tag, context).unwrap();
+        compute_batch_challenge_powers(&domain, &commitments, &zs, &ys, &proofs).unwrap()[1]
+    };
+    let expected = challenge(b"example-protocol/v1", b"slot 42");
+    assert!(expected.equals(&challenge(b"example-protocol/v1", b"slot 42")));
+    assert!(!expected.equals(&challenge(b"other-protocol/v1", b"slot 42")));
+    assert!(!expected.equals(&challenge(b"example-protocol/v1", b"slot 43")));
+    // Moving bytes between the tag and the context changes the transcript
+    assert!(!challenge(b"ab", b"c").equals(&challenge(b"a", b"bc")));
+
+    assert!(ChallengeDomain::new(b"", b"slot 42").is_err());
+    let empty = ChallengeDomain {
+        tag: b"",
+        context: b"",
+    };
+    assert!(verify_blob_kzg_proof_batch_with_domain_rust(
+        &blobs,
+        &commitments,
+        &proofs,
+        &empty,
+        &ts
+    )
+    .is_err());
+
+    proofs[1] = proofs[0].clone();
+    assert!(!verify_blob_kzg_proof_batch_with_domain_rust(
+        &blobs,
+        &commitments,
+        &proofs,
+        &domain,
+        &ts
+    )
+    .unwrap());
+}
+
+pub fn verify_with_precomputed_challenge_test<
+    TFr: Fr + Copy,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + G1LinComb<TFr, TG1Fp, TG1Affine>,
+    TG2: G2,
+    TPoly: Poly<TFr>,
+    TFFTSettings: FFTSettings<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    load_trusted_setup: &dyn Fn(&str) -> Result<TKZGSettings, String>,
+) {
+    let ts = load_trusted_setup(get_trusted_setup_path().as_str()).unwrap();
+    let mut rng = rand::thread_rng();
+
+    let blob = bytes_to_blob::<TFr>(&generate_random_blob_bytes(&mut rng)).unwrap();
+    let commitment: TG1 = blob_to_kzg_commitment_rust(&blob, &ts).unwrap();
+    let proof = compute_blob_kzg_proof_rust(&blob, &commitment, &ts).unwrap();
+
+    // The blob proof is the opening at the challenge
+    let challenge = compute_challenge(&blob, &commitment).unwrap();
+    let (opening, _) = compute_kzg_proof_rust(&blob, &challenge, &ts).unwrap();
+    assert!(opening.equals(&proof));
+
+    assert!(
+        verify_with_precomputed_challenge_rust(&blob, &commitment, &proof, &challenge, &ts)
+            .unwrap()
+    );
+    assert!(verify_blob_kzg_proof_rust(&blob, &commitment, &proof, &ts).unwrap());
+
+    // A different challenge or blob fails
+    let other_challenge = challenge.add(&TFr::one());
+    assert!(!verify_with_precomputed_challenge_rust(
+        &blob,
+        &commitment,
+        &proof,
+        &other_challenge,
+        &ts
+    )
+    .unwrap());
+    let mut other_blob = blob.clone();
+    other_blob[0] = other_blob[0].add(&TFr::one());
+    assert!(!challenge.equals(&compute_challenge(&other_blob, &commitment).unwrap()));
+    assert!(!verify_with_precomputed_challenge_rust(
+        &other_blob,
+        &commitment,
+        &proof,
+        &challenge,
+        &ts
+    )
+    .unwrap());
+
+    assert!(compute_challenge(&blob[1..], &commitment).is_err());
+    assert!(verify_with_precomputed_challenge_rust(
+        &blob[1..],
+        &commitment,
+        &proof,
+        &challenge,
+        &ts
+    )
+    .is_err());
+}
+
+pub fn verify_batch_openings_test<
+    TFr: Fr + Copy,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + G1LinComb<TFr, TG1Fp, TG1Affine> + PairingVerify<TG1, TG2>,
+    TG2: G2,
+    TPoly: Poly<TFr>,
+    TFFTSettings: FFTSettings<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    load_trusted_setup: &dyn Fn(&str) -> Result<TKZGSettings, String>,
+) {
+    let ts = load_trusted_setup(get_trusted_setup_path().as_str()).unwrap();
+    let mut rng = rand::thread_rng();
+
+    // Distinct blobs, each opened at its own point
+    let mut commitments = Vec::new();
+    let mut points = Vec::new();
+    let mut values = Vec::new();
+    let mut proofs = Vec::new();
+    for i in 0..4u64 {
+        let blob = bytes_to_blob::<TFr>(&generate_random_blob_bytes(&mut rng)).unwrap();
+        let z = TFr::from_u64(1000 + i * 17);
+        let (proof, y) = compute_kzg_proof_rust(&blob, &z, &ts).unwrap();
+        commitments.push(blob_to_kzg_commitment_rust(&blob, &ts).unwrap());
+        points.push(z);
+        values.push(y);
+        proofs.push(proof);
+    }
+
+    for count in 0..=commitments.len() {
+        assert!(verify_batch_openings(
+            &commitments[..count],
+            &points[..count],
+            &values[..count],
+            &proofs[..count],
+            &ts
+        )
+        .unwrap());
+    }
+
+    // A single wrong value, point or proof fails the whole batch
+    let mut wrong_values = values.clone();
+    wrong_values[2] = wrong_values[2].add(&TFr::one());
+    assert!(!verify_batch_openings(&commitments, &points, &wrong_values, &proofs, &ts).unwrap());
+    let mut wrong_points = points.clone();
+    wrong_points.swap(0, 3);
+    assert!(!verify_batch_openings(&commitments, &wrong_points, &values, &proofs, &ts).unwrap());
+    let mut wrong_proofs = proofs.clone();
+    wrong_proofs[1] = proofs[0].clone();
+    assert!(!verify_batch_openings(&commitments, &points, &values, &wrong_proofs, &ts).unwrap());
+
+    assert!(verify_batch_openings(&commitments, &points[1..], &values, &proofs, &ts).is_err());
+}
+
 const BLOB_TO_KZG_COMMITMENT_TESTS: &str = "src/test_vectors/blob_to_kzg_commitment/*/*/*";
 const COMPUTE_KZG_PROOF_TESTS: &str = "src/test_vectors/compute_kzg_proof/*/*/*";
 const COMPUTE_BLOB_KZG_PROOF_TESTS: &str = "src/test_vectors/compute_blob_kzg_proof/*/*/*";
@@ -1091,3 +1365,185 @@ pub fn validate_batched_input_test<
 
     assert!(res.is_err());
 }
+
+pub fn precomputation_table_manager_concurrency_test<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>()
+where
+    PrecomputationTable<TFr, TG1, TG1Fp, TG1Affine>: Send + Sync,
+{
+    let points = (0..64u64)
+        .map(|i| TG1::generator().mul(&TFr::from_u64(i + 1)))
+        .collect::<Vec<_>>();
+    let manager = PrecomputationTableManager::<TFr, TG1, TG1Fp, TG1Affine>::new();
+
+    // Settings are told apart by their G1 pointer only, which is never dereferenced
+    let settings = |i: usize| CKZGSettings {
+        max_width: 0,
+        roots_of_unity: core::ptr::null_mut(),
+        g1_values: core::ptr::null_mut::<blst_p1>().wrapping_add(i + 1),
+        g2_values: core::ptr::null_mut(),
+    };
+
+    std::thread::scope(|s| {
+        for i in 0..8 {
+            let manager = &manager;
+            let points = &points;
+            s.spawn(move || {
+                let table = precompute::<TFr, TG1, TG1Fp, TG1Affine>(points)
+                    .unwrap()
+                    .map(Arc::new);
+                let expected = table.is_some();
+
+                for _ in 0..16 {
+                    manager.save_precomputation(table.clone(), &settings(i));
+                    assert_eq!(manager.get_precomputation(&settings(i)).is_some(), expected);
+                    manager.remove_precomputation(&settings(i));
+                    assert!(manager.get_precomputation(&settings(i)).is_none());
+                }
+            });
+        }
+    });
+}
+
+pub fn shared_settings_concurrent_use_test<
+    TFr: Fr + Copy,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + G1LinComb<TFr, TG1Fp, TG1Affine> + Send,
+    TG2: G2,
+    TPoly: Poly<TFr>,
+    TFFTSettings: FFTSettings<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine> + Sync,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    load_trusted_setup: &dyn Fn(&str) -> Result<TKZGSettings, String>,
+) {
+    let ts = load_trusted_setup(get_trusted_setup_path().as_str()).unwrap();
+    let mut rng = rand::thread_rng();
+    let blob: Vec<TFr> = bytes_to_blob(&generate_random_blob_bytes(&mut rng)).unwrap();
+    let expected = blob_to_kzg_commitment_rust(&blob, &ts).unwrap();
+
+    // The settings are only borrowed, no external locking is needed
+    let commitments = std::thread::scope(|s| {
+        let handles = (0..4)
+            .map(|_| s.spawn(|| blob_to_kzg_commitment_rust(&blob, &ts).unwrap()))
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect::<Vec<_>>()
+    });
+
+    assert!(commitments
+        .iter()
+        .all(|commitment| commitment.equals(&expected)));
+}
+
+pub fn batched_hashes_test<TFr: Fr, TG1: G1>() {
+    let mut rng = rand::thread_rng();
+
+    // Counts below, at and above the number of lanes of the batched hashing
+    for count in [0, 1, 5, 8, 9, 17] {
+        let blobs = (0..count)
+            .map(|_| bytes_to_blob::<TFr>(&generate_random_blob_bytes(&mut rng)).unwrap())
+            .collect::<Vec<_>>();
+        let commitments = (0..count).map(|_| TG1::rand()).collect::<Vec<_>>();
+
+        let challenges = compute_challenges(&blobs, &commitments).unwrap();
+        assert_eq!(challenges.len(), count);
+        for ((blob, commitment), challenge) in blobs.iter().zip(&commitments).zip(&challenges) {
+            assert!(challenge.equals(&compute_challenge(blob, commitment).unwrap()));
+        }
+
+        let versioned_hashes = kzg_to_versioned_hashes(&commitments);
+        assert_eq!(versioned_hashes.len(), count);
+        for (commitment, versioned_hash) in commitments.iter().zip(&versioned_hashes) {
+            assert_eq!(versioned_hash[0], VERSIONED_HASH_VERSION_KZG);
+            assert_eq!(*versioned_hash, kzg_to_versioned_hash(commitment));
+        }
+    }
+
+    // Versioned hash of the commitment to the zero blob
+    assert_eq!(
+        hex::encode(kzg_to_versioned_hash(&TG1::identity())),
+        "010657f37554c781402a22917dee2f75def7ab966d7b770905398eba3c444014"
+    );
+
+    let blob = vec![TFr::zero(); FIELD_ELEMENTS_PER_BLOB];
+    assert!(compute_challenges::<TFr, TG1>(&[blob.clone()], &[]).is_err());
+    assert!(compute_challenges(&[blob[1..].to_vec()], &[TG1::identity()]).is_err());
+}
+
+fn trusted_setup_json(g1_bytes: &[u8], g2_bytes: &[u8]) -> String {
+    let hex_array = |bytes: &[u8], size: usize| {
+        bytes
+            .chunks(size)
+            .map(|point| format!("\"0x{}\"", hex::encode(point)))
+            .collect::<Vec<_>>()
+            .join(",\n    ")
+    };
+
+    // Laid out like the ceremony output, with the monomial G1 points first
+    format!(
+        "{{\n  \"g1_monomial\": [\n    {}\n  ],\n  \"g1_lagrange\": [\n    {}\n  ],\n  \"g2_monomial\": [\n    {}\n  ]\n}}\n",
+        hex_array(&vec![0xc0; g1_bytes.len()], BYTES_PER_COMMITMENT),
+        hex_array(g1_bytes, BYTES_PER_COMMITMENT),
+        hex_array(g2_bytes, 96)
+    )
+}
+
+pub fn load_trusted_setup_json_test<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG2: G2,
+    TPoly: Poly<TFr>,
+    TFFTSettings: FFTSettings<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    load_trusted_setup: &dyn Fn(&str) -> Result<TKZGSettings, String>,
+    load_trusted_setup_json: &dyn Fn(&str) -> Result<TKZGSettings, String>,
+) {
+    let contents = fs::read_to_string(get_trusted_setup_path()).unwrap();
+    let (g1_bytes, g2_bytes) = load_trusted_setup_string(&contents).unwrap();
+    let json = trusted_setup_json(&g1_bytes, &g2_bytes);
+    assert_eq!(
+        load_trusted_setup_json_string(&json).unwrap(),
+        (g1_bytes.clone(), g2_bytes.clone())
+    );
+
+    let path = std::env::temp_dir().join(format!("kzg_trusted_setup_{}.json", std::process::id()));
+    let path = path.to_str().unwrap();
+    fs::write(path, &json).unwrap();
+    let expected = load_trusted_setup(get_trusted_setup_path().as_str()).unwrap();
+    let actual = load_trusted_setup_json(path).unwrap();
+    fs::remove_file(path).unwrap();
+    assert!(actual
+        .get_g1_secret()
+        .iter()
+        .zip(expected.get_g1_secret())
+        .all(|(a, b)| a.equals(b)));
+    assert!(actual
+        .get_g2_secret()
+        .iter()
+        .zip(expected.get_g2_secret())
+        .all(|(a, b)| a.equals(b)));
+    assert!(load_trusted_setup_json(path).is_err());
+
+    // Errors name the field and the point
+    let missing = json.replace("g2_monomial", "g2");
+    assert!(load_trusted_setup_json_string(&missing)
+        .unwrap_err()
+        .contains("g2_monomial"));
+    let truncated = trusted_setup_json(&g1_bytes[..BYTES_PER_COMMITMENT - 1], &g2_bytes);
+    assert!(load_trusted_setup_json_string(&truncated)
+        .unwrap_err()
+        .contains("g1_lagrange point 0"));
+    let not_hex = json.replacen("\"0xa", "\"0xz", 1);
+    assert!(load_trusted_setup_json_string(&not_hex).is_err());
+    assert!(load_trusted_setup_json_string("{}").is_err());
+}
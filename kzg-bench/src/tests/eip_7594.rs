@@ -0,0 +1,198 @@
+use crate::tests::eip_4844::generate_random_blob_bytes;
+use crate::tests::utils::get_trusted_setup_path;
+use kzg::common_utils::reverse_bit_order;
+use kzg::eip_4844::{blob_to_kzg_commitment_rust, bytes_to_blob};
+use kzg::eip_7594::{
+    bytes_to_cell, cell_to_bytes, compute_cells_and_kzg_proofs_rust,
+    recover_cells_and_kzg_proofs_rust, verify_cell_kzg_proof_batch_rust, CellSettings,
+    BYTES_PER_CELL, CELLS_PER_EXT_BLOB, FIELD_ELEMENTS_PER_CELL, FIELD_ELEMENTS_PER_EXT_BLOB,
+};
+use kzg::multiproof::verify_kzg_multiproof;
+use kzg::{
+    FFTFr, FFTSettings, FK20MultiSettings, Fr, G1Affine, G1Fp, G1GetFp, G1LinComb, G1Mul, G2Mul,
+    KZGSettings, PairingVerify, Poly, PolyRecover, FFTG1, G1, G2,
+};
+
+pub fn compute_verify_and_recover_cells_test<
+    TFr: Fr + Copy,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + G1LinComb<TFr, TG1Fp, TG1Affine> + PairingVerify<TG1, TG2>,
+    TG2: G2 + G2Mul<TFr>,
+    TPoly: Poly<TFr> + PolyRecover<TFr, TPoly, TFFTSettings>,
+    TFFTSettings: FFTSettings<TFr> + FFTFr<TFr> + FFTG1<TG1>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TFK20MultiSettings: FK20MultiSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TKZGSettings, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    load_trusted_setup: &dyn Fn(&str) -> Result<TKZGSettings, String>,
+) {
+    let ts = load_trusted_setup(get_trusted_setup_path().as_str()).unwrap();
+    let cs = CellSettings::<TKZGSettings, TFK20MultiSettings>::new(&ts).unwrap();
+    let mut rng = rand::thread_rng();
+
+    let blobs = (0..2)
+        .map(|_| bytes_to_blob::<TFr>(&generate_random_blob_bytes(&mut rng)).unwrap())
+        .collect::<Vec<_>>();
+    let commitments = blobs
+        .iter()
+        .map(|blob| blob_to_kzg_commitment_rust(blob, &ts).unwrap())
+        .collect::<Vec<TG1>>();
+    let (cells, proofs) = compute_cells_and_kzg_proofs_rust(&blobs[0], &cs).unwrap();
+    assert_eq!(cells.len(), CELLS_PER_EXT_BLOB);
+    assert_eq!(proofs.len(), CELLS_PER_EXT_BLOB);
+
+    // The first half of the extension is the blob itself
+    let first_half = cells[..CELLS_PER_EXT_BLOB / 2].concat();
+    assert!(first_half.iter().zip(&blobs[0]).all(|(a, b)| a.equals(b)));
+
+    // Each proof opens the blob over the coset of its cell
+    let mut domain = cs
+        .extended_settings()
+        .get_fft_settings()
+        .get_expanded_roots_of_unity()[..FIELD_ELEMENTS_PER_EXT_BLOB]
+        .to_vec();
+    reverse_bit_order(&mut domain).unwrap();
+    for i in [0, 1, 77, CELLS_PER_EXT_BLOB - 1] {
+        let coset = &domain[i * FIELD_ELEMENTS_PER_CELL..(i + 1) * FIELD_ELEMENTS_PER_CELL];
+        assert!(verify_kzg_multiproof(
+            &commitments[0],
+            coset,
+            &cells[i],
+            &proofs[i],
+            cs.extended_settings()
+        )
+        .unwrap());
+    }
+
+    let indices = (0..CELLS_PER_EXT_BLOB).collect::<Vec<_>>();
+    let all_commitments = vec![commitments[0].clone(); CELLS_PER_EXT_BLOB];
+    let verify = |commitments: &[TG1], indices: &[usize], cells: &[Vec<TFr>], proofs: &[TG1]| {
+        verify_cell_kzg_proof_batch_rust(commitments, indices, cells, proofs, &cs)
+    };
+    assert!(verify(&all_commitments, &indices, &cells, &proofs).unwrap());
+    assert!(verify(&[], &[], &[], &[]).unwrap());
+
+    // Cells of several blobs, with repeated cells, in one batch
+    let (other_cells, other_proofs) = compute_cells_and_kzg_proofs_rust(&blobs[1], &cs).unwrap();
+    let batch = [(0, 3), (1, 3), (0, 100), (1, 5), (0, 3)];
+    let batch_commitments = batch
+        .iter()
+        .map(|&(b, _)| commitments[b].clone())
+        .collect::<Vec<_>>();
+    let batch_indices = batch.iter().map(|&(_, i)| i).collect::<Vec<_>>();
+    let batch_cells = batch
+        .iter()
+        .map(|&(b, i)| [&cells, &other_cells][b][i].clone())
+        .collect::<Vec<_>>();
+    let batch_proofs = batch
+        .iter()
+        .map(|&(b, i)| [&proofs, &other_proofs][b][i].clone())
+        .collect::<Vec<_>>();
+    assert!(verify(
+        &batch_commitments,
+        &batch_indices,
+        &batch_cells,
+        &batch_proofs
+    )
+    .unwrap());
+
+    // A wrong value, index, proof or commitment fails the batch
+    let mut wrong_cells = batch_cells.clone();
+    wrong_cells[2][7] = wrong_cells[2][7].add(&TFr::one());
+    assert!(!verify(
+        &batch_commitments,
+        &batch_indices,
+        &wrong_cells,
+        &batch_proofs
+    )
+    .unwrap());
+    let mut wrong_indices = batch_indices.clone();
+    wrong_indices[3] = 6;
+    assert!(!verify(
+        &batch_commitments,
+        &wrong_indices,
+        &batch_cells,
+        &batch_proofs
+    )
+    .unwrap());
+    let mut wrong_proofs = batch_proofs.clone();
+    wrong_proofs.swap(0, 1);
+    assert!(!verify(
+        &batch_commitments,
+        &batch_indices,
+        &batch_cells,
+        &wrong_proofs
+    )
+    .unwrap());
+    let mut wrong_commitments = batch_commitments.clone();
+    wrong_commitments.swap(0, 1);
+    assert!(!verify(
+        &wrong_commitments,
+        &batch_indices,
+        &batch_cells,
+        &batch_proofs
+    )
+    .unwrap());
+
+    let mut out_of_range = batch_indices.clone();
+    out_of_range[0] = CELLS_PER_EXT_BLOB;
+    assert!(verify(
+        &batch_commitments,
+        &out_of_range,
+        &batch_cells,
+        &batch_proofs
+    )
+    .is_err());
+    let mut short_cells = batch_cells.clone();
+    short_cells[0].pop();
+    assert!(verify(
+        &batch_commitments,
+        &batch_indices,
+        &short_cells,
+        &batch_proofs
+    )
+    .is_err());
+    assert!(verify(
+        &batch_commitments[1..],
+        &batch_indices,
+        &batch_cells,
+        &batch_proofs
+    )
+    .is_err());
+
+    // Any half of the cells, in any order, recovers all of them
+    let half = (0..CELLS_PER_EXT_BLOB / 2)
+        .map(|i| (i * 37 + 11) % CELLS_PER_EXT_BLOB)
+        .collect::<Vec<_>>();
+    let half_cells = half.iter().map(|&i| cells[i].clone()).collect::<Vec<_>>();
+    let (recovered, recovered_proofs) =
+        recover_cells_and_kzg_proofs_rust(&half, &half_cells, &cs).unwrap();
+    assert!(recovered == cells);
+    assert!(recovered_proofs
+        .iter()
+        .zip(&proofs)
+        .all(|(a, b)| a.equals(b)));
+    let (recovered, _) = recover_cells_and_kzg_proofs_rust(&indices, &cells, &cs).unwrap();
+    assert!(recovered == cells);
+
+    assert!(recover_cells_and_kzg_proofs_rust(&half[1..], &half_cells[1..], &cs).is_err());
+    let mut duplicated = half.clone();
+    duplicated[1] = duplicated[0];
+    assert!(recover_cells_and_kzg_proofs_rust(&duplicated, &half_cells, &cs).is_err());
+    let mut more = half.clone();
+    more.push(
+        half.iter()
+            .map(|i| i + 1)
+            .find(|i| !half.contains(i))
+            .unwrap(),
+    );
+    let mut corrupted = half_cells.clone();
+    corrupted.push(cells[*more.last().unwrap()].clone());
+    corrupted[4][0] = corrupted[4][0].add(&TFr::one());
+    assert!(recover_cells_and_kzg_proofs_rust(&more, &corrupted, &cs).is_err());
+
+    let bytes = cell_to_bytes(&cells[9]);
+    assert_eq!(bytes.len(), BYTES_PER_CELL);
+    assert!(bytes_to_cell::<TFr>(&bytes).unwrap() == cells[9]);
+    assert!(bytes_to_cell::<TFr>(&bytes[1..]).is_err());
+}
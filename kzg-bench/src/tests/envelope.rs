@@ -0,0 +1,51 @@
+use kzg::eip_4844::BYTES_PER_G1;
+use kzg::envelope::{
+    decode_envelope, decode_g1, decode_g1_list, encode_envelope, encode_g1, encode_g1_list,
+    CurveId, ENVELOPE_HEADER_SIZE, ENVELOPE_VERSION,
+};
+use kzg::{Fr, G1Mul, G1};
+
+pub fn envelope_roundtrip_test<TFr: Fr, TG1: G1 + G1Mul<TFr>>() {
+    let point = TG1::generator().mul(&TFr::from_u64(1234));
+    let encoded = encode_g1(&point);
+    assert_eq!(encoded.len(), ENVELOPE_HEADER_SIZE + BYTES_PER_G1);
+    assert_eq!(encoded[0], ENVELOPE_VERSION);
+    assert_eq!(encoded[1], CurveId::Bls12_381 as u8);
+    assert_eq!(&encoded[ENVELOPE_HEADER_SIZE..], &point.to_bytes());
+    assert!(decode_g1::<TG1>(&encoded).unwrap().equals(&point));
+
+    let points = (1..4)
+        .map(|i| TG1::generator().mul(&TFr::from_u64(i)))
+        .collect::<Vec<_>>();
+    let decoded = decode_g1_list::<TG1>(&encode_g1_list(&points)).unwrap();
+    assert_eq!(decoded.len(), points.len());
+    assert!(decoded.iter().zip(points.iter()).all(|(a, b)| a.equals(b)));
+    assert!(decode_g1_list::<TG1>(&encode_g1_list::<TG1>(&[]))
+        .unwrap()
+        .is_empty());
+
+    let raw = encode_envelope(CurveId::Bls12_381, &[1, 2]);
+    let (curve, payload) = decode_envelope(&raw).unwrap();
+    assert_eq!(curve, CurveId::Bls12_381);
+    assert_eq!(payload, &[1, 2]);
+}
+
+pub fn envelope_rejects_malformed_test<TFr: Fr, TG1: G1 + G1Mul<TFr>>() {
+    let encoded = encode_g1(&TG1::generator().mul(&TFr::from_u64(5)));
+
+    assert!(decode_envelope(&encoded[..1]).is_err());
+
+    let mut future_version = encoded.clone();
+    future_version[0] = ENVELOPE_VERSION + 1;
+    assert!(decode_g1::<TG1>(&future_version).is_err());
+
+    let mut unknown_curve = encoded.clone();
+    unknown_curve[1] = 0xff;
+    assert!(decode_g1::<TG1>(&unknown_curve).is_err());
+
+    assert!(decode_g1::<TG1>(&encoded[..encoded.len() - 1]).is_err());
+    assert!(decode_g1_list::<TG1>(&encoded[..encoded.len() - 1]).is_err());
+
+    // A raw point without envelope is not accepted
+    assert!(decode_g1::<TG1>(&encoded[ENVELOPE_HEADER_SIZE..]).is_err());
+}
@@ -0,0 +1,55 @@
+use kzg::equivalence::{prove_same_polynomial, verify_same_polynomial};
+use kzg::{FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1Mul, KZGSettings, Poly, G1, G2};
+
+use crate::tests::kzg_proofs::SECRET;
+
+const OTHER_SECRET: [u8; 32] = [
+    0x1f, 0x2e, 0x3d, 0x4c, 0x5b, 0x6a, 0x79, 0x88, 0x97, 0xa6, 0xb5, 0xc4, 0xd3, 0xe2, 0xf1, 0x00,
+    0x0f, 0x1e, 0x2d, 0x3c, 0x4b, 0x5a, 0x69, 0x78, 0x87, 0x96, 0xa5, 0xb4, 0xc3, 0xd2, 0xe1, 0x1a,
+];
+
+fn test_poly<TFr: Fr, TPoly: Poly<TFr>>(offset: u64) -> TPoly {
+    let coeffs = [1, 2, 3, 4, 7, 7, 7, 7, 13, 13, 13, 13, 13, 13, 13, 13];
+    let mut p = TPoly::new(coeffs.len());
+    for (x, &coeff) in coeffs.iter().enumerate() {
+        p.set_coeff_at(x, &TFr::from_u64(coeff + offset));
+    }
+    p
+}
+
+pub fn same_polynomial_proof_test<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG2: G2,
+    TPoly: Poly<TFr>,
+    TFFTSettings: FFTSettings<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    generate_trusted_setup: &dyn Fn(usize, [u8; 32usize]) -> (Vec<TG1>, Vec<TG2>),
+) {
+    let secrets_len = 17;
+    let fs = TFFTSettings::new(4).unwrap();
+    let (s1, s2) = generate_trusted_setup(secrets_len, SECRET);
+    let ks_a = TKZGSettings::new(&s1, &s2, secrets_len, &fs).unwrap();
+    let (s1, s2) = generate_trusted_setup(secrets_len, OTHER_SECRET);
+    let ks_b = TKZGSettings::new(&s1, &s2, secrets_len, &fs).unwrap();
+
+    let p: TPoly = test_poly(0);
+    let commitment_a = ks_a.commit_to_poly(&p).unwrap();
+    let commitment_b = ks_b.commit_to_poly(&p).unwrap();
+    assert!(!commitment_a.equals(&commitment_b));
+
+    let proof = prove_same_polynomial(&p, &commitment_a, &commitment_b, &ks_a, &ks_b).unwrap();
+    assert!(verify_same_polynomial(&commitment_a, &commitment_b, &proof, &ks_a, &ks_b).unwrap());
+
+    // The proof is bound to the order of the setups
+    assert!(!verify_same_polynomial(&commitment_b, &commitment_a, &proof, &ks_b, &ks_a).unwrap());
+
+    // Commitments to different polynomials cannot be linked
+    let q: TPoly = test_poly(1);
+    let commitment_q = ks_b.commit_to_poly(&q).unwrap();
+    let proof = prove_same_polynomial(&p, &commitment_a, &commitment_q, &ks_a, &ks_b).unwrap();
+    assert!(!verify_same_polynomial(&commitment_a, &commitment_q, &proof, &ks_a, &ks_b).unwrap());
+}
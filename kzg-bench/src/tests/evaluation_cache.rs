@@ -0,0 +1,69 @@
+use kzg::eip_4844::{
+    blob_to_kzg_commitment_rust, blob_to_polynomial, bytes_to_blob, compute_kzg_proof_rust,
+    evaluate_polynomial_in_evaluation_form,
+};
+use kzg::evaluation_cache::EvaluationCache;
+use kzg::{
+    FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1LinComb, G1Mul, G2Mul, KZGSettings, PairingVerify,
+    Poly, G1, G2,
+};
+
+use crate::tests::eip_4844::{generate_random_blob_bytes, generate_random_field_element_bytes};
+use crate::tests::utils::get_trusted_setup_path;
+
+pub fn evaluation_cache_test<
+    TFr: Fr + Copy,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + G1LinComb<TFr, TG1Fp, TG1Affine> + PairingVerify<TG1, TG2>,
+    TG2: G2 + G2Mul<TFr>,
+    TPoly: Poly<TFr>,
+    TFFTSettings: FFTSettings<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    load_trusted_setup: &dyn Fn(&str) -> Result<TKZGSettings, String>,
+) {
+    let ts = load_trusted_setup(get_trusted_setup_path().as_str()).unwrap();
+    let mut rng = rand::thread_rng();
+
+    assert!(EvaluationCache::<TFr, TG2>::new(&ts, 0).is_err());
+    let cache = EvaluationCache::<TFr, TG2>::new(&ts, 2).unwrap();
+    assert!(cache.is_empty());
+
+    let z_outside = TFr::from_bytes(&generate_random_field_element_bytes(&mut rng)).unwrap();
+    let z_inside = ts.get_fft_settings().get_roots_of_unity()[5];
+
+    for z in [z_outside, z_inside] {
+        // Several blobs opened at the same point share one entry
+        for _ in 0..2 {
+            let blob: Vec<TFr> = bytes_to_blob(&generate_random_blob_bytes(&mut rng)).unwrap();
+            let polynomial: TPoly = blob_to_polynomial(&blob).unwrap();
+            let commitment = blob_to_kzg_commitment_rust(&blob, &ts).unwrap();
+
+            let y = cache.evaluate(&polynomial, &z).unwrap();
+            assert!(
+                y.equals(&evaluate_polynomial_in_evaluation_form(&polynomial, &z, &ts).unwrap())
+            );
+
+            let (proof, computed_y) = cache.compute_kzg_proof(&blob, &z, &ts).unwrap();
+            let (expected_proof, _) = compute_kzg_proof_rust(&blob, &z, &ts).unwrap();
+            assert!(computed_y.equals(&y));
+            assert!(proof.equals(&expected_proof));
+
+            assert!(cache.verify_kzg_proof(&commitment, &z, &y, &proof).unwrap());
+            let wrong_y = y.add(&TFr::one());
+            assert!(!cache
+                .verify_kzg_proof(&commitment, &z, &wrong_y, &proof)
+                .unwrap());
+        }
+    }
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.point(&z_inside).unwrap().root_index(), Some(5));
+    assert_eq!(cache.point(&z_outside).unwrap().root_index(), None);
+
+    // The oldest point is dropped once the cache is full
+    cache.point(&TFr::from_u64(7)).unwrap();
+    assert_eq!(cache.len(), 2);
+    cache.clear();
+    assert!(cache.is_empty());
+}
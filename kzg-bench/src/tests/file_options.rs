@@ -0,0 +1,101 @@
+use kzg::error::KzgError;
+use kzg::file_options::{FileOptions, ZSTD_MAGIC};
+use kzg::io_utils::{
+    load_secrets_from_file, load_secrets_from_file_with_options, save_secrets_to_file,
+    save_secrets_to_file_with_options,
+};
+use kzg::msm::msm_impls::msm;
+use kzg::msm::precompute::{precompute, PrecomputationTable};
+use kzg::validation::ValidationLevel;
+use kzg::{Compression, Fr, G1Affine, G1Fp, G1GetFp, G1Mul, G1ProjAddAffine, G2Mul, G1, G2};
+
+use crate::tests::kzg_proofs::SECRET;
+
+fn temp_path(name: &str) -> String {
+    let path = std::env::temp_dir().join(format!("kzg_{}_{}.bin", name, std::process::id()));
+    path.to_str().unwrap().to_string()
+}
+
+pub fn setup_file_options_test<TFr: Fr, TG1: G1 + G1Mul<TFr>, TG2: G2 + G2Mul<TFr>>(
+    generate_trusted_setup: &dyn Fn(usize, [u8; 32usize]) -> (Vec<TG1>, Vec<TG2>),
+) {
+    let (expected_g1, expected_g2) = generate_trusted_setup(64, SECRET);
+    let plain_path = temp_path("file_options_plain");
+    let path = temp_path("file_options_zstd");
+
+    // Default options write the same file as the functions without options
+    save_secrets_to_file(&plain_path, &expected_g1, &expected_g2).unwrap();
+    save_secrets_to_file_with_options(&path, &expected_g1, &expected_g2, &FileOptions::default())
+        .unwrap();
+    let plain = std::fs::read(&plain_path).unwrap();
+    assert_eq!(std::fs::read(&path).unwrap(), plain);
+
+    let options = FileOptions {
+        point_compression: Compression::Uncompressed,
+        zstd_level: Some(19),
+    };
+    let result = save_secrets_to_file_with_options(&path, &expected_g1, &expected_g2, &options);
+    if let Err(e) = result {
+        // Built without the zstd feature
+        assert!(matches!(e, KzgError::Unsupported(_)));
+    } else {
+        let compressed = std::fs::read(&path).unwrap();
+        assert!(compressed.starts_with(&ZSTD_MAGIC));
+
+        let (g1, g2): (Vec<TG1>, Vec<TG2>) =
+            load_secrets_from_file_with_options(&path, &options, ValidationLevel::Subgroup)
+                .unwrap();
+        assert!(g1.iter().zip(&expected_g1).all(|(a, b)| a.equals(b)));
+        assert!(g2.iter().zip(&expected_g2).all(|(a, b)| a.equals(b)));
+
+        // Plain loaders read compressed files of compressed points
+        save_secrets_to_file_with_options(&path, &expected_g1, &expected_g2, &FileOptions::zstd(3))
+            .unwrap();
+        assert!(std::fs::read(&path).unwrap().starts_with(&ZSTD_MAGIC));
+        let (g1, _): (Vec<TG1>, Vec<TG2>) = load_secrets_from_file(&path).unwrap();
+        assert!(g1.iter().zip(&expected_g1).all(|(a, b)| a.equals(b)));
+    }
+
+    std::fs::remove_file(&plain_path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+}
+
+pub fn table_file_options_test<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+    TProjAddAffine: G1ProjAddAffine<TG1, TG1Fp, TG1Affine>,
+>() {
+    let npoints = 64;
+    let points = (0..npoints).map(|_| TG1::rand()).collect::<Vec<_>>();
+    let scalars = (0..npoints).map(|_| TFr::rand()).collect::<Vec<_>>();
+    let Some(table) = precompute::<TFr, TG1, TG1Fp, TG1Affine>(&points).unwrap() else {
+        return;
+    };
+    let path = temp_path("table_file_options");
+
+    if let Err(e) = table.write_to_file_with_options(&path, &FileOptions::zstd(3)) {
+        assert!(matches!(e, KzgError::Unsupported(_)));
+        return;
+    }
+    let compressed = std::fs::read(&path).unwrap();
+    assert!(compressed.starts_with(&ZSTD_MAGIC));
+
+    let read: PrecomputationTable<TFr, TG1, TG1Fp, TG1Affine> =
+        unsafe { PrecomputationTable::read_from_file(&path) }.unwrap();
+    let expected =
+        msm::<TG1, TG1Fp, TG1Affine, TProjAddAffine, TFr>(&points, &scalars, npoints, None);
+    let actual =
+        msm::<TG1, TG1Fp, TG1Affine, TProjAddAffine, TFr>(&points, &scalars, npoints, Some(&read));
+    assert!(actual.equals(&expected));
+
+    // A truncated compressed file fails to load
+    std::fs::write(&path, &compressed[..compressed.len() / 2]).unwrap();
+    assert!(
+        unsafe { PrecomputationTable::<TFr, TG1, TG1Fp, TG1Affine>::read_from_file(&path) }
+            .is_err()
+    );
+
+    std::fs::remove_file(&path).unwrap();
+}
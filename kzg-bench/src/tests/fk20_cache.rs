@@ -0,0 +1,72 @@
+use std::mem::discriminant;
+
+use kzg::eip_4844::bytes_to_blob;
+use kzg::eip_7594::{compute_cells_and_kzg_proofs_rust, CellSettings};
+use kzg::error::KzgError;
+use kzg::file_options::FileOptions;
+use kzg::fk20_cache::{fk20_cache_path, FK20_CACHE_HEADER_SIZE};
+use kzg::golden::seeded_blob_bytes;
+use kzg::{
+    FFTFr, FFTSettings, FK20MultiSettings, Fr, G1Affine, G1Fp, G1GetFp, G1Mul, KZGSettings, Poly,
+    FFTG1, G1, G2,
+};
+
+use crate::tests::utils::get_trusted_setup_path;
+
+/// Write the FK20 Toeplitz FFTs of cell settings to a cache file, build settings from it and
+/// check they compute the same proofs, and that corrupted caches are rejected
+pub fn fk20_cache_roundtrip_test<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG2: G2,
+    TPoly: Poly<TFr>,
+    TFFTSettings: FFTSettings<TFr> + FFTFr<TFr> + FFTG1<TG1>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TFK20MultiSettings: FK20MultiSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TKZGSettings, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    load_trusted_setup: &dyn Fn(&str) -> Result<TKZGSettings, String>,
+) {
+    let ts = load_trusted_setup(get_trusted_setup_path().as_str()).unwrap();
+    let cs = CellSettings::<TKZGSettings, TFK20MultiSettings>::new(&ts).unwrap();
+
+    let table_path =
+        std::env::temp_dir().join(format!("kzg_fk20_table_{}.bin", std::process::id()));
+    let path = fk20_cache_path(table_path.to_str().unwrap());
+    cs.write_fk20_cache(&path, &FileOptions::default()).unwrap();
+
+    let cached =
+        CellSettings::<TKZGSettings, TFK20MultiSettings>::new_with_fk20_cache(&ts, &path).unwrap();
+    let blob = bytes_to_blob::<TFr>(&seeded_blob_bytes(1)).unwrap();
+    let (cells, proofs) = compute_cells_and_kzg_proofs_rust(&blob, &cs).unwrap();
+    let (cached_cells, cached_proofs) = compute_cells_and_kzg_proofs_rust(&blob, &cached).unwrap();
+    assert!(cells
+        .iter()
+        .flatten()
+        .zip(cached_cells.iter().flatten())
+        .all(|(a, b)| a.equals(b)));
+    assert!(proofs.iter().zip(&cached_proofs).all(|(a, b)| a.equals(b)));
+
+    // A flipped bit in the points is corruption, in the setup digest a cache of another setup
+    let bytes = std::fs::read(&path).unwrap();
+    let format = KzgError::InvalidFormat(String::new());
+    let mismatch = KzgError::SetupMismatch(String::new());
+    for (offset, kind) in [(bytes.len() - 1, &format), (40, &mismatch)] {
+        let mut corrupted = bytes.clone();
+        corrupted[offset] ^= 1;
+        std::fs::write(&path, &corrupted).unwrap();
+        let err = CellSettings::<TKZGSettings, TFK20MultiSettings>::new_with_fk20_cache(&ts, &path)
+            .err()
+            .unwrap();
+        assert_eq!(discriminant(&err), discriminant(kind), "{}", err);
+    }
+
+    // A file too short for its header
+    std::fs::write(&path, &bytes[..FK20_CACHE_HEADER_SIZE / 2]).unwrap();
+    assert!(
+        CellSettings::<TKZGSettings, TFK20MultiSettings>::new_with_fk20_cache(&ts, &path).is_err()
+    );
+
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,60 @@
+use kzg::error::KzgError;
+use kzg::framing::{
+    scan_frames, FramedReader, FramedWriter, FRAMED_HEADER_SIZE, FRAME_HEADER_SIZE,
+};
+use kzg::setup_file::{read_trusted_setup, write_trusted_setup};
+use kzg::{Fr, G1Mul, G2Mul, G1, G2};
+
+use crate::tests::kzg_proofs::SECRET;
+
+pub fn framed_setup_roundtrip_test<TFr: Fr + Copy, TG1: G1 + G1Mul<TFr>, TG2: G2 + G2Mul<TFr>>(
+    generate_trusted_setup: &dyn Fn(usize, [u8; 32usize]) -> (Vec<TG1>, Vec<TG2>),
+) {
+    let frame_size = 100;
+    let (expected_g1, expected_g2) = generate_trusted_setup(16, SECRET);
+
+    let mut writer = FramedWriter::new(Vec::new(), frame_size).unwrap();
+    write_trusted_setup::<TFr, TG1, TG2, _>(&mut writer, 16, 5, SECRET).unwrap();
+    let bytes = writer.finish().unwrap();
+    let mut unframed = Vec::new();
+    write_trusted_setup::<TFr, TG1, TG2, _>(&mut unframed, 16, 5, SECRET).unwrap();
+    let num_frames = unframed.chunks(frame_size).count();
+    assert_eq!(
+        bytes.len(),
+        FRAMED_HEADER_SIZE + num_frames * FRAME_HEADER_SIZE + unframed.len()
+    );
+
+    let mut reader = FramedReader::new(bytes.as_slice()).unwrap();
+    assert_eq!(reader.frame_size(), frame_size);
+    let (g1, g2): (Vec<TG1>, Vec<TG2>) = read_trusted_setup(&mut reader).unwrap();
+    assert_eq!(g2.len(), 5);
+    assert!(g1.iter().zip(&expected_g1).all(|(a, b)| a.equals(b)));
+    assert!(g2.iter().zip(&expected_g2).all(|(a, b)| a.equals(b)));
+    assert!(scan_frames(&mut bytes.as_slice()).unwrap().is_empty());
+
+    // Corrupted data in frame 2 and a corrupted length in frame 4 are reported separately
+    let frame_offset = |index: usize| FRAMED_HEADER_SIZE + index * (FRAME_HEADER_SIZE + frame_size);
+    let mut corrupted = bytes.clone();
+    corrupted[frame_offset(2) + FRAME_HEADER_SIZE + 5] ^= 1;
+    corrupted[frame_offset(4)] ^= 0xff;
+    assert_eq!(scan_frames(&mut corrupted.as_slice()).unwrap(), [2, 4]);
+    let mut reader = FramedReader::new(corrupted.as_slice()).unwrap();
+    let err = read_trusted_setup::<TG1, TG2, _>(&mut reader)
+        .err()
+        .unwrap();
+    assert!(matches!(err, KzgError::Io(_)), "{}", err);
+    assert!(
+        err.to_string()
+            .contains("Frame 2 (bytes 200..300) is corrupted"),
+        "{}",
+        err
+    );
+
+    // Truncated and unframed streams
+    let truncated = &bytes[..bytes.len() - 1];
+    let mut reader = FramedReader::new(truncated).unwrap();
+    assert!(read_trusted_setup::<TG1, TG2, _>(&mut reader).is_err());
+    assert!(FramedReader::new(unframed.as_slice()).is_err());
+    assert!(scan_frames(&mut unframed.as_slice()).is_err());
+    assert!(FramedWriter::new(Vec::new(), 0).is_err());
+}
@@ -0,0 +1,58 @@
+use kzg::golden::{
+    generate_golden_vectors, golden_table_file_prefix, seeded_blob_bytes, settings_digest,
+    GOLDEN_MAINNET_SETUP_DIGEST, GOLDEN_SEEDS, GOLDEN_TABLE_FILE_PREFIX, GOLDEN_VECTORS,
+};
+use kzg::{FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1LinComb, G1Mul, KZGSettings, Poly, G1, G2};
+
+use crate::tests::utils::get_trusted_setup_path;
+
+pub fn seeded_blob_is_deterministic_test() {
+    assert_eq!(seeded_blob_bytes(7), seeded_blob_bytes(7));
+    assert_ne!(seeded_blob_bytes(7), seeded_blob_bytes(8));
+}
+
+pub fn golden_vectors_test<
+    TFr: Fr + Copy,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + G1LinComb<TFr, TG1Fp, TG1Affine>,
+    TG2: G2,
+    TPoly: Poly<TFr>,
+    TFFTSettings: FFTSettings<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    load_trusted_setup: &dyn Fn(&str) -> Result<TKZGSettings, String>,
+) {
+    let ts = load_trusted_setup(get_trusted_setup_path().as_str()).unwrap();
+
+    let digest = settings_digest(&ts);
+    assert_eq!(
+        digest, GOLDEN_MAINNET_SETUP_DIGEST,
+        "trusted setup serialization changed"
+    );
+
+    let vectors = generate_golden_vectors(&GOLDEN_SEEDS, &ts).unwrap();
+    for (actual, expected) in vectors.iter().zip(GOLDEN_VECTORS.iter()) {
+        assert!(
+            actual.matches(expected),
+            "golden vector mismatch, regenerated value: {:?}",
+            actual
+        );
+    }
+}
+
+pub fn golden_table_file_test<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>() {
+    let Some(prefix) = golden_table_file_prefix::<TFr, TG1, TG1Fp, TG1Affine>().unwrap() else {
+        return;
+    };
+
+    assert_eq!(
+        prefix, GOLDEN_TABLE_FILE_PREFIX,
+        "table file serialization changed"
+    );
+}
@@ -0,0 +1,77 @@
+use std::io::Read;
+
+use kzg::error::KzgError;
+use kzg::io_utils::{
+    batch_writer, load_secrets_from_file, load_secrets_from_reader, save_secrets_to_file,
+    save_secrets_to_writer,
+};
+use kzg::setup_file::generate_trusted_setup_to_file;
+use kzg::{Compression, Fr, G1Mul, G2Mul, G1, G2};
+
+use crate::tests::kzg_proofs::SECRET;
+
+pub fn secrets_file_roundtrip_test<TFr: Fr + Copy, TG1: G1 + G1Mul<TFr>, TG2: G2 + G2Mul<TFr>>(
+    generate_trusted_setup: &dyn Fn(usize, [u8; 32usize]) -> (Vec<TG1>, Vec<TG2>),
+) {
+    let (expected_g1, expected_g2) = generate_trusted_setup(16, SECRET);
+    let path = std::env::temp_dir().join(format!("kzg_secrets_file_{}.bin", std::process::id()));
+    let path = path.to_str().unwrap();
+
+    save_secrets_to_file(path, &expected_g1, &expected_g2).unwrap();
+    let (g1, g2): (Vec<TG1>, Vec<TG2>) = load_secrets_from_file(path).unwrap();
+    assert_eq!(g1.len(), 16);
+    assert_eq!(g2.len(), 16);
+    assert!(g1.iter().zip(&expected_g1).all(|(a, b)| a.equals(b)));
+    assert!(g2.iter().zip(&expected_g2).all(|(a, b)| a.equals(b)));
+
+    // Saved points and generated setups share the same format
+    let saved = std::fs::read(path).unwrap();
+    let mut bytes = Vec::new();
+    save_secrets_to_writer(&mut bytes, &expected_g1, &expected_g2).unwrap();
+    assert_eq!(bytes, saved);
+
+    // Any reader works, here the setup split across two streams behind a trait object
+    let (head, tail) = bytes.split_at(100);
+    let reader: &mut dyn Read = &mut head.chain(tail);
+    let (g1, g2): (Vec<TG1>, Vec<TG2>) = load_secrets_from_reader(reader).unwrap();
+    assert!(g1.iter().zip(&expected_g1).all(|(a, b)| a.equals(b)));
+    assert!(g2.iter().zip(&expected_g2).all(|(a, b)| a.equals(b)));
+    assert!(load_secrets_from_reader::<TG1, TG2, _>(&mut head.chain(&tail[1..])).is_err());
+
+    generate_trusted_setup_to_file::<TFr, TG1, TG2>(path, 16, 16, SECRET).unwrap();
+    assert_eq!(std::fs::read(path).unwrap(), saved);
+
+    std::fs::remove_file(path).unwrap();
+    assert!(load_secrets_from_file::<TG1, TG2>(path).is_err());
+}
+
+pub fn batch_writer_test<TFr: Fr, TG1: G1 + G1Mul<TFr> + Sync>() {
+    let points = (0..100u64)
+        .map(|i| TG1::generator().mul(&TFr::from_u64(i + 1)))
+        .collect::<Vec<_>>();
+    let expected = TG1::batch_to_bytes(&points, Compression::Compressed).unwrap();
+    let encode = |chunk: &[TG1]| Ok(TG1::batch_to_bytes(chunk, Compression::Compressed)?);
+
+    // Chunks are written in order whatever their length
+    for chunk_len in [1, 7, 100, 1000] {
+        let mut bytes = Vec::new();
+        batch_writer(&mut bytes, &points, chunk_len, encode).unwrap();
+        assert_eq!(bytes, expected);
+    }
+
+    // Nothing after a chunk that fails to encode is written
+    let mut bytes = Vec::new();
+    let result = batch_writer(&mut bytes, &points, 7, |chunk: &[TG1]| {
+        if chunk[0].equals(&points[21]) {
+            return Err(KzgError::Other(String::from("Encoding failed")));
+        }
+        encode(chunk)
+    });
+    assert!(matches!(result, Err(KzgError::Other(_))));
+    assert_eq!(bytes, expected[..21 * expected.len() / points.len()]);
+
+    assert!(matches!(
+        batch_writer(&mut Vec::new(), &points, 0, encode),
+        Err(KzgError::InvalidArgument(_))
+    ));
+}
@@ -1,4 +1,15 @@
-use kzg::{FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1Mul, KZGSettings, Poly, G1, G2};
+use kzg::common_utils::reverse_bit_order;
+use kzg::io_utils::save_secrets_to_writer_with_compression;
+use kzg::rerandomize::check_srs_consistency;
+use kzg::setup_bytes::load_setup_from_bytes;
+use kzg::{
+    Compression, FFTFr, FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1Mul, G2Mul, KZGSettings,
+    PairingVerify, Poly, SetupForm, FFTG1, G1, G2,
+};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use crate::tests::utils::get_trusted_setup_path;
 
 pub const SECRET: [u8; 32usize] = [
     0xa4, 0x73, 0x31, 0x95, 0x28, 0xc8, 0xb6, 0xea, 0x4d, 0x08, 0xcc, 0x53, 0x18, 0x00, 0x00, 0x00,
@@ -201,3 +212,182 @@ pub fn proof_multi<
         .unwrap();
     assert!(!result);
 }
+
+#[allow(clippy::type_complexity)]
+pub fn trusted_setup_from_rng_test<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + PairingVerify<TG1, TG2>,
+    TG2: G2 + G2Mul<TFr>,
+>(
+    generate_trusted_setup_from_rng: &dyn Fn(&mut StdRng, usize) -> (Vec<TG1>, Vec<TG2>),
+) {
+    let (g1, g2) = generate_trusted_setup_from_rng(&mut StdRng::seed_from_u64(42), 16);
+    assert_eq!(g1.len(), 16);
+    assert_eq!(g2.len(), 16);
+    assert!(check_srs_consistency(&g1, &g2).unwrap());
+
+    // The same seed gives the same setup, another seed a different one
+    let (same_g1, same_g2) = generate_trusted_setup_from_rng(&mut StdRng::seed_from_u64(42), 16);
+    assert!(g1.iter().zip(&same_g1).all(|(a, b)| a.equals(b)));
+    assert!(g2.iter().zip(&same_g2).all(|(a, b)| a.equals(b)));
+
+    let (other_g1, _) = generate_trusted_setup_from_rng(&mut StdRng::seed_from_u64(43), 16);
+    assert!(!g1[1].equals(&other_g1[1]));
+}
+
+pub fn truncate_setup<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG2: G2,
+    TPoly: Poly<TFr>,
+    TFFTSettings: FFTSettings<TFr> + FFTG1<TG1>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    generate_trusted_setup: &dyn Fn(usize, [u8; 32usize]) -> (Vec<TG1>, Vec<TG2>),
+) {
+    let (s1, s2) = generate_trusted_setup(64, SECRET);
+    let fs = TFFTSettings::new(6).unwrap();
+    let ks = TKZGSettings::new(&s1, &s2, 64, &fs).unwrap();
+
+    for (n, width) in [(1, 1), (8, 8), (9, 16), (64, 64)] {
+        let truncated = ks.truncate(n, SetupForm::Monomial).unwrap();
+        assert_eq!(truncated.get_g1_secret().len(), n);
+        assert_eq!(truncated.get_g2_secret().len(), s2.len());
+        assert_eq!(truncated.get_fft_settings().get_max_width(), width);
+        // The domain is generated by a primitive root of unity of order `width`
+        if width > 1 {
+            let root = truncated
+                .get_fft_settings()
+                .get_expanded_roots_of_unity_at(1);
+            assert!(root.pow(width).is_one());
+            assert!(!root.pow(width / 2).is_one());
+        }
+
+        // Commitments and proofs are the ones of the full setup
+        let mut p = TPoly::new(n);
+        for i in 0..n {
+            p.set_coeff_at(i, &TFr::from_u64(3 + 5 * i as u64));
+        }
+        let commitment = truncated.commit_to_poly(&p).unwrap();
+        assert!(commitment.equals(&ks.commit_to_poly(&p).unwrap()));
+        let x = TFr::from_u64(25);
+        let proof = truncated.compute_proof_single(&p, &x).unwrap();
+        assert!(truncated
+            .check_proof_single(&commitment, &proof, &x, &p.eval(&x))
+            .unwrap());
+        assert!(ks
+            .check_proof_single(&commitment, &proof, &x, &p.eval(&x))
+            .unwrap());
+    }
+
+    assert!(ks.truncate(0, SetupForm::Monomial).is_err());
+    assert!(ks.truncate(65, SetupForm::Monomial).is_err());
+}
+
+/// The mainnet setup is loaded in Lagrange form, truncating it gives Lagrange points over the
+/// smaller domain
+pub fn truncate_loaded_setup<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG2: G2,
+    TPoly: Poly<TFr>,
+    TFFTSettings: FFTSettings<TFr> + FFTFr<TFr> + FFTG1<TG1>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    load_trusted_setup: &dyn Fn(&str) -> Result<TKZGSettings, String>,
+) {
+    let ks = load_trusted_setup(get_trusted_setup_path().as_str()).unwrap();
+    let g1 = ks.get_g1_secret();
+
+    // Truncating to the full size gives the same points back
+    let same = ks.truncate(g1.len(), SetupForm::Lagrange).unwrap();
+    assert!(same
+        .get_g1_secret()
+        .iter()
+        .zip(g1)
+        .all(|(a, b)| a.equals(b)));
+
+    // The commitment to the evaluations of a small polynomial over either domain is the same
+    let n = 16;
+    let truncated = ks.truncate(n, SetupForm::Lagrange).unwrap();
+    assert_eq!(truncated.get_g1_secret().len(), n);
+
+    let coeffs = (0..n)
+        .map(|i| TFr::from_u64(7 + 2 * i as u64))
+        .collect::<Vec<_>>();
+    let commit_evaluations = |ks: &TKZGSettings, len: usize| {
+        let mut padded = coeffs.clone();
+        padded.resize(len, TFr::zero());
+        let mut evaluations = ks.get_fft_settings().fft_fr(&padded, false).unwrap();
+        reverse_bit_order(&mut evaluations).unwrap();
+        ks.get_g1_secret()
+            .iter()
+            .zip(&evaluations)
+            .fold(TG1::identity(), |acc, (point, scalar)| {
+                acc.add_or_dbl(&point.mul(scalar))
+            })
+    };
+    assert!(commit_evaluations(&truncated, n).equals(&commit_evaluations(&ks, g1.len())));
+
+    // Lagrange points need a power of two domain
+    assert!(ks.truncate(12, SetupForm::Lagrange).is_err());
+}
+
+pub fn setup_from_bytes<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG2: G2,
+    TPoly: Poly<TFr>,
+    TFFTSettings: FFTSettings<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    generate_trusted_setup: &dyn Fn(usize, [u8; 32usize]) -> (Vec<TG1>, Vec<TG2>),
+) {
+    let (s1, s2) = generate_trusted_setup(12, SECRET);
+    let fs = TFFTSettings::new(4).unwrap();
+    let ks = TKZGSettings::new(&s1, &s2, 12, &fs).unwrap();
+    let mut p = TPoly::new(12);
+    for i in 0..12 {
+        p.set_coeff_at(i, &TFr::from_u64(7 + 2 * i as u64));
+    }
+
+    for compression in [Compression::Compressed, Compression::Uncompressed] {
+        let mut bytes = Vec::new();
+        save_secrets_to_writer_with_compression(&mut bytes, &s1, &s2, compression).unwrap();
+
+        let (g1, g2) = load_setup_from_bytes::<TG1, TG2>(&bytes, compression).unwrap();
+        assert!(g1.len() == s1.len() && g1.iter().zip(&s1).all(|(a, b)| a.equals(b)));
+        assert!(g2.len() == s2.len() && g2.iter().zip(&s2).all(|(a, b)| a.equals(b)));
+
+        let loaded = TKZGSettings::from_bytes(&bytes, compression).unwrap();
+        assert_eq!(loaded.get_fft_settings().get_max_width(), 16);
+        let commitment = loaded.commit_to_poly(&p).unwrap();
+        assert!(commitment.equals(&ks.commit_to_poly(&p).unwrap()));
+
+        // Truncated or trailing bytes, and points read with the wrong encoding
+        let other = match compression {
+            Compression::Compressed => Compression::Uncompressed,
+            Compression::Uncompressed => Compression::Compressed,
+        };
+        assert!(TKZGSettings::from_bytes(&bytes[..bytes.len() - 1], compression).is_err());
+        assert!(TKZGSettings::from_bytes(&[bytes.as_slice(), &[0]].concat(), compression).is_err());
+        assert!(TKZGSettings::from_bytes(&bytes, other).is_err());
+        assert!(TKZGSettings::from_bytes(&bytes[..4], compression).is_err());
+    }
+
+    let mut empty = Vec::new();
+    save_secrets_to_writer_with_compression::<TG1, TG2, _>(
+        &mut empty,
+        &[],
+        &s2,
+        Compression::Compressed,
+    )
+    .unwrap();
+    assert!(TKZGSettings::from_bytes(&empty, Compression::Compressed).is_err());
+}
@@ -0,0 +1,56 @@
+use kzg::backend::KzgBackend;
+use kzg::eip_4844::{
+    blob_to_kzg_commitment_rust, bytes_to_blob, compute_kzg_proof_rust, verify_kzg_proof_rust,
+    FIELD_ELEMENTS_PER_BLOB,
+};
+use kzg::golden::seeded_blob_bytes;
+use kzg::lagrange::LagrangePoly;
+use kzg::{FFTSettings, Fr, KZGSettings, G1};
+
+use crate::tests::utils::get_trusted_setup_path;
+
+/// Commit to, evaluate and open blobs as `LagrangePoly`s, and compare with the blob functions
+pub fn lagrange_poly_test<B: KzgBackend>()
+where
+    B::Fr: Copy,
+{
+    let ts = B::load_trusted_setup_file(&get_trusted_setup_path()).unwrap();
+    let roots_of_unity = ts.get_fft_settings().get_roots_of_unity();
+
+    assert!(LagrangePoly::<B::Fr>::from_evaluations(vec![B::Fr::one(); 16]).is_err());
+
+    let blob = bytes_to_blob::<B::Fr>(&seeded_blob_bytes(1)).unwrap();
+    let poly = LagrangePoly::from_blob(&blob).unwrap();
+    assert_eq!(poly.len(), FIELD_ELEMENTS_PER_BLOB);
+
+    let commitment: B::G1 = poly.commit(&ts).unwrap();
+    assert!(commitment.equals(&blob_to_kzg_commitment_rust(&blob, &ts).unwrap()));
+
+    // Outside of the domain, the same opening as the blob functions
+    let z = B::Fr::from_u64(0x1234_5678);
+    let (proof, y): (B::G1, B::Fr) = poly.open_at(&z, &ts).unwrap();
+    let (expected_proof, expected_y) = compute_kzg_proof_rust(&blob, &z, &ts).unwrap();
+    assert!(proof.equals(&expected_proof));
+    assert!(y.equals(&expected_y));
+    assert!(poly.evaluate_at(&z, &ts).unwrap().equals(&y));
+    assert!(verify_kzg_proof_rust(&commitment, &z, &y, &proof, &ts).unwrap());
+
+    // Inside of the domain, the evaluation itself
+    let z = roots_of_unity[5];
+    assert!(poly.evaluate_at(&z, &ts).unwrap().equals(&blob[5]));
+    let (proof, y): (B::G1, B::Fr) = poly.open_at(&z, &ts).unwrap();
+    assert!(y.equals(&blob[5]));
+    assert!(verify_kzg_proof_rust(&commitment, &z, &y, &proof, &ts).unwrap());
+
+    // A polynomial of low degree evaluates to itself anywhere: p(x) = 3x + 2
+    let p = |x: &B::Fr| x.mul(&B::Fr::from_u64(3)).add(&B::Fr::from_u64(2));
+    let line = LagrangePoly::from_evaluations(
+        roots_of_unity[..FIELD_ELEMENTS_PER_BLOB]
+            .iter()
+            .map(p)
+            .collect(),
+    )
+    .unwrap();
+    let x = B::Fr::from_u64(987_654_321);
+    assert!(line.evaluate_at(&x, &ts).unwrap().equals(&p(&x)));
+}
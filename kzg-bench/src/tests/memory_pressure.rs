@@ -0,0 +1,194 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use kzg::eip_4844::{blob_to_kzg_commitment_rust, bytes_to_blob};
+use kzg::eip_7594::{compute_cells_and_kzg_proofs_rust, CellSettings};
+use kzg::memory_pressure::{under_memory_pressure, MemoryMonitor, MemoryUsage, ReleasableCache};
+use kzg::msm::msm_impls::msm;
+use kzg::msm::precompute::{precompute, ReleasableTable};
+use kzg::{
+    FFTFr, FFTSettings, FK20MultiSettings, Fr, G1Affine, G1Fp, G1GetFp, G1LinComb, G1Mul,
+    G1ProjAddAffine, KZGSettings, Poly, FFTG1, G1, G2,
+};
+
+use crate::tests::eip_4844::generate_random_blob_bytes;
+use crate::tests::utils::get_trusted_setup_path;
+
+static USED: AtomicUsize = AtomicUsize::new(0);
+
+fn usage() -> Option<MemoryUsage> {
+    Some(MemoryUsage {
+        used: USED.load(Ordering::SeqCst),
+        limit: 1000,
+    })
+}
+
+struct CountingCache(AtomicUsize);
+
+impl ReleasableCache for CountingCache {
+    fn release(&self) -> usize {
+        self.0.fetch_add(1, Ordering::SeqCst);
+        10
+    }
+}
+
+pub fn memory_monitor_test<
+    TFr: Fr + Send + 'static,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + 'static,
+    TG1Fp: G1Fp + 'static,
+    TG1Affine: G1Affine<TG1, TG1Fp> + 'static,
+    TProjAddAffine: G1ProjAddAffine<TG1, TG1Fp, TG1Affine>,
+>() {
+    assert!(MemoryMonitor::with_source(0, Box::new(usage)).is_err());
+    assert!(MemoryMonitor::with_source(101, Box::new(usage)).is_err());
+    assert!(!MemoryMonitor::with_source(90, Box::new(|| None))
+        .unwrap()
+        .check());
+
+    let npoints = 64;
+    let points = (0..npoints).map(|_| TG1::rand()).collect::<Vec<_>>();
+    let scalars = (0..npoints).map(|_| TFr::rand()).collect::<Vec<_>>();
+    let expected =
+        msm::<TG1, TG1Fp, TG1Affine, TProjAddAffine, TFr>(&points, &scalars, npoints, None);
+
+    let table = Arc::new(ReleasableTable::new(
+        precompute::<TFr, TG1, TG1Fp, TG1Affine>(&points).unwrap(),
+    ));
+    let had_table = table.get().is_some();
+    let cache = Arc::new(CountingCache(AtomicUsize::new(0)));
+    let mut monitor = MemoryMonitor::with_source(90, Box::new(usage)).unwrap();
+    monitor.register(table.clone());
+    monitor.register(cache.clone());
+
+    USED.store(899, Ordering::SeqCst);
+    assert!(!monitor.check());
+    assert!(!under_memory_pressure());
+    assert_eq!(cache.0.load(Ordering::SeqCst), 0);
+    assert_eq!(table.get().is_some(), had_table);
+
+    // Crossing the threshold releases the caches and stops new tables from being built
+    USED.store(900, Ordering::SeqCst);
+    assert!(monitor.check());
+    assert!(under_memory_pressure());
+    assert_eq!(cache.0.load(Ordering::SeqCst), 1);
+    assert!(table.get().is_none());
+    assert!(precompute::<TFr, TG1, TG1Fp, TG1Affine>(&points)
+        .unwrap()
+        .is_none());
+
+    // MSMs keep working without the table
+    let actual = msm::<TG1, TG1Fp, TG1Affine, TProjAddAffine, TFr>(
+        &points,
+        &scalars,
+        npoints,
+        table.get().as_deref(),
+    );
+    assert!(actual.equals(&expected));
+
+    USED.store(100, Ordering::SeqCst);
+    assert!(!monitor.check());
+    assert!(!under_memory_pressure());
+    let rebuilt = precompute::<TFr, TG1, TG1Fp, TG1Affine>(&points).unwrap();
+    assert_eq!(rebuilt.is_some(), had_table);
+    table.set(rebuilt);
+    assert_eq!(table.get().is_some(), had_table);
+
+    // Each monitor keeps its own state, the process is under pressure while any of them is
+    let mut other = MemoryMonitor::with_source(
+        90,
+        Box::new(|| {
+            Some(MemoryUsage {
+                used: 0,
+                limit: 1000,
+            })
+        }),
+    )
+    .unwrap();
+    other.register(cache.clone());
+    USED.store(950, Ordering::SeqCst);
+    assert!(monitor.check());
+    assert!(!other.check());
+    assert!(monitor.under_pressure());
+    assert!(!other.under_pressure());
+    assert!(under_memory_pressure());
+    assert_eq!(cache.0.load(Ordering::SeqCst), 2);
+    drop(other);
+    assert!(under_memory_pressure());
+
+    // Background checks
+    let handle = monitor.spawn(Duration::from_millis(1));
+    while cache.0.load(Ordering::SeqCst) < 3 {
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    assert!(handle.under_pressure());
+    assert!(under_memory_pressure());
+    assert!(table.get().is_none());
+
+    // A stopped monitor no longer holds the process under pressure
+    handle.stop();
+    assert!(!under_memory_pressure());
+    USED.store(100, Ordering::SeqCst);
+}
+
+/// The precomputation table of the settings and the FK20 settings of the cells are released, and
+/// results do not change
+pub fn settings_release_test<
+    TFr: Fr + Copy + Send + Sync + 'static,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + G1LinComb<TFr, TG1Fp, TG1Affine> + 'static,
+    TG2: G2,
+    TPoly: Poly<TFr>,
+    TFFTSettings: FFTSettings<TFr> + FFTFr<TFr> + FFTG1<TG1>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine> + Send + Sync + 'static,
+    TFK20MultiSettings: FK20MultiSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TKZGSettings, TG1Fp, TG1Affine>
+        + Send
+        + Sync
+        + 'static,
+    TG1Fp: G1Fp + 'static,
+    TG1Affine: G1Affine<TG1, TG1Fp> + 'static,
+>(
+    load_trusted_setup: &dyn Fn(&str) -> Result<TKZGSettings, String>,
+) {
+    let ts = load_trusted_setup(get_trusted_setup_path().as_str()).unwrap();
+    let cs = Arc::new(CellSettings::<TKZGSettings, TFK20MultiSettings>::new(&ts).unwrap());
+    let blob = bytes_to_blob::<TFr>(&generate_random_blob_bytes(&mut rand::thread_rng())).unwrap();
+    let commitment = blob_to_kzg_commitment_rust(&blob, &ts).unwrap();
+    let (cells, proofs) = compute_cells_and_kzg_proofs_rust(&blob, cs.as_ref()).unwrap();
+
+    let table = ts.get_releasable_precomputation().unwrap();
+    let had_table = ts.get_precomputation().is_some();
+    let mut monitor = MemoryMonitor::with_source(90, Box::new(usage)).unwrap();
+    monitor.register(table.clone());
+    monitor.register(cs.clone());
+
+    USED.store(950, Ordering::SeqCst);
+    assert!(monitor.check());
+    assert!(ts.get_precomputation().is_none());
+    // Clones share the table
+    assert!(ts.clone().get_precomputation().is_none());
+    assert!(blob_to_kzg_commitment_rust(&blob, &ts)
+        .unwrap()
+        .equals(&commitment));
+
+    // The FK20 settings are rebuilt for each call, and not kept while under pressure
+    let (released_cells, released_proofs) =
+        compute_cells_and_kzg_proofs_rust(&blob, cs.as_ref()).unwrap();
+    assert!(released_cells
+        .iter()
+        .flatten()
+        .zip(cells.iter().flatten())
+        .all(|(a, b)| a.equals(b)));
+    assert!(released_proofs
+        .iter()
+        .zip(&proofs)
+        .all(|(a, b)| a.equals(b)));
+    assert_eq!(cs.release(), 0);
+
+    USED.store(100, Ordering::SeqCst);
+    assert!(!monitor.check());
+    compute_cells_and_kzg_proofs_rust(&blob, cs.as_ref()).unwrap();
+    assert!(cs.release() > 0);
+
+    table.set(precompute(ts.get_g1_secret()).unwrap());
+    assert_eq!(ts.get_precomputation().is_some(), had_table);
+}
@@ -1,15 +1,58 @@
+pub mod artifact_store;
+pub mod backend;
+pub mod batch_inverse;
 pub mod bls12_381;
+pub mod bundle;
 pub mod c_bindings;
+pub mod cancel;
+pub mod ceremony;
 pub mod consts;
+pub mod cooperative;
+pub mod cross_backend;
 pub mod das;
+pub mod das_matrix;
+pub mod dev_setup;
+pub mod diagnostics;
 pub mod eip_4844;
+pub mod eip_7594;
+pub mod envelope;
+pub mod equivalence;
+pub mod evaluation_cache;
 pub mod fft_fr;
 pub mod fft_g1;
+pub mod file_options;
 pub mod finite;
+pub mod fk20_cache;
 pub mod fk20_proofs;
+pub mod framing;
+pub mod golden;
+pub mod io_utils;
 pub mod kzg_proofs;
+pub mod lagrange;
+pub mod memory_pressure;
 pub mod msm;
+pub mod multiproof;
+pub mod multiset;
+pub mod namespace;
+#[cfg(feature = "async")]
+pub mod nonblocking;
+pub mod opening;
+pub mod parallelism;
 pub mod poly;
+pub mod progress;
 pub mod recover;
+pub mod rerandomize;
+pub mod sampled_opening;
+pub mod secret;
+pub mod self_test;
+pub mod setup_convert;
+pub mod setup_file;
+pub mod sharding;
+pub mod shifted_opening;
 pub mod utils;
+pub mod validation;
+#[cfg(feature = "async")]
+pub mod verification_queue;
+pub mod verifier;
+pub mod witness;
 pub mod zero_poly;
@@ -0,0 +1,83 @@
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use kzg::eip_4844::BYTES_PER_G1_UNCOMPRESSED;
+use kzg::msm::accelerator::{set_msm_accelerator, MsmAccelerator};
+use kzg::msm::msm_impls::msm;
+use kzg::{Fr, G1Affine, G1Fp, G1GetFp, G1Mul, G1ProjAddAffine, Scalar256, G1};
+
+/// Accelerator computing on the CPU, counting the MSMs it is handed
+struct CountingAccelerator<TFr, TG1> {
+    available: bool,
+    calls: AtomicUsize,
+    _marker: PhantomData<fn() -> (TFr, TG1)>,
+}
+
+impl<TFr: Fr, TG1: G1 + G1Mul<TFr>> MsmAccelerator for CountingAccelerator<TFr, TG1> {
+    fn is_available(&self) -> bool {
+        self.available
+    }
+
+    fn min_len(&self) -> usize {
+        16
+    }
+
+    fn msm(&self, points: &[u8], scalars: &[Scalar256]) -> Option<[u8; BYTES_PER_G1_UNCOMPRESSED]> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+
+        let mut out = TG1::identity();
+        for (point, scalar) in points.chunks(BYTES_PER_G1_UNCOMPRESSED).zip(scalars) {
+            let mut bytes = [0u8; 32];
+            bytes.copy_from_slice(scalar.as_u8());
+            bytes.reverse();
+            let point = TG1::from_bytes_uncompressed(point).ok()?;
+            out.add_or_dbl_assign(&point.mul(&TFr::from_bytes(&bytes).ok()?));
+        }
+
+        out.to_bytes_uncompressed().ok()
+    }
+}
+
+pub fn msm_accelerator_offload_and_fallback<
+    TFr: Fr + 'static,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + 'static,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+    TProjAddAffine: G1ProjAddAffine<TG1, TG1Fp, TG1Affine>,
+>() {
+    let npoints = 64;
+    let points = (0..npoints).map(|_| TG1::rand()).collect::<Vec<_>>();
+    let scalars = (0..npoints).map(|_| TFr::rand()).collect::<Vec<_>>();
+    let lincomb =
+        |len| msm::<TG1, TG1Fp, TG1Affine, TProjAddAffine, TFr>(&points, &scalars, len, None);
+
+    set_msm_accelerator(None);
+    let expected_short = lincomb(8);
+    let expected = lincomb(npoints);
+
+    // Long enough MSMs go to the device
+    let device: &'static CountingAccelerator<TFr, TG1> = Box::leak(Box::new(CountingAccelerator {
+        available: true,
+        calls: AtomicUsize::new(0),
+        _marker: PhantomData,
+    }));
+    set_msm_accelerator(Some(device));
+    assert!(lincomb(npoints).equals(&expected));
+    assert_eq!(device.calls.load(Ordering::SeqCst), 1);
+
+    // Short MSMs stay on the CPU
+    assert!(lincomb(8).equals(&expected_short));
+    assert_eq!(device.calls.load(Ordering::SeqCst), 1);
+
+    // Without a device, the CPU path runs
+    let absent: &'static CountingAccelerator<TFr, TG1> = Box::leak(Box::new(CountingAccelerator {
+        available: false,
+        calls: AtomicUsize::new(0),
+        _marker: PhantomData,
+    }));
+    set_msm_accelerator(Some(absent));
+    assert!(lincomb(npoints).equals(&expected));
+    assert_eq!(absent.calls.load(Ordering::SeqCst), 0);
+
+    set_msm_accelerator(None);
+}
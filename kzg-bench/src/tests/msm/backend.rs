@@ -0,0 +1,45 @@
+use kzg::msm::msm_impls::{msm, BgmwMsm, PippengerMsm};
+use kzg::msm::MsmBackend;
+use kzg::{Fr, G1Affine, G1Fp, G1LinComb, G1ProjAddAffine, G1};
+
+fn check_backend<TFr: Fr, TG1: G1, TBackend: MsmBackend<TFr, TG1>>(
+    points: &[TG1],
+    scalars: &[TFr],
+    expected: &TG1,
+) {
+    let backend = TBackend::precompute(points).unwrap();
+    assert!(backend.msm(scalars).unwrap().equals(expected));
+    // The prepared points are reused
+    assert!(backend.msm(scalars).unwrap().equals(expected));
+    assert!(TBackend::msm_with_points(points, scalars)
+        .unwrap()
+        .equals(expected));
+
+    assert!(backend.msm(&scalars[1..]).is_err());
+    assert!(TBackend::msm_with_points(&points[1..], scalars).is_err());
+}
+
+pub fn msm_backend_matches_msm<
+    TFr: Fr,
+    TG1: G1LinComb<TFr, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+    TProjAddAffine: G1ProjAddAffine<TG1, TG1Fp, TG1Affine>,
+>() {
+    for npoints in [1, 7, 64] {
+        let points = (0..npoints).map(|_| TG1::rand()).collect::<Vec<_>>();
+        let scalars = (0..npoints).map(|_| TFr::rand()).collect::<Vec<_>>();
+        let expected =
+            msm::<TG1, TG1Fp, TG1Affine, TProjAddAffine, TFr>(&points, &scalars, npoints, None);
+
+        check_backend::<TFr, TG1, PippengerMsm<TFr, TG1, TG1Fp, TG1Affine>>(
+            &points, &scalars, &expected,
+        );
+        check_backend::<TFr, TG1, BgmwMsm<TFr, TG1, TG1Fp, TG1Affine>>(
+            &points, &scalars, &expected,
+        );
+    }
+
+    let backend = PippengerMsm::<TFr, TG1, TG1Fp, TG1Affine>::precompute(&[]).unwrap();
+    assert!(backend.msm(&[]).unwrap().is_inf());
+}
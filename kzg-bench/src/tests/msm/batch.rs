@@ -0,0 +1,41 @@
+use kzg::msm::msm_impls::msm;
+use kzg::msm::precompute::precompute;
+use kzg::{Fr, G1Affine, G1Fp, G1GetFp, G1Mul, G1ProjAddAffine, G1};
+
+pub fn bgmw_multiply_batch_matches_msm<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+    TProjAddAffine: G1ProjAddAffine<TG1, TG1Fp, TG1Affine>,
+>() {
+    let npoints = 150;
+    let points = (0..npoints).map(|_| TG1::rand()).collect::<Vec<_>>();
+    let Some(table) = precompute::<TFr, TG1, TG1Fp, TG1Affine>(&points).unwrap() else {
+        return;
+    };
+
+    let lens = [npoints, 0, 1, 2, 9, 77, npoints, 33];
+    let scalar_sets = lens
+        .iter()
+        .map(|&len| (0..len).map(|_| TFr::rand()).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let scalars = scalar_sets
+        .iter()
+        .map(|scalars| scalars.iter().map(TFr::to_scalar).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let scalar_refs = scalars.iter().map(Vec::as_slice).collect::<Vec<_>>();
+
+    let actual = table.multiply_batch(&scalar_refs);
+    assert_eq!(actual.len(), lens.len());
+    for (actual, scalars) in actual.iter().zip(&scalar_sets) {
+        let expected = msm::<TG1, TG1Fp, TG1Affine, TProjAddAffine, TFr>(
+            &points,
+            scalars,
+            scalars.len(),
+            None,
+        );
+        assert!(actual.equals(&expected));
+    }
+    assert!(table.multiply_batch(&[]).is_empty());
+}
@@ -0,0 +1,48 @@
+use kzg::msm::msm_impls::{msm, msm_u128, msm_u64};
+use kzg::{Fr, G1Affine, G1Fp, G1GetFp, G1Mul, G1ProjAddAffine, G1};
+
+pub fn bounded_msm_matches_msm<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+    TProjAddAffine: G1ProjAddAffine<TG1, TG1Fp, TG1Affine>,
+>() {
+    let points = (0..300).map(|_| TG1::rand()).collect::<Vec<_>>();
+
+    for len in [0, 1, 2, 9, 64, 300] {
+        // Extremes of the range, including the top bit which needs the carry window
+        let small = (0..len as u64)
+            .map(|i| match i % 4 {
+                0 => u64::MAX,
+                1 => 0,
+                _ => i.wrapping_mul(0x9E37_79B9_7F4A_7C15),
+            })
+            .collect::<Vec<_>>();
+        let wide = small
+            .iter()
+            .enumerate()
+            .map(|(i, &scalar)| match i % 4 {
+                0 => u128::MAX,
+                _ => (scalar as u128) << 64 | (i as u128),
+            })
+            .collect::<Vec<_>>();
+
+        let small_fr = small
+            .iter()
+            .map(|&scalar| TFr::from_u64(scalar))
+            .collect::<Vec<_>>();
+        let wide_fr = wide
+            .iter()
+            .map(|&scalar| TFr::from_u64_arr(&[scalar as u64, (scalar >> 64) as u64, 0, 0]))
+            .collect::<Vec<_>>();
+
+        let expected =
+            msm::<TG1, TG1Fp, TG1Affine, TProjAddAffine, TFr>(&points, &small_fr, len, None);
+        assert!(msm_u64::<TG1, TG1Fp, TG1Affine>(&points[..len], &small).equals(&expected));
+
+        let expected =
+            msm::<TG1, TG1Fp, TG1Affine, TProjAddAffine, TFr>(&points, &wide_fr, len, None);
+        assert!(msm_u128::<TG1, TG1Fp, TG1Affine>(&points[..len], &wide).equals(&expected));
+    }
+}
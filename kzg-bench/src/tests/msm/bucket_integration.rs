@@ -0,0 +1,93 @@
+use kzg::msm::{integrate_buckets_affine, integrate_buckets_xyzz, p1_to_jacobian, P1XYZZ};
+use kzg::{Fr, G1Affine, G1Fp, G1GetFp, G1Mul, G1};
+
+/// Buckets as left by Pippenger: every third one empty, and the others holding a single point,
+/// added or subtracted, with `single`, or the sum of several points otherwise
+pub fn random_buckets<TG1: G1 + G1GetFp<TFp>, TFp: G1Fp, TG1Affine: G1Affine<TG1, TFp>>(
+    count: usize,
+    single: bool,
+) -> (Vec<P1XYZZ<TFp>>, Vec<TG1>) {
+    (0..count)
+        .map(|i| {
+            if i % 3 == 2 {
+                return (P1XYZZ::default(), TG1::identity());
+            }
+
+            if single {
+                let point = TG1::rand();
+                let affine = TG1Affine::into_affine(&point);
+                let mut zzz = TFp::ONE;
+                let sum = if i % 2 == 1 {
+                    zzz.neg_assign();
+                    TG1::identity().sub(&point)
+                } else {
+                    point
+                };
+                let bucket = P1XYZZ {
+                    x: *affine.x(),
+                    y: *affine.y(),
+                    zzz,
+                    zz: TFp::ONE,
+                };
+                (bucket, sum)
+            } else {
+                // A sum in Jacobian coordinates, whose Z is not one
+                let point = TG1::rand().add_or_dbl(&TG1::rand());
+                let zz = point.z().square();
+                let bucket = P1XYZZ {
+                    x: *point.x(),
+                    y: *point.y(),
+                    zzz: zz.mul_fp(point.z()),
+                    zz,
+                };
+                (bucket, point)
+            }
+        })
+        .unzip()
+}
+
+pub fn bucket_integration_matches_naive<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TFp>,
+    TFp: G1Fp,
+    TG1Affine: G1Affine<TG1, TFp>,
+>() {
+    for count in [1, 2, 3, 64] {
+        for single in [true, false] {
+            let (buckets, points) = random_buckets::<TG1, TFp, TG1Affine>(count, single);
+
+            let mut expected = TG1::identity();
+            for (i, point) in points.iter().enumerate() {
+                expected = expected.add_or_dbl(&point.mul(&TFr::from_u64(i as u64 + 1)));
+            }
+
+            for integrated in [
+                integrate_buckets_xyzz(&buckets),
+                integrate_buckets_affine(&buckets),
+            ] {
+                let mut result = TG1::default();
+                p1_to_jacobian(&mut result, &integrated);
+                assert!(result.equals(&expected));
+            }
+        }
+    }
+
+    // Equal buckets, so that the running sum doubles instead of adding
+    let point = TG1::rand();
+    let affine = TG1Affine::into_affine(&point);
+    let bucket = P1XYZZ {
+        x: *affine.x(),
+        y: *affine.y(),
+        zzz: TFp::ONE,
+        zz: TFp::ONE,
+    };
+    let expected = point.mul(&TFr::from_u64(1 + 2 + 3 + 4));
+    for integrated in [
+        integrate_buckets_xyzz(&[bucket; 4]),
+        integrate_buckets_affine(&[bucket; 4]),
+    ] {
+        let mut result = TG1::default();
+        p1_to_jacobian(&mut result, &integrated);
+        assert!(result.equals(&expected));
+    }
+}
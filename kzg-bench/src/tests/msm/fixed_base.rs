@@ -0,0 +1,48 @@
+use kzg::msm::fixed_base::FixedBaseMsm;
+use kzg::{Fr, G1Affine, G1Fp, G1GetFp, G1Mul, G1ProjAddAffine, Poly, G1};
+
+pub fn fixed_base_msm_matches_naive<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+    TProjAddAffine: G1ProjAddAffine<TG1, TG1Fp, TG1Affine>,
+    TPoly: Poly<TFr>,
+>() {
+    let npoints = 100;
+    let mut points = (0..npoints).map(|_| TG1::rand()).collect::<Vec<_>>();
+    // The identity is stored without batch affine conversion
+    points[3] = TG1::identity();
+    let mut scalars = (0..npoints).map(|_| TFr::rand()).collect::<Vec<_>>();
+    // Edge cases of the digit decomposition
+    scalars[0] = TFr::zero();
+    scalars[1] = TFr::one();
+    scalars[2] = TFr::zero().sub(&TFr::one());
+
+    for window in [1, 4, 7] {
+        let table =
+            FixedBaseMsm::<TFr, TG1, TG1Fp, TG1Affine, TProjAddAffine>::new(&points, window)
+                .unwrap();
+        assert_eq!(table.window(), window);
+        assert!(table.memory_usage() >= npoints * ((1 << window) - 1));
+
+        for len in [0, 1, 9, 70, npoints] {
+            let poly = TPoly::from_coeffs(&scalars[..len]);
+            let mut expected = TG1::identity();
+            for (point, scalar) in points.iter().zip(&scalars).take(len) {
+                expected = expected.add_or_dbl(&point.mul(scalar));
+            }
+            assert!(table.commit(&poly).unwrap().equals(&expected));
+        }
+    }
+
+    let table =
+        FixedBaseMsm::<TFr, TG1, TG1Fp, TG1Affine, TProjAddAffine>::new(&points[..10], 4).unwrap();
+    assert!(table.commit(&TPoly::from_coeffs(&scalars[..11])).is_err());
+    for window in [0, 17] {
+        assert!(
+            FixedBaseMsm::<TFr, TG1, TG1Fp, TG1Affine, TProjAddAffine>::new(&points, window)
+                .is_err()
+        );
+    }
+}
@@ -1,3 +1,19 @@
+#[cfg(feature = "gpu")]
+pub mod accelerator;
+pub mod backend;
+pub mod batch;
 pub mod batch_adder;
+pub mod bounded;
+pub mod bucket_integration;
 pub mod bucket_msm;
+pub mod fixed_base;
+pub mod msm_g2;
 pub mod msm_slice;
+pub mod progress;
+pub mod sparse;
+pub mod streaming;
+pub mod table_file;
+pub mod tuner;
+#[cfg(feature = "msm-verify")]
+pub mod verify_mode;
+pub mod wnaf;
@@ -0,0 +1,28 @@
+use kzg::msm::msm_g2::{msm_g2, BgmwTableG2};
+use kzg::{Fr, G2Mul, G2};
+
+pub fn msm_g2_matches_naive<TFr: Fr, TG2: G2 + G2Mul<TFr>>() {
+    let npoints = 100;
+    let points = (0..npoints)
+        .map(|_| TG2::generator().mul(&TFr::rand()))
+        .collect::<Vec<_>>();
+    let mut scalars = (0..npoints).map(|_| TFr::rand()).collect::<Vec<_>>();
+    // Edge cases of the digit decomposition
+    scalars[0] = TFr::zero();
+    scalars[1] = TFr::one();
+    scalars[2] = TFr::zero().sub(&TFr::one());
+    let table = BgmwTableG2::<TFr, TG2>::new(&points).unwrap().unwrap();
+
+    for len in [0, 1, 7, 8, 9, 33, npoints] {
+        let mut expected = TG2::default();
+        for (point, scalar) in points.iter().zip(&scalars).take(len) {
+            expected = expected.add_or_dbl(&point.mul(scalar));
+        }
+
+        let actual = msm_g2(&points, &scalars, len, None);
+        assert!(actual.equals(&expected));
+
+        let actual = msm_g2(&points, &scalars, len, Some(&table));
+        assert!(actual.equals(&expected));
+    }
+}
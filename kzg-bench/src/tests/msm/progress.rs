@@ -0,0 +1,46 @@
+use kzg::msm::msm_impls::msm;
+use kzg::msm::precompute::precompute_with_progress;
+use kzg::{Fr, G1Affine, G1Fp, G1GetFp, G1Mul, G1ProjAddAffine, G1};
+
+pub fn precompute_progress_test<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+    TProjAddAffine: G1ProjAddAffine<TG1, TG1Fp, TG1Affine>,
+>() {
+    // Enough points for the rows to be split between threads with the `parallel` feature
+    let npoints = 300;
+    let points = (0..npoints).map(|_| TG1::rand()).collect::<Vec<_>>();
+    let scalars = (0..npoints).map(|_| TFr::rand()).collect::<Vec<_>>();
+
+    let mut calls = Vec::new();
+    let table =
+        precompute_with_progress::<TFr, TG1, TG1Fp, TG1Affine>(&points, &mut |done, total| {
+            calls.push((done, total))
+        })
+        .unwrap();
+    let Some(table) = table else {
+        assert!(calls.is_empty());
+        return;
+    };
+
+    // One call per row, in order, ending with the whole table
+    let total = calls[0].1;
+    assert!(total > 1);
+    assert_eq!(
+        calls,
+        (1..=total).map(|done| (done, total)).collect::<Vec<_>>()
+    );
+
+    let expected =
+        msm::<TG1, TG1Fp, TG1Affine, TProjAddAffine, TFr>(&points, &scalars, npoints, None);
+    let actual =
+        msm::<TG1, TG1Fp, TG1Affine, TProjAddAffine, TFr>(&points, &scalars, npoints, Some(&table));
+    assert!(actual.equals(&expected));
+
+    // Empty tables report no rows
+    let mut calls = 0;
+    let _ = precompute_with_progress::<TFr, TG1, TG1Fp, TG1Affine>(&[], &mut |_, _| calls += 1);
+    assert_eq!(calls, 0);
+}
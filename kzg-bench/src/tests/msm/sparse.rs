@@ -0,0 +1,67 @@
+use kzg::msm::msm_impls::msm;
+use kzg::msm::precompute::precompute;
+use kzg::{Fr, G1Affine, G1Fp, G1GetFp, G1Mul, G1ProjAddAffine, G1};
+
+pub fn bgmw_sparse_scalars_match_msm<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+    TProjAddAffine: G1ProjAddAffine<TG1, TG1Fp, TG1Affine>,
+>() {
+    let npoints = 150;
+    let points = (0..npoints).map(|_| TG1::rand()).collect::<Vec<_>>();
+    let Some(table) = precompute::<TFr, TG1, TG1Fp, TG1Affine>(&points).unwrap() else {
+        return;
+    };
+
+    let scalar_sets: [Vec<TFr>; 6] = [
+        // All zero
+        vec![TFr::zero(); npoints],
+        // Mostly zero
+        (0..npoints)
+            .map(|i| {
+                if i % 10 == 3 {
+                    TFr::rand()
+                } else {
+                    TFr::zero()
+                }
+            })
+            .collect(),
+        // Small values only, including the largest ones without a carry out of the lowest window
+        (0..npoints as u64).map(|i| TFr::from_u64(i % 5)).collect(),
+        (0..npoints as u64)
+            .map(|i| TFr::from_u64((1 << (i % 20)) - 1))
+            .collect(),
+        // Small, zero and full-width values mixed
+        (0..npoints)
+            .map(|i| match i % 3 {
+                0 => TFr::zero(),
+                1 => TFr::from_u64(i as u64),
+                _ => TFr::rand(),
+            })
+            .collect(),
+        // A few zeros, below the sparse threshold
+        (0..npoints)
+            .map(|i| {
+                if i % 10 == 3 {
+                    TFr::zero()
+                } else {
+                    TFr::rand()
+                }
+            })
+            .collect(),
+    ];
+
+    for scalars in scalar_sets.iter() {
+        let expected =
+            msm::<TG1, TG1Fp, TG1Affine, TProjAddAffine, TFr>(&points, scalars, npoints, None);
+        let actual = msm::<TG1, TG1Fp, TG1Affine, TProjAddAffine, TFr>(
+            &points,
+            scalars,
+            npoints,
+            Some(&table),
+        );
+        assert!(actual.equals(&expected));
+    }
+}
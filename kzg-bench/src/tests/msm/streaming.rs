@@ -0,0 +1,55 @@
+use kzg::msm::msm_impls::{msm, msm_streaming};
+use kzg::msm::precompute::precompute;
+use kzg::{Fr, G1Affine, G1Fp, G1GetFp, G1Mul, G1ProjAddAffine, G1};
+
+pub fn msm_streaming_matches_msm<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+    TProjAddAffine: G1ProjAddAffine<TG1, TG1Fp, TG1Affine>,
+>() {
+    // Large enough for a window of 5 bits, which divides 255
+    let npoints = 150;
+    let points = (0..npoints).map(|_| TG1::rand()).collect::<Vec<_>>();
+    let scalars = (0..npoints).map(|_| TFr::rand()).collect::<Vec<_>>();
+    let table = precompute::<TFr, TG1, TG1Fp, TG1Affine>(&points).unwrap();
+
+    for len in [0, 1, 2, 3, 33, npoints] {
+        let expected =
+            msm::<TG1, TG1Fp, TG1Affine, TProjAddAffine, TFr>(&points, &scalars, len, None);
+
+        for chunk_size in [1, 2, 7, 64, 1024] {
+            let actual = msm_streaming::<TG1, TG1Fp, TG1Affine, TFr>(
+                &points,
+                scalars[..len].iter().cloned(),
+                chunk_size,
+                None,
+            )
+            .unwrap();
+            assert!(actual.equals(&expected));
+
+            if let Some(table) = &table {
+                let actual = msm_streaming::<TG1, TG1Fp, TG1Affine, TFr>(
+                    &points,
+                    scalars[..len].iter().cloned(),
+                    chunk_size,
+                    Some(table),
+                )
+                .unwrap();
+                assert!(actual.equals(&expected));
+            }
+        }
+    }
+
+    // Scalars beyond the points are rejected
+    let too_many = scalars.iter().cloned().chain([TFr::one()]);
+    assert!(msm_streaming::<TG1, TG1Fp, TG1Affine, TFr>(&points, too_many, 16, None).is_err());
+    if let Some(table) = &table {
+        let too_many = scalars.iter().cloned().chain([TFr::one()]);
+        assert!(
+            msm_streaming::<TG1, TG1Fp, TG1Affine, TFr>(&points, too_many, 16, Some(table))
+                .is_err()
+        );
+    }
+}
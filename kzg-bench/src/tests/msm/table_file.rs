@@ -0,0 +1,231 @@
+use std::cell::Cell;
+use std::mem::discriminant;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use kzg::error::KzgError;
+use kzg::msm::msm_impls::msm;
+use kzg::msm::precompute::{precompute, PrecomputationTable};
+use kzg::progress::{set_progress_sink, ProgressStage};
+use kzg::{Fr, G1Affine, G1Fp, G1GetFp, G1Mul, G1ProjAddAffine, G1};
+
+pub fn table_file_roundtrip<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+    TProjAddAffine: G1ProjAddAffine<TG1, TG1Fp, TG1Affine>,
+>() {
+    let npoints = 64;
+    let points = (0..npoints).map(|_| TG1::rand()).collect::<Vec<_>>();
+    let scalars = (0..npoints).map(|_| TFr::rand()).collect::<Vec<_>>();
+    let Some(table) = precompute::<TFr, TG1, TG1Fp, TG1Affine>(&points).unwrap() else {
+        return;
+    };
+    let expected =
+        msm::<TG1, TG1Fp, TG1Affine, TProjAddAffine, TFr>(&points, &scalars, npoints, None);
+    let check = |table: &PrecomputationTable<TFr, TG1, TG1Fp, TG1Affine>| {
+        let actual = msm::<TG1, TG1Fp, TG1Affine, TProjAddAffine, TFr>(
+            &points,
+            &scalars,
+            npoints,
+            Some(table),
+        );
+        assert!(actual.equals(&expected));
+    };
+
+    let mut bytes = Vec::new();
+    let writer: &mut dyn std::io::Write = &mut bytes;
+    table.write_to_writer(writer).unwrap();
+    check(&unsafe { PrecomputationTable::read_from_reader(&mut bytes.as_slice()) }.unwrap());
+    let reader: &mut dyn std::io::Read = &mut bytes.as_slice();
+    check(&unsafe { PrecomputationTable::read_from_reader(reader) }.unwrap());
+
+    let path = std::env::temp_dir().join(format!("kzg_table_file_{}.bin", std::process::id()));
+    let path = path.to_str().unwrap();
+    table.write_to_file(path).unwrap();
+    check(&unsafe { PrecomputationTable::read_from_file(path) }.unwrap());
+    #[cfg(feature = "mmap")]
+    {
+        let mapped = unsafe { PrecomputationTable::<TFr, TG1, TG1Fp, TG1Affine>::load_mmap(path) };
+        if PrecomputationTable::<TFr, TG1, TG1Fp, TG1Affine>::supports_mmap() {
+            let mapped = mapped.unwrap();
+            check(&mapped);
+            assert_eq!(mapped.memory_usage(), 0);
+        } else {
+            assert!(matches!(mapped, Err(KzgError::Unsupported(_))));
+        }
+    }
+
+    // Truncated files and inconsistent headers are rejected
+    let truncated = &bytes[..bytes.len() - 1];
+    std::fs::write(path, truncated).unwrap();
+    #[cfg(feature = "mmap")]
+    assert!(unsafe { PrecomputationTable::<TFr, TG1, TG1Fp, TG1Affine>::load_mmap(path) }.is_err());
+    assert!(
+        unsafe { PrecomputationTable::<TFr, TG1, TG1Fp, TG1Affine>::read_from_file(path) }.is_err()
+    );
+    std::fs::remove_file(path).unwrap();
+    assert!(unsafe {
+        PrecomputationTable::<TFr, TG1, TG1Fp, TG1Affine>::read_from_reader(&mut &truncated[..])
+    }
+    .is_err());
+    let read = |bytes: &[u8]| unsafe {
+        PrecomputationTable::<TFr, TG1, TG1Fp, TG1Affine>::read_from_reader(&mut &bytes[..])
+    };
+    // Magic bytes, version, curve, dimensions and point data
+    let format = KzgError::InvalidFormat(String::new());
+    let mismatch = KzgError::SetupMismatch(String::new());
+    for (offset, expected, kind) in [
+        (0, "Not a precomputation table file", &format),
+        (8, "Unsupported table file version", &mismatch),
+        (9, "Unknown curve", &mismatch),
+        (56, "Inconsistent table dimensions", &format),
+        (bytes.len() - 1, "Table file is corrupted", &mismatch),
+    ] {
+        let mut corrupted = bytes.clone();
+        corrupted[offset] ^= 1;
+        let err = read(&corrupted).err().unwrap();
+        assert_eq!(discriminant(&err), discriminant(kind), "{}", err);
+        assert!(err.to_string().starts_with(expected), "{}", err);
+    }
+
+    // Windows out of the range of `new_with_window`, which would size the buckets of the MSM
+    for window in [0u64, 2, 60] {
+        let mut corrupted = bytes.clone();
+        corrupted[24..32].copy_from_slice(&window.to_le_bytes());
+        let err = read(&corrupted).err().unwrap();
+        assert!(matches!(err, KzgError::InvalidFormat(_)), "{}", err);
+        assert!(
+            err.to_string().starts_with("Table window must be between"),
+            "{}",
+            err
+        );
+    }
+}
+
+/// Table over fixed points, the identity among them, with a window that does not depend on the
+/// machine, whose MSM and file digest are the same for every backend
+pub fn table_file_known_answer<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+    TProjAddAffine: G1ProjAddAffine<TG1, TG1Fp, TG1Affine>,
+>() {
+    // At most 32 points, so that parallel builds do not split the table by thread count
+    const NPOINTS: usize = 32;
+    const WINDOW: usize = 8;
+    const MSM: &str = concat!(
+        "a8d337776bd8ab3aa7f946a83f2b1094df647689399fd316",
+        "e75c27cf1c938a54d8c6f0e5b1090e5a3a2b90d55885010c"
+    );
+    const POINTS_DIGEST: &str = "efb73bec207e36766b1b30eaabdb956d0285ba7d40b17d79a7e79e2b7ba227f8";
+
+    let points = (0..NPOINTS as u64)
+        .map(|i| TG1::generator().mul(&TFr::from_u64(i)))
+        .collect::<Vec<_>>();
+    let mut scalars = (1..=NPOINTS as u64)
+        .map(|i| TFr::from_u64(i.wrapping_mul(0x9E37_79B9_7F4A_7C15)))
+        .collect::<Vec<_>>();
+    scalars[1] = TFr::one().negate();
+    let Some(table) =
+        PrecomputationTable::<TFr, TG1, TG1Fp, TG1Affine>::new_with_window(&points, WINDOW)
+            .unwrap()
+    else {
+        return;
+    };
+
+    let mut expected = TG1::identity();
+    for (point, scalar) in points.iter().zip(&scalars) {
+        expected = expected.add_or_dbl(&point.mul(scalar));
+    }
+    let actual =
+        msm::<TG1, TG1Fp, TG1Affine, TProjAddAffine, TFr>(&points, &scalars, NPOINTS, Some(&table));
+    assert!(actual.equals(&expected));
+    assert_eq!(hex::encode(actual.to_bytes()), MSM);
+
+    // The header holds the digest of the points after the magic bytes, version and fields
+    let mut bytes = Vec::new();
+    table.write_to_writer(&mut bytes).unwrap();
+    assert_eq!(hex::encode(&bytes[80..112]), POINTS_DIGEST);
+    let read = unsafe { PrecomputationTable::read_from_reader(&mut bytes.as_slice()) }.unwrap();
+    let actual =
+        msm::<TG1, TG1Fp, TG1Affine, TProjAddAffine, TFr>(&points, &scalars, NPOINTS, Some(&read));
+    assert!(actual.equals(&expected));
+}
+
+thread_local! {
+    /// Rows reported by `generate_to_file` on this thread, and the row to interrupt it after
+    static ROWS: Cell<usize> = Cell::new(0);
+    static INTERRUPT_AFTER: Cell<Option<usize>> = Cell::new(None);
+}
+
+fn count_rows(stage: ProgressStage, _: f64) {
+    if stage == ProgressStage::PrecomputationTable {
+        let rows = ROWS.with(|rows| rows.replace(rows.get() + 1) + 1);
+        if INTERRUPT_AFTER.with(Cell::get) == Some(rows) {
+            panic!("Table generation interrupted");
+        }
+    }
+}
+
+pub fn table_generate_to_file<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>() {
+    let points = (0..64).map(|_| TG1::rand()).collect::<Vec<_>>();
+    let Some(table) = precompute::<TFr, TG1, TG1Fp, TG1Affine>(&points).unwrap() else {
+        return;
+    };
+    let mut expected = Vec::new();
+    table.write_to_writer(&mut expected).unwrap();
+
+    let path = std::env::temp_dir().join(format!("kzg_table_stream_{}.bin", std::process::id()));
+    let path = path.to_str().unwrap();
+    let manifest_path = format!("{}.manifest", path);
+    let generate = || unsafe {
+        ROWS.with(|rows| rows.set(0));
+        let result =
+            PrecomputationTable::<TFr, TG1, TG1Fp, TG1Affine>::generate_to_file(&points, path);
+        (result, ROWS.with(Cell::get))
+    };
+    set_progress_sink(Some(&count_rows));
+
+    // Same file as a table built in memory, without a manifest left behind
+    let (result, rows) = generate();
+    result.unwrap();
+    assert_eq!(std::fs::read(path).unwrap(), expected);
+    assert!(std::fs::metadata(&manifest_path).is_err());
+
+    // An interrupted run leaves a file that does not load, and the next run resumes it
+    INTERRUPT_AFTER.with(|row| row.set(Some(3)));
+    assert!(catch_unwind(AssertUnwindSafe(generate)).is_err());
+    INTERRUPT_AFTER.with(|row| row.set(None));
+    assert!(std::fs::metadata(&manifest_path).is_ok());
+    assert!(
+        unsafe { PrecomputationTable::<TFr, TG1, TG1Fp, TG1Affine>::read_from_file(path) }.is_err()
+    );
+    let (result, resumed_rows) = generate();
+    result.unwrap();
+    assert_eq!(resumed_rows + 3, rows);
+    assert_eq!(std::fs::read(path).unwrap(), expected);
+
+    // A manifest of other points is ignored
+    let other = (0..64).map(|_| TG1::rand()).collect::<Vec<_>>();
+    ROWS.with(|rows| rows.set(0));
+    INTERRUPT_AFTER.with(|row| row.set(Some(2)));
+    assert!(catch_unwind(AssertUnwindSafe(|| unsafe {
+        PrecomputationTable::<TFr, TG1, TG1Fp, TG1Affine>::generate_to_file(&other, path)
+    }))
+    .is_err());
+    INTERRUPT_AFTER.with(|row| row.set(None));
+    let (result, fresh_rows) = generate();
+    result.unwrap();
+    assert_eq!(fresh_rows, rows);
+    assert_eq!(std::fs::read(path).unwrap(), expected);
+
+    set_progress_sink(None);
+    std::fs::remove_file(path).unwrap();
+}
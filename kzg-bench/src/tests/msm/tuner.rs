@@ -0,0 +1,136 @@
+use kzg::msm::msm_impls::msm;
+use kzg::msm::precompute::PrecomputationTable;
+use kzg::msm::tuner::{MsmTuner, TuningProfile, TUNING_PROFILE_HEADER};
+use kzg::{Fr, G1Affine, G1Fp, G1GetFp, G1Mul, G1ProjAddAffine, G1};
+
+pub fn table_with_window<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+    TProjAddAffine: G1ProjAddAffine<TG1, TG1Fp, TG1Affine>,
+>() {
+    let npoints = 64;
+    let points = (0..npoints).map(|_| TG1::rand()).collect::<Vec<_>>();
+    let scalars = (0..npoints).map(|_| TFr::rand()).collect::<Vec<_>>();
+    let expected =
+        msm::<TG1, TG1Fp, TG1Affine, TProjAddAffine, TFr>(&points, &scalars, npoints, None);
+
+    for window in [3, 4, 7, 10] {
+        let Some(table) =
+            PrecomputationTable::<TFr, TG1, TG1Fp, TG1Affine>::new_with_window(&points, window)
+                .unwrap()
+        else {
+            return;
+        };
+        let actual = msm::<TG1, TG1Fp, TG1Affine, TProjAddAffine, TFr>(
+            &points,
+            &scalars,
+            npoints,
+            Some(&table),
+        );
+        assert!(actual.equals(&expected));
+    }
+
+    for window in [0, 1, 2, 21] {
+        assert!(
+            PrecomputationTable::<TFr, TG1, TG1Fp, TG1Affine>::new_with_window(&points, window)
+                .is_err()
+        );
+    }
+}
+
+pub fn tune_window<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>() {
+    assert!(MsmTuner::new(vec![], 1).is_err());
+    assert!(MsmTuner::new(vec![4], 0).is_err());
+
+    let points = (0..64).map(|_| TG1::rand()).collect::<Vec<_>>();
+    let tuner = MsmTuner::new(vec![4, 6], 1).unwrap();
+    if PrecomputationTable::<TFr, TG1, TG1Fp, TG1Affine>::new_with_window(&points, 4)
+        .unwrap()
+        .is_none()
+    {
+        assert!(tuner.tune::<TFr, TG1, TG1Fp, TG1Affine>(&points).is_err());
+        return;
+    }
+
+    let mut profile = TuningProfile::new();
+    let window = tuner
+        .tune_into::<TFr, TG1, TG1Fp, TG1Affine>(&points, &mut profile)
+        .unwrap();
+    assert!(tuner.candidates().contains(&window));
+    assert_eq!(profile.window_for(points.len()), Some(window));
+    assert_eq!(profile.window_for(points.len() + 1), None);
+
+    let table = profile
+        .precompute::<TFr, TG1, TG1Fp, TG1Affine>(&points)
+        .unwrap()
+        .unwrap();
+    assert!(table.window_width() > 0);
+
+    // The sweep reports every candidate
+    let report = tuner.sweep::<TFr, TG1, TG1Fp, TG1Affine>(&points).unwrap();
+    assert_eq!(report.npoints, points.len());
+    let windows = report.timings.iter().map(|t| t.window).collect::<Vec<_>>();
+    assert_eq!(windows, tuner.candidates());
+    assert!(report.timings.iter().all(|t| t.table_bytes > 0));
+    let recommended = report.recommended().unwrap();
+    assert!(tuner.candidates().contains(&recommended));
+    let text = report.to_text();
+    assert_eq!(text.lines().count(), 2 + tuner.candidates().len());
+    assert_eq!(text.matches("recommended").count(), 1);
+    let mut profile = TuningProfile::new();
+    assert_eq!(report.record_into(&mut profile), Some(recommended));
+    assert_eq!(profile.window_for(points.len()), Some(recommended));
+
+    // Out of range candidates are reported
+    let tuner = MsmTuner::new(vec![4, 40], 1).unwrap();
+    assert!(tuner.tune::<TFr, TG1, TG1Fp, TG1Affine>(&points).is_err());
+    assert!(tuner
+        .tune::<TFr, TG1, TG1Fp, TG1Affine>(&points[..1])
+        .is_err());
+}
+
+pub fn tuning_profile_roundtrip() {
+    let mut profile = TuningProfile::new();
+    assert!(profile.is_empty());
+    profile.insert(4096, 13);
+    profile.insert(64, 5);
+    profile.insert(4096, 12);
+    assert_eq!(profile.len(), 2);
+    assert_eq!(profile.window_for(4096), Some(12));
+
+    let text = profile.to_text();
+    assert!(text.starts_with(TUNING_PROFILE_HEADER));
+    assert_eq!(TuningProfile::from_text(&text).unwrap(), profile);
+
+    // Comments and blank lines are skipped
+    let commented = format!(
+        "{}\n\n# tuned on ci\n64 5\n 4096   12 \n",
+        TUNING_PROFILE_HEADER
+    );
+    assert_eq!(TuningProfile::from_text(&commented).unwrap(), profile);
+
+    let path = std::env::temp_dir().join(format!("kzg_tuning_profile_{}.txt", std::process::id()));
+    let path = path.to_str().unwrap();
+    profile.save_to_file(path).unwrap();
+    assert_eq!(TuningProfile::load_from_file(path).unwrap(), profile);
+    std::fs::remove_file(path).unwrap();
+    assert!(TuningProfile::load_from_file(path).is_err());
+
+    for malformed in [
+        String::new(),
+        String::from("64 5\n"),
+        format!("{}\n64\n", TUNING_PROFILE_HEADER),
+        format!("{}\n64 5 1\n", TUNING_PROFILE_HEADER),
+        format!("{}\n64 five\n", TUNING_PROFILE_HEADER),
+        format!("{}\n-64 5\n", TUNING_PROFILE_HEADER),
+    ] {
+        assert!(TuningProfile::from_text(&malformed).is_err());
+    }
+}
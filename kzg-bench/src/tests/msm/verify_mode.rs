@@ -0,0 +1,41 @@
+use kzg::msm::msm_impls::msm;
+use kzg::msm::precompute::precompute;
+use kzg::msm::verify_mode::{msm_verify_mode, set_msm_verify_mode, MsmVerifyMode};
+use kzg::{Fr, G1Affine, G1Fp, G1GetFp, G1Mul, G1ProjAddAffine, G1};
+
+pub fn msm_verify_mode_test<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+    TProjAddAffine: G1ProjAddAffine<TG1, TG1Fp, TG1Affine>,
+>() {
+    let npoints = 64;
+    let points = (0..npoints).map(|_| TG1::rand()).collect::<Vec<_>>();
+    let scalars = (0..npoints).map(|_| TFr::rand()).collect::<Vec<_>>();
+    let Some(table) = precompute::<TFr, TG1, TG1Fp, TG1Affine>(&points).unwrap() else {
+        return;
+    };
+    let expected =
+        msm::<TG1, TG1Fp, TG1Affine, TProjAddAffine, TFr>(&points, &scalars, npoints, None);
+
+    assert_eq!(msm_verify_mode(), MsmVerifyMode::Off);
+    for mode in [
+        MsmVerifyMode::Subsample { terms: 4 },
+        MsmVerifyMode::Subsample { terms: npoints + 1 },
+        MsmVerifyMode::Full,
+    ] {
+        set_msm_verify_mode(mode);
+        assert_eq!(msm_verify_mode(), mode);
+
+        // A correct table passes the check, and the result is unchanged
+        let actual = msm::<TG1, TG1Fp, TG1Affine, TProjAddAffine, TFr>(
+            &points,
+            &scalars,
+            npoints,
+            Some(&table),
+        );
+        assert!(actual.equals(&expected));
+    }
+    set_msm_verify_mode(MsmVerifyMode::Off);
+}
@@ -0,0 +1,100 @@
+use kzg::msm::msm_impls::{msm, msm_with_config, MsmConfig, Recoding};
+use kzg::msm::wnaf::{wnaf_digits, WNAF_MAX_WINDOW, WNAF_MIN_WINDOW};
+use kzg::{Fr, G1Affine, G1Fp, G1GetFp, G1Mul, G1ProjAddAffine, G1};
+
+pub fn wnaf_digits_recompose<TFr: Fr>() {
+    let scalars = [
+        TFr::zero(),
+        TFr::one(),
+        TFr::from_u64(u64::MAX),
+        TFr::one().negate(),
+        TFr::rand(),
+        TFr::rand(),
+    ];
+
+    for window in WNAF_MIN_WINDOW..=WNAF_MAX_WINDOW {
+        for scalar in scalars.iter() {
+            let digits = wnaf_digits(&scalar.to_scalar(), window);
+            assert!(digits.len() <= 257);
+
+            let mut recomposed = TFr::zero();
+            for &digit in digits.iter().rev() {
+                recomposed = recomposed.add(&recomposed);
+                let magnitude = TFr::from_u64(digit.unsigned_abs());
+                recomposed = if digit < 0 {
+                    recomposed.sub(&magnitude)
+                } else {
+                    recomposed.add(&magnitude)
+                };
+            }
+            assert!(recomposed.equals(scalar));
+
+            // Odd digits below 2^(w-1), with at least w - 1 zeros after each of them
+            let mut last_nonzero: Option<usize> = None;
+            for (i, &digit) in digits.iter().enumerate() {
+                if digit == 0 {
+                    continue;
+                }
+                assert_eq!(digit.rem_euclid(2), 1);
+                assert!(digit.unsigned_abs() < 1 << (window - 1));
+                if let Some(last) = last_nonzero {
+                    assert!(i - last >= window);
+                }
+                last_nonzero = Some(i);
+            }
+        }
+    }
+}
+
+pub fn wnaf_msm_matches_msm<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+    TProjAddAffine: G1ProjAddAffine<TG1, TG1Fp, TG1Affine>,
+>() {
+    let mut points = (0..100).map(|_| TG1::rand()).collect::<Vec<_>>();
+    points[7] = TG1::identity();
+    let scalars = (0..100u64)
+        .map(|i| match i % 5 {
+            0 => TFr::zero(),
+            1 => TFr::from_u64(i),
+            2 => TFr::one().negate(),
+            _ => TFr::rand(),
+        })
+        .collect::<Vec<_>>();
+
+    for len in [0, 1, 2, 9, 100] {
+        // Batch affine conversion does not support the point at infinity, `msm` is not a reference
+        // for these points
+        let expected = points[..len]
+            .iter()
+            .zip(&scalars)
+            .fold(TG1::identity(), |acc, (point, scalar)| {
+                acc.add_or_dbl(&point.mul(scalar))
+            });
+        let booth = msm_with_config::<TG1, TG1Fp, TG1Affine, TProjAddAffine, TFr>(
+            &points,
+            &scalars,
+            len,
+            None,
+            &MsmConfig::default(),
+        );
+        assert!(
+            booth.equals(&msm::<TG1, TG1Fp, TG1Affine, TProjAddAffine, TFr>(
+                &points, &scalars, len, None
+            ))
+        );
+
+        // Out of range windows are clamped
+        for window in [0, WNAF_MIN_WINDOW, 5, WNAF_MAX_WINDOW, 64] {
+            let config = MsmConfig {
+                recoding: Recoding::Wnaf { window },
+            };
+            let wnaf = msm_with_config::<TG1, TG1Fp, TG1Affine, TProjAddAffine, TFr>(
+                &points, &scalars, len, None, &config,
+            );
+            assert!(wnaf.equals(&expected));
+        }
+    }
+}
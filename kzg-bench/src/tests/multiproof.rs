@@ -0,0 +1,82 @@
+use kzg::multiproof::{
+    compute_kzg_multiproof, interpolate, vanishing_polynomial, verify_kzg_multiproof,
+};
+use kzg::{
+    FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1Mul, G2Mul, KZGSettings, PairingVerify, Poly, G1,
+    G2,
+};
+
+use crate::tests::kzg_proofs::SECRET;
+
+pub fn kzg_multiproof_test<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + PairingVerify<TG1, TG2>,
+    TG2: G2 + G2Mul<TFr>,
+    TPoly: Poly<TFr>,
+    TFFTSettings: FFTSettings<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    generate_trusted_setup: &dyn Fn(usize, [u8; 32usize]) -> (Vec<TG1>, Vec<TG2>),
+) {
+    let (s1, s2) = generate_trusted_setup(32, SECRET);
+    let fs = TFFTSettings::new(5).unwrap();
+    let ks = TKZGSettings::new(&s1, &s2, 32, &fs).unwrap();
+
+    let mut p = TPoly::new(16);
+    for i in 0..16 {
+        p.set_coeff_at(i, &TFr::from_u64((i * i * 7 + 3) as u64));
+    }
+    let commitment = ks.commit_to_poly(&p).unwrap();
+    let points = [3u64, 17, 1 << 40, 5, 99]
+        .iter()
+        .map(|&z| TFr::from_u64(z))
+        .collect::<Vec<_>>();
+    let verify = |points: &[TFr], values: &[TFr], proof: &TG1| {
+        verify_kzg_multiproof(&commitment, points, values, proof, &ks).unwrap()
+    };
+
+    let (proof, values) = compute_kzg_multiproof(&p, &points, &ks).unwrap();
+    for (z, y) in points.iter().zip(&values) {
+        assert!(y.equals(&p.eval(z)));
+    }
+    assert!(verify(&points, &values, &proof));
+
+    // Wrong value, points out of order, a point left out
+    let mut wrong = values.clone();
+    wrong[2] = wrong[2].add(&TFr::one());
+    assert!(!verify(&points, &wrong, &proof));
+    let mut swapped = points.clone();
+    swapped.swap(0, 1);
+    assert!(!verify(&swapped, &values, &proof));
+    assert!(!verify(&points[1..], &values[1..], &proof));
+
+    // At a single point, the proof is the single-point one
+    let (single, _) = compute_kzg_multiproof(&p, &points[..1], &ks).unwrap();
+    assert!(single.equals(&ks.compute_proof_single(&p, &points[0]).unwrap()));
+
+    // As many points as coefficients, the quotient is zero
+    let many = (0..20u64)
+        .map(|z| TFr::from_u64(z + 1000))
+        .collect::<Vec<_>>();
+    let (proof, values) = compute_kzg_multiproof(&p, &many, &ks).unwrap();
+    assert!(proof.is_inf());
+    assert!(verify(&many, &values, &proof));
+
+    // The interpolant goes through every point, and the vanishing polynomial is zero at them
+    let interpolant = TPoly::from_coeffs(&interpolate(&many, &values).unwrap());
+    let vanishing = TPoly::from_coeffs(&vanishing_polynomial(&many));
+    for (z, y) in many.iter().zip(&values) {
+        assert!(interpolant.eval(z).equals(y));
+        assert!(vanishing.eval(z).is_zero());
+    }
+
+    let duplicated = [points[0].clone(), points[1].clone(), points[0].clone()];
+    assert!(compute_kzg_multiproof(&p, &duplicated, &ks).is_err());
+    assert!(compute_kzg_multiproof(&p, &[], &ks).is_err());
+    assert!(verify_kzg_multiproof(&commitment, &points, &values[1..], &proof, &ks).is_err());
+    let too_many = (0..32u64).map(TFr::from_u64).collect::<Vec<_>>();
+    let values = too_many.iter().map(|z| p.eval(z)).collect::<Vec<_>>();
+    assert!(verify_kzg_multiproof(&commitment, &too_many, &values, &proof, &ks).is_err());
+}
@@ -0,0 +1,81 @@
+use kzg::multiset::{grand_product_constraints, grand_product_evaluations, MultisetEquality};
+use kzg::{FFTFr, FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1Mul, KZGSettings, Poly, G1, G2};
+
+use crate::tests::kzg_proofs::SECRET;
+
+pub fn multiset_equality_test<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG2: G2,
+    TPoly: Poly<TFr>,
+    TFFTSettings: FFTSettings<TFr> + FFTFr<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    generate_trusted_setup: &dyn Fn(usize, [u8; 32usize]) -> (Vec<TG1>, Vec<TG2>),
+) {
+    let secrets_len = 17;
+    let (s1, s2) = generate_trusted_setup(secrets_len, SECRET);
+    let fs = TFFTSettings::new(4).unwrap();
+    let ks = TKZGSettings::new(&s1, &s2, secrets_len, &fs).unwrap();
+
+    let n = 8;
+    let f = [3, 1, 4, 1, 5, 9, 2, 6].map(TFr::from_u64);
+    let g = [1, 1, 2, 3, 4, 5, 6, 9].map(TFr::from_u64);
+
+    let argument: MultisetEquality<TFr, TG1, TPoly> = MultisetEquality::new(&f, &g, &ks).unwrap();
+    assert!(argument
+        .f_commitment
+        .equals(&ks.commit_to_poly(&argument.f_poly).unwrap()));
+    assert!(argument
+        .z_commitment
+        .equals(&ks.commit_to_poly(&argument.z_poly).unwrap()));
+
+    // The columns are interpolated in natural order, and Z starts at 1
+    let omega = fs.get_expanded_roots_of_unity_at(fs.get_max_width() / n);
+    let z = grand_product_evaluations(&f, &g, &argument.gamma).unwrap();
+    assert!(z[0].is_one());
+
+    // Both constraints vanish on the whole domain
+    let mut x = TFr::one();
+    for i in 0..n {
+        assert!(argument.f_poly.eval(&x).equals(&f[i]));
+        assert!(argument.z_poly.eval(&x).equals(&z[i]));
+
+        let (transition, boundary) = grand_product_constraints(
+            &x,
+            n,
+            &argument.f_poly.eval(&x),
+            &argument.g_poly.eval(&x),
+            &argument.z_poly.eval(&x),
+            &argument.z_poly.eval(&x.mul(&omega)),
+            &argument.gamma,
+        )
+        .unwrap();
+        assert!(transition.is_zero());
+        assert!(boundary.is_zero());
+
+        x = x.mul(&omega);
+    }
+
+    // But not outside of it
+    let x = TFr::from_u64(1234);
+    let (transition, _) = grand_product_constraints(
+        &x,
+        n,
+        &argument.f_poly.eval(&x),
+        &argument.g_poly.eval(&x),
+        &argument.z_poly.eval(&x),
+        &argument.z_poly.eval(&x.mul(&omega)),
+        &argument.gamma,
+    )
+    .unwrap();
+    assert!(!transition.is_zero());
+
+    // Different multisets, even with the same sum, are rejected
+    let h = [1, 1, 2, 3, 4, 5, 7, 8].map(TFr::from_u64);
+    assert!(MultisetEquality::<TFr, TG1, TPoly>::new(&f, &h, &ks).is_err());
+    assert!(MultisetEquality::<TFr, TG1, TPoly>::new(&f[..6], &g[..6], &ks).is_err());
+    assert!(MultisetEquality::<TFr, TG1, TPoly>::new(&f, &g[..4], &ks).is_err());
+}
@@ -0,0 +1,95 @@
+use kzg::namespace::{
+    verify_namespace_proof, Namespace, NamespaceRangeProof, NamespacedRows, NAMESPACE_SIZE,
+};
+use kzg::{
+    FFTFr, FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1Mul, KZGSettings, PairingVerify, Poly, G1,
+    G2,
+};
+
+use crate::tests::kzg_proofs::SECRET;
+
+fn namespace(id: u8) -> Namespace {
+    let mut ns = [0u8; NAMESPACE_SIZE];
+    ns[NAMESPACE_SIZE - 1] = id;
+    ns
+}
+
+pub fn namespace_range_proof_test<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + PairingVerify<TG1, TG2>,
+    TG2: G2,
+    TPoly: Poly<TFr>,
+    TFFTSettings: FFTSettings<TFr> + FFTFr<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    generate_trusted_setup: &dyn Fn(usize, [u8; 32usize]) -> (Vec<TG1>, Vec<TG2>),
+) {
+    let row_width = 16;
+    let (s1, s2) = generate_trusted_setup(row_width + 1, SECRET);
+    let fs = TFFTSettings::new(4).unwrap();
+    let ks = TKZGSettings::new(&s1, &s2, row_width + 1, &fs).unwrap();
+
+    // Namespace 3 spans both rows, namespace 5 is absent, namespace 9 is at the end of the data
+    let ids = [
+        1, 1, 2, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 4, 6, 9,
+    ];
+    let items = ids
+        .iter()
+        .enumerate()
+        .map(|(i, &id)| (namespace(id), TFr::from_u64(100 + i as u64)))
+        .collect::<Vec<_>>();
+
+    let rows: NamespacedRows<TFr, TG1, TPoly> =
+        NamespacedRows::new(&items, row_width, &ks).unwrap();
+    let commitments = rows.commitments();
+    assert_eq!(commitments.len(), 2);
+
+    let verify = |id: u8, proof: &NamespaceRangeProof<TFr, TG1>| {
+        verify_namespace_proof(
+            &commitments[proof.row],
+            row_width,
+            &namespace(id),
+            proof,
+            &ks,
+        )
+        .unwrap()
+    };
+
+    let proofs = rows.prove_namespace(&namespace(3), &ks).unwrap();
+    assert_eq!(proofs.len(), 2);
+    let mut found = Vec::new();
+    for proof in proofs.iter() {
+        assert!(verify(3, proof));
+        found.extend_from_slice(proof.namespace_values());
+    }
+    let expected = (3..18).map(|i| TFr::from_u64(100 + i)).collect::<Vec<_>>();
+    assert_eq!(found.len(), expected.len());
+    assert!(found.iter().zip(expected.iter()).all(|(a, b)| a.equals(b)));
+
+    // Absence proofs
+    let absent = rows.prove_namespace_in_row(1, &namespace(5), &ks).unwrap();
+    assert!(absent.namespace_values().is_empty());
+    assert!(verify(5, &absent));
+    let before = rows.prove_namespace_in_row(1, &namespace(0), &ks).unwrap();
+    assert!(verify(0, &before));
+
+    // The last namespace is delimited by padding
+    let last = rows.prove_namespace(&namespace(9), &ks).unwrap();
+    assert_eq!(last.len(), 1);
+    assert!(verify(9, &last[0]));
+
+    // Tampered values, shortened ranges and proofs against the wrong row are rejected
+    let mut tampered = proofs[0].clone();
+    tampered.values[tampered.start - tampered.block_offset] = TFr::from_u64(7);
+    assert!(!verify(3, &tampered));
+
+    let mut shortened = proofs[0].clone();
+    shortened.start += 1;
+    assert!(!verify(3, &shortened));
+
+    let mut wrong_row = proofs[0].clone();
+    wrong_row.row = 1;
+    assert!(!verify(3, &wrong_row));
+}
@@ -0,0 +1,157 @@
+use std::future::Future;
+use std::pin::pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+
+use kzg::eip_4844::{blob_to_kzg_commitment_rust, bytes_to_blob, compute_blob_kzg_proof_rust};
+use kzg::nonblocking::{
+    blob_to_kzg_commitment_async, compute_blob_kzg_proof_async, load_trusted_setup_async,
+    verify_blob_kzg_proof_async, verify_blob_kzg_proof_batch_async, BlockingPool,
+};
+use kzg::{
+    FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1LinComb, G1Mul, KZGSettings, PairingVerify, Poly,
+    G1, G2,
+};
+
+use crate::tests::eip_4844::generate_random_blob_bytes;
+use crate::tests::utils::get_trusted_setup_path;
+
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Minimal executor, polling a single future on the current thread
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+pub fn blocking_pool_test() {
+    assert!(BlockingPool::new(0).is_err());
+
+    let pool = BlockingPool::new(2).unwrap();
+    assert_eq!(pool.max_concurrency(), 2);
+
+    let jobs = (0..8u64)
+        .map(|i| pool.spawn(move || Ok(i * i)))
+        .collect::<Vec<_>>();
+    let results = jobs.into_iter().map(block_on).collect::<Vec<_>>();
+    assert_eq!(
+        results,
+        (0..8u64)
+            .map(|i| Ok(i * i))
+            .collect::<Vec<Result<u64, String>>>()
+    );
+
+    assert!(block_on(pool.spawn(|| Err::<(), _>(String::from("failed")))).is_err());
+
+    // A panicking job resolves to an error instead of hanging the caller
+    let result = block_on(pool.spawn::<(), _>(|| panic!("job panic")));
+    assert!(result.is_err());
+    assert_eq!(block_on(pool.spawn(|| Ok(1))), Ok(1));
+}
+
+pub fn nonblocking_eip_4844_test<
+    TFr: Fr + Copy + Send + 'static,
+    TG1: G1
+        + G1Mul<TFr>
+        + G1GetFp<TG1Fp>
+        + G1LinComb<TFr, TG1Fp, TG1Affine>
+        + PairingVerify<TG1, TG2>
+        + Send
+        + 'static,
+    TG2: G2,
+    TPoly: Poly<TFr>,
+    TFFTSettings: FFTSettings<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine> + Send + Sync + 'static,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    load_trusted_setup: fn(&str) -> Result<TKZGSettings, String>,
+) {
+    let pool = BlockingPool::new(2).unwrap();
+    let ts = Arc::new(
+        block_on(load_trusted_setup_async(
+            &pool,
+            load_trusted_setup,
+            get_trusted_setup_path(),
+        ))
+        .unwrap(),
+    );
+    assert!(block_on(load_trusted_setup_async(
+        &pool,
+        load_trusted_setup,
+        String::from("missing_trusted_setup.txt")
+    ))
+    .is_err());
+
+    let mut rng = rand::thread_rng();
+    let blobs = (0..3)
+        .map(|_| bytes_to_blob::<TFr>(&generate_random_blob_bytes(&mut rng)).unwrap())
+        .collect::<Vec<_>>();
+
+    let commitments = blobs
+        .iter()
+        .map(|blob| blob_to_kzg_commitment_async(&pool, blob.clone(), ts.clone()))
+        .collect::<Vec<_>>()
+        .into_iter()
+        .map(|job| block_on(job).unwrap())
+        .collect::<Vec<_>>();
+    for (blob, commitment) in blobs.iter().zip(commitments.iter()) {
+        assert!(commitment.equals(&blob_to_kzg_commitment_rust(blob, ts.as_ref()).unwrap()));
+    }
+
+    let proofs = blobs
+        .iter()
+        .zip(commitments.iter())
+        .map(|(blob, commitment)| {
+            block_on(compute_blob_kzg_proof_async(
+                &pool,
+                blob.clone(),
+                commitment.clone(),
+                ts.clone(),
+            ))
+            .unwrap()
+        })
+        .collect::<Vec<_>>();
+    assert!(proofs[0]
+        .equals(&compute_blob_kzg_proof_rust(&blobs[0], &commitments[0], ts.as_ref()).unwrap()));
+
+    assert!(block_on(verify_blob_kzg_proof_async(
+        &pool,
+        blobs[1].clone(),
+        commitments[1].clone(),
+        proofs[1].clone(),
+        ts.clone(),
+    ))
+    .unwrap());
+    assert!(!block_on(verify_blob_kzg_proof_async(
+        &pool,
+        blobs[1].clone(),
+        commitments[1].clone(),
+        proofs[2].clone(),
+        ts.clone(),
+    ))
+    .unwrap());
+    assert!(block_on(verify_blob_kzg_proof_batch_async(
+        &pool,
+        blobs,
+        commitments,
+        proofs,
+        ts,
+    ))
+    .unwrap());
+}
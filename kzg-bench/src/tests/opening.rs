@@ -0,0 +1,77 @@
+use kzg::opening::{
+    commit_shifted, commit_to_quotient, divide_by_linear, verify_quotient_commitment,
+};
+use kzg::{
+    FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1LinComb, G1Mul, G2Mul, KZGSettings, PairingVerify,
+    Poly, G1, G2,
+};
+
+use crate::tests::kzg_proofs::SECRET;
+
+fn test_poly<TFr: Fr, TPoly: Poly<TFr>>() -> TPoly {
+    let coeffs = [1, 2, 3, 4, 7, 7, 7, 7, 13, 13, 13, 13, 13, 13, 13, 13];
+    let mut p = TPoly::new(coeffs.len());
+    for (x, &coeff) in coeffs.iter().enumerate() {
+        p.set_coeff_at(x, &TFr::from_u64(coeff));
+    }
+    p
+}
+
+pub fn divide_by_linear_test<TFr: Fr, TPoly: Poly<TFr>>() {
+    let p: TPoly = test_poly();
+    let z = TFr::from_u64(25);
+
+    let (q, y) = divide_by_linear(&p, &z).unwrap();
+    assert_eq!(q.len(), p.len() - 1);
+    assert!(y.equals(&p.eval(&z)));
+
+    // p(x) - y == q(x) * (x - z) at an arbitrary point
+    let x = TFr::from_u64(1234);
+    let lhs = p.eval(&x).sub(&y);
+    let rhs = q.eval(&x).mul(&x.sub(&z));
+    assert!(lhs.equals(&rhs));
+
+    assert!(divide_by_linear(&TPoly::new(0), &z).is_err());
+}
+
+pub fn quotient_commitment_test<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + PairingVerify<TG1, TG2> + G1LinComb<TFr, TG1Fp, TG1Affine>,
+    TG2: G2 + G2Mul<TFr>,
+    TPoly: Poly<TFr>,
+    TFFTSettings: FFTSettings<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    generate_trusted_setup: &dyn Fn(usize, [u8; 32usize]) -> (Vec<TG1>, Vec<TG2>),
+) {
+    let p: TPoly = test_poly();
+    let secrets_len = p.len() + 1;
+
+    let (s1, s2) = generate_trusted_setup(secrets_len, SECRET);
+    let fs = TFFTSettings::new(4).unwrap();
+    let ks = TKZGSettings::new(&s1, &s2, secrets_len, &fs).unwrap();
+
+    let z = TFr::from_u64(25);
+    let commitment = ks.commit_to_poly(&p).unwrap();
+    let (quotient, y) = commit_to_quotient(&p, &z, &ks).unwrap();
+
+    // The quotient commitment is exactly the single-point opening proof
+    assert!(quotient.equals(&ks.compute_proof_single(&p, &z).unwrap()));
+    assert!(verify_quotient_commitment(&commitment, &quotient, &z, &y, &ks).unwrap());
+    assert!(
+        !verify_quotient_commitment(&commitment, &quotient, &z, &y.add(&TFr::one()), &ks).unwrap()
+    );
+
+    // Shifting by zero is a plain commitment, shifting by one commits to x * p(x)
+    assert!(commit_shifted(&p, 0, &ks).unwrap().equals(&commitment));
+    let mut shifted = TPoly::new(p.len() + 1);
+    for i in 0..p.len() {
+        shifted.set_coeff_at(i + 1, &p.get_coeff_at(i));
+    }
+    assert!(commit_shifted(&p, 1, &ks)
+        .unwrap()
+        .equals(&ks.commit_to_poly(&shifted).unwrap()));
+    assert!(commit_shifted(&p, 2, &ks).is_err());
+}
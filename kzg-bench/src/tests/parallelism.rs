@@ -0,0 +1,67 @@
+use kzg::io_utils::batch_writer;
+use kzg::msm::msm_impls::msm;
+use kzg::msm::precompute::precompute;
+use kzg::parallelism::{is_parallel, max_threads, parallelism, set_parallelism, Parallelism};
+use kzg::{
+    Compression, FFTFr, FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1Mul, G1ProjAddAffine, FFTG1, G1,
+};
+
+pub fn parallelism_modes_test<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + Sync,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+    TProjAddAffine: G1ProjAddAffine<TG1, TG1Fp, TG1Affine>,
+    TFFTSettings: FFTSettings<TFr> + FFTFr<TFr> + FFTG1<TG1>,
+>() {
+    let fs = TFFTSettings::new(10).unwrap();
+    let fr_data = (0..1024).map(|_| TFr::rand()).collect::<Vec<_>>();
+    let g1_data = (0..64).map(|_| TG1::rand()).collect::<Vec<_>>();
+    let npoints = 300;
+    let points = (0..npoints).map(|_| TG1::rand()).collect::<Vec<_>>();
+    let scalars = (0..npoints).map(|_| TFr::rand()).collect::<Vec<_>>();
+    let table = precompute::<TFr, TG1, TG1Fp, TG1Affine>(&points).unwrap();
+
+    let run = || {
+        let fr_fft = fs.fft_fr(&fr_data, false).unwrap();
+        let g1_fft = fs.fft_g1(&g1_data, false).unwrap();
+        let msms = [None, table.as_ref()].map(|table| {
+            msm::<TG1, TG1Fp, TG1Affine, TProjAddAffine, TFr>(&points, &scalars, npoints, table)
+        });
+        let bytes = TG1::batch_to_bytes(&points, Compression::Compressed).unwrap();
+        let decoded = TG1::batch_from_bytes(&bytes, Compression::Compressed).unwrap();
+        let mut written = Vec::new();
+        batch_writer(&mut written, &points, 7, |chunk: &[TG1]| {
+            Ok(TG1::batch_to_bytes(chunk, Compression::Compressed)?)
+        })
+        .unwrap();
+        assert_eq!(written, bytes);
+
+        (fr_fft, g1_fft, msms, decoded)
+    };
+
+    assert_eq!(parallelism(), Parallelism::Auto);
+    let expected = run();
+
+    for mode in [
+        Parallelism::Off,
+        Parallelism::Threads(0),
+        Parallelism::Threads(1),
+        Parallelism::Threads(2),
+    ] {
+        set_parallelism(mode);
+        assert_eq!(parallelism(), mode);
+        if mode != Parallelism::Threads(2) {
+            assert_eq!(max_threads(), 1);
+            assert!(!is_parallel());
+        }
+
+        // Every mode computes the same results
+        let (fr_fft, g1_fft, msms, decoded) = run();
+        assert!(fr_fft.iter().zip(&expected.0).all(|(a, b)| a.equals(b)));
+        assert!(g1_fft.iter().zip(&expected.1).all(|(a, b)| a.equals(b)));
+        assert!(msms.iter().zip(&expected.2).all(|(a, b)| a.equals(b)));
+        assert!(decoded.iter().zip(&expected.3).all(|(a, b)| a.equals(b)));
+    }
+    set_parallelism(Parallelism::Auto);
+}
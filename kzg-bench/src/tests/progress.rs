@@ -0,0 +1,87 @@
+use std::sync::Mutex;
+
+use kzg::msm::msm_impls::msm;
+use kzg::msm::precompute::{precompute, PrecomputationTable};
+use kzg::progress::{set_progress_sink, ProgressStage, MSM_PROGRESS_MIN_POINTS};
+use kzg::{Fr, G1Affine, G1Fp, G1GetFp, G1Mul, G1ProjAddAffine, G1};
+
+use crate::tests::utils::get_trusted_setup_path;
+
+static REPORTS: Mutex<Vec<(ProgressStage, f64)>> = Mutex::new(Vec::new());
+
+fn record_progress(stage: ProgressStage, fraction: f64) {
+    REPORTS.lock().unwrap().push((stage, fraction));
+}
+
+/// Remove the reports of `stage`, checking that they go up to 1 in at most one report per percent
+fn take_reports(stage: ProgressStage) -> Vec<f64> {
+    let mut reports = REPORTS.lock().unwrap();
+    let fractions = reports
+        .iter()
+        .filter(|&&(s, _)| s == stage)
+        .map(|&(_, fraction)| fraction)
+        .collect::<Vec<_>>();
+    reports.retain(|&(s, _)| s != stage);
+    drop(reports);
+
+    assert!(!fractions.is_empty(), "no report for {:?}", stage);
+    assert!(fractions.len() <= 101);
+    assert!(fractions.windows(2).all(|w| w[0] <= w[1]));
+    assert_eq!(*fractions.last().unwrap(), 1.0);
+    fractions
+}
+
+pub fn progress_sink_test<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+    TProjAddAffine: G1ProjAddAffine<TG1, TG1Fp, TG1Affine>,
+    TKZGSettings,
+>(
+    load_trusted_setup: &dyn Fn(&str) -> Result<TKZGSettings, String>,
+) {
+    set_progress_sink(Some(&record_progress));
+
+    load_trusted_setup(get_trusted_setup_path().as_str()).unwrap();
+    take_reports(ProgressStage::TrustedSetup);
+    REPORTS.lock().unwrap().clear();
+
+    let points = (0..300u64)
+        .map(|i| TG1::generator().mul(&TFr::from_u64(i + 1)))
+        .collect::<Vec<_>>();
+    if let Some(table) = precompute::<TFr, TG1, TG1Fp, TG1Affine>(&points).unwrap() {
+        take_reports(ProgressStage::PrecomputationTable);
+
+        let mut bytes = Vec::new();
+        table.write_to_writer(&mut bytes).unwrap();
+        let read = unsafe {
+            PrecomputationTable::<TFr, TG1, TG1Fp, TG1Affine>::read_from_reader(
+                &mut bytes.as_slice(),
+            )
+        };
+        assert!(read.is_ok());
+        take_reports(ProgressStage::TableFileRead);
+        take_reports(ProgressStage::TableFileDigest);
+    }
+
+    // Reported per window, from the smallest MSM that reports
+    let generator = TG1::generator();
+    let mut points = Vec::with_capacity(MSM_PROGRESS_MIN_POINTS);
+    points.push(generator.clone());
+    for i in 1..MSM_PROGRESS_MIN_POINTS {
+        points.push(points[i - 1].add_or_dbl(&generator));
+    }
+    let scalars = (0..MSM_PROGRESS_MIN_POINTS as u64)
+        .map(|i| TFr::from_u64(7 * i + 3))
+        .collect::<Vec<_>>();
+    let len = points.len();
+    msm::<TG1, TG1Fp, TG1Affine, TProjAddAffine, TFr>(&points, &scalars, len, None);
+    assert!(take_reports(ProgressStage::Msm).len() > 1);
+
+    // Smaller MSMs do not report, and nothing is reported once the sink is removed
+    msm::<TG1, TG1Fp, TG1Affine, TProjAddAffine, TFr>(&points[1..], &scalars[1..], len - 1, None);
+    set_progress_sink(None);
+    let _ = precompute::<TFr, TG1, TG1Fp, TG1Affine>(&points[..300]);
+    assert!(REPORTS.lock().unwrap().is_empty());
+}
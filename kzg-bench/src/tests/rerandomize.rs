@@ -0,0 +1,74 @@
+use kzg::rerandomize::{
+    check_srs_consistency, rerandomize_srs, translate_proof, verify_srs_update,
+};
+use kzg::{
+    FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1Mul, G2Mul, KZGSettings, PairingVerify, Poly, G1,
+    G2,
+};
+
+use crate::tests::kzg_proofs::SECRET;
+
+pub fn rerandomize_srs_test<
+    TFr: Fr + Copy,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + PairingVerify<TG1, TG2>,
+    TG2: G2 + G2Mul<TFr>,
+    TPoly: Poly<TFr>,
+    TFFTSettings: FFTSettings<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    generate_trusted_setup: &dyn Fn(usize, [u8; 32usize]) -> (Vec<TG1>, Vec<TG2>),
+) {
+    let secrets_len = 17;
+    let (s1, s2) = generate_trusted_setup(secrets_len, SECRET);
+    assert!(check_srs_consistency(&s1, &s2).unwrap());
+
+    let alpha = TFr::from_u64(0x1234_5678);
+    let (t1, t2) = rerandomize_srs(&s1, &s2, &alpha).unwrap();
+    assert!(check_srs_consistency(&t1, &t2).unwrap());
+
+    // The new setup is the one of secret α * s
+    let s = TFr::from_bytes_unchecked(&SECRET).unwrap();
+    let (expected_g1, expected_g2) = generate_trusted_setup(secrets_len, s.mul(&alpha).to_bytes());
+    assert!(t1.iter().zip(&expected_g1).all(|(a, b)| a.equals(b)));
+    assert!(t2.iter().zip(&expected_g2).all(|(a, b)| a.equals(b)));
+
+    let alpha_g2 = TG2::generator().mul(&alpha);
+    assert!(verify_srs_update(&s1, &t1, &alpha_g2).unwrap());
+    assert!(!verify_srs_update(&s1, &t1, &TG2::generator().mul(&alpha.add(&TFr::one()))).unwrap());
+
+    // A tampered setup is rejected
+    let mut tampered = t1.clone();
+    tampered[5] = tampered[6].clone();
+    assert!(!check_srs_consistency(&tampered, &t2).unwrap());
+    let mut tampered = t2.clone();
+    tampered[3] = tampered[2].clone();
+    assert!(!check_srs_consistency(&t1, &tampered).unwrap());
+
+    // Existing openings carry over to the new setup
+    let fs = TFFTSettings::new(4).unwrap();
+    let ks = TKZGSettings::new(&s1, &s2, secrets_len, &fs).unwrap();
+    let new_ks = TKZGSettings::new(&t1, &t2, secrets_len, &fs).unwrap();
+
+    let mut p = TPoly::new(16);
+    for i in 0..16 {
+        p.set_coeff_at(i, &TFr::from_u64((i * i + 3) as u64));
+    }
+    let commitment = ks.commit_to_poly(&p).unwrap();
+    let z = TFr::from_u64(42);
+    let y = p.eval(&z);
+    let proof = ks.compute_proof_single(&p, &z).unwrap();
+
+    let (new_proof, new_z) = translate_proof(&proof, &z, &alpha).unwrap();
+    assert!(new_ks
+        .check_proof_single(&commitment, &new_proof, &new_z, &y)
+        .unwrap());
+    assert!(!new_ks
+        .check_proof_single(&commitment, &proof, &z, &y)
+        .unwrap());
+
+    assert!(rerandomize_srs(&s1, &s2, &TFr::zero()).is_err());
+    assert!(translate_proof(&proof, &z, &TFr::zero()).is_err());
+    assert!(check_srs_consistency(&s1[..1], &s2).is_err());
+}
@@ -0,0 +1,86 @@
+use kzg::sampled_opening::SampledPoints;
+use kzg::{
+    FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1Mul, G2Mul, KZGSettings, PairingVerify, Poly, G1,
+    G2,
+};
+
+use crate::tests::kzg_proofs::SECRET;
+
+fn test_polys<TFr: Fr, TPoly: Poly<TFr>>(count: usize, len: usize) -> Vec<TPoly> {
+    (0..count)
+        .map(|i| {
+            let mut p = TPoly::new(len);
+            for x in 0..len {
+                p.set_coeff_at(x, &TFr::from_u64((i * 31 + x * x + 7) as u64));
+            }
+            p
+        })
+        .collect()
+}
+
+pub fn sampled_opening_test<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + PairingVerify<TG1, TG2>,
+    TG2: G2 + G2Mul<TFr>,
+    TPoly: Poly<TFr>,
+    TFFTSettings: FFTSettings<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    generate_trusted_setup: &dyn Fn(usize, [u8; 32usize]) -> (Vec<TG1>, Vec<TG2>),
+) {
+    let secrets_len = 17;
+    let (s1, s2) = generate_trusted_setup(secrets_len, SECRET);
+    let fs = TFFTSettings::new(4).unwrap();
+    let ks = TKZGSettings::new(&s1, &s2, secrets_len, &fs).unwrap();
+
+    let polys: Vec<TPoly> = test_polys(3, 16);
+    let commitments = polys
+        .iter()
+        .map(|p| ks.commit_to_poly(p).unwrap())
+        .collect::<Vec<_>>();
+    let points = [5, 11, 1234, 98765].map(TFr::from_u64);
+
+    let sampled = SampledPoints::new(&points, &ks).unwrap();
+    let openings = sampled.prove(&polys, &ks).unwrap();
+    assert_eq!(openings.len(), polys.len());
+
+    for ((p, commitment), opening) in polys.iter().zip(&commitments).zip(&openings) {
+        for (z, value) in points.iter().zip(&opening.values) {
+            assert!(value.equals(&p.eval(z)));
+        }
+        assert!(sampled.verify(commitment, opening).unwrap());
+    }
+    assert!(sampled.verify_batch(&commitments, &openings).unwrap());
+    assert!(sampled.verify_batch(&[], &[]).unwrap());
+
+    // A wrong value fails both the single and the batched check
+    let mut tampered = openings.clone();
+    tampered[1].values[2] = tampered[1].values[2].add(&TFr::one());
+    assert!(!sampled.verify(&commitments[1], &tampered[1]).unwrap());
+    assert!(!sampled.verify_batch(&commitments, &tampered).unwrap());
+
+    // So does an opening checked against another commitment
+    assert!(!sampled.verify(&commitments[0], &openings[1]).unwrap());
+
+    // A single point gives the usual single-point proof
+    let single = SampledPoints::new(&points[..1], &ks).unwrap();
+    let opening = single.prove(&polys[..1], &ks).unwrap();
+    assert!(opening[0]
+        .proof
+        .equals(&ks.compute_proof_single(&polys[0], &points[0]).unwrap()));
+
+    // Malformed inputs
+    assert!(SampledPoints::<TFr, TG1, TG2>::new(&[], &ks).is_err());
+    assert!(
+        SampledPoints::<TFr, TG1, TG2>::new(&[points[0].clone(), points[0].clone()], &ks).is_err()
+    );
+    let too_many = (0..secrets_len as u64)
+        .map(TFr::from_u64)
+        .collect::<Vec<_>>();
+    assert!(SampledPoints::<TFr, TG1, TG2>::new(&too_many, &ks).is_err());
+    assert!(sampled.verify_batch(&commitments[..2], &openings).is_err());
+    tampered[0].values.pop();
+    assert!(sampled.verify(&commitments[0], &tampered[0]).is_err());
+}
@@ -0,0 +1,39 @@
+use kzg::eip_4844::hash_to_bls_field;
+use kzg::secret::{generate_trusted_setup_with_secret, wipe_scalars, SecretScalar};
+use kzg::{Fr, G1Mul, G2Mul, G1, G2};
+
+use crate::tests::kzg_proofs::SECRET;
+
+pub fn secret_scalar_setup_test<TFr: Fr + Copy, TG1: G1 + G1Mul<TFr>, TG2: G2 + G2Mul<TFr>>(
+    generate_trusted_setup: &dyn Fn(usize, [u8; 32usize]) -> (Vec<TG1>, Vec<TG2>),
+) {
+    let (expected_g1, expected_g2) = generate_trusted_setup(16, SECRET);
+
+    // Same setup whether the secret is derived from the seed or wrapped by the caller
+    let secret = SecretScalar::<TFr>::from_seed(SECRET);
+    let (g1, g2) = generate_trusted_setup_with_secret::<TFr, TG1, TG2>(16, &secret);
+    assert!(g1.iter().zip(&expected_g1).all(|(a, b)| a.equals(b)));
+    assert!(g2.iter().zip(&expected_g2).all(|(a, b)| a.equals(b)));
+
+    let s = hash_to_bls_field::<TFr>(&SECRET);
+    let wrapped = SecretScalar::new(s);
+    assert!(wrapped.expose().equals(&s));
+    let (g1, _) = generate_trusted_setup_with_secret::<TFr, TG1, TG2>(2, &wrapped);
+    assert!(g1[0].equals(&TG1::generator()));
+    assert!(g1[1].equals(&TG1::generator().mul(&s)));
+
+    let mut squared = SecretScalar::new(s);
+    squared.mul_assign(&s);
+    assert!(squared.expose().equals(&s.mul(&s)));
+
+    // Debug output never shows the secret
+    assert_eq!(format!("{:?}", wrapped), "SecretScalar(..)");
+
+    // Wiped scalars are zero with the `zeroize` feature and untouched without it
+    let mut scalars = vec![s, TFr::from_u64(7)];
+    wipe_scalars(&mut scalars);
+    assert!(
+        scalars.iter().all(Fr::is_zero)
+            || (scalars[0].equals(&s) && scalars[1].equals(&TFr::from_u64(7)))
+    );
+}
@@ -0,0 +1,45 @@
+use kzg::self_test::known_answer_test;
+use kzg::{
+    FFTFr, FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1LinComb, G1Mul, KZGSettings, Poly,
+    PolyRecover, G1, G2,
+};
+
+use crate::tests::kzg_proofs::SECRET;
+use crate::tests::utils::get_trusted_setup_path;
+
+pub fn self_test_test<
+    TFr: Fr + Copy,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + G1LinComb<TFr, TG1Fp, TG1Affine>,
+    TG2: G2,
+    TPoly: Poly<TFr> + PolyRecover<TFr, TPoly, TFFTSettings>,
+    TFFTSettings: FFTSettings<TFr> + FFTFr<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    generate_trusted_setup: &dyn Fn(usize, [u8; 32usize]) -> (Vec<TG1>, Vec<TG2>),
+    load_trusted_setup: &dyn Fn(&str) -> Result<TKZGSettings, String>,
+) {
+    known_answer_test::<TFr, TG1>().unwrap();
+
+    // EIP-4844 setup in Lagrange form, with the golden vectors
+    let ts = load_trusted_setup(get_trusted_setup_path().as_str()).unwrap();
+    ts.self_test().unwrap();
+
+    // Setup in monomial form
+    let (s1, s2) = generate_trusted_setup(32, SECRET);
+    let fs = TFFTSettings::new(5).unwrap();
+    let ks = TKZGSettings::new(&s1, &s2, 32, &fs).unwrap();
+    ks.self_test().unwrap();
+
+    // A corrupted point breaks the round trips
+    let mut corrupted = s1.clone();
+    corrupted[3] = corrupted[4].clone();
+    let ks = TKZGSettings::new(&corrupted, &s2, 32, &fs).unwrap();
+    assert!(ks.self_test().is_err());
+
+    // Lagrange setups other than the EIP-4844 one are not supported
+    let lagrange = s1[1..17].to_vec();
+    let ks = TKZGSettings::new(&lagrange, &s2, 16, &TFFTSettings::new(4).unwrap()).unwrap();
+    assert!(ks.self_test().is_err());
+}
@@ -0,0 +1,240 @@
+use std::fs;
+
+use kzg::setup_convert::{convert_setup, read_setup, write_setup, SetupFormat};
+use kzg::setup_file::read_trusted_setup;
+use kzg::{Compression, FFTSettings, Fr, G1Mul, PairingVerify, FFTG1, G1, G2};
+
+use crate::tests::kzg_proofs::SECRET;
+use crate::tests::utils::get_trusted_setup_path;
+
+fn temp_path(name: &str) -> String {
+    std::env::temp_dir()
+        .join(format!("kzg_setup_convert_{}_{}", std::process::id(), name))
+        .into_os_string()
+        .into_string()
+        .unwrap()
+}
+
+pub fn convert_text_setup_test<
+    TFr: Fr,
+    TG1: G1 + PairingVerify<TG1, TG2>,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr> + FFTG1<TG1>,
+>() {
+    let convert = |input: &str, output: &str, from, to| {
+        convert_setup::<TFr, TG1, TG2, TFFTSettings>(input, output, from, to)
+    };
+    let text_path = get_trusted_setup_path();
+    let text = fs::read_to_string(&text_path).unwrap();
+    let (binary, uncompressed, json, back) = (
+        temp_path("setup.bin"),
+        temp_path("setup_uncompressed.bin"),
+        temp_path("setup.json"),
+        temp_path("setup.txt"),
+    );
+
+    // Text to the monomial binary format, and back
+    let binary_format = SetupFormat::Binary(Compression::Compressed);
+    convert(&text_path, &binary, SetupFormat::Text, binary_format).unwrap();
+    let (g1, g2): (Vec<TG1>, Vec<TG2>) =
+        read_trusted_setup(&mut fs::read(&binary).unwrap().as_slice()).unwrap();
+    assert_eq!((g1.len(), g2.len()), (4096, 65));
+    assert!(TG1::verify(&g1[1], &g2[0], &g1[0], &g2[1]));
+    convert(&binary, &back, binary_format, SetupFormat::Text).unwrap();
+    assert_eq!(fs::read_to_string(&back).unwrap(), text);
+
+    // Through the uncompressed binary format and the ceremony JSON
+    let uncompressed_format = SetupFormat::Binary(Compression::Uncompressed);
+    convert(&binary, &uncompressed, binary_format, uncompressed_format).unwrap();
+    assert_eq!(
+        fs::metadata(&uncompressed).unwrap().len(),
+        fs::metadata(&binary).unwrap().len() + 4096 * 48
+    );
+    convert(&uncompressed, &json, uncompressed_format, SetupFormat::Json).unwrap();
+    convert(&json, &back, SetupFormat::Json, SetupFormat::Text).unwrap();
+    assert_eq!(fs::read_to_string(&back).unwrap(), text);
+
+    let json_text = fs::read_to_string(&json).unwrap();
+    let (json_g1, json_g2) = kzg::eip_4844::load_trusted_setup_json_string(&json_text).unwrap();
+    let (text_g1, text_g2) = kzg::eip_4844::load_trusted_setup_string(&text).unwrap();
+    assert_eq!((json_g1, json_g2), (text_g1, text_g2));
+
+    // Lagrange form needs a power-of-two number of points, and .ptau files are read only
+    let mut out = Vec::new();
+    assert!(write_setup::<TFr, TG1, TG2, TFFTSettings, _>(
+        &mut out,
+        &g1[..3],
+        &g2,
+        SetupFormat::Text
+    )
+    .is_err());
+    assert!(convert(&binary, &back, binary_format, SetupFormat::Ptau).is_err());
+    assert!(SetupFormat::from_name("csv").is_err());
+    assert_eq!(
+        SetupFormat::from_name("binary-uncompressed"),
+        Ok(uncompressed_format)
+    );
+
+    for path in [binary, uncompressed, json, back] {
+        fs::remove_file(path).unwrap();
+    }
+}
+
+/// Limbs of the BLS12-381 base field modulus, least significant first
+const P: [u64; 6] = [
+    0xb9fe_ffff_ffff_aaab,
+    0x1eab_fffe_b153_ffff,
+    0x6730_d2a0_f6b0_f624,
+    0x6477_4b84_f385_12bf,
+    0x4b1b_a7b6_434b_acd7,
+    0x1a01_11ea_397f_e69a,
+];
+
+fn sub_limbs(a: &mut [u64; 6], b: &[u64; 6]) {
+    let mut borrow = 0u64;
+    for i in 0..6 {
+        let (diff, b1) = a[i].overflowing_sub(b[i]);
+        let (diff, b2) = diff.overflowing_sub(borrow);
+        a[i] = diff;
+        borrow = (b1 || b2) as u64;
+    }
+}
+
+/// Little-endian Montgomery form `a 2^384 mod p` of the big-endian `a`, computed by doubling
+fn to_montgomery(be: &[u8]) -> Vec<u8> {
+    let mut a = [0u64; 6];
+    for (limb, chunk) in a.iter_mut().zip(be.rchunks(8)) {
+        *limb = u64::from_be_bytes(chunk.try_into().unwrap());
+    }
+    for _ in 0..384 {
+        let carry = a[5] >> 63;
+        for i in (1..6).rev() {
+            a[i] = (a[i] << 1) | (a[i - 1] >> 63);
+        }
+        a[0] <<= 1;
+        if carry == 1 || a.iter().rev().ge(P.iter().rev()) {
+            sub_limbs(&mut a, &P);
+        }
+    }
+
+    a.iter().flat_map(|limb| limb.to_le_bytes()).collect()
+}
+
+fn hex_coordinate(hex: &str) -> Vec<u8> {
+    to_montgomery(&hex::decode(hex).unwrap())
+}
+
+pub fn convert_ptau_setup_test<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr>,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr> + FFTG1<TG1>,
+>(
+    generate_trusted_setup: &dyn Fn(usize, [u8; 32usize]) -> (Vec<TG1>, Vec<TG2>),
+) {
+    // Power 2: 7 tauG1 points, 4 tauG2 points
+    let (g1, _) = generate_trusted_setup(8, SECRET);
+    let mut tau_g1 = Vec::new();
+    for point in TG1::batch_to_bytes(&g1[..7], Compression::Uncompressed)
+        .unwrap()
+        .chunks(96)
+    {
+        tau_g1.extend(to_montgomery(&point[..48]));
+        tau_g1.extend(to_montgomery(&point[48..]));
+    }
+
+    // The generator and its negation, y of the latter being p - y
+    let x = [
+        "024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8",
+        "13e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e",
+    ];
+    let y = [
+        "0ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801",
+        "0606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be",
+    ];
+    let negate = |hex: &str| {
+        let mut p = P;
+        let mut y = [0u64; 6];
+        for (limb, chunk) in y.iter_mut().zip(hex::decode(hex).unwrap().rchunks(8)) {
+            *limb = u64::from_be_bytes(chunk.try_into().unwrap());
+        }
+        sub_limbs(&mut p, &y);
+        hex::encode(
+            p.iter()
+                .rev()
+                .flat_map(|limb| limb.to_be_bytes())
+                .collect::<Vec<_>>(),
+        )
+    };
+    let generator = [x[0], x[1], y[0], y[1]]
+        .iter()
+        .flat_map(|c| hex_coordinate(c))
+        .collect::<Vec<_>>();
+    let negative_generator = [
+        x[0].to_string(),
+        x[1].to_string(),
+        negate(y[0]),
+        negate(y[1]),
+    ]
+    .iter()
+    .flat_map(|c| hex_coordinate(c))
+    .collect::<Vec<_>>();
+    let tau_g2 = [generator, negative_generator].concat().repeat(2);
+
+    let mut header = 48u32.to_le_bytes().to_vec();
+    header.extend(P.iter().flat_map(|limb| limb.to_le_bytes()));
+    header.extend(2u32.to_le_bytes());
+    header.extend(2u32.to_le_bytes());
+
+    let mut ptau = b"ptau".to_vec();
+    ptau.extend(1u32.to_le_bytes());
+    ptau.extend(4u32.to_le_bytes());
+    for (id, section) in [(1u32, header), (2, tau_g1), (3, tau_g2), (4, vec![0u8; 7])] {
+        ptau.extend(id.to_le_bytes());
+        ptau.extend((section.len() as u64).to_le_bytes());
+        ptau.extend(section);
+    }
+
+    let (points_g1, points_g2): (Vec<TG1>, Vec<TG2>) =
+        read_setup::<TFr, TG1, TG2, TFFTSettings, _>(&mut ptau.as_slice(), SetupFormat::Ptau)
+            .unwrap();
+    assert_eq!((points_g1.len(), points_g2.len()), (4, 4));
+    assert!(points_g1.iter().zip(&g1).all(|(a, b)| a.equals(b)));
+    for (i, point) in points_g2.iter().enumerate() {
+        let expected = if i % 2 == 0 {
+            TG2::generator()
+        } else {
+            TG2::negative_generator()
+        };
+        assert!(point.equals(&expected));
+    }
+
+    // Converted to the binary format through the files
+    let (input, output) = (temp_path("setup.ptau"), temp_path("setup_ptau.bin"));
+    fs::write(&input, &ptau).unwrap();
+    convert_setup::<TFr, TG1, TG2, TFFTSettings>(
+        &input,
+        &output,
+        SetupFormat::Ptau,
+        SetupFormat::Binary(Compression::Compressed),
+    )
+    .unwrap();
+    let (binary_g1, binary_g2): (Vec<TG1>, Vec<TG2>) =
+        read_trusted_setup(&mut fs::read(&output).unwrap().as_slice()).unwrap();
+    assert!(binary_g1.iter().zip(&points_g1).all(|(a, b)| a.equals(b)));
+    assert!(binary_g2.iter().zip(&points_g2).all(|(a, b)| a.equals(b)));
+    fs::remove_file(input).unwrap();
+    fs::remove_file(output).unwrap();
+
+    // Wrong magic, other curve and truncated tauG1 section
+    let read = |bytes: &[u8]| {
+        read_setup::<TFr, TG1, TG2, TFFTSettings, _>(&mut &bytes[..], SetupFormat::Ptau)
+    };
+    let mut corrupted = ptau.clone();
+    corrupted[0] = b'q';
+    assert!(read(&corrupted).is_err());
+    let mut corrupted = ptau.clone();
+    corrupted[4 + 8 + 12 + 4] ^= 1;
+    assert!(read(&corrupted).is_err());
+    assert!(read(&ptau[..ptau.len() / 2]).is_err());
+}
@@ -0,0 +1,69 @@
+use std::io::ErrorKind;
+
+use kzg::error::KzgError;
+use kzg::setup_file::{
+    generate_trusted_setup_to_file, load_trusted_setup_from_file, read_trusted_setup,
+    write_trusted_setup, SETUP_FILE_HEADER_SIZE,
+};
+use kzg::{Fr, G1Mul, G2Mul, G1, G2};
+
+use crate::tests::kzg_proofs::SECRET;
+
+pub fn setup_file_roundtrip_test<TFr: Fr + Copy, TG1: G1 + G1Mul<TFr>, TG2: G2 + G2Mul<TFr>>(
+    generate_trusted_setup: &dyn Fn(usize, [u8; 32usize]) -> (Vec<TG1>, Vec<TG2>),
+) {
+    let (expected_g1, expected_g2) = generate_trusted_setup(16, SECRET);
+
+    let mut bytes = Vec::new();
+    write_trusted_setup::<TFr, TG1, TG2, _>(&mut bytes, 16, 5, SECRET).unwrap();
+    assert_eq!(bytes.len(), SETUP_FILE_HEADER_SIZE + 16 * 48 + 5 * 96);
+
+    let (g1, g2): (Vec<TG1>, Vec<TG2>) = read_trusted_setup(&mut bytes.as_slice()).unwrap();
+    assert_eq!(g1.len(), 16);
+    assert_eq!(g2.len(), 5);
+    assert!(g1.iter().zip(&expected_g1).all(|(a, b)| a.equals(b)));
+    assert!(g2.iter().zip(&expected_g2).all(|(a, b)| a.equals(b)));
+
+    let path = std::env::temp_dir().join(format!("kzg_setup_file_{}.bin", std::process::id()));
+    let path = path.to_str().unwrap();
+    generate_trusted_setup_to_file::<TFr, TG1, TG2>(path, 16, 5, SECRET).unwrap();
+    let (g1, g2): (Vec<TG1>, Vec<TG2>) = load_trusted_setup_from_file(path).unwrap();
+    std::fs::remove_file(path).unwrap();
+    assert!(g1.iter().zip(&expected_g1).all(|(a, b)| a.equals(b)));
+    assert!(g2.iter().zip(&expected_g2).all(|(a, b)| a.equals(b)));
+
+    // Truncated, unknown version and unknown curve, told apart without matching the messages
+    let truncated = &bytes[..bytes.len() - 1];
+    assert!(matches!(
+        read_trusted_setup::<TG1, TG2, _>(&mut &truncated[..]),
+        Err(KzgError::Io(e)) if e.kind() == ErrorKind::UnexpectedEof
+    ));
+    let mut unknown = bytes.clone();
+    unknown[0] = 2;
+    assert!(matches!(
+        read_trusted_setup::<TG1, TG2, _>(&mut unknown.as_slice()),
+        Err(KzgError::SetupMismatch(_))
+    ));
+    let mut unknown = bytes.clone();
+    unknown[1] = 0;
+    assert!(matches!(
+        read_trusted_setup::<TG1, TG2, _>(&mut unknown.as_slice()),
+        Err(KzgError::SetupMismatch(_))
+    ));
+    assert!(matches!(
+        load_trusted_setup_from_file::<TG1, TG2>("missing_setup_file.bin"),
+        Err(KzgError::Io(e)) if e.kind() == ErrorKind::NotFound
+    ));
+
+    // Undecodable points are reported with their index in the file
+    let mut corrupted = bytes.clone();
+    corrupted[SETUP_FILE_HEADER_SIZE + 3 * 48..SETUP_FILE_HEADER_SIZE + 4 * 48].fill(0xff);
+    assert!(matches!(
+        read_trusted_setup::<TG1, TG2, _>(&mut corrupted.as_slice()),
+        Err(KzgError::Deserialization {
+            what: "G1 point",
+            index: 3,
+            ..
+        })
+    ));
+}
@@ -0,0 +1,85 @@
+use kzg::sharding::{
+    commit_to_shards, prove_shard_consistency, shard_len, split_into_shards,
+    verify_shard_consistency,
+};
+use kzg::{
+    FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1Mul, G2Mul, KZGSettings, PairingVerify, Poly, G1,
+    G2,
+};
+
+use crate::tests::kzg_proofs::SECRET;
+
+pub fn shard_consistency_test<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + PairingVerify<TG1, TG2>,
+    TG2: G2 + G2Mul<TFr>,
+    TPoly: Poly<TFr>,
+    TFFTSettings: FFTSettings<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    generate_trusted_setup: &dyn Fn(usize, [u8; 32usize]) -> (Vec<TG1>, Vec<TG2>),
+) {
+    let secrets_len = 17;
+    let (s1, s2) = generate_trusted_setup(secrets_len, SECRET);
+    let fs = TFFTSettings::new(4).unwrap();
+    let ks = TKZGSettings::new(&s1, &s2, secrets_len, &fs).unwrap();
+
+    let mut p = TPoly::new(16);
+    for i in 0..16 {
+        p.set_coeff_at(i, &TFr::from_u64(1 + (i * i) as u64));
+    }
+    let commitment = ks.commit_to_poly(&p).unwrap();
+
+    assert!(shard_len(16, 0).is_err());
+    assert!(split_into_shards(&p, 0).is_err());
+
+    // Even and padded splits
+    for num_shards in [1, 3, 4, 16] {
+        let len = shard_len(p.len(), num_shards).unwrap();
+        let shards = split_into_shards(&p, num_shards).unwrap();
+        assert_eq!(shards.len(), num_shards);
+
+        // p(x) = Σ x^(jm) * p_j(x)
+        let x = TFr::from_u64(77);
+        let mut recombined = TFr::zero();
+        for (j, shard) in shards.iter().enumerate() {
+            assert_eq!(shard.len(), len);
+            recombined = recombined.add(&x.pow(j * len).mul(&shard.eval(&x)));
+        }
+        assert!(recombined.equals(&p.eval(&x)));
+
+        let shard_commitments = commit_to_shards(&shards, &ks).unwrap();
+        let proof = prove_shard_consistency(&p, &commitment, &shard_commitments, &ks).unwrap();
+        assert!(
+            verify_shard_consistency(&commitment, len, &shard_commitments, &proof, &ks).unwrap()
+        );
+        // The shard length is part of the statement
+        assert!(
+            !verify_shard_consistency(&commitment, len + 1, &shard_commitments, &proof, &ks)
+                .unwrap()
+        );
+    }
+
+    let shards = split_into_shards(&p, 4).unwrap();
+    let mut shard_commitments = commit_to_shards(&shards, &ks).unwrap();
+    let proof = prove_shard_consistency(&p, &commitment, &shard_commitments, &ks).unwrap();
+
+    // A wrong shard value breaks the agreement, a wrong proof the pairing check
+    let mut tampered = proof.clone();
+    tampered.shard_values[2] = tampered.shard_values[2].add(&TFr::one());
+    assert!(!verify_shard_consistency(&commitment, 4, &shard_commitments, &tampered, &ks).unwrap());
+    let mut tampered = proof.clone();
+    tampered.shard_proofs[1] = tampered.shard_proofs[0].clone();
+    assert!(!verify_shard_consistency(&commitment, 4, &shard_commitments, &tampered, &ks).unwrap());
+    let mut tampered = proof.clone();
+    tampered.shard_values.pop();
+    assert!(verify_shard_consistency(&commitment, 4, &shard_commitments, &tampered, &ks).is_err());
+    assert!(verify_shard_consistency(&commitment, 4, &[], &proof, &ks).is_err());
+
+    // Shards in the wrong order do not make up the polynomial
+    shard_commitments.swap(0, 1);
+    let proof = prove_shard_consistency(&p, &commitment, &shard_commitments, &ks).unwrap();
+    assert!(!verify_shard_consistency(&commitment, 4, &shard_commitments, &proof, &ks).unwrap());
+}
@@ -0,0 +1,86 @@
+use kzg::shifted_opening::{
+    prove_shifted_opening, shifted_point, verify_shifted_opening, verify_shifted_openings_batch,
+};
+use kzg::{
+    FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1Mul, KZGSettings, PairingVerify, Poly, G1, G2,
+};
+
+use crate::tests::kzg_proofs::SECRET;
+
+fn test_poly<TFr: Fr, TPoly: Poly<TFr>>(offset: u64) -> TPoly {
+    let mut p = TPoly::new(16);
+    for i in 0..16 {
+        p.set_coeff_at(i, &TFr::from_u64(offset + (i * i) as u64));
+    }
+    p
+}
+
+pub fn shifted_opening_test<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + PairingVerify<TG1, TG2>,
+    TG2: G2,
+    TPoly: Poly<TFr>,
+    TFFTSettings: FFTSettings<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    generate_trusted_setup: &dyn Fn(usize, [u8; 32usize]) -> (Vec<TG1>, Vec<TG2>),
+) {
+    let secrets_len = 17;
+    let (s1, s2) = generate_trusted_setup(secrets_len, SECRET);
+    let fs = TFFTSettings::new(4).unwrap();
+    let ks = TKZGSettings::new(&s1, &s2, secrets_len, &fs).unwrap();
+    let domain_size = 8;
+
+    // ω generates the domain: ω^8 == 1 and ω^4 != 1
+    let z = TFr::from_u64(1234);
+    let omega = shifted_point(&fs, &TFr::one(), domain_size).unwrap();
+    assert!(omega.pow(8).is_one());
+    assert!(!omega.pow(4).is_one());
+    assert!(shifted_point(&fs, &z, domain_size)
+        .unwrap()
+        .equals(&z.mul(&omega)));
+    assert!(shifted_point(&fs, &z, 1).unwrap().equals(&z));
+    assert!(shifted_point(&fs, &z, 6).is_err());
+    assert!(shifted_point(&fs, &z, 32).is_err());
+
+    let p: TPoly = test_poly(1);
+    let commitment = ks.commit_to_poly(&p).unwrap();
+    let opening = prove_shifted_opening(&p, &z, domain_size, &ks).unwrap();
+    assert!(opening.value.equals(&p.eval(&z)));
+    assert!(opening.shifted_value.equals(&p.eval(&z.mul(&omega))));
+    assert!(verify_shifted_opening(&commitment, &z, domain_size, &opening, &ks).unwrap());
+
+    // Either wrong value fails the aggregated check
+    let mut tampered = opening.clone();
+    tampered.shifted_value = tampered.shifted_value.add(&TFr::one());
+    assert!(!verify_shifted_opening(&commitment, &z, domain_size, &tampered, &ks).unwrap());
+    let mut tampered = opening.clone();
+    tampered.value = tampered.value.add(&TFr::one());
+    assert!(!verify_shifted_opening(&commitment, &z, domain_size, &tampered, &ks).unwrap());
+    // The shift is part of the statement
+    assert!(!verify_shifted_opening(&commitment, &z, domain_size / 2, &opening, &ks).unwrap());
+
+    let polys = (0..3).map(test_poly).collect::<Vec<TPoly>>();
+    let commitments = polys
+        .iter()
+        .map(|p| ks.commit_to_poly(p).unwrap())
+        .collect::<Vec<_>>();
+    let zs = (0..3).map(|i| TFr::from_u64(100 + i)).collect::<Vec<_>>();
+    let mut openings = polys
+        .iter()
+        .zip(&zs)
+        .map(|(p, z)| prove_shifted_opening(p, z, domain_size, &ks).unwrap())
+        .collect::<Vec<_>>();
+    assert!(verify_shifted_openings_batch(&commitments, &zs, domain_size, &openings, &ks).unwrap());
+    assert!(verify_shifted_openings_batch(&[], &[], domain_size, &[], &ks).unwrap());
+    assert!(
+        verify_shifted_openings_batch(&commitments[..2], &zs, domain_size, &openings, &ks).is_err()
+    );
+
+    openings[2].shifted_proof = openings[1].shifted_proof.clone();
+    assert!(
+        !verify_shifted_openings_batch(&commitments, &zs, domain_size, &openings, &ks).unwrap()
+    );
+}
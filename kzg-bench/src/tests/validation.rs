@@ -0,0 +1,127 @@
+use kzg::error::KzgError;
+use kzg::io_utils::{load_secrets_from_reader_with_validation, save_secrets_to_writer};
+use kzg::msm::precompute::precompute;
+use kzg::setup_file::{read_trusted_setup_with_validation, SETUP_FILE_HEADER_SIZE};
+use kzg::validation::{
+    g1_affine_on_curve, validate_g1, validate_g1_affines, validate_g2, ValidationLevel,
+};
+use kzg::{Fr, G1Affine, G1Fp, G1GetFp, G1Mul, G2Mul, G1, G2};
+
+use crate::tests::kzg_proofs::SECRET;
+
+/// A point on the curve decoded from a small compressed `x`, outside of the subgroup with
+/// overwhelming probability. `None` if the backend checks the subgroup while decoding
+pub(crate) fn off_subgroup_encoding<T>(
+    size: usize,
+    decode: impl Fn(&[u8]) -> Result<T, String>,
+) -> Option<Vec<u8>> {
+    (1u8..=255).find_map(|x| {
+        let mut bytes = vec![0u8; size];
+        bytes[0] = 0x80;
+        bytes[size - 1] = x;
+        decode(&bytes).ok().map(|_| bytes)
+    })
+}
+
+pub fn setup_validation_test<TFr: Fr, TG1: G1 + G1Mul<TFr>, TG2: G2 + G2Mul<TFr>>(
+    generate_trusted_setup: &dyn Fn(usize, [u8; 32usize]) -> (Vec<TG1>, Vec<TG2>),
+) {
+    let (s1, s2) = generate_trusted_setup(16, SECRET);
+    assert!(validate_g1(&s1, ValidationLevel::Subgroup).is_ok());
+    assert!(validate_g2(&s2, ValidationLevel::Subgroup).is_ok());
+    assert!(TG2::generator().is_valid());
+
+    let mut bytes = Vec::new();
+    save_secrets_to_writer(&mut bytes, &s1, &s2).unwrap();
+    let read = |bytes: &[u8], level| {
+        read_trusted_setup_with_validation::<TG1, TG2, _>(&mut &bytes[..], level)
+    };
+    for level in [
+        ValidationLevel::None,
+        ValidationLevel::OnCurve,
+        ValidationLevel::Subgroup,
+    ] {
+        let (g1, g2) = read(&bytes, level).unwrap();
+        assert!(g1.iter().zip(&s1).all(|(a, b)| a.equals(b)));
+        assert!(g2.iter().zip(&s2).all(|(a, b)| a.equals(b)));
+    }
+
+    // Points on the curve but outside of the subgroup are only rejected by the subgroup check
+    if let Some(point) = off_subgroup_encoding(48, TG1::from_bytes) {
+        let mut tampered = bytes.clone();
+        let offset = SETUP_FILE_HEADER_SIZE + 5 * 48;
+        tampered[offset..offset + 48].copy_from_slice(&point);
+        assert!(read(&tampered, ValidationLevel::OnCurve).is_ok());
+        let err = read(&tampered, ValidationLevel::Subgroup).err().unwrap();
+        assert!(matches!(
+            err,
+            KzgError::InvalidPoint {
+                what: "G1 point",
+                index: 5,
+                ..
+            }
+        ));
+    }
+    if let Some(point) = off_subgroup_encoding(96, TG2::from_bytes) {
+        let mut tampered = bytes.clone();
+        let offset = SETUP_FILE_HEADER_SIZE + 16 * 48 + 96;
+        tampered[offset..offset + 96].copy_from_slice(&point);
+        assert!(read(&tampered, ValidationLevel::OnCurve).is_ok());
+        let err = read(&tampered, ValidationLevel::Subgroup).err().unwrap();
+        assert!(matches!(
+            err,
+            KzgError::InvalidPoint {
+                what: "G2 point",
+                index: 1,
+                ..
+            }
+        ));
+        assert!(load_secrets_from_reader_with_validation::<TG1, TG2, _>(
+            &mut tampered.as_slice(),
+            ValidationLevel::Subgroup
+        )
+        .is_err());
+    }
+}
+
+pub fn table_validation_test<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>() {
+    let mut points = (0..64).map(|_| TG1::rand()).collect::<Vec<_>>();
+    let affines = TG1Affine::into_affines(&points);
+    assert!(affines
+        .iter()
+        .all(g1_affine_on_curve::<TG1, TG1Fp, TG1Affine>));
+    assert!(
+        validate_g1_affines::<TG1, TG1Fp, TG1Affine>(&affines, ValidationLevel::Subgroup).is_ok()
+    );
+
+    // Off the curve
+    let mut tampered = affines.clone();
+    *tampered[7].y_mut() = tampered[7].y().double();
+    assert!(!g1_affine_on_curve::<TG1, TG1Fp, TG1Affine>(&tampered[7]));
+    assert!(validate_g1_affines::<TG1, TG1Fp, TG1Affine>(&tampered, ValidationLevel::None).is_ok());
+    let err = validate_g1_affines::<TG1, TG1Fp, TG1Affine>(&tampered, ValidationLevel::OnCurve)
+        .unwrap_err();
+    assert!(matches!(err, KzgError::InvalidPoint { index: 7, .. }));
+    assert_eq!(err.to_string(), "Point 7 is not on the curve");
+
+    let Some(table) = precompute::<TFr, TG1, TG1Fp, TG1Affine>(&points).unwrap() else {
+        return;
+    };
+    assert!(table.validate(ValidationLevel::Subgroup).is_ok());
+
+    // Tables built from a point outside of the subgroup hold its multiples
+    let Some(point) = off_subgroup_encoding(48, TG1::from_bytes) else {
+        return;
+    };
+    points[3] = TG1::from_bytes(&point).unwrap();
+    let table = precompute::<TFr, TG1, TG1Fp, TG1Affine>(&points)
+        .unwrap()
+        .unwrap();
+    assert!(table.validate(ValidationLevel::OnCurve).is_ok());
+    assert!(table.validate(ValidationLevel::Subgroup).is_err());
+}
@@ -0,0 +1,80 @@
+use std::sync::Arc;
+
+use kzg::eip_4844::{blob_to_kzg_commitment_rust, bytes_to_blob, compute_blob_kzg_proof_rust};
+use kzg::verification_queue::VerificationQueue;
+use kzg::{
+    FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1LinComb, G1Mul, KZGSettings, PairingVerify, Poly,
+    G1, G2,
+};
+
+use crate::tests::eip_4844::generate_random_blob_bytes;
+use crate::tests::nonblocking::block_on;
+use crate::tests::utils::get_trusted_setup_path;
+
+pub fn verification_queue_test<
+    TFr: Fr + Copy + Send + 'static,
+    TG1: G1
+        + G1Mul<TFr>
+        + G1GetFp<TG1Fp>
+        + G1LinComb<TFr, TG1Fp, TG1Affine>
+        + PairingVerify<TG1, TG2>
+        + Send
+        + 'static,
+    TG2: G2,
+    TPoly: Poly<TFr>,
+    TFFTSettings: FFTSettings<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine> + Send + Sync + 'static,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    load_trusted_setup: &dyn Fn(&str) -> Result<TKZGSettings, String>,
+) {
+    let ts = Arc::new(load_trusted_setup(get_trusted_setup_path().as_str()).unwrap());
+
+    assert!(VerificationQueue::new(ts.clone(), 0, 4).is_err());
+    assert!(VerificationQueue::new(ts.clone(), 4, 0).is_err());
+
+    let mut rng = rand::thread_rng();
+    let blobs = (0..6)
+        .map(|_| bytes_to_blob::<TFr>(&generate_random_blob_bytes(&mut rng)).unwrap())
+        .collect::<Vec<_>>();
+    let commitments = blobs
+        .iter()
+        .map(|blob| blob_to_kzg_commitment_rust(blob, ts.as_ref()).unwrap())
+        .collect::<Vec<TG1>>();
+    let mut proofs = blobs
+        .iter()
+        .zip(commitments.iter())
+        .map(|(blob, commitment)| {
+            compute_blob_kzg_proof_rust(blob, commitment, ts.as_ref()).unwrap()
+        })
+        .collect::<Vec<TG1>>();
+
+    // Invalidate one proof, which fails its batch but must not fail the others
+    proofs[3] = proofs[2].clone();
+
+    let queue = VerificationQueue::new(ts, 16, 4).unwrap();
+    let handles = blobs
+        .iter()
+        .zip(commitments.iter())
+        .zip(proofs.iter())
+        .map(|((blob, commitment), proof)| {
+            queue
+                .submit(blob.clone(), commitment.clone(), proof.clone())
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    let results = handles
+        .into_iter()
+        .map(|handle| block_on(handle).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(results, vec![true, true, true, false, true, true]);
+
+    // Proofs still pending when the queue is dropped are verified before it stops
+    let pending = queue
+        .submit(blobs[0].clone(), commitments[0].clone(), proofs[0].clone())
+        .unwrap();
+    drop(queue);
+    assert!(block_on(pending).unwrap());
+}
@@ -0,0 +1,60 @@
+use kzg::eip_4844::{
+    blob_to_kzg_commitment_rust, blob_to_polynomial, bytes_to_blob, compute_blob_kzg_proof_rust,
+    evaluate_polynomial_in_evaluation_form, verify_blob_kzg_proof_rust, FIELD_ELEMENTS_PER_BLOB,
+};
+use kzg::verifier::KzgVerifier;
+use kzg::{
+    FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1LinComb, G1Mul, G2Mul, KZGSettings, PairingVerify,
+    Poly, G1, G2,
+};
+
+use crate::tests::eip_4844::generate_random_blob_bytes;
+use crate::tests::utils::get_trusted_setup_path;
+
+pub fn fixed_size_verifier_test<
+    TFr: Fr + Copy,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + G1LinComb<TFr, TG1Fp, TG1Affine> + PairingVerify<TG1, TG2>,
+    TG2: G2 + G2Mul<TFr>,
+    TPoly: Poly<TFr>,
+    TFFTSettings: FFTSettings<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    load_trusted_setup: &dyn Fn(&str) -> Result<TKZGSettings, String>,
+) {
+    let ts = load_trusted_setup(get_trusted_setup_path().as_str()).unwrap();
+    let verifier = KzgVerifier::<TFr, TG1, TG2, FIELD_ELEMENTS_PER_BLOB>::from_settings::<
+        TFFTSettings,
+        TPoly,
+        TKZGSettings,
+        TG1Fp,
+        TG1Affine,
+    >(&ts)
+    .unwrap();
+    let mut rng = rand::thread_rng();
+
+    let blob: Vec<TFr> = bytes_to_blob(&generate_random_blob_bytes(&mut rng)).unwrap();
+    let fixed_blob: &[TFr; FIELD_ELEMENTS_PER_BLOB] = blob.as_slice().try_into().unwrap();
+    let commitment = blob_to_kzg_commitment_rust(&blob, &ts).unwrap();
+    let proof = compute_blob_kzg_proof_rust(&blob, &commitment, &ts).unwrap();
+
+    assert!(verify_blob_kzg_proof_rust(&blob, &commitment, &proof, &ts).unwrap());
+    assert!(verifier
+        .verify_blob_kzg_proof(fixed_blob, &commitment, &proof)
+        .unwrap());
+
+    // The allocation-free evaluation agrees with the native one
+    let z = verifier.compute_challenge(fixed_blob, &commitment);
+    let polynomial: TPoly = blob_to_polynomial(&blob).unwrap();
+    let y = evaluate_polynomial_in_evaluation_form(&polynomial, &z, &ts).unwrap();
+    assert!(verifier.evaluate(fixed_blob, &z).unwrap().equals(&y));
+    assert!(verifier
+        .verify_kzg_proof(&commitment, &z, &y, &proof)
+        .unwrap());
+
+    let wrong_proof = proof.add_or_dbl(&TG1::generator());
+    assert!(!verifier
+        .verify_blob_kzg_proof(fixed_blob, &commitment, &wrong_proof)
+        .unwrap());
+}
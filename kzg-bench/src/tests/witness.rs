@@ -0,0 +1,56 @@
+use kzg::eip_4844::{
+    blob_to_kzg_commitment_rust, blob_to_polynomial, bytes_to_blob, compute_blob_kzg_proof_rust,
+    evaluate_polynomial_in_evaluation_form,
+};
+use kzg::witness::verify_blob_kzg_proof_witness_rust;
+use kzg::{
+    FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1LinComb, G1Mul, G2Mul, KZGSettings, PairingVerify,
+    Poly, G1, G2,
+};
+
+use crate::tests::eip_4844::generate_random_blob_bytes;
+use crate::tests::utils::get_trusted_setup_path;
+
+pub fn verify_blob_kzg_proof_witness_test<
+    TFr: Fr + Copy,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + G1LinComb<TFr, TG1Fp, TG1Affine> + PairingVerify<TG1, TG2>,
+    TG2: G2 + G2Mul<TFr>,
+    TPoly: Poly<TFr>,
+    TFFTSettings: FFTSettings<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    load_trusted_setup: &dyn Fn(&str) -> Result<TKZGSettings, String>,
+) {
+    let ts = load_trusted_setup(get_trusted_setup_path().as_str()).unwrap();
+    let mut rng = rand::thread_rng();
+
+    let blob: Vec<TFr> = bytes_to_blob(&generate_random_blob_bytes(&mut rng)).unwrap();
+    let commitment = blob_to_kzg_commitment_rust(&blob, &ts).unwrap();
+    let proof = compute_blob_kzg_proof_rust(&blob, &commitment, &ts).unwrap();
+
+    let witness = verify_blob_kzg_proof_witness_rust(&blob, &commitment, &proof, &ts).unwrap();
+    assert!(witness.proof_witness.valid);
+
+    // The exported evaluation must match the native barycentric evaluation
+    let polynomial: TPoly = blob_to_polynomial(&blob).unwrap();
+    let y =
+        evaluate_polynomial_in_evaluation_form(&polynomial, &witness.proof_witness.z, &ts).unwrap();
+    assert!(y.equals(&witness.proof_witness.y));
+
+    // The pairing inputs alone are enough to reproduce the check
+    let pw = &witness.proof_witness;
+    assert!(TG1::verify(
+        &pw.commitment_minus_y,
+        &pw.g2_generator,
+        &pw.proof,
+        &pw.s_minus_z
+    ));
+
+    // A wrong proof still produces a witness, but an invalid one
+    let wrong_proof = proof.add_or_dbl(&TG1::generator());
+    let witness =
+        verify_blob_kzg_proof_witness_rust(&blob, &commitment, &wrong_proof, &ts).unwrap();
+    assert!(!witness.proof_witness.valid);
+}
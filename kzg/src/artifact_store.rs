@@ -0,0 +1,287 @@
+extern crate alloc;
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use std::fs;
+use std::path::PathBuf;
+
+use sha2::{Digest, Sha256};
+
+use crate::io_utils::{load_secrets_from_reader, save_secrets_to_writer};
+use crate::msm::precompute::{precompute, PrecomputationTable};
+use crate::setup_file::io_error;
+use crate::{Fr, G1Affine, G1Fp, G1GetFp, G1Mul, G1, G2};
+
+////////////////////////////// Artifact stores //////////////////////////////
+//
+// Setup points and precomputation tables are expensive to generate and are usually shared by many
+// nodes. `ArtifactStore` abstracts where they are kept: artifacts are byte strings under a key,
+// stored together with their SHA-256 digest, so that a store can report what it holds without
+// reading it back and a corrupted artifact is rejected on `get`.
+//
+// `FsArtifactStore` keeps them in a directory. Object stores such as S3 or GCS are supported by
+// implementing the trait in the application, on top of their client crate. The helpers below save
+// and load setups and tables through any store, and `cached_table` builds a table only when the
+// store does not already hold it.
+
+/// SHA-256 digest of an artifact
+pub type ArtifactDigest = [u8; 32];
+
+/// Entry of `ArtifactStore::list`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArtifactInfo {
+    pub key: String,
+    pub size: u64,
+    pub digest: ArtifactDigest,
+}
+
+/// Storage for setup and table artifacts
+///
+/// Keys are made of ASCII letters, digits, `-`, `_` and `.`, and do not start with `.`, see
+/// `check_artifact_key`.
+pub trait ArtifactStore: Send + Sync {
+    /// Store `bytes` under `key`, replacing any previous artifact, and return their digest
+    fn put(&self, key: &str, bytes: &[u8]) -> Result<ArtifactDigest, String>;
+
+    /// Artifact stored under `key`, `None` if there is none. Fails if the bytes do not match the
+    /// stored digest
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, String>;
+
+    /// Every artifact in the store
+    fn list(&self) -> Result<Vec<ArtifactInfo>, String>;
+
+    /// Same as `get`, failing unless the artifact has the digest `digest`, e.g. one pinned in the
+    /// configuration of the node
+    fn get_with_digest(
+        &self,
+        key: &str,
+        digest: &ArtifactDigest,
+    ) -> Result<Option<Vec<u8>>, String> {
+        match self.get(key)? {
+            Some(bytes) if artifact_digest(&bytes) != *digest => Err(format!(
+                "Artifact {} does not match the expected digest",
+                key
+            )),
+            bytes => Ok(bytes),
+        }
+    }
+}
+
+pub fn artifact_digest(bytes: &[u8]) -> ArtifactDigest {
+    Sha256::digest(bytes).into()
+}
+
+/// Check that `key` can be used by every store, as a file name or an object name
+pub fn check_artifact_key(key: &str) -> Result<(), String> {
+    let valid = !key.is_empty()
+        && !key.starts_with('.')
+        && key
+            .bytes()
+            .all(|c| c.is_ascii_alphanumeric() || c == b'-' || c == b'_' || c == b'.');
+    if !valid {
+        return Err(format!("Invalid artifact key {:?}", key));
+    }
+
+    Ok(())
+}
+
+/// `ArtifactStore` keeping every artifact in a file of a directory, next to a `.sha256` file
+/// holding its digest
+#[derive(Debug, Clone)]
+pub struct FsArtifactStore {
+    root: PathBuf,
+}
+
+const DIGEST_SUFFIX: &str = ".sha256";
+
+impl FsArtifactStore {
+    /// Store in the directory `root`, created if it does not exist
+    pub fn new(root: &str) -> Result<Self, String> {
+        fs::create_dir_all(root).map_err(io_error)?;
+
+        Ok(Self {
+            root: PathBuf::from(root),
+        })
+    }
+
+    fn path(&self, key: &str, suffix: &str) -> Result<PathBuf, String> {
+        check_artifact_key(key)?;
+
+        Ok(self.root.join(format!("{}{}", key, suffix)))
+    }
+
+    fn read_digest(&self, key: &str) -> Result<Option<ArtifactDigest>, String> {
+        let bytes = match fs::read(self.path(key, DIGEST_SUFFIX)?) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(io_error(e)),
+        };
+
+        bytes
+            .try_into()
+            .map(Some)
+            .map_err(|_| format!("Digest of artifact {} is corrupted", key))
+    }
+}
+
+impl ArtifactStore for FsArtifactStore {
+    fn put(&self, key: &str, bytes: &[u8]) -> Result<ArtifactDigest, String> {
+        let digest = artifact_digest(bytes);
+
+        // Written under a temporary name first, so that readers never see a partial artifact
+        let tmp = self.path(key, ".tmp")?;
+        fs::write(&tmp, bytes).map_err(io_error)?;
+        fs::write(self.path(key, DIGEST_SUFFIX)?, digest).map_err(io_error)?;
+        fs::rename(&tmp, self.path(key, "")?).map_err(io_error)?;
+
+        Ok(digest)
+    }
+
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, String> {
+        let bytes = match fs::read(self.path(key, "")?) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(io_error(e)),
+        };
+        let digest = self
+            .read_digest(key)?
+            .ok_or_else(|| format!("Artifact {} has no digest", key))?;
+        if artifact_digest(&bytes) != digest {
+            return Err(format!(
+                "Artifact {} is corrupted, its bytes do not match the stored digest",
+                key
+            ));
+        }
+
+        Ok(Some(bytes))
+    }
+
+    fn list(&self) -> Result<Vec<ArtifactInfo>, String> {
+        let mut artifacts = Vec::new();
+        for entry in fs::read_dir(&self.root).map_err(io_error)? {
+            let entry = entry.map_err(io_error)?;
+            let name = entry.file_name();
+            let Some(key) = name
+                .to_str()
+                .and_then(|name| name.strip_suffix(DIGEST_SUFFIX))
+            else {
+                continue;
+            };
+            let Some(digest) = self.read_digest(key)? else {
+                continue;
+            };
+            let size = match fs::metadata(self.path(key, "")?) {
+                Ok(metadata) => metadata.len(),
+                // Digest of an artifact that is being written
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(io_error(e)),
+            };
+            artifacts.push(ArtifactInfo {
+                key: key.to_string(),
+                size,
+                digest,
+            });
+        }
+        artifacts.sort_by(|a, b| a.key.cmp(&b.key));
+
+        Ok(artifacts)
+    }
+}
+
+/// Save the setup points under `key`, in the format of `save_secrets_to_writer`
+pub fn put_setup<TG1: G1, TG2: G2>(
+    store: &dyn ArtifactStore,
+    key: &str,
+    secret_g1: &[TG1],
+    secret_g2: &[TG2],
+) -> Result<ArtifactDigest, String> {
+    let mut bytes = Vec::new();
+    save_secrets_to_writer(&mut bytes, secret_g1, secret_g2)?;
+
+    store.put(key, &bytes)
+}
+
+/// Load the setup points saved by `put_setup`, `None` if the store does not hold `key`
+#[allow(clippy::type_complexity)]
+pub fn get_setup<TG1: G1, TG2: G2>(
+    store: &dyn ArtifactStore,
+    key: &str,
+) -> Result<Option<(Vec<TG1>, Vec<TG2>)>, String> {
+    store
+        .get(key)?
+        .map(|bytes| load_secrets_from_reader(&mut bytes.as_slice()).map_err(String::from))
+        .transpose()
+}
+
+/// Save the precomputation table under `key`, in the format of `write_to_writer`
+pub fn put_table<TFr, TG1, TG1Fp, TG1Affine>(
+    store: &dyn ArtifactStore,
+    key: &str,
+    table: &PrecomputationTable<TFr, TG1, TG1Fp, TG1Affine>,
+) -> Result<ArtifactDigest, String>
+where
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+{
+    let mut bytes = Vec::new();
+    table.write_to_writer(&mut bytes)?;
+
+    store.put(key, &bytes)
+}
+
+/// Load the precomputation table saved by `put_table`, `None` if the store does not hold `key`
+///
+/// # Safety
+///
+/// The points are not validated, the store must only hold tables written by `put_table` with the
+/// same backend, see `read_from_reader`
+pub unsafe fn get_table<TFr, TG1, TG1Fp, TG1Affine>(
+    store: &dyn ArtifactStore,
+    key: &str,
+) -> Result<Option<PrecomputationTable<TFr, TG1, TG1Fp, TG1Affine>>, String>
+where
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+{
+    store
+        .get(key)?
+        .map(|bytes| {
+            PrecomputationTable::read_from_reader(&mut bytes.as_slice()).map_err(String::from)
+        })
+        .transpose()
+}
+
+/// Precomputation table of `points`, loaded from `key` when the store holds it, and otherwise
+/// built and saved under `key` for the next call
+///
+/// # Safety
+///
+/// Same as `get_table`. The stored table is not checked against `points`, so `key` must identify
+/// the points, e.g. by the digest of the setup they come from.
+pub unsafe fn cached_table<TFr, TG1, TG1Fp, TG1Affine>(
+    store: &dyn ArtifactStore,
+    key: &str,
+    points: &[TG1],
+) -> Result<Option<PrecomputationTable<TFr, TG1, TG1Fp, TG1Affine>>, String>
+where
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+{
+    if let Some(table) = get_table(store, key)? {
+        return Ok(Some(table));
+    }
+
+    let table = precompute(points)?;
+    if let Some(table) = &table {
+        put_table(store, key, table)?;
+    }
+
+    Ok(table)
+}
@@ -0,0 +1,166 @@
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::capabilities::{capabilities, Capabilities};
+use crate::dev_setup::INSECURE_DEV_SETUP_SEED;
+use crate::eip_4844::load_trusted_setup_rust;
+use crate::secret::{generate_trusted_setup_with_secret, SecretScalar};
+use crate::{
+    FFTFr, FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1LinComb, G1Mul, G1ProjAddAffine, G2Mul,
+    KZGSettings, PairingVerify, Poly, FFTG1, G1, G2,
+};
+
+////////////////////////////// Backend type bundles //////////////////////////////
+//
+// Generic code over the traits of this crate carries up to nine type parameters, which downstream
+// crates copy from function to function. A backend bundles its concrete types behind a single
+// `Backend` implementation, so generic code only takes `B: Backend` and names `B::Fr`, `B::G1`
+// and so on. `KzgBackend` adds the constructors on top, with default implementations shared by
+// all backends.
+//
+// Code written against one backend at a time can instead alias the concrete types with
+// `backend!`:
+//
+//     mod kzg_types {
+//         kzg::backend!(rust_kzg_blst::prelude::FsBackend);
+//     }
+//
+// which defines `Fr`, `G1`, `G2`, `G1Fp`, `G1Affine`, `G1ProjAddAffine`, `Poly`, `FFTSettings`
+// and `KZGSettings`. A glob import of the module, `use kzg_types::*;`, also brings the
+// corresponding traits into scope.
+
+/// Concrete types of a backend and the traits they implement together
+pub trait Backend {
+    /// Short name of the backend, as reported by `KzgBackend::capabilities`
+    const NAME: &'static str;
+
+    type Fr: Fr + Copy;
+    type G1: G1
+        + G1Mul<Self::Fr>
+        + G1GetFp<Self::G1Fp>
+        + G1LinComb<Self::Fr, Self::G1Fp, Self::G1Affine>
+        + PairingVerify<Self::G1, Self::G2>;
+    type G2: G2 + G2Mul<Self::Fr>;
+    type G1Fp: G1Fp;
+    type G1Affine: G1Affine<Self::G1, Self::G1Fp>;
+    type G1ProjAddAffine: G1ProjAddAffine<Self::G1, Self::G1Fp, Self::G1Affine>;
+    type Poly: Poly<Self::Fr>;
+    type FFTSettings: FFTSettings<Self::Fr> + FFTFr<Self::Fr> + FFTG1<Self::G1>;
+    type KZGSettings: KZGSettings<
+        Self::Fr,
+        Self::G1,
+        Self::G2,
+        Self::FFTSettings,
+        Self::Poly,
+        Self::G1Fp,
+        Self::G1Affine,
+    >;
+}
+
+/// `Backend` together with the constructors application code needs, implemented once here for
+/// every backend so that setups are generated and loaded the same way everywhere
+pub trait KzgBackend: Backend {
+    /// Configuration of the build, including the name of this backend
+    fn capabilities() -> Capabilities {
+        Capabilities {
+            backend: Some(Self::NAME),
+            ..capabilities()
+        }
+    }
+
+    /// Insecure setup of `n` powers of the secret derived from `secret`, for tests and benchmarks
+    fn generate_trusted_setup(n: usize, secret: [u8; 32]) -> (Vec<Self::G1>, Vec<Self::G2>) {
+        Self::generate_trusted_setup_with_secret(n, &SecretScalar::from_seed(secret))
+    }
+
+    /// Setup of `n` powers of `secret`, e.g. of an application ceremony run in-process, wiping the
+    /// powers as it goes, see the `secret` module
+    fn generate_trusted_setup_with_secret(
+        n: usize,
+        secret: &SecretScalar<Self::Fr>,
+    ) -> (Vec<Self::G1>, Vec<Self::G2>) {
+        generate_trusted_setup_with_secret(n, secret)
+    }
+
+    /// Setup of `n` powers of the public development secret, see `dev_setup`
+    fn generate_insecure_dev_setup(n: usize) -> (Vec<Self::G1>, Vec<Self::G2>) {
+        Self::generate_trusted_setup(n, INSECURE_DEV_SETUP_SEED)
+    }
+
+    fn new_fft_settings(max_scale: usize) -> Result<Self::FFTSettings, String> {
+        Self::FFTSettings::new(max_scale)
+    }
+
+    fn new_kzg_settings(
+        secret_g1: &[Self::G1],
+        secret_g2: &[Self::G2],
+        length: usize,
+        fft_settings: &Self::FFTSettings,
+    ) -> Result<Self::KZGSettings, String> {
+        Self::KZGSettings::new(secret_g1, secret_g2, length, fft_settings)
+    }
+
+    /// EIP-4844 settings from the compressed points of the trusted setup, in Lagrange form
+    fn load_trusted_setup(g1_bytes: &[u8], g2_bytes: &[u8]) -> Result<Self::KZGSettings, String> {
+        load_trusted_setup_rust::<
+            Self::Fr,
+            Self::G1,
+            Self::G2,
+            Self::FFTSettings,
+            Self::Poly,
+            Self::KZGSettings,
+            Self::G1Fp,
+            Self::G1Affine,
+        >(g1_bytes, g2_bytes)
+    }
+
+    /// EIP-4844 settings from a trusted setup in the textual c-kzg format, read to the end of
+    /// `reader`
+    #[cfg(all(feature = "std", feature = "io"))]
+    fn load_trusted_setup_reader<R: std::io::Read + ?Sized>(
+        reader: &mut R,
+    ) -> Result<Self::KZGSettings, String> {
+        let mut contents = String::new();
+        reader
+            .read_to_string(&mut contents)
+            .map_err(|e| alloc::format!("Unable to read trusted setup: {}", e))?;
+        let (g1_bytes, g2_bytes) = crate::eip_4844::load_trusted_setup_string(&contents)?;
+
+        Self::load_trusted_setup(&g1_bytes, &g2_bytes)
+    }
+
+    /// EIP-4844 settings from a trusted setup file in the textual c-kzg format
+    #[cfg(all(feature = "std", feature = "io"))]
+    fn load_trusted_setup_file(path: &str) -> Result<Self::KZGSettings, String> {
+        let mut file =
+            std::fs::File::open(path).map_err(|e| alloc::format!("Unable to read file: {}", e))?;
+
+        Self::load_trusted_setup_reader(&mut file)
+    }
+}
+
+/// Alias the concrete types of a `Backend` under the names of the traits they implement, and
+/// bring those traits into scope
+#[macro_export]
+macro_rules! backend {
+    ($backend: ty) => {
+        #[allow(unused_imports)]
+        pub use $crate::{
+            FFTFr as _, FFTSettings as _, Fr as _, G1Affine as _, G1Fp as _, G1GetFp as _,
+            G1LinComb as _, G1Mul as _, G1ProjAddAffine as _, G2Mul as _, KZGSettings as _,
+            PairingVerify as _, Poly as _, FFTG1 as _, G1 as _, G2 as _,
+        };
+
+        pub type Fr = <$backend as $crate::backend::Backend>::Fr;
+        pub type G1 = <$backend as $crate::backend::Backend>::G1;
+        pub type G2 = <$backend as $crate::backend::Backend>::G2;
+        pub type G1Fp = <$backend as $crate::backend::Backend>::G1Fp;
+        pub type G1Affine = <$backend as $crate::backend::Backend>::G1Affine;
+        pub type G1ProjAddAffine = <$backend as $crate::backend::Backend>::G1ProjAddAffine;
+        pub type Poly = <$backend as $crate::backend::Backend>::Poly;
+        pub type FFTSettings = <$backend as $crate::backend::Backend>::FFTSettings;
+        pub type KZGSettings = <$backend as $crate::backend::Backend>::KZGSettings;
+    };
+}
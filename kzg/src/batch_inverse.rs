@@ -0,0 +1,163 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use crate::{Fr, G1Affine, G1Fp, G1};
+
+////////////////////////////// Batch inversion //////////////////////////////
+//
+// Inverting field elements and converting projective points to affine one at a time pays a
+// field inversion, about a hundred multiplications, per element. Montgomery's trick inverts a
+// batch with one inversion and three multiplications per element: multiply the elements together
+// left to right keeping the running products, invert the product, and walk back right to left.
+//
+// `batch_inverse` and `batch_inverse_fr` run the trick over chunks of `BATCH_INVERSE_CHUNK`
+// elements, one chunk per thread with the `parallel` feature. `to_affines_batch` splits points the
+// same way and converts each chunk with `G1Affine::into_affines_loc`, which backends implement
+// with the trick in the coordinates they use, e.g. `blst_p1s_to_affine` for blst. The point at
+// infinity has no inverse to contribute to the trick, and a single one spoils the whole batch with
+// blst, so runs of points are converted between identities and identities on their own.
+//
+// Field multiplications are those of the backend. blst implements them in assembly for x86-64,
+// with ADX and BMI2 when the CPU has them, and for ARMv8. There are no separate AVX2 or NEON
+// kernels: a 381-bit Montgomery multiplication is a chain of dependent 64-bit multiply-adds,
+// which 32-bit vector lanes do not speed up.
+
+/// Elements per chunk of `batch_inverse`, each chunk paying one inversion
+pub const BATCH_INVERSE_CHUNK: usize = 1 << 10;
+
+/// Replace every element of `chunk` by its inverse with Montgomery's trick, zeros staying zero
+fn montgomery_inverse<T: Copy>(
+    chunk: &mut [T],
+    one: T,
+    is_zero: impl Fn(&T) -> bool,
+    mul: impl Fn(&T, &T) -> T,
+    inverse: impl Fn(&T) -> T,
+) {
+    // `prefix[i]` is the product of the non-zero elements before `i`
+    let mut prefix = Vec::with_capacity(chunk.len());
+    let mut acc = one;
+    for value in chunk.iter() {
+        prefix.push(acc);
+        if !is_zero(value) {
+            acc = mul(&acc, value);
+        }
+    }
+
+    let mut inv = inverse(&acc);
+    for (value, prefix) in chunk.iter_mut().zip(prefix).rev() {
+        if is_zero(value) {
+            continue;
+        }
+        let value_inv = mul(&inv, &prefix);
+        inv = mul(&inv, value);
+        *value = value_inv;
+    }
+}
+
+/// Run `f` over the chunks of `values`, on the thread pool with the `parallel` feature
+fn for_each_chunk<T: Send>(values: &mut [T], f: impl Fn(&mut [T]) + Sync + Send) {
+    #[cfg(feature = "parallel")]
+    if crate::parallelism::is_parallel() && values.len() > BATCH_INVERSE_CHUNK {
+        use rayon::prelude::*;
+
+        crate::parallelism::install(|| values.par_chunks_mut(BATCH_INVERSE_CHUNK).for_each(&f));
+        return;
+    }
+
+    values.chunks_mut(BATCH_INVERSE_CHUNK).for_each(f);
+}
+
+/// Invert every element of `values` in place, leaving zeros as they are
+pub fn batch_inverse<TFp: G1Fp>(values: &mut [TFp]) {
+    for_each_chunk(values, |chunk| {
+        montgomery_inverse(
+            chunk,
+            TFp::ONE,
+            TFp::is_zero,
+            |a, b| a.mul_fp(b),
+            |a| a.inverse().unwrap_or(TFp::ZERO),
+        )
+    });
+}
+
+/// Same as `batch_inverse`, always on the calling thread, for callers already running on a worker
+pub(crate) fn batch_inverse_sequential<TFp: G1Fp>(values: &mut [TFp]) {
+    for chunk in values.chunks_mut(BATCH_INVERSE_CHUNK) {
+        montgomery_inverse(
+            chunk,
+            TFp::ONE,
+            TFp::is_zero,
+            |a, b| a.mul_fp(b),
+            |a| a.inverse().unwrap_or(TFp::ZERO),
+        );
+    }
+}
+
+/// Same as `batch_inverse`, for scalars
+pub fn batch_inverse_fr<TFr: Fr + Copy + Send>(values: &mut [TFr]) {
+    for_each_chunk(values, |chunk| {
+        montgomery_inverse(chunk, TFr::one(), TFr::is_zero, TFr::mul, TFr::inverse)
+    });
+}
+
+/// Convert `points` to affine into `out`, which must be as long, a chunk at a time with
+/// `G1Affine::into_affines_loc` and on the thread pool with the `parallel` feature
+pub fn to_affines_batch_loc<TG1: G1, TFp: G1Fp, TG1Affine: G1Affine<TG1, TFp>>(
+    out: &mut [TG1Affine],
+    points: &[TG1],
+) {
+    assert_eq!(out.len(), points.len(), "Output and points lengths differ");
+
+    #[cfg(feature = "parallel")]
+    if crate::parallelism::is_parallel() && points.len() > BATCH_INVERSE_CHUNK {
+        use rayon::prelude::*;
+
+        crate::parallelism::install(|| {
+            let chunk_size =
+                (points.len() / rayon::current_num_threads()).clamp(1 << 6, BATCH_INVERSE_CHUNK);
+            out.par_chunks_mut(chunk_size)
+                .zip(points.par_chunks(chunk_size))
+                .for_each(|(out, points)| into_affines_chunk(out, points))
+        });
+        return;
+    }
+
+    for (out, points) in out
+        .chunks_mut(BATCH_INVERSE_CHUNK)
+        .zip(points.chunks(BATCH_INVERSE_CHUNK))
+    {
+        into_affines_chunk(out, points);
+    }
+}
+
+/// `G1Affine::into_affines_loc` over the runs of `points` between identities
+fn into_affines_chunk<TG1: G1, TFp: G1Fp, TG1Affine: G1Affine<TG1, TFp>>(
+    out: &mut [TG1Affine],
+    points: &[TG1],
+) {
+    let mut start = 0;
+    for (i, point) in points.iter().enumerate() {
+        if !point.is_inf() {
+            continue;
+        }
+        if start < i {
+            TG1Affine::into_affines_loc(&mut out[start..i], &points[start..i]);
+        }
+        out[i] = TG1Affine::into_affine(point);
+        start = i + 1;
+    }
+    if start < points.len() {
+        TG1Affine::into_affines_loc(&mut out[start..], &points[start..]);
+    }
+}
+
+/// `points` in affine form, see `to_affines_batch_loc`
+pub fn to_affines_batch<TG1: G1, TFp: G1Fp, TG1Affine: G1Affine<TG1, TFp>>(
+    points: &[TG1],
+) -> Vec<TG1Affine> {
+    let mut out = alloc::vec![TG1Affine::default(); points.len()];
+    to_affines_batch_loc(&mut out, points);
+
+    out
+}
@@ -0,0 +1,205 @@
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use sha2::{Digest, Sha256};
+
+#[cfg(feature = "verify")]
+use crate::eip_4844::{verify_blob_kzg_proof_batch_rust, verify_blob_kzg_proof_rust};
+use crate::G1;
+#[cfg(feature = "verify")]
+use crate::{
+    FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1LinComb, G1Mul, KZGSettings, PairingVerify, Poly,
+    G2,
+};
+
+////////////////////////////// Commitments over blob bundles //////////////////////////////
+//
+// A bundle root is a Merkle root over the hashes of blob commitments, so that a rollup posting
+// many blobs can reference all of them with a single 32-byte value. A blob is opened against the
+// root with its commitment, a Merkle path and the usual blob KZG proof.
+//
+// Leaves and inner nodes are hashed with different prefixes, and the leaf count is padded to a
+// power of two with zero hashes.
+
+pub type BundleRoot = [u8; 32];
+
+const LEAF_PREFIX: u8 = 0;
+const NODE_PREFIX: u8 = 1;
+
+/// Merkle path from a commitment to the bundle root
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BundleInclusionProof {
+    /// Position of the commitment in the bundle
+    pub index: usize,
+    /// Sibling hashes, from the leaf level up
+    pub siblings: Vec<[u8; 32]>,
+}
+
+/// Everything needed to check a single blob against a bundle root
+#[derive(Debug, Clone)]
+pub struct BundleOpening<TG1> {
+    pub commitment: TG1,
+    pub blob_proof: TG1,
+    pub inclusion: BundleInclusionProof,
+}
+
+/// Leaf hash of a blob commitment
+pub fn commitment_leaf<TG1: G1>(commitment: &TG1) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([LEAF_PREFIX]);
+    hasher.update(commitment.to_bytes());
+    hasher.finalize().into()
+}
+
+fn hash_nodes(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([NODE_PREFIX]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// All levels of the tree, from the padded leaves up to the root
+fn build_tree<TG1: G1>(commitments: &[TG1]) -> Result<Vec<Vec<[u8; 32]>>, String> {
+    if commitments.is_empty() {
+        return Err(String::from("Bundle must contain at least one commitment"));
+    }
+
+    let mut leaves = vec![[0u8; 32]; commitments.len().next_power_of_two()];
+    for (leaf, commitment) in leaves.iter_mut().zip(commitments) {
+        *leaf = commitment_leaf(commitment);
+    }
+
+    let mut levels = vec![leaves];
+    while levels[levels.len() - 1].len() > 1 {
+        let next = levels[levels.len() - 1]
+            .chunks_exact(2)
+            .map(|pair| hash_nodes(&pair[0], &pair[1]))
+            .collect();
+        levels.push(next);
+    }
+
+    Ok(levels)
+}
+
+/// Compute the root committing to all blob commitments of a bundle
+pub fn bundle_root<TG1: G1>(commitments: &[TG1]) -> Result<BundleRoot, String> {
+    let levels = build_tree(commitments)?;
+    Ok(levels[levels.len() - 1][0])
+}
+
+/// Compute the Merkle path of the commitment at `index`
+pub fn bundle_inclusion_proof<TG1: G1>(
+    commitments: &[TG1],
+    index: usize,
+) -> Result<BundleInclusionProof, String> {
+    if index >= commitments.len() {
+        return Err(String::from("Commitment index is out of bounds"));
+    }
+
+    let levels = build_tree(commitments)?;
+    let siblings = levels[..levels.len() - 1]
+        .iter()
+        .enumerate()
+        .map(|(depth, level)| level[(index >> depth) ^ 1])
+        .collect();
+
+    Ok(BundleInclusionProof { index, siblings })
+}
+
+/// Check that `commitment` is part of the bundle with the given root
+pub fn verify_bundle_inclusion<TG1: G1>(
+    root: &BundleRoot,
+    commitment: &TG1,
+    proof: &BundleInclusionProof,
+) -> bool {
+    if proof.siblings.len() >= usize::BITS as usize || proof.index >> proof.siblings.len() != 0 {
+        return false;
+    }
+
+    let mut node = commitment_leaf(commitment);
+    for (depth, sibling) in proof.siblings.iter().enumerate() {
+        node = if (proof.index >> depth) & 1 == 0 {
+            hash_nodes(&node, sibling)
+        } else {
+            hash_nodes(sibling, &node)
+        };
+    }
+
+    &node == root
+}
+
+/// Compute the openings of every blob of a bundle, given their commitments and blob KZG proofs
+pub fn bundle_openings<TG1: G1>(
+    commitments: &[TG1],
+    blob_proofs: &[TG1],
+) -> Result<Vec<BundleOpening<TG1>>, String> {
+    if commitments.len() != blob_proofs.len() {
+        return Err(String::from(
+            "Number of commitments must match number of proofs",
+        ));
+    }
+
+    (0..commitments.len())
+        .map(|i| {
+            Ok(BundleOpening {
+                commitment: commitments[i].clone(),
+                blob_proof: blob_proofs[i].clone(),
+                inclusion: bundle_inclusion_proof(commitments, i)?,
+            })
+        })
+        .collect()
+}
+
+/// Check a single blob against a bundle root: the commitment must be part of the bundle, and the
+/// blob proof must be valid for the commitment.
+#[cfg(feature = "verify")]
+pub fn verify_bundle_opening_rust<
+    TFr: Fr + Copy,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    root: &BundleRoot,
+    blob: &[TFr],
+    opening: &BundleOpening<TG1>,
+    ts: &TKZGSettings,
+) -> Result<bool, String> {
+    if !verify_bundle_inclusion(root, &opening.commitment, &opening.inclusion) {
+        return Ok(false);
+    }
+
+    verify_blob_kzg_proof_rust(blob, &opening.commitment, &opening.blob_proof, ts)
+}
+
+/// Check a whole bundle against its root with a single batched pairing check
+#[cfg(feature = "verify")]
+pub fn verify_bundle_rust<
+    TFr: Fr + Copy,
+    TG1: G1 + G1Mul<TFr> + PairingVerify<TG1, TG2> + G1GetFp<TG1Fp> + G1LinComb<TFr, TG1Fp, TG1Affine>,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine> + Sync,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    root: &BundleRoot,
+    blobs: &[Vec<TFr>],
+    commitments: &[TG1],
+    blob_proofs: &[TG1],
+    ts: &TKZGSettings,
+) -> Result<bool, String> {
+    if &bundle_root(commitments)? != root {
+        return Ok(false);
+    }
+
+    verify_blob_kzg_proof_batch_rust(blobs, commitments, blob_proofs, ts)
+}
@@ -0,0 +1,64 @@
+extern crate alloc;
+
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use core::fmt;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+////////////////////////////// Cancellation //////////////////////////////
+//
+// A service computing a proof or verifying a batch for a request that was dropped would
+// otherwise burn its CPU until the operation completes. Operations taking a `CancelToken` check
+// it between units of work, on every thread they run on, and stop with `Cancelled` shortly after
+// `cancel` is called from any other thread. Functions failing with a `String` fail with the
+// message of `Cancelled` instead.
+
+/// Error of an operation stopped by its `CancelToken`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled;
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Operation cancelled")
+    }
+}
+
+impl From<Cancelled> for String {
+    fn from(cancelled: Cancelled) -> Self {
+        cancelled.to_string()
+    }
+}
+
+/// Shared cancellation flag, clones of a token cancel together
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ask the operations holding this token to stop
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Release);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Acquire)
+    }
+
+    /// `Err(Cancelled)` once `cancel` was called
+    pub fn check(&self) -> Result<(), Cancelled> {
+        if self.is_cancelled() {
+            Err(Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// `CancelToken::check` of an optional token, operations without a token are never cancelled
+#[inline]
+pub fn check_cancelled(token: Option<&CancelToken>) -> Result<(), Cancelled> {
+    token.map_or(Ok(()), CancelToken::check)
+}
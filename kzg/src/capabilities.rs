@@ -0,0 +1,139 @@
+use core::fmt;
+
+use crate::envelope::CurveId;
+
+////////////////////////////// Build introspection //////////////////////////////
+//
+// The features of this crate change which code paths run (parallel MSM, precomputation tables,
+// device offloading), so two builds of the same version can behave very differently. `capabilities`
+// reports the configuration this crate was compiled with, for orchestration tooling and bug
+// reports. Cargo unifies features across the dependency graph, so the answer is the same for
+// every backend linked into the binary. `KzgBackend::capabilities` also names the backend.
+//
+// The `Display` implementation gives a single line suitable for logs:
+//
+//     backend=blst curve=BLS12-381 features=std,parallel,io precompute=bgmw msm=parallel-pippenger
+
+/// Precomputation used for MSMs over the trusted setup
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrecomputeStrategy {
+    /// MSMs run without a table
+    None,
+    /// BGMW tables built when the trusted setup is loaded
+    Bgmw,
+}
+
+/// MSM implementation used without a precomputation table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MsmStrategy {
+    /// Single-threaded Pippenger
+    Pippenger,
+    /// Pippenger split into tiles run on the thread pool
+    ParallelPippenger,
+    /// Single-threaded ArkMSM with batch affine additions
+    Arkmsm,
+}
+
+/// Configuration of the compiled build
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+    /// Name of the backend, when queried through `KzgBackend::capabilities`
+    pub backend: Option<&'static str>,
+    pub curve: CurveId,
+    pub std: bool,
+    pub parallel: bool,
+    /// The `async` feature, non-blocking wrappers of `nonblocking`
+    pub nonblocking: bool,
+    pub gpu: bool,
+    pub mmap: bool,
+    pub io: bool,
+    pub commit: bool,
+    pub prove: bool,
+    pub verify: bool,
+    pub das: bool,
+    pub prefetch: bool,
+    pub precompute: PrecomputeStrategy,
+    pub msm: MsmStrategy,
+}
+
+impl Capabilities {
+    /// Names of the enabled features, in the order of `Cargo.toml`
+    pub fn features(&self) -> impl Iterator<Item = &'static str> {
+        [
+            ("std", self.std),
+            ("parallel", self.parallel),
+            ("async", self.nonblocking),
+            ("gpu", self.gpu),
+            ("mmap", self.mmap),
+            ("io", self.io),
+            ("commit", self.commit),
+            ("prove", self.prove),
+            ("verify", self.verify),
+            ("das", self.das),
+            ("prefetch", self.prefetch),
+        ]
+        .into_iter()
+        .filter_map(|(name, enabled)| enabled.then_some(name))
+    }
+}
+
+impl fmt::Display for Capabilities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "backend={} ", self.backend.unwrap_or("unknown"))?;
+        match self.curve {
+            CurveId::Bls12_381 => f.write_str("curve=BLS12-381")?,
+        }
+        f.write_str(" features=")?;
+        for (i, feature) in self.features().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            f.write_str(feature)?;
+        }
+        f.write_str(match self.precompute {
+            PrecomputeStrategy::None => " precompute=none",
+            PrecomputeStrategy::Bgmw => " precompute=bgmw",
+        })?;
+        f.write_str(match self.msm {
+            MsmStrategy::Pippenger => " msm=pippenger",
+            MsmStrategy::ParallelPippenger => " msm=parallel-pippenger",
+            MsmStrategy::Arkmsm => " msm=arkmsm",
+        })
+    }
+}
+
+/// Configuration this crate was compiled with
+pub const fn capabilities() -> Capabilities {
+    Capabilities {
+        backend: None,
+        curve: CurveId::Bls12_381,
+        std: cfg!(feature = "std"),
+        parallel: cfg!(feature = "parallel"),
+        nonblocking: cfg!(feature = "async"),
+        gpu: cfg!(feature = "gpu"),
+        mmap: cfg!(feature = "mmap"),
+        io: cfg!(feature = "io"),
+        commit: cfg!(feature = "commit"),
+        prove: cfg!(feature = "prove"),
+        verify: cfg!(feature = "verify"),
+        das: cfg!(feature = "das"),
+        prefetch: cfg!(feature = "prefetch"),
+        // Mirrors the selection of `PrecomputationTable` and `msm`
+        precompute: if cfg!(all(
+            feature = "precompute",
+            feature = "bgmw",
+            any(not(feature = "arkmsm"), feature = "parallel")
+        )) {
+            PrecomputeStrategy::Bgmw
+        } else {
+            PrecomputeStrategy::None
+        },
+        msm: if cfg!(feature = "parallel") {
+            MsmStrategy::ParallelPippenger
+        } else if cfg!(feature = "arkmsm") {
+            MsmStrategy::Arkmsm
+        } else {
+            MsmStrategy::Pippenger
+        },
+    }
+}
@@ -0,0 +1,215 @@
+extern crate alloc;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use sha2::{Digest, Sha256};
+
+use crate::eip_4844::hash_to_bls_field;
+use crate::rerandomize::{check_srs_consistency, rerandomize_srs, verify_srs_update};
+use crate::secret::{wipe_bytes, SecretScalar};
+use crate::validation::{validate_g1, validate_g2, ValidationLevel};
+use crate::{Fr, G1Mul, G2Mul, PairingVerify, G1, G2};
+
+////////////////////////////// Powers-of-tau ceremonies //////////////////////////////
+//
+// A ceremony starts from the setup of secret 1, every point being a generator, and passes it from
+// participant to participant. Each one multiplies the secret by a fresh `x` drawn from their
+// entropy, with `contribute`, and publishes a `ContributionProof`: `[x]₂` and the new `[s]₁`. The
+// setup is sound as long as one participant forgot their `x`.
+//
+// `verify_contribution` checks one step against the setups before and after it. Since the proofs
+// hold the successive `[s]₁`, `verify_contribution_chain` checks a whole ceremony from the initial
+// and final setups alone, the way the Ethereum KZG ceremony is checked:
+//
+//     e([s_i]₁, [1]₂) == e([s_(i-1)]₁, [x_i]₂)   for every contribution i
+//     [s_n]₁ == [s]₁ of the final setup, which has the form [s^i]₁, [s^i]₂
+//
+// Every point is also checked to be in its subgroup. The secret of a contribution only lives on
+// the stack of `contribute`, which wipes it with the `zeroize` feature, see `secret`. Callers
+// picking their own secret pass it to `contribute_with_secret`, e.g. from a `SecretScalar`.
+
+/// Domain separator of the contribution secret
+pub const CONTRIBUTION_DOMAIN: [u8; 16] = *b"POTCONTRIBUTION_";
+
+/// Least number of entropy bytes accepted by `contribute`
+pub const MIN_CONTRIBUTION_ENTROPY: usize = 32;
+
+/// Setup passed between the participants of a ceremony
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PowersOfTau<TG1, TG2> {
+    pub g1: Vec<TG1>,
+    pub g2: Vec<TG2>,
+}
+
+/// Published by a participant, proves that their setup was derived from the previous one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContributionProof<TG1, TG2> {
+    /// `[x]₂` of the contribution secret `x`
+    pub pubkey: TG2,
+    /// `[s]₁` of the setup after the contribution
+    pub tau_g1: TG1,
+}
+
+/// Contribution secret derived from `entropy`
+pub fn contribution_secret<TFr: Fr>(entropy: &[u8]) -> Result<TFr, String> {
+    if entropy.len() < MIN_CONTRIBUTION_ENTROPY {
+        return Err(format!(
+            "Contribution needs at least {} bytes of entropy, got {}",
+            MIN_CONTRIBUTION_ENTROPY,
+            entropy.len()
+        ));
+    }
+
+    let mut hasher = Sha256::new();
+    hasher.update(CONTRIBUTION_DOMAIN);
+    hasher.update(entropy);
+    let mut digest: [u8; 32] = hasher.finalize().into();
+    let secret = hash_to_bls_field::<TFr>(&digest);
+    wipe_bytes(&mut digest);
+    if secret.is_zero() {
+        return Err(String::from("Entropy hashes to a zero secret"));
+    }
+
+    Ok(secret)
+}
+
+impl<TG1: G1, TG2: G2> PowersOfTau<TG1, TG2> {
+    /// Setup of secret 1 a ceremony starts from, with `num_g1` G1 and `num_g2` G2 points
+    pub fn initial(num_g1: usize, num_g2: usize) -> Result<Self, String> {
+        if num_g1 < 2 || num_g2 < 2 {
+            return Err(String::from(
+                "Trusted setup must contain at least two points in each group",
+            ));
+        }
+
+        Ok(Self {
+            g1: (0..num_g1).map(|_| TG1::generator()).collect(),
+            g2: (0..num_g2).map(|_| TG2::generator()).collect(),
+        })
+    }
+
+    /// Multiply the secret of the setup by a secret derived from `entropy`
+    pub fn contribute<TFr: Fr + Copy>(
+        &self,
+        entropy: &[u8],
+    ) -> Result<(Self, ContributionProof<TG1, TG2>), String>
+    where
+        TG1: G1Mul<TFr>,
+        TG2: G2Mul<TFr>,
+    {
+        let secret = SecretScalar::new(contribution_secret::<TFr>(entropy)?);
+        self.contribute_with_secret(secret.expose())
+    }
+
+    /// Multiply the secret of the setup by `secret`
+    pub fn contribute_with_secret<TFr: Fr + Copy>(
+        &self,
+        secret: &TFr,
+    ) -> Result<(Self, ContributionProof<TG1, TG2>), String>
+    where
+        TG1: G1Mul<TFr>,
+        TG2: G2Mul<TFr>,
+    {
+        if self.g1.len() < 2 {
+            return Err(String::from(
+                "Trusted setup must contain at least two G1 points",
+            ));
+        }
+
+        let (g1, g2) = rerandomize_srs(&self.g1, &self.g2, secret)?;
+        let proof = ContributionProof {
+            pubkey: TG2::generator().mul(secret),
+            tau_g1: g1[1].clone(),
+        };
+
+        Ok((Self { g1, g2 }, proof))
+    }
+
+    /// Check that the setup has the form `[s^i]₁, [s^i]₂` with every point in its subgroup
+    pub fn verify<TFr: Fr>(&self) -> Result<bool, String>
+    where
+        TG1: G1Mul<TFr> + PairingVerify<TG1, TG2>,
+        TG2: G2Mul<TFr>,
+    {
+        if validate_g1(&self.g1, ValidationLevel::Subgroup).is_err()
+            || validate_g2(&self.g2, ValidationLevel::Subgroup).is_err()
+        {
+            return Ok(false);
+        }
+
+        check_srs_consistency::<TFr, TG1, TG2>(&self.g1, &self.g2)
+    }
+}
+
+/// Check that `next` was derived from `previous` by the contribution proven by `proof`
+pub fn verify_contribution<TFr, TG1, TG2>(
+    previous: &PowersOfTau<TG1, TG2>,
+    next: &PowersOfTau<TG1, TG2>,
+    proof: &ContributionProof<TG1, TG2>,
+) -> Result<bool, String>
+where
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + PairingVerify<TG1, TG2>,
+    TG2: G2 + G2Mul<TFr>,
+{
+    if previous.g1.len() != next.g1.len() || previous.g2.len() != next.g2.len() {
+        return Err(String::from(
+            "Contribution must not change the number of points",
+        ));
+    }
+    if next.g1.len() < 2 || !proof.tau_g1.equals(&next.g1[1]) || !valid_pubkey(&proof.pubkey) {
+        return Ok(false);
+    }
+
+    Ok(verify_srs_update(&previous.g1, &next.g1, &proof.pubkey)? && next.verify::<TFr>()?)
+}
+
+/// Check that `last` was derived from `initial` by the contributions proven by `proofs`, in order
+pub fn verify_contribution_chain<TFr, TG1, TG2>(
+    initial: &PowersOfTau<TG1, TG2>,
+    proofs: &[ContributionProof<TG1, TG2>],
+    last: &PowersOfTau<TG1, TG2>,
+) -> Result<bool, String>
+where
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + PairingVerify<TG1, TG2>,
+    TG2: G2 + G2Mul<TFr>,
+{
+    if initial.g1.len() != last.g1.len() || initial.g2.len() != last.g2.len() {
+        return Err(String::from(
+            "Contributions must not change the number of points",
+        ));
+    }
+    if initial.g1.len() < 2 {
+        return Err(String::from(
+            "Trusted setup must contain at least two G1 points",
+        ));
+    }
+    if !initial.verify::<TFr>()? {
+        return Ok(false);
+    }
+
+    let mut tau_g1 = &initial.g1[1];
+    for (i, proof) in proofs.iter().enumerate() {
+        // The same pubkey twice is a replayed contribution
+        let replayed = proofs[..i].iter().any(|p| p.pubkey.equals(&proof.pubkey));
+        if replayed || !valid_pubkey(&proof.pubkey) || !proof.tau_g1.is_valid() {
+            return Ok(false);
+        }
+        // e([s_i]₁, [1]₂) == e([s_(i-1)]₁, [x_i]₂)
+        if !TG1::verify(&proof.tau_g1, &TG2::generator(), tau_g1, &proof.pubkey) {
+            return Ok(false);
+        }
+        tau_g1 = &proof.tau_g1;
+    }
+
+    Ok(tau_g1.equals(&last.g1[1]) && last.verify::<TFr>()?)
+}
+
+/// A zero secret would erase the previous contributions
+fn valid_pubkey<TG2: G2>(pubkey: &TG2) -> bool {
+    // Infinity flag of the compressed encoding
+    pubkey.to_bytes()[0] & 0x40 == 0 && pubkey.is_valid()
+}
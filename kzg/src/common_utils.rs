@@ -1,6 +1,8 @@
 extern crate alloc;
 
+use alloc::format;
 use alloc::string::String;
+use alloc::vec::Vec;
 use core::mem;
 
 pub fn reverse_bit_order<T>(vals: &mut [T]) -> Result<(), String>
@@ -95,3 +97,36 @@ pub fn reverse_bits_limited(length: usize, value: usize) -> usize {
     let unused_bits = length.leading_zeros();
     value.reverse_bits() >> unused_bits
 }
+
+/// Allocate a vector with room for `capacity` elements, returning an error instead of aborting
+/// when the allocation fails.
+pub fn try_vec_with_capacity<T>(capacity: usize) -> Result<Vec<T>, String> {
+    let mut vec = Vec::new();
+    vec.try_reserve_exact(capacity)
+        .map_err(|_| format!("Failed to allocate memory for {} elements", capacity))?;
+    Ok(vec)
+}
+
+/// Fallible version of `vec![value; len]`
+pub fn try_vec<T: Clone>(value: T, len: usize) -> Result<Vec<T>, String> {
+    let mut vec = try_vec_with_capacity(len)?;
+    vec.resize(len, value);
+    Ok(vec)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common_utils::{try_vec, try_vec_with_capacity};
+
+    #[test]
+    fn try_vec_must_allocate_requested_length() {
+        assert_eq!(try_vec(7u8, 3).unwrap(), [7, 7, 7]);
+        assert!(try_vec_with_capacity::<u64>(16).unwrap().capacity() >= 16);
+    }
+
+    #[test]
+    fn try_vec_must_fail_on_impossible_allocation() {
+        assert!(try_vec(0u64, usize::MAX).is_err());
+        assert!(try_vec_with_capacity::<u64>(usize::MAX).is_err());
+    }
+}
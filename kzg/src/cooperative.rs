@@ -0,0 +1,44 @@
+use core::sync::atomic::{AtomicPtr, Ordering};
+
+////////////////////////////// Cooperative yielding //////////////////////////////
+//
+// Without the `parallel` feature, an FFT over G1 or an MSM over a full blob runs for seconds on
+// the calling thread. Browser hosts running the wasm build freeze their UI for that long, and
+// embedded hosts trip their watchdog. A handler installed with `set_yield_handler` is called from
+// inside the long FFT and MSM loops, between units of work of at most a few milliseconds, so that
+// the host can feed its watchdog, report progress or otherwise check in.
+//
+// The handler is called often, so it should return immediately unless its own time slice is over,
+// e.g. by comparing `performance.now()` with the time of the last real yield. With the `parallel`
+// feature, the loops run on the thread pool and the handler is never called.
+
+/// Callback invoked periodically from long-running loops
+pub type YieldHandler = fn();
+
+static YIELD_HANDLER: AtomicPtr<()> = AtomicPtr::new(core::ptr::null_mut());
+
+/// Install a handler called periodically from long FFT and MSM loops, or remove it with `None`
+pub fn set_yield_handler(handler: Option<YieldHandler>) {
+    let ptr = match handler {
+        Some(handler) => handler as *mut (),
+        None => core::ptr::null_mut(),
+    };
+    YIELD_HANDLER.store(ptr, Ordering::Release);
+}
+
+/// Call the installed yield handler, if any. Backends call this between units of work of their
+/// own long-running loops.
+#[inline]
+pub fn maybe_yield() {
+    #[cfg(not(feature = "parallel"))]
+    {
+        let ptr = YIELD_HANDLER.load(Ordering::Acquire);
+        if ptr.is_null() {
+            return;
+        }
+
+        // SAFETY: only `set_yield_handler` stores non-null pointers, and those are `YieldHandler`s
+        let handler = unsafe { core::mem::transmute::<*mut (), YieldHandler>(ptr) };
+        handler();
+    }
+}
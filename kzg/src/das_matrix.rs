@@ -0,0 +1,425 @@
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use sha2::{Digest, Sha256};
+
+use crate::dev_setup::{is_insecure_dev_tau, report_insecure_dev_setup};
+use crate::eip_4844::{compute_powers, hash_to_bls_field};
+use crate::opening::{commitment_minus_value, g2_s_minus_z};
+use crate::{
+    FFTFr, FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1Mul, G2Mul, KZGSettings, PairingVerify,
+    Poly, DAS, G1, G2,
+};
+
+////////////////////////////// 2D erasure-coded data availability matrix //////////////////////////////
+//
+// `k * k` data elements are extended to a `2k * 2k` matrix: every row is extended with
+// `das_fft_extension`, then every column of the result. Original data ends up at even row and
+// column indices, the extension at odd ones. Each extended row and column is the evaluation of a
+// polynomial of degree below `k` over the domain of size `2k`, so it is committed to with a single
+// KZG commitment, and any cell is sampled with a single-point proof against its row or column.
+
+/// Line of the matrix a sample is proven against
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SampleAxis {
+    Row,
+    Column,
+}
+
+/// Published commitments of an extended matrix
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatrixCommitments<TG1> {
+    /// Width of the original data, the extended matrix is `2k * 2k`
+    pub k: usize,
+    pub rows: Vec<TG1>,
+    pub columns: Vec<TG1>,
+}
+
+/// A single cell of the extended matrix and its opening
+#[derive(Debug, Clone)]
+pub struct SampleProof<TFr, TG1> {
+    pub row: usize,
+    pub column: usize,
+    pub value: TFr,
+    pub axis: SampleAxis,
+    pub proof: TG1,
+}
+
+/// Extended matrix with the polynomials of every row and column
+pub struct DasMatrix<TFr, TG1, TPoly> {
+    k: usize,
+    /// `2k * 2k` cells, row-major
+    cells: Vec<TFr>,
+    row_polys: Vec<TPoly>,
+    column_polys: Vec<TPoly>,
+    commitments: MatrixCommitments<TG1>,
+}
+
+/// Extend a line of `k` values to `2k`, with the original values at even indices
+fn extend_line<TFr: Fr, TFFTSettings: DAS<TFr>>(
+    fs: &TFFTSettings,
+    line: &[TFr],
+) -> Result<Vec<TFr>, String> {
+    let odds = fs.das_fft_extension(line)?;
+    Ok(line
+        .iter()
+        .zip(odds.iter())
+        .flat_map(|(even, odd)| [even.clone(), odd.clone()])
+        .collect())
+}
+
+/// Polynomial of degree below `k` evaluating to `line` over the domain of size `2k`
+fn line_to_poly<TFr: Fr, TPoly: Poly<TFr>, TFFTSettings: FFTFr<TFr>>(
+    fs: &TFFTSettings,
+    line: &[TFr],
+) -> Result<TPoly, String> {
+    let coeffs = fs.fft_fr(line, true)?;
+    Ok(TPoly::from_coeffs(&coeffs[..line.len() / 2]))
+}
+
+/// `ω^index` in the domain of size `2k`
+fn domain_point<TFr: Fr, TFFTSettings: FFTSettings<TFr>>(
+    fs: &TFFTSettings,
+    k: usize,
+    index: usize,
+) -> TFr {
+    fs.get_expanded_roots_of_unity_at(index * (fs.get_max_width() / (2 * k)))
+}
+
+impl<TFr: Fr, TG1: G1, TPoly: Poly<TFr>> DasMatrix<TFr, TG1, TPoly> {
+    /// Extend `k * k` row-major data and commit to every row and column of the result
+    pub fn new<
+        TG2: G2,
+        TFFTSettings: FFTSettings<TFr> + FFTFr<TFr> + DAS<TFr>,
+        TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+        TG1Fp: G1Fp,
+        TG1Affine: G1Affine<TG1, TG1Fp>,
+    >(
+        data: &[TFr],
+        k: usize,
+        ks: &TKZGSettings,
+    ) -> Result<Self, String>
+    where
+        TG1: G1Mul<TFr> + G1GetFp<TG1Fp>,
+    {
+        if !k.is_power_of_two() {
+            return Err(String::from("Matrix width must be a power of two"));
+        }
+        if data.len() != k * k {
+            return Err(String::from("Data length must be k * k"));
+        }
+        let fs = ks.get_fft_settings();
+        let width = 2 * k;
+
+        // Extend the original rows, placing them at even row indices
+        let mut cells = Vec::with_capacity(width * width);
+        for row in data.chunks_exact(k) {
+            cells.extend(extend_line(fs, row)?);
+            cells.resize(cells.len() + width, TFr::zero());
+        }
+
+        // Extend every column, filling in the odd rows
+        for column in 0..width {
+            let evens = (0..k)
+                .map(|i| cells[2 * i * width + column].clone())
+                .collect::<Vec<_>>();
+            for (row, value) in extend_line(fs, &evens)?.into_iter().enumerate() {
+                cells[row * width + column] = value;
+            }
+        }
+
+        let row_polys = cells
+            .chunks_exact(width)
+            .map(|row| line_to_poly(fs, row))
+            .collect::<Result<Vec<TPoly>, String>>()?;
+        let column_polys = (0..width)
+            .map(|column| {
+                let line = (0..width)
+                    .map(|row| cells[row * width + column].clone())
+                    .collect::<Vec<_>>();
+                line_to_poly(fs, &line)
+            })
+            .collect::<Result<Vec<TPoly>, String>>()?;
+
+        let commitments = MatrixCommitments {
+            k,
+            rows: row_polys
+                .iter()
+                .map(|p| ks.commit_to_poly(p))
+                .collect::<Result<_, _>>()?,
+            columns: column_polys
+                .iter()
+                .map(|p| ks.commit_to_poly(p))
+                .collect::<Result<_, _>>()?,
+        };
+
+        Ok(Self {
+            k,
+            cells,
+            row_polys,
+            column_polys,
+            commitments,
+        })
+    }
+
+    pub fn commitments(&self) -> &MatrixCommitments<TG1> {
+        &self.commitments
+    }
+
+    /// Cell of the extended matrix
+    pub fn get(&self, row: usize, column: usize) -> Option<&TFr> {
+        let width = 2 * self.k;
+        if row >= width || column >= width {
+            return None;
+        }
+        Some(&self.cells[row * width + column])
+    }
+
+    /// Open the cell at (`row`, `column`) against its row or column commitment
+    pub fn prove_sample<
+        TG2: G2,
+        TFFTSettings: FFTSettings<TFr>,
+        TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+        TG1Fp: G1Fp,
+        TG1Affine: G1Affine<TG1, TG1Fp>,
+    >(
+        &self,
+        row: usize,
+        column: usize,
+        axis: SampleAxis,
+        ks: &TKZGSettings,
+    ) -> Result<SampleProof<TFr, TG1>, String>
+    where
+        TG1: G1Mul<TFr> + G1GetFp<TG1Fp>,
+    {
+        let value = self
+            .get(row, column)
+            .ok_or_else(|| String::from("Sample is out of bounds"))?
+            .clone();
+
+        let fs = ks.get_fft_settings();
+        let proof = match axis {
+            SampleAxis::Row => {
+                ks.compute_proof_single(&self.row_polys[row], &domain_point(fs, self.k, column))?
+            }
+            SampleAxis::Column => {
+                ks.compute_proof_single(&self.column_polys[column], &domain_point(fs, self.k, row))?
+            }
+        };
+
+        Ok(SampleProof {
+            row,
+            column,
+            value,
+            axis,
+            proof,
+        })
+    }
+}
+
+/// Check a sample against the matrix commitments
+pub fn verify_sample<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    commitments: &MatrixCommitments<TG1>,
+    sample: &SampleProof<TFr, TG1>,
+    ks: &TKZGSettings,
+) -> Result<bool, String> {
+    let width = 2 * commitments.k;
+    if sample.row >= width || sample.column >= width {
+        return Err(String::from("Sample is out of bounds"));
+    }
+    if commitments.rows.len() != width || commitments.columns.len() != width {
+        return Err(String::from(
+            "Matrix commitments must cover 2k rows and columns",
+        ));
+    }
+    if !sample.proof.is_inf() && !sample.proof.is_valid() {
+        return Err(String::from("Invalid proof"));
+    }
+
+    let fs = ks.get_fft_settings();
+    let (commitment, x) = match sample.axis {
+        SampleAxis::Row => (
+            &commitments.rows[sample.row],
+            domain_point(fs, commitments.k, sample.column),
+        ),
+        SampleAxis::Column => (
+            &commitments.columns[sample.column],
+            domain_point(fs, commitments.k, sample.row),
+        ),
+    };
+
+    ks.check_proof_single(commitment, &sample.proof, &x, &sample.value)
+}
+
+/// Domain separator of the batched sample verification challenge
+pub const SAMPLE_BATCH_DOMAIN: [u8; 16] = *b"DASMATRIXBATCH__";
+
+/// Stand-alone sample verifier for light clients: holds the matrix commitments, the `2k` domain
+/// points and `[s]₂`, but no trusted setup or matrix data.
+#[derive(Debug, Clone)]
+pub struct SampleVerifier<TFr, TG1, TG2> {
+    commitments: MatrixCommitments<TG1>,
+    domain: Vec<TFr>,
+    s_g2: TG2,
+    /// Whether `s_g2` is that of the development setup, see `dev_setup`
+    insecure_dev_setup: bool,
+}
+
+impl<TFr, TG1, TG2> SampleVerifier<TFr, TG1, TG2>
+where
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + PairingVerify<TG1, TG2>,
+    TG2: G2 + G2Mul<TFr>,
+{
+    pub fn new(
+        commitments: MatrixCommitments<TG1>,
+        domain: Vec<TFr>,
+        s_g2: TG2,
+    ) -> Result<Self, String> {
+        let width = 2 * commitments.k;
+        if commitments.rows.len() != width || commitments.columns.len() != width {
+            return Err(String::from(
+                "Matrix commitments must cover 2k rows and columns",
+            ));
+        }
+        if domain.len() != width {
+            return Err(String::from("Domain must hold 2k points"));
+        }
+
+        Ok(Self {
+            commitments,
+            domain,
+            insecure_dev_setup: is_insecure_dev_tau(&s_g2),
+            s_g2,
+        })
+    }
+
+    /// Keep only what is needed to verify samples of the matrix with the given commitments
+    pub fn from_settings<
+        TFFTSettings: FFTSettings<TFr>,
+        TPoly: Poly<TFr>,
+        TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+        TG1Fp: G1Fp,
+        TG1Affine: G1Affine<TG1, TG1Fp>,
+    >(
+        commitments: MatrixCommitments<TG1>,
+        ks: &TKZGSettings,
+    ) -> Result<Self, String>
+    where
+        TG1: G1GetFp<TG1Fp>,
+    {
+        let fs = ks.get_fft_settings();
+        if 2 * commitments.k > fs.get_max_width() {
+            return Err(String::from("Matrix is larger than the FFT settings"));
+        }
+        let domain = (0..2 * commitments.k)
+            .map(|i| domain_point(fs, commitments.k, i))
+            .collect();
+        let s_g2 = ks
+            .get_g2_secret()
+            .get(1)
+            .ok_or_else(|| String::from("Trusted setup must contain at least two G2 points"))?
+            .clone();
+
+        Self::new(commitments, domain, s_g2)
+    }
+
+    pub fn commitments(&self) -> &MatrixCommitments<TG1> {
+        &self.commitments
+    }
+
+    /// Commitment and evaluation point a sample is checked against, rejecting proofs outside of
+    /// the subgroup
+    fn sample_target(&self, sample: &SampleProof<TFr, TG1>) -> Result<(&TG1, &TFr), String> {
+        let width = self.domain.len();
+        if sample.row >= width || sample.column >= width {
+            return Err(String::from("Sample is out of bounds"));
+        }
+        if !sample.proof.is_inf() && !sample.proof.is_valid() {
+            return Err(String::from("Invalid proof"));
+        }
+
+        Ok(match sample.axis {
+            SampleAxis::Row => (
+                &self.commitments.rows[sample.row],
+                &self.domain[sample.column],
+            ),
+            SampleAxis::Column => (
+                &self.commitments.columns[sample.column],
+                &self.domain[sample.row],
+            ),
+        })
+    }
+
+    pub fn verify_sample(&self, sample: &SampleProof<TFr, TG1>) -> Result<bool, String> {
+        let (commitment, z) = self.sample_target(sample)?;
+        if self.insecure_dev_setup {
+            report_insecure_dev_setup();
+        }
+
+        Ok(TG1::verify(
+            &commitment_minus_value(commitment, &sample.value),
+            &TG2::generator(),
+            &sample.proof,
+            &g2_s_minus_z(&self.s_g2, z),
+        ))
+    }
+
+    /// Verify many samples with a single pairing check, using a random linear combination derived
+    /// from the samples themselves.
+    pub fn verify_samples_batch(&self, samples: &[SampleProof<TFr, TG1>]) -> Result<bool, String> {
+        match samples.len() {
+            0 => return Ok(true),
+            1 => return self.verify_sample(&samples[0]),
+            _ => {}
+        }
+
+        let mut hasher = Sha256::new();
+        hasher.update(SAMPLE_BATCH_DOMAIN);
+        hasher.update((self.commitments.k as u64).to_be_bytes());
+        hasher.update((samples.len() as u64).to_be_bytes());
+        for sample in samples {
+            let (commitment, _) = self.sample_target(sample)?;
+            hasher.update(commitment.to_bytes());
+            hasher.update((sample.row as u64).to_be_bytes());
+            hasher.update((sample.column as u64).to_be_bytes());
+            hasher.update([sample.axis as u8]);
+            hasher.update(sample.value.to_bytes());
+            hasher.update(sample.proof.to_bytes());
+        }
+        let r = hash_to_bls_field(&hasher.finalize().into());
+        let r_powers = compute_powers(&r, samples.len());
+        if self.insecure_dev_setup {
+            report_insecure_dev_setup();
+        }
+
+        // e(sum r^i * proof_i, [s]) == e(sum r^i * (C_i - [y_i] + z_i * proof_i), [1])
+        let mut proof_lincomb = TG1::identity();
+        let mut rhs = TG1::identity();
+        for (sample, r_power) in samples.iter().zip(r_powers.iter()) {
+            let (commitment, z) = self.sample_target(sample)?;
+            proof_lincomb = proof_lincomb.add_or_dbl(&sample.proof.mul(r_power));
+            let term =
+                commitment_minus_value(commitment, &sample.value).add_or_dbl(&sample.proof.mul(z));
+            rhs = rhs.add_or_dbl(&term.mul(r_power));
+        }
+
+        Ok(TG1::verify(
+            &proof_lincomb,
+            &self.s_g2,
+            &rhs,
+            &TG2::generator(),
+        ))
+    }
+}
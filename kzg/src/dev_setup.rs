@@ -0,0 +1,67 @@
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use crate::diagnostics::{emit_warning, Warning};
+use crate::G2;
+
+////////////////////////////// Insecure development setup //////////////////////////////
+//
+// Tests and local networks need a setup without running a ceremony. `generate_insecure_dev_setup`
+// of every backend, and of `KzgBackend`, is `generate_trusted_setup` with the secret derived from
+// `INSECURE_DEV_SETUP_SEED`, which is public, so anybody can forge proofs against it.
+//
+// The setup carries its own tag: its `[s]₂` is `INSECURE_DEV_SETUP_TAU_G2`, in the monomial and
+// Lagrange forms alike, so `KZGSettings::is_insecure_dev_setup` recognizes it however the
+// settings were built, even after a round trip through a setup file.
+//
+// Every verification entry point, the backends' `check_proof_single` and `check_proof_multi`
+// included, checks the settings it is given with `guard_insecure_dev_setup`. Verifiers built
+// from settings, such as `KzgVerifier` or `SampleVerifier`, check `[s]₂` once when built and
+// report each verification. On a development setup, they emit `Warning::InsecureDevSetup` and
+// raise the flag read by `insecure_dev_setup_used`, which a node can check at startup or export as
+// a health metric, so a test setup does not silently ship to production. The check costs a
+// comparison of one G2 point.
+
+/// Public seed of the development setup secret
+pub const INSECURE_DEV_SETUP_SEED: [u8; 32] = *b"INSECURE DEVELOPMENT SETUP SEED!";
+
+/// Compressed `[s]₂` of the development setup
+pub const INSECURE_DEV_SETUP_TAU_G2: [u8; 96] = [
+    0x94, 0xea, 0x34, 0x88, 0x95, 0x6e, 0xe4, 0x37, 0x07, 0x3a, 0xec, 0x76, 0xff, 0xe6, 0x61, 0x65,
+    0xbd, 0x4c, 0x22, 0xd4, 0xdf, 0x42, 0x48, 0x4e, 0x47, 0xb7, 0x0b, 0xc2, 0x6a, 0x89, 0x37, 0xb6,
+    0xd9, 0x33, 0x0e, 0x19, 0x1b, 0x9a, 0x92, 0xb7, 0xb3, 0xb8, 0xa6, 0x25, 0xef, 0x7c, 0x99, 0x5a,
+    0x15, 0xa2, 0xe6, 0x12, 0x83, 0xae, 0x7f, 0x0c, 0xeb, 0x16, 0x62, 0x97, 0x27, 0xd9, 0x30, 0x0d,
+    0x52, 0x50, 0x1b, 0xdb, 0x6c, 0xd0, 0x75, 0xcd, 0x01, 0x87, 0xad, 0xbf, 0x89, 0xef, 0xd4, 0x19,
+    0xbc, 0x36, 0x23, 0x9e, 0x64, 0xbe, 0x85, 0xe3, 0x74, 0xeb, 0xaf, 0xba, 0x15, 0x14, 0x83, 0x52,
+];
+
+static INSECURE_DEV_SETUP_USED: AtomicBool = AtomicBool::new(false);
+
+/// Whether `s_g2`, the `[s]₂` point of a setup, is that of the development setup
+pub fn is_insecure_dev_tau<TG2: G2>(s_g2: &TG2) -> bool {
+    s_g2.to_bytes() == INSECURE_DEV_SETUP_TAU_G2
+}
+
+/// Whether the G2 points `secret_g2` are those of the development setup
+pub fn is_insecure_dev_setup<TG2: G2>(secret_g2: &[TG2]) -> bool {
+    secret_g2.get(1).map_or(false, is_insecure_dev_tau)
+}
+
+/// Warn and raise the flag of `insecure_dev_setup_used`, for verifiers that found the development
+/// setup with `is_insecure_dev_tau` when they were built
+pub fn report_insecure_dev_setup() {
+    INSECURE_DEV_SETUP_USED.store(true, Ordering::Relaxed);
+    emit_warning(Warning::InsecureDevSetup);
+}
+
+/// Warn and raise the flag of `insecure_dev_setup_used` when `secret_g2` belongs to the
+/// development setup
+pub fn guard_insecure_dev_setup<TG2: G2>(secret_g2: &[TG2]) {
+    if is_insecure_dev_setup(secret_g2) {
+        report_insecure_dev_setup();
+    }
+}
+
+/// Whether a verification ran against the development setup since the process started
+pub fn insecure_dev_setup_used() -> bool {
+    INSECURE_DEV_SETUP_USED.load(Ordering::Relaxed)
+}
@@ -0,0 +1,215 @@
+use core::fmt;
+use core::mem::size_of_val;
+use core::sync::atomic::{AtomicPtr, Ordering};
+
+use crate::{FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1Mul, KZGSettings, Poly, G1, G2};
+
+////////////////////////////// Diagnostics //////////////////////////////
+//
+// Some code paths silently fall back to a slower implementation (no precomputation table,
+// sequential MSM in a parallel build, non-tuned window size). The results are still correct, so
+// these are not errors, but operators may want to know why a node is slower than expected.
+// Install a handler with `set_warning_handler` to receive them.
+//
+// Verifying against the insecure development setup is reported the same way, see `dev_setup`.
+//
+// Measurements that operators may want to export (e.g. memory held by the trusted setup) are
+// reported the same way, through a handler installed with `set_metrics_handler`.
+
+/// Degraded-performance or unsafe condition reported by the library
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Warning {
+    /// No precomputation table is available for this build, plain Pippenger is used instead
+    PrecomputationUnavailable { npoints: usize },
+    /// Building the precomputation table failed (e.g. allocation), plain Pippenger is used instead
+    PrecomputationFailed { npoints: usize },
+    /// Parallel build, but the precomputation table is multiplied sequentially
+    ParallelFallbackToSequential { npoints: usize, ncpus: usize },
+    /// No tuned window size for this number of points, the generic Pippenger window is used
+    SuboptimalWindow { npoints: usize, window: usize },
+    /// The process is close to its memory limit, no precomputation table is built
+    PrecomputationSkipped { npoints: usize },
+    /// Memory usage crossed the threshold of a `MemoryMonitor`, which released its caches
+    MemoryPressure {
+        used: usize,
+        limit: usize,
+        released: usize,
+    },
+    /// Verification against the insecure development setup, whose secret is public
+    InsecureDevSetup,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Warning::PrecomputationUnavailable { npoints } => write!(
+                f,
+                "precomputation table unavailable for {} points, falling back to Pippenger",
+                npoints
+            ),
+            Warning::PrecomputationFailed { npoints } => write!(
+                f,
+                "failed to build precomputation table for {} points, falling back to Pippenger",
+                npoints
+            ),
+            Warning::ParallelFallbackToSequential { npoints, ncpus } => write!(
+                f,
+                "parallel precomputation requested, but {} points on {} cpus run sequentially",
+                npoints, ncpus
+            ),
+            Warning::SuboptimalWindow { npoints, window } => write!(
+                f,
+                "no tuned window for {} points, using default window of {} bits",
+                npoints, window
+            ),
+            Warning::PrecomputationSkipped { npoints } => write!(
+                f,
+                "memory pressure, no precomputation table built for {} points",
+                npoints
+            ),
+            Warning::MemoryPressure {
+                used,
+                limit,
+                released,
+            } => write!(
+                f,
+                "memory pressure, {} of {} bytes used, released {} bytes of caches",
+                used, limit, released
+            ),
+            Warning::InsecureDevSetup => write!(
+                f,
+                "verifying against the insecure development setup, anybody can forge proofs"
+            ),
+        }
+    }
+}
+
+/// Callback receiving warnings
+pub type WarningHandler = fn(&Warning);
+
+static WARNING_HANDLER: AtomicPtr<()> = AtomicPtr::new(core::ptr::null_mut());
+
+/// Install a handler for warnings, or remove it with `None`. Warnings are dropped when no handler
+/// is installed.
+pub fn set_warning_handler(handler: Option<WarningHandler>) {
+    let ptr = match handler {
+        Some(handler) => handler as *mut (),
+        None => core::ptr::null_mut(),
+    };
+    WARNING_HANDLER.store(ptr, Ordering::Release);
+}
+
+/// Report a warning to the installed handler, if any
+pub fn emit_warning(warning: Warning) {
+    let ptr = WARNING_HANDLER.load(Ordering::Acquire);
+    if ptr.is_null() {
+        return;
+    }
+
+    // SAFETY: only `set_warning_handler` stores non-null values, and those are `WarningHandler`s
+    let handler = unsafe { core::mem::transmute::<*mut (), WarningHandler>(ptr) };
+    handler(&warning);
+}
+
+/// Long-lived structure whose memory usage is reported
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryComponent {
+    /// G1 and G2 points of the trusted setup
+    TrustedSetup,
+    /// Roots of unity held by the FFT settings
+    FftSettings,
+    /// Fixed-base MSM precomputation table
+    PrecomputationTable,
+}
+
+/// Measurement reported by the library
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    /// Bytes held by a component
+    MemoryUsage {
+        component: MemoryComponent,
+        bytes: usize,
+    },
+}
+
+impl fmt::Display for Metric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Metric::MemoryUsage { component, bytes } => {
+                write!(f, "{:?} holds {} bytes", component, bytes)
+            }
+        }
+    }
+}
+
+/// Callback receiving metrics
+pub type MetricsHandler = fn(&Metric);
+
+static METRICS_HANDLER: AtomicPtr<()> = AtomicPtr::new(core::ptr::null_mut());
+
+/// Install a handler for metrics, or remove it with `None`. Metrics are dropped when no handler
+/// is installed.
+pub fn set_metrics_handler(handler: Option<MetricsHandler>) {
+    let ptr = match handler {
+        Some(handler) => handler as *mut (),
+        None => core::ptr::null_mut(),
+    };
+    METRICS_HANDLER.store(ptr, Ordering::Release);
+}
+
+/// Report a metric to the installed handler, if any
+pub fn emit_metric(metric: Metric) {
+    let ptr = METRICS_HANDLER.load(Ordering::Acquire);
+    if ptr.is_null() {
+        return;
+    }
+
+    // SAFETY: only `set_metrics_handler` stores non-null values, and those are `MetricsHandler`s
+    let handler = unsafe { core::mem::transmute::<*mut (), MetricsHandler>(ptr) };
+    handler(&metric);
+}
+
+/// Bytes held by the G1 and G2 points of the trusted setup
+pub fn trusted_setup_memory_usage<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    ks: &TKZGSettings,
+) -> usize {
+    size_of_val(ks.get_g1_secret()) + size_of_val(ks.get_g2_secret())
+}
+
+/// Emit a `Metric::MemoryUsage` for every component held by the settings
+pub fn report_memory_usage<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    ks: &TKZGSettings,
+) {
+    emit_metric(Metric::MemoryUsage {
+        component: MemoryComponent::TrustedSetup,
+        bytes: trusted_setup_memory_usage(ks),
+    });
+    emit_metric(Metric::MemoryUsage {
+        component: MemoryComponent::FftSettings,
+        bytes: ks.get_fft_settings().memory_usage(),
+    });
+    emit_metric(Metric::MemoryUsage {
+        component: MemoryComponent::PrecomputationTable,
+        bytes: ks
+            .get_precomputation()
+            .map_or(0, |table| table.memory_usage()),
+    });
+}
@@ -4,6 +4,7 @@ extern crate alloc;
 use alloc::collections::BTreeMap;
 use alloc::format;
 use alloc::string::String;
+#[cfg(any(feature = "prove", feature = "verify"))]
 use alloc::string::ToString;
 use alloc::sync::Arc;
 use alloc::vec;
@@ -15,12 +16,22 @@ use core::hash::Hash;
 use core::hash::Hasher;
 use sha2::{Digest, Sha256};
 use siphasher::sip::SipHasher;
+use spin::RwLock;
 
-use crate::common_utils::reverse_bit_order;
+#[cfg(feature = "verify")]
+use crate::cancel::{check_cancelled, CancelToken};
+use crate::common_utils::{reverse_bit_order, try_vec, try_vec_with_capacity};
+#[cfg(feature = "verify")]
+use crate::dev_setup::guard_insecure_dev_setup;
 use crate::msm::precompute::PrecomputationTable;
+use crate::progress::{report_progress, ProgressStage};
+use crate::sha256_batch::sha256_many;
+#[cfg(any(feature = "prove", feature = "verify"))]
+use crate::sha256_batch::SHA256_LANES;
 use crate::G1Affine;
 use crate::G1Fp;
 use crate::G1GetFp;
+#[cfg(any(feature = "commit", feature = "prove", feature = "verify"))]
 use crate::G1LinComb;
 use crate::{FFTSettings, Fr, G1Mul, KZGSettings, PairingVerify, Poly, G1, G2};
 
@@ -32,18 +43,20 @@ use rayon::prelude::*;
 pub const FIELD_ELEMENTS_PER_BLOB: usize = 4096;
 
 pub const BYTES_PER_G1: usize = 48;
+pub const BYTES_PER_G1_UNCOMPRESSED: usize = 96;
 pub const BYTES_PER_G2: usize = 96;
 pub const BYTES_PER_BLOB: usize = BYTES_PER_FIELD_ELEMENT * FIELD_ELEMENTS_PER_BLOB;
 pub const BYTES_PER_FIELD_ELEMENT: usize = 32;
 pub const BYTES_PER_PROOF: usize = 48;
 pub const BYTES_PER_COMMITMENT: usize = 48;
+/// Data bytes packed into a field element by `bytes_to_blobs`, so that it is always canonical
+pub const USABLE_BYTES_PER_FIELD_ELEMENT: usize = 31;
+pub const USABLE_BYTES_PER_BLOB: usize = USABLE_BYTES_PER_FIELD_ELEMENT * FIELD_ELEMENTS_PER_BLOB;
 
 pub const TRUSTED_SETUP_PATH: &str = "src/trusted_setup.txt";
 
-// Currently, we only support fixed amount of G1 and G2 points contained in trusted setups.
-// Issue arises when a binding using the C API loads different G1 point quantities each time.
-pub static mut TRUSTED_SETUP_NUM_G1_POINTS: usize = 0;
-
+// Currently, we only support fixed amount of G1 and G2 points contained in trusted setups, the
+// settings of the C API hold `FIELD_ELEMENTS_PER_BLOB` G1 points.
 pub const TRUSTED_SETUP_NUM_G2_POINTS: usize = 65;
 
 pub const CHALLENGE_INPUT_SIZE: usize =
@@ -53,10 +66,50 @@ pub const FIAT_SHAMIR_PROTOCOL_DOMAIN: [u8; 16] = [
     70, 83, 66, 76, 79, 66, 86, 69, 82, 73, 70, 89, 95, 86, 49, 95,
 ]; // "FSBLOBVERIFY_V1_"
 
+/// First byte of the versioned hash of a blob commitment
+pub const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
+
 pub const RANDOM_CHALLENGE_KZG_BATCH_DOMAIN: [u8; 16] = [
     82, 67, 75, 90, 71, 66, 65, 84, 67, 72, 95, 95, 95, 86, 49, 95,
 ]; // "RCKZGBATCH___V1_"
 
+/// Caller-chosen domain separation for the batch verification challenge.
+///
+/// Protocols embedding blob proofs should verify batches under their own tag, so that a batch
+/// transcript of one protocol is never a valid transcript of another. The tag and the context are
+/// length-prefixed, so distinct pairs never hash the same input.
+#[cfg(feature = "verify")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChallengeDomain<'a> {
+    /// Protocol identifier, must not be empty
+    pub tag: &'a [u8],
+    /// Additional data the batch is bound to, e.g. a slot or a message id
+    pub context: &'a [u8],
+}
+
+#[cfg(feature = "verify")]
+impl<'a> ChallengeDomain<'a> {
+    pub fn new(tag: &'a [u8], context: &'a [u8]) -> Result<Self, String> {
+        if tag.is_empty() {
+            return Err(String::from("Challenge domain tag must not be empty"));
+        }
+
+        Ok(Self { tag, context })
+    }
+
+    fn encoded_len(&self) -> usize {
+        16 + self.tag.len() + self.context.len()
+    }
+
+    fn encode(&self, out: &mut [u8]) {
+        let tag_end = 8 + self.tag.len();
+        bytes_of_uint64(&mut out[..8], self.tag.len() as u64);
+        out[8..tag_end].copy_from_slice(self.tag);
+        bytes_of_uint64(&mut out[tag_end..tag_end + 8], self.context.len() as u64);
+        out[tag_end + 8..].copy_from_slice(self.context);
+    }
+}
+
 ////////////////////////////// C API for EIP-4844 //////////////////////////////
 
 pub type C_KZG_RET = c_uint;
@@ -104,6 +157,13 @@ pub struct CKZGSettings {
     pub g2_values: *mut blst_p2,
 }
 
+type PrecomputationTables<TFr, TG1, TG1Fp, TG1Affine> =
+    BTreeMap<u64, Arc<PrecomputationTable<TFr, TG1, TG1Fp, TG1Affine>>>;
+
+/// Precomputation tables of the settings handed out through the C API, keyed by their G1 points.
+///
+/// Backends keep one manager in a `static`. It is locked internally, so settings can be loaded,
+/// used and freed concurrently from multiple threads.
 pub struct PrecomputationTableManager<TFr, TG1, TG1Fp, TG1Affine>
 where
     TFr: Fr,
@@ -111,7 +171,7 @@ where
     TG1Fp: G1Fp,
     TG1Affine: G1Affine<TG1, TG1Fp>,
 {
-    tables: BTreeMap<u64, Arc<PrecomputationTable<TFr, TG1, TG1Fp, TG1Affine>>>,
+    tables: RwLock<PrecomputationTables<TFr, TG1, TG1Fp, TG1Affine>>,
 }
 
 impl<TFr, TG1, TG1Fp, TG1Affine> PrecomputationTableManager<TFr, TG1, TG1Fp, TG1Affine>
@@ -123,30 +183,31 @@ where
 {
     pub const fn new() -> Self {
         Self {
-            tables: BTreeMap::new(),
+            tables: RwLock::new(BTreeMap::new()),
         }
     }
 
     pub fn save_precomputation(
-        &mut self,
+        &self,
         precomputation: Option<Arc<PrecomputationTable<TFr, TG1, TG1Fp, TG1Affine>>>,
         c_settings: &CKZGSettings,
     ) {
         if let Some(precomputation) = precomputation {
             self.tables
+                .write()
                 .insert(Self::get_key(c_settings), precomputation);
         }
     }
 
-    pub fn remove_precomputation(&mut self, c_settings: &CKZGSettings) {
-        self.tables.remove(&Self::get_key(c_settings));
+    pub fn remove_precomputation(&self, c_settings: &CKZGSettings) {
+        self.tables.write().remove(&Self::get_key(c_settings));
     }
 
     pub fn get_precomputation(
         &self,
         c_settings: &CKZGSettings,
     ) -> Option<Arc<PrecomputationTable<TFr, TG1, TG1Fp, TG1Affine>>> {
-        self.tables.get(&Self::get_key(c_settings)).cloned()
+        self.tables.read().get(&Self::get_key(c_settings)).cloned()
     }
 
     fn get_key(settings: &CKZGSettings) -> u64 {
@@ -159,6 +220,7 @@ where
 
 ////////////////////////////// Utility functions for EIP-4844 //////////////////////////////
 
+#[cfg(feature = "io")]
 pub fn load_trusted_setup_string(contents: &str) -> Result<(Vec<u8>, Vec<u8>), String> {
     let mut offset = 0;
 
@@ -181,7 +243,6 @@ pub fn load_trusted_setup_string(contents: &str) -> Result<(Vec<u8>, Vec<u8>), S
 
     let g1_point_count = scan_number(&mut offset, contents)?;
 
-    // FIXME: must be TRUSTED_SETUP_NUM_G1_POINTS
     if g1_point_count != FIELD_ELEMENTS_PER_BLOB {
         return Err(String::from(TRUSTED_SETUP_ERROR));
     }
@@ -192,8 +253,8 @@ pub fn load_trusted_setup_string(contents: &str) -> Result<(Vec<u8>, Vec<u8>), S
         return Err(String::from(TRUSTED_SETUP_ERROR));
     }
 
-    let mut g1_bytes = vec![0u8; g1_point_count * BYTES_PER_G1];
-    let mut g2_bytes = vec![0u8; g2_point_count * BYTES_PER_G2];
+    let mut g1_bytes = try_vec(0u8, g1_point_count * BYTES_PER_G1)?;
+    let mut g2_bytes = try_vec(0u8, g2_point_count * BYTES_PER_G2)?;
 
     #[inline(always)]
     fn scan_hex_byte(offset: &mut usize, contents: &str) -> Result<u8, String> {
@@ -233,6 +294,65 @@ pub fn load_trusted_setup_string(contents: &str) -> Result<(Vec<u8>, Vec<u8>), S
     Ok((g1_bytes, g2_bytes))
 }
 
+/// Parse the JSON trusted setup published by the KZG ceremony, e.g. `trusted_setup_4096.json`,
+/// into the G1 points in Lagrange form and the G2 points, in the format of
+/// `load_trusted_setup_string`
+///
+/// Only the `g1_lagrange` and `g2_monomial` arrays of `0x`-prefixed hex strings are read, other
+/// fields such as `g1_monomial` are ignored.
+#[cfg(feature = "io")]
+pub fn load_trusted_setup_json_string(contents: &str) -> Result<(Vec<u8>, Vec<u8>), String> {
+    let g1_bytes = json_hex_array(contents, "g1_lagrange", BYTES_PER_G1)?;
+    let g2_bytes = json_hex_array(contents, "g2_monomial", BYTES_PER_G2)?;
+
+    Ok((g1_bytes, g2_bytes))
+}
+
+/// Concatenated bytes of the JSON array `key` of hex strings of `size` bytes each
+#[cfg(feature = "io")]
+fn json_hex_array(contents: &str, key: &str, size: usize) -> Result<Vec<u8>, String> {
+    let invalid = || format!("Invalid {} array in trusted setup JSON", key);
+    let quoted = format!("\"{}\"", key);
+
+    let start = contents
+        .find(&quoted)
+        .ok_or_else(|| format!("Trusted setup JSON has no {} field", key))?
+        + quoted.len();
+    let rest = contents[start..]
+        .trim_start()
+        .strip_prefix(':')
+        .ok_or_else(invalid)?
+        .trim_start()
+        .strip_prefix('[')
+        .ok_or_else(invalid)?;
+    let end = rest.find(']').ok_or_else(invalid)?;
+
+    let mut bytes = Vec::new();
+    for (i, item) in rest[..end].split(',').enumerate() {
+        let item = item.trim();
+        if i == 0 && item.is_empty() {
+            break;
+        }
+        let hex = item
+            .strip_prefix('"')
+            .and_then(|item| item.strip_suffix('"'))
+            .ok_or_else(invalid)?;
+        let hex = hex.strip_prefix("0x").unwrap_or(hex);
+        if hex.len() != 2 * size {
+            return Err(format!("Invalid length of {} point {}", key, i));
+        }
+        for pair in hex.as_bytes().chunks(2) {
+            let byte = core::str::from_utf8(pair)
+                .ok()
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                .ok_or_else(|| format!("Invalid hex in {} point {}", key, i))?;
+            bytes.push(byte);
+        }
+    }
+
+    Ok(bytes)
+}
+
 pub fn bytes_of_uint64(out: &mut [u8], mut n: u64) {
     for byte in out.iter_mut().rev().take(8) {
         *byte = (n & 0xff) as u8;
@@ -244,6 +364,28 @@ pub fn hash(x: &[u8]) -> [u8; 32] {
     Sha256::digest(x).into()
 }
 
+/// Versioned hash of a blob commitment, `VERSIONED_HASH_VERSION_KZG || sha256(commitment)[1..]`
+pub fn kzg_to_versioned_hash<TG1: G1>(commitment: &TG1) -> [u8; 32] {
+    let mut versioned_hash = hash(&commitment.to_bytes());
+    versioned_hash[0] = VERSIONED_HASH_VERSION_KZG;
+    versioned_hash
+}
+
+/// Versioned hashes of `commitments`, computed `SHA256_LANES` at a time
+pub fn kzg_to_versioned_hashes<TG1: G1>(commitments: &[TG1]) -> Vec<[u8; 32]> {
+    let bytes = commitments.iter().map(TG1::to_bytes).collect::<Vec<_>>();
+    let inputs = bytes
+        .iter()
+        .map(|bytes| bytes.as_slice())
+        .collect::<Vec<_>>();
+
+    let mut versioned_hashes = sha256_many(&inputs);
+    for versioned_hash in versioned_hashes.iter_mut() {
+        versioned_hash[0] = VERSIONED_HASH_VERSION_KZG;
+    }
+    versioned_hashes
+}
+
 #[macro_export]
 macro_rules! cfg_into_iter {
     ($e: expr) => {{
@@ -259,6 +401,7 @@ macro_rules! cfg_into_iter {
 
 ////////////////////////////// Trait based implementations of functions for EIP-4844 //////////////////////////////
 
+#[cfg(feature = "commit")]
 fn poly_to_kzg_commitment<
     TFr: Fr,
     TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + G1LinComb<TFr, TG1Fp, TG1Affine>,
@@ -276,10 +419,11 @@ fn poly_to_kzg_commitment<
         s.get_g1_secret(),
         p.get_coeffs(),
         FIELD_ELEMENTS_PER_BLOB,
-        s.get_precomputation(),
+        s.get_precomputation().as_deref(),
     )
 }
 
+#[cfg(feature = "commit")]
 pub fn blob_to_kzg_commitment_rust<
     TFr: Fr,
     TG1: G1 + G1Mul<TFr> + G1LinComb<TFr, TG1Fp, TG1Affine> + G1GetFp<TG1Fp>,
@@ -310,23 +454,52 @@ pub fn compute_powers<TFr: Fr>(base: &TFr, num_powers: usize) -> Vec<TFr> {
     powers
 }
 
+/// Compute the powers of the random challenge combining a batch of proofs, under a caller-chosen
+/// domain. Without a domain, this is the challenge of `verify_blob_kzg_proof_batch_rust`.
+#[cfg(feature = "verify")]
+pub fn compute_batch_challenge_powers<TG1: G1, TFr: Fr>(
+    domain: &ChallengeDomain,
+    commitments_g1: &[TG1],
+    zs_fr: &[TFr],
+    ys_fr: &[TFr],
+    proofs_g1: &[TG1],
+) -> Result<Vec<TFr>, String> {
+    if domain.tag.is_empty() {
+        return Err(String::from("Challenge domain tag must not be empty"));
+    }
+    let n = commitments_g1.len();
+    if zs_fr.len() != n || ys_fr.len() != n || proofs_g1.len() != n {
+        return Err(String::from("Invalid amount of arguments"));
+    }
+
+    compute_r_powers(Some(domain), commitments_g1, zs_fr, ys_fr, proofs_g1)
+}
+
+#[cfg(feature = "verify")]
 fn compute_r_powers<TG1: G1, TFr: Fr>(
+    domain: Option<&ChallengeDomain>,
     commitments_g1: &[TG1],
     zs_fr: &[TFr],
     ys_fr: &[TFr],
     proofs_g1: &[TG1],
 ) -> Result<Vec<TFr>, String> {
     let n = commitments_g1.len();
-    let input_size =
-        32 + n * (BYTES_PER_COMMITMENT + 2 * BYTES_PER_FIELD_ELEMENT + BYTES_PER_PROOF);
+    let domain_size = domain.map_or(0, |domain| domain.encoded_len());
+    let input_size = 32
+        + domain_size
+        + n * (BYTES_PER_COMMITMENT + 2 * BYTES_PER_FIELD_ELEMENT + BYTES_PER_PROOF);
 
-    let mut bytes: Vec<u8> = vec![0; input_size];
+    let mut bytes: Vec<u8> = try_vec(0, input_size)?;
 
     // Copy domain separator
     bytes[..16].copy_from_slice(&RANDOM_CHALLENGE_KZG_BATCH_DOMAIN);
     bytes_of_uint64(&mut bytes[16..24], FIELD_ELEMENTS_PER_BLOB as u64);
     bytes_of_uint64(&mut bytes[24..32], n as u64);
-    let mut offset = 32;
+    // The caller's domain follows, so the default transcript stays compatible with c-kzg
+    if let Some(domain) = domain {
+        domain.encode(&mut bytes[32..32 + domain_size]);
+    }
+    let mut offset = 32 + domain_size;
 
     for i in 0..n {
         // Copy commitment
@@ -362,6 +535,7 @@ fn compute_r_powers<TG1: G1, TFr: Fr>(
     Ok(compute_powers(&r, n))
 }
 
+#[cfg(feature = "verify")]
 fn verify_kzg_proof_batch<
     TFr: Fr,
     TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + PairingVerify<TG1, TG2> + G1LinComb<TFr, TG1Fp, TG1Affine>,
@@ -376,14 +550,15 @@ fn verify_kzg_proof_batch<
     zs_fr: &[TFr],
     ys_fr: &[TFr],
     proofs_g1: &[TG1],
+    domain: Option<&ChallengeDomain>,
     ts: &TKZGSettings,
 ) -> Result<bool, String> {
     let n = commitments_g1.len();
-    let mut c_minus_y: Vec<TG1> = Vec::with_capacity(n);
-    let mut r_times_z: Vec<TFr> = Vec::with_capacity(n);
+    let mut c_minus_y: Vec<TG1> = try_vec_with_capacity(n)?;
+    let mut r_times_z: Vec<TFr> = try_vec_with_capacity(n)?;
 
     // Compute the random lincomb challenges
-    let r_powers = compute_r_powers(commitments_g1, zs_fr, ys_fr, proofs_g1)?;
+    let r_powers = compute_r_powers(domain, commitments_g1, zs_fr, ys_fr, proofs_g1)?;
 
     // Compute \sum r^i * Proof_i
     let proof_lincomb = TG1::g1_lincomb(proofs_g1, &r_powers, n, None);
@@ -414,6 +589,43 @@ fn verify_kzg_proof_batch<
     ))
 }
 
+/// Verify the openings `commitments[i]` to `values[i]` at `points[i]` of any polynomials with a
+/// single pairing check, combining the proofs with a random linear combination derived from all
+/// of the inputs
+#[cfg(feature = "verify")]
+pub fn verify_batch_openings<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + PairingVerify<TG1, TG2> + G1LinComb<TFr, TG1Fp, TG1Affine>,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    commitments: &[TG1],
+    points: &[TFr],
+    values: &[TFr],
+    proofs: &[TG1],
+    ts: &TKZGSettings,
+) -> Result<bool, String> {
+    let n = commitments.len();
+    if points.len() != n || values.len() != n || proofs.len() != n {
+        return Err("Invalid amount of arguments".to_string());
+    }
+    validate_batched_input(commitments, proofs)?;
+
+    match n {
+        0 => Ok(true),
+        1 => verify_kzg_proof_rust(&commitments[0], &points[0], &values[0], &proofs[0], ts),
+        _ => {
+            guard_insecure_dev_setup(ts.get_g2_secret());
+            verify_kzg_proof_batch(commitments, points, values, proofs, None, ts)
+        }
+    }
+}
+
+#[cfg(feature = "prove")]
 pub fn compute_kzg_proof_rust<
     TFr: Fr + Copy,
     TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + G1LinComb<TFr, TG1Fp, TG1Affine>,
@@ -428,19 +640,39 @@ pub fn compute_kzg_proof_rust<
     z: &TFr,
     s: &TKZGSettings,
 ) -> Result<(TG1, TFr), String> {
-    let polynomial = blob_to_polynomial(blob)?;
-    let y = evaluate_polynomial_in_evaluation_form(&polynomial, z, s)?;
+    compute_kzg_proof_from_evaluations(blob, z, s)
+}
+
+/// Proof and value of the polynomial given by its evaluations over the blob domain at `z`, with
+/// the quotient computed in evaluation form
+#[cfg(feature = "prove")]
+pub(crate) fn compute_kzg_proof_from_evaluations<
+    TFr: Fr + Copy,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + G1LinComb<TFr, TG1Fp, TG1Affine>,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    evaluations: &[TFr],
+    z: &TFr,
+    s: &TKZGSettings,
+) -> Result<(TG1, TFr), String> {
+    if evaluations.len() != FIELD_ELEMENTS_PER_BLOB {
+        return Err(String::from("Blob length must be FIELD_ELEMENTS_PER_BLOB"));
+    }
+    let roots_of_unity = s.get_fft_settings().get_roots_of_unity();
+    let y = evaluate_evaluations(evaluations, z, roots_of_unity)?;
 
     let mut tmp: TFr;
 
     let mut m: usize = 0;
-    let mut q: TPoly = TPoly::new(FIELD_ELEMENTS_PER_BLOB);
-
-    let mut inverses_in: Vec<TFr> = vec![TFr::default(); FIELD_ELEMENTS_PER_BLOB];
-    let mut inverses: Vec<TFr> = vec![TFr::default(); FIELD_ELEMENTS_PER_BLOB];
+    let mut q: Vec<TFr> = try_vec(TFr::zero(), FIELD_ELEMENTS_PER_BLOB)?;
 
-    let roots_of_unity = s.get_fft_settings().get_roots_of_unity();
-    let poly_coeffs = polynomial.get_coeffs();
+    let mut inverses_in: Vec<TFr> = try_vec(TFr::default(), FIELD_ELEMENTS_PER_BLOB)?;
+    let mut inverses: Vec<TFr> = try_vec(TFr::default(), FIELD_ELEMENTS_PER_BLOB)?;
 
     for i in 0..FIELD_ELEMENTS_PER_BLOB {
         if z.equals(&roots_of_unity[i]) {
@@ -450,20 +682,20 @@ pub fn compute_kzg_proof_rust<
             continue;
         }
         // (p_i - y) / (ω_i - z)
-        q.set_coeff_at(i, &poly_coeffs[i].sub(&y));
+        q[i] = evaluations[i].sub(&y);
         inverses_in[i] = roots_of_unity[i].sub(z);
     }
 
     fr_batch_inv(&mut inverses, &inverses_in, FIELD_ELEMENTS_PER_BLOB)?;
 
     for (i, inverse) in inverses.iter().enumerate().take(FIELD_ELEMENTS_PER_BLOB) {
-        q.set_coeff_at(i, &q.get_coeff_at(i).mul(inverse));
+        q[i] = q[i].mul(inverse);
     }
 
     if m != 0 {
         // ω_{m-1} == z
         m -= 1;
-        q.set_coeff_at(m, &TFr::zero());
+        q[m] = TFr::zero();
         for i in 0..FIELD_ELEMENTS_PER_BLOB {
             if i == m {
                 continue;
@@ -480,23 +712,24 @@ pub fn compute_kzg_proof_rust<
                 continue;
             }
             // Build numerator: ω_i * (p_i - y)
-            tmp = poly_coeffs[i].sub(&y);
+            tmp = evaluations[i].sub(&y);
             tmp = tmp.mul(&roots_of_unity[i]);
             // Do the division: (p_i - y) * ω_i / (z * (z - ω_i))
             tmp = tmp.mul(&inverses[i]);
-            q.set_coeff_at(m, &q.get_coeff_at(m).add(&tmp))
+            q[m] = q[m].add(&tmp);
         }
     }
 
     let proof = TG1::g1_lincomb(
         s.get_g1_secret(),
-        q.get_coeffs(),
+        &q,
         FIELD_ELEMENTS_PER_BLOB,
-        s.get_precomputation(),
+        s.get_precomputation().as_deref(),
     );
     Ok((proof, y))
 }
 
+#[cfg(feature = "prove")]
 pub fn compute_blob_kzg_proof_rust<
     TFr: Fr + Copy,
     TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + G1LinComb<TFr, TG1Fp, TG1Affine>,
@@ -515,11 +748,12 @@ pub fn compute_blob_kzg_proof_rust<
         return Err("Invalid commitment".to_string());
     }
 
-    let evaluation_challenge_fr = compute_challenge(blob, commitment);
+    let evaluation_challenge_fr = compute_challenge(blob, commitment)?;
     let (proof, _) = compute_kzg_proof_rust(blob, &evaluation_challenge_fr, ts)?;
     Ok(proof)
 }
 
+#[cfg(feature = "verify")]
 pub fn verify_kzg_proof_rust<
     TFr: Fr,
     TG1: G1 + G1GetFp<TG1Fp> + G1Mul<TFr>,
@@ -546,6 +780,7 @@ pub fn verify_kzg_proof_rust<
     s.check_proof_single(commitment, proof, z, y)
 }
 
+#[cfg(feature = "verify")]
 pub fn verify_blob_kzg_proof_rust<
     TFr: Fr + Copy,
     TG1: G1 + G1GetFp<TG1Fp> + G1Mul<TFr>,
@@ -569,11 +804,46 @@ pub fn verify_blob_kzg_proof_rust<
     }
 
     let polynomial = blob_to_polynomial(blob)?;
-    let evaluation_challenge_fr = compute_challenge(blob, commitment_g1);
+    let evaluation_challenge_fr = compute_challenge(blob, commitment_g1)?;
     let y_fr = evaluate_polynomial_in_evaluation_form(&polynomial, &evaluation_challenge_fr, ts)?;
     verify_kzg_proof_rust(commitment_g1, &evaluation_challenge_fr, &y_fr, proof_g1, ts)
 }
 
+/// Same as `verify_blob_kzg_proof_rust`, at a challenge computed beforehand with
+/// `compute_challenge`.
+///
+/// The proof is only sound at the challenge of this blob and commitment: passing any other point
+/// lets a prover choose where the blob is opened.
+#[cfg(feature = "verify")]
+pub fn verify_with_precomputed_challenge_rust<
+    TFr: Fr + Copy,
+    TG1: G1 + G1GetFp<TG1Fp> + G1Mul<TFr>,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    blob: &[TFr],
+    commitment_g1: &TG1,
+    proof_g1: &TG1,
+    evaluation_challenge_fr: &TFr,
+    ts: &TKZGSettings,
+) -> Result<bool, String> {
+    if !commitment_g1.is_inf() && !commitment_g1.is_valid() {
+        return Err("Invalid commitment".to_string());
+    }
+    if !proof_g1.is_inf() && !proof_g1.is_valid() {
+        return Err("Invalid proof".to_string());
+    }
+
+    let polynomial = blob_to_polynomial(blob)?;
+    let y_fr = evaluate_polynomial_in_evaluation_form(&polynomial, evaluation_challenge_fr, ts)?;
+    verify_kzg_proof_rust(commitment_g1, evaluation_challenge_fr, &y_fr, proof_g1, ts)
+}
+
+#[cfg(feature = "verify")]
 fn compute_challenges_and_evaluate_polynomial<
     TFr: Fr + Copy,
     TG1: G1 + G1GetFp<TG1Fp> + G1Mul<TFr>,
@@ -586,24 +856,25 @@ fn compute_challenges_and_evaluate_polynomial<
 >(
     blobs: &[Vec<TFr>],
     commitments_g1: &[TG1],
+    cancel: Option<&CancelToken>,
     ts: &TKZGSettings,
 ) -> Result<(Vec<TFr>, Vec<TFr>), String> {
-    let mut evaluation_challenges_fr = Vec::with_capacity(blobs.len());
-    let mut ys_fr = Vec::with_capacity(blobs.len());
+    let evaluation_challenges_fr = compute_challenges(blobs, commitments_g1)?;
+    let mut ys_fr = try_vec_with_capacity(blobs.len())?;
 
-    for i in 0..blobs.len() {
-        let polynomial = blob_to_polynomial(&blobs[i])?;
-        let evaluation_challenge_fr = compute_challenge(&blobs[i], &commitments_g1[i]);
+    for (blob, evaluation_challenge_fr) in blobs.iter().zip(&evaluation_challenges_fr) {
+        check_cancelled(cancel)?;
+        let polynomial = blob_to_polynomial(blob)?;
         let y_fr =
-            evaluate_polynomial_in_evaluation_form(&polynomial, &evaluation_challenge_fr, ts)?;
+            evaluate_polynomial_in_evaluation_form(&polynomial, evaluation_challenge_fr, ts)?;
 
-        evaluation_challenges_fr.push(evaluation_challenge_fr);
         ys_fr.push(y_fr);
     }
 
     Ok((evaluation_challenges_fr, ys_fr))
 }
 
+#[cfg(feature = "verify")]
 fn validate_batched_input<TG1: G1>(commitments: &[TG1], proofs: &[TG1]) -> Result<(), String> {
     let invalid_commitment = cfg_into_iter!(commitments)
         .any(|commitment| !commitment.is_inf() && !commitment.is_valid());
@@ -619,6 +890,7 @@ fn validate_batched_input<TG1: G1>(commitments: &[TG1], proofs: &[TG1]) -> Resul
     Ok(())
 }
 
+#[cfg(feature = "verify")]
 pub fn verify_blob_kzg_proof_batch_rust<
     TFr: Fr + Copy,
     TG1: G1 + G1Mul<TFr> + PairingVerify<TG1, TG2> + G1GetFp<TG1Fp> + G1LinComb<TFr, TG1Fp, TG1Affine>,
@@ -634,6 +906,76 @@ pub fn verify_blob_kzg_proof_batch_rust<
     proofs_g1: &[TG1],
     ts: &TKZGSettings,
 ) -> Result<bool, String> {
+    verify_blob_kzg_proof_batch(blobs, commitments_g1, proofs_g1, None, None, ts)
+}
+
+/// Same as `verify_blob_kzg_proof_batch_rust`, with the batch challenge bound to `domain`
+#[cfg(feature = "verify")]
+pub fn verify_blob_kzg_proof_batch_with_domain_rust<
+    TFr: Fr + Copy,
+    TG1: G1 + G1Mul<TFr> + PairingVerify<TG1, TG2> + G1GetFp<TG1Fp> + G1LinComb<TFr, TG1Fp, TG1Affine>,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine> + Sync,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    blobs: &[Vec<TFr>],
+    commitments_g1: &[TG1],
+    proofs_g1: &[TG1],
+    domain: &ChallengeDomain,
+    ts: &TKZGSettings,
+) -> Result<bool, String> {
+    if domain.tag.is_empty() {
+        return Err(String::from("Challenge domain tag must not be empty"));
+    }
+
+    verify_blob_kzg_proof_batch(blobs, commitments_g1, proofs_g1, Some(domain), None, ts)
+}
+
+/// Same as `verify_blob_kzg_proof_batch_rust`, checking `cancel` before each blob and failing
+/// with the message of `Cancelled` once it is cancelled
+#[cfg(feature = "verify")]
+pub fn verify_blob_kzg_proof_batch_cancellable_rust<
+    TFr: Fr + Copy,
+    TG1: G1 + G1Mul<TFr> + PairingVerify<TG1, TG2> + G1GetFp<TG1Fp> + G1LinComb<TFr, TG1Fp, TG1Affine>,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine> + Sync,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    blobs: &[Vec<TFr>],
+    commitments_g1: &[TG1],
+    proofs_g1: &[TG1],
+    cancel: &CancelToken,
+    ts: &TKZGSettings,
+) -> Result<bool, String> {
+    verify_blob_kzg_proof_batch(blobs, commitments_g1, proofs_g1, None, Some(cancel), ts)
+}
+
+#[cfg(feature = "verify")]
+fn verify_blob_kzg_proof_batch<
+    TFr: Fr + Copy,
+    TG1: G1 + G1Mul<TFr> + PairingVerify<TG1, TG2> + G1GetFp<TG1Fp> + G1LinComb<TFr, TG1Fp, TG1Affine>,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine> + Sync,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    blobs: &[Vec<TFr>],
+    commitments_g1: &[TG1],
+    proofs_g1: &[TG1],
+    domain: Option<&ChallengeDomain>,
+    cancel: Option<&CancelToken>,
+    ts: &TKZGSettings,
+) -> Result<bool, String> {
+    check_cancelled(cancel)?;
+
     // Exit early if we are given zero blobs
     if blobs.is_empty() {
         return Ok(true);
@@ -643,6 +985,7 @@ pub fn verify_blob_kzg_proof_batch_rust<
     if blobs.len() == 1 {
         return verify_blob_kzg_proof_rust(&blobs[0], &commitments_g1[0], &proofs_g1[0], ts);
     }
+    guard_insecure_dev_setup(ts.get_g2_secret());
 
     if blobs.len() != commitments_g1.len() || blobs.len() != proofs_g1.len() {
         return Err("Invalid amount of arguments".to_string());
@@ -672,14 +1015,17 @@ pub fn verify_blob_kzg_proof_batch_rust<
                         compute_challenges_and_evaluate_polynomial(
                             blob_group,
                             commitment_group,
+                            cancel,
                             ts,
                         )?;
+                    check_cancelled(cancel)?;
 
                     verify_kzg_proof_batch(
                         commitment_group,
                         &evaluation_challenges_fr,
                         &ys_fr,
                         proof_group,
+                        domain,
                         ts,
                     )
                 })
@@ -690,6 +1036,7 @@ pub fn verify_blob_kzg_proof_batch_rust<
             (blobs, commitments_g1, proofs_g1)
                 .into_par_iter()
                 .map(|(blob, commitment, proof)| {
+                    check_cancelled(cancel)?;
                     verify_blob_kzg_proof_rust(blob, commitment, proof, ts)
                 })
                 .try_reduce(|| true, |a, b| Ok(a && b))
@@ -700,13 +1047,15 @@ pub fn verify_blob_kzg_proof_batch_rust<
     {
         validate_batched_input(commitments_g1, proofs_g1)?;
         let (evaluation_challenges_fr, ys_fr) =
-            compute_challenges_and_evaluate_polynomial(blobs, commitments_g1, ts)?;
+            compute_challenges_and_evaluate_polynomial(blobs, commitments_g1, cancel, ts)?;
+        check_cancelled(cancel)?;
 
         verify_kzg_proof_batch(
             commitments_g1,
             &evaluation_challenges_fr,
             &ys_fr,
             proofs_g1,
+            domain,
             ts,
         )
     }
@@ -728,7 +1077,83 @@ pub fn bytes_to_blob<TFr: Fr>(bytes: &[u8]) -> Result<Vec<TFr>, String> {
         .collect()
 }
 
-fn fr_batch_inv<TFr: Fr + PartialEq + Copy>(
+/// Same as `bytes_to_blob` for a blob received as a sequence of buffers, without first copying
+/// them into one contiguous allocation. Field elements may span buffer boundaries.
+pub fn blob_from_chunks<'a, TFr: Fr>(
+    chunks: impl Iterator<Item = &'a [u8]>,
+) -> Result<Vec<TFr>, String> {
+    let mut blob = try_vec_with_capacity(FIELD_ELEMENTS_PER_BLOB)?;
+    let mut element = [0u8; BYTES_PER_FIELD_ELEMENT];
+    let mut filled = 0;
+    let mut total = 0;
+
+    for mut chunk in chunks {
+        total += chunk.len();
+        if total > BYTES_PER_BLOB {
+            // Keep counting for the error message
+            continue;
+        }
+
+        while !chunk.is_empty() {
+            if filled == 0 && chunk.len() >= BYTES_PER_FIELD_ELEMENT {
+                // Whole field elements are read in place
+                let (bytes, rest) = chunk.split_at(BYTES_PER_FIELD_ELEMENT);
+                blob.push(TFr::from_bytes(bytes)?);
+                chunk = rest;
+                continue;
+            }
+
+            let len = (BYTES_PER_FIELD_ELEMENT - filled).min(chunk.len());
+            element[filled..filled + len].copy_from_slice(&chunk[..len]);
+            filled += len;
+            chunk = &chunk[len..];
+            if filled == BYTES_PER_FIELD_ELEMENT {
+                blob.push(TFr::from_bytes(&element)?);
+                filled = 0;
+            }
+        }
+    }
+
+    if total != BYTES_PER_BLOB {
+        return Err(format!(
+            "Invalid byte length. Expected {} got {}",
+            BYTES_PER_BLOB, total,
+        ));
+    }
+
+    Ok(blob)
+}
+
+/// Pack arbitrary data into as many blobs as needed. The first field element holds the byte length
+/// as a big-endian u64, so that data differing by trailing zeros gives different blobs, and the
+/// data follows, 31 bytes per field element with a leading zero byte. The last blob is padded
+/// with zeros, and empty data yields a single zero blob.
+pub fn bytes_to_blobs<TFr: Fr>(bytes: &[u8]) -> Result<Vec<Vec<TFr>>, String> {
+    let mut length = [0u8; BYTES_PER_FIELD_ELEMENT];
+    length[BYTES_PER_FIELD_ELEMENT - 8..].copy_from_slice(&(bytes.len() as u64).to_be_bytes());
+
+    let mut elements = try_vec_with_capacity(
+        1 + (bytes.len() + USABLE_BYTES_PER_FIELD_ELEMENT - 1) / USABLE_BYTES_PER_FIELD_ELEMENT,
+    )?;
+    elements.push(TFr::from_bytes(&length)?);
+    for data in bytes.chunks(USABLE_BYTES_PER_FIELD_ELEMENT) {
+        let mut element = [0u8; BYTES_PER_FIELD_ELEMENT];
+        element[1..1 + data.len()].copy_from_slice(data);
+        elements.push(TFr::from_bytes(&element)?);
+    }
+
+    elements
+        .chunks(FIELD_ELEMENTS_PER_BLOB)
+        .map(|chunk| {
+            let mut blob = try_vec_with_capacity(FIELD_ELEMENTS_PER_BLOB)?;
+            blob.extend_from_slice(chunk);
+            blob.resize(FIELD_ELEMENTS_PER_BLOB, TFr::zero());
+            Ok(blob)
+        })
+        .collect()
+}
+
+pub(crate) fn fr_batch_inv<TFr: Fr + PartialEq + Copy>(
     out: &mut [TFr],
     a: &[TFr],
     len: usize,
@@ -766,7 +1191,73 @@ pub fn hash_to_bls_field<TFr: Fr>(x: &[u8; BYTES_PER_FIELD_ELEMENT]) -> TFr {
     TFr::from_bytes_unchecked(x).unwrap()
 }
 
-fn compute_challenge<TFr: Fr, TG1: G1>(blob: &[TFr], commitment: &TG1) -> TFr {
+/// Derive the Fiat-Shamir evaluation challenge of `blob` and `commitment`, the point at which
+/// `compute_blob_kzg_proof_rust` opens the blob. Pipelines can derive it as soon as the blob and
+/// its commitment are known, and verify later with `verify_with_precomputed_challenge_rust`.
+#[cfg(any(feature = "prove", feature = "verify"))]
+pub fn compute_challenge<TFr: Fr, TG1: G1>(blob: &[TFr], commitment: &TG1) -> Result<TFr, String> {
+    if blob.len() != FIELD_ELEMENTS_PER_BLOB {
+        return Err(String::from("Blob length must be FIELD_ELEMENTS_PER_BLOB"));
+    }
+
+    Ok(hash_to_bls_field(&compute_challenge_hash(blob, commitment)))
+}
+
+/// Evaluation challenges of every blob and its commitment, same as calling `compute_challenge`
+/// on each pair but with the hashes computed `SHA256_LANES` at a time
+#[cfg(any(feature = "prove", feature = "verify"))]
+pub fn compute_challenges<TFr: Fr, TG1: G1>(
+    blobs: &[Vec<TFr>],
+    commitments: &[TG1],
+) -> Result<Vec<TFr>, String> {
+    if blobs.len() != commitments.len() {
+        return Err(String::from("Invalid amount of arguments"));
+    }
+    if blobs
+        .iter()
+        .any(|blob| blob.len() != FIELD_ELEMENTS_PER_BLOB)
+    {
+        return Err(String::from("Blob length must be FIELD_ELEMENTS_PER_BLOB"));
+    }
+
+    Ok(compute_challenge_hashes(blobs, commitments)
+        .iter()
+        .map(hash_to_bls_field)
+        .collect())
+}
+
+#[cfg(any(feature = "prove", feature = "verify"))]
+pub(crate) fn compute_challenge_hash<TFr: Fr, TG1: G1>(
+    blob: &[TFr],
+    commitment: &TG1,
+) -> [u8; BYTES_PER_FIELD_ELEMENT] {
+    hash(&challenge_input(blob, commitment))
+}
+
+#[cfg(any(feature = "prove", feature = "verify"))]
+fn compute_challenge_hashes<TFr: Fr, TG1: G1>(
+    blobs: &[Vec<TFr>],
+    commitments: &[TG1],
+) -> Vec<[u8; BYTES_PER_FIELD_ELEMENT]> {
+    let mut hashes = Vec::with_capacity(blobs.len());
+    for (blobs, commitments) in blobs
+        .chunks(SHA256_LANES)
+        .zip(commitments.chunks(SHA256_LANES))
+    {
+        let inputs = blobs
+            .iter()
+            .zip(commitments)
+            .map(|(blob, commitment)| challenge_input(blob, commitment))
+            .collect::<Vec<_>>();
+        let inputs = inputs.iter().map(Vec::as_slice).collect::<Vec<_>>();
+        hashes.extend(sha256_many(&inputs));
+    }
+
+    hashes
+}
+
+#[cfg(any(feature = "prove", feature = "verify"))]
+fn challenge_input<TFr: Fr, TG1: G1>(blob: &[TFr], commitment: &TG1) -> Vec<u8> {
     let mut bytes: Vec<u8> = vec![0; CHALLENGE_INPUT_SIZE];
 
     // Copy domain separator
@@ -788,9 +1279,7 @@ fn compute_challenge<TFr: Fr, TG1: G1>(blob: &[TFr], commitment: &TG1) -> TFr {
         bytes[32 + BYTES_PER_BLOB + i] = v[i];
     }
 
-    // Now let's create the challenge!
-    let eval_challenge = hash(&bytes);
-    hash_to_bls_field(&eval_challenge)
+    bytes
 }
 
 pub fn blob_to_polynomial<TFr: Fr, TPoly: Poly<TFr>>(blob: &[TFr]) -> Result<TPoly, String> {
@@ -818,15 +1307,22 @@ pub fn evaluate_polynomial_in_evaluation_form<
         return Err(String::from("Incorrect field elements count."));
     }
 
-    let mut inverses_in: Vec<TFr> = vec![TFr::default(); FIELD_ELEMENTS_PER_BLOB];
-    let mut inverses: Vec<TFr> = vec![TFr::default(); FIELD_ELEMENTS_PER_BLOB];
+    evaluate_evaluations(p.get_coeffs(), x, s.get_fft_settings().get_roots_of_unity())
+}
 
-    let roots_of_unity = s.get_fft_settings().get_roots_of_unity();
-    let poly_coeffs = p.get_coeffs();
+/// Value at `x` of the polynomial given by its `evaluations` over the blob domain, whose points
+/// are `roots_of_unity` in the same order, with the barycentric formula
+pub(crate) fn evaluate_evaluations<TFr: Fr + Copy>(
+    evaluations: &[TFr],
+    x: &TFr,
+    roots_of_unity: &[TFr],
+) -> Result<TFr, String> {
+    let mut inverses_in: Vec<TFr> = try_vec(TFr::default(), FIELD_ELEMENTS_PER_BLOB)?;
+    let mut inverses: Vec<TFr> = try_vec(TFr::default(), FIELD_ELEMENTS_PER_BLOB)?;
 
     for i in 0..FIELD_ELEMENTS_PER_BLOB {
         if x == &roots_of_unity[i] {
-            return Ok(poly_coeffs[i]);
+            return Ok(evaluations[i]);
         }
         inverses_in[i] = x.sub(&roots_of_unity[i]);
     }
@@ -838,7 +1334,7 @@ pub fn evaluate_polynomial_in_evaluation_form<
 
     for i in 0..FIELD_ELEMENTS_PER_BLOB {
         tmp = inverses[i].mul(&roots_of_unity[i]);
-        tmp = tmp.mul(&poly_coeffs[i]);
+        tmp = tmp.mul(&evaluations[i]);
         out = out.add(&tmp);
     }
 
@@ -892,12 +1388,21 @@ pub fn load_trusted_setup_rust<
 
     let mut g1_values = g1_bytes
         .chunks(BYTES_PER_G1)
-        .map(TG1::from_bytes)
+        .enumerate()
+        .map(|(i, bytes)| {
+            let point =
+                TG1::from_bytes(bytes).map_err(|e| format!("Invalid G1 point {}: {}", i, e));
+            report_progress(ProgressStage::TrustedSetup, i + 1, num_g1_points);
+            point
+        })
         .collect::<Result<Vec<TG1>, String>>()?;
 
     let g2_values = g2_bytes
         .chunks(BYTES_PER_G2)
-        .map(TG2::from_bytes)
+        .enumerate()
+        .map(|(i, bytes)| {
+            TG2::from_bytes(bytes).map_err(|e| format!("Invalid G2 point {}: {}", i, e))
+        })
         .collect::<Result<Vec<TG2>, String>>()?;
 
     // Sanity check, that user is not trying to load old trusted setup file
@@ -914,3 +1419,22 @@ pub fn load_trusted_setup_rust<
     reverse_bit_order(&mut g1_values)?;
     TKZGSettings::new(g1_values.as_slice(), g2_values.as_slice(), max_scale, &fs)
 }
+
+/// Load the JSON trusted setup published by the KZG ceremony, see
+/// `load_trusted_setup_json_string`
+#[cfg(feature = "io")]
+pub fn load_trusted_setup_json_rust<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + PairingVerify<TG1, TG2>,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    contents: &str,
+) -> Result<TKZGSettings, String> {
+    let (g1_bytes, g2_bytes) = load_trusted_setup_json_string(contents)?;
+    load_trusted_setup_rust(&g1_bytes, &g2_bytes)
+}
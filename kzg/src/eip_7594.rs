@@ -0,0 +1,608 @@
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use spin::RwLock;
+
+use crate::common_utils::{reverse_bit_order, try_vec};
+use crate::dev_setup::guard_insecure_dev_setup;
+use crate::eip_4844::{
+    blob_to_polynomial, bytes_of_uint64, compute_powers, hash, hash_to_bls_field,
+    BYTES_PER_FIELD_ELEMENT, FIELD_ELEMENTS_PER_BLOB,
+};
+#[cfg(feature = "std")]
+use crate::error::KzgError;
+#[cfg(feature = "std")]
+use crate::file_options::FileOptions;
+#[cfg(feature = "std")]
+use crate::fk20_cache::{read_fk20_cache_file, setup_g1_digest, write_fk20_cache_file};
+use crate::{
+    FFTFr, FFTSettings, FK20MultiSettings, Fr, G1Affine, G1Fp, G1GetFp, G1LinComb, G1Mul,
+    KZGSettings, PairingVerify, Poly, PolyRecover, FFTG1, G1, G2,
+};
+
+////////////////////////////// Cells of EIP-7594 (PeerDAS) //////////////////////////////
+//
+// A blob is extended to `FIELD_ELEMENTS_PER_EXT_BLOB` evaluations of its polynomial over the
+// domain of that size, in bit-reversed order, and cut into `CELLS_PER_EXT_BLOB` cells of
+// `FIELD_ELEMENTS_PER_CELL` evaluations. In bit-reversed order, cell `i` is the coset
+// `h_i * {ω^j}` of the subgroup of order `FIELD_ELEMENTS_PER_CELL`, with `h_i = ω_ext^brp(i)`, and
+// its proof is the commitment to the quotient of the polynomial by `x^n - h_i^n`, `n` being the
+// cell size. All the proofs of a blob are computed at once with FK20.
+//
+// Proofs are computed against the monomial form of the setup, which `CellSettings::new` derives
+// from the EIP-4844 settings with a G1 FFT. `verify_cell_kzg_proof_batch_rust` combines any number
+// of cells, of any blobs, into the single pairing check of the specification:
+//
+//     e(Σ r^k π_k, [s^n]₂) = e(Σ r^k C_k - [Σ r^k I_k(s)]₁ + Σ r^k h_k^n π_k, [1]₂)
+//
+// where `I_k` interpolates cell `k` over its coset, with an inverse FFT of size `n`. Any half of
+// the cells of a blob recovers the others with `recover_cells_and_kzg_proofs_rust`.
+//
+// The FK20 settings take as much memory as the setup, so a `MemoryMonitor` may release them. Cell
+// proofs are then computed with FK20 settings rebuilt for each call, which is much slower, until
+// the pressure is gone.
+
+/// Evaluations in an extended blob
+pub const FIELD_ELEMENTS_PER_EXT_BLOB: usize = 2 * FIELD_ELEMENTS_PER_BLOB;
+/// Evaluations in a cell
+pub const FIELD_ELEMENTS_PER_CELL: usize = 64;
+pub const BYTES_PER_CELL: usize = FIELD_ELEMENTS_PER_CELL * BYTES_PER_FIELD_ELEMENT;
+pub const CELLS_PER_EXT_BLOB: usize = FIELD_ELEMENTS_PER_EXT_BLOB / FIELD_ELEMENTS_PER_CELL;
+
+pub const RANDOM_CHALLENGE_KZG_CELL_BATCH_DOMAIN: [u8; 16] = *b"RCKZGCBATCH__V1_";
+
+/// FK20 settings of `CellSettings`, `None` once released
+type SharedFk20<TFK20MultiSettings> = Arc<RwLock<Option<Arc<TFK20MultiSettings>>>>;
+
+/// Settings of the cell functions, derived from the EIP-4844 settings. Clones share their FK20
+/// settings
+#[derive(Debug, Clone)]
+pub struct CellSettings<TKZGSettings, TFK20MultiSettings> {
+    /// Monomial G1 points of the blob polynomials over the extended domain
+    extended: TKZGSettings,
+    fk20: SharedFk20<TFK20MultiSettings>,
+    /// Bytes held by the Toeplitz FFTs of the FK20 settings
+    #[cfg(feature = "std")]
+    fk20_memory_usage: usize,
+}
+
+/// `index` with its `log2(n)` low bits reversed
+fn reverse_bits(index: usize, n: usize) -> usize {
+    index.reverse_bits() >> (n.leading_zeros() + 1)
+}
+
+impl<TKZGSettings, TFK20MultiSettings> CellSettings<TKZGSettings, TFK20MultiSettings> {
+    /// Settings of the cells of the blobs committed to with `s`, whose G1 points are either in
+    /// monomial form or in bit-reversed Lagrange form as loaded from a trusted setup file
+    pub fn new<
+        TFr: Fr,
+        TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+        TG2: G2,
+        TFFTSettings: FFTSettings<TFr> + FFTG1<TG1>,
+        TPoly: Poly<TFr>,
+        TG1Fp: G1Fp,
+        TG1Affine: G1Affine<TG1, TG1Fp>,
+    >(
+        s: &TKZGSettings,
+    ) -> Result<Self, String>
+    where
+        TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+        TFK20MultiSettings:
+            FK20MultiSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TKZGSettings, TG1Fp, TG1Affine>,
+    {
+        let extended = extended_settings(s)?;
+        let fk20 = TFK20MultiSettings::new(
+            &extended,
+            FIELD_ELEMENTS_PER_EXT_BLOB,
+            FIELD_ELEMENTS_PER_CELL,
+        )?;
+
+        Ok(Self::with_fk20(extended, fk20))
+    }
+
+    /// Same as `new`, with the FFTs of the FK20 Toeplitz matrices read from the cache file at
+    /// `path` written by `write_fk20_cache` instead of computed
+    #[cfg(feature = "std")]
+    pub fn new_with_fk20_cache<
+        TFr: Fr,
+        TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+        TG2: G2,
+        TFFTSettings: FFTSettings<TFr> + FFTG1<TG1>,
+        TPoly: Poly<TFr>,
+        TG1Fp: G1Fp,
+        TG1Affine: G1Affine<TG1, TG1Fp>,
+    >(
+        s: &TKZGSettings,
+        path: &str,
+    ) -> Result<Self, KzgError>
+    where
+        TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+        TFK20MultiSettings:
+            FK20MultiSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TKZGSettings, TG1Fp, TG1Affine>,
+    {
+        let extended = extended_settings(s)?;
+        let x_ext_fft_files = read_fk20_cache_file(
+            path,
+            FIELD_ELEMENTS_PER_EXT_BLOB,
+            FIELD_ELEMENTS_PER_CELL,
+            &setup_g1_digest(extended.get_g1_secret()),
+        )?;
+        let fk20 = TFK20MultiSettings::new_with_x_ext_fft_files(
+            &extended,
+            FIELD_ELEMENTS_PER_EXT_BLOB,
+            FIELD_ELEMENTS_PER_CELL,
+            x_ext_fft_files,
+        )?;
+
+        Ok(Self::with_fk20(extended, fk20))
+    }
+
+    /// Write the FFTs of the FK20 Toeplitz matrices to the cache file at `path`, usually
+    /// `fk20_cache_path` of the table file of the setup, see `fk20_cache`
+    #[cfg(feature = "std")]
+    pub fn write_fk20_cache<
+        TFr: Fr,
+        TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+        TG2: G2,
+        TFFTSettings: FFTSettings<TFr> + FFTG1<TG1>,
+        TPoly: Poly<TFr>,
+        TG1Fp: G1Fp,
+        TG1Affine: G1Affine<TG1, TG1Fp>,
+    >(
+        &self,
+        path: &str,
+        options: &FileOptions,
+    ) -> Result<(), KzgError>
+    where
+        TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+        TFK20MultiSettings:
+            FK20MultiSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TKZGSettings, TG1Fp, TG1Affine>,
+    {
+        write_fk20_cache_file(
+            path,
+            options,
+            FIELD_ELEMENTS_PER_EXT_BLOB,
+            FIELD_ELEMENTS_PER_CELL,
+            &setup_g1_digest(self.extended.get_g1_secret()),
+            self.fk20()?.get_x_ext_fft_files(),
+        )
+    }
+
+    /// Settings over the extended domain, with the G1 points in monomial form
+    pub fn extended_settings(&self) -> &TKZGSettings {
+        &self.extended
+    }
+
+    fn with_fk20<
+        TFr: Fr,
+        TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+        TG2: G2,
+        TFFTSettings: FFTSettings<TFr>,
+        TPoly: Poly<TFr>,
+        TG1Fp: G1Fp,
+        TG1Affine: G1Affine<TG1, TG1Fp>,
+    >(
+        extended: TKZGSettings,
+        fk20: TFK20MultiSettings,
+    ) -> Self
+    where
+        TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+        TFK20MultiSettings:
+            FK20MultiSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TKZGSettings, TG1Fp, TG1Affine>,
+    {
+        #[cfg(feature = "std")]
+        let fk20_memory_usage = fk20
+            .get_x_ext_fft_files()
+            .iter()
+            .map(|x| core::mem::size_of_val(x.as_slice()))
+            .sum();
+
+        Self {
+            extended,
+            fk20: Arc::new(RwLock::new(Some(Arc::new(fk20)))),
+            #[cfg(feature = "std")]
+            fk20_memory_usage,
+        }
+    }
+
+    /// FK20 settings, rebuilt if they were released. They are kept again once no `MemoryMonitor`
+    /// is under pressure
+    fn fk20<
+        TFr: Fr,
+        TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+        TG2: G2,
+        TFFTSettings: FFTSettings<TFr>,
+        TPoly: Poly<TFr>,
+        TG1Fp: G1Fp,
+        TG1Affine: G1Affine<TG1, TG1Fp>,
+    >(
+        &self,
+    ) -> Result<Arc<TFK20MultiSettings>, String>
+    where
+        TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+        TFK20MultiSettings:
+            FK20MultiSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TKZGSettings, TG1Fp, TG1Affine>,
+    {
+        if let Some(fk20) = self.fk20.read().clone() {
+            return Ok(fk20);
+        }
+
+        let fk20 = Arc::new(TFK20MultiSettings::new(
+            &self.extended,
+            FIELD_ELEMENTS_PER_EXT_BLOB,
+            FIELD_ELEMENTS_PER_CELL,
+        )?);
+        #[cfg(feature = "std")]
+        let keep = !crate::memory_pressure::under_memory_pressure();
+        #[cfg(not(feature = "std"))]
+        let keep = true;
+        if keep {
+            *self.fk20.write() = Some(fk20.clone());
+        }
+
+        Ok(fk20)
+    }
+
+    /// Drop the FK20 settings, returning the bytes released, see `memory_pressure`
+    #[cfg(feature = "std")]
+    pub(crate) fn release_fk20(&self) -> usize {
+        self.fk20
+            .write()
+            .take()
+            .map_or(0, |_| self.fk20_memory_usage)
+    }
+}
+
+/// Settings over the extended domain with the G1 points of `s` in monomial form, see
+/// `CellSettings::new`
+fn extended_settings<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr> + FFTG1<TG1>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    s: &TKZGSettings,
+) -> Result<TKZGSettings, String> {
+    let g1 = s.get_g1_secret();
+    if g1.len() != FIELD_ELEMENTS_PER_BLOB {
+        return Err(format!(
+            "Cells need a setup of {} G1 points, got {}",
+            FIELD_ELEMENTS_PER_BLOB,
+            g1.len()
+        ));
+    }
+    if s.get_g2_secret().len() <= FIELD_ELEMENTS_PER_CELL {
+        return Err(format!(
+            "Cells need a setup of more than {} G2 points",
+            FIELD_ELEMENTS_PER_CELL
+        ));
+    }
+
+    let fs = TFFTSettings::new(FIELD_ELEMENTS_PER_EXT_BLOB.trailing_zeros() as usize)?;
+    let monomial = if g1[0].equals(&TG1::generator()) {
+        g1.to_vec()
+    } else {
+        let mut lagrange = g1.to_vec();
+        reverse_bit_order(&mut lagrange)?;
+        fs.fft_g1(&lagrange, false)?
+    };
+
+    TKZGSettings::new(&monomial, s.get_g2_secret(), FIELD_ELEMENTS_PER_BLOB, &fs)
+}
+
+/// Decode a cell from `BYTES_PER_CELL` bytes
+pub fn bytes_to_cell<TFr: Fr>(bytes: &[u8]) -> Result<Vec<TFr>, String> {
+    if bytes.len() != BYTES_PER_CELL {
+        return Err(format!(
+            "Invalid byte length. Expected {} got {}",
+            BYTES_PER_CELL,
+            bytes.len(),
+        ));
+    }
+
+    bytes
+        .chunks(BYTES_PER_FIELD_ELEMENT)
+        .map(TFr::from_bytes)
+        .collect()
+}
+
+/// Encode a cell into `BYTES_PER_CELL` bytes
+pub fn cell_to_bytes<TFr: Fr>(cell: &[TFr]) -> Vec<u8> {
+    cell.iter().flat_map(|fr| fr.to_bytes()).collect()
+}
+
+/// Cells of the extension of `blob` and their proofs, in cell index order
+#[allow(clippy::type_complexity)]
+pub fn compute_cells_and_kzg_proofs_rust<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr> + FFTFr<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TFK20MultiSettings: FK20MultiSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TKZGSettings, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    blob: &[TFr],
+    s: &CellSettings<TKZGSettings, TFK20MultiSettings>,
+) -> Result<(Vec<Vec<TFr>>, Vec<TG1>), String> {
+    let polynomial: TPoly = blob_to_polynomial(blob)?;
+    let mut evaluations = polynomial.get_coeffs().to_vec();
+    reverse_bit_order(&mut evaluations)?;
+    let coeffs = s.extended.get_fft_settings().fft_fr(&evaluations, true)?;
+
+    cells_and_proofs(coeffs, s)
+}
+
+/// Cells and proofs of the polynomial of coefficients `coeffs`, of degree below
+/// `FIELD_ELEMENTS_PER_BLOB`
+#[allow(clippy::type_complexity)]
+fn cells_and_proofs<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr> + FFTFr<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TFK20MultiSettings: FK20MultiSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TKZGSettings, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    mut coeffs: Vec<TFr>,
+    s: &CellSettings<TKZGSettings, TFK20MultiSettings>,
+) -> Result<(Vec<Vec<TFr>>, Vec<TG1>), String> {
+    // FK20 returns the proofs of the cosets in bit-reversed order, which is cell order
+    let proofs = s.fk20()?.data_availability(&TPoly::from_coeffs(&coeffs))?;
+
+    coeffs.resize(FIELD_ELEMENTS_PER_EXT_BLOB, TFr::zero());
+    let mut extension = s.extended.get_fft_settings().fft_fr(&coeffs, false)?;
+    reverse_bit_order(&mut extension)?;
+    let cells = extension
+        .chunks_exact(FIELD_ELEMENTS_PER_CELL)
+        .map(|cell| cell.to_vec())
+        .collect();
+
+    Ok((cells, proofs))
+}
+
+/// Every cell of an extended blob and their proofs, from the cells at `cell_indices`, at least
+/// half of them
+#[allow(clippy::type_complexity)]
+pub fn recover_cells_and_kzg_proofs_rust<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr> + FFTFr<TFr>,
+    TPoly: Poly<TFr> + PolyRecover<TFr, TPoly, TFFTSettings>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TFK20MultiSettings: FK20MultiSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TKZGSettings, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    cell_indices: &[usize],
+    cells: &[Vec<TFr>],
+    s: &CellSettings<TKZGSettings, TFK20MultiSettings>,
+) -> Result<(Vec<Vec<TFr>>, Vec<TG1>), String> {
+    if cell_indices.len() != cells.len() {
+        return Err(String::from("Invalid amount of arguments"));
+    }
+    if cells.len() < CELLS_PER_EXT_BLOB / 2 {
+        return Err(format!(
+            "Recovery needs at least {} cells, got {}",
+            CELLS_PER_EXT_BLOB / 2,
+            cells.len()
+        ));
+    }
+    check_cells(cell_indices, cells)?;
+
+    // Place the evaluations at their index in natural order
+    let mut samples = try_vec(None, FIELD_ELEMENTS_PER_EXT_BLOB)?;
+    for (index, cell) in cell_indices.iter().zip(cells) {
+        for (i, value) in cell.iter().enumerate() {
+            let position = index * FIELD_ELEMENTS_PER_CELL + i;
+            let sample = &mut samples[reverse_bits(position, FIELD_ELEMENTS_PER_EXT_BLOB)];
+            if sample.is_some() {
+                return Err(format!("Cell {} is given twice", index));
+            }
+            *sample = Some(value.clone());
+        }
+    }
+
+    let fs = s.extended.get_fft_settings();
+    let mut coeffs = if samples.iter().all(Option::is_some) {
+        let evaluations = samples.into_iter().flatten().collect::<Vec<_>>();
+        fs.fft_fr(&evaluations, true)?
+    } else {
+        TPoly::recover_poly_coeffs_from_samples(&samples, fs)?
+            .get_coeffs()
+            .to_vec()
+    };
+    if coeffs[FIELD_ELEMENTS_PER_BLOB..]
+        .iter()
+        .any(|c| !c.is_zero())
+    {
+        return Err(String::from("Cells are not the extension of a blob"));
+    }
+    coeffs.truncate(FIELD_ELEMENTS_PER_BLOB);
+
+    let (recovered, proofs) = cells_and_proofs(coeffs, s)?;
+    for (index, cell) in cell_indices.iter().zip(cells) {
+        let matches = recovered[*index].iter().zip(cell).all(|(a, b)| a.equals(b));
+        if !matches {
+            return Err(String::from("Cells are not the extension of a blob"));
+        }
+    }
+
+    Ok((recovered, proofs))
+}
+
+fn check_cells<TFr: Fr>(cell_indices: &[usize], cells: &[Vec<TFr>]) -> Result<(), String> {
+    if let Some(index) = cell_indices.iter().find(|&&i| i >= CELLS_PER_EXT_BLOB) {
+        return Err(format!(
+            "Cell index {} is out of range, there are {} cells",
+            index, CELLS_PER_EXT_BLOB
+        ));
+    }
+    if cells
+        .iter()
+        .any(|cell| cell.len() != FIELD_ELEMENTS_PER_CELL)
+    {
+        return Err(String::from("Cell length must be FIELD_ELEMENTS_PER_CELL"));
+    }
+
+    Ok(())
+}
+
+/// Check that every `cells[k]` is the cell at `cell_indices[k]` of the blob committed to by
+/// `commitments[k]`, with proof `proofs[k]`, with a single pairing check
+pub fn verify_cell_kzg_proof_batch_rust<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + PairingVerify<TG1, TG2> + G1LinComb<TFr, TG1Fp, TG1Affine>,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr> + FFTFr<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TFK20MultiSettings,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    commitments: &[TG1],
+    cell_indices: &[usize],
+    cells: &[Vec<TFr>],
+    proofs: &[TG1],
+    s: &CellSettings<TKZGSettings, TFK20MultiSettings>,
+) -> Result<bool, String> {
+    let n = cells.len();
+    if commitments.len() != n || cell_indices.len() != n || proofs.len() != n {
+        return Err(String::from("Invalid amount of arguments"));
+    }
+    check_cells(cell_indices, cells)?;
+    if commitments.iter().any(|c| !c.is_inf() && !c.is_valid()) {
+        return Err(String::from("Invalid commitment"));
+    }
+    if proofs.iter().any(|p| !p.is_inf() && !p.is_valid()) {
+        return Err(String::from("Invalid proof"));
+    }
+    let g2 = s.extended.get_g2_secret();
+    guard_insecure_dev_setup(g2);
+    if n == 0 {
+        return Ok(true);
+    }
+
+    // Each distinct commitment is weighted once
+    let mut unique = Vec::new();
+    let mut positions = BTreeMap::new();
+    let commitment_indices = commitments
+        .iter()
+        .map(|c| {
+            *positions.entry(c.to_bytes()).or_insert_with(|| {
+                unique.push(c.clone());
+                unique.len() - 1
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let r_powers = compute_powers(
+        &cell_batch_challenge(&unique, &commitment_indices, cell_indices, cells, proofs),
+        n,
+    );
+
+    let fs = s.extended.get_fft_settings();
+    let root =
+        |exponent: usize| fs.get_expanded_roots_of_unity_at(exponent % FIELD_ELEMENTS_PER_EXT_BLOB);
+    // Exponent of the coset shift h_i = ω^shift
+    let shift = |index: usize| reverse_bits(index, CELLS_PER_EXT_BLOB);
+
+    let mut weights = vec![TFr::zero(); unique.len()];
+    let mut shifted_r_powers = Vec::with_capacity(n);
+    // Σ r^k cells[k] of each cell index, interpolated once per index
+    let mut combined_cells = BTreeMap::new();
+    for k in 0..n {
+        let i = commitment_indices[k];
+        weights[i] = weights[i].add(&r_powers[k]);
+        shifted_r_powers
+            .push(r_powers[k].mul(&root(shift(cell_indices[k]) * FIELD_ELEMENTS_PER_CELL)));
+
+        let combined = combined_cells
+            .entry(cell_indices[k])
+            .or_insert_with(|| vec![TFr::zero(); FIELD_ELEMENTS_PER_CELL]);
+        for (sum, value) in combined.iter_mut().zip(&cells[k]) {
+            *sum = sum.add(&value.mul(&r_powers[k]));
+        }
+    }
+
+    // Σ r^k I_k(x): the inverse FFT interpolates over the subgroup, coefficient j is then divided
+    // by h^j to move to the coset
+    let mut interpolated = vec![TFr::zero(); FIELD_ELEMENTS_PER_CELL];
+    for (index, mut combined) in combined_cells {
+        reverse_bit_order(&mut combined)?;
+        let coeffs = fs.fft_fr(&combined, true)?;
+        let shift_inv = root(FIELD_ELEMENTS_PER_EXT_BLOB - shift(index));
+        let mut factor = TFr::one();
+        for (sum, coeff) in interpolated.iter_mut().zip(&coeffs) {
+            *sum = sum.add(&coeff.mul(&factor));
+            factor = factor.mul(&shift_inv);
+        }
+    }
+
+    let proof_lincomb = TG1::g1_lincomb(proofs, &r_powers, n, None);
+    let commitment_lincomb = TG1::g1_lincomb(&unique, &weights, unique.len(), None);
+    let shifted_proof_lincomb = TG1::g1_lincomb(proofs, &shifted_r_powers, n, None);
+    let interpolated_g1 = s
+        .extended
+        .commit_to_poly(&TPoly::from_coeffs(&interpolated))?;
+    let rhs = commitment_lincomb
+        .sub(&interpolated_g1)
+        .add_or_dbl(&shifted_proof_lincomb);
+
+    Ok(TG1::verify(
+        &proof_lincomb,
+        &g2[FIELD_ELEMENTS_PER_CELL],
+        &rhs,
+        &TG2::generator(),
+    ))
+}
+
+/// Challenge `r` of `verify_cell_kzg_proof_batch_rust`, hashed from every input as in the
+/// specification
+fn cell_batch_challenge<TFr: Fr, TG1: G1>(
+    commitments: &[TG1],
+    commitment_indices: &[usize],
+    cell_indices: &[usize],
+    cells: &[Vec<TFr>],
+    proofs: &[TG1],
+) -> TFr {
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(&RANDOM_CHALLENGE_KZG_CELL_BATCH_DOMAIN);
+    let push_u64 = |bytes: &mut Vec<u8>, n: usize| {
+        let mut be = [0u8; 8];
+        bytes_of_uint64(&mut be, n as u64);
+        bytes.extend_from_slice(&be);
+    };
+    push_u64(&mut bytes, FIELD_ELEMENTS_PER_BLOB);
+    push_u64(&mut bytes, FIELD_ELEMENTS_PER_CELL);
+    push_u64(&mut bytes, commitments.len());
+    push_u64(&mut bytes, cells.len());
+    for commitment in commitments {
+        bytes.extend_from_slice(&commitment.to_bytes());
+    }
+    for k in 0..cells.len() {
+        push_u64(&mut bytes, commitment_indices[k]);
+        push_u64(&mut bytes, cell_indices[k]);
+        bytes.extend_from_slice(&cell_to_bytes(&cells[k]));
+        bytes.extend_from_slice(&proofs[k].to_bytes());
+    }
+
+    hash_to_bls_field(&hash(&bytes))
+}
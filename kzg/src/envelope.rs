@@ -0,0 +1,119 @@
+extern crate alloc;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::eip_4844::BYTES_PER_G1;
+use crate::G1;
+
+////////////////////////////// Self-describing proof encoding //////////////////////////////
+//
+// Commitments and proofs that are stored for a long time are wrapped in a small envelope
+//
+//     version (1 byte) || curve id (1 byte) || payload
+//
+// so that a future change of the point encoding or of the curve can be detected when decoding,
+// instead of silently misinterpreting old bytes.
+
+/// Current envelope format version
+pub const ENVELOPE_VERSION: u8 = 1;
+
+/// Size of the envelope header preceding the payload
+pub const ENVELOPE_HEADER_SIZE: usize = 2;
+
+/// Curve the payload of an envelope belongs to
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CurveId {
+    Bls12_381 = 1,
+}
+
+impl CurveId {
+    pub fn from_u8(id: u8) -> Result<Self, String> {
+        match id {
+            1 => Ok(CurveId::Bls12_381),
+            _ => Err(format!("Unknown curve id {}", id)),
+        }
+    }
+
+    /// Size of a compressed G1 point on this curve
+    pub fn g1_size(&self) -> usize {
+        match self {
+            CurveId::Bls12_381 => BYTES_PER_G1,
+        }
+    }
+}
+
+/// Wrap `payload` into an envelope of the current version
+pub fn encode_envelope(curve: CurveId, payload: &[u8]) -> Vec<u8> {
+    let mut out = Vec::new();
+    out.push(ENVELOPE_VERSION);
+    out.push(curve as u8);
+    out.extend_from_slice(payload);
+    out
+}
+
+/// Split an envelope into its curve id and payload, rejecting unknown versions and curves
+pub fn decode_envelope(bytes: &[u8]) -> Result<(CurveId, &[u8]), String> {
+    if bytes.len() < ENVELOPE_HEADER_SIZE {
+        return Err(String::from("Envelope is too short"));
+    }
+    if bytes[0] != ENVELOPE_VERSION {
+        return Err(format!("Unsupported envelope version {}", bytes[0]));
+    }
+    let curve = CurveId::from_u8(bytes[1])?;
+
+    Ok((curve, &bytes[ENVELOPE_HEADER_SIZE..]))
+}
+
+/// Encode a BLS12-381 commitment or proof
+pub fn encode_g1<TG1: G1>(point: &TG1) -> Vec<u8> {
+    encode_envelope(CurveId::Bls12_381, &point.to_bytes())
+}
+
+/// Decode a BLS12-381 commitment or proof encoded with `encode_g1`
+pub fn decode_g1<TG1: G1>(bytes: &[u8]) -> Result<TG1, String> {
+    let (curve, payload) = decode_envelope(bytes)?;
+    if curve != CurveId::Bls12_381 {
+        return Err(String::from("Envelope does not hold a BLS12-381 point"));
+    }
+    if payload.len() != curve.g1_size() {
+        return Err(format!(
+            "Invalid payload length. Expected {}, got {}",
+            curve.g1_size(),
+            payload.len()
+        ));
+    }
+
+    TG1::from_bytes(payload)
+}
+
+/// Encode a list of commitments or proofs into a single envelope
+pub fn encode_g1_list<TG1: G1>(points: &[TG1]) -> Vec<u8> {
+    let payload = points
+        .iter()
+        .flat_map(|point| point.to_bytes())
+        .collect::<Vec<u8>>();
+    encode_envelope(CurveId::Bls12_381, &payload)
+}
+
+/// Decode a list of commitments or proofs encoded with `encode_g1_list`
+pub fn decode_g1_list<TG1: G1>(bytes: &[u8]) -> Result<Vec<TG1>, String> {
+    let (curve, payload) = decode_envelope(bytes)?;
+    if curve != CurveId::Bls12_381 {
+        return Err(String::from("Envelope does not hold BLS12-381 points"));
+    }
+    if payload.len() % curve.g1_size() != 0 {
+        return Err(format!(
+            "Invalid payload length. Expected a multiple of {}, got {}",
+            curve.g1_size(),
+            payload.len()
+        ));
+    }
+
+    payload
+        .chunks_exact(curve.g1_size())
+        .map(TG1::from_bytes)
+        .collect()
+}
@@ -0,0 +1,120 @@
+extern crate alloc;
+
+use alloc::string::String;
+
+use sha2::{Digest, Sha256};
+
+use crate::eip_4844::hash_to_bls_field;
+use crate::{FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1Mul, KZGSettings, Poly, G1, G2};
+
+////////////////////////////// Same-polynomial proofs across setups //////////////////////////////
+//
+// When a system migrates from one trusted setup to another, commitments made under the old setup
+// `[p(s)]₁` need to be linked to commitments under the new one `[p(t)]₁`. Both commitments are
+// opened at a challenge `z` derived from the two commitments and the two setups, with the same
+// claimed value `y`. Two different polynomials of degree below the setup size agree at a random
+// point only with negligible probability, so two valid openings to the same `y` show that both
+// commitments are to the same polynomial.
+//
+// The check only needs the `[s]₂` element of each setup, so it also links a commitment computed
+// from the Lagrange form of a setup to one computed from a different ceremony's monomial form.
+
+/// Domain separator of the same-polynomial challenge
+pub const SAME_POLYNOMIAL_DOMAIN: [u8; 16] = *b"SAMEPOLYNOMIAL__";
+
+/// Proof that two commitments under different setups are to the same polynomial
+#[derive(Debug, Clone, PartialEq)]
+pub struct SamePolynomialProof<TFr, TG1> {
+    /// Evaluation of the polynomial at the challenge
+    pub value: TFr,
+    /// Opening of the commitment under the first setup
+    pub proof_a: TG1,
+    /// Opening of the commitment under the second setup
+    pub proof_b: TG1,
+}
+
+/// Challenge binding both commitments and both setups
+pub fn same_polynomial_challenge<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    commitment_a: &TG1,
+    commitment_b: &TG1,
+    ks_a: &TKZGSettings,
+    ks_b: &TKZGSettings,
+) -> Result<TFr, String> {
+    let s_g2 = |ks: &TKZGSettings| {
+        ks.get_g2_secret()
+            .get(1)
+            .map(|point| point.to_bytes())
+            .ok_or_else(|| String::from("Trusted setup must contain at least two G2 points"))
+    };
+
+    let mut hasher = Sha256::new();
+    hasher.update(SAME_POLYNOMIAL_DOMAIN);
+    hasher.update(s_g2(ks_a)?);
+    hasher.update(s_g2(ks_b)?);
+    hasher.update(commitment_a.to_bytes());
+    hasher.update(commitment_b.to_bytes());
+
+    Ok(hash_to_bls_field(&hasher.finalize().into()))
+}
+
+/// Prove that `commitment_a` under `ks_a` and `commitment_b` under `ks_b` are both commitments
+/// to `p`
+pub fn prove_same_polynomial<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    p: &TPoly,
+    commitment_a: &TG1,
+    commitment_b: &TG1,
+    ks_a: &TKZGSettings,
+    ks_b: &TKZGSettings,
+) -> Result<SamePolynomialProof<TFr, TG1>, String> {
+    let z = same_polynomial_challenge(commitment_a, commitment_b, ks_a, ks_b)?;
+
+    Ok(SamePolynomialProof {
+        value: p.eval(&z),
+        proof_a: ks_a.compute_proof_single(p, &z)?,
+        proof_b: ks_b.compute_proof_single(p, &z)?,
+    })
+}
+
+/// Check that `commitment_a` under `ks_a` and `commitment_b` under `ks_b` commit to the same
+/// polynomial
+pub fn verify_same_polynomial<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    commitment_a: &TG1,
+    commitment_b: &TG1,
+    proof: &SamePolynomialProof<TFr, TG1>,
+    ks_a: &TKZGSettings,
+    ks_b: &TKZGSettings,
+) -> Result<bool, String> {
+    let z = same_polynomial_challenge(commitment_a, commitment_b, ks_a, ks_b)?;
+
+    Ok(
+        ks_a.check_proof_single(commitment_a, &proof.proof_a, &z, &proof.value)?
+            && ks_b.check_proof_single(commitment_b, &proof.proof_b, &z, &proof.value)?,
+    )
+}
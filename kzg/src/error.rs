@@ -0,0 +1,125 @@
+extern crate alloc;
+
+use alloc::string::{String, ToString};
+use core::fmt;
+
+////////////////////////////// Errors //////////////////////////////
+//
+// Most of the crate fails with a `String`, which is enough to log a failure but leaves callers
+// string-matching to tell a missing file from a corrupted one. The setup and table loaders fail
+// with a `KzgError` instead, whose variants separate the failures a caller may recover from:
+// retrying an IO error, rebuilding a table written by another backend, or reporting the index
+// of the point a setup file got wrong.
+//
+// `KzgError` converts into `String`, so functions failing with a `String` call the loaders with
+// `?` as before, and the other way around errors of such functions end up in `KzgError::Other`.
+
+/// Error of the setup and table loaders
+#[derive(Debug)]
+pub enum KzgError {
+    /// Reading or writing a file or stream failed
+    #[cfg(feature = "std")]
+    Io(std::io::Error),
+    /// Element `index` of the input could not be decoded, e.g. `what` is "G1 point"
+    Deserialization {
+        what: &'static str,
+        index: usize,
+        message: String,
+    },
+    /// Decoded point `index` failed the requested validation
+    InvalidPoint {
+        what: &'static str,
+        index: usize,
+        reason: &'static str,
+    },
+    /// Input too short, too long or not a whole number of elements
+    InvalidLength(String),
+    /// Input that is not in the expected format at all
+    InvalidFormat(String),
+    /// Well-formed setup or table for another version, curve or backend, or one that does not
+    /// match its digest
+    SetupMismatch(String),
+    /// Dimensions that cannot be allocated on this machine
+    OutOfMemory(String),
+    /// Argument outside of the supported range
+    InvalidArgument(String),
+    /// Feature compiled out of this build
+    Unsupported(String),
+    /// Error of a function failing with a `String`
+    Other(String),
+}
+
+impl KzgError {
+    /// Same error, with the indices of a `Deserialization` or `InvalidPoint` error shifted by
+    /// `offset` and labelled `what`, for elements decoded or validated in chunks
+    pub fn at_offset(self, what: &'static str, offset: usize) -> Self {
+        match self {
+            Self::Deserialization { index, message, .. } => Self::Deserialization {
+                what,
+                index: index + offset,
+                message,
+            },
+            Self::InvalidPoint { index, reason, .. } => Self::InvalidPoint {
+                what,
+                index: index + offset,
+                reason,
+            },
+            e => e,
+        }
+    }
+}
+
+impl fmt::Display for KzgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            #[cfg(feature = "std")]
+            Self::Io(e) => write!(f, "I/O error: {}", e),
+            Self::Deserialization {
+                what,
+                index,
+                message,
+            } => write!(f, "{} {}: {}", what, index, message),
+            Self::InvalidPoint {
+                what,
+                index,
+                reason,
+            } => write!(f, "{} {} {}", what, index, reason),
+            Self::InvalidLength(s)
+            | Self::InvalidFormat(s)
+            | Self::SetupMismatch(s)
+            | Self::OutOfMemory(s)
+            | Self::InvalidArgument(s)
+            | Self::Unsupported(s)
+            | Self::Other(s) => f.write_str(s),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for KzgError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for KzgError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<String> for KzgError {
+    fn from(message: String) -> Self {
+        Self::Other(message)
+    }
+}
+
+impl From<KzgError> for String {
+    fn from(e: KzgError) -> Self {
+        e.to_string()
+    }
+}
@@ -0,0 +1,305 @@
+extern crate alloc;
+
+use alloc::collections::{BTreeMap, VecDeque};
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+use spin::RwLock;
+
+use crate::common_utils::try_vec;
+#[cfg(feature = "verify")]
+use crate::dev_setup::{is_insecure_dev_tau, report_insecure_dev_setup};
+#[cfg(feature = "prove")]
+use crate::eip_4844::blob_to_polynomial;
+use crate::eip_4844::{fr_batch_inv, FIELD_ELEMENTS_PER_BLOB};
+#[cfg(feature = "verify")]
+use crate::opening::commitment_minus_value;
+use crate::opening::g2_s_minus_z;
+#[cfg(feature = "prove")]
+use crate::G1LinComb;
+#[cfg(feature = "verify")]
+use crate::PairingVerify;
+use crate::{FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1Mul, G2Mul, KZGSettings, Poly, G1, G2};
+
+////////////////////////////// Evaluation cache for shared opening points //////////////////////////////
+//
+// Opening a blob at `z` and checking the opening need values that depend on `z` and on the
+// settings, but not on the blob:
+//
+//     1 / (ω_i - z)                  quotient of the proof in evaluation form
+//     ω_i / (z - ω_i) * (z^n - 1)/n  barycentric weights, so that p(z) = Σ w_i * p_i
+//     [s - z]₂                        second pairing input of the verification
+//
+// Services that open many commitments at the same challenge compute them once per point with an
+// `EvaluationCache` built from their settings. Points are kept up to the capacity of the cache,
+// after which the oldest one is dropped.
+
+/// Values at `z` shared by every opening at `z` under the same settings
+#[derive(Debug, Clone)]
+pub struct EvaluationPoint<TFr, TG2> {
+    z: TFr,
+    /// Index of `z` in the domain, when `z` is a root of unity
+    root_index: Option<usize>,
+    /// `1 / (ω_i - z)`, set to zero at `root_index`
+    #[cfg(feature = "prove")]
+    inverses: Vec<TFr>,
+    /// Barycentric weights outside of the domain. At `root_index = m`, the weights of the
+    /// quotient coefficient at `m` instead: `ω_i / (z * (z - ω_i))`, zero at `m`
+    weights: Vec<TFr>,
+    s_minus_z: TG2,
+}
+
+impl<TFr: Fr, TG2: G2> EvaluationPoint<TFr, TG2> {
+    pub fn z(&self) -> &TFr {
+        &self.z
+    }
+
+    /// Index of `z` in the domain, when `z` is a root of unity
+    pub fn root_index(&self) -> Option<usize> {
+        self.root_index
+    }
+
+    /// `[s - z]₂`
+    pub fn s_minus_z(&self) -> &TG2 {
+        &self.s_minus_z
+    }
+}
+
+/// Cache of `EvaluationPoint`s, for the settings it was built from
+///
+/// The cache is locked internally and can be shared between threads.
+pub struct EvaluationCache<TFr, TG2> {
+    roots_of_unity: Vec<TFr>,
+    s_g2: TG2,
+    /// Whether `s_g2` is that of the development setup, see `dev_setup`
+    #[cfg(feature = "verify")]
+    insecure_dev_setup: bool,
+    capacity: usize,
+    points: RwLock<EvaluationPoints<TFr, TG2>>,
+}
+
+struct EvaluationPoints<TFr, TG2> {
+    by_z: BTreeMap<[u8; 32], Arc<EvaluationPoint<TFr, TG2>>>,
+    /// Keys in insertion order, oldest first
+    order: VecDeque<[u8; 32]>,
+}
+
+impl<TFr: Fr + Copy, TG2: G2 + G2Mul<TFr>> EvaluationCache<TFr, TG2> {
+    /// Build an empty cache keeping up to `capacity` points for the settings `s`
+    pub fn new<
+        TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+        TFFTSettings: FFTSettings<TFr>,
+        TPoly: Poly<TFr>,
+        TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+        TG1Fp: G1Fp,
+        TG1Affine: G1Affine<TG1, TG1Fp>,
+    >(
+        s: &TKZGSettings,
+        capacity: usize,
+    ) -> Result<Self, String> {
+        if capacity == 0 {
+            return Err(String::from("Cache capacity must be non-zero"));
+        }
+        let roots_of_unity = s
+            .get_fft_settings()
+            .get_roots_of_unity()
+            .get(..FIELD_ELEMENTS_PER_BLOB)
+            .ok_or_else(|| String::from("Settings must cover the blob domain"))?
+            .to_vec();
+        let s_g2 = s
+            .get_g2_secret()
+            .get(1)
+            .ok_or_else(|| String::from("Trusted setup must contain at least two G2 points"))?
+            .clone();
+
+        Ok(Self {
+            roots_of_unity,
+            #[cfg(feature = "verify")]
+            insecure_dev_setup: is_insecure_dev_tau(&s_g2),
+            s_g2,
+            capacity,
+            points: RwLock::new(EvaluationPoints {
+                by_z: BTreeMap::new(),
+                order: VecDeque::new(),
+            }),
+        })
+    }
+
+    /// Number of cached points
+    pub fn len(&self) -> usize {
+        self.points.read().by_z.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&self) {
+        let mut points = self.points.write();
+        points.by_z.clear();
+        points.order.clear();
+    }
+
+    /// Values at `z`, computed on the first call for `z`
+    pub fn point(&self, z: &TFr) -> Result<Arc<EvaluationPoint<TFr, TG2>>, String> {
+        let key = z.to_bytes();
+        if let Some(point) = self.points.read().by_z.get(&key) {
+            return Ok(point.clone());
+        }
+
+        // Computed without the lock, a concurrent miss on the same point computes it twice
+        let point = Arc::new(self.compute_point(z)?);
+
+        let mut points = self.points.write();
+        if let Some(point) = points.by_z.get(&key) {
+            return Ok(point.clone());
+        }
+        if points.order.len() == self.capacity {
+            if let Some(oldest) = points.order.pop_front() {
+                points.by_z.remove(&oldest);
+            }
+        }
+        points.order.push_back(key);
+        points.by_z.insert(key, point.clone());
+        Ok(point)
+    }
+
+    fn compute_point(&self, z: &TFr) -> Result<EvaluationPoint<TFr, TG2>, String> {
+        let n = self.roots_of_unity.len();
+        let root_index = self.roots_of_unity.iter().position(|root| root.equals(z));
+
+        let mut inverses_in = try_vec(TFr::one(), n)?;
+        let mut inverses = try_vec(TFr::default(), n)?;
+        for (i, root) in self.roots_of_unity.iter().enumerate() {
+            if Some(i) != root_index {
+                inverses_in[i] = root.sub(z);
+            }
+        }
+        fr_batch_inv(&mut inverses, &inverses_in, n)?;
+
+        // With z = ω_m:   ω_i / (z * (z - ω_i)) = -ω_i / (ω_i - z) * 1/z
+        // Otherwise:      ω_i / (z - ω_i) * (z^n - 1)/n = -ω_i / (ω_i - z) * (z^n - 1)/n
+        let scale = match root_index {
+            Some(m) => {
+                inverses[m] = TFr::zero();
+                z.inverse()
+            }
+            None => z.pow(n).sub(&TFr::one()).div(&TFr::from_u64(n as u64))?,
+        }
+        .negate();
+        let weights = self
+            .roots_of_unity
+            .iter()
+            .zip(&inverses)
+            .map(|(root, inverse)| root.mul(inverse).mul(&scale))
+            .collect();
+
+        Ok(EvaluationPoint {
+            z: *z,
+            root_index,
+            #[cfg(feature = "prove")]
+            inverses,
+            weights,
+            s_minus_z: g2_s_minus_z(&self.s_g2, z),
+        })
+    }
+
+    /// Evaluate the polynomial in evaluation form `p` at `z`, same as
+    /// `evaluate_polynomial_in_evaluation_form`
+    pub fn evaluate<TPoly: Poly<TFr>>(&self, p: &TPoly, z: &TFr) -> Result<TFr, String> {
+        let point = self.point(z)?;
+        Self::evaluate_at(&point, p)
+    }
+
+    fn evaluate_at<TPoly: Poly<TFr>>(
+        point: &EvaluationPoint<TFr, TG2>,
+        p: &TPoly,
+    ) -> Result<TFr, String> {
+        if p.len() != FIELD_ELEMENTS_PER_BLOB {
+            return Err(String::from("Incorrect field elements count."));
+        }
+        let coeffs = p.get_coeffs();
+        if let Some(m) = point.root_index {
+            return Ok(coeffs[m]);
+        }
+
+        Ok(coeffs
+            .iter()
+            .zip(&point.weights)
+            .fold(TFr::zero(), |acc, (coeff, weight)| {
+                acc.add(&coeff.mul(weight))
+            }))
+    }
+
+    /// Compute the proof of the blob at `z` and the evaluation, same as `compute_kzg_proof_rust`
+    #[cfg(feature = "prove")]
+    pub fn compute_kzg_proof<
+        TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + G1LinComb<TFr, TG1Fp, TG1Affine>,
+        TFFTSettings: FFTSettings<TFr>,
+        TPoly: Poly<TFr>,
+        TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+        TG1Fp: G1Fp,
+        TG1Affine: G1Affine<TG1, TG1Fp>,
+    >(
+        &self,
+        blob: &[TFr],
+        z: &TFr,
+        s: &TKZGSettings,
+    ) -> Result<(TG1, TFr), String> {
+        let point = self.point(z)?;
+        let polynomial: TPoly = blob_to_polynomial(blob)?;
+        let y = Self::evaluate_at(&point, &polynomial)?;
+
+        // (p_i - y) / (ω_i - z), and at z = ω_m: Σ (p_i - y) * ω_i / (z * (z - ω_i))
+        let mut q = try_vec(TFr::zero(), FIELD_ELEMENTS_PER_BLOB)?;
+        let mut q_m = TFr::zero();
+        for (i, coeff) in polynomial.get_coeffs().iter().enumerate() {
+            let numerator = coeff.sub(&y);
+            q[i] = numerator.mul(&point.inverses[i]);
+            if point.root_index.is_some() {
+                q_m = q_m.add(&numerator.mul(&point.weights[i]));
+            }
+        }
+        if let Some(m) = point.root_index {
+            q[m] = q_m;
+        }
+
+        let proof = TG1::g1_lincomb(
+            s.get_g1_secret(),
+            &q,
+            FIELD_ELEMENTS_PER_BLOB,
+            s.get_precomputation().as_deref(),
+        );
+        Ok((proof, y))
+    }
+
+    /// Check the proof that the polynomial committed to by `commitment` is `y` at `z`, same as
+    /// `verify_kzg_proof_rust`
+    #[cfg(feature = "verify")]
+    pub fn verify_kzg_proof<TG1: G1 + G1Mul<TFr> + PairingVerify<TG1, TG2>>(
+        &self,
+        commitment: &TG1,
+        z: &TFr,
+        y: &TFr,
+        proof: &TG1,
+    ) -> Result<bool, String> {
+        if !commitment.is_inf() && !commitment.is_valid() {
+            return Err(String::from("Invalid commitment"));
+        }
+        if !proof.is_inf() && !proof.is_valid() {
+            return Err(String::from("Invalid proof"));
+        }
+        let point = self.point(z)?;
+        if self.insecure_dev_setup {
+            report_insecure_dev_setup();
+        }
+
+        Ok(TG1::verify(
+            &commitment_minus_value(commitment, y),
+            &TG2::generator(),
+            proof,
+            &point.s_minus_z,
+        ))
+    }
+}
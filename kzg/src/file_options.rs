@@ -0,0 +1,110 @@
+extern crate alloc;
+
+#[cfg(not(feature = "zstd"))]
+use alloc::string::String;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+
+use crate::error::KzgError;
+use crate::Compression;
+
+////////////////////////////// File options //////////////////////////////
+//
+// Setup files and precomputation tables shipped in containers are large. The `_with_options`
+// variants of the file functions take a `FileOptions` choosing the encoding of the G1 points of
+// setup files and, with the `zstd` feature, a zstd level to compress the whole file with.
+//
+// Curve points are close to uniformly random bytes, so zstd leaves files made only of points
+// about as large as they were. Compressed point encoding is what makes setup files smaller.
+//
+// Readers recognize a zstd frame by its magic number at the start of the file, so compressed and
+// plain files load through the same functions, and `zstd_level` is only read when writing.
+// Without the `zstd` feature, writing with a level and reading a compressed file fail with
+// `KzgError::Unsupported`. Compressed tables cannot be mapped with `load_mmap`.
+
+/// Magic number at the start of a zstd frame
+pub const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+
+/// Encoding of setup and table files
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileOptions {
+    /// Encoding of the G1 points of setup files, the points of tables are always stored as in
+    /// memory
+    pub point_compression: Compression,
+    /// zstd level the whole file is compressed with, `None` for a plain file
+    pub zstd_level: Option<i32>,
+}
+
+impl Default for FileOptions {
+    /// Compressed points and no zstd, the format of the functions without options
+    fn default() -> Self {
+        Self {
+            point_compression: Compression::Compressed,
+            zstd_level: None,
+        }
+    }
+}
+
+impl FileOptions {
+    /// Default options, compressing the file with zstd at `level`
+    pub fn zstd(level: i32) -> Self {
+        Self {
+            zstd_level: Some(level),
+            ..Self::default()
+        }
+    }
+}
+
+#[cfg(not(feature = "zstd"))]
+fn zstd_disabled() -> KzgError {
+    KzgError::Unsupported(String::from(
+        "zstd compressed files require the zstd feature",
+    ))
+}
+
+/// Create the file at `path` and pass a writer to it to `write`, compressing with zstd as
+/// required by `options`
+pub(crate) fn write_file(
+    path: &str,
+    options: &FileOptions,
+    write: impl FnOnce(&mut dyn Write) -> Result<(), KzgError>,
+) -> Result<(), KzgError> {
+    let mut writer = BufWriter::new(File::create(path)?);
+
+    match options.zstd_level {
+        None => {
+            write(&mut writer)?;
+            writer.flush()?;
+
+            Ok(())
+        }
+        #[cfg(feature = "zstd")]
+        Some(level) => {
+            let mut encoder = zstd::Encoder::new(writer, level)?;
+            write(&mut encoder)?;
+            encoder.finish()?.flush()?;
+
+            Ok(())
+        }
+        #[cfg(not(feature = "zstd"))]
+        Some(_) => Err(zstd_disabled()),
+    }
+}
+
+/// Open the file at `path` and pass a reader of its contents to `read`, decompressing files that
+/// start with a zstd frame
+pub(crate) fn read_file<T>(
+    path: &str,
+    read: impl FnOnce(&mut dyn Read) -> Result<T, KzgError>,
+) -> Result<T, KzgError> {
+    let mut reader = BufReader::new(File::open(path)?);
+
+    if !reader.fill_buf()?.starts_with(&ZSTD_MAGIC) {
+        return read(&mut reader);
+    }
+
+    #[cfg(feature = "zstd")]
+    return read(&mut zstd::Decoder::with_buffer(reader)?);
+    #[cfg(not(feature = "zstd"))]
+    Err(zstd_disabled())
+}
@@ -0,0 +1,223 @@
+extern crate alloc;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use std::io::{Read, Write};
+
+use sha2::{Digest, Sha256};
+
+use crate::envelope::CurveId;
+use crate::error::KzgError;
+use crate::file_options::{read_file, write_file, FileOptions};
+use crate::{Compression, G1};
+
+////////////////////////////// FK20 Toeplitz FFT cache files //////////////////////////////
+//
+// FK20 computes all the proofs of a polynomial with one Toeplitz matrix-vector product per offset
+// in a chunk. The matrices only depend on the setup, so `FK20MultiSettings::new` computes the FFT
+// of each of them once, `chunk_len` G1 FFTs of `n2 / chunk_len` points, and every later call of
+// `data_availability` reuses them. For the cells of EIP-7594 these FFTs take seconds, paid by
+// every process building its `CellSettings`.
+//
+// The FFTs can be written to a cache file, kept next to the BGMW table file of the same setup at
+// `fk20_cache_path(table_path)`, and read back instead of computed:
+//
+//     magic || version || curve id || compression || n2 || chunk_len || file length
+//           || setup digest || points digest || points ..
+//
+// with the dimensions as u64 LE. The setup digest is the SHA-256 of the G1 points of the settings
+// the FFTs were computed from, so a cache is never used with another setup, and the points digest
+// that of the encoded points, so corrupted files are rejected. Points are uncompressed when the
+// backend supports it, which decodes faster, and compressed otherwise.
+
+/// First bytes of FK20 cache files
+pub const FK20_CACHE_MAGIC: [u8; 8] = *b"KZGFK20C";
+
+/// Version of the FK20 cache file format, bumped on incompatible changes
+pub const FK20_CACHE_VERSION: u8 = 1;
+
+/// Size of the header of FK20 cache files
+pub const FK20_CACHE_HEADER_SIZE: usize = 128;
+
+/// Suffix of the FK20 cache file of a precomputation table file
+pub const FK20_CACHE_SUFFIX: &str = ".fk20";
+
+/// Offset of the dimensions in the header
+const FK20_CACHE_FIELDS_OFFSET: usize = 16;
+
+/// Offset of the setup digest in the header, followed by the points digest
+const FK20_CACHE_DIGEST_OFFSET: usize = FK20_CACHE_FIELDS_OFFSET + 3 * 8;
+
+/// Path of the FK20 cache kept next to the precomputation table file at `table_path`
+pub fn fk20_cache_path(table_path: &str) -> String {
+    format!("{}{}", table_path, FK20_CACHE_SUFFIX)
+}
+
+/// SHA-256 digest of the compressed `g1` points of a setup
+pub fn setup_g1_digest<TG1: G1>(g1: &[TG1]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    for point in g1 {
+        hasher.update(point.to_bytes());
+    }
+
+    hasher.finalize().into()
+}
+
+/// Write the Toeplitz FFTs `files` of FK20 settings of size `n2` and chunk length `chunk_len`,
+/// built from the setup of digest `setup_digest`, see `setup_g1_digest`
+pub fn write_fk20_cache<TG1: G1, W: Write + ?Sized>(
+    writer: &mut W,
+    n2: usize,
+    chunk_len: usize,
+    setup_digest: &[u8; 32],
+    files: &[Vec<TG1>],
+) -> Result<(), KzgError> {
+    if chunk_len == 0
+        || files.len() != chunk_len
+        || files.iter().any(|file| file.len() != n2 / chunk_len)
+    {
+        return Err(KzgError::InvalidArgument(String::from(
+            "Toeplitz FFTs do not match n2 and chunk_len",
+        )));
+    }
+
+    let points = files.concat();
+    let (compression, bytes) = match TG1::batch_to_bytes(&points, Compression::Uncompressed) {
+        Ok(bytes) => (Compression::Uncompressed, bytes),
+        Err(_) => (
+            Compression::Compressed,
+            TG1::batch_to_bytes(&points, Compression::Compressed)?,
+        ),
+    };
+
+    let mut header = [0u8; FK20_CACHE_HEADER_SIZE];
+    header[..8].copy_from_slice(&FK20_CACHE_MAGIC);
+    header[8] = FK20_CACHE_VERSION;
+    header[9] = CurveId::Bls12_381 as u8;
+    header[10] = (compression == Compression::Uncompressed) as u8;
+    for (bytes, field) in header[FK20_CACHE_FIELDS_OFFSET..FK20_CACHE_DIGEST_OFFSET]
+        .chunks_exact_mut(8)
+        .zip([n2, chunk_len, n2 / chunk_len])
+    {
+        bytes.copy_from_slice(&(field as u64).to_le_bytes());
+    }
+    header[FK20_CACHE_DIGEST_OFFSET..FK20_CACHE_DIGEST_OFFSET + 32].copy_from_slice(setup_digest);
+    header[FK20_CACHE_DIGEST_OFFSET + 32..FK20_CACHE_DIGEST_OFFSET + 64]
+        .copy_from_slice(&Sha256::digest(&bytes));
+
+    writer.write_all(&header)?;
+    writer.write_all(&bytes)?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+/// Read the Toeplitz FFTs written by `write_fk20_cache`, rejecting caches of other dimensions or
+/// of another setup and files whose points do not match their digest
+pub fn read_fk20_cache<TG1: G1, R: Read + ?Sized>(
+    reader: &mut R,
+    n2: usize,
+    chunk_len: usize,
+    setup_digest: &[u8; 32],
+) -> Result<Vec<Vec<TG1>>, KzgError> {
+    if chunk_len == 0 || n2 % chunk_len != 0 {
+        return Err(KzgError::InvalidArgument(String::from(
+            "chunk_len must divide n2",
+        )));
+    }
+
+    let mut header = [0u8; FK20_CACHE_HEADER_SIZE];
+    reader.read_exact(&mut header)?;
+    if header[..8] != FK20_CACHE_MAGIC {
+        return Err(KzgError::InvalidFormat(String::from(
+            "Not an FK20 cache file",
+        )));
+    }
+    if header[8] != FK20_CACHE_VERSION {
+        return Err(KzgError::SetupMismatch(format!(
+            "Unsupported FK20 cache file version {}",
+            header[8]
+        )));
+    }
+    if CurveId::from_u8(header[9]).map_err(KzgError::SetupMismatch)? != CurveId::Bls12_381 {
+        return Err(KzgError::SetupMismatch(String::from(
+            "FK20 cache file does not hold BLS12-381 points",
+        )));
+    }
+    let compression = match header[10] {
+        0 => Compression::Compressed,
+        1 => Compression::Uncompressed,
+        _ => {
+            return Err(KzgError::InvalidFormat(String::from(
+                "Unknown FK20 cache point encoding",
+            )))
+        }
+    };
+
+    let mut fields = [0u64; 3];
+    for (field, bytes) in fields
+        .iter_mut()
+        .zip(header[FK20_CACHE_FIELDS_OFFSET..FK20_CACHE_DIGEST_OFFSET].chunks_exact(8))
+    {
+        let mut le = [0u8; 8];
+        le.copy_from_slice(bytes);
+        *field = u64::from_le_bytes(le);
+    }
+    if fields[..2] != [n2 as u64, chunk_len as u64] || fields[2] != (n2 / chunk_len) as u64 {
+        return Err(KzgError::SetupMismatch(format!(
+            "FK20 cache was built for n2 {} and chunk_len {}, not {} and {}",
+            fields[0], fields[1], n2, chunk_len
+        )));
+    }
+    if header[FK20_CACHE_DIGEST_OFFSET..FK20_CACHE_DIGEST_OFFSET + 32] != setup_digest[..] {
+        return Err(KzgError::SetupMismatch(String::from(
+            "FK20 cache was built from another setup",
+        )));
+    }
+
+    let file_len = n2 / chunk_len;
+    let mut bytes = vec![0u8; chunk_len * file_len * compression.g1_size()];
+    reader.read_exact(&mut bytes)?;
+    if Sha256::digest(&bytes)[..]
+        != header[FK20_CACHE_DIGEST_OFFSET + 32..FK20_CACHE_DIGEST_OFFSET + 64]
+    {
+        return Err(KzgError::InvalidFormat(String::from(
+            "FK20 cache file is corrupted, the points do not match the digest in its header",
+        )));
+    }
+
+    let points = TG1::batch_from_bytes(&bytes, compression)?;
+
+    Ok(points
+        .chunks_exact(file_len)
+        .map(|file| file.to_vec())
+        .collect())
+}
+
+/// Write the Toeplitz FFTs to the file at `path`, see `write_fk20_cache`
+pub fn write_fk20_cache_file<TG1: G1>(
+    path: &str,
+    options: &FileOptions,
+    n2: usize,
+    chunk_len: usize,
+    setup_digest: &[u8; 32],
+    files: &[Vec<TG1>],
+) -> Result<(), KzgError> {
+    write_file(path, options, |writer| {
+        write_fk20_cache(writer, n2, chunk_len, setup_digest, files)
+    })
+}
+
+/// Read the FK20 cache file at `path`, plain or zstd compressed, see `read_fk20_cache`
+pub fn read_fk20_cache_file<TG1: G1>(
+    path: &str,
+    n2: usize,
+    chunk_len: usize,
+    setup_digest: &[u8; 32],
+) -> Result<Vec<Vec<TG1>>, KzgError> {
+    read_file(path, |reader| {
+        read_fk20_cache(reader, n2, chunk_len, setup_digest)
+    })
+}
@@ -0,0 +1,294 @@
+extern crate alloc;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use std::io::{self, ErrorKind, Read, Write};
+
+use crate::eip_4844::BYTES_PER_G1;
+
+////////////////////////////// CRC-protected framing //////////////////////////////
+//
+// Setup and table files of several gigabytes are read back in full and checked as a whole, so a
+// single flipped bit on the storage medium invalidates the entire artifact. `FramedWriter` and
+// `FramedReader` wrap any other writer and reader and cut the stream into frames, each with its
+// own CRC32:
+//
+//     magic || frame size (u32 LE) || [length (u32 LE) || crc32 (u32 LE) || data] ..
+//
+// Every frame but the last holds exactly `frame size` bytes of data. The serializers of this
+// crate are unaware of the framing:
+//
+//     let mut writer = FramedWriter::new(BufWriter::new(file), DEFAULT_FRAME_SIZE)?;
+//     write_trusted_setup::<TFr, TG1, TG2, _>(&mut writer, num_g1, num_g2, secret)?;
+//     writer.finish()?;
+//
+// A corrupted frame fails the read with the index and byte range of the frame, and `scan_frames`
+// lists every damaged frame of a file, so that only the affected ranges need to be regenerated.
+
+/// Magic bytes at the start of a framed stream
+pub const FRAME_MAGIC: [u8; 8] = *b"KZGCRC32";
+
+/// Size of the stream header preceding the first frame
+pub const FRAMED_HEADER_SIZE: usize = FRAME_MAGIC.len() + 4;
+
+/// Size of the header preceding the data of each frame
+pub const FRAME_HEADER_SIZE: usize = 8;
+
+/// Frames of 2^16 compressed G1 points
+pub const DEFAULT_FRAME_SIZE: usize = (1 << 16) * BYTES_PER_G1;
+
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// CRC32 (IEEE 802.3) of `bytes`
+pub fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0u32, |crc, byte| {
+        (crc >> 8) ^ CRC32_TABLE[((crc ^ *byte as u32) & 0xFF) as usize]
+    })
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, message)
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    let mut le = [0u8; 4];
+    le.copy_from_slice(bytes);
+    u32::from_le_bytes(le)
+}
+
+fn read_stream_header<R: Read + ?Sized>(reader: &mut R) -> io::Result<usize> {
+    let mut header = [0u8; FRAMED_HEADER_SIZE];
+    reader.read_exact(&mut header)?;
+    if header[..FRAME_MAGIC.len()] != FRAME_MAGIC {
+        return Err(invalid_data(String::from("Not a framed stream")));
+    }
+    let frame_size = read_u32(&header[FRAME_MAGIC.len()..]) as usize;
+    if frame_size == 0 {
+        return Err(invalid_data(String::from("Invalid frame size 0")));
+    }
+
+    Ok(frame_size)
+}
+
+/// Read the next frame into `data` and tell whether it matches its checksum, `None` at the end of
+/// the stream
+fn read_frame<R: Read + ?Sized>(
+    reader: &mut R,
+    frame_size: usize,
+    data: &mut Vec<u8>,
+) -> io::Result<Option<bool>> {
+    let mut header = [0u8; FRAME_HEADER_SIZE];
+    let mut filled = 0;
+    while filled < FRAME_HEADER_SIZE {
+        match reader.read(&mut header[filled..]) {
+            Ok(0) if filled == 0 => return Ok(None),
+            Ok(0) => return Err(ErrorKind::UnexpectedEof.into()),
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    let len = read_u32(&header[..4]) as usize;
+    if len == 0 || len > frame_size {
+        // A damaged length is replaced by the frame size, which every frame but the last has
+        data.clear();
+        (&mut *reader).take(frame_size as u64).read_to_end(data)?;
+        return Ok(Some(false));
+    }
+    data.resize(len, 0);
+    reader.read_exact(data)?;
+
+    Ok(Some(crc32(data) == read_u32(&header[4..])))
+}
+
+/// Writer cutting the stream into CRC32-protected frames of `frame_size` bytes
+pub struct FramedWriter<W: Write> {
+    writer: Option<W>,
+    frame_size: usize,
+    frame: Vec<u8>,
+}
+
+impl<W: Write> FramedWriter<W> {
+    /// Write the stream header to `writer`
+    pub fn new(mut writer: W, frame_size: usize) -> Result<Self, String> {
+        let size = u32::try_from(frame_size)
+            .ok()
+            .filter(|size| *size != 0)
+            .ok_or_else(|| format!("Invalid frame size {}", frame_size))?;
+        writer
+            .write_all(&FRAME_MAGIC)
+            .and_then(|_| writer.write_all(&size.to_le_bytes()))
+            .map_err(|e| format!("I/O error: {}", e))?;
+
+        Ok(Self {
+            writer: Some(writer),
+            frame_size,
+            frame: Vec::with_capacity(frame_size),
+        })
+    }
+
+    fn write_frame(&mut self) -> io::Result<()> {
+        if self.frame.is_empty() {
+            return Ok(());
+        }
+        let writer = self
+            .writer
+            .as_mut()
+            .expect("writer is only taken by finish");
+        writer.write_all(&(self.frame.len() as u32).to_le_bytes())?;
+        writer.write_all(&crc32(&self.frame).to_le_bytes())?;
+        writer.write_all(&self.frame)?;
+        self.frame.clear();
+
+        Ok(())
+    }
+
+    /// Write the last, possibly partial, frame and return the underlying writer. Dropping the
+    /// writer does the same but ignores errors.
+    pub fn finish(mut self) -> Result<W, String> {
+        self.write_frame()
+            .and_then(|_| self.flush())
+            .map_err(|e| format!("I/O error: {}", e))?;
+
+        Ok(self.writer.take().expect("writer is only taken by finish"))
+    }
+}
+
+impl<W: Write> Write for FramedWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = buf.len().min(self.frame_size - self.frame.len());
+        self.frame.extend_from_slice(&buf[..len]);
+        if self.frame.len() == self.frame_size {
+            self.write_frame()?;
+        }
+
+        Ok(len)
+    }
+
+    /// Flush the underlying writer. The current frame stays buffered until it is full, as only
+    /// the last frame may be partial.
+    fn flush(&mut self) -> io::Result<()> {
+        match self.writer.as_mut() {
+            Some(writer) => writer.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<W: Write> Drop for FramedWriter<W> {
+    fn drop(&mut self) {
+        if self.writer.is_some() {
+            let _ = self.write_frame().and_then(|_| self.flush());
+        }
+    }
+}
+
+/// Reader of a stream written by `FramedWriter`, failing on the first corrupted frame
+pub struct FramedReader<R: Read> {
+    reader: R,
+    frame_size: usize,
+    frame: Vec<u8>,
+    pos: usize,
+    index: usize,
+}
+
+impl<R: Read> FramedReader<R> {
+    /// Read the stream header from `reader`
+    pub fn new(mut reader: R) -> Result<Self, String> {
+        let frame_size = read_stream_header(&mut reader).map_err(|e| format!("{}", e))?;
+
+        Ok(Self {
+            reader,
+            frame_size,
+            frame: Vec::new(),
+            pos: 0,
+            index: 0,
+        })
+    }
+
+    pub fn frame_size(&self) -> usize {
+        self.frame_size
+    }
+}
+
+impl<R: Read> Read for FramedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.pos == self.frame.len() {
+            self.pos = 0;
+            match read_frame(&mut self.reader, self.frame_size, &mut self.frame)? {
+                None => {
+                    self.frame.clear();
+                    return Ok(0);
+                }
+                Some(false) => {
+                    let start = self.index * self.frame_size;
+                    return Err(invalid_data(format!(
+                        "Frame {} (bytes {}..{}) is corrupted",
+                        self.index,
+                        start,
+                        start + self.frame_size
+                    )));
+                }
+                Some(true) => self.index += 1,
+            }
+        }
+
+        let len = buf.len().min(self.frame.len() - self.pos);
+        buf[..len].copy_from_slice(&self.frame[self.pos..self.pos + len]);
+        self.pos += len;
+
+        Ok(len)
+    }
+}
+
+/// Indices of the frames of a framed stream that do not match their checksum. Frame `i` covers
+/// the bytes `[i * frame_size, (i + 1) * frame_size)` of the unframed data.
+pub fn scan_frames<R: Read + ?Sized>(reader: &mut R) -> Result<Vec<usize>, String> {
+    let frame_size = read_stream_header(reader).map_err(|e| format!("{}", e))?;
+
+    let mut damaged = Vec::new();
+    let mut data = vec![];
+    let mut index = 0;
+    while let Some(intact) =
+        read_frame(reader, frame_size, &mut data).map_err(|e| format!("I/O error: {}", e))?
+    {
+        if !intact {
+            damaged.push(index);
+        }
+        index += 1;
+    }
+
+    Ok(damaged)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::framing::crc32;
+
+    #[test]
+    fn crc32_must_match_reference_values() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+}
@@ -0,0 +1,208 @@
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use sha2::{Digest, Sha256};
+
+use crate::eip_4844::{
+    blob_to_kzg_commitment_rust, bytes_to_blob, compute_blob_kzg_proof_rust, BYTES_PER_BLOB,
+    BYTES_PER_FIELD_ELEMENT, FIELD_ELEMENTS_PER_BLOB,
+};
+#[cfg(feature = "std")]
+use crate::error::KzgError;
+#[cfg(feature = "std")]
+use crate::msm::precompute::PrecomputationTable;
+use crate::{
+    FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1LinComb, G1Mul, KZGSettings, Poly, G1, G2,
+};
+
+////////////////////////////// Golden vectors //////////////////////////////
+//
+// Fixed outputs for deterministic inputs. Any change to the serialization of settings,
+// commitments, proofs or precomputation table files shows up as a mismatch against these values.
+// When such a change is intentional, regenerate the constants with `generate_golden_vectors`,
+// `settings_digest` and `golden_table_file_prefix`.
+
+/// Seeds of the blobs used for the golden vectors
+pub const GOLDEN_SEEDS: [u64; 3] = [0, 1, 0xdead_beef];
+
+/// SHA-256 of the serialized mainnet trusted setup, as returned by `settings_digest`
+pub const GOLDEN_MAINNET_SETUP_DIGEST: &str =
+    "0x67d34d181ca3dfaa2d8ab9815f8b34b6b04633d0cfbee3ddc830123320a33c41";
+
+/// Commitments and proofs of the blobs built from `GOLDEN_SEEDS`, against the mainnet setup
+pub const GOLDEN_VECTORS: [GoldenVector<&str>; 3] = [
+    GoldenVector {
+        seed: 0,
+        blob_digest: "0x5dd82fee0fbe07f025a6c98377faafd9afde6c12be90a7bbfacbad1222a6a42c",
+        commitment: "0x998ae06dfaaa6dfbb3a3d76c71d399798a2b00ac3a47a6b8bce58a86cbb681045f4952083db98032af7704a5a3d4c1b8",
+        proof: "0xacceb6a9af75362b9dc91be85506870be7a279cf490dba5ab2744394f012a1bee947d1a40968a9a04acc6c4850e88feb",
+    },
+    GoldenVector {
+        seed: 1,
+        blob_digest: "0x0bafa152707e2a44744cc6cdb9d88618eba834ede3ef86d877700c8eefd3b6d3",
+        commitment: "0xa0c5fcc3a4ab6ca3d00c0710213a508061b3c8d670745bf2e3165978b56ba6b4656795aef80ebceab1e764cf781c81a9",
+        proof: "0x9257c8700db419dba9f77fdab418cd58af35244b9cda0d8ebf60a94ce75b300c6d3806112538599f235440f3cbbb406d",
+    },
+    GoldenVector {
+        seed: 0xdead_beef,
+        blob_digest: "0x72b58bf2ca6544983e583a40fffd27bd5df5a2b63464381b986b1e3f8072d9d7",
+        commitment: "0x860030e877bebf7f2d74de6223079b271600a8e8f4311d8050e3e7a7974e262720bc5988c0881a0eee3b8bdbb42d8995",
+        proof: "0x948b8a648a66b029a912f3b80983475f0e046d0b27919e4a26ddfa699a7c9ad6a0defffe675c23de4c6b66e44519d8ff",
+    },
+];
+
+/// Number of points `[i]G`, `i` from 1, of the golden table file. At most 32, so that parallel
+/// builds do not split the table by thread count
+pub const GOLDEN_TABLE_POINTS: usize = 16;
+
+/// Window of the golden table file
+pub const GOLDEN_TABLE_WINDOW: usize = 8;
+
+/// Header of the golden table file followed by its first point, as returned by
+/// `golden_table_file_prefix`
+pub const GOLDEN_TABLE_FILE_PREFIX: &str = concat!(
+    "0x",
+    "4b5a4742474d5754010100000000000000000000000000000800000000000000",
+    "0000000000000000000000000000000010000000000000002000000000000000",
+    "600000000000000000000000000000009dbbb70f4f8b5c35493bb08a2dcc4429",
+    "1c435c3646dca41af36f0ea232c9620000000000000000000000000000000000",
+    "160c53fd9087b35cf5ff769967fc1778c1a13b14c7954f1547e7d0f3cd6aaef0",
+    "40f4db21cc6eceed75fb0b9e417701127122e70cd593acba8efd18791a63228c",
+    "ce250757135f59dd945140502958ac51c05900ad3f8c1c0e6aa20850fc3ebc0b",
+);
+
+/// Size of the header of table files followed by one point, two 48-byte coordinates
+#[cfg(feature = "std")]
+const GOLDEN_TABLE_FILE_PREFIX_SIZE: usize = 128 + 2 * 48;
+
+/// Serialized outputs for a single seeded blob, as `0x`-prefixed hex strings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GoldenVector<T> {
+    pub seed: u64,
+    pub blob_digest: T,
+    pub commitment: T,
+    pub proof: T,
+}
+
+impl GoldenVector<String> {
+    pub fn matches(&self, expected: &GoldenVector<&str>) -> bool {
+        self.seed == expected.seed
+            && self.blob_digest == expected.blob_digest
+            && self.commitment == expected.commitment
+            && self.proof == expected.proof
+    }
+}
+
+/// Encode bytes as a `0x`-prefixed lowercase hex string
+pub fn bytes_to_hex(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+    let mut out = String::with_capacity(2 + bytes.len() * 2);
+    out.push_str("0x");
+    for byte in bytes {
+        out.push(DIGITS[(byte >> 4) as usize] as char);
+        out.push(DIGITS[(byte & 0xf) as usize] as char);
+    }
+    out
+}
+
+/// Build a canonical blob deterministically from `seed`.
+///
+/// Every field element is `SHA-256(seed || index)` with the most significant byte cleared, so it
+/// is always smaller than the BLS modulus.
+pub fn seeded_blob_bytes(seed: u64) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(BYTES_PER_BLOB);
+
+    for i in 0..FIELD_ELEMENTS_PER_BLOB as u64 {
+        let mut hasher = Sha256::new();
+        hasher.update(seed.to_be_bytes());
+        hasher.update(i.to_be_bytes());
+        let mut element: [u8; BYTES_PER_FIELD_ELEMENT] = hasher.finalize().into();
+        element[0] = 0;
+        bytes.extend_from_slice(&element);
+    }
+
+    bytes
+}
+
+/// Digest of the serialized trusted setup held by the settings (G1 points followed by G2 points)
+pub fn settings_digest<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    s: &TKZGSettings,
+) -> String {
+    let mut hasher = Sha256::new();
+    for point in s.get_g1_secret() {
+        hasher.update(point.to_bytes());
+    }
+    for point in s.get_g2_secret() {
+        hasher.update(point.to_bytes());
+    }
+    bytes_to_hex(&hasher.finalize())
+}
+
+/// Recompute the golden vectors for the given seeds
+pub fn generate_golden_vectors<
+    TFr: Fr + Copy,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + G1LinComb<TFr, TG1Fp, TG1Affine>,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    seeds: &[u64],
+    s: &TKZGSettings,
+) -> Result<Vec<GoldenVector<String>>, String> {
+    seeds
+        .iter()
+        .map(|&seed| {
+            let bytes = seeded_blob_bytes(seed);
+            let blob: Vec<TFr> = bytes_to_blob(&bytes)?;
+            let commitment = blob_to_kzg_commitment_rust(&blob, s)?;
+            let proof = compute_blob_kzg_proof_rust(&blob, &commitment, s)?;
+
+            Ok(GoldenVector {
+                seed,
+                blob_digest: bytes_to_hex(&Sha256::digest(&bytes)),
+                commitment: bytes_to_hex(&commitment.to_bytes()),
+                proof: bytes_to_hex(&proof.to_bytes()),
+            })
+        })
+        .collect()
+}
+
+/// Header and first point of the table file written by `PrecomputationTable::write_to_writer` for
+/// the golden table, `None` when precomputation tables are compiled out
+#[cfg(feature = "std")]
+pub fn golden_table_file_prefix<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>() -> Result<Option<String>, KzgError> {
+    let points = (1..=GOLDEN_TABLE_POINTS as u64)
+        .map(|i| TG1::generator().mul(&TFr::from_u64(i)))
+        .collect::<Vec<_>>();
+    let Some(table) = PrecomputationTable::<TFr, TG1, TG1Fp, TG1Affine>::new_with_window(
+        &points,
+        GOLDEN_TABLE_WINDOW,
+    )?
+    else {
+        return Ok(None);
+    };
+
+    let mut bytes = Vec::new();
+    table.write_to_writer(&mut bytes)?;
+    Ok(Some(bytes_to_hex(&bytes[..GOLDEN_TABLE_FILE_PREFIX_SIZE])))
+}
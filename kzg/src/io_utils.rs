@@ -0,0 +1,199 @@
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+#[cfg(feature = "parallel")]
+use std::sync::mpsc::sync_channel;
+
+use crate::error::KzgError;
+use crate::file_options::{read_file, write_file, FileOptions};
+use crate::setup_file::{
+    load_trusted_setup_from_file, load_trusted_setup_from_file_with_validation, read_trusted_setup,
+    read_trusted_setup_with_compression, read_trusted_setup_with_validation, write_header,
+    SETUP_FILE_CHUNK_SIZE,
+};
+use crate::validation::ValidationLevel;
+use crate::{Compression, G1, G2};
+
+////////////////////////////// Setup points on disk //////////////////////////////
+//
+// Save and load the monomial setup points passed to `KZGSettings::new`, for every backend. The
+// files use the format of `setup_file`, so a setup saved by one backend loads in any other and
+// `generate_trusted_setup_to_file` output loads here as well. The reader and writer variants take
+// any `Read` or `Write`, trait objects included, for setups held in memory, received over the
+// network or stored in compressed archives.
+//
+// Points are written by `batch_writer`, which with the `parallel` feature encodes chunks on worker
+// threads while the chunks before them are written, so that writing a large setup is bound by
+// the disk rather than by point compression.
+
+/// Number of encoded chunks each `batch_writer` worker keeps ahead of the writer
+pub const BATCH_WRITER_QUEUE_DEPTH: usize = 2;
+
+/// Write `items` to `writer` in chunks of `chunk_len` items, each encoded by `encode`. With the
+/// `parallel` feature, chunks are encoded on up to `parallelism::max_threads` worker threads and
+/// written in order as they are ready. At most `BATCH_WRITER_QUEUE_DEPTH` chunks per worker are held in memory, and nothing
+/// after a chunk that fails to encode is written
+pub fn batch_writer<T, W, F>(
+    writer: &mut W,
+    items: &[T],
+    chunk_len: usize,
+    encode: F,
+) -> Result<(), KzgError>
+where
+    T: Sync,
+    W: Write + ?Sized,
+    F: Fn(&[T]) -> Result<Vec<u8>, KzgError> + Sync,
+{
+    if chunk_len == 0 {
+        return Err(KzgError::InvalidArgument(String::from(
+            "Chunk length must be positive",
+        )));
+    }
+
+    #[cfg(feature = "parallel")]
+    {
+        let num_chunks = (items.len() + chunk_len - 1) / chunk_len;
+        let workers = crate::parallelism::max_threads().min(num_chunks);
+        if workers > 1 {
+            return std::thread::scope(|scope| {
+                // Worker `w` encodes chunks `w`, `w + workers`, .., so reading the queues in turn
+                // yields the chunks in order
+                let queues = (0..workers)
+                    .map(|w| {
+                        let (sender, receiver) = sync_channel(BATCH_WRITER_QUEUE_DEPTH);
+                        let encode = &encode;
+                        scope.spawn(move || {
+                            for chunk in items.chunks(chunk_len).skip(w).step_by(workers) {
+                                // The writer stopped on an error
+                                if sender.send(encode(chunk)).is_err() {
+                                    break;
+                                }
+                            }
+                        });
+                        receiver
+                    })
+                    .collect::<Vec<_>>();
+
+                for i in 0..num_chunks {
+                    let bytes = queues[i % workers].recv().map_err(|_| {
+                        KzgError::Other(String::from("Batch writer worker stopped"))
+                    })??;
+                    writer.write_all(&bytes)?;
+                }
+
+                Ok(())
+            });
+        }
+    }
+
+    for chunk in items.chunks(chunk_len) {
+        writer.write_all(&encode(chunk)?)?;
+    }
+
+    Ok(())
+}
+
+/// Write the setup points `secret_g1` and `secret_g2` to `writer`. Points are encoded in chunks
+/// by `batch_writer`, so that `writer` sees few large writes.
+pub fn save_secrets_to_writer<TG1: G1, TG2: G2, W: Write + ?Sized>(
+    writer: &mut W,
+    secret_g1: &[TG1],
+    secret_g2: &[TG2],
+) -> Result<(), KzgError> {
+    save_secrets_to_writer_with_compression(writer, secret_g1, secret_g2, Compression::Compressed)
+}
+
+/// Same as `save_secrets_to_writer`, encoding the G1 points with `compression`. Such files are
+/// read back with `read_trusted_setup_with_compression`
+pub fn save_secrets_to_writer_with_compression<TG1: G1, TG2: G2, W: Write + ?Sized>(
+    writer: &mut W,
+    secret_g1: &[TG1],
+    secret_g2: &[TG2],
+    compression: Compression,
+) -> Result<(), KzgError> {
+    write_header(writer, secret_g1.len(), secret_g2.len())?;
+    batch_writer(writer, secret_g1, SETUP_FILE_CHUNK_SIZE, |chunk| {
+        Ok(TG1::batch_to_bytes(chunk, compression)?)
+    })?;
+    batch_writer(writer, secret_g2, SETUP_FILE_CHUNK_SIZE, |chunk| {
+        Ok(TG2::batch_to_bytes(chunk))
+    })?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+/// Save the setup points `secret_g1` and `secret_g2` to the file at `path`, see
+/// `save_secrets_to_writer`
+pub fn save_secrets_to_file<TG1: G1, TG2: G2>(
+    path: &str,
+    secret_g1: &[TG1],
+    secret_g2: &[TG2],
+) -> Result<(), KzgError> {
+    let file = File::create(path)?;
+
+    save_secrets_to_writer(&mut BufWriter::new(file), secret_g1, secret_g2)
+}
+
+/// Same as `save_secrets_to_file`, encoding the G1 points and compressing the file as required
+/// by `options`
+pub fn save_secrets_to_file_with_options<TG1: G1, TG2: G2>(
+    path: &str,
+    secret_g1: &[TG1],
+    secret_g2: &[TG2],
+    options: &FileOptions,
+) -> Result<(), KzgError> {
+    write_file(path, options, |writer| {
+        save_secrets_to_writer_with_compression(
+            writer,
+            secret_g1,
+            secret_g2,
+            options.point_compression,
+        )
+    })
+}
+
+/// Load the setup points saved by `save_secrets_to_file`
+pub fn load_secrets_from_file<TG1: G1, TG2: G2>(
+    path: &str,
+) -> Result<(Vec<TG1>, Vec<TG2>), KzgError> {
+    load_trusted_setup_from_file(path)
+}
+
+/// Same as `load_secrets_from_file`, checking the G1 and G2 points as required by `level`
+pub fn load_secrets_from_file_with_validation<TG1: G1, TG2: G2>(
+    path: &str,
+    level: ValidationLevel,
+) -> Result<(Vec<TG1>, Vec<TG2>), KzgError> {
+    load_trusted_setup_from_file_with_validation(path, level)
+}
+
+/// Load the setup points saved by `save_secrets_to_file_with_options` with the same
+/// `point_compression`, checking them as required by `level`. The zstd level is not needed
+pub fn load_secrets_from_file_with_options<TG1: G1, TG2: G2>(
+    path: &str,
+    options: &FileOptions,
+    level: ValidationLevel,
+) -> Result<(Vec<TG1>, Vec<TG2>), KzgError> {
+    read_file(path, |reader| {
+        read_trusted_setup_with_compression(reader, options.point_compression, level)
+    })
+}
+
+/// Load setup points written by `save_secrets_to_writer` from `reader`
+pub fn load_secrets_from_reader<TG1: G1, TG2: G2, R: Read + ?Sized>(
+    reader: &mut R,
+) -> Result<(Vec<TG1>, Vec<TG2>), KzgError> {
+    read_trusted_setup(reader)
+}
+
+/// Same as `load_secrets_from_reader`, checking the G1 and G2 points as required by `level`
+pub fn load_secrets_from_reader_with_validation<TG1: G1, TG2: G2, R: Read + ?Sized>(
+    reader: &mut R,
+    level: ValidationLevel,
+) -> Result<(Vec<TG1>, Vec<TG2>), KzgError> {
+    read_trusted_setup_with_validation(reader, level)
+}
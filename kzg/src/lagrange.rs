@@ -0,0 +1,136 @@
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[cfg(feature = "prove")]
+use crate::eip_4844::compute_kzg_proof_from_evaluations;
+use crate::eip_4844::{evaluate_evaluations, FIELD_ELEMENTS_PER_BLOB};
+#[cfg(any(feature = "commit", feature = "prove"))]
+use crate::G1LinComb;
+use crate::{FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1Mul, KZGSettings, Poly, G1, G2};
+
+////////////////////////////// Polynomials in evaluation form //////////////////////////////
+//
+// EIP-4844 blobs are polynomials given by their evaluations over the blob domain, the roots of
+// unity in bit-reversed order, and the setups loaded for them hold the G1 points in Lagrange form
+// over the same domain. A `LagrangePoly` keeps a polynomial in that form: it is committed to with
+// the Lagrange points directly, evaluated anywhere with the barycentric formula
+//
+//     p(x) = (x^n - 1) / n * Σ p_i * ω_i / (x - ω_i)
+//
+// and opened with the quotient `(p_i - y) / (ω_i - z)` computed over the domain as well, so no
+// operation converts it to coefficient form with an inverse FFT.
+
+/// Polynomial given by its evaluations over the blob domain, in the order of blobs
+#[derive(Debug, Clone, PartialEq)]
+pub struct LagrangePoly<TFr> {
+    evaluations: Vec<TFr>,
+}
+
+impl<TFr: Fr + Copy> LagrangePoly<TFr> {
+    /// Polynomial taking `evaluations[i]` at the `i`-th point of the blob domain
+    pub fn from_evaluations(evaluations: Vec<TFr>) -> Result<Self, String> {
+        if evaluations.len() != FIELD_ELEMENTS_PER_BLOB {
+            return Err(String::from(
+                "Evaluations must cover the FIELD_ELEMENTS_PER_BLOB points of the domain",
+            ));
+        }
+
+        Ok(Self { evaluations })
+    }
+
+    /// Polynomial of a blob
+    pub fn from_blob(blob: &[TFr]) -> Result<Self, String> {
+        Self::from_evaluations(blob.to_vec())
+    }
+
+    pub fn evaluations(&self) -> &[TFr] {
+        &self.evaluations
+    }
+
+    pub fn into_evaluations(self) -> Vec<TFr> {
+        self.evaluations
+    }
+
+    /// Number of evaluations, `FIELD_ELEMENTS_PER_BLOB`
+    pub fn len(&self) -> usize {
+        self.evaluations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.evaluations.is_empty()
+    }
+
+    /// Commitment with the Lagrange points of `s`, the same as `blob_to_kzg_commitment_rust`
+    #[cfg(feature = "commit")]
+    pub fn commit<
+        TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + G1LinComb<TFr, TG1Fp, TG1Affine>,
+        TG2: G2,
+        TFFTSettings: FFTSettings<TFr>,
+        TPoly: Poly<TFr>,
+        TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+        TG1Fp: G1Fp,
+        TG1Affine: G1Affine<TG1, TG1Fp>,
+    >(
+        &self,
+        s: &TKZGSettings,
+    ) -> Result<TG1, String> {
+        if s.get_g1_secret().len() < FIELD_ELEMENTS_PER_BLOB {
+            return Err(String::from("Settings must cover the blob domain"));
+        }
+
+        Ok(TG1::g1_lincomb(
+            s.get_g1_secret(),
+            &self.evaluations,
+            FIELD_ELEMENTS_PER_BLOB,
+            s.get_precomputation().as_deref(),
+        ))
+    }
+
+    /// Value at `x`, with the barycentric formula over the domain of `s`
+    pub fn evaluate_at<
+        TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+        TG2: G2,
+        TFFTSettings: FFTSettings<TFr>,
+        TPoly: Poly<TFr>,
+        TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+        TG1Fp: G1Fp,
+        TG1Affine: G1Affine<TG1, TG1Fp>,
+    >(
+        &self,
+        x: &TFr,
+        s: &TKZGSettings,
+    ) -> Result<TFr, String> {
+        let roots_of_unity = s.get_fft_settings().get_roots_of_unity();
+        if roots_of_unity.len() < FIELD_ELEMENTS_PER_BLOB {
+            return Err(String::from("Not enough roots of unity in FFT settings"));
+        }
+
+        evaluate_evaluations(&self.evaluations, x, roots_of_unity)
+    }
+
+    /// Proof of the value at `z` and the value, the same as `compute_kzg_proof_rust`
+    #[cfg(feature = "prove")]
+    pub fn open_at<
+        TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + G1LinComb<TFr, TG1Fp, TG1Affine>,
+        TG2: G2,
+        TFFTSettings: FFTSettings<TFr>,
+        TPoly: Poly<TFr>,
+        TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+        TG1Fp: G1Fp,
+        TG1Affine: G1Affine<TG1, TG1Fp>,
+    >(
+        &self,
+        z: &TFr,
+        s: &TKZGSettings,
+    ) -> Result<(TG1, TFr), String> {
+        if s.get_fft_settings().get_roots_of_unity().len() < FIELD_ELEMENTS_PER_BLOB
+            || s.get_g1_secret().len() < FIELD_ELEMENTS_PER_BLOB
+        {
+            return Err(String::from("Settings must cover the blob domain"));
+        }
+
+        compute_kzg_proof_from_evaluations(&self.evaluations, z, s)
+    }
+}
@@ -2,14 +2,90 @@
 
 extern crate alloc;
 
+// Browsers have no threads to back `rayon` or the `async` pool, spawning would panic at runtime
+#[cfg(all(
+    target_arch = "wasm32",
+    target_os = "unknown",
+    any(feature = "parallel", feature = "async")
+))]
+compile_error!(
+    "the `parallel` and `async` features need threads, unavailable on wasm32-unknown-unknown"
+);
+
 use alloc::string::String;
+use alloc::sync::Arc;
 use alloc::vec::Vec;
 use core::fmt::Debug;
-use msm::precompute::PrecomputationTable;
-
+use error::KzgError;
+use msm::precompute::{PrecomputationTable, ReleasableTable};
+
+pub use capabilities::capabilities;
+
+#[cfg(feature = "std")]
+pub mod artifact_store;
+pub mod backend;
+pub mod batch_inverse;
+pub mod bundle;
+pub mod cancel;
+pub mod capabilities;
+pub mod ceremony;
 pub mod common_utils;
+pub mod consts;
+pub mod cooperative;
+#[cfg(feature = "das")]
+pub mod das_matrix;
+pub mod dev_setup;
+pub mod diagnostics;
 pub mod eip_4844;
+#[cfg(feature = "das")]
+pub mod eip_7594;
+pub mod envelope;
+pub mod equivalence;
+pub mod error;
+pub mod evaluation_cache;
+#[cfg(feature = "std")]
+pub mod file_options;
+#[cfg(feature = "std")]
+pub mod fk20_cache;
+#[cfg(feature = "std")]
+pub mod framing;
+#[cfg(all(feature = "commit", feature = "prove"))]
+pub mod golden;
+#[cfg(feature = "std")]
+pub mod io_utils;
+pub mod lagrange;
+#[cfg(feature = "std")]
+pub mod memory_pressure;
 pub mod msm;
+pub mod multiproof;
+pub mod multiset;
+#[cfg(feature = "das")]
+pub mod namespace;
+#[cfg(feature = "async")]
+pub mod nonblocking;
+pub mod opening;
+pub mod parallelism;
+pub mod progress;
+pub mod rerandomize;
+pub mod sampled_opening;
+pub mod secret;
+#[cfg(all(feature = "commit", feature = "prove", feature = "verify"))]
+pub mod self_test;
+pub mod setup_bytes;
+#[cfg(all(feature = "std", feature = "io"))]
+pub mod setup_convert;
+#[cfg(feature = "std")]
+pub mod setup_file;
+pub mod sha256_batch;
+pub mod sharding;
+pub mod shifted_opening;
+pub mod validation;
+#[cfg(all(feature = "async", feature = "verify"))]
+pub mod verification_queue;
+#[cfg(feature = "verify")]
+pub mod verifier;
+#[cfg(feature = "verify")]
+pub mod witness;
 
 pub trait Fr: Default + Clone + PartialEq + Sync {
     fn null() -> Self;
@@ -68,6 +144,18 @@ pub trait Fr: Default + Clone + PartialEq + Sync {
     }
 
     fn to_scalar(&self) -> Scalar256;
+
+    /// Primitive `order`-th root of unity, for `order` a power of two below 2^32
+    fn root_of_unity(order: usize) -> Result<Self, String> {
+        let scale = order.trailing_zeros() as usize;
+        if !order.is_power_of_two() || scale >= consts::NUM_ROOTS {
+            return Err(String::from(
+                "Order of a root of unity must be a power of two below 2^32",
+            ));
+        }
+
+        Ok(Self::from_u64_arr(&consts::SCALE2_ROOT_OF_UNITY[scale]))
+    }
 }
 
 pub trait G1: Clone + Default + PartialEq + Sync + Debug + Send {
@@ -75,8 +163,10 @@ pub trait G1: Clone + Default + PartialEq + Sync + Debug + Send {
 
     fn identity() -> Self;
 
+    /// Standard generator of G1
     fn generator() -> Self;
 
+    /// Negation of the standard generator of G1
     fn negative_generator() -> Self;
 
     #[cfg(feature = "rand")]
@@ -109,6 +199,115 @@ pub trait G1: Clone + Default + PartialEq + Sync + Debug + Send {
     fn add_or_dbl_assign(&mut self, b: &Self);
     fn add_assign(&mut self, b: &Self);
     fn dbl_assign(&mut self);
+
+    /// Uncompressed encoding `x || y`, for backends that support it
+    fn to_bytes_uncompressed(&self) -> Result<[u8; eip_4844::BYTES_PER_G1_UNCOMPRESSED], String> {
+        Err(String::from(
+            "Uncompressed encoding is not supported by this backend",
+        ))
+    }
+
+    /// Decode the uncompressed encoding `x || y`, for backends that support it
+    fn from_bytes_uncompressed(_bytes: &[u8]) -> Result<Self, String> {
+        Err(String::from(
+            "Uncompressed encoding is not supported by this backend",
+        ))
+    }
+
+    /// Encode `points` back to back, in parallel with the `parallel` feature
+    fn batch_to_bytes(points: &[Self], compression: Compression) -> Result<Vec<u8>, String> {
+        #[cfg(feature = "parallel")]
+        use rayon::prelude::*;
+
+        let size = compression.g1_size();
+        let mut out = alloc::vec![0u8; points.len() * size];
+        let encode = |(chunk, point): (&mut [u8], &Self)| -> Result<(), String> {
+            match compression {
+                Compression::Compressed => chunk.copy_from_slice(&point.to_bytes()),
+                Compression::Uncompressed => chunk.copy_from_slice(&point.to_bytes_uncompressed()?),
+            }
+            Ok(())
+        };
+
+        #[cfg(feature = "parallel")]
+        if parallelism::is_parallel() {
+            parallelism::install(|| {
+                out.par_chunks_mut(size)
+                    .zip(points.par_iter())
+                    .try_for_each(encode)
+            })?;
+            return Ok(out);
+        }
+
+        out.chunks_mut(size)
+            .zip(points.iter())
+            .try_for_each(encode)?;
+
+        Ok(out)
+    }
+
+    /// Decode points encoded back to back by `batch_to_bytes`, in parallel with the `parallel`
+    /// feature
+    fn batch_from_bytes(bytes: &[u8], compression: Compression) -> Result<Vec<Self>, KzgError> {
+        #[cfg(feature = "parallel")]
+        use rayon::prelude::*;
+
+        let size = compression.g1_size();
+        if !bytes.chunks_exact(size).remainder().is_empty() {
+            return Err(KzgError::InvalidLength(String::from(
+                "Byte length must be a multiple of the point size",
+            )));
+        }
+        let decode = |(index, chunk): (usize, &[u8])| {
+            match compression {
+                Compression::Compressed => Self::from_bytes(chunk),
+                Compression::Uncompressed => Self::from_bytes_uncompressed(chunk),
+            }
+            .map_err(|message| KzgError::Deserialization {
+                what: "Point",
+                index,
+                message,
+            })
+        };
+
+        #[cfg(feature = "parallel")]
+        if parallelism::is_parallel() {
+            return parallelism::install(|| {
+                bytes.par_chunks(size).enumerate().map(decode).collect()
+            });
+        }
+
+        bytes.chunks(size).enumerate().map(decode).collect()
+    }
+}
+
+/// Point encoding of the batch serialization helpers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    /// `x` with flag bits, as used by the trusted setup and EIP-4844
+    Compressed,
+    /// `x || y`, larger but cheaper to decode
+    Uncompressed,
+}
+
+impl Compression {
+    /// Size of an encoded G1 point
+    pub const fn g1_size(self) -> usize {
+        match self {
+            Compression::Compressed => eip_4844::BYTES_PER_G1,
+            Compression::Uncompressed => eip_4844::BYTES_PER_G1_UNCOMPRESSED,
+        }
+    }
+}
+
+/// Basis of the G1 points of a trusted setup
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetupForm {
+    /// Powers `[s^i]G1`, as generated by `generate_trusted_setup` and used by `commit_to_poly`
+    Monomial,
+    /// Lagrange points over the bit-reversed domain of the setup, as loaded for EIP-4844 and used
+    /// by the blob functions
+    Lagrange,
 }
 
 pub trait G1GetFp<TFp: G1Fp>: G1 + Clone {
@@ -304,6 +503,37 @@ impl Scalar256 {
         unsafe { core::slice::from_raw_parts(&*(self.data.as_ptr() as *const u8), 32) }
     }
 
+    /// `Fr::to_scalar` of every element of `scalars`, in parallel with the `parallel` feature
+    pub fn from_fr_batch<TFr: Fr>(scalars: &[TFr]) -> Vec<Self> {
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+
+            scalars.par_iter().map(TFr::to_scalar).collect()
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        scalars.iter().map(TFr::to_scalar).collect()
+    }
+
+    /// Field element of value `self`, reduced modulo the group order
+    pub fn to_fr<TFr: Fr>(&self) -> TFr {
+        TFr::from_u64_arr(&self.data)
+    }
+
+    /// `to_fr` of every element of `scalars`, in parallel with the `parallel` feature
+    pub fn to_fr_batch<TFr: Fr + Send>(scalars: &[Self]) -> Vec<TFr> {
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+
+            scalars.par_iter().map(Self::to_fr).collect()
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        scalars.iter().map(Self::to_fr).collect()
+    }
+
     const fn cast_scalar_to_u64_arr<const N: usize, const N_U8: usize>(
         input: &[u8; N_U8],
     ) -> [u64; N] {
@@ -345,9 +575,11 @@ impl Scalar256 {
     }
 }
 
-pub trait G2: Clone + Default {
+pub trait G2: Clone + Default + Sync + Send {
+    /// Standard generator of G2
     fn generator() -> Self;
 
+    /// Negation of the standard generator of G2
     fn negative_generator() -> Self;
 
     fn from_bytes(bytes: &[u8]) -> Result<Self, String>;
@@ -361,6 +593,88 @@ pub trait G2: Clone + Default {
     fn sub(&self, b: &Self) -> Self;
 
     fn equals(&self, b: &Self) -> bool;
+
+    /// Encode `points` back to back, compressed, in parallel with the `parallel` feature
+    fn batch_to_bytes(points: &[Self]) -> Vec<u8> {
+        #[cfg(feature = "parallel")]
+        use rayon::prelude::*;
+
+        let mut out = alloc::vec![0u8; points.len() * eip_4844::BYTES_PER_G2];
+        let encode = |(chunk, point): (&mut [u8], &Self)| chunk.copy_from_slice(&point.to_bytes());
+
+        #[cfg(feature = "parallel")]
+        if parallelism::is_parallel() {
+            parallelism::install(|| {
+                out.par_chunks_mut(eip_4844::BYTES_PER_G2)
+                    .zip(points.par_iter())
+                    .for_each(encode)
+            });
+            return out;
+        }
+
+        out.chunks_mut(eip_4844::BYTES_PER_G2)
+            .zip(points.iter())
+            .for_each(encode);
+
+        out
+    }
+
+    /// Decode compressed points encoded back to back by `batch_to_bytes`, in parallel with the
+    /// `parallel` feature. Decompression, which takes a square root in Fp2 for every point, only
+    /// checks that points are on the curve, see `validation::validate_g2` for the subgroup check
+    fn batch_from_bytes(bytes: &[u8]) -> Result<Vec<Self>, KzgError> {
+        #[cfg(feature = "parallel")]
+        use rayon::prelude::*;
+
+        if !bytes
+            .chunks_exact(eip_4844::BYTES_PER_G2)
+            .remainder()
+            .is_empty()
+        {
+            return Err(KzgError::InvalidLength(String::from(
+                "Byte length must be a multiple of the point size",
+            )));
+        }
+        let decode = |(index, chunk): (usize, &[u8])| {
+            Self::from_bytes(chunk).map_err(|message| KzgError::Deserialization {
+                what: "Point",
+                index,
+                message,
+            })
+        };
+
+        #[cfg(feature = "parallel")]
+        if parallelism::is_parallel() {
+            return parallelism::install(|| {
+                bytes
+                    .par_chunks(eip_4844::BYTES_PER_G2)
+                    .enumerate()
+                    .map(decode)
+                    .collect()
+            });
+        }
+
+        bytes
+            .chunks(eip_4844::BYTES_PER_G2)
+            .enumerate()
+            .map(decode)
+            .collect()
+    }
+
+    /// Whether the point is in the subgroup of order `r`, checked as `[r]P = O` unless the backend
+    /// has a faster check
+    fn is_valid(&self) -> bool {
+        let identity = self.sub(self);
+        let mut acc = identity.clone();
+        for bit in (0..256).rev() {
+            acc = acc.dbl();
+            if (consts::BLS12_381_R[bit / 64] >> (bit % 64)) & 1 == 1 {
+                acc = acc.add_or_dbl(self);
+            }
+        }
+
+        acc.equals(&identity)
+    }
 }
 
 pub trait G2Mul<Fr>: Clone {
@@ -431,6 +745,13 @@ pub trait FFTSettings<Coeff: Fr>: Default + Clone {
     fn get_roots_of_unity_at(&self, i: usize) -> Coeff;
 
     fn get_roots_of_unity(&self) -> &[Coeff];
+
+    /// Bytes held by the roots of unity
+    fn memory_usage(&self) -> usize {
+        core::mem::size_of_val(self.get_expanded_roots_of_unity())
+            + core::mem::size_of_val(self.get_reversed_roots_of_unity())
+            + core::mem::size_of_val(self.get_roots_of_unity())
+    }
 }
 
 pub trait FFTSettingsPoly<Coeff: Fr, Polynomial: Poly<Coeff>, FSettings: FFTSettings<Coeff>> {
@@ -547,7 +868,105 @@ pub trait KZGSettings<
 
     fn get_g2_secret(&self) -> &[Coeff3];
 
-    fn get_precomputation(&self) -> Option<&PrecomputationTable<Coeff1, Coeff2, TG1Fp, TG1Affine>>;
+    /// Precomputation table of the G1 points, `None` without one or once released
+    fn get_precomputation(
+        &self,
+    ) -> Option<Arc<PrecomputationTable<Coeff1, Coeff2, TG1Fp, TG1Affine>>>;
+
+    /// The precomputation table as held by the settings, e.g. to register it with a
+    /// `MemoryMonitor`. Releasing it affects every clone of the settings
+    fn get_releasable_precomputation(
+        &self,
+    ) -> Option<Arc<ReleasableTable<Coeff1, Coeff2, TG1Fp, TG1Affine>>> {
+        None
+    }
+
+    /// Settings over the first `n` G1 points of the setup and the same G2 points, with FFT
+    /// settings of the smallest power of two domain holding `n` points, e.g. to serve several
+    /// polynomial sizes from one large setup
+    ///
+    /// The result keeps the `form` of the setup. Lagrange points are tied to their domain, so a
+    /// setup in Lagrange form is brought to monomial form, truncated, and brought back to Lagrange
+    /// form over the domain of `n` points, which must then be a power of two.
+    fn truncate(&self, n: usize, form: SetupForm) -> Result<Self, String>
+    where
+        Fs: FFTG1<Coeff2>,
+    {
+        let g1 = self.get_g1_secret();
+        if n == 0 || n > g1.len() {
+            return Err(alloc::format!(
+                "Cannot truncate a setup of {} points to {} points",
+                g1.len(),
+                n
+            ));
+        }
+
+        let fs = Fs::new(n.next_power_of_two().trailing_zeros() as usize)?;
+        match form {
+            SetupForm::Monomial => Self::new(&g1[..n], self.get_g2_secret(), n, &fs),
+            SetupForm::Lagrange => {
+                if !g1.len().is_power_of_two() || !n.is_power_of_two() {
+                    return Err(alloc::format!(
+                        "Lagrange setups truncate between powers of two, got {} and {} points",
+                        g1.len(),
+                        n
+                    ));
+                }
+
+                let mut lagrange = g1.to_vec();
+                common_utils::reverse_bit_order(&mut lagrange)?;
+                let monomial =
+                    Fs::new(g1.len().trailing_zeros() as usize)?.fft_g1(&lagrange, false)?;
+
+                let mut truncated = fs.fft_g1(&monomial[..n], true)?;
+                common_utils::reverse_bit_order(&mut truncated)?;
+                Self::new(&truncated, self.get_g2_secret(), n, &fs)
+            }
+        }
+    }
+
+    /// Settings over the setup file held in `bytes`, e.g. embedded with `include_bytes!`, whose G1
+    /// points are encoded with `compression`. The FFT settings are those of the smallest power of
+    /// two domain holding the G1 points, see `setup_bytes`
+    fn from_bytes(bytes: &[u8], compression: Compression) -> Result<Self, String> {
+        let (g1, g2) = setup_bytes::load_setup_from_bytes::<Coeff2, Coeff3>(bytes, compression)?;
+        if g1.is_empty() {
+            return Err(String::from("Setup holds no G1 points"));
+        }
+
+        let fs = Fs::new(g1.len().next_power_of_two().trailing_zeros() as usize)?;
+        Self::new(&g1, &g2, g1.len(), &fs)
+    }
+
+    /// Run known-answer tests and commit, prove, verify and recovery round trips against the
+    /// setup and tables of the settings, e.g. at deploy time, see `self_test`
+    #[cfg(all(feature = "commit", feature = "prove", feature = "verify"))]
+    fn self_test(&self) -> Result<(), String>
+    where
+        Coeff1: Copy,
+        Coeff2: G1LinComb<Coeff1, TG1Fp, TG1Affine>,
+        Fs: FFTFr<Coeff1>,
+        Polynomial: PolyRecover<Coeff1, Polynomial, Fs>,
+    {
+        self_test::run_self_test::<Coeff1, Coeff2, Coeff3, Fs, Polynomial, Self, TG1Fp, TG1Affine>(
+            self,
+        )
+    }
+
+    /// Whether the setup is the insecure development one, see `dev_setup`
+    fn is_insecure_dev_setup(&self) -> bool {
+        dev_setup::is_insecure_dev_setup(self.get_g2_secret())
+    }
+
+    /// Bytes held by the trusted setup, the FFT settings and the precomputation table
+    fn memory_usage(&self) -> usize {
+        core::mem::size_of_val(self.get_g1_secret())
+            + core::mem::size_of_val(self.get_g2_secret())
+            + self.get_fft_settings().memory_usage()
+            + self
+                .get_precomputation()
+                .map_or(0, |table| table.memory_usage())
+    }
 }
 
 pub trait FK20SingleSettings<
@@ -584,4 +1003,23 @@ pub trait FK20MultiSettings<
     fn data_availability(&self, p: &Polynomial) -> Result<Vec<Coeff2>, String>;
 
     fn data_availability_optimized(&self, p: &Polynomial) -> Result<Vec<Coeff2>, String>;
+
+    /// FFTs of the Toeplitz matrices of the setup built by `new`, one per offset in a chunk, see
+    /// `fk20_cache`
+    fn get_x_ext_fft_files(&self) -> &[Vec<Coeff2>] {
+        &[]
+    }
+
+    /// Same as `new`, with the FFTs returned by `get_x_ext_fft_files` of settings built with the
+    /// same arguments instead of computing them
+    fn new_with_x_ext_fft_files(
+        _ks: &Ks,
+        _n2: usize,
+        _chunk_len: usize,
+        _x_ext_fft_files: Vec<Vec<Coeff2>>,
+    ) -> Result<Self, String> {
+        Err(String::from(
+            "FK20 settings of this backend cannot be built from cached FFTs",
+        ))
+    }
 }
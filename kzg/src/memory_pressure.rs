@@ -0,0 +1,258 @@
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use crate::diagnostics::{emit_warning, Warning};
+#[cfg(feature = "das")]
+use crate::eip_7594::CellSettings;
+use crate::evaluation_cache::EvaluationCache;
+use crate::msm::precompute::ReleasableTable;
+use crate::{Fr, G1Affine, G1Fp, G1GetFp, G1Mul, G2Mul, G1, G2};
+
+////////////////////////////// Memory pressure //////////////////////////////
+//
+// A node that runs out of memory in the middle of an epoch is killed by the kernel, while most of
+// what the library keeps in memory is only there to go faster. `MemoryMonitor` polls the memory
+// usage of the cgroup of the process and, once it crosses a threshold of the limit, releases the
+// caches registered with it and enters the pressure state:
+//
+//   - registered `ReleasableCache`s are emptied. The precomputation table of a `KZGSettings`, from
+//     `get_releasable_precomputation`, or any other `ReleasableTable` drops its table, so that
+//     MSMs over it run Pippenger. `CellSettings` drop their FK20 settings, rebuilt for each call
+//     while the pressure lasts, and an `EvaluationCache` forgets its points
+//   - `precompute` builds no new table while any monitor is under pressure, see
+//     `under_memory_pressure`
+//
+// Each monitor leaves the pressure state on its first check below the threshold, or when it is
+// dropped. Released tables are not restored, their owners rebuild them when they see fit. The
+// trusted setup itself is never released.
+
+/// Memory use and limit of the cgroup of the process, in bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryUsage {
+    pub used: usize,
+    pub limit: usize,
+}
+
+/// Source of `MemoryUsage`, `None` when there is no limit
+pub type MemoryUsageSource = Box<dyn Fn() -> Option<MemoryUsage> + Send + Sync>;
+
+/// cgroup v1 reports "no limit" as a value close to `i64::MAX`
+const CGROUP_V1_UNLIMITED: u64 = 1 << 62;
+
+fn read_u64(path: &str) -> Option<u64> {
+    std::fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+/// Memory usage of the cgroup the process runs in, from cgroup v2 or v1, `None` without a limit
+pub fn cgroup_memory_usage() -> Option<MemoryUsage> {
+    let (used, limit) = match read_u64("/sys/fs/cgroup/memory.current") {
+        // cgroup v2, where `memory.max` is "max" without a limit
+        Some(used) => (used, read_u64("/sys/fs/cgroup/memory.max")?),
+        None => (
+            read_u64("/sys/fs/cgroup/memory/memory.usage_in_bytes")?,
+            read_u64("/sys/fs/cgroup/memory/memory.limit_in_bytes")?,
+        ),
+    };
+    if limit == 0 || limit >= CGROUP_V1_UNLIMITED {
+        return None;
+    }
+
+    Some(MemoryUsage {
+        used: used as usize,
+        limit: limit as usize,
+    })
+}
+
+/// Number of `MemoryMonitor`s whose last check found the process under pressure
+static MONITORS_UNDER_PRESSURE: AtomicUsize = AtomicUsize::new(0);
+
+/// Whether any `MemoryMonitor` found the process close to its memory limit on its last check
+pub fn under_memory_pressure() -> bool {
+    MONITORS_UNDER_PRESSURE.load(Ordering::Acquire) > 0
+}
+
+/// Cache that can be emptied without affecting results
+pub trait ReleasableCache: Send + Sync {
+    /// Drop the cached data and return the number of bytes released, as far as it is known
+    fn release(&self) -> usize;
+}
+
+impl<TFr, TG2> ReleasableCache for EvaluationCache<TFr, TG2>
+where
+    TFr: Fr + Copy + Send + Sync,
+    TG2: G2 + G2Mul<TFr> + Send + Sync,
+{
+    fn release(&self) -> usize {
+        // Points are shared through `Arc`s, their size is not known here
+        self.clear();
+        0
+    }
+}
+
+impl<TFr, TG1, TG1Fp, TG1Affine> ReleasableCache for ReleasableTable<TFr, TG1, TG1Fp, TG1Affine>
+where
+    TFr: Fr + Send + Sync,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+{
+    fn release(&self) -> usize {
+        self.take().map_or(0, |table| table.memory_usage())
+    }
+}
+
+#[cfg(feature = "das")]
+impl<TKZGSettings, TFK20MultiSettings> ReleasableCache
+    for CellSettings<TKZGSettings, TFK20MultiSettings>
+where
+    TKZGSettings: Send + Sync,
+    TFK20MultiSettings: Send + Sync,
+{
+    fn release(&self) -> usize {
+        self.release_fk20()
+    }
+}
+
+/// Polls the memory usage and releases the registered caches when it gets close to the limit
+pub struct MemoryMonitor {
+    threshold_percent: usize,
+    source: MemoryUsageSource,
+    caches: Vec<Arc<dyn ReleasableCache>>,
+    pressure: Arc<AtomicBool>,
+}
+
+impl MemoryMonitor {
+    /// Monitor of the cgroup of the process, reacting once `threshold_percent` of the limit is used
+    pub fn new(threshold_percent: usize) -> Result<Self, String> {
+        Self::with_source(threshold_percent, Box::new(cgroup_memory_usage))
+    }
+
+    /// Monitor reading the memory usage from `source` instead of the cgroup, e.g. for a limit
+    /// enforced outside of cgroups
+    pub fn with_source(
+        threshold_percent: usize,
+        source: MemoryUsageSource,
+    ) -> Result<Self, String> {
+        if !(1..=100).contains(&threshold_percent) {
+            return Err(String::from("Threshold must be between 1 and 100 percent"));
+        }
+
+        Ok(Self {
+            threshold_percent,
+            source,
+            caches: Vec::new(),
+            pressure: Arc::new(AtomicBool::new(false)),
+        })
+    }
+
+    /// Release `cache` on the next checks under pressure
+    pub fn register(&mut self, cache: Arc<dyn ReleasableCache>) {
+        self.caches.push(cache);
+    }
+
+    /// Read the memory usage, release the caches if it is above the threshold and update
+    /// `under_memory_pressure`. Returns whether the process is under pressure
+    pub fn check(&self) -> bool {
+        let usage = (self.source)();
+        let pressure = usage.map_or(false, |usage| {
+            usage.used.saturating_mul(100) >= usage.limit.saturating_mul(self.threshold_percent)
+        });
+        set_pressure(&self.pressure, pressure);
+
+        if let (true, Some(usage)) = (pressure, usage) {
+            let released = self.caches.iter().map(|cache| cache.release()).sum();
+            emit_warning(Warning::MemoryPressure {
+                used: usage.used,
+                limit: usage.limit,
+                released,
+            });
+        }
+
+        pressure
+    }
+
+    /// Whether the last check of this monitor found the process under pressure
+    pub fn under_pressure(&self) -> bool {
+        self.pressure.load(Ordering::Acquire)
+    }
+
+    /// Run `check` every `interval` on a background thread, until the handle is stopped or dropped
+    pub fn spawn(self, interval: Duration) -> MemoryMonitorHandle {
+        let pressure = self.pressure.clone();
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread = {
+            let stop = stop.clone();
+            std::thread::spawn(move || {
+                while !stop.load(Ordering::Acquire) {
+                    self.check();
+                    std::thread::park_timeout(interval);
+                }
+            })
+        };
+
+        MemoryMonitorHandle {
+            stop,
+            pressure,
+            thread: Some(thread),
+        }
+    }
+}
+
+impl Drop for MemoryMonitor {
+    fn drop(&mut self) {
+        set_pressure(&self.pressure, false);
+    }
+}
+
+/// Record the pressure state of one monitor, counted in `under_memory_pressure`
+fn set_pressure(state: &AtomicBool, pressure: bool) {
+    match (state.swap(pressure, Ordering::AcqRel), pressure) {
+        (false, true) => {
+            MONITORS_UNDER_PRESSURE.fetch_add(1, Ordering::AcqRel);
+        }
+        (true, false) => {
+            MONITORS_UNDER_PRESSURE.fetch_sub(1, Ordering::AcqRel);
+        }
+        _ => {}
+    }
+}
+
+/// Background thread started by `MemoryMonitor::spawn`
+pub struct MemoryMonitorHandle {
+    stop: Arc<AtomicBool>,
+    pressure: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl MemoryMonitorHandle {
+    /// Whether the last check of the monitor found the process under pressure
+    pub fn under_pressure(&self) -> bool {
+        self.pressure.load(Ordering::Acquire)
+    }
+
+    /// Stop the thread and wait for it to exit
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        self.stop.store(true, Ordering::Release);
+        if let Some(thread) = self.thread.take() {
+            thread.thread().unpark();
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for MemoryMonitorHandle {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
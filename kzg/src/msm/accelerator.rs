@@ -0,0 +1,58 @@
+use spin::RwLock;
+
+use crate::eip_4844::BYTES_PER_G1_UNCOMPRESSED;
+use crate::{Compression, Fr, Scalar256, G1};
+
+////////////////////////////// Offloading MSM to a device //////////////////////////////
+//
+// With the `gpu` feature, `msm` hands large MSMs to the accelerator registered with
+// `set_msm_accelerator` before running the CPU implementations. Device code is not part of this
+// crate: a CUDA (or other) implementation lives in its own crate, implements `MsmAccelerator` and
+// is registered once at startup.
+//
+// The interface does not depend on the backend. Points are passed in the uncompressed encoding
+// `x || y` of `G1::to_bytes_uncompressed` and scalars as canonical `Scalar256` values, four
+// little-endian 64-bit limbs. The CPU path runs whenever no accelerator is registered, the
+// accelerator reports no device, the MSM is shorter than `min_len`, the backend has no
+// uncompressed encoding, or the accelerator returns `None`.
+
+/// Device implementation of `Σ scalars[i] * points[i]`
+pub trait MsmAccelerator: Sync + Send {
+    /// Whether a device is present and usable
+    fn is_available(&self) -> bool;
+
+    /// Smallest MSM worth the transfer to the device
+    fn min_len(&self) -> usize {
+        1 << 10
+    }
+
+    /// Compute the MSM of `points`, `scalars.len()` uncompressed encodings back to back, and
+    /// return its uncompressed encoding, or `None` to fall back to the CPU
+    fn msm(&self, points: &[u8], scalars: &[Scalar256]) -> Option<[u8; BYTES_PER_G1_UNCOMPRESSED]>;
+}
+
+static ACCELERATOR: RwLock<Option<&'static dyn MsmAccelerator>> = RwLock::new(None);
+
+/// Register the accelerator used by `msm`, or remove it with `None`
+pub fn set_msm_accelerator(accelerator: Option<&'static dyn MsmAccelerator>) {
+    *ACCELERATOR.write() = accelerator;
+}
+
+/// Currently registered accelerator
+pub fn msm_accelerator() -> Option<&'static dyn MsmAccelerator> {
+    *ACCELERATOR.read()
+}
+
+/// Run the MSM on the registered accelerator, `None` if it has to run on the CPU
+pub(crate) fn accelerated_msm<TG1: G1, TFr: Fr>(points: &[TG1], scalars: &[TFr]) -> Option<TG1> {
+    let accelerator = msm_accelerator()?;
+    if scalars.len() < accelerator.min_len() || !accelerator.is_available() {
+        return None;
+    }
+
+    let points = TG1::batch_to_bytes(points, Compression::Uncompressed).ok()?;
+    let scalars = Scalar256::from_fr_batch(scalars);
+    let out = accelerator.msm(&points, &scalars)?;
+
+    TG1::from_bytes_uncompressed(&out).ok()
+}
@@ -3,6 +3,7 @@ use crate::{
     msm::arkmsm::bucket_msm::BucketMSM,
     msm::arkmsm::glv::decompose,
     msm::types::{G1_SCALAR_SIZE, G1_SCALAR_SIZE_GLV},
+    progress::report_msm_progress,
     Fr, G1Affine, G1Fp, G1ProjAddAffine, Scalar256, G1,
 };
 
@@ -129,13 +130,17 @@ impl VariableBaseMSM {
         );
 
         let mut slices = vec![0u32; num_slices];
+        let npoints = points.len().min(scalars.len());
         scalars
             .iter()
             .zip(points)
-            .filter(|(s, _)| !s.is_zero())
-            .for_each(|(&scalar, point)| {
-                Self::msm_slice(scalar, &mut slices[..num_slices], window_bits);
-                bucket_msm.process_point_and_slices(point, &slices[..num_slices]);
+            .enumerate()
+            .for_each(|(i, (&scalar, point))| {
+                if !scalar.is_zero() {
+                    Self::msm_slice(scalar, &mut slices[..num_slices], window_bits);
+                    bucket_msm.process_point_and_slices(point, &slices[..num_slices]);
+                }
+                report_msm_progress(npoints, i + 1, npoints);
             });
 
         bucket_msm.process_complete();
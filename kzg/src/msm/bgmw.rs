@@ -1,10 +1,41 @@
+extern crate alloc;
+
+#[cfg(feature = "std")]
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
 use core::marker::PhantomData;
-
+use core::ops::Deref;
+#[cfg(feature = "std")]
+use std::fs::{File, OpenOptions};
+#[cfg(feature = "std")]
+use std::io::{Read, Seek, SeekFrom, Write};
+#[cfg(feature = "mmap")]
+use std::sync::Arc;
+
+#[cfg(feature = "std")]
+use sha2::{Digest, Sha256};
+
+use crate::batch_inverse::to_affines_batch_loc;
+use crate::cancel::{check_cancelled, CancelToken, Cancelled};
+use crate::cooperative::maybe_yield;
+use crate::diagnostics::{emit_warning, Warning};
+#[cfg(feature = "std")]
+use crate::envelope::CurveId;
+use crate::error::KzgError;
+#[cfg(feature = "std")]
+use crate::file_options::{read_file, write_file, FileOptions};
+use crate::progress::report_msm_progress;
+#[cfg(feature = "std")]
+use crate::progress::{report_progress, ProgressStage};
+use crate::validation::{validate_g1_affines, ValidationLevel};
 use crate::{Fr, G1Affine, G1Fp, G1GetFp, G1Mul, Scalar256, G1};
 
 use super::pippenger_utils::{
-    booth_decode, booth_encode, get_wval_limb, is_zero, p1_dadd, p1_to_jacobian,
-    pippenger_window_size, type_is_zero, P1XYZZ,
+    booth_decode, booth_encode, get_wval_limb, integrate_buckets_xyzz, is_zero, p1_prefetch,
+    p1_to_jacobian, pippenger_window_size, P1XYZZ,
 };
 
 #[derive(Debug, Clone)]
@@ -16,7 +47,7 @@ where
     TG1Affine: G1Affine<TG1, TG1Fp>,
 {
     window: BgmwWindow,
-    points: Vec<TG1Affine>,
+    points: TablePoints<TG1Affine>,
     numpoints: usize,
     h: usize,
 
@@ -27,6 +58,128 @@ where
 
 const NBITS: usize = 255;
 
+/// Smallest window accepted by `BgmwTable::new_with_window`
+pub const BGMW_MIN_WINDOW: usize = 3;
+/// Largest window accepted by `BgmwTable::new_with_window`, every worker holds `2^(w-1)` buckets
+pub const BGMW_MAX_WINDOW: usize = 20;
+
+/// `multiply_sequential` goes over the indices of the other scalars once at least one scalar in
+/// `SPARSE_RATIO` is zero or fits in the lowest window
+const SPARSE_RATIO: usize = 8;
+
+/// Magic bytes opening a table file
+#[cfg(feature = "std")]
+pub const TABLE_FILE_MAGIC: [u8; 8] = *b"KZGBGMWT";
+
+/// Version of the table file format
+#[cfg(feature = "std")]
+pub const TABLE_FILE_VERSION: u8 = 1;
+
+/// Size of the table file header, which keeps the points aligned in a memory-mapped file
+#[cfg(feature = "std")]
+pub const TABLE_FILE_HEADER_SIZE: usize = 128;
+
+// Header layout: magic || version || curve id || zero padding up to byte 16 || eight
+// little-endian u64 dimension fields || SHA-256 of the point data || zero padding
+#[cfg(feature = "std")]
+const TABLE_FILE_FIELDS_OFFSET: usize = 16;
+#[cfg(feature = "std")]
+const TABLE_FILE_DIGEST_OFFSET: usize = TABLE_FILE_FIELDS_OFFSET + 64;
+
+/// Bytes of points read or hashed between two progress reports
+#[cfg(feature = "std")]
+const TABLE_FILE_CHUNK_SIZE: usize = 1 << 20;
+
+fn table_too_large() -> KzgError {
+    KzgError::OutOfMemory(String::from("BGMW precomputation table is too large"))
+}
+
+/// Suffix of the manifest `BgmwTable::generate_to_file` keeps next to an incomplete table file
+#[cfg(feature = "std")]
+pub const TABLE_MANIFEST_SUFFIX: &str = ".manifest";
+
+// Manifest layout: header of the table, with the digest of its first row in place of the digest
+// of the points || rows written (u64 LE) || SHA-256 of the last row written
+#[cfg(feature = "std")]
+const TABLE_MANIFEST_SIZE: usize = TABLE_FILE_HEADER_SIZE + 8 + 32;
+
+/// Rows written and digest of the last one, if the manifest at `path` opens with `header`
+#[cfg(feature = "std")]
+fn read_table_manifest(
+    path: &str,
+    header: &[u8; TABLE_FILE_HEADER_SIZE],
+) -> Result<Option<(usize, [u8; 32])>, KzgError> {
+    let manifest = match std::fs::read(path) {
+        Ok(manifest) => manifest,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    if manifest.len() != TABLE_MANIFEST_SIZE || manifest[..TABLE_FILE_HEADER_SIZE] != header[..] {
+        return Ok(None);
+    }
+
+    let mut rows = [0u8; 8];
+    rows.copy_from_slice(&manifest[TABLE_FILE_HEADER_SIZE..TABLE_FILE_HEADER_SIZE + 8]);
+    let mut digest = [0u8; 32];
+    digest.copy_from_slice(&manifest[TABLE_FILE_HEADER_SIZE + 8..]);
+
+    Ok(usize::try_from(u64::from_le_bytes(rows))
+        .ok()
+        .map(|rows| (rows, digest)))
+}
+
+/// Replace the manifest at `path`, through a temporary file so that it is never left torn
+#[cfg(feature = "std")]
+fn write_table_manifest(
+    path: &str,
+    header: &[u8; TABLE_FILE_HEADER_SIZE],
+    rows: usize,
+    digest: &[u8],
+) -> Result<(), KzgError> {
+    let mut manifest = Vec::with_capacity(TABLE_MANIFEST_SIZE);
+    manifest.extend_from_slice(header);
+    manifest.extend_from_slice(&(rows as u64).to_le_bytes());
+    manifest.extend_from_slice(digest);
+
+    let tmp_path = format!("{}.tmp", path);
+    let mut file = File::create(&tmp_path)?;
+    file.write_all(&manifest)?;
+    file.sync_data()?;
+    std::fs::rename(&tmp_path, path)?;
+
+    Ok(())
+}
+
+/// Precomputed points, either owned or mapped from a table file
+#[derive(Debug, Clone)]
+enum TablePoints<TG1Affine> {
+    Owned(Vec<TG1Affine>),
+    #[cfg(feature = "mmap")]
+    Mapped {
+        mmap: Arc<memmap2::Mmap>,
+        len: usize,
+        marker: PhantomData<TG1Affine>,
+    },
+}
+
+impl<TG1Affine> Deref for TablePoints<TG1Affine> {
+    type Target = [TG1Affine];
+
+    fn deref(&self) -> &[TG1Affine] {
+        match self {
+            Self::Owned(points) => points,
+            #[cfg(feature = "mmap")]
+            Self::Mapped { mmap, len, .. } => unsafe {
+                // Alignment and length are checked in `load_mmap`
+                core::slice::from_raw_parts(
+                    mmap[TABLE_FILE_HEADER_SIZE..].as_ptr() as *const TG1Affine,
+                    *len,
+                )
+            },
+        }
+    }
+}
+
 #[cfg(feature = "parallel")]
 #[derive(Debug, Clone, Copy)]
 enum BgmwWindow {
@@ -85,8 +238,41 @@ impl<
         TG1Affine: G1Affine<TG1, TG1Fp>,
     > BgmwTable<TFr, TG1, TG1Fp, TG1Affine>
 {
-    pub fn new(points: &[TG1]) -> Result<Option<Self>, String> {
-        let window = Self::window(points.len());
+    pub fn new(points: &[TG1]) -> Result<Option<Self>, KzgError> {
+        Self::new_with_progress(points, &mut |_, _| {})
+    }
+
+    /// Same as `new`, calling `progress(rows_done, rows)` on the calling thread after each row of
+    /// the table, so that callers can report the progress of large tables
+    pub fn new_with_progress(
+        points: &[TG1],
+        progress: &mut dyn FnMut(usize, usize),
+    ) -> Result<Option<Self>, KzgError> {
+        Self::with_window(points, Self::default_window(points.len()), progress)
+    }
+
+    /// Build the table with a window of `window` bits instead of the default for `points.len()`,
+    /// e.g. one picked by `MsmTuner` for this hardware
+    ///
+    /// In a parallel build, `window` is the window of a single worker before the table is split
+    /// between the cpus, as for the default window.
+    pub fn new_with_window(points: &[TG1], window: usize) -> Result<Option<Self>, KzgError> {
+        if !(BGMW_MIN_WINDOW..=BGMW_MAX_WINDOW).contains(&window) {
+            return Err(KzgError::InvalidArgument(format!(
+                "BGMW window must be between {} and {} bits, got {}",
+                BGMW_MIN_WINDOW, BGMW_MAX_WINDOW, window
+            )));
+        }
+
+        Self::with_window(points, window, &mut |_, _| {})
+    }
+
+    fn with_window(
+        points: &[TG1],
+        window: usize,
+        progress: &mut dyn FnMut(usize, usize),
+    ) -> Result<Option<Self>, KzgError> {
+        let window = Self::layout(points.len(), window);
 
         let (window_width, h) = get_table_dimensions(window);
 
@@ -95,22 +281,22 @@ impl<
 
         table
             .try_reserve_exact(points.len() * h)
-            .map_err(|_| "BGMW precomputation table is too large".to_string())?;
+            .map_err(|_| table_too_large())?;
 
         unsafe { table.set_len(points.len() * h) };
 
-        for i in 0..points.len() {
-            let mut tmp_point = points[i].clone();
-            for j in 0..h {
-                let idx = j * points.len() + i;
-                table[idx] = TG1Affine::into_affine(&tmp_point);
-                tmp_point = tmp_point.mul(&q);
+        // Row `j` of the table holds `q^j * points`, each row is computed from the previous one
+        if !points.is_empty() {
+            let mut row = points.to_vec();
+            for (j, table_row) in table.chunks_exact_mut(points.len()).enumerate() {
+                Self::fill_row(&mut row, table_row, &q, j + 1 < h);
+                progress(j + 1, h);
             }
         }
 
         Ok(Some(Self {
             numpoints: points.len(),
-            points: table,
+            points: TablePoints::Owned(table),
             window,
             h,
 
@@ -120,10 +306,600 @@ impl<
         }))
     }
 
+    /// Store `row` into `table_row` and, unless it is the last row, advance it to `q * row`
+    fn fill_row(row: &mut [TG1], table_row: &mut [TG1Affine], q: &TFr, advance: bool) {
+        to_affines_batch_loc(table_row, row);
+        if !advance {
+            return;
+        }
+        let advance_chunk = |row: &mut [TG1]| {
+            for point in row.iter_mut() {
+                *point = point.mul(q);
+            }
+        };
+
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+
+            let chunk_size = (row.len() / rayon::current_num_threads()).max(1 << 6);
+            row.par_chunks_mut(chunk_size).for_each(advance_chunk);
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        {
+            advance_chunk(row);
+            maybe_yield();
+        }
+    }
+
+    /// Width in bits of the windows the scalars are split into
+    pub fn window_width(&self) -> usize {
+        get_table_dimensions(self.window).0
+    }
+
+    /// Bytes allocated for the precomputed points, zero for a memory-mapped table
+    pub fn memory_usage(&self) -> usize {
+        match &self.points {
+            TablePoints::Owned(points) => points.capacity() * core::mem::size_of::<TG1Affine>(),
+            #[cfg(feature = "mmap")]
+            TablePoints::Mapped { .. } => 0,
+        }
+    }
+
+    /// Bytes of a point in table files, its x and y coordinates
+    #[cfg(feature = "std")]
+    const FILE_POINT_SIZE: usize = 2 * core::mem::size_of::<TG1Fp>();
+
+    /// Whether `TG1Affine` is laid out as its x and y coordinates, as with blst, so that table
+    /// files hold the points as they are in memory and can be memory-mapped
+    #[cfg(feature = "std")]
+    pub fn supports_mmap() -> bool {
+        let point = TG1Affine::zero();
+        let base = &point as *const TG1Affine as usize;
+
+        core::mem::size_of::<TG1Affine>() == Self::FILE_POINT_SIZE
+            && point.x() as *const TG1Fp as usize == base
+            && point.y() as *const TG1Fp as usize == base + core::mem::size_of::<TG1Fp>()
+    }
+
+    /// `points` as stored in table files: the in-memory representation of their x and y
+    /// coordinates, zeros for the identity. Affine points of other layouts, such as those of
+    /// arkworks with an infinity flag and padding, are copied coordinate by coordinate.
+    #[cfg(feature = "std")]
+    fn points_bytes(points: &[TG1Affine]) -> Cow<'_, [u8]> {
+        if Self::supports_mmap() {
+            return Cow::Borrowed(unsafe {
+                core::slice::from_raw_parts(
+                    points.as_ptr() as *const u8,
+                    core::mem::size_of_val(points),
+                )
+            });
+        }
+
+        let zero = TG1Fp::ZERO;
+        let mut bytes = Vec::with_capacity(points.len() * Self::FILE_POINT_SIZE);
+        for point in points {
+            let (x, y) = if point.is_zero() {
+                (&zero, &zero)
+            } else {
+                (point.x(), point.y())
+            };
+            for fp in [x, y] {
+                bytes.extend_from_slice(unsafe {
+                    core::slice::from_raw_parts(
+                        fp as *const TG1Fp as *const u8,
+                        core::mem::size_of::<TG1Fp>(),
+                    )
+                });
+            }
+        }
+
+        Cow::Owned(bytes)
+    }
+
+    /// Call `f` with the bytes of `points` in table files, a chunk of about
+    /// `TABLE_FILE_CHUNK_SIZE` at a time
+    #[cfg(feature = "std")]
+    fn for_each_points_chunk<E>(
+        points: &[TG1Affine],
+        mut f: impl FnMut(&[u8]) -> Result<(), E>,
+    ) -> Result<(), E> {
+        points
+            .chunks(TABLE_FILE_CHUNK_SIZE / Self::FILE_POINT_SIZE)
+            .try_for_each(|points| f(&Self::points_bytes(points)))
+    }
+
+    /// Read back points from bytes written by `points_bytes`
+    ///
+    /// # Safety
+    ///
+    /// `bytes` must hold coordinates in the in-memory representation of `TG1Fp`.
+    #[cfg(feature = "std")]
+    unsafe fn read_points_bytes(bytes: &[u8], points: &mut [TG1Affine]) {
+        if Self::supports_mmap() {
+            core::slice::from_raw_parts_mut(points.as_mut_ptr() as *mut u8, bytes.len())
+                .copy_from_slice(bytes);
+            return;
+        }
+
+        // Overwriting the coordinates of a point that is not the identity keeps flags such as
+        // the infinity flag of arkworks cleared
+        let finite = TG1Affine::into_affine(&TG1::generator());
+        let fp_size = core::mem::size_of::<TG1Fp>();
+        for (point, bytes) in points
+            .iter_mut()
+            .zip(bytes.chunks_exact(Self::FILE_POINT_SIZE))
+        {
+            let mut coordinates = [TG1Fp::ZERO; 2];
+            for (fp, bytes) in coordinates.iter_mut().zip(bytes.chunks_exact(fp_size)) {
+                core::ptr::copy_nonoverlapping(
+                    bytes.as_ptr(),
+                    fp as *mut TG1Fp as *mut u8,
+                    fp_size,
+                );
+            }
+            let [x, y] = coordinates;
+            if x.is_zero() && y.is_zero() {
+                *point = TG1Affine::zero();
+            } else {
+                *point = finite;
+                *point.x_mut() = x;
+                *point.y_mut() = y;
+            }
+        }
+    }
+
+    /// SHA-256 digest of the bytes of `points` in table files, reporting the chunks hashed with
+    /// `stage`
+    #[cfg(feature = "std")]
+    fn points_digest(points: &[TG1Affine], stage: Option<ProgressStage>) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        let chunk_points = TABLE_FILE_CHUNK_SIZE / Self::FILE_POINT_SIZE;
+        let chunks = (points.len() + chunk_points - 1) / chunk_points;
+        let mut done = 0;
+        let _ = Self::for_each_points_chunk::<()>(points, |bytes| {
+            hasher.update(bytes);
+            done += 1;
+            if let Some(stage) = stage {
+                report_progress(stage, done, chunks);
+            }
+            Ok(())
+        });
+
+        hasher.finalize().into()
+    }
+
+    #[cfg(feature = "std")]
+    fn header(&self) -> [u8; TABLE_FILE_HEADER_SIZE] {
+        Self::header_for(
+            self.window,
+            self.numpoints,
+            self.h,
+            &Self::points_digest(&self.points, None),
+        )
+    }
+
+    /// Header of a table of the given dimensions whose points have the SHA-256 `digest`
+    #[cfg(feature = "std")]
+    fn header_for(
+        window: BgmwWindow,
+        numpoints: usize,
+        h: usize,
+        digest: &[u8],
+    ) -> [u8; TABLE_FILE_HEADER_SIZE] {
+        #[cfg(feature = "parallel")]
+        let window = match window {
+            BgmwWindow::Sync(wnd) => [0, wnd, 0, 0],
+            BgmwWindow::Parallel((nx, ny, wnd)) => [1, wnd, nx, ny],
+        };
+        #[cfg(not(feature = "parallel"))]
+        let window = [0, window, 0, 0];
+
+        let fields = [
+            window[0],
+            window[1],
+            window[2],
+            window[3],
+            numpoints,
+            h,
+            Self::FILE_POINT_SIZE,
+            0,
+        ];
+        let mut header = [0u8; TABLE_FILE_HEADER_SIZE];
+        header[..8].copy_from_slice(&TABLE_FILE_MAGIC);
+        header[8] = TABLE_FILE_VERSION;
+        header[9] = CurveId::Bls12_381 as u8;
+        for (bytes, field) in header[TABLE_FILE_FIELDS_OFFSET..TABLE_FILE_DIGEST_OFFSET]
+            .chunks_exact_mut(8)
+            .zip(fields)
+        {
+            bytes.copy_from_slice(&(field as u64).to_le_bytes());
+        }
+        header[TABLE_FILE_DIGEST_OFFSET..TABLE_FILE_DIGEST_OFFSET + 32].copy_from_slice(digest);
+
+        header
+    }
+
+    /// Parse a header written by `header`, returning the table without its points, the number
+    /// of points to read and their expected digest
+    #[cfg(feature = "std")]
+    fn from_header(header: &[u8]) -> Result<(Self, usize, [u8; 32]), KzgError> {
+        if header[..8] != TABLE_FILE_MAGIC {
+            return Err(KzgError::InvalidFormat(String::from(
+                "Not a precomputation table file",
+            )));
+        }
+        if header[8] != TABLE_FILE_VERSION {
+            return Err(KzgError::SetupMismatch(format!(
+                "Unsupported table file version {}",
+                header[8]
+            )));
+        }
+        if CurveId::from_u8(header[9]).map_err(KzgError::SetupMismatch)? != CurveId::Bls12_381 {
+            return Err(KzgError::SetupMismatch(String::from(
+                "Table file does not hold BLS12-381 points",
+            )));
+        }
+
+        let mut fields = [0usize; 8];
+        for (field, bytes) in fields
+            .iter_mut()
+            .zip(header[TABLE_FILE_FIELDS_OFFSET..TABLE_FILE_DIGEST_OFFSET].chunks_exact(8))
+        {
+            let mut le = [0u8; 8];
+            le.copy_from_slice(bytes);
+            *field = usize::try_from(u64::from_le_bytes(le)).map_err(|_| table_too_large())?;
+        }
+        let [kind, wnd, _nx, _ny, numpoints, h, point_size, _] = fields;
+
+        if point_size != Self::FILE_POINT_SIZE {
+            return Err(KzgError::SetupMismatch(String::from(
+                "Table was written by a different backend",
+            )));
+        }
+        let window = match kind {
+            #[cfg(feature = "parallel")]
+            0 => BgmwWindow::Sync(wnd),
+            #[cfg(feature = "parallel")]
+            1 if _nx > 0 && _nx <= numpoints => BgmwWindow::Parallel((_nx, _ny, wnd)),
+            #[cfg(not(feature = "parallel"))]
+            0 => wnd,
+            #[cfg(not(feature = "parallel"))]
+            1 => {
+                return Err(KzgError::SetupMismatch(String::from(
+                    "Table was built for the parallel implementation",
+                )))
+            }
+            _ => {
+                return Err(KzgError::InvalidFormat(String::from(
+                    "Unknown table window layout",
+                )))
+            }
+        };
+        // The same range as `new_with_window`, a larger window would size the buckets of the MSM
+        if !(BGMW_MIN_WINDOW..=BGMW_MAX_WINDOW).contains(&wnd) {
+            return Err(KzgError::InvalidFormat(format!(
+                "Table window must be between {} and {} bits, got {}",
+                BGMW_MIN_WINDOW, BGMW_MAX_WINDOW, wnd
+            )));
+        }
+        if get_table_dimensions(window).1 != h {
+            return Err(KzgError::InvalidFormat(String::from(
+                "Inconsistent table dimensions",
+            )));
+        }
+        let len = numpoints.checked_mul(h).ok_or_else(table_too_large)?;
+
+        let mut digest = [0u8; 32];
+        digest.copy_from_slice(&header[TABLE_FILE_DIGEST_OFFSET..TABLE_FILE_DIGEST_OFFSET + 32]);
+
+        let table = Self {
+            window,
+            points: TablePoints::Owned(Vec::new()),
+            numpoints,
+            h,
+
+            fr_marker: PhantomData,
+            g1_fp_marker: PhantomData,
+            g1_marker: PhantomData,
+        };
+
+        Ok((table, len, digest))
+    }
+
+    #[cfg(feature = "std")]
+    fn check_digest(points: &[TG1Affine], digest: &[u8; 32]) -> Result<(), KzgError> {
+        if Self::points_digest(points, Some(ProgressStage::TableFileDigest)) != *digest {
+            return Err(KzgError::SetupMismatch(String::from(
+                "Table file is corrupted, the points do not match the digest in its header",
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Write the table, a versioned header followed by the coordinates of the points in their
+    /// in-memory representation. The header records the table dimensions and a SHA-256 digest
+    /// of the points. The file can only be read back by a backend with the same field
+    /// representation, such as blst and arkworks, on a machine of the same endianness.
+    #[cfg(feature = "std")]
+    pub fn write_to_writer<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), KzgError> {
+        writer.write_all(&self.header())?;
+        Self::for_each_points_chunk(&self.points, |bytes| writer.write_all(bytes))?;
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    /// Read a table written by `write_to_writer`, rejecting files with a different format
+    /// version, curve or backend and files whose points do not match the digest
+    ///
+    /// # Safety
+    ///
+    /// The points are not validated beyond the digest: the data must come from `write_to_writer`
+    /// with a backend of the same field representation.
+    #[cfg(feature = "std")]
+    pub unsafe fn read_from_reader<R: Read + ?Sized>(reader: &mut R) -> Result<Self, KzgError> {
+        let mut header = [0u8; TABLE_FILE_HEADER_SIZE];
+        reader.read_exact(&mut header)?;
+        let (mut table, len, digest) = Self::from_header(&header)?;
+
+        let mut points: Vec<TG1Affine> = Vec::new();
+        points
+            .try_reserve_exact(len)
+            .map_err(|_| table_too_large())?;
+        points.resize(len, TG1Affine::zero());
+        let chunk_points = TABLE_FILE_CHUNK_SIZE / Self::FILE_POINT_SIZE;
+        let mut bytes = vec![0u8; chunk_points.min(len) * Self::FILE_POINT_SIZE];
+        let chunks = (len + chunk_points - 1) / chunk_points;
+        for (i, chunk) in points.chunks_mut(chunk_points).enumerate() {
+            let bytes = &mut bytes[..chunk.len() * Self::FILE_POINT_SIZE];
+            reader.read_exact(bytes)?;
+            Self::read_points_bytes(bytes, chunk);
+            report_progress(ProgressStage::TableFileRead, i + 1, chunks);
+        }
+        Self::check_digest(&points, &digest)?;
+
+        table.points = TablePoints::Owned(points);
+        Ok(table)
+    }
+
+    /// Check the points of a table read from a file, which `read_from_reader` and `load_mmap`
+    /// only check against the digest in its header
+    pub fn validate(&self, level: ValidationLevel) -> Result<(), KzgError> {
+        validate_g1_affines::<TG1, TG1Fp, TG1Affine>(&self.points, level)
+    }
+
+    /// Write the table to the file at `path`, see `write_to_writer`
+    #[cfg(feature = "std")]
+    pub fn write_to_file(&self, path: &str) -> Result<(), KzgError> {
+        self.write_to_file_with_options(path, &FileOptions::default())
+    }
+
+    /// Same as `write_to_file`, compressing the file with the zstd level of `options`
+    #[cfg(feature = "std")]
+    pub fn write_to_file_with_options(
+        &self,
+        path: &str,
+        options: &FileOptions,
+    ) -> Result<(), KzgError> {
+        write_file(path, options, |writer| self.write_to_writer(writer))
+    }
+
+    /// Read the table file at `path`, plain or zstd compressed, see `read_from_reader`
+    ///
+    /// # Safety
+    ///
+    /// Same as `read_from_reader`.
+    #[cfg(feature = "std")]
+    pub unsafe fn read_from_file(path: &str) -> Result<Self, KzgError> {
+        read_file(path, |reader| Self::read_from_reader(reader))
+    }
+
+    /// Map the table file at `path` into memory instead of reading it into an allocation. The
+    /// digest is checked with a single sequential pass over the mapping, and no memory is
+    /// allocated for the points.
+    ///
+    /// # Safety
+    ///
+    /// Same as `read_from_reader`. In addition, the file must not be modified while the table is
+    /// alive.
+    ///
+    /// Only backends whose affine points are laid out as their coordinates can map tables, see
+    /// `supports_mmap`, others fail with `Unsupported` and should use `read_from_file`.
+    #[cfg(feature = "mmap")]
+    pub unsafe fn load_mmap(path: &str) -> Result<Self, KzgError> {
+        if !Self::supports_mmap() {
+            return Err(KzgError::Unsupported(String::from(
+                "Affine points of this backend cannot be mapped from a table file",
+            )));
+        }
+
+        let file = File::open(path)?;
+        let mmap = memmap2::Mmap::map(&file)?;
+
+        if mmap.len() < TABLE_FILE_HEADER_SIZE {
+            return Err(KzgError::InvalidLength(String::from(
+                "Table file is too short",
+            )));
+        }
+        let (mut table, len, digest) = Self::from_header(&mmap[..TABLE_FILE_HEADER_SIZE])?;
+        let expected = len
+            .checked_mul(core::mem::size_of::<TG1Affine>())
+            .and_then(|bytes| bytes.checked_add(TABLE_FILE_HEADER_SIZE));
+        if expected != Some(mmap.len()) {
+            return Err(KzgError::InvalidLength(String::from(
+                "Table file size does not match its header",
+            )));
+        }
+        let points_ptr = mmap[TABLE_FILE_HEADER_SIZE..].as_ptr();
+        if points_ptr.align_offset(core::mem::align_of::<TG1Affine>()) != 0 {
+            return Err(KzgError::InvalidArgument(String::from(
+                "Mapped table is not aligned",
+            )));
+        }
+
+        table.points = TablePoints::Mapped {
+            mmap: Arc::new(mmap),
+            len,
+            marker: PhantomData,
+        };
+        Self::check_digest(&table.points, &digest)?;
+        Ok(table)
+    }
+
+    /// Build the table of `points` directly into the file at `path`, in the format of
+    /// `write_to_file`, holding a single row of `points.len()` points in memory instead of the
+    /// whole table
+    ///
+    /// Each row is flushed to the file and recorded in a manifest at `path` followed by
+    /// `.manifest`, which is removed once the table is complete. A call for the same
+    /// points and path after an interruption resumes after the last recorded row, and starts
+    /// over if the manifest is missing or was written for other points or another window layout.
+    /// The header is written last, so that an incomplete file never loads as a table.
+    ///
+    /// # Safety
+    ///
+    /// Resuming reads the last recorded row back as in `read_from_reader`: the file and its
+    /// manifest must only have been written by this function with a backend of the same field
+    /// representation.
+    #[cfg(feature = "std")]
+    pub unsafe fn generate_to_file(points: &[TG1], path: &str) -> Result<(), KzgError> {
+        let window = Self::layout(points.len(), Self::default_window(points.len()));
+        let (window_width, h) = get_table_dimensions(window);
+        let q = TFr::from_u64(1u64 << window_width);
+        let row_bytes = points
+            .len()
+            .checked_mul(Self::FILE_POINT_SIZE)
+            .ok_or_else(table_too_large)?;
+        let points_bytes = row_bytes.checked_mul(h).ok_or_else(table_too_large)?;
+
+        let mut row = points.to_vec();
+        let mut table_row = vec![TG1Affine::zero(); points.len()];
+
+        // The digest of the first row ties the manifest to `points`
+        TG1Affine::into_affines_loc(&mut table_row, points);
+        let manifest_header = Self::header_for(
+            window,
+            points.len(),
+            h,
+            &Sha256::digest(Self::points_bytes(&table_row)),
+        );
+        let manifest_path = format!("{}{}", path, TABLE_MANIFEST_SUFFIX);
+
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .open(path)?;
+        let file_len = file.metadata()?.len();
+        let mut rows_done = 0;
+        if let Some((rows, digest)) = read_table_manifest(&manifest_path, &manifest_header)? {
+            if rows > 0
+                && rows <= h
+                && file_len >= (TABLE_FILE_HEADER_SIZE + rows * row_bytes) as u64
+            {
+                // Row `rows - 1`, from which the next row is computed
+                let offset = TABLE_FILE_HEADER_SIZE + (rows - 1) * row_bytes;
+                file.seek(SeekFrom::Start(offset as u64))?;
+                let mut bytes = vec![0u8; row_bytes];
+                file.read_exact(&mut bytes)?;
+                if Sha256::digest(&bytes).as_slice() == digest {
+                    Self::read_points_bytes(&bytes, &mut table_row);
+                    rows_done = rows;
+                }
+            }
+        }
+
+        if rows_done == 0 {
+            // Nothing to resume from, drop whatever the file held
+            file.set_len(0)?;
+        } else {
+            row = table_row.iter().map(TG1Affine::to_proj).collect();
+            Self::fill_row(&mut row, &mut table_row, &q, true);
+        }
+
+        for j in rows_done..h {
+            Self::fill_row(&mut row, &mut table_row, &q, j + 1 < h);
+            let bytes = Self::points_bytes(&table_row);
+            file.seek(SeekFrom::Start(
+                (TABLE_FILE_HEADER_SIZE + j * row_bytes) as u64,
+            ))?;
+            file.write_all(&bytes)?;
+            file.sync_data()?;
+            write_table_manifest(
+                &manifest_path,
+                &manifest_header,
+                j + 1,
+                &Sha256::digest(&bytes),
+            )?;
+            report_progress(ProgressStage::PrecomputationTable, j + 1, h);
+        }
+        drop(row);
+        drop(table_row);
+
+        let mut hasher = Sha256::new();
+        let mut chunk = vec![0u8; TABLE_FILE_CHUNK_SIZE.min(points_bytes)];
+        let chunks = (points_bytes + TABLE_FILE_CHUNK_SIZE - 1) / TABLE_FILE_CHUNK_SIZE;
+        file.seek(SeekFrom::Start(TABLE_FILE_HEADER_SIZE as u64))?;
+        for i in 0..chunks {
+            let len = (points_bytes - i * TABLE_FILE_CHUNK_SIZE).min(TABLE_FILE_CHUNK_SIZE);
+            file.read_exact(&mut chunk[..len])?;
+            hasher.update(&chunk[..len]);
+            report_progress(ProgressStage::TableFileDigest, i + 1, chunks);
+        }
+
+        file.set_len((TABLE_FILE_HEADER_SIZE + points_bytes) as u64)?;
+        file.seek(SeekFrom::Start(0))?;
+        file.write_all(&Self::header_for(
+            window,
+            points.len(),
+            h,
+            hasher.finalize().as_slice(),
+        ))?;
+        file.sync_all()?;
+        std::fs::remove_file(&manifest_path)?;
+
+        Ok(())
+    }
+
     pub fn multiply_sequential(&self, scalars: &[Scalar256]) -> TG1 {
         let window = get_sequential_window_size(self.window);
         let mut buckets = vec![P1XYZZ::<TG1Fp>::default(); 1 << (window - 1)];
 
+        self.multiply_sequential_with(scalars, &mut buckets, None)
+    }
+
+    /// `multiply_sequential` with caller-provided buckets, which are left zeroed for the next call.
+    /// Stops early with a meaningless result once `cancel` is cancelled.
+    fn multiply_sequential_with(
+        &self,
+        scalars: &[Scalar256],
+        buckets: &mut [P1XYZZ<TG1Fp>],
+        cancel: Option<&CancelToken>,
+    ) -> TG1 {
+        let window = get_sequential_window_size(self.window);
+
+        // Zero scalars have no digits, and scalars below 2^(window - 1) only have one in the
+        // lowest window, without a Booth carry into the next. When enough scalars can be skipped,
+        // the tiles go over the indices of the others instead of the whole slice.
+        let small_bound = 1u64 << (window - 1);
+        let is_small =
+            |scalar: &Scalar256| scalar.data[1..] == [0; 3] && scalar.data[0] < small_bound;
+        let skipped = scalars.iter().filter(|scalar| is_small(scalar)).count();
+        let sparse = (skipped * SPARSE_RATIO >= scalars.len()).then(|| {
+            let nonzero = (0..scalars.len())
+                .filter(|&i| !scalars[i].is_zero())
+                .collect::<Vec<_>>();
+            let large = nonzero
+                .iter()
+                .copied()
+                .filter(|&i| !is_small(&scalars[i]))
+                .collect::<Vec<_>>();
+            (nonzero, large)
+        });
+
         let mut wbits: usize = 255 % window;
         let mut cbits: usize = wbits + 1;
         let mut bit0: usize = 255;
@@ -137,175 +913,369 @@ impl<
                 break;
             }
 
-            p1_tile_bgmw(
-                &self.points[q_idx * self.numpoints..(q_idx + 1) * self.numpoints],
-                scalars,
-                &mut buckets,
-                bit0,
-                wbits,
-                cbits,
-            );
+            let points = &self.points[q_idx * self.numpoints..(q_idx + 1) * self.numpoints];
+            match &sparse {
+                Some((_, large)) => {
+                    p1_tile_bgmw_sparse(points, scalars, large, buckets, bit0, wbits, cbits)
+                }
+                None => p1_tile_bgmw(points, scalars, buckets, bit0, wbits, cbits),
+            }
+            maybe_yield();
+            report_msm_progress(scalars.len(), self.h - q_idx, self.h);
+            if check_cancelled(cancel).is_err() {
+                buckets.fill(P1XYZZ::default());
+                return TG1::default();
+            }
 
             cbits = window;
             wbits = window;
         }
-        p1_tile_bgmw(
-            &self.points[0..self.numpoints],
-            scalars,
-            &mut buckets,
-            0,
-            wbits,
-            cbits,
-        );
+        let points = &self.points[0..self.numpoints];
+        match &sparse {
+            Some((nonzero, _)) => {
+                p1_tile_bgmw_sparse(points, scalars, nonzero, buckets, 0, wbits, cbits)
+            }
+            None => p1_tile_bgmw(points, scalars, buckets, 0, wbits, cbits),
+        }
+        report_msm_progress(scalars.len(), self.h, self.h);
 
         let mut ret = TG1::default();
-        integrate_buckets(&mut ret, &buckets, wbits - 1);
+        integrate_buckets(&mut ret, buckets, wbits - 1);
+        buckets.fill(P1XYZZ::default());
 
         ret
     }
 
+    /// One MSM per set of scalars, as `multiply_sequential` or `multiply_parallel` would compute
+    /// them, sharing the buckets and, with the `parallel` feature, a single dispatch of work to
+    /// the thread pool. Tiles of all sets are handed out to the workers together, so that many
+    /// small MSMs keep every thread busy.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a set holds more scalars than the table holds points.
+    pub fn multiply_batch(&self, scalar_sets: &[&[Scalar256]]) -> Vec<TG1> {
+        // Tiles take at least two scalars, pad shorter sets with zeros
+        let padded = scalar_sets
+            .iter()
+            .map(|scalars| {
+                assert!(
+                    scalars.len() <= self.numpoints,
+                    "More scalars than points in the precomputation table"
+                );
+                let mut padded = [Scalar256::default(); 2];
+                padded[..scalars.len().min(2)].copy_from_slice(&scalars[..scalars.len().min(2)]);
+                padded
+            })
+            .collect::<Vec<_>>();
+        let scalar_sets = scalar_sets
+            .iter()
+            .zip(&padded)
+            .map(|(scalars, padded)| {
+                if scalars.len() < 2 {
+                    &padded[..]
+                } else {
+                    scalars
+                }
+            })
+            .collect::<Vec<_>>();
+
+        #[cfg(feature = "parallel")]
+        if let Some((nx, ny, window)) = self.parallel_window() {
+            return self.multiply_batch_parallel(&scalar_sets, nx, ny, window, None);
+        }
+
+        let window = get_sequential_window_size(self.window);
+        let mut buckets = vec![P1XYZZ::<TG1Fp>::default(); 1 << (window - 1)];
+        scalar_sets
+            .iter()
+            .map(|scalars| self.multiply_sequential_with(scalars, &mut buckets, None))
+            .collect()
+    }
+
+    /// Same as `multiply_sequential`, with scalars pulled from `scalars` in chunks of `chunk_size`
+    /// instead of read from a slice. All windows share the same buckets, so every chunk is folded
+    /// into them as soon as it is read and the scalars never need to be in memory at once.
+    pub fn multiply_streaming(
+        &self,
+        scalars: impl Iterator<Item = Scalar256>,
+        chunk_size: usize,
+    ) -> Result<TG1, String> {
+        let (window, h) = get_table_dimensions(self.window);
+        let mut buckets = vec![P1XYZZ::<TG1Fp>::default(); 1 << (window - 1)];
+
+        let mut scalars = scalars;
+        let mut chunk = Vec::with_capacity(chunk_size.max(1));
+        let mut offset = 0;
+        loop {
+            chunk.clear();
+            chunk.extend(scalars.by_ref().take(chunk_size.max(1)));
+            if chunk.is_empty() {
+                break;
+            }
+            if offset + chunk.len() > self.numpoints {
+                return Err(String::from(
+                    "More scalars than points in the precomputation table",
+                ));
+            }
+
+            for row in 0..h {
+                let bit0 = row * window;
+                if bit0 > NBITS {
+                    break;
+                }
+                let (wbits, cbits) = if bit0 + window > NBITS {
+                    let wbits = NBITS - bit0;
+                    (wbits, wbits + 1)
+                } else {
+                    (window, window)
+                };
+
+                let start = row * self.numpoints + offset;
+                let points = &self.points[start..start + chunk.len()];
+                if chunk.len() == 1 {
+                    // Tiles take at least two points, pad with a zero scalar
+                    p1_tile_bgmw(
+                        &[points[0], points[0]],
+                        &[chunk[0], Scalar256::default()],
+                        &mut buckets,
+                        bit0,
+                        wbits,
+                        cbits,
+                    );
+                } else {
+                    p1_tile_bgmw(points, &chunk, &mut buckets, bit0, wbits, cbits);
+                }
+            }
+            offset += chunk.len();
+        }
+
+        let mut ret = TG1::default();
+        integrate_buckets(&mut ret, &buckets, window - 1);
+
+        Ok(ret)
+    }
+
+    /// Tile layout of a table built for the thread pool, unless threads are turned off by
+    /// `parallelism::set_parallelism`
+    #[cfg(feature = "parallel")]
+    fn parallel_window(&self) -> Option<(usize, usize, usize)> {
+        match self.window {
+            BgmwWindow::Parallel(layout) if crate::parallelism::is_parallel() => Some(layout),
+            _ => None,
+        }
+    }
+
     #[cfg(feature = "parallel")]
     pub fn multiply_parallel(&self, scalars: &[Scalar256]) -> TG1 {
-        use super::{
-            cell::Cell,
-            thread_pool::{da_pool, ThreadPoolExt},
-        };
+        match self.parallel_window() {
+            None => self.multiply_sequential(scalars),
+            Some((nx, ny, window)) => self
+                .multiply_batch_parallel(&[scalars], nx, ny, window, None)
+                .pop()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// `multiply_parallel` with the `parallel` feature, `multiply_sequential` without, checking
+    /// `cancel` between tiles and failing with `Cancelled` once it is cancelled
+    pub fn multiply_cancellable(
+        &self,
+        scalars: &[Scalar256],
+        cancel: &CancelToken,
+    ) -> Result<TG1, Cancelled> {
+        #[cfg(feature = "parallel")]
+        if let Some((nx, ny, window)) = self.parallel_window() {
+            let result = self
+                .multiply_batch_parallel(&[scalars], nx, ny, window, Some(cancel))
+                .pop()
+                .unwrap_or_default();
+            return cancel.check().map(|_| result);
+        }
+
+        let window = get_sequential_window_size(self.window);
+        let mut buckets = vec![P1XYZZ::<TG1Fp>::default(); 1 << (window - 1)];
+        let result = self.multiply_sequential_with(scalars, &mut buckets, Some(cancel));
+        cancel.check().map(|_| result)
+    }
+
+    /// Stops early with meaningless results once `cancel` is cancelled
+    #[cfg(feature = "parallel")]
+    fn multiply_batch_parallel(
+        &self,
+        scalar_sets: &[&[Scalar256]],
+        nx: usize,
+        ny: usize,
+        window: usize,
+        cancel: Option<&CancelToken>,
+    ) -> Vec<TG1> {
+        use super::thread_pool::{da_pool, ThreadPoolExt};
         use core::sync::atomic::{AtomicUsize, Ordering};
-        use std::sync::{mpsc, Arc};
+        use std::sync::mpsc;
 
-        let npoints = scalars.len();
         let pool = da_pool();
-        let ncpus = pool.max_count();
+        let max_threads = crate::parallelism::max_threads();
+
+        // One pool per NUMA node when enabled, each taking a band of columns of every set
+        #[cfg(feature = "numa")]
+        let pools = super::numa::node_pools()
+            .filter(|_| super::numa::numa_msm())
+            .unwrap_or(core::slice::from_ref(&pool));
+        #[cfg(not(feature = "numa"))]
+        let pools = core::slice::from_ref(&pool);
 
         struct Tile {
+            set: usize,
             x: usize,
             dx: usize,
             y: usize,
-            dy: usize,
         }
 
-        let (nx, ny, window) = match self.window {
-            BgmwWindow::Sync(_) => return self.multiply_sequential(scalars),
-            BgmwWindow::Parallel(values) => values,
-        };
-
-        // |grid[]| holds "coordinates" and place for result
-        let mut grid: Vec<(Tile, Cell<TG1>)> = Vec::with_capacity(nx * ny);
-        #[allow(clippy::uninit_vec)]
-        unsafe {
-            grid.set_len(grid.capacity())
-        };
-        let dx = npoints / nx;
-        let mut y = window * (ny - 1);
-        let mut total = 0usize;
-
-        while total < nx {
-            grid[total].0.x = total * dx;
-            grid[total].0.dx = dx;
-            grid[total].0.y = y;
-            grid[total].0.dy = 255 - y;
-            total += 1;
-        }
-        grid[total - 1].0.dx = npoints - grid[total - 1].0.x;
-        while y != 0 {
-            y -= window;
-            for i in 0..nx {
-                grid[total].0.x = grid[i].0.x;
-                grid[total].0.dx = grid[i].0.dx;
-                grid[total].0.y = y;
-                grid[total].0.dy = window;
-                total += 1;
+        // Tiles of every set, set by set: columns of `dx` points, rows of `window` bits from the
+        // top, so that workers taking tiles in order see each set in one contiguous run
+        let mut grids: Vec<Vec<Tile>> = (0..pools.len()).map(|_| Vec::new()).collect();
+        for (set, scalars) in scalar_sets.iter().enumerate() {
+            let npoints = scalars.len();
+            let nx = nx.min(npoints / 2).max(1);
+            let dx = npoints / nx;
+            let mut y = window * (ny - 1);
+            loop {
+                for i in 0..nx {
+                    let x = i * dx;
+                    let dx = if i + 1 == nx { npoints - x } else { dx };
+                    grids[i * pools.len() / nx].push(Tile { set, x, dx, y });
+                }
+                if y == 0 {
+                    break;
+                }
+                y -= window;
             }
         }
-        let grid = &grid[..];
-
-        let mut row_sync: Vec<AtomicUsize> = Vec::with_capacity(ny);
-        row_sync.resize_with(ny, Default::default);
-        let counter = Arc::new(AtomicUsize::new(0));
+        let total = grids.iter().map(Vec::len).sum::<usize>();
+        let npoints = scalar_sets
+            .iter()
+            .map(|scalars| scalars.len())
+            .sum::<usize>();
+        let pool_threads = pools.iter().map(|pool| pool.max_count()).sum::<usize>();
+
+        let counters = (0..pools.len())
+            .map(|_| AtomicUsize::new(0))
+            .collect::<Vec<_>>();
+        let done = AtomicUsize::new(0);
+        let done = &done;
         let (tx, rx) = mpsc::channel();
-        let n_workers = core::cmp::min(ncpus, total);
-
-        let mut results: Vec<Cell<TG1>> = Vec::with_capacity(n_workers);
-        #[allow(clippy::uninit_vec)]
-        unsafe {
-            results.set_len(results.capacity());
-        };
-
-        let results = &results[..];
-
-        #[allow(clippy::needless_range_loop)]
-        for worker_index in 0..n_workers {
-            let tx = tx.clone();
-            let counter = counter.clone();
-
-            pool.joined_execute(move || {
-                let mut buckets = vec![P1XYZZ::<TG1Fp>::default(); 1 << (window - 1)];
-                loop {
-                    let work = counter.fetch_add(1, Ordering::Relaxed);
-                    if work >= total {
-                        integrate_buckets(
-                            unsafe { results[worker_index].as_ptr().as_mut() }.unwrap(),
-                            &buckets,
-                            window - 1,
-                        );
-                        tx.send(worker_index).expect("disaster");
-
-                        break;
+        let mut n_workers = 0;
+
+        for ((pool, grid), counter) in pools.iter().zip(&grids).zip(&counters) {
+            // Threads of each pool in proportion to its size, at most `max_threads` overall
+            let share = (max_threads * pool.max_count() + pool_threads - 1) / pool_threads;
+            let workers = share.min(pool.max_count()).min(grid.len());
+            n_workers += workers;
+
+            for _ in 0..workers {
+                let tx = tx.clone();
+
+                pool.joined_execute(move || {
+                    let mut buckets = vec![P1XYZZ::<TG1Fp>::default(); 1 << (window - 1)];
+                    let mut partials: Vec<(usize, TG1)> = Vec::new();
+                    let mut current = None;
+                    loop {
+                        let work = counter.fetch_add(1, Ordering::Relaxed);
+                        let tile = grid.get(work).filter(|_| check_cancelled(cancel).is_ok());
+
+                        // Fold the buckets into a partial result whenever the set changes
+                        if let Some(set) = current {
+                            if tile.map(|tile| tile.set) != Some(set) {
+                                let mut partial = TG1::default();
+                                integrate_buckets(&mut partial, &buckets, window - 1);
+                                buckets.fill(P1XYZZ::default());
+                                partials.push((set, partial));
+                            }
+                        }
+                        let Some(tile) = tile else {
+                            tx.send(partials).expect("disaster");
+                            break;
+                        };
+                        current = Some(tile.set);
+
+                        let row_start = (tile.y / window) * self.numpoints + tile.x;
+                        let points = &self.points[row_start..(row_start + tile.dx)];
+                        let scalars = &scalar_sets[tile.set][tile.x..tile.x + tile.dx];
+
+                        let (wbits, cbits) = if tile.y + window > NBITS {
+                            let wbits = NBITS - tile.y;
+                            (wbits, wbits + 1)
+                        } else {
+                            (window, window)
+                        };
+
+                        p1_tile_bgmw(points, scalars, &mut buckets, tile.y, wbits, cbits);
+                        let done = done.fetch_add(1, Ordering::Relaxed) + 1;
+                        report_msm_progress(npoints, done, total);
                     }
+                });
+            }
+        }
 
-                    let x = grid[work].0.x;
-                    let y = grid[work].0.y;
-                    let dx = grid[work].0.dx;
-
-                    let row_start = (y / window) * self.numpoints + x;
-                    let points = &self.points[row_start..(row_start + dx)];
+        // Partial sums of every worker, across nodes
+        let mut ret = vec![TG1::default(); scalar_sets.len()];
+        for _ in 0..n_workers {
+            for (set, partial) in rx.recv().unwrap() {
+                ret[set].add_or_dbl_assign(&partial);
+            }
+        }
+        ret
+    }
 
-                    let (wbits, cbits) = if y + window > NBITS {
-                        let wbits = NBITS - y;
-                        (wbits, wbits + 1)
-                    } else {
-                        (window, window)
-                    };
+    /// Window for `npoints` points, within the range accepted by `new_with_window` and table files
+    fn default_window(npoints: usize) -> usize {
+        Self::pick_window(npoints).clamp(BGMW_MIN_WINDOW, BGMW_MAX_WINDOW)
+    }
 
-                    p1_tile_bgmw(points, &scalars[x..], &mut buckets, y, wbits, cbits);
-                }
-            });
+    fn pick_window(npoints: usize) -> usize {
+        #[cfg(feature = "parallel")]
+        {
+            pippenger_window_size(npoints)
         }
 
-        let mut ret = <TG1>::default();
-        for _ in 0..n_workers {
-            let idx = rx.recv().unwrap();
+        #[cfg(not(feature = "parallel"))]
+        {
+            let n_exponent = npoints.trailing_zeros();
 
-            ret.add_or_dbl_assign(results[idx].as_mut());
+            // Only 2^12 points have a measured window, the best one depends on the machine for
+            // other sizes. `kzg-cli tune` sweeps the windows (q = 2^window) on the current machine
+            // and writes a `TuningProfile` for `TuningProfile::precompute`
+            match n_exponent {
+                12 => 13, // this value is picked from https://github.com/LuoGuiwen/MSM_blst/blob/2e098f09f07969ac3191406976be6d1c197100f2/ches_config_files/config_file_n_exp_12.h#L17
+                _ => {
+                    // default to pippenger window size. This is not optimal window size, but still better than simple pippenger
+                    let window = pippenger_window_size(npoints);
+                    emit_warning(Warning::SuboptimalWindow { npoints, window });
+                    window
+                }
+            }
         }
-        ret
     }
 
-    fn window(npoints: usize) -> BgmwWindow {
+    #[cfg_attr(not(feature = "parallel"), allow(unused_variables))]
+    fn layout(npoints: usize, window: usize) -> BgmwWindow {
         #[cfg(feature = "parallel")]
         {
-            let default_window = pippenger_window_size(npoints);
-
             use super::{parallel_pippenger_utils::breakdown, thread_pool::da_pool};
 
             let pool = da_pool();
             let ncpus = pool.max_count();
             if npoints > 32 && ncpus > 2 {
-                BgmwWindow::Parallel(breakdown(default_window, ncpus))
+                BgmwWindow::Parallel(breakdown(window, ncpus))
             } else {
-                BgmwWindow::Sync(default_window)
+                emit_warning(Warning::ParallelFallbackToSequential { npoints, ncpus });
+                BgmwWindow::Sync(window)
             }
         }
 
         #[cfg(not(feature = "parallel"))]
         {
-            let n_exponent = npoints.trailing_zeros();
-
-            // TODO: experiment with different q exponents, to find optimal
-            match n_exponent {
-                12 => 13, // this value is picked from https://github.com/LuoGuiwen/MSM_blst/blob/2e098f09f07969ac3191406976be6d1c197100f2/ches_config_files/config_file_n_exp_12.h#L17
-                _ => pippenger_window_size(npoints), // default to pippenger window size. This is not optimal window size, but still better than simple pippenger
-            }
+            window
         }
     }
 }
@@ -369,9 +1339,8 @@ pub fn p1_tile_bgmw<TG1: G1 + G1GetFp<TFp>, TFp: G1Fp, TG1Affine: G1Affine<TG1,
         wnxt = (get_wval_limb(scalar, bit0, wbits) << z) & wmask;
         wnxt = booth_encode(wnxt, cbits);
 
-        // TODO: add prefetching
-        // POINTonE1_prefetch(buckets, wnxt, cbits);
-        // p1_prefetch(buckets, wnxt, cbits);
+        // Fetch the next bucket while the current point is added
+        p1_prefetch(buckets, wnxt, cbits);
 
         // Get current point
         let point = &points[i];
@@ -386,6 +1355,29 @@ pub fn p1_tile_bgmw<TG1: G1 + G1GetFp<TFp>, TFp: G1Fp, TG1Affine: G1Affine<TG1,
     booth_decode(buckets, wnxt, cbits, point);
 }
 
+/// Same as `p1_tile_bgmw`, for the scalars and points at `indices` only
+#[allow(clippy::too_many_arguments)]
+pub fn p1_tile_bgmw_sparse<TG1: G1 + G1GetFp<TFp>, TFp: G1Fp, TG1Affine: G1Affine<TG1, TFp>>(
+    points: &[TG1Affine],
+    scalars: &[Scalar256],
+    indices: &[usize],
+    buckets: &mut [P1XYZZ<TFp>],
+    bit0: usize,
+    wbits: usize,
+    cbits: usize,
+) {
+    // Same window extraction as `p1_tile_bgmw`
+    let wmask = (1u64 << (wbits + 1)) - 1;
+    let z = is_zero(bit0.try_into().unwrap());
+    let bit0 = bit0 - (z ^ 1) as usize;
+    let wbits = wbits + (z ^ 1) as usize;
+
+    for &i in indices {
+        let wval = (get_wval_limb(&scalars[i], bit0, wbits) << z) & wmask;
+        booth_decode(buckets, booth_encode(wval, cbits), cbits, &points[i]);
+    }
+}
+
 /// Calculate bucket sum
 ///
 /// This function multiplies the point in each bucket by it's index. Then, it will sum all multiplication results and write
@@ -402,21 +1394,6 @@ fn integrate_buckets<TG1: G1 + G1GetFp<TFp>, TFp: G1Fp>(
     buckets: &[P1XYZZ<TFp>],
     wbits: usize,
 ) {
-    let mut n = (1usize << wbits) - 1;
-    let mut ret = buckets[n];
-    let mut acc = buckets[n];
-
-    loop {
-        if n == 0 {
-            break;
-        }
-        n -= 1;
-
-        if type_is_zero(&buckets[n]) == 0 {
-            p1_dadd(&mut acc, &buckets[n]);
-        }
-        p1_dadd(&mut ret, &acc);
-    }
-
+    let ret = integrate_buckets_xyzz(&buckets[..1 << wbits]);
     p1_to_jacobian(out, &ret);
 }
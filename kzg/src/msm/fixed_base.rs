@@ -0,0 +1,170 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use crate::{Fr, G1Affine, G1Fp, G1GetFp, G1Mul, G1ProjAddAffine, Poly, Scalar256, G1};
+
+use super::pippenger_utils::get_wval_limb;
+
+////////////////////////////// Fixed-base MSM //////////////////////////////
+//
+// Commitments are MSMs over the same setup points every time. `FixedBaseMsm` stores, for every
+// point `P` and every window `j`, all the multiples `d * 2^(j * window) * P` for the digits
+// `d = 1 .. 2^window - 1`. A scalar multiplication then costs one table lookup and one mixed
+// addition per non-zero digit, with no doublings and no bucket accumulation, so that an MSM of
+// `n` points costs about `n * ceil(255 / window)` additions.
+//
+// Unlike the BGMW table, whose size grows with `ceil(255 / window)`, the table holds
+// `n * ceil(255 / window) * (2^window - 1)` affine points: 8-bit windows over the 4096 points of
+// the EIP-4844 setup take about 3 GiB, 4-bit windows about 360 MiB. The window is chosen by the
+// caller to fit the memory budget.
+//
+// Each addition is a full mixed addition into a single accumulator, while Pippenger amortizes
+// cheaper bucket additions over many points. With blst, 8-bit windows commit about 3x faster
+// than `msm` for 16 points and break even around 256 points; for a full blob, where the tables
+// do not fit in the caches, `msm` with or without the BGMW table is faster. The table pays off
+// for small polynomials committed repeatedly against the same bases.
+
+const NBITS: usize = 255;
+
+#[derive(Debug, Clone)]
+pub struct FixedBaseMsm<TFr, TG1, TG1Fp, TG1Affine, TProjAddAffine>
+where
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+    TProjAddAffine: G1ProjAddAffine<TG1, TG1Fp, TG1Affine>,
+{
+    window: usize,
+    /// Windows per scalar
+    h: usize,
+    numpoints: usize,
+    /// `table[(i * h + j) * (2^window - 1) + d - 1] = d * 2^(j * window) * points[i]`
+    table: Vec<TG1Affine>,
+
+    fr_marker: PhantomData<TFr>,
+    g1_marker: PhantomData<TG1>,
+    g1_fp_marker: PhantomData<TG1Fp>,
+    proj_add_affine_marker: PhantomData<TProjAddAffine>,
+}
+
+impl<
+        TFr: Fr,
+        TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+        TG1Fp: G1Fp,
+        TG1Affine: G1Affine<TG1, TG1Fp>,
+        TProjAddAffine: G1ProjAddAffine<TG1, TG1Fp, TG1Affine>,
+    > FixedBaseMsm<TFr, TG1, TG1Fp, TG1Affine, TProjAddAffine>
+{
+    /// Build the tables of `points` for digits of `window` bits, between 1 and 16
+    pub fn new(points: &[TG1], window: usize) -> Result<Self, String> {
+        if !(1..=16).contains(&window) {
+            return Err(String::from(
+                "Fixed-base window must be between 1 and 16 bits",
+            ));
+        }
+        let h = (NBITS + window - 1) / window;
+        let digits = (1 << window) - 1;
+
+        let mut table: Vec<TG1Affine> = Vec::new();
+        points
+            .len()
+            .checked_mul(h * digits)
+            .and_then(|len| table.try_reserve_exact(len).ok())
+            .ok_or_else(|| "Fixed-base precomputation table is too large".to_string())?;
+
+        let mut multiples = Vec::with_capacity(digits);
+        for point in points {
+            // Batch affine conversion does not support the identity
+            if point.is_inf() {
+                table.resize(table.len() + h * digits, TG1Affine::zero());
+                continue;
+            }
+            let mut base = point.clone();
+            for _ in 0..h {
+                multiples.clear();
+                multiples.push(base.clone());
+                for d in 1..digits {
+                    multiples.push(multiples[d - 1].add_or_dbl(&base));
+                }
+                table.extend(TG1Affine::into_affines(&multiples));
+                base = multiples[digits - 1].add_or_dbl(&base);
+            }
+        }
+
+        Ok(Self {
+            window,
+            h,
+            numpoints: points.len(),
+            table,
+
+            fr_marker: PhantomData,
+            g1_marker: PhantomData,
+            g1_fp_marker: PhantomData,
+            proj_add_affine_marker: PhantomData,
+        })
+    }
+
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Bytes held by the precomputed points
+    pub fn memory_usage(&self) -> usize {
+        self.table.capacity() * core::mem::size_of::<TG1Affine>()
+    }
+
+    fn multiply_range(&self, start: usize, scalars: &[Scalar256]) -> TG1 {
+        let digits = (1 << self.window) - 1;
+        let mut acc = TG1::identity();
+        for (i, scalar) in (start..).zip(scalars) {
+            let tables = &self.table[i * self.h * digits..(i + 1) * self.h * digits];
+            for (j, table) in tables.chunks_exact(digits).enumerate() {
+                let bit0 = j * self.window;
+                let wbits = self.window.min(NBITS - bit0);
+                let digit = (get_wval_limb(scalar, bit0, wbits) & ((1 << wbits) - 1)) as usize;
+                if digit != 0 {
+                    TProjAddAffine::add_or_double_assign_affine(&mut acc, &table[digit - 1]);
+                }
+            }
+        }
+
+        acc
+    }
+
+    /// `Σ scalars[i] * points[i]` over the first `scalars.len()` points, split across the thread
+    /// pool with the `parallel` feature
+    pub fn multiply(&self, scalars: &[Scalar256]) -> TG1 {
+        let scalars = &scalars[..scalars.len().min(self.numpoints)];
+
+        #[cfg(feature = "parallel")]
+        if crate::parallelism::is_parallel() {
+            use rayon::prelude::*;
+
+            return crate::parallelism::install(|| {
+                let chunk_size = (scalars.len() / rayon::current_num_threads()).max(1 << 6);
+                scalars
+                    .par_chunks(chunk_size)
+                    .enumerate()
+                    .map(|(chunk, scalars)| self.multiply_range(chunk * chunk_size, scalars))
+                    .reduce(TG1::identity, |a, b| a.add_or_dbl(&b))
+            });
+        }
+
+        self.multiply_range(0, scalars)
+    }
+
+    /// Commitment to `poly`, whose coefficients are taken in the basis of the table points, e.g.
+    /// evaluations for the Lagrange form of the setup
+    pub fn commit<TPoly: Poly<TFr>>(&self, poly: &TPoly) -> Result<TG1, String> {
+        if poly.len() > self.numpoints {
+            return Err(String::from(
+                "Polynomial is longer than the fixed-base precomputation",
+            ));
+        }
+        let scalars = Scalar256::from_fr_batch(poly.get_coeffs());
+
+        Ok(self.multiply(&scalars))
+    }
+}
@@ -1,13 +1,24 @@
+#[cfg(feature = "gpu")]
+pub mod accelerator;
 pub mod arkmsm;
 pub mod cell;
+pub mod fixed_base;
+pub mod msm_g2;
 pub mod msm_impls;
+#[cfg(feature = "numa")]
+pub mod numa;
 pub mod precompute;
 #[cfg(feature = "parallel")]
 pub mod thread_pool;
 #[cfg(feature = "parallel")]
 pub mod tiling_parallel_pippenger;
 pub mod tiling_pippenger_ops;
+#[cfg(feature = "std")]
+pub mod tuner;
 pub mod types;
+#[cfg(feature = "msm-verify")]
+pub mod verify_mode;
+pub mod wnaf;
 
 #[cfg(feature = "parallel")]
 mod parallel_pippenger_utils;
@@ -15,3 +26,29 @@ mod pippenger_utils;
 
 #[cfg(all(feature = "bgmw", any(not(feature = "arkmsm"), feature = "parallel")))]
 mod bgmw;
+
+use alloc::string::String;
+
+use crate::{Fr, G1};
+
+pub use pippenger_utils::{
+    integrate_buckets_affine, integrate_buckets_xyzz, p1_to_jacobian, P1XYZZ,
+};
+
+/// Multi-scalar multiplication `Σ scalars[i] * points[i]` over a set of points, hiding the
+/// precomputation tables and scalar conversions behind one interface
+///
+/// Implemented by `msm_impls::PippengerMsm` and `msm_impls::BgmwMsm`, and open to device
+/// implementations in other crates.
+pub trait MsmBackend<TFr: Fr, TG1: G1>: Sized {
+    /// Prepare the MSM over `points`, building any table the implementation uses
+    fn precompute(points: &[TG1]) -> Result<Self, String>;
+
+    /// MSM over the points given to `precompute`, `scalars` must have the same length
+    fn msm(&self, scalars: &[TFr]) -> Result<TG1, String>;
+
+    /// One-off MSM over `points`, without keeping anything for later calls
+    fn msm_with_points(points: &[TG1], scalars: &[TFr]) -> Result<TG1, String> {
+        Self::precompute(points)?.msm(scalars)
+    }
+}
@@ -0,0 +1,206 @@
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use crate::{Fr, G2Mul, Scalar256, G2};
+
+use super::pippenger_utils::pippenger_window_size;
+
+////////////////////////////// MSM over G2 //////////////////////////////
+//
+// Multi-proof verification and IPA-style protocols need MSMs over G2, for which the traits
+// provide no affine form or coordinate access. The functions below only use the `G2` group law,
+// with `G2::default()` as the identity like the G1 code does.
+//
+// `BgmwTableG2` is the G2 counterpart of the BGMW table: row `j` holds `2^(j * window) * P_i`
+// for every point, so that all windows of all scalars share a single set of buckets and no
+// doublings are needed at multiplication time. Digits are unsigned, so the `ceil(255 / window)`
+// rows cover every canonical scalar.
+
+const NBITS: usize = 255;
+
+/// Bits `[bit0, bit0 + wbits)` of `scalar`
+fn get_digit(scalar: &Scalar256, bit0: usize, wbits: usize) -> usize {
+    let bytes = scalar.as_u8();
+    let mut limb = [0u8; 4];
+    let start = bit0 / 8;
+    let end = (start + 4).min(bytes.len());
+    limb[..end - start].copy_from_slice(&bytes[start..end]);
+
+    ((u32::from_le_bytes(limb) >> (bit0 % 8)) as usize) & ((1 << wbits) - 1)
+}
+
+fn accumulate<TG2: G2>(
+    buckets: &mut [Option<TG2>],
+    points: &[TG2],
+    scalars: &[Scalar256],
+    bit0: usize,
+    wbits: usize,
+) {
+    for (point, scalar) in points.iter().zip(scalars) {
+        let digit = get_digit(scalar, bit0, wbits);
+        if digit == 0 {
+            continue;
+        }
+        let bucket = &mut buckets[digit - 1];
+        *bucket = Some(match bucket {
+            Some(sum) => sum.add_or_dbl(point),
+            None => point.clone(),
+        });
+    }
+}
+
+/// `Σ (d + 1) * buckets[d]`, with the running sum trick. Buckets are emptied.
+fn integrate_buckets<TG2: G2>(buckets: &mut [Option<TG2>]) -> Option<TG2> {
+    let mut running: Option<TG2> = None;
+    let mut total: Option<TG2> = None;
+    for bucket in buckets.iter_mut().rev() {
+        if let Some(point) = bucket.take() {
+            running = Some(match &mut running {
+                Some(sum) => sum.add_or_dbl(&point),
+                None => point,
+            });
+        }
+        if let Some(running) = &running {
+            total = Some(match &mut total {
+                Some(sum) => sum.add_or_dbl(running),
+                None => running.clone(),
+            });
+        }
+    }
+
+    total
+}
+
+#[derive(Debug, Clone)]
+pub struct BgmwTableG2<TFr, TG2>
+where
+    TFr: Fr,
+    TG2: G2 + G2Mul<TFr>,
+{
+    window: usize,
+    points: Vec<TG2>,
+    numpoints: usize,
+    h: usize,
+
+    fr_marker: PhantomData<TFr>,
+}
+
+impl<TFr: Fr, TG2: G2 + G2Mul<TFr>> BgmwTableG2<TFr, TG2> {
+    pub fn new(points: &[TG2]) -> Result<Option<Self>, String> {
+        let window = pippenger_window_size(points.len());
+        let h = (NBITS + window - 1) / window;
+
+        let mut table: Vec<TG2> = Vec::new();
+        table
+            .try_reserve_exact(points.len() * h)
+            .map_err(|_| "BGMW precomputation table is too large".to_string())?;
+        table.resize(points.len() * h, TG2::default());
+
+        let q = TFr::from_u64(1u64 << window);
+        for (i, point) in points.iter().enumerate() {
+            let mut tmp_point = point.clone();
+            for j in 0..h {
+                table[j * points.len() + i] = tmp_point.clone();
+                tmp_point = tmp_point.mul(&q);
+            }
+        }
+
+        Ok(Some(Self {
+            window,
+            points: table,
+            numpoints: points.len(),
+            h,
+
+            fr_marker: PhantomData,
+        }))
+    }
+
+    /// Bytes held by the precomputed points
+    pub fn memory_usage(&self) -> usize {
+        self.points.capacity() * core::mem::size_of::<TG2>()
+    }
+
+    pub fn multiply_sequential(&self, scalars: &[Scalar256]) -> TG2 {
+        let scalars = &scalars[..scalars.len().min(self.numpoints)];
+        let mut buckets = vec![None; (1 << self.window) - 1];
+
+        for row in 0..self.h {
+            let bit0 = row * self.window;
+            let start = row * self.numpoints;
+            accumulate(
+                &mut buckets,
+                &self.points[start..start + scalars.len()],
+                scalars,
+                bit0,
+                self.window.min(NBITS - bit0),
+            );
+        }
+
+        integrate_buckets(&mut buckets).unwrap_or_default()
+    }
+}
+
+/// Windowed Pippenger over G2, for points without a precomputation table
+pub fn g2_pippenger<TG2: G2>(points: &[TG2], scalars: &[Scalar256]) -> TG2 {
+    let len = points.len().min(scalars.len());
+    let window = pippenger_window_size(len);
+    let mut buckets = vec![None; (1 << window) - 1];
+
+    let mut ret: Option<TG2> = None;
+    let mut bit0 = (NBITS - 1) / window * window;
+    loop {
+        if let Some(acc) = &mut ret {
+            for _ in 0..window {
+                *acc = acc.dbl();
+            }
+        }
+
+        accumulate(
+            &mut buckets,
+            &points[..len],
+            &scalars[..len],
+            bit0,
+            window.min(NBITS - bit0),
+        );
+        if let Some(sum) = integrate_buckets(&mut buckets) {
+            ret = Some(match &mut ret {
+                Some(acc) => acc.add_or_dbl(&sum),
+                None => sum,
+            });
+        }
+
+        if bit0 == 0 {
+            break;
+        }
+        bit0 -= window;
+    }
+
+    ret.unwrap_or_default()
+}
+
+/// `Σ scalars[i] * points[i]` over the first `len` points, with the precomputation table if
+/// given
+pub fn msm_g2<TFr: Fr, TG2: G2 + G2Mul<TFr>>(
+    points: &[TG2],
+    scalars: &[TFr],
+    len: usize,
+    precomputation: Option<&BgmwTableG2<TFr, TG2>>,
+) -> TG2 {
+    if len < 8 {
+        let mut out = TG2::default();
+        for i in 0..len {
+            out = out.add_or_dbl(&points[i].mul(&scalars[i]));
+        }
+        return out;
+    }
+
+    let scalars = Scalar256::from_fr_batch(&scalars[0..len]);
+
+    if let Some(precomputation) = precomputation {
+        precomputation.multiply_sequential(&scalars)
+    } else {
+        g2_pippenger(&points[0..len], &scalars)
+    }
+}
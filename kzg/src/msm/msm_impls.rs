@@ -1,15 +1,23 @@
-use crate::{Fr, G1Affine, G1Fp, G1GetFp, G1Mul, G1ProjAddAffine, Scalar256, G1};
+use crate::batch_inverse::to_affines_batch;
+use crate::{Fr, G1Affine, G1Fp, G1GetFp, G1LinComb, G1Mul, G1ProjAddAffine, Scalar256, G1};
+use alloc::string::String;
 use alloc::vec::Vec;
+use core::marker::PhantomData;
 
+#[cfg(feature = "gpu")]
+use super::accelerator::accelerated_msm;
 #[cfg(all(feature = "arkmsm", not(feature = "parallel")))]
 use super::arkmsm::arkmsm_msm::VariableBaseMSM;
-use super::precompute::PrecomputationTable;
+use super::precompute::{precompute, PrecomputationTable};
+use super::MsmBackend;
 
 #[cfg(all(not(feature = "arkmsm"), not(feature = "parallel")))]
 use super::tiling_pippenger_ops::tiling_pippenger;
+use super::tiling_pippenger_ops::{tiling_pippenger_bounded, tiling_pippenger_streaming};
+use super::wnaf::{wnaf_msm, WNAF_DEFAULT_WINDOW};
 
 #[cfg(feature = "parallel")]
-use super::tiling_parallel_pippenger::{parallel_affine_conv, tiling_parallel_pippenger};
+use super::tiling_parallel_pippenger::tiling_parallel_pippenger;
 
 #[cfg(feature = "parallel")]
 fn msm_parallel<
@@ -62,11 +70,7 @@ fn msm_sequential<
 fn batch_convert<TG1: G1, TFp: G1Fp, TG1Affine: G1Affine<TG1, TFp> + Sized>(
     points: &[TG1],
 ) -> Vec<TG1Affine> {
-    #[cfg(feature = "parallel")]
-    return parallel_affine_conv::<TG1, TFp, TG1Affine>(points);
-
-    #[cfg(not(feature = "parallel"))]
-    return TG1Affine::into_affines(points);
+    to_affines_batch::<TG1, TFp, TG1Affine>(points)
 }
 
 #[allow(clippy::extra_unused_type_parameters)]
@@ -91,19 +95,293 @@ pub fn msm<
         return out;
     }
 
+    #[cfg(feature = "gpu")]
+    if let Some(out) = accelerated_msm(&points[0..len], &scalars[0..len]) {
+        return out;
+    }
+
+    #[cfg(feature = "msm-verify")]
+    let (original_points, original_scalars) = (points, scalars);
+
     let points = batch_convert::<TG1, TG1Fp, TG1Affine>(&points[0..len]);
-    let scalars = scalars[0..len]
-        .iter()
-        .map(TFr::to_scalar)
-        .collect::<Vec<_>>();
+    let scalars = Scalar256::from_fr_batch(&scalars[0..len]);
 
     #[cfg(feature = "parallel")]
-    return msm_parallel::<TFr, TG1, TG1Fp, TG1Affine>(&points, &scalars, precomputation);
+    let result = msm_parallel::<TFr, TG1, TG1Fp, TG1Affine>(&points, &scalars, precomputation);
 
     #[cfg(not(feature = "parallel"))]
-    return msm_sequential::<TFr, TG1, TG1Fp, TG1Affine, TProjAddAffine>(
+    let result = msm_sequential::<TFr, TG1, TG1Fp, TG1Affine, TProjAddAffine>(
         &points,
         &scalars,
         precomputation,
     );
+
+    #[cfg(feature = "msm-verify")]
+    if let Some(table) = precomputation {
+        super::verify_mode::verify_table_msm(
+            &original_points[0..len],
+            &original_scalars[0..len],
+            table,
+            &result,
+            || {
+                #[cfg(feature = "parallel")]
+                return msm_parallel::<TFr, TG1, TG1Fp, TG1Affine>(&points, &scalars, None);
+
+                #[cfg(not(feature = "parallel"))]
+                return msm_sequential::<TFr, TG1, TG1Fp, TG1Affine, TProjAddAffine>(
+                    &points, &scalars, None,
+                );
+            },
+        );
+    }
+
+    result
+}
+
+/// Scalar recoding used by `msm_with_config`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Recoding {
+    /// Booth digits, one per window, as in `msm`
+    #[default]
+    Booth,
+    /// Width-`window` non-adjacent form, see `wnaf_msm`
+    Wnaf { window: usize },
+}
+
+/// Options of `msm_with_config`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MsmConfig {
+    pub recoding: Recoding,
+}
+
+impl MsmConfig {
+    /// wNAF recoding with `WNAF_DEFAULT_WINDOW`
+    pub const fn wnaf() -> Self {
+        Self {
+            recoding: Recoding::Wnaf {
+                window: WNAF_DEFAULT_WINDOW,
+            },
+        }
+    }
+}
+
+/// Same as `msm`, with the scalar recoding chosen by `config`. The precomputation table is only
+/// used with Booth recoding, and the wNAF path runs sequentially.
+pub fn msm_with_config<
+    TG1: G1 + G1GetFp<TG1Fp> + G1Mul<TFr>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+    TProjAddAffine: G1ProjAddAffine<TG1, TG1Fp, TG1Affine>,
+    TFr: Fr,
+>(
+    points: &[TG1],
+    scalars: &[TFr],
+    len: usize,
+    precomputation: Option<&PrecomputationTable<TFr, TG1, TG1Fp, TG1Affine>>,
+    config: &MsmConfig,
+) -> TG1 {
+    match config.recoding {
+        Recoding::Booth => {
+            msm::<TG1, TG1Fp, TG1Affine, TProjAddAffine, TFr>(points, scalars, len, precomputation)
+        }
+        Recoding::Wnaf { window } => {
+            let scalars = Scalar256::from_fr_batch(&scalars[0..len]);
+            wnaf_msm(&points[0..len], &scalars, window)
+        }
+    }
+}
+
+/// Same as `msm`, with scalars pulled from an iterator in chunks of `chunk_size` instead of read
+/// from a slice, for provers whose scalars do not fit in memory at once. Runs sequentially, with
+/// the precomputation table if given and windowed Pippenger otherwise.
+pub fn msm_streaming<
+    TG1: G1 + G1GetFp<TG1Fp> + G1Mul<TFr>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+    TFr: Fr,
+>(
+    points: &[TG1],
+    scalars: impl Iterator<Item = TFr>,
+    chunk_size: usize,
+    precomputation: Option<&PrecomputationTable<TFr, TG1, TG1Fp, TG1Affine>>,
+) -> Result<TG1, String> {
+    let scalars = scalars.map(|scalar| scalar.to_scalar());
+
+    if let Some(precomputation) = precomputation {
+        precomputation.multiply_streaming(scalars, chunk_size)
+    } else {
+        tiling_pippenger_streaming::<TG1, TG1Fp, TG1Affine>(points, scalars, chunk_size)
+    }
+}
+
+/// Pippenger over the scalars below `2^nbits`, split across the thread pool with the `parallel`
+/// feature
+fn msm_bounded<TG1: G1 + G1GetFp<TG1Fp>, TG1Fp: G1Fp, TG1Affine: G1Affine<TG1, TG1Fp>>(
+    points: &[TG1],
+    scalars: &[Scalar256],
+    nbits: usize,
+) -> TG1 {
+    let len = points.len().min(scalars.len());
+    if len == 0 {
+        return TG1::identity();
+    }
+
+    let points = batch_convert::<TG1, TG1Fp, TG1Affine>(&points[0..len]);
+    let scalars = &scalars[0..len];
+    let pippenger = |points: &[TG1Affine], scalars: &[Scalar256]| {
+        if points.len() == 1 {
+            // Tiles take at least two points, pad with a zero scalar
+            let points = [points[0], points[0]];
+            let scalars = [scalars[0], Scalar256::default()];
+            return tiling_pippenger_bounded(&points, &scalars, nbits);
+        }
+        tiling_pippenger_bounded(points, scalars, nbits)
+    };
+
+    #[cfg(feature = "parallel")]
+    if crate::parallelism::is_parallel() {
+        use rayon::prelude::*;
+
+        return crate::parallelism::install(|| {
+            let chunk_size = (len / rayon::current_num_threads()).max(1 << 8);
+            points
+                .par_chunks(chunk_size)
+                .zip(scalars.par_chunks(chunk_size))
+                .map(|(points, scalars)| pippenger(points, scalars))
+                .reduce(TG1::identity, |a, b| a.add_or_dbl(&b))
+        });
+    }
+
+    pippenger(&points, scalars)
+}
+
+/// Same as `msm`, for scalars known to fit in 64 bits (counts, indices), without conversion to
+/// field elements.
+///
+/// Only the windows of the low 64 bits are processed, a quarter of the additions of an MSM over
+/// full-width scalars. `msm` over the same small values already skips the additions of the zero
+/// high digits and only pays for scanning their buckets, so the gain over it is smaller: about a
+/// quarter of the time for 64 points with blst, and nothing measurable for 4096 points.
+pub fn msm_u64<TG1: G1 + G1GetFp<TG1Fp>, TG1Fp: G1Fp, TG1Affine: G1Affine<TG1, TG1Fp>>(
+    points: &[TG1],
+    scalars: &[u64],
+) -> TG1 {
+    let scalars = scalars
+        .iter()
+        .map(|&scalar| Scalar256::from_u64_s(scalar))
+        .collect::<Vec<_>>();
+
+    msm_bounded::<TG1, TG1Fp, TG1Affine>(points, &scalars, 64)
+}
+
+/// Same as `msm_u64`, for scalars known to fit in 128 bits
+pub fn msm_u128<TG1: G1 + G1GetFp<TG1Fp>, TG1Fp: G1Fp, TG1Affine: G1Affine<TG1, TG1Fp>>(
+    points: &[TG1],
+    scalars: &[u128],
+) -> TG1 {
+    let scalars = scalars
+        .iter()
+        .map(|&scalar| Scalar256::from_u64([scalar as u64, (scalar >> 64) as u64, 0, 0]))
+        .collect::<Vec<_>>();
+
+    msm_bounded::<TG1, TG1Fp, TG1Affine>(points, &scalars, 128)
+}
+
+fn check_msm_len(npoints: usize, nscalars: usize) -> Result<(), String> {
+    if npoints != nscalars {
+        return Err(String::from("Points and scalars must have the same length"));
+    }
+
+    Ok(())
+}
+
+/// `MsmBackend` running Pippenger through `G1LinComb`, without precomputation
+#[derive(Debug, Clone)]
+pub struct PippengerMsm<TFr, TG1, TG1Fp, TG1Affine> {
+    points: Vec<TG1>,
+
+    fr_marker: PhantomData<TFr>,
+    g1_fp_marker: PhantomData<TG1Fp>,
+    g1_affine_marker: PhantomData<TG1Affine>,
+}
+
+impl<TFr, TG1, TG1Fp, TG1Affine> MsmBackend<TFr, TG1> for PippengerMsm<TFr, TG1, TG1Fp, TG1Affine>
+where
+    TFr: Fr,
+    TG1: G1LinComb<TFr, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+{
+    fn precompute(points: &[TG1]) -> Result<Self, String> {
+        Ok(Self {
+            points: points.to_vec(),
+
+            fr_marker: PhantomData,
+            g1_fp_marker: PhantomData,
+            g1_affine_marker: PhantomData,
+        })
+    }
+
+    fn msm(&self, scalars: &[TFr]) -> Result<TG1, String> {
+        Self::msm_with_points(&self.points, scalars)
+    }
+
+    fn msm_with_points(points: &[TG1], scalars: &[TFr]) -> Result<TG1, String> {
+        check_msm_len(points.len(), scalars.len())?;
+
+        Ok(TG1::g1_lincomb(points, scalars, scalars.len(), None))
+    }
+}
+
+/// `MsmBackend` running BGMW over the precomputation table of the points, or Pippenger when
+/// tables are not available in this build
+#[derive(Debug, Clone)]
+pub struct BgmwMsm<TFr, TG1, TG1Fp, TG1Affine>
+where
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+{
+    points: Vec<TG1>,
+    table: Option<PrecomputationTable<TFr, TG1, TG1Fp, TG1Affine>>,
+}
+
+impl<TFr, TG1, TG1Fp, TG1Affine> BgmwMsm<TFr, TG1, TG1Fp, TG1Affine>
+where
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+{
+    /// Whether a precomputation table was built for the points
+    pub fn has_table(&self) -> bool {
+        self.table.is_some()
+    }
+}
+
+impl<TFr, TG1, TG1Fp, TG1Affine> MsmBackend<TFr, TG1> for BgmwMsm<TFr, TG1, TG1Fp, TG1Affine>
+where
+    TFr: Fr,
+    TG1: G1LinComb<TFr, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+{
+    fn precompute(points: &[TG1]) -> Result<Self, String> {
+        Ok(Self {
+            points: points.to_vec(),
+            table: precompute(points)?,
+        })
+    }
+
+    fn msm(&self, scalars: &[TFr]) -> Result<TG1, String> {
+        check_msm_len(self.points.len(), scalars.len())?;
+
+        Ok(TG1::g1_lincomb(
+            &self.points,
+            scalars,
+            scalars.len(),
+            self.table.as_ref(),
+        ))
+    }
 }
@@ -0,0 +1,101 @@
+use core::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Barrier, OnceLock};
+
+use hwloc::{CpuSet, ObjectType, Topology, CPUBIND_THREAD};
+use threadpool::ThreadPool;
+
+////////////////////////////// NUMA-aware table multiplication //////////////////////////////
+//
+// On machines with several NUMA nodes, the workers of `da_pool` run on any core and allocate
+// their buckets wherever they happen to start, so a parallel table multiplication spends much of
+// its time moving buckets and table rows between sockets. With the `numa` feature and
+// `set_numa_msm(true)`, `BgmwTable::multiply_parallel` and `multiply_batch` split the tile grid
+// into one band of columns per node instead. Each band runs on a pool whose threads are bound to
+// the cores of its node, and each worker allocates its buckets from there, which places them in
+// the memory of that node. The partial sums of every node are added together at the end.
+//
+// Nodes are found with hwloc once, on first use. Machines with a single node, or on which hwloc
+// cannot find nodes with cores, multiply as without the option.
+
+/// Cores of a NUMA node, as found by hwloc
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumaNode {
+    /// Index of the node given by the operating system
+    pub os_index: u32,
+    /// Operating system indices of the cores of the node
+    pub cpus: Vec<u32>,
+}
+
+static NUMA_MSM: AtomicBool = AtomicBool::new(false);
+
+/// Split parallel table multiplications by NUMA node, see the module documentation
+pub fn set_numa_msm(enabled: bool) {
+    NUMA_MSM.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether parallel table multiplications are split by NUMA node
+pub fn numa_msm() -> bool {
+    NUMA_MSM.load(Ordering::Relaxed)
+}
+
+/// NUMA nodes of this machine that have cores
+pub fn numa_nodes() -> &'static [NumaNode] {
+    static NODES: OnceLock<Vec<NumaNode>> = OnceLock::new();
+
+    NODES.get_or_init(|| {
+        let topology = Topology::new();
+        let Ok(nodes) = topology.objects_with_type(&ObjectType::NUMANode) else {
+            return Vec::new();
+        };
+
+        nodes
+            .iter()
+            .filter_map(|node| {
+                let cpus = node.cpuset()?.into_iter().collect::<Vec<_>>();
+                (!cpus.is_empty()).then(|| NumaNode {
+                    os_index: node.os_index(),
+                    cpus,
+                })
+            })
+            .collect()
+    })
+}
+
+/// One pool per node of `numa_nodes`, each with a thread per core bound to the cores of its
+/// node, or `None` on machines with fewer than two such nodes
+pub(crate) fn node_pools() -> Option<&'static [ThreadPool]> {
+    static POOLS: OnceLock<Vec<ThreadPool>> = OnceLock::new();
+
+    let pools = POOLS.get_or_init(|| {
+        let nodes = numa_nodes();
+        if nodes.len() < 2 {
+            return Vec::new();
+        }
+
+        nodes.iter().map(node_pool).collect()
+    });
+
+    (!pools.is_empty()).then_some(&pools[..])
+}
+
+/// Pool with a thread per core of `node`, bound to them. The binding jobs wait for each other, so
+/// that each of them runs on a thread of its own
+fn node_pool(node: &NumaNode) -> ThreadPool {
+    let threads = node.cpus.len();
+    let pool = ThreadPool::with_name(format!("kzg-numa-{}", node.os_index), threads);
+
+    let barrier = Arc::new(Barrier::new(threads));
+    for _ in 0..threads {
+        let barrier = barrier.clone();
+        let cpus = node.cpus.clone();
+        pool.execute(move || {
+            // A thread left unbound still computes the right result, only from a farther node
+            let cpus = cpus.into_iter().collect::<CpuSet>();
+            let _ = Topology::new().set_cpubind(cpus, CPUBIND_THREAD);
+            barrier.wait();
+        });
+    }
+    pool.join();
+
+    pool
+}
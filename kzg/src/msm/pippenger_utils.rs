@@ -1,7 +1,11 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
 use core::mem::size_of;
 
 use crate::{G1Affine, G1Fp, G1GetFp, Scalar256, G1};
 
+/// Bucket of Pippenger MSMs, a point in XYZZ coordinates: `x = X / ZZ` and `y = Y / ZZZ`
 #[repr(C)]
 #[derive(Default, Clone, Copy, Debug)]
 pub struct P1XYZZ<TFp: G1Fp> {
@@ -67,20 +71,7 @@ pub fn type_is_zero<T>(a: &T) -> u64 {
     is_zero(acc)
 }
 
-#[inline(always)]
-fn vec_copy(ret: *mut u8, a: *const u8, num: usize) {
-    let rp = ret as *mut u64;
-    let ap = a as *const u64;
-
-    let num = num / size_of::<u64>();
-
-    for i in 0..num {
-        unsafe {
-            *rp.wrapping_add(i) = *ap.wrapping_add(i);
-        }
-    }
-}
-
+/// Convert `input` to the Jacobian coordinates of `out`
 pub fn p1_to_jacobian<TG1: G1 + G1GetFp<TFp>, TFp: G1Fp>(out: &mut TG1, input: &P1XYZZ<TFp>) {
     *out.x_mut() = input.x.mul_fp(&input.zz);
     *out.y_mut() = input.y.mul_fp(&input.zzz);
@@ -92,14 +83,23 @@ fn p1_dadd_affine<TG1: G1, TFp: G1Fp, TG1Affine: G1Affine<TG1, TFp>>(
     p2: &TG1Affine,
     subtract: bool, // Need to replace this somehow
 ) {
-    if type_is_zero(p2) != 0 {
+    // Not `type_is_zero`, affine points may mark the identity with a flag and have padding
+    if p2.is_zero() {
         return;
-    } else if vec_is_zero(&out.zzz as *const TFp as *const u8, 2 * size_of::<TFp>()) != 0 {
-        vec_copy(
-            &mut (out.x) as *mut TFp as *mut u8,
-            ((*p2).x()) as *const TFp as *const u8,
-            2 * size_of::<TFp>(),
-        );
+    }
+
+    p1_madd(out, p2.x(), p2.y(), subtract);
+}
+
+/// Add the affine point `(x, y)`, or subtract it with `subtract`, to `out`
+///
+/// Mixed addition: with the Z coordinates of the second point being one, it costs 8
+/// multiplications and 2 squarings instead of the 12 and 2 of [p1_dadd].
+#[inline(always)]
+fn p1_madd<TFp: G1Fp>(out: &mut P1XYZZ<TFp>, x: &TFp, y: &TFp, subtract: bool) {
+    if vec_is_zero(&out.zzz as *const TFp as *const u8, 2 * size_of::<TFp>()) != 0 {
+        out.x = *x;
+        out.y = *y;
 
         out.zzz = TFp::BLS12_381_RX_P;
         if subtract {
@@ -110,8 +110,8 @@ fn p1_dadd_affine<TG1: G1, TFp: G1Fp, TG1Affine: G1Affine<TG1, TFp>>(
         return;
     }
 
-    let mut p = p2.x().mul_fp(&out.zz);
-    let mut r = p2.y().mul_fp(&out.zzz);
+    let mut p = x.mul_fp(&out.zz);
+    let mut r = y.mul_fp(&out.zzz);
     if subtract {
         r.neg_assign();
     }
@@ -132,16 +132,16 @@ fn p1_dadd_affine<TG1: G1, TFp: G1Fp, TG1Affine: G1Affine<TG1, TFp>>(
         out.zz.mul_assign_fp(&pp);
         out.zzz.mul_assign_fp(&ppp);
     } else if type_is_zero(&r) != 0 {
-        let mut u = p2.y().add_fp(p2.y());
+        let mut u = y.add_fp(y);
         out.zz = u.square();
         out.zzz = out.zz.mul_fp(&u);
-        let mut s = p2.x().mul_fp(&out.zz);
-        let mut m = p2.x().square();
+        let mut s = x.mul_fp(&out.zz);
+        let mut m = x.square();
         m = m.add_fp(&m).add_fp(&m);
         out.x = m.square();
         u = s.add_fp(&s);
         out.x.sub_assign_fp(&u);
-        out.y = out.zzz.mul_fp(p2.y());
+        out.y = out.zzz.mul_fp(y);
         s.sub_assign_fp(&out.x);
         s.mul_assign_fp(&m);
         out.y = s.sub_fp(&out.y);
@@ -156,6 +156,21 @@ fn p1_dadd_affine<TG1: G1, TFp: G1Fp, TG1Affine: G1Affine<TG1, TFp>>(
     }
 }
 
+/// Same as [p1_dadd], with mixed addition when `p2` is affine
+///
+/// A bucket that received a single point keeps it with `zz` equal to one and `zzz` equal to one,
+/// or minus one for a subtracted point, which is the case of most buckets of small-window MSMs.
+/// Checking for it costs a comparison, and adding such a bucket with [p1_madd] saves 4
+/// multiplications.
+#[inline(always)]
+pub fn p1_dadd_mixed<TFp: G1Fp>(out: &mut P1XYZZ<TFp>, p2: &P1XYZZ<TFp>) {
+    if p2.zz == TFp::BLS12_381_RX_P {
+        p1_madd(out, &p2.x, &p2.y, p2.zzz != TFp::BLS12_381_RX_P);
+    } else {
+        p1_dadd(out, p2);
+    }
+}
+
 pub fn p1_dadd<TFp: G1Fp>(out: &mut P1XYZZ<TFp>, p2: &P1XYZZ<TFp>) {
     if vec_is_zero(&p2.zzz as *const TFp as *const u8, 2 * size_of::<TFp>()) != 0 {
         return;
@@ -209,6 +224,60 @@ pub fn p1_dadd<TFp: G1Fp>(out: &mut P1XYZZ<TFp>, p2: &P1XYZZ<TFp>) {
     }
 }
 
+/// Sum of `buckets[i] * (i + 1)`
+///
+/// Running sum from the last bucket down: `acc` adds up the buckets seen so far and `ret` the
+/// successive values of `acc`. Buckets holding a single point are added with mixed addition, see
+/// [p1_dadd_mixed].
+pub fn integrate_buckets_xyzz<TFp: G1Fp>(buckets: &[P1XYZZ<TFp>]) -> P1XYZZ<TFp> {
+    let mut n = buckets.len() - 1;
+    let mut ret = buckets[n];
+    let mut acc = buckets[n];
+
+    while n > 0 {
+        n -= 1;
+
+        if type_is_zero(&buckets[n]) == 0 {
+            p1_dadd_mixed(&mut acc, &buckets[n]);
+        }
+        p1_dadd(&mut ret, &acc);
+    }
+
+    ret
+}
+
+/// Same as [integrate_buckets_xyzz], converting every bucket to affine first
+///
+/// The conversion pays one field inversion for all the buckets with Montgomery's trick, then
+/// about 6 multiplications per bucket, to add every bucket to `acc` with mixed addition. That is
+/// more than the 4 multiplications mixed addition saves, and with blst on x86-64 this is slower
+/// than [integrate_buckets_xyzz], which MSMs use. It is kept to compare the two on other
+/// backends and machines, see `bench_bucket_integration`.
+pub fn integrate_buckets_affine<TFp: G1Fp>(buckets: &[P1XYZZ<TFp>]) -> P1XYZZ<TFp> {
+    // x = X / ZZ and y = Y / ZZZ, with 1 / ZZ = (ZZ / ZZZ)^2 since ZZ^3 = ZZZ^2
+    let mut inverses = buckets.iter().map(|bucket| bucket.zzz).collect::<Vec<_>>();
+    crate::batch_inverse::batch_inverse_sequential(&mut inverses);
+    let affines = buckets
+        .iter()
+        .zip(&inverses)
+        .map(|(bucket, zzz_inv)| {
+            let zz_inv = bucket.zz.mul_fp(zzz_inv).square();
+            (bucket.x.mul_fp(&zz_inv), bucket.y.mul_fp(zzz_inv))
+        })
+        .collect::<Vec<_>>();
+
+    let mut ret = P1XYZZ::default();
+    let mut acc = P1XYZZ::default();
+    for (bucket, (x, y)) in buckets.iter().zip(&affines).rev() {
+        if type_is_zero(bucket) == 0 {
+            p1_madd(&mut acc, x, y, false);
+        }
+        p1_dadd(&mut ret, &acc);
+    }
+
+    ret
+}
+
 /// Extract `bits` from the beginning of `d` array, with offset `off`.
 ///
 /// This function is used to extract N bits from the scalar, decomposing it into q-ary representation.
@@ -280,6 +349,45 @@ pub fn booth_decode<TG1: G1, TFp: G1Fp, TG1Affine: G1Affine<TG1, TFp>>(
     }
 }
 
+/// Prefetch the bucket that [booth_decode] will move the next point to
+///
+/// Same as `POINTonE1_prefetch` in blst: every cache line of the bucket is fetched with a
+/// non-temporal hint, so that the addition into it does not stall on memory. Prefetching is only
+/// done on x86 with the `prefetch` feature, otherwise this is a no-op.
+///
+/// ## Arguments:
+///
+/// * buckets   - pointer to the bucket array beginning
+/// * booth_idx - bucket index, encoded with [booth_encode] function
+/// * wbits     - window size, aka exponent of q (q^window)
+///
+#[inline(always)]
+#[allow(unused_variables)]
+pub fn p1_prefetch<TFp: G1Fp>(buckets: &[P1XYZZ<TFp>], booth_idx: u64, wbits: usize) {
+    #[cfg(all(
+        feature = "prefetch",
+        any(target_arch = "x86", target_arch = "x86_64"),
+        target_feature = "sse"
+    ))]
+    {
+        #[cfg(target_arch = "x86")]
+        use core::arch::x86::{_mm_prefetch, _MM_HINT_NTA};
+        #[cfg(target_arch = "x86_64")]
+        use core::arch::x86_64::{_mm_prefetch, _MM_HINT_NTA};
+
+        const CACHE_LINE: usize = 64;
+
+        let booth_idx = booth_idx & ((1 << wbits) - 1);
+        if booth_idx != 0 {
+            let bucket = &buckets[(booth_idx - 1) as usize] as *const P1XYZZ<TFp> as *const i8;
+            for offset in (0..size_of::<P1XYZZ<TFp>>()).step_by(CACHE_LINE) {
+                // SAFETY: `offset` is inside the bucket, and prefetching never faults
+                unsafe { _mm_prefetch::<_MM_HINT_NTA>(bucket.add(offset)) };
+            }
+        }
+    }
+}
+
 pub const fn num_bits(l: usize) -> usize {
     8 * core::mem::size_of::<usize>() - l.leading_zeros() as usize
 }
@@ -1,8 +1,17 @@
 extern crate alloc;
 
 use alloc::string::String;
+use alloc::sync::Arc;
 
-use crate::{Fr, G1Affine, G1Fp, G1GetFp, G1Mul, G1};
+use spin::RwLock;
+
+use super::msm_g2::BgmwTableG2;
+#[cfg(feature = "precompute")]
+use crate::diagnostics::{emit_warning, Warning};
+use crate::error::KzgError;
+#[cfg(feature = "precompute")]
+use crate::progress::{report_progress, ProgressStage};
+use crate::{Fr, G1Affine, G1Fp, G1GetFp, G1Mul, G2Mul, G1, G2};
 
 #[cfg(all(feature = "bgmw", any(not(feature = "arkmsm"), feature = "parallel")))]
 pub type PrecomputationTable<TFr, TG1, TG1Fp, TG1Affine> =
@@ -37,18 +46,125 @@ where
     TG1Fp: G1Fp,
     TG1Affine: G1Affine<TG1, TG1Fp>,
 {
-    fn new(_: &[TG1]) -> Result<Option<Self>, String> {
+    #[cfg(feature = "precompute")]
+    fn new_with_progress(
+        _: &[TG1],
+        _: &mut dyn FnMut(usize, usize),
+    ) -> Result<Option<Self>, KzgError> {
+        Ok(None)
+    }
+
+    pub fn new_with_window(_: &[TG1], _: usize) -> Result<Option<Self>, KzgError> {
         Ok(None)
     }
 
+    pub fn window_width(&self) -> usize {
+        panic!("This function must not be called")
+    }
+
+    pub fn memory_usage(&self) -> usize {
+        0
+    }
+
     pub fn multiply_sequential(&self, _: &[crate::Scalar256]) -> TG1 {
         panic!("This function must not be called")
     }
 
+    pub fn multiply_streaming(
+        &self,
+        _: impl Iterator<Item = crate::Scalar256>,
+        _: usize,
+    ) -> Result<TG1, String> {
+        panic!("This function must not be called")
+    }
+
+    pub fn multiply_batch(&self, _: &[&[crate::Scalar256]]) -> alloc::vec::Vec<TG1> {
+        panic!("This function must not be called")
+    }
+
     #[cfg(feature = "parallel")]
     pub fn multiply_parallel(&self, _: &[crate::Scalar256]) -> TG1 {
         panic!("This function must not be called")
     }
+
+    pub fn multiply_cancellable(
+        &self,
+        _: &[crate::Scalar256],
+        _: &crate::cancel::CancelToken,
+    ) -> Result<TG1, crate::cancel::Cancelled> {
+        panic!("This function must not be called")
+    }
+
+    pub fn validate(&self, _: crate::validation::ValidationLevel) -> Result<(), KzgError> {
+        panic!("This function must not be called")
+    }
+
+    #[cfg(feature = "std")]
+    pub fn write_to_writer<W: std::io::Write + ?Sized>(&self, _: &mut W) -> Result<(), KzgError> {
+        panic!("This function must not be called")
+    }
+
+    #[cfg(feature = "std")]
+    pub fn write_to_file(&self, _: &str) -> Result<(), KzgError> {
+        panic!("This function must not be called")
+    }
+
+    #[cfg(feature = "std")]
+    pub fn write_to_file_with_options(
+        &self,
+        _: &str,
+        _: &crate::file_options::FileOptions,
+    ) -> Result<(), KzgError> {
+        panic!("This function must not be called")
+    }
+
+    /// # Safety
+    ///
+    /// Always fails, precomputation tables are compiled out
+    #[cfg(feature = "std")]
+    pub unsafe fn read_from_reader<R: std::io::Read + ?Sized>(_: &mut R) -> Result<Self, KzgError> {
+        Err(tables_disabled())
+    }
+
+    /// # Safety
+    ///
+    /// Always fails, precomputation tables are compiled out
+    #[cfg(feature = "std")]
+    pub unsafe fn read_from_file(_: &str) -> Result<Self, KzgError> {
+        Err(tables_disabled())
+    }
+
+    /// # Safety
+    ///
+    /// Always fails, precomputation tables are compiled out
+    #[cfg(feature = "std")]
+    pub unsafe fn generate_to_file(_: &[TG1], _: &str) -> Result<(), KzgError> {
+        Err(tables_disabled())
+    }
+
+    #[cfg(feature = "std")]
+    pub fn supports_mmap() -> bool {
+        false
+    }
+
+    /// # Safety
+    ///
+    /// Always fails, precomputation tables are compiled out
+    #[cfg(feature = "mmap")]
+    pub unsafe fn load_mmap(_: &str) -> Result<Self, KzgError> {
+        Err(tables_disabled())
+    }
+}
+
+#[cfg(all(
+    feature = "std",
+    any(
+        not(feature = "bgmw"),
+        all(feature = "arkmsm", not(feature = "parallel"))
+    )
+))]
+fn tables_disabled() -> KzgError {
+    KzgError::Unsupported(String::from("Precomputation tables are not enabled"))
 }
 
 #[cfg(any(
@@ -57,14 +173,169 @@ where
 ))]
 pub type PrecomputationTable<TFr, TG1, TG1Fp, TG1Affine> = EmptyTable<TFr, TG1, TG1Fp, TG1Affine>;
 
+#[cfg(feature = "precompute")]
 pub fn precompute<TFr, TG1, TG1Fp, TG1Affine>(
     points: &[TG1],
-) -> Result<Option<PrecomputationTable<TFr, TG1, TG1Fp, TG1Affine>>, String>
+) -> Result<Option<PrecomputationTable<TFr, TG1, TG1Fp, TG1Affine>>, KzgError>
+where
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+{
+    precompute_with_progress(points, &mut |_, _| {})
+}
+
+/// Same as `precompute`, calling `progress(done, total)` as the table is built, in addition to the
+/// installed `ProgressSink`
+#[cfg(feature = "precompute")]
+pub fn precompute_with_progress<TFr, TG1, TG1Fp, TG1Affine>(
+    points: &[TG1],
+    progress: &mut dyn FnMut(usize, usize),
+) -> Result<Option<PrecomputationTable<TFr, TG1, TG1Fp, TG1Affine>>, KzgError>
+where
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+{
+    let npoints = points.len();
+    #[cfg(feature = "std")]
+    if crate::memory_pressure::under_memory_pressure() {
+        emit_warning(Warning::PrecomputationSkipped { npoints });
+        return Ok(None);
+    }
+
+    let table = PrecomputationTable::<TFr, TG1, TG1Fp, TG1Affine>::new_with_progress(
+        points,
+        &mut |done, total| {
+            report_progress(ProgressStage::PrecomputationTable, done, total);
+            progress(done, total);
+        },
+    );
+
+    match &table {
+        Ok(Some(_)) => {}
+        Ok(None) => emit_warning(Warning::PrecomputationUnavailable { npoints }),
+        Err(_) => emit_warning(Warning::PrecomputationFailed { npoints }),
+    }
+
+    table
+}
+
+/// Precomputation is compiled out, so MSMs over the setup always run without a table
+#[cfg(not(feature = "precompute"))]
+pub fn precompute<TFr, TG1, TG1Fp, TG1Affine>(
+    _points: &[TG1],
+) -> Result<Option<PrecomputationTable<TFr, TG1, TG1Fp, TG1Affine>>, KzgError>
+where
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+{
+    Ok(None)
+}
+
+/// Precomputation is compiled out, `progress` is never called
+#[cfg(not(feature = "precompute"))]
+pub fn precompute_with_progress<TFr, TG1, TG1Fp, TG1Affine>(
+    _points: &[TG1],
+    _progress: &mut dyn FnMut(usize, usize),
+) -> Result<Option<PrecomputationTable<TFr, TG1, TG1Fp, TG1Affine>>, KzgError>
+where
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+{
+    Ok(None)
+}
+
+/// Shared precomputation table, `None` once released
+type SharedTable<TFr, TG1, TG1Fp, TG1Affine> =
+    RwLock<Option<Arc<PrecomputationTable<TFr, TG1, TG1Fp, TG1Affine>>>>;
+
+/// Precomputation table that can be dropped while in use, e.g. by a `MemoryMonitor`, the one held
+/// by `KZGSettings` included
+#[derive(Debug)]
+pub struct ReleasableTable<TFr, TG1, TG1Fp, TG1Affine>
+where
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+{
+    table: SharedTable<TFr, TG1, TG1Fp, TG1Affine>,
+}
+
+impl<TFr, TG1, TG1Fp, TG1Affine> ReleasableTable<TFr, TG1, TG1Fp, TG1Affine>
+where
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+{
+    pub fn new(table: Option<PrecomputationTable<TFr, TG1, TG1Fp, TG1Affine>>) -> Self {
+        table.map(Arc::new).into()
+    }
+
+    /// Table to pass to the MSM, `None` once released. The table stays alive while the returned
+    /// `Arc` is held
+    pub fn get(&self) -> Option<Arc<PrecomputationTable<TFr, TG1, TG1Fp, TG1Affine>>> {
+        self.table.read().clone()
+    }
+
+    /// Put a table back, e.g. once the pressure is gone
+    pub fn set(&self, table: Option<PrecomputationTable<TFr, TG1, TG1Fp, TG1Affine>>) {
+        *self.table.write() = table.map(Arc::new);
+    }
+
+    /// Release the table, returning it
+    pub fn take(&self) -> Option<Arc<PrecomputationTable<TFr, TG1, TG1Fp, TG1Affine>>> {
+        self.table.write().take()
+    }
+}
+
+impl<TFr, TG1, TG1Fp, TG1Affine> From<Option<Arc<PrecomputationTable<TFr, TG1, TG1Fp, TG1Affine>>>>
+    for ReleasableTable<TFr, TG1, TG1Fp, TG1Affine>
 where
     TFr: Fr,
     TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
     TG1Fp: G1Fp,
     TG1Affine: G1Affine<TG1, TG1Fp>,
 {
-    PrecomputationTable::<TFr, TG1, TG1Fp, TG1Affine>::new(points)
+    fn from(table: Option<Arc<PrecomputationTable<TFr, TG1, TG1Fp, TG1Affine>>>) -> Self {
+        Self {
+            table: RwLock::new(table),
+        }
+    }
+}
+
+impl<TFr, TG1, TG1Fp, TG1Affine> Default for ReleasableTable<TFr, TG1, TG1Fp, TG1Affine>
+where
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+{
+    fn default() -> Self {
+        None.into()
+    }
+}
+
+/// G2 counterpart of `precompute`, for protocols that need MSMs over G2
+#[cfg(all(feature = "precompute", feature = "bgmw"))]
+pub fn precompute_g2<TFr: Fr, TG2: G2 + G2Mul<TFr>>(
+    points: &[TG2],
+) -> Result<Option<BgmwTableG2<TFr, TG2>>, String> {
+    BgmwTableG2::new(points)
+}
+
+/// Precomputation is compiled out, so MSMs over G2 always run without a table
+#[cfg(not(all(feature = "precompute", feature = "bgmw")))]
+pub fn precompute_g2<TFr: Fr, TG2: G2 + G2Mul<TFr>>(
+    _points: &[TG2],
+) -> Result<Option<BgmwTableG2<TFr, TG2>>, String> {
+    Ok(None)
 }
@@ -5,18 +5,24 @@ pub trait ThreadPoolExt {
 }
 
 use core::mem::transmute;
-use std::sync::{Mutex, Once};
+use std::sync::Mutex;
 use threadpool::ThreadPool;
 
+static POOL: Mutex<Option<ThreadPool>> = Mutex::new(None);
+
+/// Pool running the tiles of parallel MSMs and table multiplications, one thread per CPU unless
+/// replaced by `set_da_pool`
 pub fn da_pool() -> ThreadPool {
-    static INIT: Once = Once::new();
-    static mut POOL: *const Mutex<ThreadPool> = 0 as *const Mutex<ThreadPool>;
+    POOL.lock()
+        .unwrap()
+        .get_or_insert_with(ThreadPool::default)
+        .clone()
+}
 
-    INIT.call_once(|| {
-        let pool = Mutex::new(ThreadPool::default());
-        unsafe { POOL = transmute(Box::new(pool)) };
-    });
-    unsafe { (*POOL).lock().unwrap().clone() }
+/// Run the work of `da_pool` on `pool` from now on, e.g. a pool sized to the CPU budget of the
+/// application. Work already dispatched finishes on the previous pool
+pub fn set_da_pool(pool: ThreadPool) {
+    *POOL.lock().unwrap() = Some(pool);
 }
 
 type Thunk<'any> = Box<dyn FnOnce() + Send + 'any>;
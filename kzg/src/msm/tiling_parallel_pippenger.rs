@@ -6,6 +6,8 @@ use core::{
 use alloc::sync::Arc;
 use std::sync::{mpsc::channel, Barrier};
 
+use crate::parallelism::max_threads;
+use crate::progress::report_msm_progress;
 use crate::{G1Affine, G1Fp, G1GetFp, Scalar256, G1};
 
 use super::{
@@ -28,7 +30,7 @@ pub fn parallel_affine_conv<TG1: G1, TFp: G1Fp, TG1Affine: G1Affine<TG1, TFp> +
 ) -> Vec<TG1Affine> {
     let npoints = points.len();
     let pool = da_pool();
-    let ncpus = pool.max_count();
+    let ncpus = pool.max_count().min(max_threads());
     if ncpus < 2 || npoints < 768 {
         return TG1Affine::into_affines(points);
     }
@@ -81,7 +83,7 @@ pub fn tiling_parallel_pippenger<
     let npoints = points.len();
 
     let pool = da_pool();
-    let ncpus = pool.max_count();
+    let ncpus = pool.max_count().min(max_threads());
 
     if ncpus < 2 || npoints < 32 {
         return tiling_pippenger(points, scalars);
@@ -162,7 +164,7 @@ pub fn tiling_parallel_pippenger<
     let mut ret = <TG1>::default();
     let mut rows = vec![false; ny];
     let mut row = 0usize;
-    for _ in 0..ny {
+    for done in 1..=ny {
         let mut y = rx.recv().unwrap();
         rows[y / window] = true;
         while grid[row].0.y == y {
@@ -181,6 +183,7 @@ pub fn tiling_parallel_pippenger<
                 break;
             }
         }
+        report_msm_progress(npoints, done, ny);
     }
     ret
 }
@@ -1,10 +1,14 @@
+use crate::cooperative::maybe_yield;
+use crate::progress::report_msm_progress;
 use crate::{G1Affine, G1Fp, G1GetFp, Scalar256, G1};
 
+use alloc::string::String;
 use alloc::vec;
+use alloc::vec::Vec;
 
 use super::pippenger_utils::{
-    booth_decode, booth_encode, get_wval_limb, is_zero, p1_dadd, p1_to_jacobian,
-    pippenger_window_size, type_is_zero, type_zero, P1XYZZ,
+    booth_decode, booth_encode, get_wval_limb, is_zero, p1_dadd, p1_dadd_mixed, p1_prefetch,
+    p1_to_jacobian, pippenger_window_size, type_is_zero, type_zero, P1XYZZ,
 };
 
 fn p1_integrate_buckets<TG1: G1 + G1GetFp<TFp>, TFp: G1Fp>(
@@ -24,7 +28,7 @@ fn p1_integrate_buckets<TG1: G1 + G1GetFp<TFp>, TFp: G1Fp>(
         n -= 1;
 
         if type_is_zero(&buckets[n]) == 0 {
-            p1_dadd(&mut acc, &buckets[n]);
+            p1_dadd_mixed(&mut acc, &buckets[n]);
             type_zero(&mut buckets[n]);
         }
         p1_dadd(&mut ret, &acc);
@@ -113,9 +117,8 @@ pub fn p1s_tile_pippenger<TG1: G1 + G1GetFp<TFp>, TFp: G1Fp, TG1Affine: G1Affine
         wnxt = (get_wval_limb(scalar, bit0, wbits) << z) & wmask;
         wnxt = booth_encode(wnxt, cbits);
 
-        // TODO: add prefetching
-        // POINTonE1_prefetch(buckets, wnxt, cbits);
-        // p1_prefetch(buckets, wnxt, cbits);
+        // Fetch the next bucket while the current point is added
+        p1_prefetch(buckets, wnxt, cbits);
 
         // Get current point
         let point = &points[i];
@@ -136,16 +139,32 @@ pub fn tiling_pippenger<TG1: G1 + G1GetFp<TG1Fp>, TG1Fp: G1Fp, TG1Affine: G1Affi
     points: &[TG1Affine],
     scalars: &[Scalar256],
 ) -> TG1 {
-    let window = pippenger_window_size(points.len());
+    tiling_pippenger_bounded(points, scalars, 255)
+}
+
+/// Same as `tiling_pippenger`, for scalars below `2^nbits`. Only the windows covering the low
+/// `nbits` bits are processed. Scalars with higher bits set give a wrong result.
+pub fn tiling_pippenger_bounded<
+    TG1: G1 + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    points: &[TG1Affine],
+    scalars: &[Scalar256],
+    nbits: usize,
+) -> TG1 {
+    let window = pippenger_window_size(points.len()).min(nbits.max(1));
     let mut buckets = vec![P1XYZZ::<TG1Fp>::default(); 1 << (window - 1)];
 
-    let mut wbits: usize = 255 % window;
+    let mut wbits: usize = nbits % window;
     let mut cbits: usize = wbits + 1;
-    let mut bit0: usize = 255;
+    let mut bit0: usize = nbits;
     let mut tile = TG1::default();
 
     let mut ret = TG1::default();
 
+    let windows = nbits / window + 1;
+    let mut done = 0;
     loop {
         bit0 -= wbits;
         if bit0 == 0 {
@@ -153,6 +172,9 @@ pub fn tiling_pippenger<TG1: G1 + G1GetFp<TG1Fp>, TG1Fp: G1Fp, TG1Affine: G1Affi
         }
 
         p1s_tile_pippenger(&mut tile, points, scalars, &mut buckets, bit0, wbits, cbits);
+        maybe_yield();
+        done += 1;
+        report_msm_progress(points.len(), done, windows);
 
         ret.add_assign(&tile);
         for _ in 0..window {
@@ -162,6 +184,77 @@ pub fn tiling_pippenger<TG1: G1 + G1GetFp<TG1Fp>, TG1Fp: G1Fp, TG1Affine: G1Affi
         wbits = window;
     }
     p1s_tile_pippenger(&mut tile, points, scalars, &mut buckets, 0, wbits, cbits);
+    report_msm_progress(points.len(), windows, windows);
     ret.add_assign(&tile);
     ret
 }
+
+/// Same as `tiling_pippenger`, with scalars pulled from `scalars` in chunks of `chunk_size`
+/// instead of read from a slice, so they never need to be in memory at once. Each window sum is
+/// accumulated over the chunks and the windows are combined at the end. Points are converted to
+/// affine form one chunk at a time.
+pub fn tiling_pippenger_streaming<
+    TG1: G1 + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    points: &[TG1],
+    scalars: impl Iterator<Item = Scalar256>,
+    chunk_size: usize,
+) -> Result<TG1, String> {
+    const NBITS: usize = 255;
+
+    let chunk_size = chunk_size.max(1);
+    let window = pippenger_window_size(chunk_size.min(points.len()));
+    let mut buckets = vec![P1XYZZ::<TG1Fp>::default(); 1 << (window - 1)];
+    // One more window than the bits need, for the carry out of the top Booth digit when `window`
+    // divides 255
+    let mut window_sums = vec![TG1::identity(); NBITS / window + 1];
+    let mut tile = TG1::default();
+
+    let mut scalars = scalars;
+    let mut chunk = Vec::with_capacity(chunk_size);
+    let mut offset = 0;
+    loop {
+        chunk.clear();
+        chunk.extend(scalars.by_ref().take(chunk_size));
+        if chunk.is_empty() {
+            break;
+        }
+        let len = chunk.len();
+        if offset + len > points.len() {
+            return Err(String::from("More scalars than points"));
+        }
+
+        let mut affines = TG1Affine::into_affines(&points[offset..offset + len]);
+        if len == 1 {
+            // Tiles take at least two points, pad with a zero scalar
+            affines.push(affines[0]);
+            chunk.push(Scalar256::default());
+        }
+
+        for (i, sum) in window_sums.iter_mut().enumerate() {
+            p1s_tile_pippenger_pub(
+                &mut tile,
+                &affines,
+                &chunk,
+                &mut buckets,
+                i * window,
+                window,
+            );
+            sum.add_or_dbl_assign(&tile);
+            maybe_yield();
+        }
+        offset += len;
+    }
+
+    let mut ret = TG1::identity();
+    for sum in window_sums.iter().rev() {
+        for _ in 0..window {
+            ret.dbl_assign();
+        }
+        ret.add_or_dbl_assign(sum);
+    }
+
+    Ok(ret)
+}
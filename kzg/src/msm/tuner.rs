@@ -0,0 +1,326 @@
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use std::time::{Duration, Instant};
+
+use sha2::{Digest, Sha256};
+
+use super::precompute::PrecomputationTable;
+use crate::eip_4844::hash_to_bls_field;
+use crate::error::KzgError;
+use crate::setup_file::io_error;
+use crate::{Fr, G1Affine, G1Fp, G1GetFp, G1Mul, Scalar256, G1};
+
+////////////////////////////// MSM window tuning //////////////////////////////
+//
+// `BgmwTable::new` picks the window from the number of points only, which is not the best choice
+// on every machine. `MsmTuner` builds a table for each candidate window, times a few MSMs with it
+// and keeps the fastest. The result goes into a `TuningProfile`, which maps a number of points to
+// a window and can be saved to disk, so that nodes tune once and load the profile on startup:
+//
+//     # rust-kzg msm tuning profile
+//     4096 13
+//     65536 12
+//
+// Tables are then built with `TuningProfile::precompute`, which falls back to the default window
+// for sizes the profile does not cover. `MsmTuner::sweep` keeps every measurement in a
+// `TuningReport` instead, with the table size and build time of each window, and `kzg-cli tune`
+// writes both the report and the profile.
+
+/// First line of a saved profile
+pub const TUNING_PROFILE_HEADER: &str = "# rust-kzg msm tuning profile";
+
+/// Benchmarks candidate windows of the precomputation table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MsmTuner {
+    candidates: Vec<usize>,
+    iterations: usize,
+}
+
+impl Default for MsmTuner {
+    fn default() -> Self {
+        Self {
+            candidates: (4..=16).collect(),
+            iterations: 3,
+        }
+    }
+}
+
+impl MsmTuner {
+    /// Tuner trying every window in `candidates`, timing `iterations` MSMs for each
+    pub fn new(candidates: Vec<usize>, iterations: usize) -> Result<Self, String> {
+        if candidates.is_empty() {
+            return Err(String::from("At least one candidate window is required"));
+        }
+        if iterations == 0 {
+            return Err(String::from("Number of iterations must be non-zero"));
+        }
+
+        Ok(Self {
+            candidates,
+            iterations,
+        })
+    }
+
+    pub fn candidates(&self) -> &[usize] {
+        &self.candidates
+    }
+
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    /// Time every candidate window for an MSM over `points`
+    pub fn sweep<TFr, TG1, TG1Fp, TG1Affine>(&self, points: &[TG1]) -> Result<TuningReport, String>
+    where
+        TFr: Fr,
+        TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+        TG1Fp: G1Fp,
+        TG1Affine: G1Affine<TG1, TG1Fp>,
+    {
+        if points.len() < 2 {
+            return Err(String::from("At least two points are required"));
+        }
+        let scalars = tuning_scalars::<TFr>(points.len());
+
+        let mut timings = Vec::with_capacity(self.candidates.len());
+        for &window in &self.candidates {
+            let start = Instant::now();
+            let table =
+                PrecomputationTable::<TFr, TG1, TG1Fp, TG1Affine>::new_with_window(points, window)?
+                    .ok_or_else(|| String::from("Precomputation tables are not enabled"))?;
+            let build = start.elapsed();
+
+            let mut best = Duration::MAX;
+            let mut total = Duration::ZERO;
+            for _ in 0..self.iterations {
+                let start = Instant::now();
+                #[cfg(feature = "parallel")]
+                let result = table.multiply_parallel(&scalars);
+                #[cfg(not(feature = "parallel"))]
+                let result = table.multiply_sequential(&scalars);
+                let elapsed = start.elapsed();
+                core::hint::black_box(result);
+                best = best.min(elapsed);
+                total += elapsed;
+            }
+
+            timings.push(WindowTiming {
+                window,
+                table_bytes: table.memory_usage(),
+                build,
+                best,
+                mean: total / self.iterations as u32,
+            });
+        }
+
+        Ok(TuningReport {
+            npoints: points.len(),
+            iterations: self.iterations,
+            timings,
+        })
+    }
+
+    /// Fastest candidate window for an MSM over `points`
+    pub fn tune<TFr, TG1, TG1Fp, TG1Affine>(&self, points: &[TG1]) -> Result<usize, String>
+    where
+        TFr: Fr,
+        TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+        TG1Fp: G1Fp,
+        TG1Affine: G1Affine<TG1, TG1Fp>,
+    {
+        self.sweep::<TFr, TG1, TG1Fp, TG1Affine>(points)?
+            .recommended()
+            .ok_or_else(|| String::from("At least one candidate window is required"))
+    }
+
+    /// Tune the window for `points` and record it in `profile`
+    pub fn tune_into<TFr, TG1, TG1Fp, TG1Affine>(
+        &self,
+        points: &[TG1],
+        profile: &mut TuningProfile,
+    ) -> Result<usize, String>
+    where
+        TFr: Fr,
+        TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+        TG1Fp: G1Fp,
+        TG1Affine: G1Affine<TG1, TG1Fp>,
+    {
+        let window = self.tune::<TFr, TG1, TG1Fp, TG1Affine>(points)?;
+        profile.insert(points.len(), window);
+
+        Ok(window)
+    }
+}
+
+/// Measurements of one candidate window
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WindowTiming {
+    pub window: usize,
+    /// Bytes allocated for the table
+    pub table_bytes: usize,
+    /// Time to build the table
+    pub build: Duration,
+    /// Fastest MSM over the iterations
+    pub best: Duration,
+    /// Mean MSM time over the iterations
+    pub mean: Duration,
+}
+
+/// Result of `MsmTuner::sweep`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TuningReport {
+    pub npoints: usize,
+    pub iterations: usize,
+    pub timings: Vec<WindowTiming>,
+}
+
+impl TuningReport {
+    /// Window with the fastest MSM, `None` if no window was timed
+    pub fn recommended(&self) -> Option<usize> {
+        self.timings
+            .iter()
+            .min_by_key(|timing| timing.best)
+            .map(|timing| timing.window)
+    }
+
+    /// Record the recommended window in `profile`
+    pub fn record_into(&self, profile: &mut TuningProfile) -> Option<usize> {
+        let window = self.recommended()?;
+        profile.insert(self.npoints, window);
+
+        Some(window)
+    }
+
+    /// Table of the measurements, one line per window, for humans
+    pub fn to_text(&self) -> String {
+        let ms = |duration: Duration| duration.as_secs_f64() * 1000.0;
+        let recommended = self.recommended();
+
+        let mut text = format!(
+            "# MSM window sweep, {} points, {} iterations\n",
+            self.npoints, self.iterations
+        );
+        text.push_str("# window  q      table MiB  build ms    best ms    mean ms\n");
+        for timing in &self.timings {
+            text.push_str(&format!(
+                "{:>8}  2^{:<4} {:>9.1} {:>9.1} {:>10.3} {:>10.3}{}\n",
+                timing.window,
+                timing.window,
+                timing.table_bytes as f64 / (1024.0 * 1024.0),
+                ms(timing.build),
+                ms(timing.best),
+                ms(timing.mean),
+                if recommended == Some(timing.window) {
+                    "  <- recommended"
+                } else {
+                    ""
+                }
+            ));
+        }
+
+        text
+    }
+}
+
+/// Full-width scalars, the same on every run so that timings are comparable
+fn tuning_scalars<TFr: Fr>(npoints: usize) -> Vec<Scalar256> {
+    (0..npoints)
+        .map(|i| {
+            let hash = Sha256::digest((i as u64).to_be_bytes());
+            hash_to_bls_field::<TFr>(&hash.into()).to_scalar()
+        })
+        .collect()
+}
+
+/// Tuned window per number of points
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TuningProfile {
+    windows: BTreeMap<usize, usize>,
+}
+
+impl TuningProfile {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, npoints: usize, window: usize) {
+        self.windows.insert(npoints, window);
+    }
+
+    /// Tuned window for exactly `npoints` points
+    pub fn window_for(&self, npoints: usize) -> Option<usize> {
+        self.windows.get(&npoints).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.windows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.windows.is_empty()
+    }
+
+    /// Build the precomputation table for `points` with the tuned window, or with the default one
+    /// when the profile has none for `points.len()`
+    pub fn precompute<TFr, TG1, TG1Fp, TG1Affine>(
+        &self,
+        points: &[TG1],
+    ) -> Result<Option<PrecomputationTable<TFr, TG1, TG1Fp, TG1Affine>>, KzgError>
+    where
+        TFr: Fr,
+        TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+        TG1Fp: G1Fp,
+        TG1Affine: G1Affine<TG1, TG1Fp>,
+    {
+        match self.window_for(points.len()) {
+            Some(window) => PrecomputationTable::new_with_window(points, window),
+            None => super::precompute::precompute(points),
+        }
+    }
+
+    /// Text form of the profile, one `npoints window` line per entry after the header
+    pub fn to_text(&self) -> String {
+        let mut text = format!("{}\n", TUNING_PROFILE_HEADER);
+        for (npoints, window) in &self.windows {
+            text.push_str(&format!("{} {}\n", npoints, window));
+        }
+
+        text
+    }
+
+    /// Parse the text form of a profile, as written by `to_text`
+    pub fn from_text(text: &str) -> Result<Self, String> {
+        let mut lines = text.lines();
+        if lines.next().map(str::trim) != Some(TUNING_PROFILE_HEADER) {
+            return Err(String::from("Not an MSM tuning profile"));
+        }
+
+        let mut profile = Self::new();
+        for (i, line) in lines.enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let parse = |field: Option<&str>| field.and_then(|field| field.parse::<usize>().ok());
+            let mut fields = line.split_whitespace();
+            match (parse(fields.next()), parse(fields.next()), fields.next()) {
+                (Some(npoints), Some(window), None) => profile.insert(npoints, window),
+                _ => return Err(format!("Invalid tuning profile entry on line {}", i + 2)),
+            }
+        }
+
+        Ok(profile)
+    }
+
+    pub fn save_to_file(&self, path: &str) -> Result<(), String> {
+        std::fs::write(path, self.to_text()).map_err(io_error)
+    }
+
+    pub fn load_from_file(path: &str) -> Result<Self, String> {
+        Self::from_text(&std::fs::read_to_string(path).map_err(io_error)?)
+    }
+}
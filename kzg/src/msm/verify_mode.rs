@@ -0,0 +1,137 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::hash::Hasher;
+use core::sync::atomic::{AtomicU64, Ordering};
+
+use siphasher::sip::SipHasher13;
+use spin::RwLock;
+
+use crate::{Fr, G1Affine, G1Fp, G1GetFp, G1Mul, Scalar256, G1};
+
+use super::precompute::PrecomputationTable;
+
+////////////////////////////// MSM self-check //////////////////////////////
+//
+// A backend or table format that gets one bucket or window wrong still returns a point, and the
+// mistake only shows up later as proofs that fail to verify. With the `msm-verify` feature,
+// `set_msm_verify_mode` makes `msm` check every result it computes over a precomputation table
+// against an implementation that does not use the table, and panic when they differ.
+//
+// `Subsample` runs the table again on a few randomly chosen terms of the MSM, all other scalars
+// set to zero, and compares with double-and-add on the same terms. It costs a pass over the
+// table and `terms` scalar multiplications per MSM, and catches table and bucket errors that
+// affect most scalars. `Full` recomputes the whole MSM with Pippenger, doubling its cost, and
+// checks the exact result that is returned.
+
+/// Check run by `msm` on MSMs over a precomputation table
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MsmVerifyMode {
+    /// No check
+    #[default]
+    Off,
+    /// Compare the table with double-and-add on `terms` random terms of each MSM
+    Subsample { terms: usize },
+    /// Compare each result with Pippenger over the same points and scalars
+    Full,
+}
+
+static MODE: RwLock<MsmVerifyMode> = RwLock::new(MsmVerifyMode::Off);
+
+/// Number of subsampled checks so far, which seeds the choice of terms
+static CHECKS: AtomicU64 = AtomicU64::new(0);
+
+/// Set the check run on MSMs over a precomputation table, for all threads
+pub fn set_msm_verify_mode(mode: MsmVerifyMode) {
+    *MODE.write() = mode;
+}
+
+/// Currently selected check
+pub fn msm_verify_mode() -> MsmVerifyMode {
+    *MODE.read()
+}
+
+/// `terms` distinct indices below `len`, in increasing order
+fn sample_indices(len: usize, terms: usize) -> Vec<usize> {
+    if terms >= len {
+        return (0..len).collect();
+    }
+
+    let seed = CHECKS.fetch_add(1, Ordering::Relaxed);
+    let mut indices = Vec::with_capacity(terms);
+    let mut counter = 0u64;
+    while indices.len() < terms {
+        let mut hasher = SipHasher13::new_with_keys(seed, len as u64);
+        hasher.write_u64(counter);
+        counter += 1;
+
+        let index = (hasher.finish() % len as u64) as usize;
+        if !indices.contains(&index) {
+            indices.push(index);
+        }
+    }
+    indices.sort_unstable();
+
+    indices
+}
+
+fn table_multiply<TFr, TG1, TG1Fp, TG1Affine>(
+    table: &PrecomputationTable<TFr, TG1, TG1Fp, TG1Affine>,
+    scalars: &[Scalar256],
+) -> TG1
+where
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+{
+    #[cfg(feature = "parallel")]
+    return table.multiply_parallel(scalars);
+
+    #[cfg(not(feature = "parallel"))]
+    return table.multiply_sequential(scalars);
+}
+
+/// Run the check selected by `set_msm_verify_mode` on `result`, the MSM of `points` and
+/// `scalars` over `table`. `reference` computes the same MSM without the table
+pub(crate) fn verify_table_msm<TFr, TG1, TG1Fp, TG1Affine>(
+    points: &[TG1],
+    scalars: &[TFr],
+    table: &PrecomputationTable<TFr, TG1, TG1Fp, TG1Affine>,
+    result: &TG1,
+    reference: impl FnOnce() -> TG1,
+) where
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+{
+    match msm_verify_mode() {
+        MsmVerifyMode::Off => {}
+        MsmVerifyMode::Subsample { terms } => {
+            let indices = sample_indices(scalars.len(), terms);
+            let mut sparse = vec![Scalar256::default(); scalars.len()];
+            let mut expected = TG1::identity();
+            for &i in &indices {
+                sparse[i] = scalars[i].to_scalar();
+                expected.add_or_dbl_assign(&points[i].mul(&scalars[i]));
+            }
+
+            if !table_multiply(table, &sparse).equals(&expected) {
+                panic!(
+                    "MSM of {} points over a precomputation table diverges from double-and-add \
+                     on terms {:?}",
+                    scalars.len(),
+                    indices
+                );
+            }
+        }
+        MsmVerifyMode::Full => {
+            if !reference().equals(result) {
+                panic!(
+                    "MSM of {} points over a precomputation table diverges from Pippenger",
+                    scalars.len()
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use alloc::vec::Vec;
+
+use crate::cooperative::maybe_yield;
+use crate::{Scalar256, G1};
+
+////////////////////////////// wNAF recoding //////////////////////////////
+//
+// The Pippenger and BGMW implementations recode scalars into Booth digits, one signed digit per
+// window. The width-`w` non-adjacent form (wNAF) recodes them into odd signed digits
+// `|d| < 2^(w-1)`, at most one non-zero digit in any `w` consecutive bits, so that a scalar has
+// about `256 / (w + 1)` non-zero digits.
+//
+// `wnaf_msm` evaluates the MSM over the wNAF digits with Straus' interleaving: every point gets
+// a table of its odd multiples `P, 3P, .., (2^(w-1) - 1)P`, and a single accumulator is doubled
+// once per bit, adding the table entry of each non-zero digit at that bit. It trades the bucket
+// sums of Pippenger for `256` doublings and the tables, so it is only competitive for small MSMs
+// or scalars with few non-zero digits.
+
+/// Smallest supported wNAF width
+pub const WNAF_MIN_WINDOW: usize = 2;
+/// Largest supported wNAF width, the tables hold `2^(w-2)` points per input point
+pub const WNAF_MAX_WINDOW: usize = 12;
+
+/// Width used when none is configured, tables of 8 points
+pub const WNAF_DEFAULT_WINDOW: usize = 5;
+
+/// Recode `scalar` into its width-`window` non-adjacent form, least significant digit first
+///
+/// Every digit is zero or odd with `|d| < 2^(window - 1)`, and `scalar = Σ d_i * 2^i`.
+pub fn wnaf_digits(scalar: &Scalar256, window: usize) -> Vec<i64> {
+    debug_assert!((WNAF_MIN_WINDOW..=WNAF_MAX_WINDOW).contains(&window));
+
+    let width = 1u64 << window;
+    // One more limb than the scalar, for the carry of negative digits
+    let mut k = [0u64; 5];
+    k[..4].copy_from_slice(&scalar.data);
+
+    let mut digits = Vec::with_capacity(257);
+    while k.iter().any(|&limb| limb != 0) {
+        let mut digit = 0i64;
+        if k[0] & 1 == 1 {
+            digit = (k[0] & (width - 1)) as i64;
+            if digit >= (width >> 1) as i64 {
+                digit -= width as i64;
+            }
+            // k -= digit, leaving the low `window` bits zero
+            if digit > 0 {
+                limbs_sub(&mut k, digit as u64);
+            } else {
+                limbs_add(&mut k, digit.unsigned_abs());
+            }
+        }
+        digits.push(digit);
+
+        for i in 0..4 {
+            k[i] = (k[i] >> 1) | (k[i + 1] << 63);
+        }
+        k[4] >>= 1;
+    }
+
+    digits
+}
+
+fn limbs_add(k: &mut [u64; 5], value: u64) {
+    let mut carry = value;
+    for limb in k.iter_mut() {
+        let (sum, overflow) = limb.overflowing_add(carry);
+        *limb = sum;
+        if !overflow {
+            return;
+        }
+        carry = 1;
+    }
+}
+
+fn limbs_sub(k: &mut [u64; 5], value: u64) {
+    let mut borrow = value;
+    for limb in k.iter_mut() {
+        let (difference, underflow) = limb.overflowing_sub(borrow);
+        *limb = difference;
+        if !underflow {
+            return;
+        }
+        borrow = 1;
+    }
+}
+
+/// Odd multiples `P, 3P, .., (2^(window - 1) - 1)P`
+fn odd_multiples<TG1: G1>(point: &TG1, window: usize) -> Vec<TG1> {
+    let mut multiples = Vec::with_capacity(1 << (window - 2));
+    let double = point.dbl();
+    multiples.push(point.clone());
+    for i in 1..(1 << (window - 2)) {
+        let next = multiples[i - 1].add_or_dbl(&double);
+        multiples.push(next);
+    }
+
+    multiples
+}
+
+/// Compute `Σ scalars[i] * points[i]` over the width-`window` wNAF digits of the scalars
+///
+/// `window` is clamped to `WNAF_MIN_WINDOW..=WNAF_MAX_WINDOW`.
+pub fn wnaf_msm<TG1: G1>(points: &[TG1], scalars: &[Scalar256], window: usize) -> TG1 {
+    let window = window.clamp(WNAF_MIN_WINDOW, WNAF_MAX_WINDOW);
+
+    let mut tables = Vec::new();
+    let mut digits = Vec::new();
+    for (point, scalar) in points.iter().zip(scalars) {
+        let scalar_digits = wnaf_digits(scalar, window);
+        if scalar_digits.is_empty() || point.is_inf() {
+            continue;
+        }
+        tables.push(odd_multiples(point, window));
+        digits.push(scalar_digits);
+    }
+
+    let nbits = digits.iter().map(Vec::len).max().unwrap_or(0);
+    let mut ret = TG1::identity();
+    for bit in (0..nbits).rev() {
+        ret.dbl_assign();
+        for (table, scalar_digits) in tables.iter().zip(&digits) {
+            let digit = match scalar_digits.get(bit) {
+                Some(&digit) if digit != 0 => digit,
+                _ => continue,
+            };
+            let multiple = &table[(digit.unsigned_abs() >> 1) as usize];
+            if digit > 0 {
+                ret.add_or_dbl_assign(multiple);
+            } else {
+                ret = ret.sub(multiple);
+            }
+        }
+        maybe_yield();
+    }
+
+    ret
+}
@@ -0,0 +1,185 @@
+extern crate alloc;
+
+use alloc::collections::BTreeSet;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::dev_setup::guard_insecure_dev_setup;
+use crate::msm::msm_g2::msm_g2;
+use crate::{
+    FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1Mul, G2Mul, KZGSettings, PairingVerify, Poly, G1,
+    G2,
+};
+
+////////////////////////////// Openings at many points //////////////////////////////
+//
+// A single proof opens `p` at `k` arbitrary points `z_i` to the values `y_i = p(z_i)`. With the
+// vanishing polynomial `Z(x) = Π (x - z_i)` and the interpolant `I(x)` of degree below `k` through
+// every `(z_i, y_i)`, the prover commits to the quotient of
+//
+//     p(x) - I(x) = q(x) * Z(x)
+//
+// and the verifier checks `e(C - [I(s)]₁, [1]₂) = e([q(s)]₁, [Z(s)]₂)`. `I(x)` is the remainder of
+// the division of `p(x)` by `Z(x)`, so the prover gets it for free, while the verifier
+// interpolates it in `O(k²)`. `[Z(s)]₂` needs the first `k + 1` G2 points of the setup, which is
+// in monomial form as for `opening`. Unlike `KZGSettings::compute_proof_multi`, the points need
+// not form a coset of the roots of unity.
+
+/// Coefficients of `Π (x - z_i)`, lowest degree first, of degree `points.len()`
+pub fn vanishing_polynomial<TFr: Fr>(points: &[TFr]) -> Vec<TFr> {
+    let mut coeffs = vec![TFr::one()];
+    for z in points {
+        // Multiply by (x - z)
+        let mut next = vec![TFr::zero(); coeffs.len() + 1];
+        for (i, coeff) in coeffs.iter().enumerate() {
+            next[i + 1] = next[i + 1].add(coeff);
+            next[i] = next[i].sub(&coeff.mul(z));
+        }
+        coeffs = next;
+    }
+
+    coeffs
+}
+
+/// Coefficients of the polynomial of degree below `points.len()` through every
+/// `(points[i], values[i])`, lowest degree first
+pub fn interpolate<TFr: Fr>(points: &[TFr], values: &[TFr]) -> Result<Vec<TFr>, String> {
+    if points.len() != values.len() {
+        return Err(String::from("Number of points and values must be the same"));
+    }
+    check_distinct(points)?;
+
+    let vanishing = vanishing_polynomial(points);
+    let mut coeffs = vec![TFr::zero(); points.len()];
+    for (z, y) in points.iter().zip(values) {
+        // Z(x) / (x - z), whose value at z is Π (z - z_j) over the other points
+        let (basis, _) = divide_by_root(&vanishing, z);
+        let denominator = basis
+            .iter()
+            .rev()
+            .fold(TFr::zero(), |acc, c| acc.mul(z).add(c));
+        let scale = y.div(&denominator)?;
+        for (coeff, b) in coeffs.iter_mut().zip(&basis) {
+            *coeff = coeff.add(&b.mul(&scale));
+        }
+    }
+
+    Ok(coeffs)
+}
+
+/// Quotient and remainder of `p(x)` by `x - z`, with synthetic division as in
+/// `opening::divide_by_linear`
+fn divide_by_root<TFr: Fr>(p: &[TFr], z: &TFr) -> (Vec<TFr>, TFr) {
+    let mut quotient = Vec::with_capacity(p.len() - 1);
+    let mut acc = p[p.len() - 1].clone();
+    for coeff in p[..p.len() - 1].iter().rev() {
+        quotient.push(acc.clone());
+        acc = acc.mul(z).add(coeff);
+    }
+    quotient.reverse();
+
+    (quotient, acc)
+}
+
+fn check_distinct<TFr: Fr>(points: &[TFr]) -> Result<(), String> {
+    if points.is_empty() {
+        return Err(String::from("At least one point is required"));
+    }
+    let mut seen = BTreeSet::new();
+    if !points.iter().all(|z| seen.insert(z.to_bytes())) {
+        return Err(String::from("Points must be distinct"));
+    }
+
+    Ok(())
+}
+
+/// Open `p` at every point of `points` with a single proof. Returns the proof and the values
+/// `p(points[i])`
+pub fn compute_kzg_multiproof<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    p: &TPoly,
+    points: &[TFr],
+    s: &TKZGSettings,
+) -> Result<(TG1, Vec<TFr>), String> {
+    if p.is_empty() {
+        return Err(String::from("Polynomial must not be empty"));
+    }
+    check_distinct(points)?;
+    let vanishing = vanishing_polynomial(points);
+    let k = points.len();
+
+    // Long division by the monic Z(x), the remainder is left in the low coefficients
+    let mut remainder = p.get_coeffs().to_vec();
+    let mut quotient = vec![TFr::zero(); remainder.len().saturating_sub(k)];
+    for i in (0..quotient.len()).rev() {
+        let lead = remainder[i + k].clone();
+        for (j, coeff) in vanishing[..k].iter().enumerate() {
+            remainder[i + j] = remainder[i + j].sub(&lead.mul(coeff));
+        }
+        quotient[i] = lead;
+    }
+
+    let proof = if quotient.is_empty() {
+        TG1::identity()
+    } else {
+        s.commit_to_poly(&TPoly::from_coeffs(&quotient))?
+    };
+    let values = points.iter().map(|z| p.eval(z)).collect();
+
+    Ok((proof, values))
+}
+
+/// Check that `commitment` opens to `values[i]` at every `points[i]`, with the proof of
+/// `compute_kzg_multiproof`
+pub fn verify_kzg_multiproof<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + PairingVerify<TG1, TG2>,
+    TG2: G2 + G2Mul<TFr>,
+    TFFTSettings: FFTSettings<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    commitment: &TG1,
+    points: &[TFr],
+    values: &[TFr],
+    proof: &TG1,
+    s: &TKZGSettings,
+) -> Result<bool, String> {
+    if !commitment.is_inf() && !commitment.is_valid() {
+        return Err(String::from("Invalid commitment"));
+    }
+    if !proof.is_inf() && !proof.is_valid() {
+        return Err(String::from("Invalid proof"));
+    }
+    let secret_g2 = s.get_g2_secret();
+    if secret_g2.len() <= points.len() {
+        return Err(String::from(
+            "Trusted setup holds too few G2 points for this many openings",
+        ));
+    }
+    guard_insecure_dev_setup(secret_g2);
+
+    let interpolant = interpolate(points, values)?;
+    let vanishing = vanishing_polynomial(points);
+    let interpolant_g1 = s.commit_to_poly(&TPoly::from_coeffs(&interpolant))?;
+    let vanishing_g2 = msm_g2(secret_g2, &vanishing, vanishing.len(), None);
+
+    // e(C - [I(s)]₁, [1]₂) = e([q(s)]₁, [Z(s)]₂)
+    Ok(TG1::verify(
+        &commitment.sub(&interpolant_g1),
+        &TG2::generator(),
+        proof,
+        &vanishing_g2,
+    ))
+}
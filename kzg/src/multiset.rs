@@ -0,0 +1,154 @@
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use sha2::{Digest, Sha256};
+
+use crate::eip_4844::hash_to_bls_field;
+use crate::{FFTFr, FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1Mul, KZGSettings, Poly, G1, G2};
+
+////////////////////////////// Multiset equality via a grand product //////////////////////////////
+//
+// Plumbing for permutation and lookup arguments. Two columns `f` and `g` of length `n` are equal
+// as multisets iff, for a random `γ`,
+//
+//     prod_i (f_i + γ) = prod_i (g_i + γ)
+//
+// The running product `Z(ω^0) = 1`, `Z(ω^(i+1)) = Z(ω^i) * (f_i + γ) / (g_i + γ)` wraps around to
+// `1` exactly then, so on every point `x` of the domain of size `n`
+//
+//     Z(ω * x) * (g(x) + γ) - Z(x) * (f(x) + γ) = 0
+//     L_0(x) * (Z(x) - 1) = 0
+//
+// Columns are interpolated over the domain in natural order, `f(ω^i) = f_i`. `γ` is derived from
+// the commitments to `f` and `g`. Proving that the constraints vanish on the domain is left to the
+// argument built on top.
+
+/// Domain separator of the multiset equality challenge
+pub const MULTISET_CHALLENGE_DOMAIN: [u8; 16] = *b"MULTISETEQUALITY";
+
+/// Columns, grand product and their commitments
+#[derive(Debug, Clone)]
+pub struct MultisetEquality<TFr, TG1, TPoly> {
+    pub gamma: TFr,
+    pub f_poly: TPoly,
+    pub g_poly: TPoly,
+    pub z_poly: TPoly,
+    pub f_commitment: TG1,
+    pub g_commitment: TG1,
+    pub z_commitment: TG1,
+}
+
+/// Challenge `γ` binding the commitments to both columns
+pub fn multiset_challenge<TFr: Fr, TG1: G1>(f_commitment: &TG1, g_commitment: &TG1) -> TFr {
+    let mut hasher = Sha256::new();
+    hasher.update(MULTISET_CHALLENGE_DOMAIN);
+    hasher.update(f_commitment.to_bytes());
+    hasher.update(g_commitment.to_bytes());
+
+    hash_to_bls_field(&hasher.finalize().into())
+}
+
+/// Evaluations `Z(ω^i)` of the grand product. Fails if `f` and `g` are not equal as multisets.
+pub fn grand_product_evaluations<TFr: Fr>(
+    f: &[TFr],
+    g: &[TFr],
+    gamma: &TFr,
+) -> Result<Vec<TFr>, String> {
+    if f.len() != g.len() {
+        return Err(String::from("Columns must have the same length"));
+    }
+
+    let mut z = Vec::with_capacity(f.len());
+    let mut acc = TFr::one();
+    for (f_i, g_i) in f.iter().zip(g) {
+        let denominator = g_i.add(gamma);
+        if denominator.is_zero() {
+            return Err(String::from("Challenge collides with a column value"));
+        }
+        z.push(acc.clone());
+        acc = acc.mul(&f_i.add(gamma)).div(&denominator)?;
+    }
+    if !acc.is_one() {
+        return Err(String::from("Columns are not equal as multisets"));
+    }
+
+    Ok(z)
+}
+
+/// Values of the transition and boundary constraints at `x`, both zero on the domain of size
+/// `n` for a valid grand product
+pub fn grand_product_constraints<TFr: Fr>(
+    x: &TFr,
+    n: usize,
+    f_x: &TFr,
+    g_x: &TFr,
+    z_x: &TFr,
+    z_shifted_x: &TFr,
+    gamma: &TFr,
+) -> Result<(TFr, TFr), String> {
+    let transition = z_shifted_x
+        .mul(&g_x.add(gamma))
+        .sub(&z_x.mul(&f_x.add(gamma)));
+
+    // L_0(x) = (x^n - 1) / (n * (x - 1)), which is 1 at x = 1
+    let l0 = if x.is_one() {
+        TFr::one()
+    } else {
+        x.pow(n)
+            .sub(&TFr::one())
+            .div(&TFr::from_u64(n as u64).mul(&x.sub(&TFr::one())))?
+    };
+    let boundary = l0.mul(&z_x.sub(&TFr::one()));
+
+    Ok((transition, boundary))
+}
+
+impl<TFr: Fr, TG1: G1, TPoly: Poly<TFr>> MultisetEquality<TFr, TG1, TPoly> {
+    /// Interpolate and commit to the columns `f` and `g` and to their grand product
+    pub fn new<
+        TG2: G2,
+        TFFTSettings: FFTSettings<TFr> + FFTFr<TFr>,
+        TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+        TG1Fp: G1Fp,
+        TG1Affine: G1Affine<TG1, TG1Fp>,
+    >(
+        f: &[TFr],
+        g: &[TFr],
+        ks: &TKZGSettings,
+    ) -> Result<Self, String>
+    where
+        TG1: G1Mul<TFr> + G1GetFp<TG1Fp>,
+    {
+        if !f.len().is_power_of_two() || f.len() > ks.get_fft_settings().get_max_width() {
+            return Err(String::from(
+                "Column length must be a power of two no larger than the FFT settings",
+            ));
+        }
+        if f.len() != g.len() {
+            return Err(String::from("Columns must have the same length"));
+        }
+
+        let fs = ks.get_fft_settings();
+        let f_poly = TPoly::from_coeffs(&fs.fft_fr(f, true)?);
+        let g_poly = TPoly::from_coeffs(&fs.fft_fr(g, true)?);
+        let f_commitment = ks.commit_to_poly(&f_poly)?;
+        let g_commitment = ks.commit_to_poly(&g_poly)?;
+
+        let gamma = multiset_challenge(&f_commitment, &g_commitment);
+        let z = grand_product_evaluations(f, g, &gamma)?;
+        let z_poly = TPoly::from_coeffs(&fs.fft_fr(&z, true)?);
+        let z_commitment = ks.commit_to_poly(&z_poly)?;
+
+        Ok(Self {
+            gamma,
+            f_poly,
+            g_poly,
+            z_poly,
+            f_commitment,
+            g_commitment,
+            z_commitment,
+        })
+    }
+}
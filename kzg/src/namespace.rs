@@ -0,0 +1,334 @@
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::common_utils::reverse_bit_order;
+use crate::{FFTFr, FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1Mul, KZGSettings, Poly, G1, G2};
+
+////////////////////////////// Namespaced row commitments //////////////////////////////
+//
+// KZG alternative to namespaced Merkle trees. Namespaced items, sorted by namespace, are split into
+// rows of `row_width` elements. Every row is committed to twice: once for the data and once for
+// the namespaces, both as polynomials in evaluation form.
+//
+// Item `p` of a row is stored at `ω^bitrev(p)`, so that any aligned block of `n` consecutive items
+// is a coset of the size-`n` subgroup and can be opened with a single multiproof. A namespace
+// range proof opens the smallest aligned block holding the range and its neighbours: a neighbour
+// with a different namespace (or the row boundary) on both sides shows that the range is complete
+// within the row.
+
+pub const NAMESPACE_SIZE: usize = 29;
+
+pub type Namespace = [u8; NAMESPACE_SIZE];
+
+/// Namespace of the padding items that fill the last row
+pub const PADDING_NAMESPACE: Namespace = [0xff; NAMESPACE_SIZE];
+
+/// Published commitments of a single row
+#[derive(Debug, Clone, PartialEq)]
+pub struct NamespacedRowCommitment<TG1> {
+    pub data_commitment: TG1,
+    pub namespace_commitment: TG1,
+}
+
+/// Opening of all items of a namespace in one row
+#[derive(Debug, Clone)]
+pub struct NamespaceRangeProof<TFr, TG1> {
+    pub row: usize,
+    /// Items of the namespace are at positions `start..end` of the row
+    pub start: usize,
+    pub end: usize,
+    /// Position of the first item of the opened block
+    pub block_offset: usize,
+    /// Namespaces and values of every item in the opened block
+    pub namespaces: Vec<Namespace>,
+    pub values: Vec<TFr>,
+    pub data_proof: TG1,
+    pub namespace_proof: TG1,
+}
+
+impl<TFr, TG1> NamespaceRangeProof<TFr, TG1> {
+    /// Values of the proven namespace
+    pub fn namespace_values(&self) -> &[TFr] {
+        &self.values[self.start - self.block_offset..self.end - self.block_offset]
+    }
+}
+
+struct NamespacedRow<TFr, TG1, TPoly> {
+    namespaces: Vec<Namespace>,
+    values: Vec<TFr>,
+    data_poly: TPoly,
+    namespace_poly: TPoly,
+    commitment: NamespacedRowCommitment<TG1>,
+}
+
+/// Prover-side state: the rows with their polynomials and commitments
+pub struct NamespacedRows<TFr, TG1, TPoly> {
+    row_width: usize,
+    rows: Vec<NamespacedRow<TFr, TG1, TPoly>>,
+}
+
+/// Map a namespace to a field element, as a big-endian integer
+pub fn namespace_to_fr<TFr: Fr>(namespace: &Namespace) -> TFr {
+    let mut bytes = [0u8; 32];
+    bytes[32 - NAMESPACE_SIZE..].copy_from_slice(namespace);
+    // 29 bytes are always below the modulus
+    TFr::from_bytes(&bytes).unwrap()
+}
+
+/// `ω^bitrev(position)` in the domain of size `row_width`
+fn position_to_point<TFr: Fr, TFFTSettings: FFTSettings<TFr>>(
+    fs: &TFFTSettings,
+    row_width: usize,
+    position: usize,
+) -> TFr {
+    // `reverse_bits() >> BITS` would overflow for a single-item row, where the only point is 1
+    if row_width == 1 {
+        return TFr::one();
+    }
+    let stride = fs.get_max_width() / row_width;
+    let reversed = position.reverse_bits() >> (usize::BITS - row_width.trailing_zeros());
+    fs.get_expanded_roots_of_unity_at(reversed * stride)
+}
+
+/// Polynomial whose evaluation at `ω^bitrev(p)` is `values[p]`
+fn interpolate_row<TFr: Fr, TPoly: Poly<TFr>, TFFTSettings: FFTSettings<TFr> + FFTFr<TFr>>(
+    fs: &TFFTSettings,
+    values: &[TFr],
+) -> Result<TPoly, String> {
+    let mut evaluations = values.to_vec();
+    reverse_bit_order(&mut evaluations)?;
+    Ok(TPoly::from_coeffs(&fs.fft_fr(&evaluations, true)?))
+}
+
+/// Smallest aligned power-of-two block containing positions `lo..=hi`
+fn covering_block(lo: usize, hi: usize) -> (usize, usize) {
+    let mut size = 1;
+    while lo / size != hi / size {
+        size *= 2;
+    }
+    (lo / size * size, size)
+}
+
+impl<TFr: Fr, TG1: G1, TPoly: Poly<TFr>> NamespacedRows<TFr, TG1, TPoly> {
+    /// Split items sorted by namespace into rows of `row_width` and commit to every row
+    pub fn new<
+        TG2: G2,
+        TFFTSettings: FFTSettings<TFr> + FFTFr<TFr>,
+        TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+        TG1Fp: G1Fp,
+        TG1Affine: G1Affine<TG1, TG1Fp>,
+    >(
+        items: &[(Namespace, TFr)],
+        row_width: usize,
+        ks: &TKZGSettings,
+    ) -> Result<Self, String>
+    where
+        TG1: G1Mul<TFr> + G1GetFp<TG1Fp>,
+    {
+        if !row_width.is_power_of_two() {
+            return Err(String::from("Row width must be a power of two"));
+        }
+        let fs = ks.get_fft_settings();
+        if row_width > fs.get_max_width() {
+            return Err(String::from("Row width is larger than the FFT settings"));
+        }
+        if items.windows(2).any(|pair| pair[0].0 > pair[1].0) {
+            return Err(String::from("Items must be sorted by namespace"));
+        }
+        if items
+            .iter()
+            .any(|(namespace, _)| namespace == &PADDING_NAMESPACE)
+        {
+            return Err(String::from("Padding namespace is reserved"));
+        }
+
+        let mut rows = Vec::new();
+        for chunk in items.chunks(row_width) {
+            let mut namespaces = chunk.iter().map(|(ns, _)| *ns).collect::<Vec<_>>();
+            let mut values = chunk.iter().map(|(_, v)| v.clone()).collect::<Vec<_>>();
+            namespaces.resize(row_width, PADDING_NAMESPACE);
+            values.resize(row_width, TFr::zero());
+
+            let namespace_values = namespaces.iter().map(namespace_to_fr).collect::<Vec<TFr>>();
+            let data_poly: TPoly = interpolate_row(fs, &values)?;
+            let namespace_poly: TPoly = interpolate_row(fs, &namespace_values)?;
+
+            let commitment = NamespacedRowCommitment {
+                data_commitment: ks.commit_to_poly(&data_poly)?,
+                namespace_commitment: ks.commit_to_poly(&namespace_poly)?,
+            };
+
+            rows.push(NamespacedRow {
+                namespaces,
+                values,
+                data_poly,
+                namespace_poly,
+                commitment,
+            });
+        }
+
+        Ok(Self { row_width, rows })
+    }
+
+    pub fn row_width(&self) -> usize {
+        self.row_width
+    }
+
+    pub fn commitments(&self) -> Vec<NamespacedRowCommitment<TG1>> {
+        self.rows.iter().map(|row| row.commitment.clone()).collect()
+    }
+
+    /// Prove which items of `row` belong to `namespace`. If there are none, the proof shows that
+    /// the namespace is absent from the row.
+    pub fn prove_namespace_in_row<
+        TG2: G2,
+        TFFTSettings: FFTSettings<TFr> + FFTFr<TFr>,
+        TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+        TG1Fp: G1Fp,
+        TG1Affine: G1Affine<TG1, TG1Fp>,
+    >(
+        &self,
+        row: usize,
+        namespace: &Namespace,
+        ks: &TKZGSettings,
+    ) -> Result<NamespaceRangeProof<TFr, TG1>, String>
+    where
+        TG1: G1Mul<TFr> + G1GetFp<TG1Fp>,
+    {
+        let data = self
+            .rows
+            .get(row)
+            .ok_or_else(|| String::from("Row index is out of bounds"))?;
+
+        let start = data.namespaces.partition_point(|ns| ns < namespace);
+        let end = data.namespaces.partition_point(|ns| ns <= namespace);
+
+        // The block must include the neighbours on both sides of the range, if there are any
+        let lo = start.saturating_sub(1);
+        let hi = end.min(self.row_width - 1);
+        let (block_offset, block_size) = covering_block(lo, hi);
+        if block_size >= ks.get_g2_secret().len() {
+            return Err(String::from(
+                "Namespace range is too large for the trusted setup",
+            ));
+        }
+
+        let x0 = position_to_point(ks.get_fft_settings(), self.row_width, block_offset);
+        let block = block_offset..block_offset + block_size;
+
+        Ok(NamespaceRangeProof {
+            row,
+            start,
+            end,
+            block_offset,
+            namespaces: data.namespaces[block.clone()].to_vec(),
+            values: data.values[block].to_vec(),
+            data_proof: ks.compute_proof_multi(&data.data_poly, &x0, block_size)?,
+            namespace_proof: ks.compute_proof_multi(&data.namespace_poly, &x0, block_size)?,
+        })
+    }
+
+    /// Prove `namespace` in every row whose namespace range includes it
+    pub fn prove_namespace<
+        TG2: G2,
+        TFFTSettings: FFTSettings<TFr> + FFTFr<TFr>,
+        TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+        TG1Fp: G1Fp,
+        TG1Affine: G1Affine<TG1, TG1Fp>,
+    >(
+        &self,
+        namespace: &Namespace,
+        ks: &TKZGSettings,
+    ) -> Result<Vec<NamespaceRangeProof<TFr, TG1>>, String>
+    where
+        TG1: G1Mul<TFr> + G1GetFp<TG1Fp>,
+    {
+        (0..self.rows.len())
+            .filter(|&row| {
+                let namespaces = &self.rows[row].namespaces;
+                &namespaces[0] <= namespace && namespace <= &namespaces[namespaces.len() - 1]
+            })
+            .map(|row| self.prove_namespace_in_row(row, namespace, ks))
+            .collect()
+    }
+}
+
+/// Check that `proof.namespace_values()` are exactly the items of `namespace` in the committed
+/// row, assuming the row was built sorted by namespace.
+pub fn verify_namespace_proof<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr> + FFTFr<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    commitment: &NamespacedRowCommitment<TG1>,
+    row_width: usize,
+    namespace: &Namespace,
+    proof: &NamespaceRangeProof<TFr, TG1>,
+    ks: &TKZGSettings,
+) -> Result<bool, String> {
+    let block_size = proof.values.len();
+    if !row_width.is_power_of_two()
+        || !block_size.is_power_of_two()
+        || proof.namespaces.len() != block_size
+        || proof.block_offset & (block_size - 1) != 0
+        || proof.block_offset + block_size > row_width
+        || block_size >= ks.get_g2_secret().len()
+    {
+        return Err(String::from("Malformed namespace range proof"));
+    }
+
+    let block_end = proof.block_offset + block_size;
+    if proof.start > proof.end || proof.start < proof.block_offset || proof.end > block_end {
+        return Ok(false);
+    }
+
+    // The range holds exactly the namespace, and is delimited by other namespaces or the row ends
+    let in_range = |position: usize| proof.namespaces[position - proof.block_offset] == *namespace;
+    if !(proof.start..proof.end).all(in_range) {
+        return Ok(false);
+    }
+    if proof.start > 0
+        && (proof.start == proof.block_offset
+            || proof.namespaces[proof.start - 1 - proof.block_offset] >= *namespace)
+    {
+        return Ok(false);
+    }
+    if proof.end < row_width
+        && (proof.end == block_end
+            || proof.namespaces[proof.end - proof.block_offset] <= *namespace)
+    {
+        return Ok(false);
+    }
+
+    // Block items are at x0 * ω_n^bitrev(j), while multiproofs expect them at x0 * ω_n^j
+    let x0 = position_to_point(ks.get_fft_settings(), row_width, proof.block_offset);
+    let mut values = proof.values.clone();
+    reverse_bit_order(&mut values)?;
+    let mut namespace_values = proof
+        .namespaces
+        .iter()
+        .map(namespace_to_fr)
+        .collect::<Vec<TFr>>();
+    reverse_bit_order(&mut namespace_values)?;
+
+    Ok(ks.check_proof_multi(
+        &commitment.data_commitment,
+        &proof.data_proof,
+        &x0,
+        &values,
+        block_size,
+    )? && ks.check_proof_multi(
+        &commitment.namespace_commitment,
+        &proof.namespace_proof,
+        &x0,
+        &namespace_values,
+        block_size,
+    )?)
+}
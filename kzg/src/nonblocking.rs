@@ -0,0 +1,296 @@
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::sync::Arc;
+#[cfg(any(feature = "commit", feature = "prove", feature = "verify"))]
+use alloc::vec::Vec;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+use std::sync::Mutex;
+
+use threadpool::ThreadPool;
+
+#[cfg(feature = "commit")]
+use crate::eip_4844::blob_to_kzg_commitment_rust;
+#[cfg(feature = "prove")]
+use crate::eip_4844::{compute_blob_kzg_proof_rust, compute_kzg_proof_rust};
+#[cfg(feature = "verify")]
+use crate::eip_4844::{
+    verify_blob_kzg_proof_batch_rust, verify_blob_kzg_proof_rust, verify_kzg_proof_rust,
+};
+#[cfg(feature = "verify")]
+use crate::PairingVerify;
+#[cfg(any(feature = "commit", feature = "prove", feature = "verify"))]
+use crate::{
+    FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1LinComb, G1Mul, KZGSettings, Poly, G1, G2,
+};
+
+////////////////////////////// Non-blocking wrappers //////////////////////////////
+//
+// Committing, proving and verifying take milliseconds of CPU time, which must not run on an async
+// executor thread. The wrappers below move each call onto a `BlockingPool` and return a future
+// resolving to its result. The pool has a fixed number of worker threads, which bounds how many
+// heavy operations run at once; further jobs wait in the pool's queue.
+//
+// The futures do not depend on a particular runtime, so they can be awaited from tokio, async-std
+// or a plain `block_on`.
+
+/// State shared between a job and the future awaiting it
+struct JobState<T> {
+    result: Option<Result<T, String>>,
+    waker: Option<Waker>,
+}
+
+/// Future resolving to the result of a job spawned on a `BlockingPool`
+pub struct JobHandle<T> {
+    state: Arc<Mutex<JobState<T>>>,
+}
+
+impl<T> Future for JobHandle<T> {
+    type Output = Result<T, String>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.lock().unwrap();
+        match state.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Sending half of a job, completes the `JobHandle` when dropped
+pub(crate) struct JobCompleter<T> {
+    state: Arc<Mutex<JobState<T>>>,
+    result: Option<Result<T, String>>,
+}
+
+impl<T> JobCompleter<T> {
+    pub(crate) fn complete(mut self, result: Result<T, String>) {
+        self.result = Some(result);
+    }
+}
+
+/// Create a pending job, returning the future awaiting it and the half completing it
+pub(crate) fn job_channel<T>() -> (JobHandle<T>, JobCompleter<T>) {
+    let state = Arc::new(Mutex::new(JobState {
+        result: None,
+        waker: None,
+    }));
+    let completer = JobCompleter {
+        state: state.clone(),
+        result: None,
+    };
+
+    (JobHandle { state }, completer)
+}
+
+impl<T> Drop for JobCompleter<T> {
+    fn drop(&mut self) {
+        // A missing result means the job panicked
+        let result = self
+            .result
+            .take()
+            .unwrap_or_else(|| Err(String::from("Job panicked")));
+
+        let waker = {
+            let mut state = match self.state.lock() {
+                Ok(state) => state,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            state.result = Some(result);
+            state.waker.take()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+/// Fixed-size pool of worker threads running blocking KZG operations
+#[derive(Clone)]
+pub struct BlockingPool {
+    pool: ThreadPool,
+}
+
+impl BlockingPool {
+    /// Create a pool running at most `max_concurrency` jobs at once
+    pub fn new(max_concurrency: usize) -> Result<Self, String> {
+        if max_concurrency == 0 {
+            return Err(String::from("Concurrency limit must be at least 1"));
+        }
+
+        Ok(Self {
+            pool: ThreadPool::new(max_concurrency),
+        })
+    }
+
+    pub fn max_concurrency(&self) -> usize {
+        self.pool.max_count()
+    }
+
+    /// Number of jobs waiting for a free worker
+    pub fn queued_count(&self) -> usize {
+        self.pool.queued_count()
+    }
+
+    /// Run `job` on the pool, returning a future resolving to its result
+    pub fn spawn<T, F>(&self, job: F) -> JobHandle<T>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> Result<T, String> + Send + 'static,
+    {
+        let (handle, completer) = job_channel();
+
+        self.pool.execute(move || {
+            // The completer is dropped by the job even on panic
+            completer.complete(job());
+        });
+
+        handle
+    }
+}
+
+impl Default for BlockingPool {
+    /// One worker per CPU
+    fn default() -> Self {
+        Self {
+            pool: ThreadPool::new(num_cpus::get()),
+        }
+    }
+}
+
+/// Load a trusted setup without blocking the caller
+pub fn load_trusted_setup_async<TKZGSettings: Send + 'static>(
+    pool: &BlockingPool,
+    load_trusted_setup: fn(&str) -> Result<TKZGSettings, String>,
+    path: String,
+) -> JobHandle<TKZGSettings> {
+    pool.spawn(move || load_trusted_setup(&path))
+}
+
+#[cfg(feature = "commit")]
+pub fn blob_to_kzg_commitment_async<
+    TFr: Fr + Send + 'static,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + G1LinComb<TFr, TG1Fp, TG1Affine> + Send + 'static,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine> + Send + Sync + 'static,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    pool: &BlockingPool,
+    blob: Vec<TFr>,
+    ts: Arc<TKZGSettings>,
+) -> JobHandle<TG1> {
+    pool.spawn(move || blob_to_kzg_commitment_rust(&blob, ts.as_ref()))
+}
+
+#[cfg(feature = "prove")]
+pub fn compute_kzg_proof_async<
+    TFr: Fr + Copy + Send + 'static,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + G1LinComb<TFr, TG1Fp, TG1Affine> + Send + 'static,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine> + Send + Sync + 'static,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    pool: &BlockingPool,
+    blob: Vec<TFr>,
+    z: TFr,
+    ts: Arc<TKZGSettings>,
+) -> JobHandle<(TG1, TFr)> {
+    pool.spawn(move || compute_kzg_proof_rust(&blob, &z, ts.as_ref()))
+}
+
+#[cfg(feature = "prove")]
+pub fn compute_blob_kzg_proof_async<
+    TFr: Fr + Copy + Send + 'static,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + G1LinComb<TFr, TG1Fp, TG1Affine> + Send + 'static,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine> + Send + Sync + 'static,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    pool: &BlockingPool,
+    blob: Vec<TFr>,
+    commitment: TG1,
+    ts: Arc<TKZGSettings>,
+) -> JobHandle<TG1> {
+    pool.spawn(move || compute_blob_kzg_proof_rust(&blob, &commitment, ts.as_ref()))
+}
+
+#[cfg(feature = "verify")]
+pub fn verify_kzg_proof_async<
+    TFr: Fr + Send + 'static,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + Send + 'static,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine> + Send + Sync + 'static,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    pool: &BlockingPool,
+    commitment: TG1,
+    z: TFr,
+    y: TFr,
+    proof: TG1,
+    ts: Arc<TKZGSettings>,
+) -> JobHandle<bool> {
+    pool.spawn(move || verify_kzg_proof_rust(&commitment, &z, &y, &proof, ts.as_ref()))
+}
+
+#[cfg(feature = "verify")]
+pub fn verify_blob_kzg_proof_async<
+    TFr: Fr + Copy + Send + 'static,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + Send + 'static,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine> + Send + Sync + 'static,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    pool: &BlockingPool,
+    blob: Vec<TFr>,
+    commitment: TG1,
+    proof: TG1,
+    ts: Arc<TKZGSettings>,
+) -> JobHandle<bool> {
+    pool.spawn(move || verify_blob_kzg_proof_rust(&blob, &commitment, &proof, ts.as_ref()))
+}
+
+#[cfg(feature = "verify")]
+pub fn verify_blob_kzg_proof_batch_async<
+    TFr: Fr + Copy + Send + 'static,
+    TG1: G1
+        + G1Mul<TFr>
+        + PairingVerify<TG1, TG2>
+        + G1GetFp<TG1Fp>
+        + G1LinComb<TFr, TG1Fp, TG1Affine>
+        + Send
+        + 'static,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine> + Send + Sync + 'static,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    pool: &BlockingPool,
+    blobs: Vec<Vec<TFr>>,
+    commitments: Vec<TG1>,
+    proofs: Vec<TG1>,
+    ts: Arc<TKZGSettings>,
+) -> JobHandle<bool> {
+    pool.spawn(move || verify_blob_kzg_proof_batch_rust(&blobs, &commitments, &proofs, ts.as_ref()))
+}
@@ -0,0 +1,138 @@
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::dev_setup::guard_insecure_dev_setup;
+use crate::{
+    FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1LinComb, G1Mul, G2Mul, KZGSettings, PairingVerify,
+    Poly, G1, G2,
+};
+
+////////////////////////////// Quotient building blocks for KZG openings //////////////////////////////
+//
+// These helpers expose the pieces of the single-point opening argument
+//
+//     p(x) - y = q(x) * (x - z),   e([p(s)]₁ - [y]₁, [1]₂) = e([q(s)]₁, [s]₂ - [z]₂)
+//
+// so that custom opening arguments can be assembled without re-implementing `KZGSettings`.
+// All of them assume that the settings hold the setup in monomial form, as `commit_to_poly` does.
+
+/// Divide `p(x)` by `(x - z)` using synthetic division.
+///
+/// Returns the quotient `q(x)` and the remainder, which is equal to `p(z)`.
+pub fn divide_by_linear<TFr: Fr, TPoly: Poly<TFr>>(
+    p: &TPoly,
+    z: &TFr,
+) -> Result<(TPoly, TFr), String> {
+    if p.is_empty() {
+        return Err(String::from("Polynomial must not be empty"));
+    }
+
+    let coeffs = p.get_coeffs();
+    let mut quotient = Vec::with_capacity(coeffs.len() - 1);
+
+    // Horner's scheme from the leading coefficient: every intermediate value is the next
+    // quotient coefficient, the last one is the remainder.
+    let mut acc = coeffs[coeffs.len() - 1].clone();
+    for coeff in coeffs[..coeffs.len() - 1].iter().rev() {
+        quotient.push(acc.clone());
+        acc = acc.mul(z).add(coeff);
+    }
+    quotient.reverse();
+
+    Ok((TPoly::from_coeffs(&quotient), acc))
+}
+
+/// Compute the quotient commitment `[q(s)]₁`, where `q(x) = (p(x) - p(z)) / (x - z)`, together with
+/// the evaluation `p(z)`.
+pub fn commit_to_quotient<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    p: &TPoly,
+    z: &TFr,
+    s: &TKZGSettings,
+) -> Result<(TG1, TFr), String> {
+    let (quotient, y) = divide_by_linear(p, z)?;
+
+    Ok((s.commit_to_poly(&quotient)?, y))
+}
+
+/// Compute `[p(s) - y]₁ = C - [y]₁` from a commitment `C` to `p(x)` and a claimed value `y`.
+pub fn commitment_minus_value<TFr: Fr, TG1: G1 + G1Mul<TFr>>(commitment: &TG1, y: &TFr) -> TG1 {
+    commitment.sub(&TG1::generator().mul(y))
+}
+
+/// Compute `[s - z]₂` from the `[s]₂` element of the trusted setup.
+pub fn g2_s_minus_z<TFr: Fr, TG2: G2 + G2Mul<TFr>>(s_g2: &TG2, z: &TFr) -> TG2 {
+    s_g2.sub(&TG2::generator().mul(z))
+}
+
+/// Check the quotient relation `C - [y]₁ = [q(s)]₁ * (s - z)` with a single pairing equation.
+///
+/// This is the same check as `KZGSettings::check_proof_single`, with every operand exposed.
+pub fn verify_quotient_commitment<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + PairingVerify<TG1, TG2>,
+    TG2: G2 + G2Mul<TFr>,
+    TFFTSettings: FFTSettings<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    commitment: &TG1,
+    quotient: &TG1,
+    z: &TFr,
+    y: &TFr,
+    s: &TKZGSettings,
+) -> Result<bool, String> {
+    let s_g2 = s
+        .get_g2_secret()
+        .get(1)
+        .ok_or_else(|| String::from("Trusted setup must contain at least two G2 points"))?;
+    guard_insecure_dev_setup(s.get_g2_secret());
+
+    Ok(TG1::verify(
+        &commitment_minus_value(commitment, y),
+        &TG2::generator(),
+        quotient,
+        &g2_s_minus_z(s_g2, z),
+    ))
+}
+
+/// Commit to `x^shift * p(x)` by offsetting into the monomial setup, i.e. compute
+/// `[s^shift * p(s)]₁` without building the shifted polynomial.
+pub fn commit_shifted<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + G1LinComb<TFr, TG1Fp, TG1Affine>,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    p: &TPoly,
+    shift: usize,
+    s: &TKZGSettings,
+) -> Result<TG1, String> {
+    let secret_g1 = s.get_g1_secret();
+    if p.len() + shift > secret_g1.len() {
+        return Err(String::from("Shifted polynomial is longer than secret g1"));
+    }
+
+    Ok(TG1::g1_lincomb(
+        &secret_g1[shift..],
+        p.get_coeffs(),
+        p.len(),
+        None,
+    ))
+}
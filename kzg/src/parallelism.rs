@@ -0,0 +1,233 @@
+#[cfg(feature = "parallel")]
+extern crate alloc;
+
+#[cfg(feature = "parallel")]
+use alloc::format;
+#[cfg(feature = "parallel")]
+use alloc::string::{String, ToString};
+#[cfg(feature = "parallel")]
+use alloc::sync::Arc;
+use spin::RwLock;
+
+#[cfg(feature = "parallel")]
+use crate::error::KzgError;
+
+////////////////////////////// Runtime parallelism //////////////////////////////
+//
+// The `parallel` feature decides at compile time whether the crate may use threads. Reproducible
+// benchmarks and debugging under a record-and-replay debugger such as rr also need to turn them
+// off, or pin their number, in a build that has the feature. `set_parallelism` does so for the
+// whole process: precomputation table multiplications, Pippenger over the thread pool, batch
+// point encoding and decoding when reading and writing setups, `io_utils::batch_writer` and the
+// FFTs of every backend check it before splitting work across threads.
+//
+// `Parallelism::Off` runs that work on the calling thread. `Parallelism::Threads(n)` caps the
+// thread pool workers at `n` and runs rayon work on a dedicated pool of `n` threads. Without the
+// `parallel` feature, every mode runs sequentially.
+//
+// The thread pools themselves default to one thread per CPU: the rayon global pool and the pool
+// of `msm::thread_pool::da_pool`. Applications managing their own CPU budget can run the crate on
+// their pools instead, with `set_rayon_pool` and `msm::thread_pool::set_da_pool`, or have
+// `ThreadPoolConfig::install` build both with a thread count, stack size and, with the
+// `pin-threads` feature, threads pinned to cores. Calls made from a worker of a rayon pool
+// already run on that pool.
+
+/// Threads used by the parallel code paths
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Parallelism {
+    /// All threads of the thread pools, as with the `parallel` feature alone
+    #[default]
+    Auto,
+    /// Run on the calling thread only
+    Off,
+    /// At most `n` threads, `Threads(0)` and `Threads(1)` being the same as `Off`
+    Threads(usize),
+}
+
+static PARALLELISM: RwLock<Parallelism> = RwLock::new(Parallelism::Auto);
+
+#[cfg(feature = "parallel")]
+static RAYON_POOL: RwLock<Option<Arc<rayon::ThreadPool>>> = RwLock::new(None);
+
+/// Set the threads used by the parallel code paths, for the whole process
+pub fn set_parallelism(parallelism: Parallelism) {
+    *PARALLELISM.write() = parallelism;
+}
+
+/// Currently selected parallelism
+pub fn parallelism() -> Parallelism {
+    *PARALLELISM.read()
+}
+
+/// Number of threads work may be split across, 1 without the `parallel` feature
+pub fn max_threads() -> usize {
+    match parallelism() {
+        Parallelism::Off => 1,
+        #[cfg(feature = "parallel")]
+        Parallelism::Auto => match &*RAYON_POOL.read() {
+            Some(pool) if rayon::current_thread_index().is_none() => pool.current_num_threads(),
+            _ => rayon::current_num_threads(),
+        },
+        #[cfg(feature = "parallel")]
+        Parallelism::Threads(n) => n.max(1),
+        #[cfg(not(feature = "parallel"))]
+        Parallelism::Auto | Parallelism::Threads(_) => 1,
+    }
+}
+
+/// Whether work should be split across threads
+pub fn is_parallel() -> bool {
+    max_threads() > 1
+}
+
+/// Run the rayon work of the crate on `pool`, or on the rayon global pool with `None`
+#[cfg(feature = "parallel")]
+pub fn set_rayon_pool(pool: Option<Arc<rayon::ThreadPool>>) {
+    *RAYON_POOL.write() = pool;
+}
+
+/// Run `op`, in a rayon pool of `n` threads with `Parallelism::Threads(n)` and in the pool of
+/// `set_rayon_pool` otherwise, so that rayon work it spawns runs there
+#[cfg(feature = "parallel")]
+pub fn install<R: Send>(op: impl FnOnce() -> R + Send) -> R {
+    static POOL: std::sync::Mutex<Option<(usize, Arc<rayon::ThreadPool>)>> =
+        std::sync::Mutex::new(None);
+
+    if rayon::current_thread_index().is_some() {
+        // Already on a rayon worker, whose pool decides
+        return op();
+    }
+    let n = match parallelism() {
+        Parallelism::Off => return op(),
+        Parallelism::Threads(n) if n > 1 => n,
+        Parallelism::Threads(_) => return op(),
+        Parallelism::Auto => {
+            let pool = RAYON_POOL.read().clone();
+            return match pool {
+                Some(pool) => pool.install(op),
+                None => op(),
+            };
+        }
+    };
+
+    let pool = {
+        let mut cached = POOL.lock().unwrap_or_else(|e| e.into_inner());
+        match &*cached {
+            Some((threads, pool)) if *threads == n => pool.clone(),
+            _ => {
+                let Ok(pool) = rayon::ThreadPoolBuilder::new().num_threads(n).build() else {
+                    drop(cached);
+                    return op();
+                };
+                let pool = Arc::new(pool);
+                *cached = Some((n, pool.clone()));
+                pool
+            }
+        }
+    };
+
+    pool.install(op)
+}
+
+/// Thread pools built and installed by `install`, the defaults being those of the pools the
+/// crate creates on its own
+#[cfg(feature = "parallel")]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ThreadPoolConfig {
+    /// Threads of each pool, one per CPU if `None`
+    pub num_threads: Option<usize>,
+    /// Stack size of each thread in bytes, the default of the standard library if `None`
+    pub stack_size: Option<usize>,
+    /// Name of the threads, rayon threads getting their index appended
+    pub thread_name: Option<String>,
+    /// Pin thread `i` of each pool to core `i`, modulo the number of cores
+    #[cfg(feature = "pin-threads")]
+    pub pin_threads: bool,
+}
+
+#[cfg(feature = "parallel")]
+impl ThreadPoolConfig {
+    /// Pools of `num_threads` threads
+    pub fn with_threads(num_threads: usize) -> Self {
+        Self {
+            num_threads: Some(num_threads),
+            ..Self::default()
+        }
+    }
+
+    /// Build a rayon pool and a `da_pool` and run the parallel work of the crate on them, see
+    /// `set_rayon_pool` and `msm::thread_pool::set_da_pool`
+    pub fn install(&self) -> Result<(), KzgError> {
+        let num_threads = self.num_threads.unwrap_or_else(num_cpus::get);
+        if num_threads == 0 {
+            return Err(KzgError::InvalidArgument(String::from(
+                "Thread pools need at least one thread",
+            )));
+        }
+
+        let mut builder = threadpool::Builder::new().num_threads(num_threads);
+        let mut rayon_builder = rayon::ThreadPoolBuilder::new().num_threads(num_threads);
+        if let Some(stack_size) = self.stack_size {
+            builder = builder.thread_stack_size(stack_size);
+            rayon_builder = rayon_builder.stack_size(stack_size);
+        }
+        if let Some(name) = &self.thread_name {
+            builder = builder.thread_name(name.clone());
+            let name = name.clone();
+            rayon_builder = rayon_builder.thread_name(move |i| format!("{}-{}", name, i));
+        }
+
+        #[cfg(feature = "pin-threads")]
+        let cores = if self.pin_threads {
+            let cores = core_affinity::get_core_ids()
+                .filter(|cores| !cores.is_empty())
+                .ok_or_else(|| {
+                    KzgError::Unsupported(String::from("The cores of this machine are unknown"))
+                })?;
+            let handler_cores = cores.clone();
+            rayon_builder = rayon_builder.start_handler(move |i| {
+                core_affinity::set_for_current(handler_cores[i % handler_cores.len()]);
+            });
+            Some(cores)
+        } else {
+            None
+        };
+
+        let rayon_pool = rayon_builder
+            .build()
+            .map_err(|e| KzgError::Other(e.to_string()))?;
+        let pool = builder.build();
+        #[cfg(feature = "pin-threads")]
+        if let Some(cores) = cores {
+            pin_pool(&pool, &cores);
+        }
+
+        crate::msm::thread_pool::set_da_pool(pool);
+        set_rayon_pool(Some(Arc::new(rayon_pool)));
+
+        Ok(())
+    }
+}
+
+/// Pin each thread of `pool` to a core. The jobs wait for each other, so that each of them runs on
+/// a thread of its own
+#[cfg(feature = "pin-threads")]
+fn pin_pool(pool: &threadpool::ThreadPool, cores: &[core_affinity::CoreId]) {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Barrier;
+
+    let threads = pool.max_count();
+    let barrier = Arc::new(Barrier::new(threads));
+    let next = Arc::new(AtomicUsize::new(0));
+    for _ in 0..threads {
+        let barrier = barrier.clone();
+        let next = next.clone();
+        let core = cores.to_vec();
+        pool.execute(move || {
+            let i = next.fetch_add(1, Ordering::Relaxed);
+            core_affinity::set_for_current(core[i % core.len()]);
+            barrier.wait();
+        });
+    }
+    pool.join();
+}
@@ -0,0 +1,71 @@
+use spin::RwLock;
+
+////////////////////////////// Progress reporting //////////////////////////////
+//
+// Loading the trusted setup, building or reading a multi-GB precomputation table and large MSMs
+// run for seconds to minutes without any output, long enough for an operator to assume the
+// process hung. A sink installed with `set_progress_sink` receives the stage being run and the
+// fraction of it done, from 0 to 1, and can drive a progress bar or a log line.
+//
+// Reports are throttled to whole percents of each stage, the last one always has fraction 1. With
+// the `parallel` feature they may come from any thread of the pool, and stages of operations
+// running concurrently interleave.
+
+/// Long-running operation whose progress is reported
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressStage {
+    /// Parsing and checking the G1 points of the trusted setup
+    TrustedSetup,
+    /// Building the rows of the fixed-base MSM precomputation table
+    PrecomputationTable,
+    /// Reading the points of a precomputation table file
+    TableFileRead,
+    /// Checking the points of a precomputation table file against the digest in its header
+    TableFileDigest,
+    /// MSM over at least `MSM_PROGRESS_MIN_POINTS` points, reported per window
+    Msm,
+}
+
+/// Smallest MSM whose progress is reported, below it MSMs take milliseconds
+pub const MSM_PROGRESS_MIN_POINTS: usize = 1 << 16;
+
+/// Receiver of progress reports, called from inside the long-running loops
+pub trait ProgressSink: Sync {
+    /// `fraction` of `stage` is done, from 0 to 1
+    fn progress(&self, stage: ProgressStage, fraction: f64);
+}
+
+impl<F: Fn(ProgressStage, f64) + Sync> ProgressSink for F {
+    fn progress(&self, stage: ProgressStage, fraction: f64) {
+        self(stage, fraction)
+    }
+}
+
+static PROGRESS_SINK: RwLock<Option<&'static dyn ProgressSink>> = RwLock::new(None);
+
+/// Install a sink for progress reports, or remove it with `None`. Reports are dropped when no
+/// sink is installed.
+pub fn set_progress_sink(sink: Option<&'static dyn ProgressSink>) {
+    *PROGRESS_SINK.write() = sink;
+}
+
+/// Report that `done` of the `total` units of `stage` are done to the installed sink, if any.
+/// Backends call this from their own long-running loops.
+pub fn report_progress(stage: ProgressStage, done: usize, total: usize) {
+    if total == 0 || (0 < done && done < total && done * 100 / total == (done - 1) * 100 / total) {
+        return;
+    }
+
+    if let Some(sink) = *PROGRESS_SINK.read() {
+        sink.progress(stage, done as f64 / total as f64);
+    }
+}
+
+/// `report_progress` for window `done` of `total` of an MSM over `npoints` points, ignoring MSMs
+/// below `MSM_PROGRESS_MIN_POINTS`
+#[inline]
+pub fn report_msm_progress(npoints: usize, done: usize, total: usize) {
+    if npoints >= MSM_PROGRESS_MIN_POINTS {
+        report_progress(ProgressStage::Msm, done, total);
+    }
+}
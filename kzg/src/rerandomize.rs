@@ -0,0 +1,147 @@
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use sha2::{Digest, Sha256};
+
+use crate::eip_4844::{compute_powers, hash_to_bls_field};
+use crate::secret::wipe_scalars;
+use crate::{Fr, G1Mul, G2Mul, PairingVerify, G1, G2};
+
+////////////////////////////// Trusted setup re-randomization //////////////////////////////
+//
+// Re-randomizing a setup `[s^i]` with a fresh secret `α` gives the setup `[(αs)^i] = α^i * [s^i]`,
+// so no one who knew `s` alone knows the new secret. Whoever picks `α` publishes `[α]₂`, which
+// lets anyone check that the new setup was derived from the old one.
+//
+// A commitment `[p(s)]₁` made under the old setup is, unchanged, the commitment to
+// `p'(x) = p(x / α)` under the new one. An opening of `p` at `z` to `y` with proof `π` becomes the
+// opening of `p'` at `α * z` to the same `y`, with proof `π / α`. Commitments to the original
+// `p(x)` under the new setup cannot be derived from old commitments and must be recomputed.
+
+/// Domain separator of the setup consistency challenge
+pub const SRS_CHECK_DOMAIN: [u8; 16] = *b"SRSCONSISTENCY__";
+
+/// Compute the setup `[(αs)^i]` from the setup `[s^i]`
+pub fn rerandomize_srs<TFr: Fr + Copy, TG1: G1 + G1Mul<TFr>, TG2: G2 + G2Mul<TFr>>(
+    secret_g1: &[TG1],
+    secret_g2: &[TG2],
+    alpha: &TFr,
+) -> Result<(Vec<TG1>, Vec<TG2>), String> {
+    if alpha.is_zero() {
+        return Err(String::from("Re-randomization secret must not be zero"));
+    }
+
+    let mut powers = compute_powers(alpha, secret_g1.len().max(secret_g2.len()));
+    let g1 = secret_g1
+        .iter()
+        .zip(powers.iter())
+        .map(|(point, power)| point.mul(power))
+        .collect();
+    let g2 = secret_g2
+        .iter()
+        .zip(powers.iter())
+        .map(|(point, power)| point.mul(power))
+        .collect();
+    wipe_scalars(&mut powers);
+
+    Ok((g1, g2))
+}
+
+/// Check that a setup has the form `[s^i]₁, [s^i]₂` for some `s`, starting from the generators
+pub fn check_srs_consistency<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + PairingVerify<TG1, TG2>,
+    TG2: G2 + G2Mul<TFr>,
+>(
+    secret_g1: &[TG1],
+    secret_g2: &[TG2],
+) -> Result<bool, String> {
+    if secret_g1.len() < 2 || secret_g2.len() < 2 {
+        return Err(String::from(
+            "Trusted setup must contain at least two points in each group",
+        ));
+    }
+    if !secret_g1[0].equals(&TG1::generator()) || !secret_g2[0].equals(&TG2::generator()) {
+        return Ok(false);
+    }
+    if secret_g1[1].is_inf() {
+        return Ok(false);
+    }
+
+    let mut hasher = Sha256::new();
+    hasher.update(SRS_CHECK_DOMAIN);
+    hasher.update((secret_g1.len() as u64).to_be_bytes());
+    hasher.update((secret_g2.len() as u64).to_be_bytes());
+    for point in secret_g1 {
+        hasher.update(point.to_bytes());
+    }
+    for point in secret_g2 {
+        hasher.update(point.to_bytes());
+    }
+    let r = hash_to_bls_field::<TFr>(&hasher.finalize().into());
+    let r_powers = compute_powers(&r, secret_g1.len().max(secret_g2.len()));
+
+    // Every consecutive pair is related by s: e([s^i]₁, [s]₂) == e([s^(i+1)]₁, [1]₂), checked for
+    // all i at once with random weights. Same for G2, against [s]₁.
+    let mut g1_lo = TG1::identity();
+    let mut g1_hi = TG1::identity();
+    for (pair, r_power) in secret_g1.windows(2).zip(r_powers.iter()) {
+        g1_lo = g1_lo.add_or_dbl(&pair[0].mul(r_power));
+        g1_hi = g1_hi.add_or_dbl(&pair[1].mul(r_power));
+    }
+    if !TG1::verify(&g1_lo, &secret_g2[1], &g1_hi, &TG2::generator()) {
+        return Ok(false);
+    }
+
+    let mut g2_lo = secret_g2[0].mul(&r_powers[0]);
+    let mut g2_hi = secret_g2[1].mul(&r_powers[0]);
+    for (pair, r_power) in secret_g2.windows(2).zip(r_powers.iter()).skip(1) {
+        g2_lo = g2_lo.add_or_dbl(&pair[0].mul(r_power));
+        g2_hi = g2_hi.add_or_dbl(&pair[1].mul(r_power));
+    }
+
+    Ok(TG1::verify(
+        &secret_g1[1],
+        &g2_lo,
+        &TG1::generator(),
+        &g2_hi,
+    ))
+}
+
+/// Check that `new_secret_g1` was derived from `old_secret_g1` with the secret published as
+/// `alpha_g2 = [α]₂`. The new setup should also pass `check_srs_consistency`.
+pub fn verify_srs_update<TG1: G1 + PairingVerify<TG1, TG2>, TG2: G2>(
+    old_secret_g1: &[TG1],
+    new_secret_g1: &[TG1],
+    alpha_g2: &TG2,
+) -> Result<bool, String> {
+    if old_secret_g1.len() < 2 || new_secret_g1.len() < 2 {
+        return Err(String::from(
+            "Trusted setup must contain at least two G1 points",
+        ));
+    }
+
+    // e([αs]₁, [1]₂) == e([s]₁, [α]₂)
+    Ok(TG1::verify(
+        &new_secret_g1[1],
+        &TG2::generator(),
+        &old_secret_g1[1],
+        alpha_g2,
+    ))
+}
+
+/// Translate an opening of `p` at `z` under the old setup into an opening of `p(x / α)` at `α * z`
+/// under the re-randomized one. The value is unchanged. Returns the new proof and point.
+pub fn translate_proof<TFr: Fr, TG1: G1 + G1Mul<TFr>>(
+    proof: &TG1,
+    z: &TFr,
+    alpha: &TFr,
+) -> Result<(TG1, TFr), String> {
+    if alpha.is_zero() {
+        return Err(String::from("Re-randomization secret must not be zero"));
+    }
+
+    Ok((proof.mul(&alpha.inverse()), z.mul(alpha)))
+}
@@ -0,0 +1,279 @@
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use sha2::{Digest, Sha256};
+
+use crate::dev_setup::{is_insecure_dev_setup, report_insecure_dev_setup};
+use crate::eip_4844::{compute_powers, hash_to_bls_field};
+use crate::{
+    FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1Mul, G2Mul, KZGSettings, PairingVerify, Poly, G1,
+    G2,
+};
+
+////////////////////////////// Openings at common sampled points //////////////////////////////
+//
+// Sampling protocols open every blob at the same small set of random points `z_0..z_{k-1}`. Each
+// blob polynomial `p(x)` is opened with one multiproof
+//
+//     p(x) - I(x) = q(x) * Z(x),   e(C - [I(s)]₁, [1]₂) = e([q(s)]₁, [Z(s)]₂)
+//
+// where `Z(x) = (x - z_0) * .. * (x - z_{k-1})` and `I(x)` interpolates the opened values. `Z(x)`,
+// `[Z(s)]₂` and the commitments to the Lagrange basis of the points depend only on the points, so
+// they are computed once in `SampledPoints` and shared by the openings of all blobs. The verifier
+// then gets `[I(s)]₁` as a `k`-point linear combination, without interpolating.
+
+/// Domain separator of the batched opening verification challenge
+pub const SAMPLED_OPENING_BATCH_DOMAIN: [u8; 16] = *b"SAMPLEDOPENBATCH";
+
+/// Values of one polynomial at the sampled points, with a proof for all of them
+#[derive(Debug, Clone, PartialEq)]
+pub struct SampledOpening<TFr, TG1> {
+    pub values: Vec<TFr>,
+    pub proof: TG1,
+}
+
+/// Sampled points, with everything about them shared by the openings of all blobs
+#[derive(Debug, Clone)]
+pub struct SampledPoints<TFr, TG1, TG2> {
+    points: Vec<TFr>,
+    /// Coefficients of `Z(x)`, monic, of degree `points.len()`
+    vanishing_poly: Vec<TFr>,
+    /// `[L_i(s)]₁` for the Lagrange basis of the points
+    lagrange_commitments: Vec<TG1>,
+    /// `[Z(s)]₂`
+    vanishing_g2: TG2,
+    /// Whether the setup is the development one, see `dev_setup`
+    insecure_dev_setup: bool,
+}
+
+/// Multiply the polynomial `coeffs` by `(x - z)` in place
+fn mul_by_linear<TFr: Fr>(coeffs: &mut Vec<TFr>, z: &TFr) {
+    coeffs.push(TFr::zero());
+    for i in (1..coeffs.len()).rev() {
+        coeffs[i] = coeffs[i - 1].sub(&coeffs[i].mul(z));
+    }
+    coeffs[0] = coeffs[0].mul(z).negate();
+}
+
+/// Divide `coeffs` by the monic polynomial `divisor`, returning the quotient and the remainder
+fn divide_by_monic<TFr: Fr>(coeffs: &[TFr], divisor: &[TFr]) -> (Vec<TFr>, Vec<TFr>) {
+    let k = divisor.len() - 1;
+    if coeffs.len() <= k {
+        let mut remainder = coeffs.to_vec();
+        remainder.resize(k, TFr::zero());
+        return (Vec::new(), remainder);
+    }
+
+    let mut remainder = coeffs.to_vec();
+    let mut quotient = alloc::vec![TFr::zero(); coeffs.len() - k];
+    for i in (0..quotient.len()).rev() {
+        let factor = remainder[i + k].clone();
+        for (j, d) in divisor[..k].iter().enumerate() {
+            remainder[i + j] = remainder[i + j].sub(&factor.mul(d));
+        }
+        quotient[i] = factor;
+    }
+    remainder.truncate(k);
+
+    (quotient, remainder)
+}
+
+impl<TFr: Fr, TG1: G1 + G1Mul<TFr>, TG2: G2 + G2Mul<TFr>> SampledPoints<TFr, TG1, TG2> {
+    /// Prepare openings at `points`, which must be distinct and fewer than the G2 points of the
+    /// setup
+    pub fn new<
+        TFFTSettings: FFTSettings<TFr>,
+        TPoly: Poly<TFr>,
+        TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+        TG1Fp: G1Fp,
+        TG1Affine: G1Affine<TG1, TG1Fp>,
+    >(
+        points: &[TFr],
+        ks: &TKZGSettings,
+    ) -> Result<Self, String>
+    where
+        TG1: G1GetFp<TG1Fp>,
+    {
+        if points.is_empty() {
+            return Err(String::from("At least one point must be sampled"));
+        }
+        if points.len() >= ks.get_g2_secret().len() {
+            return Err(String::from("Too many points for the trusted setup"));
+        }
+
+        let mut vanishing_poly = Vec::from([TFr::one()]);
+        for z in points {
+            mul_by_linear(&mut vanishing_poly, z);
+        }
+
+        // L_i(x) = Z(x) / (x - z_i) / prod_{m != i} (z_i - z_m)
+        let mut lagrange_commitments = Vec::with_capacity(points.len());
+        for (i, z_i) in points.iter().enumerate() {
+            let mut denominator = TFr::one();
+            for (m, z_m) in points.iter().enumerate() {
+                if m != i {
+                    denominator = denominator.mul(&z_i.sub(z_m));
+                }
+            }
+            if denominator.is_zero() {
+                return Err(String::from("Sampled points must be distinct"));
+            }
+
+            let (basis, _) = divide_by_monic(&vanishing_poly, &[z_i.negate(), TFr::one()]);
+            let inverse = denominator.inverse();
+            let basis = basis.iter().map(|c| c.mul(&inverse)).collect::<Vec<_>>();
+            lagrange_commitments.push(ks.commit_to_poly(&TPoly::from_coeffs(&basis))?);
+        }
+
+        let secret_g2 = ks.get_g2_secret();
+        let mut vanishing_g2 = secret_g2[0].mul(&vanishing_poly[0]);
+        for (c, s_i) in vanishing_poly.iter().zip(secret_g2).skip(1) {
+            vanishing_g2 = vanishing_g2.add_or_dbl(&s_i.mul(c));
+        }
+
+        Ok(Self {
+            points: points.to_vec(),
+            vanishing_poly,
+            lagrange_commitments,
+            vanishing_g2,
+            insecure_dev_setup: is_insecure_dev_setup(secret_g2),
+        })
+    }
+
+    pub fn points(&self) -> &[TFr] {
+        &self.points
+    }
+
+    /// Open every polynomial of `polys` at all sampled points
+    pub fn prove<
+        TFFTSettings: FFTSettings<TFr>,
+        TPoly: Poly<TFr>,
+        TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+        TG1Fp: G1Fp,
+        TG1Affine: G1Affine<TG1, TG1Fp>,
+    >(
+        &self,
+        polys: &[TPoly],
+        ks: &TKZGSettings,
+    ) -> Result<Vec<SampledOpening<TFr, TG1>>, String>
+    where
+        TG1: G1GetFp<TG1Fp>,
+    {
+        polys
+            .iter()
+            .map(|poly| {
+                let (quotient, _) = divide_by_monic(poly.get_coeffs(), &self.vanishing_poly);
+                let values = self.points.iter().map(|z| poly.eval(z)).collect();
+                let proof = if quotient.is_empty() {
+                    TG1::identity()
+                } else {
+                    ks.commit_to_poly(&TPoly::from_coeffs(&quotient))?
+                };
+
+                Ok(SampledOpening { values, proof })
+            })
+            .collect()
+    }
+
+    /// `C - [I(s)]₁` for an opening against `commitment`
+    fn commitment_minus_interpolant(
+        &self,
+        commitment: &TG1,
+        opening: &SampledOpening<TFr, TG1>,
+    ) -> Result<TG1, String> {
+        if opening.values.len() != self.points.len() {
+            return Err(String::from(
+                "Opening must hold one value per sampled point",
+            ));
+        }
+
+        // Few points are sampled, so a plain sum is cheaper than setting up an MSM
+        let mut interpolant = TG1::identity();
+        for (basis, value) in self.lagrange_commitments.iter().zip(&opening.values) {
+            interpolant = interpolant.add_or_dbl(&basis.mul(value));
+        }
+        Ok(commitment.sub(&interpolant))
+    }
+
+    /// Check an opening of the polynomial committed to by `commitment`
+    pub fn verify(
+        &self,
+        commitment: &TG1,
+        opening: &SampledOpening<TFr, TG1>,
+    ) -> Result<bool, String>
+    where
+        TG1: PairingVerify<TG1, TG2>,
+    {
+        let lhs = self.commitment_minus_interpolant(commitment, opening)?;
+        if self.insecure_dev_setup {
+            report_insecure_dev_setup();
+        }
+
+        Ok(TG1::verify(
+            &lhs,
+            &TG2::generator(),
+            &opening.proof,
+            &self.vanishing_g2,
+        ))
+    }
+
+    /// Check the openings of many commitments with a single pairing check, by combining them
+    /// with powers of a Fiat-Shamir challenge
+    pub fn verify_batch(
+        &self,
+        commitments: &[TG1],
+        openings: &[SampledOpening<TFr, TG1>],
+    ) -> Result<bool, String>
+    where
+        TG1: PairingVerify<TG1, TG2>,
+    {
+        if commitments.len() != openings.len() {
+            return Err(String::from(
+                "Commitments and openings must have the same length",
+            ));
+        }
+        match openings.len() {
+            0 => return Ok(true),
+            1 => return self.verify(&commitments[0], &openings[0]),
+            _ => {}
+        }
+
+        let mut hasher = Sha256::new();
+        hasher.update(SAMPLED_OPENING_BATCH_DOMAIN);
+        hasher.update((self.points.len() as u64).to_be_bytes());
+        hasher.update((openings.len() as u64).to_be_bytes());
+        for point in &self.points {
+            hasher.update(point.to_bytes());
+        }
+        for (commitment, opening) in commitments.iter().zip(openings) {
+            hasher.update(commitment.to_bytes());
+            for value in &opening.values {
+                hasher.update(value.to_bytes());
+            }
+            hasher.update(opening.proof.to_bytes());
+        }
+        let r = hash_to_bls_field(&hasher.finalize().into());
+        let r_powers = compute_powers(&r, openings.len());
+
+        // e(sum r^j * (C_j - [I_j(s)]), [1]) == e(sum r^j * proof_j, [Z(s)])
+        let mut lhs = TG1::identity();
+        let mut proof_lincomb = TG1::identity();
+        for ((commitment, opening), r_power) in commitments.iter().zip(openings).zip(&r_powers) {
+            let term = self.commitment_minus_interpolant(commitment, opening)?;
+            lhs = lhs.add_or_dbl(&term.mul(r_power));
+            proof_lincomb = proof_lincomb.add_or_dbl(&opening.proof.mul(r_power));
+        }
+        if self.insecure_dev_setup {
+            report_insecure_dev_setup();
+        }
+
+        Ok(TG1::verify(
+            &lhs,
+            &TG2::generator(),
+            &proof_lincomb,
+            &self.vanishing_g2,
+        ))
+    }
+}
@@ -0,0 +1,136 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::eip_4844::hash_to_bls_field;
+use crate::{Fr, G1Mul, G2Mul, G1, G2};
+
+////////////////////////////// Secret scalars //////////////////////////////
+//
+// Generating a setup or contributing to a ceremony in-process leaves the secret `s`, its powers
+// and the bytes it was derived from in released memory, where a core dump or a later memory
+// disclosure can find them. With the `zeroize` feature, the setup generators, ceremony
+// contributions and re-randomization overwrite them once done, and `SecretScalar` overwrites the
+// scalar it holds when dropped. Without the feature the `wipe_*` functions do nothing.
+//
+// Scalars are overwritten with `Fr::zero()` through a volatile write, which the compiler does not
+// elide, and bytes with `zeroize`. Copies left behind by moves or in registers are out of reach.
+
+/// Scalar overwritten when dropped, see `wipe_scalar`
+pub struct SecretScalar<TFr: Fr + Copy>(TFr);
+
+impl<TFr: Fr + Copy> SecretScalar<TFr> {
+    pub fn new(secret: TFr) -> Self {
+        Self(secret)
+    }
+
+    /// Secret of `generate_trusted_setup` for `seed`, the seed is wiped
+    pub fn from_seed(mut seed: [u8; 32]) -> Self {
+        let secret = Self(hash_to_bls_field(&seed));
+        wipe_bytes(&mut seed);
+
+        secret
+    }
+
+    pub fn expose(&self) -> &TFr {
+        &self.0
+    }
+
+    /// Multiply the secret by `other` in place
+    pub fn mul_assign(&mut self, other: &TFr) {
+        self.0 = self.0.mul(other);
+    }
+}
+
+impl<TFr: Fr + Copy> fmt::Debug for SecretScalar<TFr> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretScalar(..)")
+    }
+}
+
+impl<TFr: Fr + Copy> Drop for SecretScalar<TFr> {
+    fn drop(&mut self) {
+        wipe_scalar(&mut self.0);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<TFr: Fr + Copy> zeroize::Zeroize for SecretScalar<TFr> {
+    fn zeroize(&mut self) {
+        wipe_scalar(&mut self.0);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<TFr: Fr + Copy> zeroize::ZeroizeOnDrop for SecretScalar<TFr> {}
+
+/// Overwrite `scalar` with zero, with the `zeroize` feature
+#[inline]
+pub fn wipe_scalar<TFr: Fr + Copy>(scalar: &mut TFr) {
+    #[cfg(feature = "zeroize")]
+    {
+        // `Copy` scalars have no drop glue, nothing is leaked by not dropping the old one
+        unsafe { core::ptr::write_volatile(scalar, TFr::zero()) };
+        core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+    }
+    #[cfg(not(feature = "zeroize"))]
+    let _ = scalar;
+}
+
+/// `wipe_scalar` for every scalar of `scalars`
+pub fn wipe_scalars<TFr: Fr + Copy>(scalars: &mut [TFr]) {
+    scalars.iter_mut().for_each(wipe_scalar);
+}
+
+/// Overwrite `bytes` with zeros, with the `zeroize` feature
+#[inline]
+pub fn wipe_bytes(bytes: &mut [u8]) {
+    #[cfg(feature = "zeroize")]
+    zeroize::Zeroize::zeroize(bytes);
+    #[cfg(not(feature = "zeroize"))]
+    let _ = bytes;
+}
+
+/// Setup of `n` powers of `secret` in both groups, wiping the powers as it goes
+pub fn generate_trusted_setup_with_secret<
+    TFr: Fr + Copy,
+    TG1: G1 + G1Mul<TFr>,
+    TG2: G2 + G2Mul<TFr>,
+>(
+    n: usize,
+    secret: &SecretScalar<TFr>,
+) -> (Vec<TG1>, Vec<TG2>) {
+    let mut s_pow = SecretScalar::new(TFr::one());
+
+    let mut s1 = Vec::with_capacity(n);
+    let mut s2 = Vec::with_capacity(n);
+    for _ in 0..n {
+        s1.push(TG1::generator().mul(s_pow.expose()));
+        s2.push(TG2::generator().mul(s_pow.expose()));
+        s_pow.mul_assign(secret.expose());
+    }
+
+    (s1, s2)
+}
+
+/// Setup of `n` powers of a secret drawn from `rng`, so that seeded runs of every backend get the
+/// same setup. For tests only, the secret is as good as the seed of `rng`
+#[cfg(feature = "rand")]
+pub fn generate_trusted_setup_from_rng<
+    TFr: Fr + Copy,
+    TG1: G1 + G1Mul<TFr>,
+    TG2: G2 + G2Mul<TFr>,
+    R: rand_core::RngCore + ?Sized,
+>(
+    rng: &mut R,
+    n: usize,
+) -> (Vec<TG1>, Vec<TG2>) {
+    let mut seed = [0u8; 32];
+    rng.fill_bytes(&mut seed);
+
+    let secret = SecretScalar::from_seed(seed);
+    wipe_bytes(&mut seed);
+
+    generate_trusted_setup_with_secret(n, &secret)
+}
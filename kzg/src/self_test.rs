@@ -0,0 +1,207 @@
+extern crate alloc;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use sha2::{Digest, Sha256};
+
+use crate::eip_4844::{
+    blob_to_kzg_commitment_rust, bytes_to_blob, compute_blob_kzg_proof_rust, hash_to_bls_field,
+    verify_blob_kzg_proof_rust, FIELD_ELEMENTS_PER_BLOB,
+};
+use crate::golden::{
+    bytes_to_hex, generate_golden_vectors, seeded_blob_bytes, settings_digest,
+    GOLDEN_MAINNET_SETUP_DIGEST, GOLDEN_SEEDS, GOLDEN_VECTORS,
+};
+use crate::{
+    FFTFr, FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1LinComb, G1Mul, KZGSettings, Poly,
+    PolyRecover, G1, G2,
+};
+
+////////////////////////////// Installation self-test //////////////////////////////
+//
+// `KZGSettings::self_test` checks a deployment end-to-end with the settings it will serve: a
+// corrupted setup file, a stale precomputation table or a miscompiled backend all make it fail.
+// It runs, in order:
+//
+// - a known-answer test of the backend, a scalar multiplication with a fixed result;
+// - commit, prove and verify round trips over the setup and its precomputation table, with
+//   polynomials for setups in monomial form and blobs for EIP-4844 setups in Lagrange form, and
+//   the golden vectors when the setup is the mainnet one;
+// - a recovery round trip over the FFT domain of the settings, with half of the samples erased.
+//
+// Inputs are derived from fixed seeds, so a failure reproduces. Every round trip also checks that
+// a wrong value is rejected, a verifier accepting everything does not pass.
+
+/// Domain separator of the self-test inputs
+pub const SELF_TEST_DOMAIN: [u8; 16] = *b"KZGSELFTEST_____";
+
+/// Compressed `[x]₁`, for the scalar `x` of `known_answer_test`
+pub const SELF_TEST_G1: &str =
+    "0xa8cc2bb22c13daaf2ad61227c3789e4c5847ab46c38bcf40ea9b8440a434b37c972bfbbf5ac7f8a186b7e1f316496ace";
+
+/// Scalars derived from `seed`
+fn seeded_scalars<TFr: Fr>(seed: u64, n: usize) -> Vec<TFr> {
+    (0..n as u64)
+        .map(|i| {
+            let mut hasher = Sha256::new();
+            hasher.update(SELF_TEST_DOMAIN);
+            hasher.update(seed.to_be_bytes());
+            hasher.update(i.to_be_bytes());
+            hash_to_bls_field(&hasher.finalize().into())
+        })
+        .collect()
+}
+
+/// Check a full-width scalar multiplication of the generator against `SELF_TEST_G1`
+pub fn known_answer_test<TFr: Fr, TG1: G1 + G1Mul<TFr>>() -> Result<(), String> {
+    let x = &seeded_scalars::<TFr>(0, 1)[0];
+    let point = bytes_to_hex(&TG1::generator().mul(x).to_bytes());
+    if point != SELF_TEST_G1 {
+        return Err(format!(
+            "Known-answer test failed: got {}, expected {}",
+            point, SELF_TEST_G1
+        ));
+    }
+
+    Ok(())
+}
+
+/// Run the self-test of the settings `s`, see `KZGSettings::self_test`
+pub fn run_self_test<
+    TFr: Fr + Copy,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + G1LinComb<TFr, TG1Fp, TG1Affine>,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr> + FFTFr<TFr>,
+    TPoly: Poly<TFr> + PolyRecover<TFr, TPoly, TFFTSettings>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    s: &TKZGSettings,
+) -> Result<(), String> {
+    known_answer_test::<TFr, TG1>()?;
+
+    let g1 = s.get_g1_secret();
+    if g1.is_empty() || s.get_g2_secret().len() < 2 {
+        return Err(String::from(
+            "Self-test needs at least one G1 and two G2 points",
+        ));
+    }
+    if g1[0].equals(&TG1::generator()) {
+        polynomial_round_trip(s)?;
+    } else if g1.len() == FIELD_ELEMENTS_PER_BLOB {
+        blob_round_trip(s)?;
+        if settings_digest(s) == GOLDEN_MAINNET_SETUP_DIGEST {
+            let vectors = generate_golden_vectors(&GOLDEN_SEEDS, s)?;
+            for (vector, expected) in vectors.iter().zip(GOLDEN_VECTORS.iter()) {
+                if !vector.matches(expected) {
+                    return Err(format!(
+                        "Golden vector of seed {} does not match",
+                        expected.seed
+                    ));
+                }
+            }
+        }
+    } else {
+        return Err(format!(
+            "Setup of {} G1 points is neither in monomial form nor an EIP-4844 setup",
+            g1.len()
+        ));
+    }
+
+    recovery_round_trip::<TFr, TFFTSettings, TPoly>(s.get_fft_settings())
+}
+
+fn polynomial_round_trip<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    s: &TKZGSettings,
+) -> Result<(), String> {
+    let n = s.get_g1_secret().len().min(64);
+    let p = TPoly::from_coeffs(&seeded_scalars::<TFr>(1, n));
+    let commitment = s.commit_to_poly(&p)?;
+
+    for x in seeded_scalars::<TFr>(2, 2) {
+        let y = p.eval(&x);
+        let proof = s.compute_proof_single(&p, &x)?;
+        if !s.check_proof_single(&commitment, &proof, &x, &y)? {
+            return Err(String::from("Self-test proof was rejected"));
+        }
+        if s.check_proof_single(&commitment, &proof, &x, &y.add(&TFr::one()))? {
+            return Err(String::from(
+                "Self-test proof of a wrong value was accepted",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn blob_round_trip<
+    TFr: Fr + Copy,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + G1LinComb<TFr, TG1Fp, TG1Affine>,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    s: &TKZGSettings,
+) -> Result<(), String> {
+    let mut proven = Vec::new();
+    for seed in &GOLDEN_SEEDS[..2] {
+        let blob: Vec<TFr> = bytes_to_blob(&seeded_blob_bytes(*seed))?;
+        let commitment = blob_to_kzg_commitment_rust(&blob, s)?;
+        let proof = compute_blob_kzg_proof_rust(&blob, &commitment, s)?;
+        if !verify_blob_kzg_proof_rust(&blob, &commitment, &proof, s)? {
+            return Err(String::from("Self-test blob proof was rejected"));
+        }
+        proven.push((blob, commitment, proof));
+    }
+
+    let (blob, commitment, _) = &proven[0];
+    if verify_blob_kzg_proof_rust(blob, commitment, &proven[1].2, s)? {
+        return Err(String::from("Self-test proof of another blob was accepted"));
+    }
+
+    Ok(())
+}
+
+fn recovery_round_trip<
+    TFr: Fr,
+    TFFTSettings: FFTSettings<TFr> + FFTFr<TFr>,
+    TPoly: Poly<TFr> + PolyRecover<TFr, TPoly, TFFTSettings>,
+>(
+    fs: &TFFTSettings,
+) -> Result<(), String> {
+    let width = fs.get_max_width();
+    if width < 2 {
+        return Ok(());
+    }
+
+    let mut coeffs = seeded_scalars::<TFr>(3, width / 2);
+    coeffs.resize(width, TFr::zero());
+    let data = fs.fft_fr(&coeffs, false)?;
+    let samples = data
+        .iter()
+        .enumerate()
+        .map(|(i, d)| (i % 4 == 0 || i % 4 == 3).then(|| d.clone()))
+        .collect::<Vec<_>>();
+
+    let recovered = TPoly::recover_poly_from_samples(&samples, fs)?;
+    if (0..width).any(|i| !recovered.get_coeff_at(i).equals(&data[i])) {
+        return Err(String::from("Self-test recovery returned wrong samples"));
+    }
+
+    Ok(())
+}
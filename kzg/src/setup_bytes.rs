@@ -0,0 +1,88 @@
+extern crate alloc;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::eip_4844::BYTES_PER_G2;
+use crate::envelope::{CurveId, ENVELOPE_HEADER_SIZE, ENVELOPE_VERSION};
+use crate::error::KzgError;
+use crate::{Compression, G1, G2};
+
+////////////////////////////// Setups held in memory //////////////////////////////
+//
+// Embedded and WASM targets may have no filesystem to read the trusted setup from at runtime.
+// The setup can instead be built into the binary and loaded from memory, without the `std`
+// feature:
+//
+//     static SETUP: &[u8] = include_bytes!("trusted_setup.bin");
+//     let settings = FsKZGSettings::from_bytes(SETUP, Compression::Uncompressed)?;
+//
+// The bytes are in the format of `setup_file`, as written by `save_secrets_to_file` or
+// `kzg-cli convert`. The header does not record the G1 encoding, the caller passes it along.
+// Uncompressed G1 points take twice the space but skip a square root per point on loading.
+
+/// Size of the header preceding the points
+pub const SETUP_FILE_HEADER_SIZE: usize = ENVELOPE_HEADER_SIZE + 16;
+
+/// Number of G1 and G2 points announced by the setup file header `header`
+pub fn parse_setup_header(header: &[u8]) -> Result<(usize, usize), KzgError> {
+    if header.len() < SETUP_FILE_HEADER_SIZE {
+        return Err(KzgError::InvalidLength(String::from(
+            "Setup file is too short",
+        )));
+    }
+    if header[0] != ENVELOPE_VERSION {
+        return Err(KzgError::SetupMismatch(format!(
+            "Unsupported setup file version {}",
+            header[0]
+        )));
+    }
+    if CurveId::from_u8(header[1]).map_err(KzgError::SetupMismatch)? != CurveId::Bls12_381 {
+        return Err(KzgError::SetupMismatch(String::from(
+            "Setup file does not hold BLS12-381 points",
+        )));
+    }
+    let count = |bytes: &[u8]| {
+        let mut be = [0u8; 8];
+        be.copy_from_slice(bytes);
+        usize::try_from(u64::from_be_bytes(be))
+            .map_err(|_| KzgError::OutOfMemory(String::from("Point count does not fit in memory")))
+    };
+
+    Ok((
+        count(&header[ENVELOPE_HEADER_SIZE..ENVELOPE_HEADER_SIZE + 8])?,
+        count(&header[ENVELOPE_HEADER_SIZE + 8..SETUP_FILE_HEADER_SIZE])?,
+    ))
+}
+
+/// Load the setup points of a setup file held in `bytes`, whose G1 points are encoded with
+/// `compression`
+#[allow(clippy::type_complexity)]
+pub fn load_setup_from_bytes<TG1: G1, TG2: G2>(
+    bytes: &[u8],
+    compression: Compression,
+) -> Result<(Vec<TG1>, Vec<TG2>), KzgError> {
+    let too_large = || KzgError::OutOfMemory(String::from("Point count does not fit in memory"));
+    let (num_g1, num_g2) = parse_setup_header(bytes)?;
+    let g1_len = num_g1
+        .checked_mul(compression.g1_size())
+        .ok_or_else(too_large)?;
+    let g2_len = num_g2.checked_mul(BYTES_PER_G2).ok_or_else(too_large)?;
+    let points = &bytes[SETUP_FILE_HEADER_SIZE..];
+    if g1_len.checked_add(g2_len) != Some(points.len()) {
+        return Err(KzgError::InvalidLength(format!(
+            "Setup file announces {} G1 and {} G2 points, but holds {} bytes of points",
+            num_g1,
+            num_g2,
+            points.len()
+        )));
+    }
+
+    let (g1_bytes, g2_bytes) = points.split_at(g1_len);
+    let g1 =
+        TG1::batch_from_bytes(g1_bytes, compression).map_err(|e| e.at_offset("G1 point", 0))?;
+    let g2 = TG2::batch_from_bytes(g2_bytes).map_err(|e| e.at_offset("G2 point", 0))?;
+
+    Ok((g1, g2))
+}
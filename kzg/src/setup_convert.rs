@@ -0,0 +1,481 @@
+extern crate alloc;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write as _;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+
+use crate::eip_4844::{
+    load_trusted_setup_json_string, BYTES_PER_G1, BYTES_PER_G1_UNCOMPRESSED, BYTES_PER_G2,
+    TRUSTED_SETUP_NUM_G2_POINTS,
+};
+use crate::io_utils::save_secrets_to_writer_with_compression;
+use crate::setup_file::{io_error, read_trusted_setup_with_compression};
+use crate::validation::ValidationLevel;
+use crate::{Compression, FFTSettings, Fr, FFTG1, G1, G2};
+
+////////////////////////////// Trusted setup conversion //////////////////////////////
+//
+// Trusted setups circulate in several formats: the setup files of `setup_file`, the text format
+// of c-kzg-4844, the JSON published by the KZG ceremony and the `.ptau` files of snarkjs and the
+// perpetual powers of tau ceremony. `convert_setup` reads any of them and writes any of them but
+// `.ptau`, which also holds Groth16 points that a KZG setup does not have.
+//
+// Setups are read into the monomial form `[s^i]₁`, `[s^i]₂`. The text and JSON formats hold the
+// G1 points in Lagrange form over the roots of unity, in natural order, and are converted with a
+// G1 FFT, so they require a power-of-two number of G1 points. A `.ptau` file of power `k` yields
+// its first `2^k` G1 points and at most `TRUSTED_SETUP_NUM_G2_POINTS` G2 points.
+
+/// Trusted setup file format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetupFormat {
+    /// Setup file of `setup_file`, with G1 points encoded with the given compression
+    Binary(Compression),
+    /// Text format of c-kzg-4844, e.g. `trusted_setup.txt`
+    Text,
+    /// JSON published by the KZG ceremony, e.g. `trusted_setup_4096.json`
+    Json,
+    /// Powers of tau file of snarkjs, read only
+    Ptau,
+}
+
+impl SetupFormat {
+    /// Format named `binary`, `binary-uncompressed`, `text`, `json` or `ptau`
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        match name {
+            "binary" => Ok(Self::Binary(Compression::Compressed)),
+            "binary-uncompressed" => Ok(Self::Binary(Compression::Uncompressed)),
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "ptau" => Ok(Self::Ptau),
+            _ => Err(format!("Unknown setup format {}", name)),
+        }
+    }
+}
+
+/// Convert the setup at `input` in format `from` into the file at `output` in format `to`
+pub fn convert_setup<TFr, TG1, TG2, TFFTSettings>(
+    input: &str,
+    output: &str,
+    from: SetupFormat,
+    to: SetupFormat,
+) -> Result<(), String>
+where
+    TFr: Fr,
+    TG1: G1,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr> + FFTG1<TG1>,
+{
+    if to == SetupFormat::Ptau {
+        return Err(String::from("Writing .ptau files is not supported"));
+    }
+
+    let file = File::open(input).map_err(io_error)?;
+    let (g1, g2) = read_setup::<TFr, TG1, TG2, TFFTSettings, _>(&mut BufReader::new(file), from)?;
+
+    let file = File::create(output).map_err(io_error)?;
+    write_setup::<TFr, TG1, TG2, TFFTSettings, _>(&mut BufWriter::new(file), &g1, &g2, to)
+}
+
+/// Read a setup in format `format` from `reader`, returning its monomial G1 and G2 points
+#[allow(clippy::type_complexity)]
+pub fn read_setup<TFr, TG1, TG2, TFFTSettings, R>(
+    reader: &mut R,
+    format: SetupFormat,
+) -> Result<(Vec<TG1>, Vec<TG2>), String>
+where
+    TFr: Fr,
+    TG1: G1,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr> + FFTG1<TG1>,
+    R: Read + ?Sized,
+{
+    match format {
+        SetupFormat::Binary(compression) => {
+            read_trusted_setup_with_compression(reader, compression, ValidationLevel::None)
+                .map_err(String::from)
+        }
+        SetupFormat::Text | SetupFormat::Json => {
+            let mut contents = String::new();
+            reader.read_to_string(&mut contents).map_err(io_error)?;
+            let (g1_bytes, g2_bytes) = if format == SetupFormat::Text {
+                parse_text_setup(&contents)?
+            } else {
+                load_trusted_setup_json_string(&contents)?
+            };
+            let lagrange = TG1::batch_from_bytes(&g1_bytes, Compression::Compressed)?;
+            let g2 = TG2::batch_from_bytes(&g2_bytes)?;
+
+            Ok((
+                fft_settings::<TFr, TFFTSettings>(lagrange.len())?.fft_g1(&lagrange, false)?,
+                g2,
+            ))
+        }
+        SetupFormat::Ptau => read_ptau(reader),
+    }
+}
+
+/// Write the monomial points `g1` and `g2` to `writer` in format `format`
+pub fn write_setup<TFr, TG1, TG2, TFFTSettings, W>(
+    writer: &mut W,
+    g1: &[TG1],
+    g2: &[TG2],
+    format: SetupFormat,
+) -> Result<(), String>
+where
+    TFr: Fr,
+    TG1: G1,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr> + FFTG1<TG1>,
+    W: Write + ?Sized,
+{
+    let lagrange = || -> Result<Vec<u8>, String> {
+        let points = fft_settings::<TFr, TFFTSettings>(g1.len())?.fft_g1(g1, true)?;
+        TG1::batch_to_bytes(&points, Compression::Compressed)
+    };
+
+    let contents = match format {
+        SetupFormat::Binary(compression) => {
+            return save_secrets_to_writer_with_compression(writer, g1, g2, compression)
+                .map_err(String::from)
+        }
+        SetupFormat::Text => {
+            let mut text = format!("{}\n{}\n", g1.len(), g2.len());
+            for point in lagrange()?.chunks(BYTES_PER_G1) {
+                push_hex(&mut text, point);
+                text.push('\n');
+            }
+            for point in TG2::batch_to_bytes(g2).chunks(BYTES_PER_G2) {
+                push_hex(&mut text, point);
+                text.push('\n');
+            }
+            text
+        }
+        SetupFormat::Json => {
+            // Laid out like the ceremony output
+            let monomial = TG1::batch_to_bytes(g1, Compression::Compressed)?;
+            let mut json = String::from("{\n");
+            json_hex_array(&mut json, "g1_monomial", &monomial, BYTES_PER_G1, ",");
+            json_hex_array(&mut json, "g1_lagrange", &lagrange()?, BYTES_PER_G1, ",");
+            json_hex_array(
+                &mut json,
+                "g2_monomial",
+                &TG2::batch_to_bytes(g2),
+                BYTES_PER_G2,
+                "",
+            );
+            json.push_str("}\n");
+            json
+        }
+        SetupFormat::Ptau => return Err(String::from("Writing .ptau files is not supported")),
+    };
+
+    writer.write_all(contents.as_bytes()).map_err(io_error)?;
+    writer.flush().map_err(io_error)
+}
+
+fn fft_settings<TFr: Fr, TFFTSettings: FFTSettings<TFr>>(
+    npoints: usize,
+) -> Result<TFFTSettings, String> {
+    if !npoints.is_power_of_two() {
+        return Err(format!(
+            "Lagrange form requires a power-of-two number of G1 points, got {}",
+            npoints
+        ));
+    }
+
+    TFFTSettings::new(npoints.trailing_zeros() as usize)
+}
+
+fn push_hex(out: &mut String, bytes: &[u8]) {
+    for byte in bytes {
+        let _ = write!(out, "{:02x}", byte);
+    }
+}
+
+fn json_hex_array(out: &mut String, key: &str, bytes: &[u8], size: usize, separator: &str) {
+    let _ = writeln!(out, "  \"{}\": [", key);
+    for (i, point) in bytes.chunks(size).enumerate() {
+        if i > 0 {
+            out.push_str(",\n");
+        }
+        out.push_str("    \"0x");
+        push_hex(out, point);
+        out.push('"');
+    }
+    let _ = writeln!(out, "\n  ]{}", separator);
+}
+
+/// Text format of c-kzg-4844 with any number of points: the G1 and G2 counts, then the hex
+/// encoded G1 points in Lagrange form and the G2 points
+fn parse_text_setup(contents: &str) -> Result<(Vec<u8>, Vec<u8>), String> {
+    let invalid = || String::from("Incorrect trusted setup format");
+    let mut tokens = contents.split_whitespace();
+    let mut count = || -> Result<usize, String> {
+        tokens
+            .next()
+            .and_then(|token| token.parse().ok())
+            .ok_or_else(invalid)
+    };
+    let num_g1 = count()?;
+    let num_g2 = count()?;
+
+    let mut bytes = Vec::with_capacity(num_g1 * BYTES_PER_G1 + num_g2 * BYTES_PER_G2);
+    for token in tokens {
+        if token.len() % 2 != 0 {
+            return Err(invalid());
+        }
+        for pair in token.as_bytes().chunks(2) {
+            let byte = core::str::from_utf8(pair)
+                .ok()
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                .ok_or_else(invalid)?;
+            bytes.push(byte);
+        }
+    }
+    if bytes.len() != num_g1 * BYTES_PER_G1 + num_g2 * BYTES_PER_G2 {
+        return Err(String::from(
+            "Trusted setup does not hold the announced number of points",
+        ));
+    }
+    let g2_bytes = bytes.split_off(num_g1 * BYTES_PER_G1);
+
+    Ok((bytes, g2_bytes))
+}
+
+////////////////////////////// .ptau files //////////////////////////////
+//
+// A `.ptau` file is made of sections, each behind its id (u32 LE) and size (u64 LE):
+//
+//     "ptau" || version (u32 LE) || section count (u32 LE) || sections ..
+//
+// The header section holds the size of a field element, the modulus and the power `k`, the tauG1
+// section the `2^(k+1) - 1` points `[τ^i]₁` as `x || y`, and the tauG2 section the `2^k` points
+// `[τ^i]₂` as `x.c0 || x.c1 || y.c0 || y.c1`. Coordinates are little-endian and in Montgomery
+// form with `R = 2^384`. Sections are read in order, those after tauG2 are skipped.
+
+const PTAU_SECTION_HEADER: u32 = 1;
+const PTAU_SECTION_TAU_G1: u32 = 2;
+const PTAU_SECTION_TAU_G2: u32 = 3;
+
+/// Size of a coordinate in a `.ptau` file
+const PTAU_FP_SIZE: usize = 48;
+
+/// Limbs of the BLS12-381 base field modulus, least significant first
+const P: [u64; 6] = [
+    0xb9fe_ffff_ffff_aaab,
+    0x1eab_fffe_b153_ffff,
+    0x6730_d2a0_f6b0_f624,
+    0x6477_4b84_f385_12bf,
+    0x4b1b_a7b6_434b_acd7,
+    0x1a01_11ea_397f_e69a,
+];
+
+/// `-p^-1 mod 2^64`
+const P_INV: u64 = 0x89f3_fffc_fffc_fffd;
+
+type Limbs = [u64; 6];
+
+fn limbs_from_le(bytes: &[u8]) -> Limbs {
+    let mut limbs = [0u64; 6];
+    for (limb, chunk) in limbs.iter_mut().zip(bytes.chunks(8)) {
+        let mut le = [0u8; 8];
+        le.copy_from_slice(chunk);
+        *limb = u64::from_le_bytes(le);
+    }
+    limbs
+}
+
+fn limbs_to_be(limbs: &Limbs) -> [u8; PTAU_FP_SIZE] {
+    let mut bytes = [0u8; PTAU_FP_SIZE];
+    for (chunk, limb) in bytes.chunks_mut(8).zip(limbs.iter().rev()) {
+        chunk.copy_from_slice(&limb.to_be_bytes());
+    }
+    bytes
+}
+
+fn less_than(a: &Limbs, b: &Limbs) -> bool {
+    a.iter().rev().cmp(b.iter().rev()) == core::cmp::Ordering::Less
+}
+
+fn sub(a: &Limbs, b: &Limbs) -> Limbs {
+    let mut out = [0u64; 6];
+    let mut borrow = false;
+    for i in 0..6 {
+        let (diff, b1) = a[i].overflowing_sub(b[i]);
+        let (diff, b2) = diff.overflowing_sub(borrow as u64);
+        out[i] = diff;
+        borrow = b1 || b2;
+    }
+    out
+}
+
+/// Canonical value of the little-endian Montgomery coordinate `bytes`, i.e. `a R^-1 mod p`
+fn from_montgomery(bytes: &[u8]) -> Result<Limbs, String> {
+    let a = limbs_from_le(bytes);
+    if !less_than(&a, &P) {
+        return Err(String::from("Coordinate is not reduced modulo p"));
+    }
+
+    // Montgomery reduction of `a`
+    let mut t = [0u64; 12];
+    t[..6].copy_from_slice(&a);
+    for i in 0..6 {
+        let m = t[i].wrapping_mul(P_INV);
+        let mut carry = 0u128;
+        for j in 0..6 {
+            let v = t[i + j] as u128 + m as u128 * P[j] as u128 + carry;
+            t[i + j] = v as u64;
+            carry = v >> 64;
+        }
+        for limb in &mut t[i + 6..] {
+            let v = *limb as u128 + carry;
+            *limb = v as u64;
+            carry = v >> 64;
+        }
+    }
+    let mut out = [0u64; 6];
+    out.copy_from_slice(&t[6..]);
+    if !less_than(&out, &P) {
+        out = sub(&out, &P);
+    }
+
+    Ok(out)
+}
+
+/// Whether `y` is larger than `p - y`, the sign of compressed points
+fn is_lexicographically_largest(y: &Limbs) -> bool {
+    *y != [0u64; 6] && less_than(&sub(&P, y), y)
+}
+
+fn ptau_g1<TG1: G1>(bytes: &[u8]) -> Result<TG1, String> {
+    let x = from_montgomery(&bytes[..PTAU_FP_SIZE])?;
+    let y = from_montgomery(&bytes[PTAU_FP_SIZE..])?;
+
+    let mut encoded = [0u8; BYTES_PER_G1_UNCOMPRESSED];
+    if x == [0u64; 6] && y == [0u64; 6] {
+        encoded[0] = 0x40;
+    } else {
+        encoded[..PTAU_FP_SIZE].copy_from_slice(&limbs_to_be(&x));
+        encoded[PTAU_FP_SIZE..].copy_from_slice(&limbs_to_be(&y));
+    }
+
+    TG1::from_bytes_uncompressed(&encoded)
+}
+
+fn ptau_g2<TG2: G2>(bytes: &[u8]) -> Result<TG2, String> {
+    let coordinate = |i: usize| from_montgomery(&bytes[i * PTAU_FP_SIZE..(i + 1) * PTAU_FP_SIZE]);
+    let (x0, x1, y0, y1) = (
+        coordinate(0)?,
+        coordinate(1)?,
+        coordinate(2)?,
+        coordinate(3)?,
+    );
+
+    // Compressed as `x.c1 || x.c0` with flag bits, the sign being the one of `y.c1`, or of `y.c0`
+    // when `y.c1` is zero
+    let mut encoded = [0u8; BYTES_PER_G2];
+    if [x0, x1, y0, y1].iter().all(|c| *c == [0u64; 6]) {
+        encoded[0] = 0xc0;
+    } else {
+        encoded[..PTAU_FP_SIZE].copy_from_slice(&limbs_to_be(&x1));
+        encoded[PTAU_FP_SIZE..].copy_from_slice(&limbs_to_be(&x0));
+        let sign = if y1 != [0u64; 6] {
+            is_lexicographically_largest(&y1)
+        } else {
+            is_lexicographically_largest(&y0)
+        };
+        encoded[0] |= if sign { 0xa0 } else { 0x80 };
+    }
+
+    TG2::from_bytes(&encoded)
+}
+
+fn read_u32<R: Read + ?Sized>(reader: &mut R) -> Result<u32, String> {
+    let mut bytes = [0u8; 4];
+    reader.read_exact(&mut bytes).map_err(io_error)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64<R: Read + ?Sized>(reader: &mut R) -> Result<u64, String> {
+    let mut bytes = [0u8; 8];
+    reader.read_exact(&mut bytes).map_err(io_error)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+/// Read the points of a `.ptau` file, see the format above
+#[allow(clippy::type_complexity)]
+fn read_ptau<TG1: G1, TG2: G2, R: Read + ?Sized>(
+    reader: &mut R,
+) -> Result<(Vec<TG1>, Vec<TG2>), String> {
+    let mut magic = [0u8; 4];
+    reader.read_exact(&mut magic).map_err(io_error)?;
+    if &magic != b"ptau" {
+        return Err(String::from("Not a .ptau file"));
+    }
+    let _version = read_u32(reader)?;
+    let num_sections = read_u32(reader)?;
+
+    let mut power = None;
+    let mut g1 = Vec::new();
+    let mut g2 = Vec::new();
+    for _ in 0..num_sections {
+        let id = read_u32(reader)?;
+        let size = read_u64(reader)?;
+        let mut section = (&mut *reader).take(size);
+
+        match id {
+            PTAU_SECTION_HEADER => {
+                let n8 = read_u32(&mut section)? as usize;
+                let mut q = [0u8; PTAU_FP_SIZE];
+                if n8 != PTAU_FP_SIZE {
+                    return Err(String::from(".ptau file is not over BLS12-381"));
+                }
+                section.read_exact(&mut q).map_err(io_error)?;
+                if limbs_from_le(&q) != P {
+                    return Err(String::from(".ptau file is not over BLS12-381"));
+                }
+                let k = read_u32(&mut section)?;
+                if k >= usize::BITS - 1 {
+                    return Err(format!("Invalid .ptau power {}", k));
+                }
+                power = Some(k);
+            }
+            PTAU_SECTION_TAU_G1 | PTAU_SECTION_TAU_G2 => {
+                let k = power.ok_or_else(|| String::from(".ptau points precede the header"))?;
+                let (point_size, count) = if id == PTAU_SECTION_TAU_G1 {
+                    (2 * PTAU_FP_SIZE, 1usize << k)
+                } else {
+                    (
+                        4 * PTAU_FP_SIZE,
+                        (1usize << k).min(TRUSTED_SETUP_NUM_G2_POINTS),
+                    )
+                };
+                if size < (count * point_size) as u64 {
+                    return Err(format!("Section {} of the .ptau file is too short", id));
+                }
+
+                let mut bytes = alloc::vec![0u8; point_size];
+                for i in 0..count {
+                    section.read_exact(&mut bytes).map_err(io_error)?;
+                    if id == PTAU_SECTION_TAU_G1 {
+                        g1.push(ptau_g1(&bytes).map_err(|e| format!("tauG1 point {}: {}", i, e))?);
+                    } else {
+                        g2.push(ptau_g2(&bytes).map_err(|e| format!("tauG2 point {}: {}", i, e))?);
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        // Rest of the section
+        std::io::copy(&mut section, &mut std::io::sink()).map_err(io_error)?;
+        if !g1.is_empty() && !g2.is_empty() {
+            return Ok((g1, g2));
+        }
+    }
+
+    Err(String::from(".ptau file has no tauG1 or tauG2 section"))
+}
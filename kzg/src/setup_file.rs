@@ -0,0 +1,186 @@
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+
+use crate::eip_4844::BYTES_PER_G2;
+use crate::envelope::{CurveId, ENVELOPE_VERSION};
+use crate::error::KzgError;
+use crate::file_options::read_file;
+use crate::secret::SecretScalar;
+use crate::setup_bytes::parse_setup_header;
+pub use crate::setup_bytes::SETUP_FILE_HEADER_SIZE;
+use crate::validation::{validate_g1, validate_g2, ValidationLevel};
+use crate::{Compression, Fr, G1Mul, G2Mul, G1, G2};
+
+////////////////////////////// Streamed trusted setup files //////////////////////////////
+//
+// `generate_trusted_setup` keeps both point vectors in memory, which does not scale to setups of
+// 2^24 points and more. The functions below write every point as soon as it is computed, so
+// memory use does not depend on the setup size. The file holds the monomial form
+//
+//     version || curve id || g1 count (u64 BE) || g2 count (u64 BE) || [s^i]₁ .. || [s^i]₂ ..
+//
+// with compressed points, behind the same header as the envelope encoding. G1 points are encoded
+// and decoded in chunks of `SETUP_FILE_CHUNK_SIZE` with the batch helpers of the `G1` and `G2`
+// traits.
+// Files with uncompressed G1 points, which load faster, are read with
+// `read_trusted_setup_with_compression`, and files held in memory with
+// `setup_bytes::load_setup_from_bytes`.
+
+/// Number of G1 points encoded or decoded at once
+pub const SETUP_FILE_CHUNK_SIZE: usize = 1 << 12;
+
+/// IO error message of the modules failing with a `String`
+pub(crate) fn io_error(e: std::io::Error) -> String {
+    KzgError::from(e).into()
+}
+
+pub(crate) fn write_header<W: Write + ?Sized>(
+    writer: &mut W,
+    num_g1: usize,
+    num_g2: usize,
+) -> Result<(), KzgError> {
+    writer.write_all(&[ENVELOPE_VERSION, CurveId::Bls12_381 as u8])?;
+    writer.write_all(&(num_g1 as u64).to_be_bytes())?;
+    writer.write_all(&(num_g2 as u64).to_be_bytes())?;
+
+    Ok(())
+}
+
+/// Write the insecure setup of `generate_trusted_setup` for `secret` to `writer`, one point at a
+/// time
+pub fn write_trusted_setup<
+    TFr: Fr + Copy,
+    TG1: G1 + G1Mul<TFr>,
+    TG2: G2 + G2Mul<TFr>,
+    W: Write + ?Sized,
+>(
+    writer: &mut W,
+    num_g1: usize,
+    num_g2: usize,
+    secret: [u8; 32],
+) -> Result<(), KzgError> {
+    write_header(writer, num_g1, num_g2)?;
+
+    let s = SecretScalar::<TFr>::from_seed(secret);
+
+    let mut s_pow = SecretScalar::new(TFr::one());
+    let mut remaining = num_g1;
+    while remaining > 0 {
+        let len = remaining.min(SETUP_FILE_CHUNK_SIZE);
+        let chunk = (0..len)
+            .map(|_| {
+                let point = TG1::generator().mul(s_pow.expose());
+                s_pow.mul_assign(s.expose());
+                point
+            })
+            .collect::<Vec<_>>();
+        writer.write_all(&TG1::batch_to_bytes(&chunk, Compression::Compressed)?)?;
+        remaining -= len;
+    }
+
+    let mut s_pow = SecretScalar::new(TFr::one());
+    for _ in 0..num_g2 {
+        writer.write_all(&TG2::generator().mul(s_pow.expose()).to_bytes())?;
+        s_pow.mul_assign(s.expose());
+    }
+    writer.flush()?;
+
+    Ok(())
+}
+
+/// Generate a setup directly into the file at `path`, see `write_trusted_setup`
+pub fn generate_trusted_setup_to_file<
+    TFr: Fr + Copy,
+    TG1: G1 + G1Mul<TFr>,
+    TG2: G2 + G2Mul<TFr>,
+>(
+    path: &str,
+    num_g1: usize,
+    num_g2: usize,
+    secret: [u8; 32],
+) -> Result<(), KzgError> {
+    let file = File::create(path)?;
+    let mut writer = BufWriter::new(file);
+
+    write_trusted_setup::<TFr, TG1, TG2, _>(&mut writer, num_g1, num_g2, secret)
+}
+
+/// Read a setup written by `write_trusted_setup`
+pub fn read_trusted_setup<TG1: G1, TG2: G2, R: Read + ?Sized>(
+    reader: &mut R,
+) -> Result<(Vec<TG1>, Vec<TG2>), KzgError> {
+    read_trusted_setup_with_validation(reader, ValidationLevel::None)
+}
+
+/// Same as `read_trusted_setup`, checking the points as required by `level`. G1 points are
+/// checked chunk by chunk as they are decoded
+pub fn read_trusted_setup_with_validation<TG1: G1, TG2: G2, R: Read + ?Sized>(
+    reader: &mut R,
+    level: ValidationLevel,
+) -> Result<(Vec<TG1>, Vec<TG2>), KzgError> {
+    read_trusted_setup_with_compression(reader, Compression::Compressed, level)
+}
+
+/// Same as `read_trusted_setup_with_validation`, for a file whose G1 points are encoded with
+/// `compression`. The header does not record it, and G2 points are always compressed
+pub fn read_trusted_setup_with_compression<TG1: G1, TG2: G2, R: Read + ?Sized>(
+    reader: &mut R,
+    compression: Compression,
+    level: ValidationLevel,
+) -> Result<(Vec<TG1>, Vec<TG2>), KzgError> {
+    let mut header = [0u8; SETUP_FILE_HEADER_SIZE];
+    reader.read_exact(&mut header)?;
+    let (num_g1, num_g2) = parse_setup_header(&header)?;
+
+    let mut g1 = Vec::new();
+    let mut bytes = vec![0u8; num_g1.min(SETUP_FILE_CHUNK_SIZE) * compression.g1_size()];
+    let mut remaining = num_g1;
+    while remaining > 0 {
+        let len = remaining.min(SETUP_FILE_CHUNK_SIZE);
+        let chunk = &mut bytes[..len * compression.g1_size()];
+        reader.read_exact(chunk)?;
+        let points = TG1::batch_from_bytes(chunk, compression)
+            .and_then(|points| validate_g1(&points, level).map(|_| points))
+            .map_err(|e| e.at_offset("G1 point", g1.len()))?;
+        g1.extend(points);
+        remaining -= len;
+    }
+
+    let mut g2 = Vec::new();
+    let mut bytes = vec![0u8; num_g2.min(SETUP_FILE_CHUNK_SIZE) * BYTES_PER_G2];
+    let mut remaining = num_g2;
+    while remaining > 0 {
+        let len = remaining.min(SETUP_FILE_CHUNK_SIZE);
+        let chunk = &mut bytes[..len * BYTES_PER_G2];
+        reader.read_exact(chunk)?;
+        let points = TG2::batch_from_bytes(chunk)
+            .and_then(|points| validate_g2(&points, level).map(|_| points))
+            .map_err(|e| e.at_offset("G2 point", g2.len()))?;
+        g2.extend(points);
+        remaining -= len;
+    }
+
+    Ok((g1, g2))
+}
+
+/// Read the setup file at `path`, plain or zstd compressed, see `read_trusted_setup`
+pub fn load_trusted_setup_from_file<TG1: G1, TG2: G2>(
+    path: &str,
+) -> Result<(Vec<TG1>, Vec<TG2>), KzgError> {
+    read_file(path, |reader| read_trusted_setup(reader))
+}
+
+/// Load the setup file at `path`, see `read_trusted_setup_with_validation`
+pub fn load_trusted_setup_from_file_with_validation<TG1: G1, TG2: G2>(
+    path: &str,
+    level: ValidationLevel,
+) -> Result<(Vec<TG1>, Vec<TG2>), KzgError> {
+    read_file(path, |reader| {
+        read_trusted_setup_with_validation(reader, level)
+    })
+}
@@ -0,0 +1,323 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use sha2::{Digest, Sha256};
+
+////////////////////////////// Multi-buffer SHA-256 //////////////////////////////
+//
+// Batch verification hashes one challenge input per blob, and every commitment is hashed into
+// its versioned hash. These are many independent messages of the same length, so they can be
+// hashed `SHA256_LANES` at a time: the compression function runs on arrays holding the same state
+// word of every message, which the compiler vectorizes into AVX2 registers.
+//
+// This only pays off on x86-64 CPUs with AVX2 and without the SHA extensions, where the lanes
+// hash faster than `sha2` does in software. CPUs with the SHA extensions hash a single message
+// faster than the lanes do with AVX2, and `sha2` uses the extensions when they are detected, so
+// `sha256_many` falls back to `sha2` message by message everywhere else. The `sha256_batch`
+// benchmark of kzg-bench measures every path available on the CPU it runs on.
+
+/// Number of messages hashed together
+pub const SHA256_LANES: usize = 8;
+
+type Lanes = [u32; SHA256_LANES];
+
+const H0: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+const K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+#[inline(always)]
+fn map(a: &Lanes, f: impl Fn(u32) -> u32) -> Lanes {
+    let mut out = [0u32; SHA256_LANES];
+    for i in 0..SHA256_LANES {
+        out[i] = f(a[i]);
+    }
+    out
+}
+
+#[inline(always)]
+fn zip(a: &Lanes, b: &Lanes, f: impl Fn(u32, u32) -> u32) -> Lanes {
+    let mut out = [0u32; SHA256_LANES];
+    for i in 0..SHA256_LANES {
+        out[i] = f(a[i], b[i]);
+    }
+    out
+}
+
+#[inline(always)]
+fn add(a: &Lanes, b: &Lanes) -> Lanes {
+    zip(a, b, u32::wrapping_add)
+}
+
+/// Compress one 64-byte block of every lane into `state`
+#[inline(always)]
+fn compress(state: &mut [Lanes; 8], blocks: &[&[u8]; SHA256_LANES]) {
+    let mut w = [[0u32; SHA256_LANES]; 64];
+    for (t, word) in w.iter_mut().take(16).enumerate() {
+        for (lane, block) in blocks.iter().enumerate() {
+            word[lane] = u32::from_be_bytes(block[4 * t..4 * t + 4].try_into().unwrap());
+        }
+    }
+    for t in 16..64 {
+        let s0 = map(&w[t - 15], |x| {
+            x.rotate_right(7) ^ x.rotate_right(18) ^ (x >> 3)
+        });
+        let s1 = map(&w[t - 2], |x| {
+            x.rotate_right(17) ^ x.rotate_right(19) ^ (x >> 10)
+        });
+        w[t] = add(&add(&w[t - 16], &s0), &add(&w[t - 7], &s1));
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+    for t in 0..64 {
+        let s1 = map(&e, |x| {
+            x.rotate_right(6) ^ x.rotate_right(11) ^ x.rotate_right(25)
+        });
+        let mut ch = [0u32; SHA256_LANES];
+        for i in 0..SHA256_LANES {
+            ch[i] = (e[i] & f[i]) ^ (!e[i] & g[i]);
+        }
+        let t1 = add(
+            &add(&h, &s1),
+            &add(&ch, &map(&w[t], |x| x.wrapping_add(K[t]))),
+        );
+        let s0 = map(&a, |x| {
+            x.rotate_right(2) ^ x.rotate_right(13) ^ x.rotate_right(22)
+        });
+        let mut maj = [0u32; SHA256_LANES];
+        for i in 0..SHA256_LANES {
+            maj[i] = (a[i] & b[i]) ^ (a[i] & c[i]) ^ (b[i] & c[i]);
+        }
+        let t2 = add(&s0, &maj);
+
+        h = g;
+        g = f;
+        f = e;
+        e = add(&d, &t1);
+        d = c;
+        c = b;
+        b = a;
+        a = add(&t1, &t2);
+    }
+
+    for (word, value) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+        *word = add(word, &value);
+    }
+}
+
+/// Padded last blocks of a message of `len` bytes ending with `tail`, one or two blocks long
+fn padded_tail(tail: &[u8], len: usize) -> ([u8; 128], usize) {
+    let mut padded = [0u8; 128];
+    padded[..tail.len()].copy_from_slice(tail);
+    padded[tail.len()] = 0x80;
+    let size = if tail.len() < 56 { 64 } else { 128 };
+    padded[size - 8..size].copy_from_slice(&((len as u64) * 8).to_be_bytes());
+
+    (padded, size)
+}
+
+/// Hash up to `SHA256_LANES` messages of the same length together
+#[inline(always)]
+fn hash_lanes(inputs: &[&[u8]], out: &mut Vec<[u8; 32]>) {
+    debug_assert!(!inputs.is_empty() && inputs.len() <= SHA256_LANES);
+    let len = inputs[0].len();
+    // Unused lanes hash the first message again, their digests are dropped
+    let lane = |i: usize| inputs.get(i).unwrap_or(&inputs[0]);
+
+    let mut state = H0.map(|word| [word; SHA256_LANES]);
+    let full_blocks = len / 64;
+    for block in 0..full_blocks {
+        let blocks = core::array::from_fn(|i| &lane(i)[64 * block..64 * (block + 1)]);
+        compress(&mut state, &blocks);
+    }
+
+    let tails: [([u8; 128], usize); SHA256_LANES] =
+        core::array::from_fn(|i| padded_tail(&lane(i)[64 * full_blocks..], len));
+    let tail_size = tails[0].1;
+    for offset in (0..tail_size).step_by(64) {
+        let blocks = core::array::from_fn(|i| &tails[i].0[offset..offset + 64]);
+        compress(&mut state, &blocks);
+    }
+
+    for i in 0..inputs.len() {
+        let mut digest = [0u8; 32];
+        for (word, bytes) in state.iter().zip(digest.chunks_exact_mut(4)) {
+            bytes.copy_from_slice(&word[i].to_be_bytes());
+        }
+        out.push(digest);
+    }
+}
+
+#[cfg(all(feature = "std", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+unsafe fn hash_lanes_avx2(inputs: &[&[u8]], out: &mut Vec<[u8; 32]>) {
+    hash_lanes(inputs, out)
+}
+
+/// Way `sha256_many_with` hashes its messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sha256Path {
+    /// `sha2`, one message at a time
+    Scalar,
+    /// Lanes built for the baseline features of the target
+    Lanes,
+    /// Lanes built with AVX2, on x86-64 CPUs that support it
+    Avx2,
+}
+
+impl Sha256Path {
+    /// Paths the current CPU can run
+    pub fn available() -> Vec<Sha256Path> {
+        let mut paths = Vec::from([Sha256Path::Scalar, Sha256Path::Lanes]);
+        if avx2_detected() {
+            paths.push(Sha256Path::Avx2);
+        }
+        paths
+    }
+}
+
+fn avx2_detected() -> bool {
+    #[cfg(all(feature = "std", target_arch = "x86_64"))]
+    return std::is_x86_feature_detected!("avx2");
+
+    #[cfg(not(all(feature = "std", target_arch = "x86_64")))]
+    return false;
+}
+
+/// Path `sha256_many` takes on the current CPU, see the notes above
+pub fn sha256_path() -> Sha256Path {
+    #[cfg(all(feature = "std", target_arch = "x86_64"))]
+    if avx2_detected() && !std::is_x86_feature_detected!("sha") {
+        return Sha256Path::Avx2;
+    }
+
+    Sha256Path::Scalar
+}
+
+/// Whether `sha256_many` hashes messages together, see the notes above
+pub fn multi_buffer_enabled() -> bool {
+    sha256_path() != Sha256Path::Scalar
+}
+
+/// SHA-256 digest of every message of `inputs`, in order
+pub fn sha256_many(inputs: &[&[u8]]) -> Vec<[u8; 32]> {
+    sha256_many_with(inputs, sha256_path())
+}
+
+/// SHA-256 digest of every message of `inputs`, in order, hashed the way `path` says. `Avx2`
+/// falls back to `Lanes` on CPUs without AVX2
+pub fn sha256_many_with(inputs: &[&[u8]], path: Sha256Path) -> Vec<[u8; 32]> {
+    let mut digests = Vec::with_capacity(inputs.len());
+    for group in inputs.chunks(SHA256_LANES) {
+        let same_length = group.iter().all(|input| input.len() == group[0].len());
+        if path != Sha256Path::Scalar && group.len() > 1 && same_length {
+            #[cfg(all(feature = "std", target_arch = "x86_64"))]
+            if path == Sha256Path::Avx2 && avx2_detected() {
+                // SAFETY: the CPU supports AVX2
+                unsafe { hash_lanes_avx2(group, &mut digests) };
+                continue;
+            }
+            hash_lanes(group, &mut digests);
+        } else {
+            digests.extend(
+                group
+                    .iter()
+                    .map(|input| -> [u8; 32] { Sha256::digest(input).into() }),
+            );
+        }
+    }
+
+    digests
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use sha2::{Digest, Sha256};
+
+    use crate::eip_4844::CHALLENGE_INPUT_SIZE;
+    use crate::sha256_batch::{sha256_many_with, Sha256Path, SHA256_LANES};
+
+    fn assert_matches_sha256(inputs: &[&[u8]]) {
+        for path in Sha256Path::available() {
+            let digests = sha256_many_with(inputs, path);
+            assert_eq!(digests.len(), inputs.len());
+            for (input, digest) in inputs.iter().zip(&digests) {
+                assert_eq!(
+                    digest.as_slice(),
+                    Sha256::digest(input).as_slice(),
+                    "{:?} path, {} bytes",
+                    path,
+                    input.len()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn sha256_many_must_match_sha256() {
+        let message: Vec<u8> = (0..1000u32).map(|i| (i * 31 + 7) as u8).collect();
+        for len in [0, 1, 3, 55, 56, 57, 63, 64, 65, 119, 120, 128, 500, 1000] {
+            for count in 1..=2 * SHA256_LANES + 1 {
+                // Distinct messages of the same length
+                let inputs: Vec<Vec<u8>> = (0..count)
+                    .map(|i| message.iter().map(|b| b ^ i as u8).take(len).collect())
+                    .collect();
+                let inputs: Vec<&[u8]> = inputs.iter().map(Vec::as_slice).collect();
+                assert_matches_sha256(&inputs);
+            }
+        }
+
+        // Messages of different lengths
+        let inputs: Vec<&[u8]> = (0..20).map(|i| &message[..i * 37]).collect();
+        assert_matches_sha256(&inputs);
+    }
+
+    #[test]
+    fn sha256_many_must_match_sha256_on_random_messages() {
+        // xorshift64, seeded so that failures reproduce
+        let mut state = 0x9e37_79b9_7f4a_7c15u64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+
+        for _ in 0..200 {
+            let count = (next() % (3 * SHA256_LANES as u64)) as usize + 1;
+            let len = (next() % 4096) as usize;
+            // Every other batch mixes lengths, so groups take both the lanes and `sha2`
+            let mixed = next() % 2 == 0;
+            let inputs: Vec<Vec<u8>> = (0..count)
+                .map(|_| {
+                    let len = if mixed { (next() % 4096) as usize } else { len };
+                    (0..len).map(|_| next() as u8).collect()
+                })
+                .collect();
+            let inputs: Vec<&[u8]> = inputs.iter().map(Vec::as_slice).collect();
+            assert_matches_sha256(&inputs);
+        }
+
+        // Commitments hashed into versioned hashes, and challenge inputs
+        for len in [48, CHALLENGE_INPUT_SIZE] {
+            let inputs: Vec<Vec<u8>> = (0..SHA256_LANES + 1)
+                .map(|_| (0..len).map(|_| next() as u8).collect())
+                .collect();
+            let inputs: Vec<&[u8]> = inputs.iter().map(Vec::as_slice).collect();
+            assert_matches_sha256(&inputs);
+        }
+    }
+}
@@ -0,0 +1,221 @@
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use sha2::{Digest, Sha256};
+
+use crate::eip_4844::{compute_powers, hash_to_bls_field};
+use crate::opening::verify_quotient_commitment;
+use crate::{
+    FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1Mul, G2Mul, KZGSettings, PairingVerify, Poly, G1,
+    G2,
+};
+
+////////////////////////////// Polynomials split into shards //////////////////////////////
+//
+// Provers that spread one large polynomial over several machines give each machine a contiguous
+// run of `m` coefficients, so that
+//
+//     p(x) = p_0(x) + x^m * p_1(x) + .. + x^((k-1)m) * p_{k-1}(x)
+//
+// Each shard is committed to on its own. The split is shown to be consistent with the commitment
+// to `p(x)` by opening `p(x)` and every shard at a challenge `z` derived from all commitments and
+// checking that the values agree:
+//
+//     p(z) = Σ z^(jm) * p_j(z)
+//
+// All `k + 1` openings are at the same point, so they are checked with one pairing equation on
+// their random linear combination.
+
+/// Domain separator of the shard consistency challenges
+pub const SHARD_CONSISTENCY_DOMAIN: [u8; 16] = *b"SHARDCONSISTENCY";
+
+/// Openings of a polynomial and of its shards at the consistency challenge
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShardConsistencyProof<TFr, TG1> {
+    pub value: TFr,
+    pub proof: TG1,
+    pub shard_values: Vec<TFr>,
+    pub shard_proofs: Vec<TG1>,
+}
+
+/// Number of coefficients in each of `num_shards` shards of a polynomial with `len` coefficients
+pub fn shard_len(len: usize, num_shards: usize) -> Result<usize, String> {
+    if num_shards == 0 {
+        return Err(String::from("Number of shards must be non-zero"));
+    }
+
+    Ok((len + num_shards - 1) / num_shards)
+}
+
+/// Split `p` into `num_shards` runs of `shard_len(p.len(), num_shards)` coefficients, the last one
+/// padded with zeros
+pub fn split_into_shards<TFr: Fr, TPoly: Poly<TFr>>(
+    p: &TPoly,
+    num_shards: usize,
+) -> Result<Vec<TPoly>, String> {
+    let shard_len = shard_len(p.len(), num_shards)?;
+    let coeffs = p.get_coeffs();
+
+    Ok((0..num_shards)
+        .map(|j| {
+            let mut shard = TPoly::new(shard_len);
+            let start = core::cmp::min(j * shard_len, coeffs.len());
+            let end = core::cmp::min(start + shard_len, coeffs.len());
+            for (i, coeff) in coeffs[start..end].iter().enumerate() {
+                shard.set_coeff_at(i, coeff);
+            }
+            shard
+        })
+        .collect())
+}
+
+/// Commit to every shard
+pub fn commit_to_shards<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    shards: &[TPoly],
+    ks: &TKZGSettings,
+) -> Result<Vec<TG1>, String> {
+    shards
+        .iter()
+        .map(|shard| ks.commit_to_poly(shard))
+        .collect()
+}
+
+/// Derive the evaluation point `z` from the commitments to the polynomial and to its shards
+pub fn shard_consistency_challenge<TFr: Fr, TG1: G1>(
+    commitment: &TG1,
+    shard_len: usize,
+    shard_commitments: &[TG1],
+) -> TFr {
+    let mut hasher = Sha256::new();
+    hasher.update(SHARD_CONSISTENCY_DOMAIN);
+    hasher.update((shard_len as u64).to_be_bytes());
+    hasher.update((shard_commitments.len() as u64).to_be_bytes());
+    hasher.update(commitment.to_bytes());
+    for shard_commitment in shard_commitments {
+        hasher.update(shard_commitment.to_bytes());
+    }
+
+    hash_to_bls_field(&hasher.finalize().into())
+}
+
+/// Open `p` and its shards at the consistency challenge
+///
+/// The shards are the ones `split_into_shards` makes for `shard_commitments.len()` shards.
+pub fn prove_shard_consistency<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    p: &TPoly,
+    commitment: &TG1,
+    shard_commitments: &[TG1],
+    ks: &TKZGSettings,
+) -> Result<ShardConsistencyProof<TFr, TG1>, String> {
+    let shards = split_into_shards(p, shard_commitments.len())?;
+    let shard_len = shard_len(p.len(), shard_commitments.len())?;
+    let z = shard_consistency_challenge(commitment, shard_len, shard_commitments);
+
+    Ok(ShardConsistencyProof {
+        value: p.eval(&z),
+        proof: ks.compute_proof_single(p, &z)?,
+        shard_values: shards.iter().map(|shard| shard.eval(&z)).collect(),
+        shard_proofs: shards
+            .iter()
+            .map(|shard| ks.compute_proof_single(shard, &z))
+            .collect::<Result<Vec<_>, _>>()?,
+    })
+}
+
+/// Check that the shards with commitments `shard_commitments`, each `shard_len` coefficients long,
+/// make up the polynomial committed to by `commitment`
+pub fn verify_shard_consistency<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + PairingVerify<TG1, TG2>,
+    TG2: G2 + G2Mul<TFr>,
+    TFFTSettings: FFTSettings<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    commitment: &TG1,
+    shard_len: usize,
+    shard_commitments: &[TG1],
+    proof: &ShardConsistencyProof<TFr, TG1>,
+    ks: &TKZGSettings,
+) -> Result<bool, String> {
+    if shard_commitments.is_empty() {
+        return Err(String::from("Number of shards must be non-zero"));
+    }
+    if proof.shard_values.len() != shard_commitments.len()
+        || proof.shard_proofs.len() != shard_commitments.len()
+    {
+        return Err(String::from(
+            "Shard commitments, values and proofs must have the same length",
+        ));
+    }
+
+    let z: TFr = shard_consistency_challenge(commitment, shard_len, shard_commitments);
+
+    // p(z) = Σ z^(jm) * p_j(z)
+    let z_m = z.pow(shard_len);
+    let mut expected = TFr::zero();
+    for value in proof.shard_values.iter().rev() {
+        expected = expected.mul(&z_m).add(value);
+    }
+    if !expected.equals(&proof.value) {
+        return Ok(false);
+    }
+
+    let mut hasher = Sha256::new();
+    hasher.update(SHARD_CONSISTENCY_DOMAIN);
+    hasher.update(z.to_bytes());
+    hasher.update(proof.value.to_bytes());
+    hasher.update(proof.proof.to_bytes());
+    for (value, shard_proof) in proof.shard_values.iter().zip(&proof.shard_proofs) {
+        hasher.update(value.to_bytes());
+        hasher.update(shard_proof.to_bytes());
+    }
+    let r = hash_to_bls_field(&hasher.finalize().into());
+    let r_powers = compute_powers(&r, shard_commitments.len() + 1);
+
+    // Σ r^i * (C_i - [y_i]₁) = (s - z) * Σ r^i * π_i, with the whole polynomial at i = 0
+    let mut combined_commitment = commitment.clone();
+    let mut combined_value = proof.value.clone();
+    let mut combined_proof = proof.proof.clone();
+    for (i, ((shard_commitment, value), shard_proof)) in shard_commitments
+        .iter()
+        .zip(&proof.shard_values)
+        .zip(&proof.shard_proofs)
+        .enumerate()
+    {
+        let r_power = &r_powers[i + 1];
+        combined_commitment = combined_commitment.add_or_dbl(&shard_commitment.mul(r_power));
+        combined_value = combined_value.add(&value.mul(r_power));
+        combined_proof = combined_proof.add_or_dbl(&shard_proof.mul(r_power));
+    }
+
+    verify_quotient_commitment(
+        &combined_commitment,
+        &combined_proof,
+        &z,
+        &combined_value,
+        ks,
+    )
+}
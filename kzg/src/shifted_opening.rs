@@ -0,0 +1,178 @@
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use sha2::{Digest, Sha256};
+
+use crate::dev_setup::guard_insecure_dev_setup;
+use crate::eip_4844::{compute_powers, hash_to_bls_field};
+use crate::opening::commitment_minus_value;
+use crate::{
+    FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1Mul, KZGSettings, PairingVerify, Poly, G1, G2,
+};
+
+////////////////////////////// Openings at z and ω·z //////////////////////////////
+//
+// PLONK-style protocols open the same polynomial at a challenge `z` and at the next point of the
+// evaluation domain `ω·z`, where `ω` generates the domain of size `domain_size`. The helpers below
+// look `ω` up in the FFT settings, so callers only pass `z` and the domain size.
+//
+// Both openings, or the openings of many polynomials, are checked with a single pairing equation
+//
+//     e(Σ r^i * π_i, [s]₂) = e(Σ r^i * (C_i - [y_i]₁ + z_i * π_i), [1]₂)
+//
+// where `r` is derived from all the openings.
+
+/// Domain separator of the aggregated opening challenge
+pub const SHIFTED_OPENING_BATCH_DOMAIN: [u8; 16] = *b"SHIFTEDOPENBATCH";
+
+/// Openings of one polynomial at `z` and `ω·z`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShiftedOpening<TFr, TG1> {
+    pub value: TFr,
+    pub shifted_value: TFr,
+    pub proof: TG1,
+    pub shifted_proof: TG1,
+}
+
+/// Compute `ω·z`, where `ω` generates the subgroup of size `domain_size`
+pub fn shifted_point<TFr: Fr, TFFTSettings: FFTSettings<TFr>>(
+    fs: &TFFTSettings,
+    z: &TFr,
+    domain_size: usize,
+) -> Result<TFr, String> {
+    if !domain_size.is_power_of_two() || domain_size > fs.get_max_width() {
+        return Err(String::from(
+            "Domain size must be a power of two no larger than the FFT settings",
+        ));
+    }
+
+    let omega = fs.get_expanded_roots_of_unity_at(fs.get_max_width() / domain_size);
+    Ok(z.mul(&omega))
+}
+
+/// Open `p` at `z` and `ω·z`
+pub fn prove_shifted_opening<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    p: &TPoly,
+    z: &TFr,
+    domain_size: usize,
+    ks: &TKZGSettings,
+) -> Result<ShiftedOpening<TFr, TG1>, String> {
+    let shifted_z = shifted_point(ks.get_fft_settings(), z, domain_size)?;
+
+    Ok(ShiftedOpening {
+        value: p.eval(z),
+        shifted_value: p.eval(&shifted_z),
+        proof: ks.compute_proof_single(p, z)?,
+        shifted_proof: ks.compute_proof_single(p, &shifted_z)?,
+    })
+}
+
+/// Check the openings of `commitment` at `z` and `ω·z` with a single pairing check
+pub fn verify_shifted_opening<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + PairingVerify<TG1, TG2>,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    commitment: &TG1,
+    z: &TFr,
+    domain_size: usize,
+    opening: &ShiftedOpening<TFr, TG1>,
+    ks: &TKZGSettings,
+) -> Result<bool, String> {
+    verify_shifted_openings_batch(
+        core::slice::from_ref(commitment),
+        core::slice::from_ref(z),
+        domain_size,
+        core::slice::from_ref(opening),
+        ks,
+    )
+}
+
+/// Check the openings of many commitments, each at its own `z` and `ω·z`, with a single pairing
+/// check
+pub fn verify_shifted_openings_batch<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp> + PairingVerify<TG1, TG2>,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    commitments: &[TG1],
+    zs: &[TFr],
+    domain_size: usize,
+    openings: &[ShiftedOpening<TFr, TG1>],
+    ks: &TKZGSettings,
+) -> Result<bool, String> {
+    if commitments.len() != zs.len() || commitments.len() != openings.len() {
+        return Err(String::from(
+            "Commitments, points and openings must have the same length",
+        ));
+    }
+    if commitments.is_empty() {
+        return Ok(true);
+    }
+    let s_g2 = ks
+        .get_g2_secret()
+        .get(1)
+        .ok_or_else(|| String::from("Trusted setup must contain at least two G2 points"))?;
+    guard_insecure_dev_setup(ks.get_g2_secret());
+
+    // Every commitment is opened twice, once at z and once at ω·z
+    let mut points = Vec::with_capacity(2 * zs.len());
+    for z in zs {
+        points.push(z.clone());
+        points.push(shifted_point(ks.get_fft_settings(), z, domain_size)?);
+    }
+
+    let mut hasher = Sha256::new();
+    hasher.update(SHIFTED_OPENING_BATCH_DOMAIN);
+    hasher.update((domain_size as u64).to_be_bytes());
+    hasher.update((commitments.len() as u64).to_be_bytes());
+    for ((commitment, z), opening) in commitments.iter().zip(zs).zip(openings) {
+        hasher.update(commitment.to_bytes());
+        hasher.update(z.to_bytes());
+        hasher.update(opening.value.to_bytes());
+        hasher.update(opening.shifted_value.to_bytes());
+        hasher.update(opening.proof.to_bytes());
+        hasher.update(opening.shifted_proof.to_bytes());
+    }
+    let r = hash_to_bls_field(&hasher.finalize().into());
+    let r_powers = compute_powers(&r, points.len());
+
+    let mut proof_lincomb = TG1::identity();
+    let mut rhs = TG1::identity();
+    for (i, (commitment, opening)) in commitments.iter().zip(openings).enumerate() {
+        let pairs = [
+            (&opening.value, &opening.proof),
+            (&opening.shifted_value, &opening.shifted_proof),
+        ];
+        for (j, (value, proof)) in pairs.into_iter().enumerate() {
+            let r_power = &r_powers[2 * i + j];
+            proof_lincomb = proof_lincomb.add_or_dbl(&proof.mul(r_power));
+            let term = commitment_minus_value(commitment, value)
+                .add_or_dbl(&proof.mul(&points[2 * i + j]));
+            rhs = rhs.add_or_dbl(&term.mul(r_power));
+        }
+    }
+
+    Ok(TG1::verify(&proof_lincomb, s_g2, &rhs, &TG2::generator()))
+}
@@ -0,0 +1,121 @@
+extern crate alloc;
+
+use crate::error::KzgError;
+use crate::{G1Affine, G1Fp, G1, G2};
+
+////////////////////////////// Point validation //////////////////////////////
+//
+// Setup and table files are trusted by default: compressed setup points are only checked to be
+// on the curve while they are decoded, and table points are read back as raw affine coordinates.
+// Operators loading setups or tables produced by somebody else can ask for every point to be
+// checked instead, since a point outside of the subgroup of order `r` breaks the soundness of
+// the proofs. The checks run in parallel with the `parallel` feature.
+//
+// The subgroup check of G1 points is `G1::is_valid` of the backend, and the one of G2 points is
+// `G2::is_valid`. G2 points are decoded with `G2::batch_from_bytes` and checked in parallel like
+// G1 points: a G2 decompression or subgroup check costs several times the G1 one.
+
+/// Checks applied to the points of a setup or table file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ValidationLevel {
+    /// No checks beyond the ones of decoding
+    #[default]
+    None,
+    /// Every point is on the curve
+    OnCurve,
+    /// Every point is on the curve and in the subgroup of order `r`
+    Subgroup,
+}
+
+/// Whether the affine point is the point at infinity or satisfies `y^2 = x^3 + 4`
+pub fn g1_affine_on_curve<TG1: G1, TG1Fp: G1Fp, TG1Affine: G1Affine<TG1, TG1Fp>>(
+    point: &TG1Affine,
+) -> bool {
+    if point.is_infinity() {
+        return true;
+    }
+    let b = TG1Fp::ONE.double().double();
+    let x = point.x();
+    let rhs = x.square().mul_fp(x).add_fp(&b);
+
+    point.y().square() == rhs
+}
+
+fn check_points<T: Sync>(
+    points: &[T],
+    check: impl Fn(&T) -> Result<(), &'static str> + Sync,
+) -> Result<(), KzgError> {
+    #[cfg(feature = "parallel")]
+    let failed = {
+        use rayon::prelude::*;
+
+        points
+            .par_iter()
+            .enumerate()
+            .find_map_first(|(i, point)| check(point).err().map(|e| (i, e)))
+    };
+
+    #[cfg(not(feature = "parallel"))]
+    let failed = points
+        .iter()
+        .enumerate()
+        .find_map(|(i, point)| check(point).err().map(|e| (i, e)));
+
+    match failed {
+        Some((index, reason)) => Err(KzgError::InvalidPoint {
+            what: "Point",
+            index,
+            reason,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Check affine G1 points read as raw coordinates, e.g. from a table file
+pub fn validate_g1_affines<TG1: G1, TG1Fp: G1Fp, TG1Affine: G1Affine<TG1, TG1Fp>>(
+    points: &[TG1Affine],
+    level: ValidationLevel,
+) -> Result<(), KzgError> {
+    if level == ValidationLevel::None {
+        return Ok(());
+    }
+
+    check_points(points, |point| {
+        if !g1_affine_on_curve::<TG1, TG1Fp, TG1Affine>(point) {
+            return Err("is not on the curve");
+        }
+        if level == ValidationLevel::Subgroup && !point.is_infinity() && !point.to_proj().is_valid()
+        {
+            return Err("is not in the G1 subgroup");
+        }
+        Ok(())
+    })
+}
+
+/// Check decoded G1 points, which decoding already found on the curve
+pub fn validate_g1<TG1: G1>(points: &[TG1], level: ValidationLevel) -> Result<(), KzgError> {
+    if level != ValidationLevel::Subgroup {
+        return Ok(());
+    }
+
+    check_points(points, |point| {
+        if !point.is_inf() && !point.is_valid() {
+            return Err("is not in the G1 subgroup");
+        }
+        Ok(())
+    })
+}
+
+/// Check decoded G2 points, which decoding already found on the curve
+pub fn validate_g2<TG2: G2>(points: &[TG2], level: ValidationLevel) -> Result<(), KzgError> {
+    if level != ValidationLevel::Subgroup {
+        return Ok(());
+    }
+
+    check_points(points, |point| {
+        if !point.is_valid() {
+            return Err("is not in the G2 subgroup");
+        }
+        Ok(())
+    })
+}
@@ -0,0 +1,187 @@
+extern crate alloc;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender, TrySendError};
+use std::thread::{self, JoinHandle};
+
+use crate::eip_4844::{verify_blob_kzg_proof_batch_rust, verify_blob_kzg_proof_rust};
+use crate::nonblocking::{job_channel, JobCompleter, JobHandle};
+use crate::{
+    FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1LinComb, G1Mul, KZGSettings, PairingVerify, Poly,
+    G1, G2,
+};
+
+////////////////////////////// Background verification queue //////////////////////////////
+//
+// Gossip validation receives blob proofs one at a time, but verifying them together is much
+// cheaper: a batch costs a single aggregated pairing check. The queue collects submitted proofs on
+// a background thread and verifies whatever has accumulated, up to `max_batch_size`, in one batch.
+// If a batch fails, its proofs are verified one by one, so that every submitter gets its own
+// result.
+//
+// The queue holds at most `capacity` pending proofs. Submitting to a full queue fails immediately,
+// leaving it to the caller to drop or retry the message.
+
+struct VerificationJob<TFr, TG1> {
+    blob: Vec<TFr>,
+    commitment: TG1,
+    proof: TG1,
+    completer: JobCompleter<bool>,
+}
+
+pub struct VerificationQueue<
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+> {
+    sender: Option<SyncSender<VerificationJob<TFr, TG1>>>,
+    worker: Option<JoinHandle<()>>,
+    #[allow(clippy::type_complexity)]
+    _marker: PhantomData<fn() -> (TG2, TFFTSettings, TPoly, TKZGSettings, TG1Fp, TG1Affine)>,
+}
+
+impl<
+        TFr: Fr + Copy + Send + 'static,
+        TG1: G1
+            + G1Mul<TFr>
+            + PairingVerify<TG1, TG2>
+            + G1GetFp<TG1Fp>
+            + G1LinComb<TFr, TG1Fp, TG1Affine>
+            + Send
+            + 'static,
+        TG2: G2,
+        TFFTSettings: FFTSettings<TFr>,
+        TPoly: Poly<TFr>,
+        TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine> + Send + Sync + 'static,
+        TG1Fp: G1Fp,
+        TG1Affine: G1Affine<TG1, TG1Fp>,
+    > VerificationQueue<TFr, TG1, TG2, TFFTSettings, TPoly, TKZGSettings, TG1Fp, TG1Affine>
+{
+    /// Start a queue holding up to `capacity` pending proofs and verifying up to
+    /// `max_batch_size` of them at once
+    pub fn new(
+        ts: Arc<TKZGSettings>,
+        capacity: usize,
+        max_batch_size: usize,
+    ) -> Result<Self, String> {
+        if capacity == 0 {
+            return Err(String::from("Queue capacity must be at least 1"));
+        }
+        if max_batch_size == 0 {
+            return Err(String::from("Batch size must be at least 1"));
+        }
+
+        let (sender, receiver) = sync_channel(capacity);
+        let worker = thread::Builder::new()
+            .name(String::from("kzg-verification-queue"))
+            .spawn(move || Self::run(receiver, ts.as_ref(), max_batch_size))
+            .map_err(|e| format!("Failed to start verification thread: {}", e))?;
+
+        Ok(Self {
+            sender: Some(sender),
+            worker: Some(worker),
+            _marker: PhantomData,
+        })
+    }
+
+    /// Queue a blob proof for verification, returning a future resolving to whether it is valid.
+    /// Fails without blocking if the queue is full.
+    pub fn submit(
+        &self,
+        blob: Vec<TFr>,
+        commitment: TG1,
+        proof: TG1,
+    ) -> Result<JobHandle<bool>, String> {
+        let (handle, completer) = job_channel();
+        let job = VerificationJob {
+            blob,
+            commitment,
+            proof,
+            completer,
+        };
+
+        match self.sender.as_ref().unwrap().try_send(job) {
+            Ok(()) => Ok(handle),
+            Err(TrySendError::Full(_)) => Err(String::from("Verification queue is full")),
+            Err(TrySendError::Disconnected(_)) => {
+                Err(String::from("Verification queue has stopped"))
+            }
+        }
+    }
+
+    fn run(
+        receiver: Receiver<VerificationJob<TFr, TG1>>,
+        ts: &TKZGSettings,
+        max_batch_size: usize,
+    ) {
+        // Wait for the first proof, then take whatever else is already pending
+        while let Ok(job) = receiver.recv() {
+            let mut batch = Vec::from([job]);
+            while batch.len() < max_batch_size {
+                match receiver.try_recv() {
+                    Ok(job) => batch.push(job),
+                    Err(_) => break,
+                }
+            }
+
+            Self::verify_batch(batch, ts);
+        }
+    }
+
+    fn verify_batch(batch: Vec<VerificationJob<TFr, TG1>>, ts: &TKZGSettings) {
+        if batch.len() > 1 {
+            let blobs = batch.iter().map(|job| job.blob.clone()).collect::<Vec<_>>();
+            let commitments = batch
+                .iter()
+                .map(|job| job.commitment.clone())
+                .collect::<Vec<_>>();
+            let proofs = batch
+                .iter()
+                .map(|job| job.proof.clone())
+                .collect::<Vec<_>>();
+
+            if let Ok(true) = verify_blob_kzg_proof_batch_rust(&blobs, &commitments, &proofs, ts) {
+                for job in batch {
+                    job.completer.complete(Ok(true));
+                }
+                return;
+            }
+        }
+
+        // Find out which proofs made the batch fail
+        for job in batch {
+            let result = verify_blob_kzg_proof_rust(&job.blob, &job.commitment, &job.proof, ts);
+            job.completer.complete(result);
+        }
+    }
+}
+
+impl<
+        TFr: Fr,
+        TG1: G1 + G1Mul<TFr> + G1GetFp<TG1Fp>,
+        TG2: G2,
+        TFFTSettings: FFTSettings<TFr>,
+        TPoly: Poly<TFr>,
+        TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+        TG1Fp: G1Fp,
+        TG1Affine: G1Affine<TG1, TG1Fp>,
+    > Drop
+    for VerificationQueue<TFr, TG1, TG2, TFFTSettings, TPoly, TKZGSettings, TG1Fp, TG1Affine>
+{
+    /// Verify the proofs still pending, then stop the worker thread
+    fn drop(&mut self) {
+        drop(self.sender.take());
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use sha2::{Digest, Sha256};
+
+use crate::dev_setup::{is_insecure_dev_tau, report_insecure_dev_setup};
+use crate::eip_4844::{hash_to_bls_field, BYTES_PER_FIELD_ELEMENT, FIAT_SHAMIR_PROTOCOL_DOMAIN};
+use crate::opening::{commitment_minus_value, g2_s_minus_z};
+use crate::{
+    FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1Mul, G2Mul, KZGSettings, PairingVerify, Poly, G1,
+    G2,
+};
+
+////////////////////////////// Fixed-size verifier //////////////////////////////
+//
+// Verification-only subset of EIP-4844 for targets where the blob size is known at compile time
+// (zkVM guests, embedded devices). Everything is sized by `N`, so none of the methods allocate:
+// the challenge is hashed incrementally, and the barycentric evaluation accumulates a single
+// fraction instead of batch-inverting into a buffer. Errors are static strings for the same
+// reason.
+
+/// Verifier for blobs of exactly `N` field elements
+#[derive(Debug, Clone)]
+pub struct KzgVerifier<TFr, TG1, TG2, const N: usize> {
+    /// First `N` roots of unity of the evaluation domain, in the same order as the blob
+    roots_of_unity: [TFr; N],
+    /// `[s]₂` element of the trusted setup
+    s_g2: TG2,
+    /// Whether `s_g2` is that of the development setup, see `dev_setup`
+    insecure_dev_setup: bool,
+
+    g1_marker: core::marker::PhantomData<TG1>,
+}
+
+impl<TFr, TG1, TG2, const N: usize> KzgVerifier<TFr, TG1, TG2, N>
+where
+    TFr: Fr,
+    TG1: G1 + G1Mul<TFr> + PairingVerify<TG1, TG2>,
+    TG2: G2 + G2Mul<TFr>,
+{
+    pub fn new(roots_of_unity: [TFr; N], s_g2: TG2) -> Result<Self, &'static str> {
+        if N == 0 || !N.is_power_of_two() {
+            return Err("Blob size must be a power of two");
+        }
+
+        Ok(Self {
+            roots_of_unity,
+            insecure_dev_setup: is_insecure_dev_tau(&s_g2),
+            s_g2,
+            g1_marker: core::marker::PhantomData,
+        })
+    }
+
+    /// Copy the parts of the settings needed for verification
+    pub fn from_settings<
+        TFFTSettings: FFTSettings<TFr>,
+        TPoly: Poly<TFr>,
+        TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+        TG1Fp: G1Fp,
+        TG1Affine: G1Affine<TG1, TG1Fp>,
+    >(
+        s: &TKZGSettings,
+    ) -> Result<Self, &'static str>
+    where
+        TG1: G1GetFp<TG1Fp>,
+    {
+        let roots_of_unity = s.get_fft_settings().get_roots_of_unity();
+        if roots_of_unity.len() < N {
+            return Err("Not enough roots of unity in FFT settings");
+        }
+        let s_g2 = s
+            .get_g2_secret()
+            .get(1)
+            .ok_or("Trusted setup must contain at least two G2 points")?;
+
+        Self::new(
+            core::array::from_fn(|i| roots_of_unity[i].clone()),
+            s_g2.clone(),
+        )
+    }
+
+    /// Check that `proof` opens `commitment` to `y` at `z`
+    pub fn verify_kzg_proof(
+        &self,
+        commitment: &TG1,
+        z: &TFr,
+        y: &TFr,
+        proof: &TG1,
+    ) -> Result<bool, &'static str> {
+        if !commitment.is_inf() && !commitment.is_valid() {
+            return Err("Invalid commitment");
+        }
+        if !proof.is_inf() && !proof.is_valid() {
+            return Err("Invalid proof");
+        }
+        if self.insecure_dev_setup {
+            report_insecure_dev_setup();
+        }
+
+        Ok(TG1::verify(
+            &commitment_minus_value(commitment, y),
+            &TG2::generator(),
+            proof,
+            &g2_s_minus_z(&self.s_g2, z),
+        ))
+    }
+
+    /// Check a blob proof, as `verify_blob_kzg_proof_rust` does for full-size blobs
+    pub fn verify_blob_kzg_proof(
+        &self,
+        blob: &[TFr; N],
+        commitment: &TG1,
+        proof: &TG1,
+    ) -> Result<bool, &'static str> {
+        if !commitment.is_inf() && !commitment.is_valid() {
+            return Err("Invalid commitment");
+        }
+
+        let z = self.compute_challenge(blob, commitment);
+        let y = self.evaluate(blob, &z)?;
+        self.verify_kzg_proof(commitment, &z, &y, proof)
+    }
+
+    /// Fiat-Shamir challenge of a blob and its commitment, with `N` as the polynomial degree
+    pub fn compute_challenge(&self, blob: &[TFr; N], commitment: &TG1) -> TFr {
+        let mut degree = [0u8; 16];
+        degree[8..].copy_from_slice(&(N as u64).to_be_bytes());
+
+        let mut hasher = Sha256::new();
+        hasher.update(FIAT_SHAMIR_PROTOCOL_DOMAIN);
+        hasher.update(degree);
+        for field in blob {
+            hasher.update(field.to_bytes());
+        }
+        hasher.update(commitment.to_bytes());
+
+        let challenge: [u8; BYTES_PER_FIELD_ELEMENT] = hasher.finalize().into();
+        hash_to_bls_field(&challenge)
+    }
+
+    /// Evaluate a blob in evaluation form at `x`
+    pub fn evaluate(&self, blob: &[TFr; N], x: &TFr) -> Result<TFr, &'static str> {
+        if let Some(i) = self.roots_of_unity.iter().position(|root| root.equals(x)) {
+            return Ok(blob[i].clone());
+        }
+
+        // sum(blob_i * ω_i / (x - ω_i)) kept as a single fraction num / den
+        let mut num = TFr::zero();
+        let mut den = TFr::one();
+        for (value, root) in blob.iter().zip(self.roots_of_unity.iter()) {
+            let diff = x.sub(root);
+            num = num.mul(&diff).add(&value.mul(root).mul(&den));
+            den = den.mul(&diff);
+        }
+
+        // Multiply by (x^N - 1) / N
+        let factor = x.pow(N).sub(&TFr::one());
+        let den = den.mul(&TFr::from_u64(N as u64));
+        let out = num
+            .mul(&factor)
+            .div(&den)
+            .map_err(|_| "Evaluation point is not invertible")?;
+
+        Ok(out)
+    }
+}
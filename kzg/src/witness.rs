@@ -0,0 +1,188 @@
+extern crate alloc;
+
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::dev_setup::guard_insecure_dev_setup;
+use crate::eip_4844::{
+    blob_to_polynomial, compute_challenge_hash, fr_batch_inv, hash_to_bls_field,
+    BYTES_PER_FIELD_ELEMENT, FIELD_ELEMENTS_PER_BLOB,
+};
+use crate::opening::{commitment_minus_value, g2_s_minus_z};
+use crate::{
+    FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1Mul, G2Mul, KZGSettings, PairingVerify, Poly, G1,
+    G2,
+};
+
+////////////////////////////// Witness export for proof verification //////////////////////////////
+//
+// Circuits that re-verify a KZG proof (zkVM guests, SNARK wrappers) need the exact intermediate
+// values computed by the native verifier. The functions below run the same verification as
+// `verify_kzg_proof_rust` / `verify_blob_kzg_proof_rust` and return every intermediate value.
+
+/// Intermediate values of the pairing check `e(C - [y]₁, [1]₂) = e(π, [s - z]₂)`.
+#[derive(Debug, Clone)]
+pub struct KzgProofWitness<TFr, TG1, TG2> {
+    /// Evaluation point `z`
+    pub z: TFr,
+    /// Claimed evaluation `y = p(z)`
+    pub y: TFr,
+    /// First pairing input in G1: `C - [y]₁`
+    pub commitment_minus_y: TG1,
+    /// First pairing input in G2: `[1]₂`
+    pub g2_generator: TG2,
+    /// Second pairing input in G1: the proof `π`
+    pub proof: TG1,
+    /// Second pairing input in G2: `[s - z]₂`
+    pub s_minus_z: TG2,
+    /// Outcome of the pairing check
+    pub valid: bool,
+}
+
+/// Intermediate values of `verify_blob_kzg_proof_rust`.
+#[derive(Debug, Clone)]
+pub struct BlobKzgProofWitness<TFr, TG1, TG2> {
+    /// SHA-256 digest of the Fiat-Shamir transcript, before reduction into the field
+    pub challenge_hash: [u8; BYTES_PER_FIELD_ELEMENT],
+    /// Barycentric weights `w_i`, such that `y = sum(w_i * blob_i)`
+    pub barycentric_weights: Vec<TFr>,
+    /// Witness of the final pairing check
+    pub proof_witness: KzgProofWitness<TFr, TG1, TG2>,
+}
+
+/// Compute the barycentric weights of the evaluation domain at `z`, so that the evaluation of a
+/// polynomial in evaluation form is the inner product of the weights and its evaluations.
+pub fn compute_barycentric_weights<
+    TFr: Fr + Copy,
+    TG1: G1 + G1GetFp<TG1Fp> + G1Mul<TFr>,
+    TG2: G2,
+    TFFTSettings: FFTSettings<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    z: &TFr,
+    s: &TKZGSettings,
+) -> Result<Vec<TFr>, String> {
+    let roots_of_unity = s.get_fft_settings().get_roots_of_unity();
+    if roots_of_unity.len() < FIELD_ELEMENTS_PER_BLOB {
+        return Err(String::from("Not enough roots of unity in FFT settings"));
+    }
+
+    let mut weights = vec![TFr::zero(); FIELD_ELEMENTS_PER_BLOB];
+
+    // Inside the domain the evaluation is the value itself
+    if let Some(i) = roots_of_unity[..FIELD_ELEMENTS_PER_BLOB]
+        .iter()
+        .position(|root| root.equals(z))
+    {
+        weights[i] = TFr::one();
+        return Ok(weights);
+    }
+
+    let inverses_in = roots_of_unity[..FIELD_ELEMENTS_PER_BLOB]
+        .iter()
+        .map(|root| z.sub(root))
+        .collect::<Vec<_>>();
+    let mut inverses = vec![TFr::zero(); FIELD_ELEMENTS_PER_BLOB];
+    fr_batch_inv(&mut inverses, &inverses_in, FIELD_ELEMENTS_PER_BLOB)?;
+
+    // w_i = (z^n - 1) / n * ω_i / (z - ω_i)
+    let factor = z
+        .pow(FIELD_ELEMENTS_PER_BLOB)
+        .sub(&TFr::one())
+        .div(&TFr::from_u64(FIELD_ELEMENTS_PER_BLOB as u64))?;
+
+    for (i, weight) in weights.iter_mut().enumerate() {
+        *weight = inverses[i].mul(&roots_of_unity[i]).mul(&factor);
+    }
+
+    Ok(weights)
+}
+
+/// Verify a KZG proof and export the pairing inputs.
+pub fn verify_kzg_proof_witness_rust<
+    TFr: Fr,
+    TG1: G1 + G1GetFp<TG1Fp> + G1Mul<TFr> + PairingVerify<TG1, TG2>,
+    TG2: G2 + G2Mul<TFr>,
+    TFFTSettings: FFTSettings<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    commitment: &TG1,
+    z: &TFr,
+    y: &TFr,
+    proof: &TG1,
+    s: &TKZGSettings,
+) -> Result<KzgProofWitness<TFr, TG1, TG2>, String> {
+    if !commitment.is_inf() && !commitment.is_valid() {
+        return Err("Invalid commitment".to_string());
+    }
+    if !proof.is_inf() && !proof.is_valid() {
+        return Err("Invalid proof".to_string());
+    }
+
+    let s_g2 = s
+        .get_g2_secret()
+        .get(1)
+        .ok_or_else(|| String::from("Trusted setup must contain at least two G2 points"))?;
+    guard_insecure_dev_setup(s.get_g2_secret());
+
+    let commitment_minus_y = commitment_minus_value(commitment, y);
+    let g2_generator = TG2::generator();
+    let s_minus_z = g2_s_minus_z(s_g2, z);
+    let valid = TG1::verify(&commitment_minus_y, &g2_generator, proof, &s_minus_z);
+
+    Ok(KzgProofWitness {
+        z: z.clone(),
+        y: y.clone(),
+        commitment_minus_y,
+        g2_generator,
+        proof: proof.clone(),
+        s_minus_z,
+        valid,
+    })
+}
+
+/// Verify a blob KZG proof and export the challenge, the barycentric weights and the pairing
+/// inputs.
+pub fn verify_blob_kzg_proof_witness_rust<
+    TFr: Fr + Copy,
+    TG1: G1 + G1GetFp<TG1Fp> + G1Mul<TFr> + PairingVerify<TG1, TG2>,
+    TG2: G2 + G2Mul<TFr>,
+    TFFTSettings: FFTSettings<TFr>,
+    TPoly: Poly<TFr>,
+    TKZGSettings: KZGSettings<TFr, TG1, TG2, TFFTSettings, TPoly, TG1Fp, TG1Affine>,
+    TG1Fp: G1Fp,
+    TG1Affine: G1Affine<TG1, TG1Fp>,
+>(
+    blob: &[TFr],
+    commitment: &TG1,
+    proof: &TG1,
+    s: &TKZGSettings,
+) -> Result<BlobKzgProofWitness<TFr, TG1, TG2>, String> {
+    let polynomial: TPoly = blob_to_polynomial(blob)?;
+    let challenge_hash = compute_challenge_hash(blob, commitment);
+    let z: TFr = hash_to_bls_field(&challenge_hash);
+
+    let barycentric_weights = compute_barycentric_weights(&z, s)?;
+    let y = polynomial
+        .get_coeffs()
+        .iter()
+        .zip(barycentric_weights.iter())
+        .fold(TFr::zero(), |acc, (value, weight)| {
+            acc.add(&value.mul(weight))
+        });
+
+    let proof_witness = verify_kzg_proof_witness_rust(commitment, &z, &y, proof, s)?;
+
+    Ok(BlobKzgProofWitness {
+        challenge_hash,
+        barycentric_weights,
+        proof_witness,
+    })
+}
@@ -3,7 +3,8 @@ mod consts_tests {
     use kzg_bench::tests::consts::*;
     use rust_kzg_mcl::data_types::fr::Fr;
     use rust_kzg_mcl::fk20_fft::{
-        expand_root_of_unity, init_globals, FFTSettings, SCALE_2_ROOT_OF_UNITY,
+        expand_root_of_unity, scale_2_roots_of_unity, FFTSettings,
+        SCALE_2_ROOT_OF_UNITY_PR5_STRINGS,
     };
     use rust_kzg_mcl::mcl_methods::init;
     use rust_kzg_mcl::CurveType;
@@ -22,27 +23,23 @@ mod consts_tests {
     fn roots_of_unity_are_plausible_() {
         assert!(init(CurveType::BLS12_381));
 
-        unsafe {
-            init_globals();
-            let mut scale2_root_of_unity_arr: [[u64; 4]; 32] = [[0; 4]; 32];
-            for i in 0..SCALE_2_ROOT_OF_UNITY.len() {
-                scale2_root_of_unity_arr[i] = SCALE_2_ROOT_OF_UNITY[i].to_u64_arr();
-            }
-            roots_of_unity_are_plausible::<Fr>(&scale2_root_of_unity_arr);
+        let roots = scale_2_roots_of_unity(&SCALE_2_ROOT_OF_UNITY_PR5_STRINGS);
+        let mut scale2_root_of_unity_arr: [[u64; 4]; 32] = [[0; 4]; 32];
+        for i in 0..roots.len() {
+            scale2_root_of_unity_arr[i] = roots[i].to_u64_arr();
         }
+        roots_of_unity_are_plausible::<Fr>(&scale2_root_of_unity_arr);
     }
 
     #[test]
     fn expand_roots_is_plausible_() {
         assert!(init(CurveType::BLS12_381));
-        unsafe {
-            init_globals();
-            let mut scale2_root_of_unity_arr: [[u64; 4]; 32] = [[0; 4]; 32];
-            for i in 0..SCALE_2_ROOT_OF_UNITY.len() {
-                scale2_root_of_unity_arr[i] = SCALE_2_ROOT_OF_UNITY[i].to_u64_arr();
-            }
-            expand_roots_is_plausible::<Fr>(&scale2_root_of_unity_arr, &expand_root_of_unityarr);
+        let roots = scale_2_roots_of_unity(&SCALE_2_ROOT_OF_UNITY_PR5_STRINGS);
+        let mut scale2_root_of_unity_arr: [[u64; 4]; 32] = [[0; 4]; 32];
+        for i in 0..roots.len() {
+            scale2_root_of_unity_arr[i] = roots[i].to_u64_arr();
         }
+        expand_roots_is_plausible::<Fr>(&scale2_root_of_unity_arr, &expand_root_of_unityarr);
     }
 
     #[test]
@@ -39,7 +39,6 @@ unsafe fn ks_to_cks(t: &mut mKZGSettings, out: *mut CKZGSettings) {
 }
 
 unsafe fn cks_to_ks(t: *const CKZGSettings) -> mKZGSettings {
-    crate::fk20_fft::init_globals();
     let fs = (*t).fs;
     let mw = (*fs).max_width as usize;
     let mut ks = mKZGSettings {
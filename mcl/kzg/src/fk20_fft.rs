@@ -174,9 +174,6 @@ pub const G1_NEGATIVE_GENERATOR: G1 = G1 {
     },
 };
 
-pub static mut SCALE_2_ROOT_OF_UNITY: Vec<Fr> = vec![];
-pub static mut GLOBALS_INITIALIZED: bool = false;
-pub static mut DEFAULT_GLOBALS_INITIALIZED: bool = false;
 pub const PRIMITIVE_ROOT: i32 = 5;
 
 pub fn make_data(n: usize) -> Vec<G1> {
@@ -192,33 +189,12 @@ pub fn make_data(n: usize) -> Vec<G1> {
     data
 }
 
-/// # Safety
-///
-/// use of mutable static is unsafe and requires unsafe function or block
-pub unsafe fn init_globals() {
-    if GLOBALS_INITIALIZED && DEFAULT_GLOBALS_INITIALIZED {
-        return;
-    }
-    SCALE_2_ROOT_OF_UNITY = SCALE_2_ROOT_OF_UNITY_PR5_STRINGS
+/// Roots of unity of orders `2^0` to `2^31`, parsed from `root_strings`
+pub fn scale_2_roots_of_unity(root_strings: &[&str; 32]) -> Vec<Fr> {
+    root_strings
         .iter()
         .map(|x| Fr::from_str(x, 10).unwrap())
-        .collect();
-
-    GLOBALS_INITIALIZED = true;
-    DEFAULT_GLOBALS_INITIALIZED = true;
-}
-
-/// # Safety
-///
-/// use of mutable static is unsafe and requires unsafe function or block
-pub unsafe fn init_globals_custom(root_strings: [&str; 32]) {
-    SCALE_2_ROOT_OF_UNITY = root_strings
-        .iter()
-        .map(|x| Fr::from_str(x, 10).unwrap())
-        .collect();
-
-    GLOBALS_INITIALIZED = true;
-    DEFAULT_GLOBALS_INITIALIZED = false;
+        .collect()
 }
 
 pub fn expand_root_of_unity(root: &Fr) -> Vec<Fr> {
@@ -248,16 +224,9 @@ impl Default for FFTSettings {
 }
 
 impl FFTSettings {
-    //fix this mess
-    /// # Safety
-    ///
-    /// use of mutable static is unsafe and requires unsafe function or block
     pub fn new(max_scale: u8) -> FFTSettings {
-        let root_of_unity: Fr;
-        unsafe {
-            init_globals();
-            root_of_unity = SCALE_2_ROOT_OF_UNITY[max_scale as usize]
-        }
+        let root_of_unity =
+            Fr::from_str(SCALE_2_ROOT_OF_UNITY_PR5_STRINGS[max_scale as usize], 10).unwrap();
         let expanded_roots_of_unity = expand_root_of_unity(&root_of_unity);
         let mut reverse_roots_of_unity = expanded_roots_of_unity.clone();
         reverse_roots_of_unity.reverse();
@@ -275,23 +244,16 @@ impl FFTSettings {
         }
     }
 
-    /// # Safety
-    ///
-    /// use of mutable static is unsafe and requires unsafe function or block
     pub fn new_custom_primitive_roots(
         max_scale: u8,
         root_strings: [&str; 32],
     ) -> Result<FFTSettings, String> {
-        let root_of_unity: Fr;
-        unsafe {
-            init_globals_custom(root_strings);
-            if max_scale as usize >= SCALE_2_ROOT_OF_UNITY.len() {
-                return Err(String::from(
-                    "Scale is expected to be within root of unity matrix row size",
-                ));
-            }
-            root_of_unity = SCALE_2_ROOT_OF_UNITY[max_scale as usize]
+        if max_scale as usize >= root_strings.len() {
+            return Err(String::from(
+                "Scale is expected to be within root of unity matrix row size",
+            ));
         }
+        let root_of_unity = Fr::from_str(root_strings[max_scale as usize], 10).unwrap();
 
         let expanded_roots_of_unity = expand_root_of_unity(&root_of_unity);
         let mut reverse_roots_of_unity = expanded_roots_of_unity.clone();
@@ -1,5 +1,6 @@
 use crate::kzg_proofs::FFTSettings;
 use crate::kzg_types::ZFr as BlstFr;
+use kzg::common_utils::try_vec;
 use kzg::{FFTFr, Fr as FFr};
 
 impl FFTFr<BlstFr> for FFTSettings {
@@ -12,7 +13,7 @@ impl FFTFr<BlstFr> for FFTSettings {
         }
 
         let stride = self.max_width / data.len();
-        let mut ret = vec![BlstFr::default(); data.len()];
+        let mut ret = try_vec(BlstFr::default(), data.len())?;
 
         let roots = if inverse {
             &self.reverse_roots_of_unity
@@ -20,6 +21,10 @@ impl FFTFr<BlstFr> for FFTSettings {
             &self.expanded_roots_of_unity
         };
 
+        #[cfg(feature = "parallel")]
+        kzg::parallelism::install(|| fft_fr_fast(&mut ret, data, 1, roots, stride));
+
+        #[cfg(not(feature = "parallel"))]
         fft_fr_fast(&mut ret, data, 1, roots, stride);
 
         if inverse {
@@ -55,7 +60,7 @@ pub fn fft_fr_fast(
 
         #[cfg(feature = "parallel")]
         {
-            if half > 256 {
+            if half > 256 && kzg::parallelism::is_parallel() {
                 let (lo, hi) = ret.split_at_mut(half);
                 rayon::join(
                     || fft_fr_fast(lo, data, stride * 2, roots, roots_stride * 2),
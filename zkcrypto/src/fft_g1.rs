@@ -2,6 +2,7 @@ use crate::consts::G1_GENERATOR;
 use crate::kzg_proofs::FFTSettings;
 use crate::kzg_types::{ZFp, ZFr, ZG1Affine, ZG1};
 use crate::multiscalar_mul::msm_variable_base;
+use kzg::common_utils::try_vec;
 use kzg::msm::precompute::PrecomputationTable;
 use kzg::{Fr as KzgFr, G1Mul};
 use kzg::{FFTG1, G1};
@@ -40,7 +41,7 @@ impl FFTG1<ZG1> for FFTSettings {
         }
 
         let stride: usize = self.max_width / data.len();
-        let mut ret = vec![ZG1::default(); data.len()];
+        let mut ret = try_vec(ZG1::default(), data.len())?;
 
         let roots = if inverse {
             &self.reverse_roots_of_unity
@@ -48,6 +49,10 @@ impl FFTG1<ZG1> for FFTSettings {
             &self.expanded_roots_of_unity
         };
 
+        #[cfg(feature = "parallel")]
+        kzg::parallelism::install(|| fft_g1_fast(&mut ret, data, 1, roots, stride, 1));
+
+        #[cfg(not(feature = "parallel"))]
         fft_g1_fast(&mut ret, data, 1, roots, stride, 1);
 
         if inverse {
@@ -91,11 +96,30 @@ pub fn fft_g1_fast(
     if half > 0 {
         #[cfg(feature = "parallel")]
         {
-            let (lo, hi) = ret.split_at_mut(half);
-            rayon::join(
-                || fft_g1_fast(hi, &data[stride..], stride * 2, roots, roots_stride * 2, 1),
-                || fft_g1_fast(lo, data, stride * 2, roots, roots_stride * 2, 1),
-            );
+            if kzg::parallelism::is_parallel() {
+                let (lo, hi) = ret.split_at_mut(half);
+                rayon::join(
+                    || fft_g1_fast(hi, &data[stride..], stride * 2, roots, roots_stride * 2, 1),
+                    || fft_g1_fast(lo, data, stride * 2, roots, roots_stride * 2, 1),
+                );
+            } else {
+                fft_g1_fast(
+                    &mut ret[..half],
+                    data,
+                    stride * 2,
+                    roots,
+                    roots_stride * 2,
+                    1,
+                );
+                fft_g1_fast(
+                    &mut ret[half..],
+                    &data[stride..],
+                    stride * 2,
+                    roots,
+                    roots_stride * 2,
+                    1,
+                );
+            }
         }
 
         #[cfg(not(feature = "parallel"))]
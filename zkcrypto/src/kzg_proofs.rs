@@ -1,15 +1,16 @@
 #![allow(non_camel_case_types)]
-use crate::consts::{G1_GENERATOR, G2_GENERATOR};
 use crate::kzg_types::{ZFp, ZFr, ZG1Affine};
 use crate::kzg_types::{ZFr as BlstFr, ZG1, ZG2};
 use crate::poly::PolyData;
 use bls12_381::{
     multi_miller_loop, Fp12 as ZFp12, G1Affine, G2Affine, G2Prepared, MillerLoopResult,
 };
-use kzg::eip_4844::hash_to_bls_field;
-use kzg::msm::precompute::PrecomputationTable;
-use kzg::{Fr as FrTrait, G1Mul, G2Mul};
+use kzg::dev_setup::INSECURE_DEV_SETUP_SEED;
+use kzg::msm::precompute::ReleasableTable;
+use kzg::secret::{generate_trusted_setup_with_secret, SecretScalar};
+use kzg::Fr as FrTrait;
 use std::ops::{Add, Neg};
+use std::sync::Arc;
 
 #[derive(Debug, Clone)]
 pub struct FFTSettings {
@@ -43,24 +44,17 @@ pub struct KZGSettings {
     pub fs: FFTSettings,
     pub secret_g1: Vec<ZG1>,
     pub secret_g2: Vec<ZG2>,
-    pub precomputation: Option<PrecomputationTable<ZFr, ZG1, ZFp, ZG1Affine>>,
+    pub precomputation: Arc<ReleasableTable<ZFr, ZG1, ZFp, ZG1Affine>>,
 }
 
 pub fn generate_trusted_setup(len: usize, secret: [u8; 32usize]) -> (Vec<ZG1>, Vec<ZG2>) {
-    let s = hash_to_bls_field::<ZFr>(&secret);
-    let mut s_pow = ZFr::one();
-
-    let mut s1 = Vec::with_capacity(len);
-    let mut s2 = Vec::with_capacity(len);
-
-    for _ in 0..len {
-        s1.push(G1_GENERATOR.mul(&s_pow));
-        s2.push(G2_GENERATOR.mul(&s_pow));
-
-        s_pow = s_pow.mul(&s);
-    }
+    generate_trusted_setup_with_secret::<ZFr, _, _>(len, &SecretScalar::from_seed(secret))
+}
 
-    (s1, s2)
+/// Setup of the public development secret, for tests and local networks only. Verifying against
+/// it raises a warning, see `kzg::dev_setup`
+pub fn generate_insecure_dev_setup(len: usize) -> (Vec<ZG1>, Vec<ZG2>) {
+    generate_trusted_setup(len, INSECURE_DEV_SETUP_SEED)
 }
 
 pub fn eval_poly(p: &PolyData, x: &ZFr) -> ZFr {
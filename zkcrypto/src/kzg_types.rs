@@ -15,14 +15,18 @@ use bls12_381::{Fp, G1Affine, G1Projective, G2Affine, G2Projective, Scalar, MODU
 use blst::{blst_fr, blst_p1};
 use ff::Field;
 use kzg::common_utils::reverse_bit_order;
-use kzg::eip_4844::{BYTES_PER_FIELD_ELEMENT, BYTES_PER_G1, BYTES_PER_G2};
-use kzg::msm::precompute::{precompute, PrecomputationTable};
+use kzg::dev_setup::guard_insecure_dev_setup;
+use kzg::eip_4844::{
+    BYTES_PER_FIELD_ELEMENT, BYTES_PER_G1, BYTES_PER_G1_UNCOMPRESSED, BYTES_PER_G2,
+};
+use kzg::msm::precompute::{precompute, PrecomputationTable, ReleasableTable};
 use kzg::G1Affine as G1AffineTrait;
 use kzg::{
     FFTFr, FFTSettings, Fr as KzgFr, G1Fp, G1GetFp, G1LinComb, G1Mul, G1ProjAddAffine, G2Mul,
     KZGSettings, PairingVerify, Poly, Scalar256, G1, G2,
 };
 use std::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
+use std::sync::Arc;
 
 use ff::derive::sbb;
 use subtle::{Choice, ConstantTimeEq, CtOption};
@@ -489,6 +493,29 @@ impl G1 for ZG1 {
         self.proj = self.proj.double();
     }
 
+    fn to_bytes_uncompressed(&self) -> Result<[u8; BYTES_PER_G1_UNCOMPRESSED], String> {
+        Ok(G1Affine::from(self.proj).to_uncompressed())
+    }
+
+    fn from_bytes_uncompressed(bytes: &[u8]) -> Result<Self, String> {
+        bytes
+            .try_into()
+            .map_err(|_| {
+                format!(
+                    "Invalid byte length. Expected {}, got {}",
+                    BYTES_PER_G1_UNCOMPRESSED,
+                    bytes.len()
+                )
+            })
+            .and_then(|bytes: &[u8; BYTES_PER_G1_UNCOMPRESSED]| {
+                let affine: CtOption<G1Affine> = G1Affine::from_uncompressed(bytes);
+                match affine.into() {
+                    Some(x) => Ok(ZG1::affine_to_projective(x)),
+                    None => Err("Failed to deserialize G1: Affine not available".to_string()),
+                }
+            })
+    }
+
     const ZERO: Self = Self {
         proj: G1Projective {
             x: Fp([
@@ -827,7 +854,7 @@ impl KZGSettings<ZFr, ZG1, ZG2, ZFFTSettings, PolyData, ZFp, ZG1Affine> for ZKZG
             secret_g1: secret_g1.to_vec(),
             secret_g2: secret_g2.to_vec(),
             fs: fft_settings.clone(),
-            precomputation: precompute(secret_g1).ok().flatten(),
+            precomputation: Arc::new(ReleasableTable::new(precompute(secret_g1).ok().flatten())),
         })
     }
 
@@ -864,6 +891,8 @@ impl KZGSettings<ZFr, ZG1, ZG2, ZFFTSettings, PolyData, ZFp, ZG1Affine> for ZKZG
     }
 
     fn check_proof_single(&self, com: &ZG1, proof: &ZG1, x: &ZFr, y: &ZFr) -> Result<bool, String> {
+        guard_insecure_dev_setup(&self.secret_g2);
+
         let x_g2 = G2_GENERATOR.mul(x);
         let s_minus_x: ZG2 = self.secret_g2[1].sub(&x_g2);
         let y_g1 = G1_GENERATOR.mul(y);
@@ -924,6 +953,7 @@ impl KZGSettings<ZFr, ZG1, ZG2, ZFFTSettings, PolyData, ZFp, ZG1Affine> for ZKZG
         if !n.is_power_of_two() {
             return Err(String::from("n is not a power of two"));
         }
+        guard_insecure_dev_setup(&self.secret_g2);
 
         // Interpolate at a coset.
         let mut interp = PolyData {
@@ -946,7 +976,7 @@ impl KZGSettings<ZFr, ZG1, ZG2, ZFFTSettings, PolyData, ZFp, ZG1Affine> for ZKZG
         let xn_minus_yn = self.secret_g2[n].sub(&xn2);
 
         // [interpolation_polynomial(s)]_1
-        let is1 = self.commit_to_poly(&interp).unwrap();
+        let is1 = self.commit_to_poly(&interp)?;
 
         // [commitment - interpolation_polynomial(s)]_1 = [commit]_1 - [interpolation_polynomial(s)]_1
         let commit_minus_interp = com.sub(&is1);
@@ -975,7 +1005,13 @@ impl KZGSettings<ZFr, ZG1, ZG2, ZFFTSettings, PolyData, ZFp, ZG1Affine> for ZKZG
         &self.secret_g2
     }
 
-    fn get_precomputation(&self) -> Option<&PrecomputationTable<ZFr, ZG1, ZFp, ZG1Affine>> {
-        self.precomputation.as_ref()
+    fn get_precomputation(&self) -> Option<Arc<PrecomputationTable<ZFr, ZG1, ZFp, ZG1Affine>>> {
+        self.precomputation.get()
+    }
+
+    fn get_releasable_precomputation(
+        &self,
+    ) -> Option<Arc<ReleasableTable<ZFr, ZG1, ZFp, ZG1Affine>>> {
+        Some(self.precomputation.clone())
     }
 }
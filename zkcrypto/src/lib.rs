@@ -19,6 +19,7 @@ pub mod kzg_proofs;
 pub mod kzg_types;
 mod multiscalar_mul;
 pub mod poly;
+pub mod prelude;
 pub mod recover;
 pub mod utils;
 pub mod zero_poly;
@@ -0,0 +1,33 @@
+//! Concrete types of the zkcrypto backend together with the traits they implement, for
+//! `use rust_kzg_zkcrypto::prelude::*;`
+
+use kzg::backend::{Backend, KzgBackend};
+
+pub use kzg::{
+    FFTFr, FFTSettings, Fr, G1Affine, G1Fp, G1GetFp, G1LinComb, G1Mul, G1ProjAddAffine, G2Mul,
+    KZGSettings, PairingVerify, Poly, FFTG1, G1, G2,
+};
+
+pub use crate::kzg_proofs::{FFTSettings as ZFFTSettings, KZGSettings as ZKZGSettings};
+pub use crate::kzg_types::{ZFp, ZFr, ZG1Affine, ZG1ProjAddAffine, ZG1, ZG2};
+pub use crate::poly::PolyData;
+
+/// `Backend` bundle of the zkcrypto types
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ZBackend;
+
+impl Backend for ZBackend {
+    const NAME: &'static str = "zkcrypto";
+
+    type Fr = ZFr;
+    type G1 = ZG1;
+    type G2 = ZG2;
+    type G1Fp = ZFp;
+    type G1Affine = ZG1Affine;
+    type G1ProjAddAffine = ZG1ProjAddAffine;
+    type Poly = PolyData;
+    type FFTSettings = ZFFTSettings;
+    type KZGSettings = ZKZGSettings;
+}
+
+impl KzgBackend for ZBackend {}
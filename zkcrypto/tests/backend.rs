@@ -0,0 +1,16 @@
+#[cfg(test)]
+mod tests {
+    use kzg_bench::tests::backend::{kzg_backend_capabilities, kzg_backend_constructors};
+    use rust_kzg_zkcrypto::kzg_proofs::generate_trusted_setup;
+    use rust_kzg_zkcrypto::prelude::ZBackend;
+
+    #[test]
+    fn kzg_backend_constructors_() {
+        kzg_backend_constructors::<ZBackend>(&generate_trusted_setup);
+    }
+
+    #[test]
+    fn kzg_backend_capabilities_() {
+        kzg_backend_capabilities::<ZBackend>();
+    }
+}
@@ -92,6 +92,11 @@ mod tests {
         g1_identity_is_identity::<ZG1>();
     }
 
+    #[test]
+    pub fn g1_batch_serialization_roundtrip_() {
+        g1_batch_serialization_roundtrip::<ZFr, ZG1>();
+    }
+
     #[test]
     pub fn g1_make_linear_combination_() {
         g1_make_linear_combination::<ZFr, ZG1, ZFp, ZG1Affine>(&g1_linear_combination);
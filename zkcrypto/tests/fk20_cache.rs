@@ -0,0 +1,24 @@
+#[cfg(test)]
+mod tests {
+    use kzg_bench::tests::fk20_cache::fk20_cache_roundtrip_test;
+    use rust_kzg_zkcrypto::eip_4844::load_trusted_setup_filename_rust;
+    use rust_kzg_zkcrypto::fk20_proofs::KzgFK20MultiSettings;
+    use rust_kzg_zkcrypto::kzg_proofs::{FFTSettings, KZGSettings};
+    use rust_kzg_zkcrypto::kzg_types::{ZFp, ZFr, ZG1Affine, ZG1, ZG2};
+    use rust_kzg_zkcrypto::poly::PolyData;
+
+    #[test]
+    fn fk20_cache_roundtrip_test_() {
+        fk20_cache_roundtrip_test::<
+            ZFr,
+            ZG1,
+            ZG2,
+            PolyData,
+            FFTSettings,
+            KZGSettings,
+            KzgFK20MultiSettings,
+            ZFp,
+            ZG1Affine,
+        >(&load_trusted_setup_filename_rust);
+    }
+}